@@ -0,0 +1,290 @@
+//! Obstacle and wall avoidance
+//!
+//! Steering behaviors that probe the physics world with rays ahead of the
+//! agent. Call `update` with a [`QueryWorld`] snapshot each frame, then blend
+//! the behavior with `Seek`/`Arrive` through [`BlendedSteering`](super::BlendedSteering).
+
+use glam::{Quat, Vec3};
+
+use super::steering::{SteeringBehavior, SteeringOutput};
+use crate::physics::{ColliderHandle, QueryWorld, RaycastHit};
+
+/// Cast a horizontal probe, skipping the agent's own collider and ignoring flat ground
+fn probe(
+    query: &QueryWorld,
+    origin: Vec3,
+    direction: Vec3,
+    length: f32,
+    ignore: Option<ColliderHandle>,
+) -> Option<RaycastHit> {
+    let hit = query.raycast_filtered(origin, direction, length, |handle| Some(handle) != ignore)?;
+    // Flatten the normal so agents steer sideways instead of into the floor
+    let normal = Vec3::new(hit.normal.x, 0.0, hit.normal.z).normalize_or_zero();
+    (normal != Vec3::ZERO).then_some(RaycastHit { normal, ..hit })
+}
+
+/// Horizontal heading of a velocity, or `None` when the agent is not moving
+fn heading(velocity: Vec3) -> Option<Vec3> {
+    let flat = Vec3::new(velocity.x, 0.0, velocity.z);
+    (flat.length_squared() > 1e-6).then(|| flat.normalize())
+}
+
+/// Obstacle avoidance - steer around geometry in the direction of travel
+///
+/// Casts a center ray along the velocity plus two angled whiskers. When one
+/// hits, the agent seeks a point pushed out from the surface along its normal.
+#[derive(Debug, Clone)]
+pub struct ObstacleAvoidance {
+    /// Length of the center ray at rest
+    pub lookahead: f32,
+    /// Additional lookahead per unit of speed
+    pub speed_lookahead: f32,
+    /// Distance from the surface to steer towards
+    pub avoid_distance: f32,
+    /// Angle of the side whiskers from the center ray (radians)
+    pub whisker_angle: f32,
+    /// Whisker length relative to the center ray
+    pub whisker_scale: f32,
+    /// Height of the probes above the agent position
+    pub probe_height: f32,
+    /// Maximum acceleration
+    pub max_acceleration: f32,
+    /// Collider ignored by the probes, usually the agent's own
+    pub ignore: Option<ColliderHandle>,
+    /// Point to seek from the last update
+    target: Option<Vec3>,
+}
+
+impl ObstacleAvoidance {
+    /// Create a new obstacle avoidance behavior
+    #[must_use]
+    pub fn new(lookahead: f32, avoid_distance: f32, max_acceleration: f32) -> Self {
+        Self {
+            lookahead,
+            speed_lookahead: 0.5,
+            avoid_distance,
+            whisker_angle: 30f32.to_radians(),
+            whisker_scale: 0.6,
+            probe_height: 0.5,
+            max_acceleration,
+            ignore: None,
+            target: None,
+        }
+    }
+
+    /// Ignore the agent's own collider
+    #[must_use]
+    pub fn with_ignore(mut self, collider: ColliderHandle) -> Self {
+        self.ignore = Some(collider);
+        self
+    }
+
+    /// Set the side whisker angle and relative length
+    #[must_use]
+    pub fn with_whiskers(mut self, angle: f32, scale: f32) -> Self {
+        self.whisker_angle = angle;
+        self.whisker_scale = scale;
+        self
+    }
+
+    /// Set the probe height above the agent position
+    #[must_use]
+    pub fn with_probe_height(mut self, height: f32) -> Self {
+        self.probe_height = height;
+        self
+    }
+
+    /// Probe the world ahead of the agent
+    pub fn update(&mut self, query: &QueryWorld, position: Vec3, velocity: Vec3) {
+        self.target = None;
+        let Some(forward) = heading(velocity) else {
+            return;
+        };
+        let origin = position + Vec3::Y * self.probe_height;
+        let length = self.lookahead + velocity.length() * self.speed_lookahead;
+        let rays = [
+            (forward, length),
+            (
+                Quat::from_rotation_y(self.whisker_angle) * forward,
+                length * self.whisker_scale,
+            ),
+            (
+                Quat::from_rotation_y(-self.whisker_angle) * forward,
+                length * self.whisker_scale,
+            ),
+        ];
+
+        let nearest = rays
+            .iter()
+            .filter_map(|&(direction, length)| probe(query, origin, direction, length, self.ignore))
+            .min_by(|a, b| a.distance.total_cmp(&b.distance));
+        if let Some(hit) = nearest {
+            let mut away = hit.normal;
+            // Head-on hits have no sideways component; pick a side
+            if away.dot(forward) < -0.99 {
+                away = (away + forward.cross(Vec3::Y)).normalize_or_zero();
+            }
+            let target = hit.point + away * self.avoid_distance;
+            self.target = Some(Vec3::new(target.x, position.y, target.z));
+        }
+    }
+
+    /// Whether the last update found an obstacle
+    #[must_use]
+    pub fn is_avoiding(&self) -> bool {
+        self.target.is_some()
+    }
+
+    /// Point being steered towards, if avoiding
+    #[must_use]
+    pub fn target(&self) -> Option<Vec3> {
+        self.target
+    }
+}
+
+impl SteeringBehavior for ObstacleAvoidance {
+    fn calculate(&self, position: Vec3, _velocity: Vec3) -> SteeringOutput {
+        let Some(target) = self.target else {
+            return SteeringOutput::ZERO;
+        };
+        SteeringOutput {
+            linear: (target - position).normalize_or_zero() * self.max_acceleration,
+            angular: 0.0,
+        }
+    }
+}
+
+/// Wall avoidance - push away from walls touched by feelers
+///
+/// Casts a fan of feelers; each one that penetrates a wall pushes the agent
+/// along the wall normal in proportion to how far it overshoots.
+#[derive(Debug, Clone)]
+pub struct WallAvoidance {
+    /// Length of the feelers
+    pub feeler_length: f32,
+    /// Number of feelers spread across `spread`
+    pub feelers: usize,
+    /// Total angle covered by the feelers (radians)
+    pub spread: f32,
+    /// Height of the feelers above the agent position
+    pub probe_height: f32,
+    /// Maximum acceleration
+    pub max_acceleration: f32,
+    /// Collider ignored by the feelers, usually the agent's own
+    pub ignore: Option<ColliderHandle>,
+    /// Push accumulated in the last update
+    force: Vec3,
+}
+
+impl WallAvoidance {
+    /// Create a new wall avoidance behavior with three feelers
+    #[must_use]
+    pub fn new(feeler_length: f32, max_acceleration: f32) -> Self {
+        Self {
+            feeler_length,
+            feelers: 3,
+            spread: 90f32.to_radians(),
+            probe_height: 0.5,
+            max_acceleration,
+            ignore: None,
+            force: Vec3::ZERO,
+        }
+    }
+
+    /// Ignore the agent's own collider
+    #[must_use]
+    pub fn with_ignore(mut self, collider: ColliderHandle) -> Self {
+        self.ignore = Some(collider);
+        self
+    }
+
+    /// Set the number of feelers and their total spread
+    #[must_use]
+    pub fn with_feelers(mut self, count: usize, spread: f32) -> Self {
+        self.feelers = count.max(1);
+        self.spread = spread;
+        self
+    }
+
+    /// Set the feeler height above the agent position
+    #[must_use]
+    pub fn with_probe_height(mut self, height: f32) -> Self {
+        self.probe_height = height;
+        self
+    }
+
+    /// Probe the world around the agent's heading
+    pub fn update(&mut self, query: &QueryWorld, position: Vec3, velocity: Vec3) {
+        self.force = Vec3::ZERO;
+        let Some(forward) = heading(velocity) else {
+            return;
+        };
+        let origin = position + Vec3::Y * self.probe_height;
+        let (start, step) = if self.feelers > 1 {
+            (-self.spread * 0.5, self.spread / (self.feelers - 1) as f32)
+        } else {
+            (0.0, 0.0)
+        };
+        for i in 0..self.feelers {
+            let angle = start + step * i as f32;
+            let direction = Quat::from_rotation_y(angle) * forward;
+            // Side feelers reach less far than the center one
+            let length = self.feeler_length * (0.5 + 0.5 * angle.cos());
+            if let Some(hit) = probe(query, origin, direction, length, self.ignore) {
+                self.force += hit.normal * (length - hit.distance) / length;
+            }
+        }
+    }
+
+    /// Whether the last update touched a wall
+    #[must_use]
+    pub fn is_avoiding(&self) -> bool {
+        self.force != Vec3::ZERO
+    }
+}
+
+impl SteeringBehavior for WallAvoidance {
+    fn calculate(&self, _position: Vec3, _velocity: Vec3) -> SteeringOutput {
+        SteeringOutput {
+            linear: self.force.clamp_length_max(1.0) * self.max_acceleration,
+            angular: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{BlendedSteering, Seek};
+    use crate::physics::Physics;
+
+    #[test]
+    fn test_avoidance_steers_around_wall() {
+        let mut physics = Physics::new();
+        // Wall ahead of the agent, slightly offset so it can pass on +Z
+        let wall = physics.create_static_body(Vec3::new(4.0, 1.0, -0.5), Quat::IDENTITY);
+        physics.add_box_collider(wall, Vec3::new(0.5, 1.0, 2.0), 1.0);
+        let query = physics.query_world();
+
+        let (position, velocity) = (Vec3::ZERO, Vec3::X * 2.0);
+        let mut obstacles = ObstacleAvoidance::new(3.0, 1.5, 10.0);
+        obstacles.update(&query, position, velocity);
+        assert!(obstacles.is_avoiding());
+
+        let mut walls = WallAvoidance::new(4.0, 10.0);
+        walls.update(&query, position, velocity);
+        assert!(walls.is_avoiding());
+        assert!(walls.calculate(position, velocity).linear.x < 0.0);
+
+        let seek = Seek::new(Vec3::new(10.0, 0.0, 0.0), 5.0);
+        let blended = BlendedSteering::new(10.0)
+            .with(&seek, 1.0)
+            .with(&obstacles, 2.0)
+            .calculate(position, velocity);
+        assert!(blended.linear.z.abs() > 0.1);
+
+        // Nothing ahead when moving away
+        obstacles.update(&query, position, -velocity);
+        assert!(!obstacles.is_avoiding());
+    }
+}
@@ -0,0 +1,246 @@
+//! Blackboard for AI agents
+//!
+//! A typed key-value store shared between steering, decision making and
+//! perception. Keys are hashed strings, so they can be written inline as
+//! `"target_visible"` or declared once as [`BlackboardKey`] constants. Every
+//! write that changes a value bumps a revision and is queued as a change, so
+//! decision layers can react when a fact flips.
+
+use std::any::Any;
+use std::fmt;
+
+use rustc_hash::FxHashMap;
+
+/// Handle to a blackboard entry (FNV-1a hash of its name)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BlackboardKey(pub u64);
+
+impl BlackboardKey {
+    /// Hash a name into a key; usable in constants
+    #[must_use]
+    pub const fn new(name: &str) -> Self {
+        let bytes = name.as_bytes();
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut i = 0;
+        while i < bytes.len() {
+            hash ^= bytes[i] as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+            i += 1;
+        }
+        Self(hash)
+    }
+}
+
+impl From<&str> for BlackboardKey {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+/// A stored value and the revision it was last changed at
+struct Entry {
+    /// Type-erased value
+    value: Box<dyn Any + Send + Sync>,
+    /// Blackboard revision of the last change
+    revision: u64,
+}
+
+/// Typed key-value store with change tracking
+#[derive(Default)]
+pub struct Blackboard {
+    /// Entries by key
+    entries: FxHashMap<BlackboardKey, Entry>,
+    /// Incremented on every change
+    revision: u64,
+    /// Keys changed since the last `take_changes`, in order, without duplicates
+    changes: Vec<BlackboardKey>,
+}
+
+impl Blackboard {
+    /// Create an empty blackboard
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a value, returning whether it changed
+    ///
+    /// Writing an equal value is a no-op; writing a value of a different type
+    /// replaces the old one.
+    pub fn set<T>(&mut self, key: impl Into<BlackboardKey>, value: T) -> bool
+    where
+        T: Any + PartialEq + Send + Sync,
+    {
+        let key = key.into();
+        if let Some(entry) = self.entries.get(&key)
+            && entry.value.downcast_ref::<T>() == Some(&value)
+        {
+            return false;
+        }
+        self.revision += 1;
+        self.entries.insert(
+            key,
+            Entry {
+                value: Box::new(value),
+                revision: self.revision,
+            },
+        );
+        self.mark_changed(key);
+        true
+    }
+
+    /// Get a value, or `None` if it is missing or of another type
+    #[must_use]
+    pub fn get<T: Any>(&self, key: impl Into<BlackboardKey>) -> Option<&T> {
+        self.entries.get(&key.into())?.value.downcast_ref()
+    }
+
+    /// Get a copy of a value, or `default` if it is missing or of another type
+    #[must_use]
+    pub fn get_or<T: Any + Clone>(&self, key: impl Into<BlackboardKey>, default: T) -> T {
+        self.get(key).cloned().unwrap_or(default)
+    }
+
+    /// Get a boolean flag, treating a missing entry as `false`
+    #[must_use]
+    pub fn flag(&self, key: impl Into<BlackboardKey>) -> bool {
+        self.get_or(key, false)
+    }
+
+    /// Check if a key has a value of any type
+    #[must_use]
+    pub fn contains(&self, key: impl Into<BlackboardKey>) -> bool {
+        self.entries.contains_key(&key.into())
+    }
+
+    /// Remove a value, returning whether it existed
+    pub fn remove(&mut self, key: impl Into<BlackboardKey>) -> bool {
+        let key = key.into();
+        if self.entries.remove(&key).is_none() {
+            return false;
+        }
+        self.revision += 1;
+        self.mark_changed(key);
+        true
+    }
+
+    /// Remove all values, recording each as changed
+    pub fn clear(&mut self) {
+        let keys: Vec<_> = self.entries.drain().map(|(key, _)| key).collect();
+        if !keys.is_empty() {
+            self.revision += 1;
+        }
+        for key in keys {
+            self.mark_changed(key);
+        }
+    }
+
+    /// Number of stored values
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the blackboard is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Current revision; compare with [`Blackboard::changed_since`] later
+    #[must_use]
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Revision at which a key's value last changed
+    #[must_use]
+    pub fn revision_of(&self, key: impl Into<BlackboardKey>) -> Option<u64> {
+        self.entries.get(&key.into()).map(|entry| entry.revision)
+    }
+
+    /// Check if a key changed after `revision`
+    ///
+    /// Removed keys are not tracked here; use [`Blackboard::take_changes`].
+    #[must_use]
+    pub fn changed_since(&self, key: impl Into<BlackboardKey>, revision: u64) -> bool {
+        self.revision_of(key)
+            .is_some_and(|changed| changed > revision)
+    }
+
+    /// Keys changed since the last call, including removals, in first-change order
+    pub fn take_changes(&mut self) -> Vec<BlackboardKey> {
+        std::mem::take(&mut self.changes)
+    }
+
+    /// Keys changed since the last `take_changes`
+    #[must_use]
+    pub fn changes(&self) -> &[BlackboardKey] {
+        &self.changes
+    }
+
+    fn mark_changed(&mut self, key: BlackboardKey) {
+        if !self.changes.contains(&key) {
+            self.changes.push(key);
+        }
+    }
+}
+
+impl fmt::Debug for Blackboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Blackboard")
+            .field("entries", &self.entries.len())
+            .field("revision", &self.revision)
+            .field("changes", &self.changes)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+
+    const TARGET_VISIBLE: BlackboardKey = BlackboardKey::new("target_visible");
+
+    #[test]
+    fn test_typed_values() {
+        let mut blackboard = Blackboard::new();
+        blackboard.set("health", 75.0f32);
+        blackboard.set("last_seen", Vec3::new(1.0, 0.0, 2.0));
+
+        assert_eq!(blackboard.get::<f32>("health"), Some(&75.0));
+        assert_eq!(blackboard.get::<i32>("health"), None);
+        assert_eq!(
+            blackboard.get_or("last_seen", Vec3::ZERO),
+            Vec3::new(1.0, 0.0, 2.0)
+        );
+        assert!(!blackboard.flag(TARGET_VISIBLE));
+        assert_eq!(BlackboardKey::from("target_visible"), TARGET_VISIBLE);
+    }
+
+    #[test]
+    fn test_change_notifications() {
+        let mut blackboard = Blackboard::new();
+        assert!(blackboard.set(TARGET_VISIBLE, false));
+        blackboard.take_changes();
+        let seen = blackboard.revision();
+
+        // Rewriting the same value is not a change
+        assert!(!blackboard.set(TARGET_VISIBLE, false));
+        assert!(!blackboard.changed_since(TARGET_VISIBLE, seen));
+        assert!(blackboard.take_changes().is_empty());
+
+        assert!(blackboard.set("target_visible", true));
+        blackboard.set("ammo", 3u32);
+        blackboard.set(TARGET_VISIBLE, false);
+        assert!(blackboard.changed_since(TARGET_VISIBLE, seen));
+        assert_eq!(
+            blackboard.take_changes(),
+            vec![TARGET_VISIBLE, BlackboardKey::new("ammo")]
+        );
+
+        assert!(blackboard.remove("ammo"));
+        assert_eq!(blackboard.take_changes(), vec![BlackboardKey::new("ammo")]);
+    }
+}
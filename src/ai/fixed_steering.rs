@@ -0,0 +1,136 @@
+//! Fixed-point steering for lockstep simulation
+//!
+//! Mirrors [`SteeringBehavior`](super::SteeringBehavior) with
+//! [`FixedVec3`] inputs and outputs, so agents steered on every peer end up
+//! bit-identical. Behavior parameters are converted from `f32` on each call;
+//! that conversion is deterministic, so only the agent state needs to stay
+//! fixed.
+
+use crate::math::{Fixed, FixedVec3};
+
+use super::{Arrive, Flee, Seek};
+
+/// Output from a fixed-point steering behavior
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FixedSteeringOutput {
+    /// Linear acceleration
+    pub linear: FixedVec3,
+    /// Angular acceleration (yaw)
+    pub angular: Fixed,
+}
+
+impl FixedSteeringOutput {
+    /// Zero steering
+    pub const ZERO: Self = Self {
+        linear: FixedVec3::ZERO,
+        angular: Fixed::ZERO,
+    };
+
+    /// Combine with another steering output
+    #[must_use]
+    pub fn combine(self, other: Self) -> Self {
+        Self {
+            linear: self.linear + other.linear,
+            angular: self.angular + other.angular,
+        }
+    }
+
+    /// Scale the output
+    #[must_use]
+    pub fn scale(self, factor: Fixed) -> Self {
+        Self {
+            linear: self.linear * factor,
+            angular: self.angular * factor,
+        }
+    }
+}
+
+/// Steering behaviors that can run on fixed-point agent state
+pub trait FixedSteeringBehavior {
+    /// Calculate steering based on agent state
+    fn calculate_fixed(&self, position: FixedVec3, velocity: FixedVec3) -> FixedSteeringOutput;
+}
+
+impl FixedSteeringBehavior for Seek {
+    fn calculate_fixed(&self, position: FixedVec3, _velocity: FixedVec3) -> FixedSteeringOutput {
+        let direction = (FixedVec3::from_vec3(self.target) - position).normalize_or_zero();
+        FixedSteeringOutput {
+            linear: direction * Fixed::from_f32(self.max_acceleration),
+            angular: Fixed::ZERO,
+        }
+    }
+}
+
+impl FixedSteeringBehavior for Flee {
+    fn calculate_fixed(&self, position: FixedVec3, _velocity: FixedVec3) -> FixedSteeringOutput {
+        let direction = (position - FixedVec3::from_vec3(self.target)).normalize_or_zero();
+        FixedSteeringOutput {
+            linear: direction * Fixed::from_f32(self.max_acceleration),
+            angular: Fixed::ZERO,
+        }
+    }
+}
+
+impl FixedSteeringBehavior for Arrive {
+    fn calculate_fixed(&self, position: FixedVec3, velocity: FixedVec3) -> FixedSteeringOutput {
+        let to_target = FixedVec3::from_vec3(self.target) - position;
+        let distance = to_target.length();
+
+        if distance < Fixed::from_f32(self.target_radius) {
+            return FixedSteeringOutput::ZERO;
+        }
+
+        let max_speed = Fixed::from_f32(self.max_speed);
+        let slow_radius = Fixed::from_f32(self.slow_radius);
+        let target_speed = if distance > slow_radius {
+            max_speed
+        } else {
+            max_speed * distance / slow_radius
+        };
+
+        let target_velocity = to_target.normalize_or_zero() * target_speed;
+        FixedSteeringOutput {
+            linear: (target_velocity - velocity)
+                .clamp_length_max(Fixed::from_f32(self.max_acceleration)),
+            angular: Fixed::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Vec3;
+
+    use super::*;
+    use crate::ai::SteeringBehavior;
+
+    #[test]
+    fn test_fixed_seek_and_flee_match_float() {
+        let seek = Seek::new(Vec3::new(10.0, 0.0, 0.0), 5.0);
+        let output = seek.calculate_fixed(FixedVec3::ZERO, FixedVec3::ZERO);
+        assert_eq!(output.linear, FixedVec3::X * Fixed::from_int(5));
+
+        let flee = Flee::new(Vec3::new(10.0, 0.0, 0.0), 5.0);
+        let output = flee.calculate_fixed(FixedVec3::ZERO, FixedVec3::ZERO);
+        assert_eq!(output.linear, -FixedVec3::X * Fixed::from_int(5));
+    }
+
+    #[test]
+    fn test_fixed_arrive_matches_float() {
+        let arrive = Arrive::new(Vec3::new(2.0, 0.0, 1.0), 5.0, 10.0);
+        let (position, velocity) = (Vec3::new(0.5, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+
+        let expected = arrive.calculate(position, velocity).linear;
+        let output = arrive.calculate_fixed(
+            FixedVec3::from_vec3(position),
+            FixedVec3::from_vec3(velocity),
+        );
+        assert!((output.linear.to_vec3() - expected).length() < 1e-4);
+
+        let inside = FixedVec3::from_vec3(Vec3::new(2.0, 0.0, 1.2));
+        assert_eq!(
+            arrive.calculate_fixed(inside, FixedVec3::ZERO),
+            FixedSteeringOutput::ZERO
+        );
+    }
+}
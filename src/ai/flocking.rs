@@ -0,0 +1,375 @@
+//! Flocking behaviors
+//!
+//! Boids-style separation, cohesion and alignment, combined by a weighted
+//! [`Flocking`] behavior, with a spatial hash for neighbor queries.
+
+use glam::{IVec3, Vec3};
+use rustc_hash::FxHashMap;
+
+use super::steering::{SteeringBehavior, SteeringOutput};
+
+/// Position and velocity of a nearby agent
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Neighbor {
+    /// World position
+    pub position: Vec3,
+    /// Current velocity
+    pub velocity: Vec3,
+}
+
+impl Neighbor {
+    /// Create a neighbor
+    #[must_use]
+    pub fn new(position: Vec3, velocity: Vec3) -> Self {
+        Self { position, velocity }
+    }
+}
+
+/// Uniform grid bucketing agent indices by position for radius queries
+#[derive(Debug, Clone)]
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: FxHashMap<IVec3, Vec<usize>>,
+    positions: Vec<Vec3>,
+}
+
+impl SpatialHash {
+    /// Create an empty hash; `cell_size` should be about the query radius
+    #[must_use]
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(f32::EPSILON),
+            cells: FxHashMap::default(),
+            positions: Vec::new(),
+        }
+    }
+
+    /// Cell size
+    #[must_use]
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Remove all entries, keeping allocations
+    pub fn clear(&mut self) {
+        for cell in self.cells.values_mut() {
+            cell.clear();
+        }
+        self.positions.clear();
+    }
+
+    /// Replace the contents with `positions`, indexed by slice position
+    pub fn rebuild(&mut self, positions: impl IntoIterator<Item = Vec3>) {
+        self.clear();
+        for position in positions {
+            self.insert(position);
+        }
+    }
+
+    /// Insert a position, returning its index
+    pub fn insert(&mut self, position: Vec3) -> usize {
+        let index = self.positions.len();
+        self.positions.push(position);
+        self.cells
+            .entry(self.cell(position))
+            .or_default()
+            .push(index);
+        index
+    }
+
+    /// Number of entries
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Whether the hash is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Append the indices of all entries within `radius` of `position` to `out`
+    pub fn query(&self, position: Vec3, radius: f32, out: &mut Vec<usize>) {
+        let min = self.cell(position - Vec3::splat(radius));
+        let max = self.cell(position + Vec3::splat(radius));
+        let radius_sq = radius * radius;
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                for z in min.z..=max.z {
+                    let Some(cell) = self.cells.get(&IVec3::new(x, y, z)) else {
+                        continue;
+                    };
+                    out.extend(
+                        cell.iter()
+                            .copied()
+                            .filter(|&i| self.positions[i].distance_squared(position) <= radius_sq),
+                    );
+                }
+            }
+        }
+    }
+
+    fn cell(&self, position: Vec3) -> IVec3 {
+        (position / self.cell_size).floor().as_ivec3()
+    }
+}
+
+/// Separation - steer away from crowded neighbors
+#[derive(Debug, Clone)]
+pub struct Separation {
+    /// Neighbors closer than this push the agent away
+    pub radius: f32,
+    /// Maximum acceleration
+    pub max_acceleration: f32,
+}
+
+impl Separation {
+    /// Create a new separation behavior
+    #[must_use]
+    pub fn new(radius: f32, max_acceleration: f32) -> Self {
+        Self {
+            radius,
+            max_acceleration,
+        }
+    }
+
+    /// Calculate steering against a set of neighbors
+    #[must_use]
+    pub fn calculate(&self, position: Vec3, neighbors: &[Neighbor]) -> SteeringOutput {
+        let mut push = Vec3::ZERO;
+        for neighbor in neighbors {
+            let away = position - neighbor.position;
+            let distance = away.length();
+            if distance > f32::EPSILON && distance < self.radius {
+                // Stronger the closer the neighbor is
+                push += away / distance * (1.0 - distance / self.radius);
+            }
+        }
+        SteeringOutput {
+            linear: push.clamp_length_max(1.0) * self.max_acceleration,
+            angular: 0.0,
+        }
+    }
+}
+
+/// Cohesion - steer towards the neighbors' center of mass
+#[derive(Debug, Clone)]
+pub struct Cohesion {
+    /// Maximum acceleration
+    pub max_acceleration: f32,
+}
+
+impl Cohesion {
+    /// Create a new cohesion behavior
+    #[must_use]
+    pub fn new(max_acceleration: f32) -> Self {
+        Self { max_acceleration }
+    }
+
+    /// Calculate steering against a set of neighbors
+    #[must_use]
+    pub fn calculate(&self, position: Vec3, neighbors: &[Neighbor]) -> SteeringOutput {
+        if neighbors.is_empty() {
+            return SteeringOutput::ZERO;
+        }
+        let center = neighbors.iter().map(|n| n.position).sum::<Vec3>() / neighbors.len() as f32;
+        SteeringOutput {
+            linear: (center - position).normalize_or_zero() * self.max_acceleration,
+            angular: 0.0,
+        }
+    }
+}
+
+/// Alignment - match the neighbors' average velocity
+#[derive(Debug, Clone)]
+pub struct Alignment {
+    /// Maximum acceleration
+    pub max_acceleration: f32,
+}
+
+impl Alignment {
+    /// Create a new alignment behavior
+    #[must_use]
+    pub fn new(max_acceleration: f32) -> Self {
+        Self { max_acceleration }
+    }
+
+    /// Calculate steering against a set of neighbors
+    #[must_use]
+    pub fn calculate(&self, velocity: Vec3, neighbors: &[Neighbor]) -> SteeringOutput {
+        if neighbors.is_empty() {
+            return SteeringOutput::ZERO;
+        }
+        let average = neighbors.iter().map(|n| n.velocity).sum::<Vec3>() / neighbors.len() as f32;
+        SteeringOutput {
+            linear: (average - velocity).clamp_length_max(self.max_acceleration),
+            angular: 0.0,
+        }
+    }
+}
+
+/// Weighted combination of separation, cohesion and alignment
+///
+/// Used as a [`SteeringBehavior`] after [`Flocking::set_neighbors`], or for a
+/// whole flock at once with [`Flocking::calculate_flock`].
+#[derive(Debug, Clone)]
+pub struct Flocking {
+    /// Separation behavior
+    pub separation: Separation,
+    /// Cohesion behavior
+    pub cohesion: Cohesion,
+    /// Alignment behavior
+    pub alignment: Alignment,
+    /// Weight of separation
+    pub separation_weight: f32,
+    /// Weight of cohesion
+    pub cohesion_weight: f32,
+    /// Weight of alignment
+    pub alignment_weight: f32,
+    /// Agents within this distance are neighbors
+    pub neighbor_radius: f32,
+    /// Maximum combined acceleration
+    pub max_acceleration: f32,
+    neighbors: Vec<Neighbor>,
+}
+
+impl Flocking {
+    /// Create a flocking behavior with default weights
+    #[must_use]
+    pub fn new(neighbor_radius: f32, max_acceleration: f32) -> Self {
+        Self {
+            separation: Separation::new(neighbor_radius * 0.5, max_acceleration),
+            cohesion: Cohesion::new(max_acceleration),
+            alignment: Alignment::new(max_acceleration),
+            separation_weight: 1.5,
+            cohesion_weight: 1.0,
+            alignment_weight: 1.0,
+            neighbor_radius,
+            max_acceleration,
+            neighbors: Vec::new(),
+        }
+    }
+
+    /// Set the weights of separation, cohesion and alignment
+    #[must_use]
+    pub fn with_weights(mut self, separation: f32, cohesion: f32, alignment: f32) -> Self {
+        self.separation_weight = separation;
+        self.cohesion_weight = cohesion;
+        self.alignment_weight = alignment;
+        self
+    }
+
+    /// Set the separation radius
+    #[must_use]
+    pub fn with_separation_radius(mut self, radius: f32) -> Self {
+        self.separation.radius = radius;
+        self
+    }
+
+    /// Replace the neighbors used by [`SteeringBehavior::calculate`]
+    pub fn set_neighbors(&mut self, neighbors: impl IntoIterator<Item = Neighbor>) {
+        self.neighbors.clear();
+        self.neighbors.extend(neighbors);
+    }
+
+    /// Current neighbors
+    #[must_use]
+    pub fn neighbors(&self) -> &[Neighbor] {
+        &self.neighbors
+    }
+
+    /// Steering for one agent given its neighbors (which must not include itself)
+    #[must_use]
+    pub fn calculate_with(
+        &self,
+        position: Vec3,
+        velocity: Vec3,
+        neighbors: &[Neighbor],
+    ) -> SteeringOutput {
+        let linear = self.separation.calculate(position, neighbors).linear * self.separation_weight
+            + self.cohesion.calculate(position, neighbors).linear * self.cohesion_weight
+            + self.alignment.calculate(velocity, neighbors).linear * self.alignment_weight;
+        SteeringOutput {
+            linear: linear.clamp_length_max(self.max_acceleration),
+            angular: 0.0,
+        }
+    }
+
+    /// Steering for every agent in a flock, using `hash` for neighbor queries
+    ///
+    /// The returned outputs are in the same order as `agents`.
+    pub fn calculate_flock(
+        &self,
+        agents: &[Neighbor],
+        hash: &mut SpatialHash,
+    ) -> Vec<SteeringOutput> {
+        hash.rebuild(agents.iter().map(|agent| agent.position));
+        let mut indices = Vec::new();
+        let mut neighbors = Vec::new();
+        agents
+            .iter()
+            .enumerate()
+            .map(|(index, agent)| {
+                indices.clear();
+                hash.query(agent.position, self.neighbor_radius, &mut indices);
+                neighbors.clear();
+                neighbors.extend(indices.iter().filter(|&&i| i != index).map(|&i| agents[i]));
+                self.calculate_with(agent.position, agent.velocity, &neighbors)
+            })
+            .collect()
+    }
+}
+
+impl SteeringBehavior for Flocking {
+    fn calculate(&self, position: Vec3, velocity: Vec3) -> SteeringOutput {
+        self.calculate_with(position, velocity, &self.neighbors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spatial_hash_query() {
+        let mut hash = SpatialHash::new(2.0);
+        hash.rebuild([
+            Vec3::ZERO,
+            Vec3::new(1.5, 0.0, 0.0),
+            Vec3::new(-2.5, 0.0, 0.0),
+            Vec3::new(10.0, 0.0, 0.0),
+        ]);
+
+        let mut found = Vec::new();
+        hash.query(Vec3::ZERO, 3.0, &mut found);
+        found.sort_unstable();
+        assert_eq!(found, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_flocking_components() {
+        let neighbors = [
+            Neighbor::new(Vec3::new(1.0, 0.0, 0.0), Vec3::Z),
+            Neighbor::new(Vec3::new(3.0, 0.0, 0.0), Vec3::Z),
+        ];
+
+        let separation = Separation::new(2.0, 1.0).calculate(Vec3::ZERO, &neighbors);
+        assert!(separation.linear.x < 0.0);
+
+        let cohesion = Cohesion::new(1.0).calculate(Vec3::ZERO, &neighbors);
+        assert!(cohesion.linear.x > 0.0);
+
+        let alignment = Alignment::new(1.0).calculate(Vec3::ZERO, &neighbors);
+        assert!(alignment.linear.z > 0.0);
+
+        let agents = [
+            Neighbor::new(Vec3::ZERO, Vec3::ZERO),
+            neighbors[0],
+            neighbors[1],
+        ];
+        let outputs = Flocking::new(5.0, 2.0).calculate_flock(&agents, &mut SpatialHash::new(5.0));
+        assert_eq!(outputs.len(), 3);
+        assert!(outputs.iter().all(|o| o.linear.length() <= 2.0 + 1e-4));
+    }
+}
@@ -0,0 +1,360 @@
+//! Finite state machine
+//!
+//! A small FSM over user-defined state types (usually a fieldless enum) with
+//! enter/exit/update hooks and guarded transitions. Hooks and guards receive a
+//! context value, so the same machine drives enemy brains, doors or game flow.
+
+use std::fmt;
+use std::hash::Hash;
+
+use rustc_hash::FxHashMap;
+
+/// Hook run on entering or leaving a state
+type Hook<C> = Box<dyn FnMut(&mut C) + Send + Sync>;
+
+/// Hook run every update while in a state
+type UpdateHook<C> = Box<dyn FnMut(&mut C, f32) + Send + Sync>;
+
+/// Condition for taking a transition
+type Guard<C> = Box<dyn Fn(&C) -> bool + Send + Sync>;
+
+/// Hooks registered for one state
+struct Hooks<C> {
+    /// Run when the state becomes current
+    enter: Vec<Hook<C>>,
+    /// Run when the state stops being current
+    exit: Vec<Hook<C>>,
+    /// Run each update while current
+    update: Vec<UpdateHook<C>>,
+}
+
+impl<C> Default for Hooks<C> {
+    fn default() -> Self {
+        Self {
+            enter: Vec::new(),
+            exit: Vec::new(),
+            update: Vec::new(),
+        }
+    }
+}
+
+/// A guarded edge between states
+struct Transition<S, C> {
+    /// Source state, or `None` for any state
+    from: Option<S>,
+    /// Destination state
+    to: S,
+    /// Minimum time in the source state before the transition can fire
+    after: f32,
+    /// Extra condition, if any
+    guard: Option<Guard<C>>,
+}
+
+/// Finite state machine over states `S` with context `C`
+///
+/// Transitions are checked in registration order after the current state's
+/// update hooks run; the first one whose source matches, whose minimum time
+/// has elapsed and whose guard passes is taken. At most one transition
+/// happens per update.
+pub struct StateMachine<S, C> {
+    /// Current state
+    current: S,
+    /// State before the last transition
+    previous: Option<S>,
+    /// Seconds spent in the current state
+    time_in_state: f32,
+    /// Whether the initial state's enter hooks have run
+    started: bool,
+    /// Hooks by state
+    hooks: FxHashMap<S, Hooks<C>>,
+    /// Transitions in priority order
+    transitions: Vec<Transition<S, C>>,
+}
+
+impl<S, C> StateMachine<S, C>
+where
+    S: Copy + Eq + Hash + fmt::Debug,
+{
+    /// Create a machine starting in `initial`
+    ///
+    /// The initial state's enter hooks run on the first update.
+    #[must_use]
+    pub fn new(initial: S) -> Self {
+        Self {
+            current: initial,
+            previous: None,
+            time_in_state: 0.0,
+            started: false,
+            hooks: FxHashMap::default(),
+            transitions: Vec::new(),
+        }
+    }
+
+    /// Add a hook run when entering `state`
+    #[must_use]
+    pub fn on_enter(mut self, state: S, hook: impl FnMut(&mut C) + Send + Sync + 'static) -> Self {
+        self.hooks
+            .entry(state)
+            .or_default()
+            .enter
+            .push(Box::new(hook));
+        self
+    }
+
+    /// Add a hook run when leaving `state`
+    #[must_use]
+    pub fn on_exit(mut self, state: S, hook: impl FnMut(&mut C) + Send + Sync + 'static) -> Self {
+        self.hooks
+            .entry(state)
+            .or_default()
+            .exit
+            .push(Box::new(hook));
+        self
+    }
+
+    /// Add a hook run every update while in `state`, with the frame delta time
+    #[must_use]
+    pub fn on_update(
+        mut self,
+        state: S,
+        hook: impl FnMut(&mut C, f32) + Send + Sync + 'static,
+    ) -> Self {
+        self.hooks
+            .entry(state)
+            .or_default()
+            .update
+            .push(Box::new(hook));
+        self
+    }
+
+    /// Add a transition taken when `guard` passes in `from`
+    #[must_use]
+    pub fn with_transition(
+        mut self,
+        from: S,
+        to: S,
+        guard: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.transitions.push(Transition {
+            from: Some(from),
+            to,
+            after: 0.0,
+            guard: Some(Box::new(guard)),
+        });
+        self
+    }
+
+    /// Add a transition taken from any other state when `guard` passes
+    #[must_use]
+    pub fn with_any_transition(
+        mut self,
+        to: S,
+        guard: impl Fn(&C) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.transitions.push(Transition {
+            from: None,
+            to,
+            after: 0.0,
+            guard: Some(Box::new(guard)),
+        });
+        self
+    }
+
+    /// Add a transition taken after spending `seconds` in `from`
+    #[must_use]
+    pub fn with_timeout(mut self, from: S, to: S, seconds: f32) -> Self {
+        self.transitions.push(Transition {
+            from: Some(from),
+            to,
+            after: seconds,
+            guard: None,
+        });
+        self
+    }
+
+    /// Current state
+    #[must_use]
+    pub fn current(&self) -> S {
+        self.current
+    }
+
+    /// State before the last transition
+    #[must_use]
+    pub fn previous(&self) -> Option<S> {
+        self.previous
+    }
+
+    /// Check if the machine is in `state`
+    #[must_use]
+    pub fn is_in(&self, state: S) -> bool {
+        self.current == state
+    }
+
+    /// Seconds spent in the current state
+    #[must_use]
+    pub fn time_in_state(&self) -> f32 {
+        self.time_in_state
+    }
+
+    /// Run update hooks, then take the first matching transition
+    ///
+    /// Returns the new state if a transition happened.
+    pub fn update(&mut self, context: &mut C, dt: f32) -> Option<S> {
+        self.start(context);
+        self.time_in_state += dt;
+        if let Some(hooks) = self.hooks.get_mut(&self.current) {
+            for hook in &mut hooks.update {
+                hook(context, dt);
+            }
+        }
+
+        let current = self.current;
+        let time = self.time_in_state;
+        let next = self
+            .transitions
+            .iter()
+            .find(|t| {
+                t.from.is_none_or(|from| from == current)
+                    && t.to != current
+                    && time >= t.after
+                    && t.guard.as_ref().is_none_or(|guard| guard(context))
+            })
+            .map(|t| t.to)?;
+        self.change(next, context);
+        Some(next)
+    }
+
+    /// Switch to `state` immediately, running exit and enter hooks
+    ///
+    /// Switching to the current state re-enters it.
+    pub fn change(&mut self, state: S, context: &mut C) {
+        self.start(context);
+        log::debug!("State machine: {:?} -> {:?}", self.current, state);
+        self.run_hooks(self.current, context, |hooks| &mut hooks.exit);
+        self.previous = Some(self.current);
+        self.current = state;
+        self.time_in_state = 0.0;
+        self.run_hooks(state, context, |hooks| &mut hooks.enter);
+    }
+
+    /// Current state and time in it, formatted for a debug overlay
+    #[must_use]
+    pub fn debug_state(&self) -> String {
+        format!("{:?} ({:.1}s)", self.current, self.time_in_state)
+    }
+
+    /// Run the initial state's enter hooks once
+    fn start(&mut self, context: &mut C) {
+        if !self.started {
+            self.started = true;
+            self.run_hooks(self.current, context, |hooks| &mut hooks.enter);
+        }
+    }
+
+    fn run_hooks(
+        &mut self,
+        state: S,
+        context: &mut C,
+        select: impl Fn(&mut Hooks<C>) -> &mut Vec<Hook<C>>,
+    ) {
+        if let Some(hooks) = self.hooks.get_mut(&state) {
+            for hook in select(hooks) {
+                hook(context);
+            }
+        }
+    }
+}
+
+impl<S: fmt::Debug, C> fmt::Debug for StateMachine<S, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateMachine")
+            .field("current", &self.current)
+            .field("previous", &self.previous)
+            .field("time_in_state", &self.time_in_state)
+            .field("transitions", &self.transitions.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Enemy {
+        Patrol,
+        Chase,
+        Attack,
+        Dead,
+    }
+
+    #[derive(Default)]
+    struct Brain {
+        distance: f32,
+        health: f32,
+        log: Vec<&'static str>,
+    }
+
+    fn machine() -> StateMachine<Enemy, Brain> {
+        StateMachine::new(Enemy::Patrol)
+            .on_enter(Enemy::Patrol, |brain: &mut Brain| {
+                brain.log.push("enter patrol")
+            })
+            .on_exit(Enemy::Patrol, |brain: &mut Brain| {
+                brain.log.push("exit patrol")
+            })
+            .on_enter(Enemy::Chase, |brain: &mut Brain| {
+                brain.log.push("enter chase")
+            })
+            .on_update(Enemy::Chase, |brain: &mut Brain, dt| {
+                brain.distance -= 4.0 * dt
+            })
+            .with_any_transition(Enemy::Dead, |brain| brain.health <= 0.0)
+            .with_transition(Enemy::Patrol, Enemy::Chase, |brain| brain.distance < 10.0)
+            .with_transition(Enemy::Chase, Enemy::Attack, |brain| brain.distance < 2.0)
+            .with_timeout(Enemy::Attack, Enemy::Chase, 1.0)
+    }
+
+    #[test]
+    fn test_guarded_transitions() {
+        let mut fsm = machine();
+        let mut brain = Brain {
+            distance: 20.0,
+            health: 100.0,
+            ..Default::default()
+        };
+
+        assert_eq!(fsm.update(&mut brain, 0.1), None);
+        brain.distance = 5.0;
+        assert_eq!(fsm.update(&mut brain, 0.1), Some(Enemy::Chase));
+        assert_eq!(brain.log, ["enter patrol", "exit patrol", "enter chase"]);
+        assert_eq!(fsm.previous(), Some(Enemy::Patrol));
+
+        // Chasing closes the distance until the attack guard passes
+        let mut steps = 0;
+        while fsm.is_in(Enemy::Chase) && steps < 10 {
+            fsm.update(&mut brain, 0.25);
+            steps += 1;
+        }
+        assert!(fsm.is_in(Enemy::Attack));
+
+        // Any-state transition wins regardless of the current state
+        brain.health = 0.0;
+        assert_eq!(fsm.update(&mut brain, 0.1), Some(Enemy::Dead));
+        assert_eq!(fsm.update(&mut brain, 0.1), None);
+    }
+
+    #[test]
+    fn test_timeout_and_debug() {
+        let mut fsm = machine();
+        let mut brain = Brain {
+            distance: 1.0,
+            health: 100.0,
+            ..Default::default()
+        };
+        fsm.change(Enemy::Attack, &mut brain);
+        assert_eq!(fsm.update(&mut brain, 0.5), None);
+        assert_eq!(fsm.debug_state(), "Attack (0.5s)");
+        assert_eq!(fsm.update(&mut brain, 0.5), Some(Enemy::Chase));
+    }
+}
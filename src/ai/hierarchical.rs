@@ -0,0 +1,575 @@
+//! Hierarchical pathfinding (HPA*) for large grids
+//!
+//! The grid is split into square clusters. Walkable openings along each
+//! cluster border become entrance nodes, linked inside a cluster by their
+//! precomputed walking distance. Queries search this small abstract graph
+//! first and then refine each hop with a local search confined to one
+//! cluster. Changing cells only rebuilds the clusters around them.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use glam::Vec2;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use super::pathfinding::{Grid, GridRegion, PathResult, calculate_path_length};
+
+/// Openings at least this wide get an entrance at each end instead of one in the middle
+const WIDE_ENTRANCE: usize = 6;
+
+/// Abstract node id of the virtual goal during a query
+const GOAL: usize = usize::MAX;
+
+/// Links of one entrance node
+#[derive(Debug, Clone, Default)]
+struct EntranceNode {
+    /// Other entrances of the same cluster and the walking cost to them
+    intra: Vec<(usize, f32)>,
+    /// Facing cells across the cluster border
+    inter: Vec<usize>,
+}
+
+/// Cell rectangle `[x0, x1) x [y0, y1)`
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    x0: usize,
+    y0: usize,
+    x1: usize,
+    y1: usize,
+}
+
+impl Bounds {
+    fn contains(&self, x: usize, y: usize) -> bool {
+        x >= self.x0 && x < self.x1 && y >= self.y0 && y < self.y1
+    }
+}
+
+/// Open-list entry ordered by lowest cost first
+#[derive(Debug, Clone, Copy)]
+struct Open {
+    cost: f32,
+    node: usize,
+}
+
+impl PartialEq for Open {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost.total_cmp(&other.cost) == Ordering::Equal
+    }
+}
+
+impl Eq for Open {}
+
+impl Ord for Open {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Open list and bookkeeping of an abstract search
+#[derive(Debug, Default)]
+struct Frontier {
+    open: BinaryHeap<Open>,
+    g_score: FxHashMap<usize, f32>,
+    came_from: FxHashMap<usize, usize>,
+}
+
+impl Frontier {
+    /// Record a cheaper way to reach `node`
+    fn relax(&mut self, node: usize, from: Option<usize>, cost: f32, heuristic: f32) {
+        if cost < self.g_score.get(&node).copied().unwrap_or(f32::MAX) {
+            self.g_score.insert(node, cost);
+            if let Some(from) = from {
+                self.came_from.insert(node, from);
+            }
+            self.open.push(Open {
+                cost: cost + heuristic,
+                node,
+            });
+        }
+    }
+}
+
+/// Abstract graph over a [`Grid`] for fast long-distance queries
+///
+/// Cells are addressed by their linear index `y * width + x`. After changing
+/// the grid, pass [`Grid::take_dirty`] to [`HierarchicalGrid::mark_region`]
+/// and call [`HierarchicalGrid::update`] before the next query.
+#[derive(Debug, Clone)]
+pub struct HierarchicalGrid {
+    /// Grid width in cells
+    width: usize,
+    /// Grid height in cells
+    height: usize,
+    /// Cluster edge length in cells
+    cluster_size: usize,
+    /// Clusters along X
+    clusters_x: usize,
+    /// Clusters along Y
+    clusters_y: usize,
+    /// Entrance pairs per border, keyed by (lower cluster, higher cluster)
+    borders: FxHashMap<(usize, usize), Vec<(usize, usize)>>,
+    /// Entrance nodes of each cluster
+    nodes: Vec<FxHashMap<usize, EntranceNode>>,
+    /// Clusters waiting to be rebuilt
+    dirty: FxHashSet<usize>,
+}
+
+impl HierarchicalGrid {
+    /// Build the abstract graph for a grid
+    #[must_use]
+    pub fn new(grid: &Grid, cluster_size: usize) -> Self {
+        let cluster_size = cluster_size.max(2);
+        let clusters_x = grid.width.div_ceil(cluster_size);
+        let clusters_y = grid.height.div_ceil(cluster_size);
+        let count = clusters_x * clusters_y;
+        let mut hierarchy = Self {
+            width: grid.width,
+            height: grid.height,
+            cluster_size,
+            clusters_x,
+            clusters_y,
+            borders: FxHashMap::default(),
+            nodes: vec![FxHashMap::default(); count],
+            dirty: (0..count).collect(),
+        };
+        hierarchy.update(grid);
+        hierarchy
+    }
+
+    /// Cluster edge length in cells
+    #[must_use]
+    pub fn cluster_size(&self) -> usize {
+        self.cluster_size
+    }
+
+    /// Number of clusters
+    #[must_use]
+    pub fn cluster_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of entrance nodes in the abstract graph
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.nodes.iter().map(FxHashMap::len).sum()
+    }
+
+    /// Whether any cluster is waiting for [`HierarchicalGrid::update`]
+    #[must_use]
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Record that a cell's walkability changed
+    pub fn mark_dirty(&mut self, x: usize, y: usize) {
+        if x < self.width && y < self.height {
+            self.dirty.insert(self.cluster_of(x, y));
+        }
+    }
+
+    /// Record that every cell in a region changed, e.g. from [`Grid::take_dirty`]
+    pub fn mark_region(&mut self, region: &GridRegion) {
+        let max_x = region.max_x.min(self.width.saturating_sub(1)) / self.cluster_size;
+        let max_y = region.max_y.min(self.height.saturating_sub(1)) / self.cluster_size;
+        for cy in region.min_y / self.cluster_size..=max_y {
+            for cx in region.min_x / self.cluster_size..=max_x {
+                if cx < self.clusters_x && cy < self.clusters_y {
+                    self.dirty.insert(cy * self.clusters_x + cx);
+                }
+            }
+        }
+    }
+
+    /// Change a cell on the grid and mark it dirty
+    pub fn set_walkable(&mut self, grid: &mut Grid, x: usize, y: usize, walkable: bool) {
+        if grid.is_walkable(x, y) != walkable {
+            grid.set_walkable(x, y, walkable);
+            self.mark_dirty(x, y);
+        }
+    }
+
+    /// Rebuild dirty clusters and their neighbors' links
+    ///
+    /// Returns the number of clusters whose links were recomputed.
+    pub fn update(&mut self, grid: &Grid) -> usize {
+        if self.dirty.is_empty() {
+            return 0;
+        }
+        let dirty: Vec<usize> = self.dirty.drain().collect();
+        let mut relink = FxHashSet::default();
+        for &cluster in &dirty {
+            relink.insert(cluster);
+            for neighbor in self.cluster_neighbors(cluster) {
+                relink.insert(neighbor);
+                let key = (cluster.min(neighbor), cluster.max(neighbor));
+                let entrances = self.find_entrances(grid, key.0, key.1);
+                self.borders.insert(key, entrances);
+            }
+        }
+        for &cluster in &relink {
+            self.link_cluster(grid, cluster);
+        }
+        relink.len()
+    }
+
+    /// Find a path between two world positions
+    ///
+    /// Paths may be slightly longer than the optimum from
+    /// [`find_path`](super::find_path) since they pass through entrance nodes.
+    /// Pending changes are ignored until [`HierarchicalGrid::update`] runs.
+    #[must_use]
+    pub fn find_path(&self, grid: &Grid, start: Vec2, goal: Vec2) -> PathResult {
+        let (Some(start), Some(goal)) = (self.cell_at(grid, start), self.cell_at(grid, goal))
+        else {
+            return PathResult::default();
+        };
+        let start_cluster = self.cluster_of_cell(start);
+        let goal_cluster = self.cluster_of_cell(goal);
+
+        if start_cluster == goal_cluster
+            && let Some(cells) = self.local_path(grid, start, goal)
+        {
+            return self.to_result(grid, &cells);
+        }
+
+        let from_start = self.local_costs(grid, start, start_cluster);
+        let to_goal = self.local_costs(grid, goal, goal_cluster);
+        let Some(route) = self.abstract_search(grid, start, goal, &from_start, &to_goal) else {
+            return PathResult::default();
+        };
+
+        let mut cells = vec![start];
+        for pair in route.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            if self.cluster_of_cell(from) == self.cluster_of_cell(to) {
+                let Some(segment) = self.local_path(grid, from, to) else {
+                    return PathResult::default();
+                };
+                cells.extend_from_slice(&segment[1..]);
+            } else {
+                cells.push(to);
+            }
+        }
+        self.to_result(grid, &cells)
+    }
+
+    /// A* over entrance nodes from `start` to `goal` via precomputed local costs
+    fn abstract_search(
+        &self,
+        grid: &Grid,
+        start: usize,
+        goal: usize,
+        from_start: &FxHashMap<usize, f32>,
+        to_goal: &FxHashMap<usize, f32>,
+    ) -> Option<Vec<usize>> {
+        let goal_cluster = self.cluster_of_cell(goal);
+        let heuristic = |cell: usize| {
+            let (x, y) = (cell % self.width, cell / self.width);
+            let (gx, gy) = (goal % self.width, goal / self.width);
+            (x.abs_diff(gx) + y.abs_diff(gy)) as f32
+        };
+
+        let estimate = |node: usize| if node == GOAL { 0.0 } else { heuristic(node) };
+        let mut frontier = Frontier::default();
+        let mut closed = FxHashSet::default();
+        for (&node, &cost) in from_start {
+            if self.nodes[self.cluster_of_cell(node)].contains_key(&node) {
+                frontier.relax(node, None, cost, estimate(node));
+            }
+        }
+
+        while let Some(Open { node, .. }) = frontier.open.pop() {
+            if !closed.insert(node) {
+                continue;
+            }
+            if node == GOAL {
+                let mut route = vec![goal];
+                let mut current = GOAL;
+                while let Some(&previous) = frontier.came_from.get(&current) {
+                    route.push(previous);
+                    current = previous;
+                }
+                route.push(start);
+                route.reverse();
+                return Some(route);
+            }
+
+            let cost = frontier.g_score[&node];
+            let cluster = self.cluster_of_cell(node);
+            if cluster == goal_cluster
+                && let Some(&remaining) = to_goal.get(&node)
+            {
+                frontier.relax(GOAL, Some(node), cost + remaining, 0.0);
+            }
+            let Some(links) = self.nodes[cluster].get(&node) else {
+                continue;
+            };
+            for &(next, step) in &links.intra {
+                frontier.relax(next, Some(node), cost + step, estimate(next));
+            }
+            for &next in &links.inter {
+                let step = grid.cost(next % self.width, next / self.width);
+                frontier.relax(next, Some(node), cost + step, estimate(next));
+            }
+        }
+        None
+    }
+
+    /// Recompute the entrance nodes and their links for one cluster
+    fn link_cluster(&mut self, grid: &Grid, cluster: usize) {
+        let mut nodes: FxHashMap<usize, EntranceNode> = FxHashMap::default();
+        for neighbor in self.cluster_neighbors(cluster) {
+            let key = (cluster.min(neighbor), cluster.max(neighbor));
+            for &(low, high) in self.borders.get(&key).into_iter().flatten() {
+                let (own, other) = if key.0 == cluster {
+                    (low, high)
+                } else {
+                    (high, low)
+                };
+                nodes.entry(own).or_default().inter.push(other);
+            }
+        }
+
+        let cells: Vec<usize> = nodes.keys().copied().collect();
+        for &cell in &cells {
+            let costs = self.local_costs(grid, cell, cluster);
+            let intra = cells
+                .iter()
+                .filter(|&&other| other != cell)
+                .filter_map(|other| costs.get(other).map(|&cost| (*other, cost)))
+                .collect();
+            if let Some(node) = nodes.get_mut(&cell) {
+                node.intra = intra;
+            }
+        }
+        self.nodes[cluster] = nodes;
+    }
+
+    /// Walkable openings on the border between two adjacent clusters
+    fn find_entrances(&self, grid: &Grid, low: usize, high: usize) -> Vec<(usize, usize)> {
+        let bounds = self.cluster_bounds(low);
+        // Cells facing each other across the border, in order along it
+        let side_by_side = high / self.clusters_x == low / self.clusters_x;
+        let pairs: Vec<((usize, usize), (usize, usize))> = if side_by_side {
+            (bounds.y0..bounds.y1)
+                .map(|y| ((bounds.x1 - 1, y), (bounds.x1, y)))
+                .collect()
+        } else {
+            (bounds.x0..bounds.x1)
+                .map(|x| ((x, bounds.y1 - 1), (x, bounds.y1)))
+                .collect()
+        };
+
+        let mut entrances = Vec::new();
+        let mut run: Vec<(usize, usize)> = Vec::new();
+        let flush = |run: &mut Vec<(usize, usize)>, entrances: &mut Vec<(usize, usize)>| {
+            match run.len() {
+                0 => {}
+                n if n >= WIDE_ENTRANCE => {
+                    entrances.push(run[0]);
+                    entrances.push(run[n - 1]);
+                }
+                n => entrances.push(run[n / 2]),
+            }
+            run.clear();
+        };
+        for ((ax, ay), (bx, by)) in pairs {
+            if grid.is_walkable(ax, ay) && grid.is_walkable(bx, by) {
+                run.push((ay * self.width + ax, by * self.width + bx));
+            } else {
+                flush(&mut run, &mut entrances);
+            }
+        }
+        flush(&mut run, &mut entrances);
+        entrances
+    }
+
+    /// Dijkstra from `start` within a cluster, returning the cost to every reached cell
+    fn local_costs(&self, grid: &Grid, start: usize, cluster: usize) -> FxHashMap<usize, f32> {
+        self.local_search(grid, start, None, self.cluster_bounds(cluster))
+            .0
+    }
+
+    /// Shortest path between two cells of the same cluster, staying inside it
+    fn local_path(&self, grid: &Grid, start: usize, goal: usize) -> Option<Vec<usize>> {
+        let bounds = self.cluster_bounds(self.cluster_of_cell(start));
+        let (costs, came_from) = self.local_search(grid, start, Some(goal), bounds);
+        if !costs.contains_key(&goal) {
+            return None;
+        }
+        let mut path = vec![goal];
+        let mut current = goal;
+        while let Some(&previous) = came_from.get(&current) {
+            path.push(previous);
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Uniform-cost search inside `bounds`, stopping early once `target` is settled
+    fn local_search(
+        &self,
+        grid: &Grid,
+        start: usize,
+        target: Option<usize>,
+        bounds: Bounds,
+    ) -> (FxHashMap<usize, f32>, FxHashMap<usize, usize>) {
+        let mut costs = FxHashMap::default();
+        let mut came_from = FxHashMap::default();
+        let mut closed = FxHashSet::default();
+        let mut open = BinaryHeap::new();
+        costs.insert(start, 0.0);
+        open.push(Open {
+            cost: 0.0,
+            node: start,
+        });
+
+        while let Some(Open { cost, node }) = open.pop() {
+            if !closed.insert(node) {
+                continue;
+            }
+            if Some(node) == target {
+                break;
+            }
+            let (x, y) = (node % self.width, node / self.width);
+            for (nx, ny) in grid.neighbors(x, y) {
+                if !bounds.contains(nx, ny) {
+                    continue;
+                }
+                let next = ny * self.width + nx;
+                let next_cost = cost + grid.cost(nx, ny);
+                if next_cost < costs.get(&next).copied().unwrap_or(f32::MAX) {
+                    costs.insert(next, next_cost);
+                    came_from.insert(next, node);
+                    open.push(Open {
+                        cost: next_cost,
+                        node: next,
+                    });
+                }
+            }
+        }
+        (costs, came_from)
+    }
+
+    fn to_result(&self, grid: &Grid, cells: &[usize]) -> PathResult {
+        let waypoints: Vec<Vec2> = cells
+            .iter()
+            .map(|&cell| grid.grid_to_world(cell % self.width, cell / self.width))
+            .collect();
+        let length = calculate_path_length(&waypoints);
+        PathResult { waypoints, length }
+    }
+
+    /// Walkable cell under a world position
+    fn cell_at(&self, grid: &Grid, position: Vec2) -> Option<usize> {
+        let (x, y) = grid.world_to_grid(position);
+        let (x, y) = (usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+        grid.is_walkable(x, y).then_some(y * self.width + x)
+    }
+
+    fn cluster_of(&self, x: usize, y: usize) -> usize {
+        (y / self.cluster_size) * self.clusters_x + x / self.cluster_size
+    }
+
+    fn cluster_of_cell(&self, cell: usize) -> usize {
+        self.cluster_of(cell % self.width, cell / self.width)
+    }
+
+    fn cluster_bounds(&self, cluster: usize) -> Bounds {
+        let (cx, cy) = (cluster % self.clusters_x, cluster / self.clusters_x);
+        let (x0, y0) = (cx * self.cluster_size, cy * self.cluster_size);
+        Bounds {
+            x0,
+            y0,
+            x1: (x0 + self.cluster_size).min(self.width),
+            y1: (y0 + self.cluster_size).min(self.height),
+        }
+    }
+
+    fn cluster_neighbors(&self, cluster: usize) -> impl Iterator<Item = usize> + use<> {
+        let (cx, cy) = (cluster % self.clusters_x, cluster / self.clusters_x);
+        let clusters_x = self.clusters_x;
+        [
+            (cx > 0).then(|| cluster - 1),
+            (cx + 1 < self.clusters_x).then_some(cluster + 1),
+            (cy > 0).then(|| cluster - clusters_x),
+            (cy + 1 < self.clusters_y).then_some(cluster + clusters_x),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::find_path;
+
+    /// Check that a path only steps between adjacent walkable cells
+    fn assert_connected(grid: &Grid, path: &PathResult) {
+        for pair in path.waypoints.windows(2) {
+            assert!((pair[0].distance(pair[1]) - grid.cell_size).abs() < 1e-4);
+        }
+        for point in &path.waypoints {
+            let (x, y) = grid.world_to_grid(*point);
+            assert!(grid.is_walkable(x as usize, y as usize));
+        }
+    }
+
+    #[test]
+    fn test_matches_flat_search() {
+        let mut grid = Grid::new(40, 40, 1.0);
+        // Walls with gaps crossing several clusters
+        for y in 0..35 {
+            grid.set_walkable(12, y, false);
+        }
+        for y in 5..40 {
+            grid.set_walkable(27, y, false);
+        }
+        let hierarchy = HierarchicalGrid::new(&grid, 8);
+        assert_eq!(hierarchy.cluster_count(), 25);
+
+        let (start, goal) = (Vec2::new(2.5, 2.5), Vec2::new(37.5, 37.5));
+        let flat = find_path(&grid, start, goal);
+        let path = hierarchy.find_path(&grid, start, goal);
+        assert!(!path.is_empty());
+        assert_connected(&grid, &path);
+        assert_eq!(path.waypoints.first(), flat.waypoints.first());
+        assert_eq!(path.waypoints.last(), flat.waypoints.last());
+        assert!(path.length <= flat.length * 1.2);
+    }
+
+    #[test]
+    fn test_incremental_update() {
+        let mut grid = Grid::new(32, 16, 1.0);
+        let mut hierarchy = HierarchicalGrid::new(&grid, 8);
+        let (start, goal) = (Vec2::new(1.5, 8.5), Vec2::new(30.5, 8.5));
+
+        // Close a wall across the whole grid
+        for y in 0..16 {
+            hierarchy.set_walkable(&mut grid, 16, y, false);
+        }
+        assert!(hierarchy.is_dirty());
+        assert!(hierarchy.update(&grid) < hierarchy.cluster_count());
+        assert!(hierarchy.find_path(&grid, start, goal).is_empty());
+
+        // Open a door
+        hierarchy.set_walkable(&mut grid, 16, 3, true);
+        hierarchy.update(&grid);
+        let path = hierarchy.find_path(&grid, start, goal);
+        assert!(!path.is_empty());
+        assert_connected(&grid, &path);
+        assert!(
+            path.waypoints
+                .iter()
+                .any(|p| p.distance(Vec2::new(16.5, 3.5)) < 1e-4)
+        );
+    }
+}
@@ -1,9 +1,32 @@
 //! AI and navigation module
 //!
-//! Provides pathfinding, steering behaviors, and AI utilities.
+//! Provides pathfinding, navigation meshes, steering behaviors, and AI utilities.
 
+mod avoidance;
+mod blackboard;
+#[cfg(feature = "fixed-math")]
+mod fixed_steering;
+mod flocking;
+mod fsm;
+mod hierarchical;
+mod navmesh;
+mod navquery;
 mod pathfinding;
+mod patrol;
 mod steering;
+mod utility;
 
-pub use pathfinding::{Grid, PathResult, find_path};
-pub use steering::{Arrive, Flee, Seek, SteeringBehavior, SteeringOutput, Wander};
+pub use avoidance::{ObstacleAvoidance, WallAvoidance};
+pub use blackboard::{Blackboard, BlackboardKey};
+#[cfg(feature = "fixed-math")]
+pub use fixed_steering::{FixedSteeringBehavior, FixedSteeringOutput};
+pub use flocking::{Alignment, Cohesion, Flocking, Neighbor, Separation, SpatialHash};
+pub use fsm::StateMachine;
+pub use hierarchical::HierarchicalGrid;
+pub use navmesh::{NavMesh, NavMeshConfig, NavMeshSync, NavPoly, NavPolyRef, NavTile, build_tile};
+pub use pathfinding::{Grid, GridRegion, PathResult, PathWatcher, find_path};
+pub use patrol::{PathRecorder, PatrolPath, simplify};
+pub use steering::{
+    Arrive, BlendedSteering, Flee, FollowPath, Seek, SteeringBehavior, SteeringOutput, Wander,
+};
+pub use utility::{Consideration, ResponseCurve, UtilityAction, UtilityBrain, UtilityContext};
@@ -0,0 +1,680 @@
+//! Tiled navigation mesh
+//!
+//! Builds walkable polygons by sampling the level's colliders column by
+//! column, so stacked floors produce separate layers. The mesh is split
+//! into tiles that rebuild independently on worker threads when static
+//! geometry changes, then hot-swap into the live mesh.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::sync::mpsc::{Receiver, Sender, channel};
+
+use glam::{Vec2, Vec3};
+
+use crate::physics::{ColliderHandle, Physics, QueryWorld};
+
+/// Small offset used to start clearance rays above a surface
+const SURFACE_EPSILON: f32 = 0.01;
+
+/// Navmesh generation settings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NavMeshConfig {
+    /// Minimum corner of the covered volume
+    pub origin: Vec3,
+    /// Number of tiles along X and Z
+    pub tiles: (u32, u32),
+    /// Tile edge length in world units
+    pub tile_size: f32,
+    /// Sampling resolution in world units
+    pub cell_size: f32,
+    /// Height of the covered volume
+    pub height: f32,
+    /// Free space needed above a surface
+    pub agent_height: f32,
+    /// Distance kept from walls and ledges
+    pub agent_radius: f32,
+    /// Largest height difference an agent can step over
+    pub max_step: f32,
+}
+
+impl NavMeshConfig {
+    /// Create a config covering `tiles` tiles from `origin`
+    #[must_use]
+    pub fn new(origin: Vec3, tiles: (u32, u32), tile_size: f32) -> Self {
+        Self {
+            origin,
+            tiles,
+            tile_size,
+            cell_size: 0.25,
+            height: 20.0,
+            agent_height: 1.8,
+            agent_radius: 0.4,
+            max_step: 0.35,
+        }
+    }
+
+    /// Set the sampling resolution
+    #[must_use]
+    pub fn with_cell_size(mut self, cell_size: f32) -> Self {
+        self.cell_size = cell_size.max(0.01);
+        self
+    }
+
+    /// Set the height of the covered volume
+    #[must_use]
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Set the agent dimensions
+    #[must_use]
+    pub fn with_agent(mut self, height: f32, radius: f32, max_step: f32) -> Self {
+        self.agent_height = height;
+        self.agent_radius = radius;
+        self.max_step = max_step;
+        self
+    }
+
+    /// Get the number of sampled cells along a tile edge
+    #[must_use]
+    pub fn cells_per_tile(&self) -> usize {
+        (self.tile_size / self.cell_size).ceil().max(1.0) as usize
+    }
+
+    /// Get the tile containing a world position
+    #[must_use]
+    pub fn tile_at(&self, position: Vec3) -> Option<(u32, u32)> {
+        let local = (Vec2::new(position.x, position.z) - Vec2::new(self.origin.x, self.origin.z))
+            / self.tile_size;
+        let (x, z) = (local.x.floor(), local.y.floor());
+        (x >= 0.0 && z >= 0.0 && (x as u32) < self.tiles.0 && (z as u32) < self.tiles.1)
+            .then_some((x as u32, z as u32))
+    }
+
+    /// Get a tile's minimum XZ corner
+    fn tile_origin(&self, tile: (u32, u32)) -> Vec2 {
+        Vec2::new(self.origin.x, self.origin.z)
+            + Vec2::new(tile.0 as f32, tile.1 as f32) * self.tile_size
+    }
+}
+
+/// An axis-aligned walkable rectangle at a constant height
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NavPoly {
+    /// Minimum XZ corner
+    pub min: Vec2,
+    /// Maximum XZ corner
+    pub max: Vec2,
+    /// Surface height
+    pub height: f32,
+}
+
+impl NavPoly {
+    /// Get the center on the surface
+    #[must_use]
+    pub fn center(&self) -> Vec3 {
+        let center = (self.min + self.max) * 0.5;
+        Vec3::new(center.x, self.height, center.y)
+    }
+
+    /// Check if an XZ position lies inside the rectangle
+    #[must_use]
+    pub fn contains(&self, xz: Vec2) -> bool {
+        xz.cmpge(self.min).all() && xz.cmple(self.max).all()
+    }
+
+    /// Get the shared edge with another polygon, if they touch
+    fn portal(&self, other: &Self, max_step: f32) -> Option<(Vec3, Vec3)> {
+        const EPSILON: f32 = 1e-3;
+        if (self.height - other.height).abs() > max_step {
+            return None;
+        }
+        let y = (self.height + other.height) * 0.5;
+        let touches_x = (self.max.x - other.min.x).abs() < EPSILON
+            || (other.max.x - self.min.x).abs() < EPSILON;
+        let touches_z = (self.max.y - other.min.y).abs() < EPSILON
+            || (other.max.y - self.min.y).abs() < EPSILON;
+
+        if touches_x {
+            let x = if (self.max.x - other.min.x).abs() < EPSILON {
+                self.max.x
+            } else {
+                self.min.x
+            };
+            let (z0, z1) = (self.min.y.max(other.min.y), self.max.y.min(other.max.y));
+            (z1 - z0 > EPSILON).then(|| (Vec3::new(x, y, z0), Vec3::new(x, y, z1)))
+        } else if touches_z {
+            let z = if (self.max.y - other.min.y).abs() < EPSILON {
+                self.max.y
+            } else {
+                self.min.y
+            };
+            let (x0, x1) = (self.min.x.max(other.min.x), self.max.x.min(other.max.x));
+            (x1 - x0 > EPSILON).then(|| (Vec3::new(x0, y, z), Vec3::new(x1, y, z)))
+        } else {
+            None
+        }
+    }
+}
+
+/// Reference to a polygon, valid until its tile is rebuilt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NavPolyRef {
+    /// Tile coordinate
+    pub tile: (u32, u32),
+    /// Polygon index within the tile
+    pub poly: usize,
+}
+
+/// One tile of walkable polygons
+#[derive(Debug, Clone, Default)]
+pub struct NavTile {
+    /// Walkable polygons
+    pub polys: Vec<NavPoly>,
+    /// Incremented every time the tile is replaced
+    pub revision: u32,
+}
+
+/// A finished background tile build
+struct TileResult {
+    /// Tile coordinate
+    tile: (u32, u32),
+    /// Request stamp, to drop results overtaken by a newer build
+    stamp: u64,
+    /// Built polygons
+    polys: Vec<NavPoly>,
+}
+
+/// Tiled navigation mesh with background tile rebuilds
+pub struct NavMesh {
+    /// Generation settings
+    config: NavMeshConfig,
+    /// Tiles in row-major order (X fastest)
+    tiles: Vec<NavTile>,
+    /// Stamp of the newest build request per tile
+    requested: Vec<u64>,
+    /// Stamp of the build currently in each tile
+    built: Vec<u64>,
+    /// Tiles waiting for a rebuild
+    dirty: HashSet<(u32, u32)>,
+    /// Next request stamp
+    next_stamp: u64,
+    /// Number of tile builds in flight
+    in_flight: usize,
+    /// Completed builds from worker threads
+    results: Receiver<TileResult>,
+    /// Sender cloned into worker threads
+    sender: Sender<TileResult>,
+}
+
+impl NavMesh {
+    /// Create a mesh with no walkable area
+    #[must_use]
+    pub fn new(config: NavMeshConfig) -> Self {
+        let count = (config.tiles.0 * config.tiles.1) as usize;
+        let (sender, results) = channel();
+        Self {
+            config,
+            tiles: vec![NavTile::default(); count],
+            requested: vec![0; count],
+            built: vec![0; count],
+            dirty: HashSet::new(),
+            next_stamp: 1,
+            in_flight: 0,
+            results,
+            sender,
+        }
+    }
+
+    /// Build every tile on the calling thread
+    #[must_use]
+    pub fn build(config: NavMeshConfig, world: &QueryWorld) -> Self {
+        let mut mesh = Self::new(config);
+        for z in 0..config.tiles.1 {
+            for x in 0..config.tiles.0 {
+                let index = mesh.tile_index((x, z));
+                mesh.tiles[index].polys = build_tile(&config, (x, z), world);
+            }
+        }
+        mesh
+    }
+
+    /// Get the generation settings
+    #[must_use]
+    pub const fn config(&self) -> &NavMeshConfig {
+        &self.config
+    }
+
+    /// Get a tile
+    #[must_use]
+    pub fn tile(&self, tile: (u32, u32)) -> Option<&NavTile> {
+        (tile.0 < self.config.tiles.0 && tile.1 < self.config.tiles.1)
+            .then(|| &self.tiles[self.tile_index(tile)])
+    }
+
+    /// Get a polygon
+    #[must_use]
+    pub fn poly(&self, poly: NavPolyRef) -> Option<&NavPoly> {
+        self.tile(poly.tile)?.polys.get(poly.poly)
+    }
+
+    /// Iterate over all polygons
+    pub fn polys(&self) -> impl Iterator<Item = (NavPolyRef, &NavPoly)> {
+        let width = self.config.tiles.0;
+        self.tiles
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, tile)| {
+                let coord = (index as u32 % width, index as u32 / width);
+                tile.polys
+                    .iter()
+                    .enumerate()
+                    .map(move |(poly, p)| (NavPolyRef { tile: coord, poly }, p))
+            })
+    }
+
+    /// Find the polygon under a position
+    ///
+    /// Picks the surface closest in height within the agent height, so the
+    /// right floor is chosen in multi-level geometry.
+    #[must_use]
+    pub fn find_poly(&self, position: Vec3) -> Option<NavPolyRef> {
+        let tile = self.config.tile_at(position)?;
+        let xz = Vec2::new(position.x, position.z);
+        self.tile(tile)?
+            .polys
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.contains(xz))
+            .map(|(index, p)| (index, (p.height - position.y).abs()))
+            .filter(|(_, dy)| *dy <= self.config.agent_height)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(poly, _)| NavPolyRef { tile, poly })
+    }
+
+    /// Get the polygons connected to a polygon and the shared edge with each
+    #[must_use]
+    pub fn neighbors(&self, poly: NavPolyRef) -> Vec<(NavPolyRef, (Vec3, Vec3))> {
+        let Some(source) = self.poly(poly) else {
+            return Vec::new();
+        };
+        let (tx, tz) = (poly.tile.0 as i64, poly.tile.1 as i64);
+        let mut result = Vec::new();
+        for (dx, dz) in [(0, 0), (-1, 0), (1, 0), (0, -1), (0, 1)] {
+            let (x, z) = (tx + dx, tz + dz);
+            if x < 0 || z < 0 {
+                continue;
+            }
+            let coord = (x as u32, z as u32);
+            let Some(tile) = self.tile(coord) else {
+                continue;
+            };
+            for (index, other) in tile.polys.iter().enumerate() {
+                let other_ref = NavPolyRef {
+                    tile: coord,
+                    poly: index,
+                };
+                if other_ref == poly {
+                    continue;
+                }
+                if let Some(portal) = source.portal(other, self.config.max_step) {
+                    result.push((other_ref, portal));
+                }
+            }
+        }
+        result
+    }
+
+    /// Mark tiles overlapping a world-space box for rebuilding
+    ///
+    /// The box is grown by the agent radius, since geometry near a tile
+    /// edge affects the neighboring tile's erosion.
+    pub fn mark_dirty(&mut self, min: Vec3, max: Vec3) {
+        let pad = Vec3::splat(self.config.agent_radius + self.config.cell_size);
+        let (Some(lo), Some(hi)) = (
+            self.config.tile_at(clamp_xz(&self.config, min - pad)),
+            self.config.tile_at(clamp_xz(&self.config, max + pad)),
+        ) else {
+            return;
+        };
+        for z in lo.1..=hi.1 {
+            for x in lo.0..=hi.0 {
+                self.dirty.insert((x, z));
+            }
+        }
+    }
+
+    /// Get the number of tiles waiting for a rebuild
+    #[must_use]
+    pub fn dirty_count(&self) -> usize {
+        self.dirty.len()
+    }
+
+    /// Check if tile builds are running in the background
+    #[must_use]
+    pub const fn is_rebuilding(&self) -> bool {
+        self.in_flight > 0
+    }
+
+    /// Start background builds for all dirty tiles against a snapshot
+    pub fn rebuild_dirty(&mut self, world: Arc<QueryWorld>) {
+        if self.dirty.is_empty() {
+            return;
+        }
+        let mut jobs = Vec::with_capacity(self.dirty.len());
+        for tile in self.dirty.drain() {
+            let index = (tile.1 * self.config.tiles.0 + tile.0) as usize;
+            self.requested[index] = self.next_stamp;
+            jobs.push((tile, self.next_stamp));
+            self.next_stamp += 1;
+        }
+        self.in_flight += jobs.len();
+
+        let config = self.config;
+        let sender = self.sender.clone();
+        std::thread::spawn(move || {
+            for (tile, stamp) in jobs {
+                let polys = build_tile(&config, tile, &world);
+                if sender.send(TileResult { tile, stamp, polys }).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    /// Swap finished tiles into the mesh (call each frame)
+    ///
+    /// Returns the tiles that changed. Results older than the tile's
+    /// current contents are dropped.
+    pub fn poll(&mut self) -> Vec<(u32, u32)> {
+        let mut swapped = Vec::new();
+        while let Ok(result) = self.results.try_recv() {
+            self.apply(result, &mut swapped);
+        }
+        swapped
+    }
+
+    /// Block until every background build has finished and swap them in
+    pub fn wait(&mut self) -> Vec<(u32, u32)> {
+        let mut swapped = Vec::new();
+        while self.in_flight > 0 {
+            match self.results.recv() {
+                Ok(result) => self.apply(result, &mut swapped),
+                Err(_) => break,
+            }
+        }
+        swapped
+    }
+
+    /// Check if a newer build of a tile has been requested
+    #[must_use]
+    pub fn is_stale(&self, tile: (u32, u32)) -> bool {
+        self.tile(tile).is_some_and(|_| {
+            let index = self.tile_index(tile);
+            self.requested[index] > self.built[index]
+        })
+    }
+
+    fn apply(&mut self, result: TileResult, swapped: &mut Vec<(u32, u32)>) {
+        self.in_flight = self.in_flight.saturating_sub(1);
+        let index = self.tile_index(result.tile);
+        if result.stamp < self.built[index] {
+            return;
+        }
+        self.built[index] = result.stamp;
+        let tile = &mut self.tiles[index];
+        tile.polys = result.polys;
+        tile.revision += 1;
+        swapped.push(result.tile);
+    }
+
+    fn tile_index(&self, tile: (u32, u32)) -> usize {
+        (tile.1 * self.config.tiles.0 + tile.0) as usize
+    }
+}
+
+/// Clamp a position's XZ into the covered area
+fn clamp_xz(config: &NavMeshConfig, position: Vec3) -> Vec3 {
+    let max = config.origin
+        + Vec3::new(
+            config.tiles.0 as f32 * config.tile_size - 1e-3,
+            0.0,
+            config.tiles.1 as f32 * config.tile_size - 1e-3,
+        );
+    Vec3::new(
+        position.x.clamp(config.origin.x, max.x),
+        position.y,
+        position.z.clamp(config.origin.z, max.z),
+    )
+}
+
+/// Keeps a navmesh in sync with the static colliders of a physics world
+#[derive(Debug, Default)]
+pub struct NavMeshSync {
+    /// Bounds of static colliders at the last update
+    known: HashMap<ColliderHandle, (Vec3, Vec3)>,
+}
+
+impl NavMeshSync {
+    /// Create a tracker that treats every existing collider as new
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Detect added, removed, and moved static colliders, mark the affected
+    /// tiles dirty, and start rebuilding them in the background
+    ///
+    /// Returns `true` if any rebuild was started. Call after the physics
+    /// step, then `NavMesh::poll` each frame to hot-swap finished tiles.
+    pub fn update(&mut self, physics: &Physics, navmesh: &mut NavMesh) -> bool {
+        let current: HashMap<ColliderHandle, (Vec3, Vec3)> = physics
+            .static_collider_bounds()
+            .into_iter()
+            .map(|(handle, min, max)| (handle, (min, max)))
+            .collect();
+
+        for (handle, bounds) in &current {
+            match self.known.get(handle) {
+                Some(old) if old == bounds => {}
+                Some(old) => {
+                    navmesh.mark_dirty(old.0, old.1);
+                    navmesh.mark_dirty(bounds.0, bounds.1);
+                }
+                None => navmesh.mark_dirty(bounds.0, bounds.1),
+            }
+        }
+        for (handle, old) in &self.known {
+            if !current.contains_key(handle) {
+                navmesh.mark_dirty(old.0, old.1);
+            }
+        }
+        self.known = current;
+
+        if navmesh.dirty_count() == 0 {
+            return false;
+        }
+        navmesh.rebuild_dirty(Arc::new(physics.query_world()));
+        true
+    }
+}
+
+/// Build the walkable polygons of one tile
+#[must_use]
+pub fn build_tile(config: &NavMeshConfig, tile: (u32, u32), world: &QueryWorld) -> Vec<NavPoly> {
+    let cells = config.cells_per_tile();
+    let border = (config.agent_radius / config.cell_size).ceil() as usize;
+    let span = cells + border * 2;
+    let start = config.tile_origin(tile) - Vec2::splat(border as f32 * config.cell_size);
+    let top = config.origin.y + config.height;
+
+    // Walkable surface heights per column, including a border for erosion
+    let mut columns: Vec<Vec<f32>> = Vec::with_capacity(span * span);
+    for z in 0..span {
+        for x in 0..span {
+            let xz = start + (Vec2::new(x as f32, z as f32) + 0.5) * config.cell_size;
+            let origin = Vec3::new(xz.x, top, xz.y);
+            let surfaces = world
+                .raycast_all(origin, Vec3::NEG_Y, config.height)
+                .into_iter()
+                .filter(|hit| hit.distance > 0.0)
+                .map(|hit| hit.point.y)
+                .filter(|&y| {
+                    let above = Vec3::new(xz.x, y + SURFACE_EPSILON, xz.y);
+                    world.raycast(above, Vec3::Y, config.agent_height).is_none()
+                })
+                .collect();
+            columns.push(surfaces);
+        }
+    }
+
+    // Erode: drop surfaces with a wall or ledge within the agent radius
+    let r = border as i64;
+    let reachable = |x: usize, z: usize, height: f32| {
+        columns[z * span + x]
+            .iter()
+            .any(|&h| (h - height).abs() <= config.max_step)
+    };
+    let mut layers: Vec<Vec<f32>> = Vec::with_capacity(cells * cells);
+    for z in border..border + cells {
+        for x in border..border + cells {
+            let kept = columns[z * span + x]
+                .iter()
+                .copied()
+                .filter(|&h| {
+                    (-r..=r).all(|dz| {
+                        (-r..=r).all(|dx| {
+                            dx * dx + dz * dz > r * r
+                                || reachable((x as i64 + dx) as usize, (z as i64 + dz) as usize, h)
+                        })
+                    })
+                })
+                .collect();
+            layers.push(kept);
+        }
+    }
+
+    merge_cells(config, tile, cells, &layers)
+}
+
+/// Greedily merge walkable cells of similar height into rectangles
+fn merge_cells(
+    config: &NavMeshConfig,
+    tile: (u32, u32),
+    cells: usize,
+    layers: &[Vec<f32>],
+) -> Vec<NavPoly> {
+    let mut used: Vec<Vec<bool>> = layers.iter().map(|l| vec![false; l.len()]).collect();
+    let find = |used: &[Vec<bool>], x: usize, z: usize, height: f32| {
+        let index = z * cells + x;
+        layers[index]
+            .iter()
+            .enumerate()
+            .find(|(i, h)| !used[index][*i] && (**h - height).abs() <= config.max_step)
+            .map(|(i, _)| i)
+    };
+    let origin = config.tile_origin(tile);
+    let mut polys = Vec::new();
+
+    for z in 0..cells {
+        for x in 0..cells {
+            for layer in 0..layers[z * cells + x].len() {
+                if used[z * cells + x][layer] {
+                    continue;
+                }
+                let base = layers[z * cells + x][layer];
+                used[z * cells + x][layer] = true;
+                let mut picked = vec![(x, z, layer)];
+
+                let mut width = 1;
+                while x + width < cells
+                    && let Some(l) = find(&used, x + width, z, base)
+                {
+                    used[z * cells + x + width][l] = true;
+                    picked.push((x + width, z, l));
+                    width += 1;
+                }
+
+                let mut depth = 1;
+                while z + depth < cells {
+                    let row: Option<Vec<usize>> = (x..x + width)
+                        .map(|cx| find(&used, cx, z + depth, base))
+                        .collect();
+                    let Some(row) = row else {
+                        break;
+                    };
+                    for (offset, l) in row.into_iter().enumerate() {
+                        used[(z + depth) * cells + x + offset][l] = true;
+                        picked.push((x + offset, z + depth, l));
+                    }
+                    depth += 1;
+                }
+
+                let height = picked
+                    .iter()
+                    .map(|&(cx, cz, l)| layers[cz * cells + cx][l])
+                    .sum::<f32>()
+                    / picked.len() as f32;
+                let min = origin + Vec2::new(x as f32, z as f32) * config.cell_size;
+                let max = (min + Vec2::new(width as f32, depth as f32) * config.cell_size)
+                    .min(origin + Vec2::splat(config.tile_size));
+                polys.push(NavPoly { min, max, height });
+            }
+        }
+    }
+
+    polys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Quat;
+
+    fn level() -> Physics {
+        let mut physics = Physics::new();
+        let ground = physics.create_static_body(Vec3::new(8.0, -0.5, 8.0), Quat::IDENTITY);
+        physics.add_box_collider(ground, Vec3::new(8.0, 0.5, 8.0), 1.0);
+        physics
+    }
+
+    fn config() -> NavMeshConfig {
+        NavMeshConfig::new(Vec3::new(0.0, -2.0, 0.0), (2, 2), 8.0)
+            .with_cell_size(0.5)
+            .with_height(10.0)
+    }
+
+    #[test]
+    fn test_flat_ground_is_walkable() {
+        let physics = level();
+        let mesh = NavMesh::build(config(), &physics.query_world());
+
+        let poly = mesh.find_poly(Vec3::new(4.0, 0.0, 4.0)).unwrap();
+        assert!(mesh.poly(poly).unwrap().height.abs() < 1e-3);
+        assert!(!mesh.neighbors(poly).is_empty());
+        // Eroded along the level edge
+        assert!(mesh.find_poly(Vec3::new(0.1, 0.0, 4.0)).is_none());
+    }
+
+    #[test]
+    fn test_added_collider_rebuilds_tile() {
+        let mut physics = level();
+        let mut mesh = NavMesh::new(config());
+        let mut sync = NavMeshSync::new();
+        assert!(sync.update(&physics, &mut mesh));
+        mesh.wait();
+        assert!(mesh.find_poly(Vec3::new(12.0, 0.0, 12.0)).is_some());
+        assert!(!sync.update(&physics, &mut mesh));
+
+        // A pillar blocks the ground; its top becomes a separate layer
+        let pillar = physics.create_static_body(Vec3::new(12.0, 2.0, 12.0), Quat::IDENTITY);
+        physics.add_box_collider(pillar, Vec3::new(1.0, 2.0, 1.0), 1.0);
+        assert!(sync.update(&physics, &mut mesh));
+        let swapped = mesh.wait();
+
+        assert_eq!(swapped, vec![(1, 1)]);
+        assert!(mesh.find_poly(Vec3::new(12.0, 0.0, 12.0)).is_none());
+        assert_eq!(mesh.tile((0, 0)).unwrap().revision, 1);
+    }
+}
@@ -0,0 +1,365 @@
+//! Navmesh path queries
+//!
+//! Polygon A* over a `NavMesh` with funnel string-pulling into smooth
+//! waypoint paths, plus nearest-point and random-point helpers.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use glam::{Vec2, Vec3};
+use rustc_hash::FxHashMap;
+
+use super::navmesh::{NavMesh, NavPoly, NavPolyRef};
+
+/// Random point attempts before giving up on a circle
+const RANDOM_ATTEMPTS: usize = 16;
+
+/// A* node for the polygon search
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    /// Polygon
+    poly: NavPolyRef,
+    /// Cost from the start
+    g_cost: f32,
+    /// g_cost + heuristic
+    f_cost: f32,
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.poly == other.poly
+    }
+}
+
+impl Eq for Node {}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reverse for min-heap
+        other.f_cost.total_cmp(&self.f_cost)
+    }
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Closest point of a polygon to a position
+fn closest_on_poly(poly: &NavPoly, position: Vec3) -> Vec3 {
+    let xz = Vec2::new(position.x, position.z).clamp(poly.min, poly.max);
+    Vec3::new(xz.x, poly.height, xz.y)
+}
+
+/// Twice the signed XZ area of the triangle (a, b, c)
+fn triarea2(a: Vec3, b: Vec3, c: Vec3) -> f32 {
+    let (ax, az) = (b.x - a.x, b.z - a.z);
+    let (bx, bz) = (c.x - a.x, c.z - a.z);
+    bx * az - ax * bz
+}
+
+/// Shorten a corridor of (left, right) portals into corner waypoints
+///
+/// The first and last portals are the start and end points.
+fn string_pull(portals: &[(Vec3, Vec3)]) -> Vec<Vec3> {
+    const EPSILON: f32 = 1e-6;
+    let Some(&(start, _)) = portals.first() else {
+        return Vec::new();
+    };
+    let mut path = vec![start];
+    let (mut apex, mut left, mut right) = (start, start, start);
+    let (mut left_index, mut right_index) = (0, 0);
+
+    let mut i = 1;
+    while i < portals.len() {
+        let (portal_left, portal_right) = portals[i];
+
+        // Tighten the right side
+        if triarea2(apex, right, portal_right) <= 0.0 {
+            if apex.distance_squared(right) < EPSILON || triarea2(apex, left, portal_right) > 0.0 {
+                right = portal_right;
+                right_index = i;
+            } else {
+                // Right crossed left: the left point is a corner
+                path.push(left);
+                apex = left;
+                let apex_index = left_index;
+                (left, right) = (apex, apex);
+                (left_index, right_index) = (apex_index, apex_index);
+                i = apex_index + 1;
+                continue;
+            }
+        }
+
+        // Tighten the left side
+        if triarea2(apex, left, portal_left) >= 0.0 {
+            if apex.distance_squared(left) < EPSILON || triarea2(apex, right, portal_left) < 0.0 {
+                left = portal_left;
+                left_index = i;
+            } else {
+                path.push(right);
+                apex = right;
+                let apex_index = right_index;
+                (left, right) = (apex, apex);
+                (left_index, right_index) = (apex_index, apex_index);
+                i = apex_index + 1;
+                continue;
+            }
+        }
+        i += 1;
+    }
+
+    let Some(&(end, _)) = portals.last() else {
+        return path;
+    };
+    if path
+        .last()
+        .is_none_or(|last| last.distance_squared(end) > EPSILON)
+    {
+        path.push(end);
+    }
+    path
+}
+
+impl NavMesh {
+    /// Find a smooth path between two positions
+    ///
+    /// Both ends are snapped onto the mesh (within the agent height). The
+    /// path starts and ends at the snapped points and only turns at
+    /// polygon corners. Returns `None` if either end is off the mesh or no
+    /// connection exists.
+    #[must_use]
+    pub fn find_path(&self, start: Vec3, end: Vec3) -> Option<Vec<Vec3>> {
+        let reach = self.config().agent_height;
+        let (start_poly, start) = self.nearest_poly(start, reach)?;
+        let (end_poly, end) = self.nearest_poly(end, reach)?;
+        let corridor = self.find_corridor(start_poly, start, end_poly, end)?;
+
+        // Orient each portal as (left, right) seen from the previous polygon
+        let mut portals = vec![(start, start)];
+        for pair in corridor.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            let (_, (a, b)) = self.neighbors(from).into_iter().find(|(p, _)| *p == to)?;
+            let center = self.poly(from)?.center();
+            portals.push(if triarea2(center, a, b) > 0.0 {
+                (a, b)
+            } else {
+                (b, a)
+            });
+        }
+        portals.push((end, end));
+        Some(string_pull(&portals))
+    }
+
+    /// Polygon A* from start to end, returning the polygon corridor
+    fn find_corridor(
+        &self,
+        start_poly: NavPolyRef,
+        start: Vec3,
+        end_poly: NavPolyRef,
+        end: Vec3,
+    ) -> Option<Vec<NavPolyRef>> {
+        let mut open_set = BinaryHeap::new();
+        // Per polygon: best cost, entry point, and the polygon it came from
+        let mut visited: FxHashMap<NavPolyRef, (f32, Vec3, Option<NavPolyRef>)> =
+            FxHashMap::default();
+        visited.insert(start_poly, (0.0, start, None));
+        open_set.push(Node {
+            poly: start_poly,
+            g_cost: 0.0,
+            f_cost: start.distance(end),
+        });
+
+        while let Some(current) = open_set.pop() {
+            let (best, entry, _) = visited[&current.poly];
+            if current.g_cost > best {
+                continue;
+            }
+            if current.poly == end_poly {
+                let mut corridor = vec![end_poly];
+                let mut poly = end_poly;
+                while let Some(previous) = visited[&poly].2 {
+                    corridor.push(previous);
+                    poly = previous;
+                }
+                corridor.reverse();
+                return Some(corridor);
+            }
+
+            for (neighbor, (a, b)) in self.neighbors(current.poly) {
+                let midpoint = (a + b) * 0.5;
+                let mut g_cost = current.g_cost + entry.distance(midpoint);
+                if neighbor == end_poly {
+                    g_cost += midpoint.distance(end);
+                }
+                if visited
+                    .get(&neighbor)
+                    .is_some_and(|(cost, ..)| *cost <= g_cost)
+                {
+                    continue;
+                }
+                visited.insert(neighbor, (g_cost, midpoint, Some(current.poly)));
+                open_set.push(Node {
+                    poly: neighbor,
+                    g_cost,
+                    f_cost: g_cost + midpoint.distance(end),
+                });
+            }
+        }
+        None
+    }
+
+    /// Find the polygon and point on the mesh closest to a position
+    ///
+    /// Only polygons within `max_distance` are considered.
+    #[must_use]
+    pub fn nearest_poly(&self, position: Vec3, max_distance: f32) -> Option<(NavPolyRef, Vec3)> {
+        if let Some(poly) = self.find_poly(position) {
+            let point = closest_on_poly(self.poly(poly)?, position);
+            return Some((poly, point));
+        }
+        self.polys()
+            .map(|(poly, p)| (poly, closest_on_poly(p, position)))
+            .map(|(poly, point)| (poly, point, point.distance_squared(position)))
+            .filter(|(.., d)| *d <= max_distance * max_distance)
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+            .map(|(poly, point, _)| (poly, point))
+    }
+
+    /// Find the point on the mesh closest to a position
+    #[must_use]
+    pub fn nearest_point_on_mesh(&self, position: Vec3, max_distance: f32) -> Option<Vec3> {
+        self.nearest_poly(position, max_distance)
+            .map(|(_, point)| point)
+    }
+
+    /// Pick a uniformly distributed random point on the mesh
+    ///
+    /// `random` returns values in [0, 1).
+    pub fn random_point(&self, mut random: impl FnMut() -> f32) -> Option<Vec3> {
+        let polys: Vec<&NavPoly> = self.polys().map(|(_, p)| p).collect();
+        let areas: Vec<f32> = polys
+            .iter()
+            .map(|p| {
+                let size = p.max - p.min;
+                size.x * size.y
+            })
+            .collect();
+        let index = pick_weighted(&areas, random())?;
+        let poly = polys[index];
+        let xz = poly.min + (poly.max - poly.min) * Vec2::new(random(), random());
+        Some(Vec3::new(xz.x, poly.height, xz.y))
+    }
+
+    /// Pick a random point on the mesh within `radius` (in XZ) of a position
+    ///
+    /// `random` returns values in [0, 1). Returns `None` if no mesh lies
+    /// within the circle.
+    pub fn random_point_around(
+        &self,
+        center: Vec3,
+        radius: f32,
+        mut random: impl FnMut() -> f32,
+    ) -> Option<Vec3> {
+        let center_xz = Vec2::new(center.x, center.z);
+        let (lo, hi) = (
+            center_xz - Vec2::splat(radius),
+            center_xz + Vec2::splat(radius),
+        );
+        let candidates: Vec<(Vec2, Vec2, f32)> = self
+            .polys()
+            .map(|(_, p)| (p.min.max(lo), p.max.min(hi), p.height))
+            .filter(|(min, max, height)| {
+                min.cmplt(*max).all() && (height - center.y).abs() <= self.config().height
+            })
+            .collect();
+        let areas: Vec<f32> = candidates
+            .iter()
+            .map(|(min, max, _)| (max.x - min.x) * (max.y - min.y))
+            .collect();
+
+        for _ in 0..RANDOM_ATTEMPTS {
+            let (min, max, height) = candidates[pick_weighted(&areas, random())?];
+            let xz = min + (max - min) * Vec2::new(random(), random());
+            if xz.distance_squared(center_xz) <= radius * radius {
+                return Some(Vec3::new(xz.x, height, xz.y));
+            }
+        }
+        None
+    }
+}
+
+/// Pick an index with probability proportional to its weight
+fn pick_weighted(weights: &[f32], random: f32) -> Option<usize> {
+    let total: f32 = weights.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut remaining = random.clamp(0.0, 1.0) * total;
+    for (index, &weight) in weights.iter().enumerate() {
+        if remaining < weight {
+            return Some(index);
+        }
+        remaining -= weight;
+    }
+    weights.iter().rposition(|&w| w > 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::NavMeshConfig;
+    use crate::physics::Physics;
+    use glam::Quat;
+
+    #[test]
+    fn test_path_bends_around_wall() {
+        let mut physics = Physics::new();
+        let ground = physics.create_static_body(Vec3::new(8.0, -0.5, 8.0), Quat::IDENTITY);
+        physics.add_box_collider(ground, Vec3::new(8.0, 0.5, 8.0), 1.0);
+        // Wall across the middle with a gap at high Z
+        let wall = physics.create_static_body(Vec3::new(8.0, 2.0, 5.0), Quat::IDENTITY);
+        physics.add_box_collider(wall, Vec3::new(0.5, 2.0, 5.0), 1.0);
+
+        let config = NavMeshConfig::new(Vec3::new(0.0, -2.0, 0.0), (2, 2), 8.0)
+            .with_cell_size(0.5)
+            .with_height(10.0);
+        let mesh = NavMesh::build(config, &physics.query_world());
+
+        let (start, end) = (Vec3::new(4.0, 0.0, 2.0), Vec3::new(12.0, 0.0, 2.0));
+        let path = mesh.find_path(start, end).unwrap();
+        assert!(path.first().unwrap().distance(start) < 1e-3);
+        assert!(path.last().unwrap().distance(end) < 1e-3);
+        // Must detour past the end of the wall
+        assert!(path.iter().any(|p| p.z > 10.0));
+        assert!(
+            path.iter()
+                .all(|p| mesh.nearest_point_on_mesh(*p, 0.01).is_some())
+        );
+
+        let mut seed = 0.1f32;
+        let point = mesh
+            .random_point(|| {
+                seed = (seed * 7.31 + 0.17).fract();
+                seed
+            })
+            .unwrap();
+        assert!(mesh.nearest_point_on_mesh(point, 1e-3).is_some());
+    }
+
+    #[test]
+    fn test_string_pull_straight_corridor() {
+        // Two wide portals on a straight line produce no corners
+        let portals = [
+            (Vec3::ZERO, Vec3::ZERO),
+            (Vec3::new(-1.0, 0.0, 1.0), Vec3::new(1.0, 0.0, 1.0)),
+            (Vec3::new(-1.0, 0.0, 2.0), Vec3::new(1.0, 0.0, 2.0)),
+            (Vec3::new(0.0, 0.0, 3.0), Vec3::new(0.0, 0.0, 3.0)),
+        ];
+        assert_eq!(
+            string_pull(&portals),
+            vec![Vec3::ZERO, Vec3::new(0.0, 0.0, 3.0)]
+        );
+    }
+}
@@ -4,6 +4,7 @@
 
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
+use std::fmt;
 
 use glam::Vec2;
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -19,6 +20,10 @@ pub struct Grid {
     pub cell_size: f32,
     /// Walkable cells (true = walkable)
     cells: Vec<bool>,
+    /// Cost of entering each cell (at least 1)
+    costs: Vec<f32>,
+    /// Cells changed since the last `take_dirty`
+    dirty: Option<GridRegion>,
     /// World origin offset
     pub origin: Vec2,
 }
@@ -32,6 +37,8 @@ impl Grid {
             height,
             cell_size,
             cells: vec![true; width * height],
+            costs: vec![1.0; width * height],
+            dirty: None,
             origin: Vec2::ZERO,
         }
     }
@@ -39,10 +46,66 @@ impl Grid {
     /// Set a cell's walkability
     pub fn set_walkable(&mut self, x: usize, y: usize, walkable: bool) {
         if x < self.width && y < self.height {
-            self.cells[y * self.width + x] = walkable;
+            let index = y * self.width + x;
+            if self.cells[index] != walkable {
+                self.cells[index] = walkable;
+                self.mark_dirty(GridRegion::cell(x, y));
+            }
+        }
+    }
+
+    /// Set the walkability of every cell in a region, e.g. a door or a building footprint
+    pub fn set_region_walkable(&mut self, region: GridRegion, walkable: bool) {
+        for (x, y) in region.cells() {
+            self.set_walkable(x, y, walkable);
+        }
+    }
+
+    /// Set the cost of entering a cell
+    ///
+    /// Costs below 1 are raised to 1 so the distance heuristic stays admissible.
+    pub fn set_cost(&mut self, x: usize, y: usize, cost: f32) {
+        if x < self.width && y < self.height {
+            let index = y * self.width + x;
+            let cost = cost.max(1.0);
+            if self.costs[index] != cost {
+                self.costs[index] = cost;
+                self.mark_dirty(GridRegion::cell(x, y));
+            }
+        }
+    }
+
+    /// Set the cost of every cell in a region, e.g. mud or a road
+    pub fn set_region_cost(&mut self, region: GridRegion, cost: f32) {
+        for (x, y) in region.cells() {
+            self.set_cost(x, y, cost);
         }
     }
 
+    /// Cost of entering a cell (1 for open ground, infinite outside the grid)
+    #[must_use]
+    pub fn cost(&self, x: usize, y: usize) -> f32 {
+        if x >= self.width || y >= self.height {
+            return f32::INFINITY;
+        }
+        self.costs[y * self.width + x]
+    }
+
+    /// Bounding region of all cells changed since the last [`Grid::take_dirty`]
+    #[must_use]
+    pub fn dirty_region(&self) -> Option<GridRegion> {
+        self.dirty
+    }
+
+    /// Take and clear the changed region
+    pub fn take_dirty(&mut self) -> Option<GridRegion> {
+        self.dirty.take()
+    }
+
+    fn mark_dirty(&mut self, region: GridRegion) {
+        self.dirty = Some(self.dirty.map_or(region, |dirty| dirty.union(region)));
+    }
+
     /// Check if a cell is walkable
     #[must_use]
     pub fn is_walkable(&self, x: usize, y: usize) -> bool {
@@ -73,7 +136,7 @@ impl Grid {
     }
 
     /// Get neighbors of a cell (4-directional)
-    fn neighbors(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+    pub(super) fn neighbors(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
         let mut result = Vec::with_capacity(4);
 
         if x > 0 && self.is_walkable(x - 1, y) {
@@ -93,6 +156,61 @@ impl Grid {
     }
 }
 
+/// Inclusive rectangle of grid cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridRegion {
+    /// Lowest cell X
+    pub min_x: usize,
+    /// Lowest cell Y
+    pub min_y: usize,
+    /// Highest cell X
+    pub max_x: usize,
+    /// Highest cell Y
+    pub max_y: usize,
+}
+
+impl GridRegion {
+    /// Create a region from two corner cells in any order
+    #[must_use]
+    pub fn new(a: (usize, usize), b: (usize, usize)) -> Self {
+        Self {
+            min_x: a.0.min(b.0),
+            min_y: a.1.min(b.1),
+            max_x: a.0.max(b.0),
+            max_y: a.1.max(b.1),
+        }
+    }
+
+    /// Region covering a single cell
+    #[must_use]
+    pub fn cell(x: usize, y: usize) -> Self {
+        Self::new((x, y), (x, y))
+    }
+
+    /// Check if a cell lies in the region
+    #[must_use]
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x >= self.min_x && x <= self.max_x && y >= self.min_y && y <= self.max_y
+    }
+
+    /// Smallest region covering both
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            min_x: self.min_x.min(other.min_x),
+            min_y: self.min_y.min(other.min_y),
+            max_x: self.max_x.max(other.max_x),
+            max_y: self.max_y.max(other.max_y),
+        }
+    }
+
+    /// Iterate over the cells of the region, row by row
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> + use<> {
+        let (min_x, max_x) = (self.min_x, self.max_x);
+        (self.min_y..=self.max_y).flat_map(move |y| (min_x..=max_x).map(move |x| (x, y)))
+    }
+}
+
 /// Result of pathfinding
 #[derive(Debug, Clone)]
 pub struct PathResult {
@@ -108,6 +226,15 @@ impl PathResult {
     pub fn is_empty(&self) -> bool {
         self.waypoints.is_empty()
     }
+
+    /// Check if any waypoint lies in a changed region of the grid
+    #[must_use]
+    pub fn crosses(&self, grid: &Grid, region: &GridRegion) -> bool {
+        self.waypoints.iter().any(|&point| {
+            let (x, y) = grid.world_to_grid(point);
+            x >= 0 && y >= 0 && region.contains(x as usize, y as usize)
+        })
+    }
 }
 
 impl Default for PathResult {
@@ -119,6 +246,113 @@ impl Default for PathResult {
     }
 }
 
+/// Callback invoked with the key of each invalidated path
+type InvalidateCallback<K> = Box<dyn FnMut(K) + Send + Sync>;
+
+/// Tracks agents' current paths and reports which ones a grid change crosses
+///
+/// Agents whose path is unaffected by a door closing or a building being
+/// placed keep their path; only the reported ones need to replan.
+pub struct PathWatcher<K> {
+    /// Watched paths in registration order
+    paths: Vec<(K, PathResult)>,
+    /// Called for every invalidated path
+    on_invalidate: Option<InvalidateCallback<K>>,
+}
+
+impl<K: Copy + PartialEq> PathWatcher<K> {
+    /// Create an empty watcher
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            paths: Vec::new(),
+            on_invalidate: None,
+        }
+    }
+
+    /// Set a callback invoked for every invalidated path
+    pub fn on_invalidate(&mut self, callback: impl FnMut(K) + Send + Sync + 'static) {
+        self.on_invalidate = Some(Box::new(callback));
+    }
+
+    /// Watch a path, replacing any previous path for the same key
+    pub fn watch(&mut self, key: K, path: PathResult) {
+        if let Some(entry) = self.paths.iter_mut().find(|(k, _)| *k == key) {
+            entry.1 = path;
+        } else {
+            self.paths.push((key, path));
+        }
+    }
+
+    /// Stop watching a path
+    pub fn unwatch(&mut self, key: K) -> Option<PathResult> {
+        let index = self.paths.iter().position(|(k, _)| *k == key)?;
+        Some(self.paths.remove(index).1)
+    }
+
+    /// Watched path for a key
+    #[must_use]
+    pub fn path(&self, key: K) -> Option<&PathResult> {
+        self.paths
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, path)| path)
+    }
+
+    /// Number of watched paths
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Whether no paths are watched
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Consume the grid's dirty region and invalidate the paths crossing it
+    pub fn process(&mut self, grid: &mut Grid) -> Vec<K> {
+        match grid.take_dirty() {
+            Some(region) => self.invalidate(grid, &region),
+            None => Vec::new(),
+        }
+    }
+
+    /// Stop watching and report every path crossing `region`
+    pub fn invalidate(&mut self, grid: &Grid, region: &GridRegion) -> Vec<K> {
+        let mut invalidated = Vec::new();
+        self.paths.retain(|(key, path)| {
+            let crosses = path.crosses(grid, region);
+            if crosses {
+                invalidated.push(*key);
+            }
+            !crosses
+        });
+        if let Some(callback) = &mut self.on_invalidate {
+            for &key in &invalidated {
+                callback(key);
+            }
+        }
+        invalidated
+    }
+}
+
+impl<K: Copy + PartialEq> Default for PathWatcher<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: fmt::Debug> fmt::Debug for PathWatcher<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PathWatcher")
+            .field("paths", &self.paths)
+            .field("on_invalidate", &self.on_invalidate.is_some())
+            .finish()
+    }
+}
+
 /// A* node for priority queue
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -229,7 +463,8 @@ pub fn find_path(grid: &Grid, start: Vec2, goal: Vec2) -> PathResult {
         }
 
         for (nx, ny) in grid.neighbors(current.x, current.y) {
-            let tentative_g = g_score.get(&(current.x, current.y)).unwrap_or(&f32::MAX) + 1.0;
+            let tentative_g =
+                g_score.get(&(current.x, current.y)).unwrap_or(&f32::MAX) + grid.cost(nx, ny);
 
             if tentative_g < *g_score.get(&(nx, ny)).unwrap_or(&f32::MAX) {
                 came_from.insert((nx, ny), (current.x, current.y));
@@ -254,7 +489,7 @@ pub fn find_path(grid: &Grid, start: Vec2, goal: Vec2) -> PathResult {
 }
 
 /// Calculate total path length
-fn calculate_path_length(waypoints: &[Vec2]) -> f32 {
+pub(super) fn calculate_path_length(waypoints: &[Vec2]) -> f32 {
     let mut length = 0.0;
     for i in 1..waypoints.len() {
         length += waypoints[i].distance(waypoints[i - 1]);
@@ -291,6 +526,43 @@ mod tests {
         assert_eq!(path.waypoints.len(), 4); // 4 cells in a line
     }
 
+    #[test]
+    fn test_costs_and_invalidation() {
+        let mut grid = Grid::new(10, 10, 1.0);
+        // Expensive swamp on the direct route
+        grid.set_region_cost(GridRegion::new((3, 0), (6, 3)), 10.0);
+        assert_eq!(grid.dirty_region(), Some(GridRegion::new((3, 0), (6, 3))));
+        grid.take_dirty();
+
+        let path = find_path(&grid, Vec2::new(0.5, 0.5), Vec2::new(9.5, 0.5));
+        assert!(
+            path.waypoints
+                .iter()
+                .all(|p| p.y > 4.0 || p.x < 3.0 || p.x > 7.0)
+        );
+
+        let mut watcher = PathWatcher::new();
+        let invalidated = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = invalidated.clone();
+        watcher.on_invalidate(move |key| log.lock().unwrap().push(key));
+        watcher.watch(1, path);
+        watcher.watch(
+            2,
+            find_path(&grid, Vec2::new(0.5, 9.5), Vec2::new(9.5, 9.5)),
+        );
+
+        // Unchanged cells do not mark the grid dirty
+        grid.set_walkable(5, 9, true);
+        assert!(watcher.process(&mut grid).is_empty());
+
+        // A door closing across the top row only affects the second path
+        grid.set_region_walkable(GridRegion::new((5, 8), (5, 9)), false);
+        assert_eq!(watcher.process(&mut grid), vec![2]);
+        assert_eq!(*invalidated.lock().unwrap(), vec![2]);
+        assert!(watcher.path(1).is_some());
+        assert!(grid.dirty_region().is_none());
+    }
+
     #[test]
     fn test_no_path() {
         let mut grid = Grid::new(5, 5, 1.0);
@@ -0,0 +1,280 @@
+//! Patrol paths and in-game path recording
+//!
+//! Lets designers drive a path in-game, simplify it, and save it as a
+//! spline asset usable for patrols and camera rails.
+
+use std::fs;
+use std::path::Path;
+
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+use crate::core::SceneError;
+
+/// A Catmull-Rom spline through control points
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PatrolPath {
+    /// Path name
+    pub name: String,
+    /// Control points the spline passes through
+    pub points: Vec<Vec3>,
+    /// Whether the path loops back to the first point
+    pub looped: bool,
+}
+
+impl PatrolPath {
+    /// Create a path from control points
+    #[must_use]
+    pub fn new(name: impl Into<String>, points: Vec<Vec3>, looped: bool) -> Self {
+        Self {
+            name: name.into(),
+            points,
+            looped,
+        }
+    }
+
+    /// Get the number of spline segments
+    #[must_use]
+    pub fn segment_count(&self) -> usize {
+        match self.points.len() {
+            0 | 1 => 0,
+            n if self.looped => n,
+            n => n - 1,
+        }
+    }
+
+    /// Sample the spline at a parameter in `[0, segment_count]`
+    #[must_use]
+    pub fn sample(&self, t: f32) -> Vec3 {
+        let count = self.points.len();
+        if count == 0 {
+            return Vec3::ZERO;
+        }
+        let segments = self.segment_count();
+        if segments == 0 {
+            return self.points[0];
+        }
+
+        let t = if self.looped {
+            t.rem_euclid(segments as f32)
+        } else {
+            t.clamp(0.0, segments as f32)
+        };
+        let segment = (t.floor() as usize).min(segments - 1);
+        let local_t = t - segment as f32;
+
+        let point = |i: isize| -> Vec3 {
+            if self.looped {
+                self.points[i.rem_euclid(count as isize) as usize]
+            } else {
+                self.points[i.clamp(0, count as isize - 1) as usize]
+            }
+        };
+
+        let i = segment as isize;
+        catmull_rom(point(i - 1), point(i), point(i + 1), point(i + 2), local_t)
+    }
+
+    /// Approximate the spline with evenly spaced samples per segment
+    #[must_use]
+    pub fn tessellate(&self, samples_per_segment: usize) -> Vec<Vec3> {
+        let samples_per_segment = samples_per_segment.max(1);
+        let total = self.segment_count() * samples_per_segment;
+        (0..=total)
+            .map(|i| self.sample(i as f32 / samples_per_segment as f32))
+            .collect()
+    }
+
+    /// Approximate total length of the spline
+    #[must_use]
+    pub fn length(&self) -> f32 {
+        self.tessellate(16)
+            .windows(2)
+            .map(|pair| pair[0].distance(pair[1]))
+            .sum()
+    }
+
+    /// Save the path to a RON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written or serialization fails
+    pub fn save_ron(&self, path: impl AsRef<Path>) -> Result<(), SceneError> {
+        let ron_string = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| SceneError::SerializeError(e.to_string()))?;
+        fs::write(path, ron_string).map_err(|e| SceneError::IoError(e.to_string()))
+    }
+
+    /// Load a path from a RON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or deserialization fails
+    pub fn load_ron(path: impl AsRef<Path>) -> Result<Self, SceneError> {
+        let content = fs::read_to_string(path).map_err(|e| SceneError::IoError(e.to_string()))?;
+        ron::from_str(&content).map_err(|e| SceneError::DeserializeError(e.to_string()))
+    }
+}
+
+/// Evaluate a uniform Catmull-Rom segment between `p1` and `p2`
+fn catmull_rom(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * ((2.0 * p1)
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+/// Records an entity's positions over time
+#[derive(Debug, Clone)]
+pub struct PathRecorder {
+    /// Recorded samples
+    samples: Vec<Vec3>,
+    /// Minimum distance between recorded samples
+    pub min_distance: f32,
+    /// Whether recording is active
+    recording: bool,
+}
+
+impl PathRecorder {
+    /// Create a recorder that keeps samples at least `min_distance` apart
+    #[must_use]
+    pub fn new(min_distance: f32) -> Self {
+        Self {
+            samples: Vec::new(),
+            min_distance,
+            recording: false,
+        }
+    }
+
+    /// Start recording (clears previous samples)
+    pub fn start(&mut self) {
+        self.samples.clear();
+        self.recording = true;
+    }
+
+    /// Stop recording
+    pub fn stop(&mut self) {
+        self.recording = false;
+    }
+
+    /// Check if recording is active
+    #[must_use]
+    pub const fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Record a position (call each frame while recording)
+    pub fn record(&mut self, position: Vec3) {
+        if !self.recording {
+            return;
+        }
+        let far_enough = self
+            .samples
+            .last()
+            .is_none_or(|last| last.distance(position) >= self.min_distance);
+        if far_enough {
+            self.samples.push(position);
+        }
+    }
+
+    /// Get the raw recorded samples
+    #[must_use]
+    pub fn samples(&self) -> &[Vec3] {
+        &self.samples
+    }
+
+    /// Simplify the recording into a spline path
+    ///
+    /// Points closer than `tolerance` to the simplified polyline are removed.
+    #[must_use]
+    pub fn to_path(&self, name: impl Into<String>, tolerance: f32, looped: bool) -> PatrolPath {
+        PatrolPath::new(name, simplify(&self.samples, tolerance), looped)
+    }
+}
+
+impl Default for PathRecorder {
+    fn default() -> Self {
+        Self::new(0.25)
+    }
+}
+
+/// Ramer-Douglas-Peucker polyline simplification
+#[must_use]
+pub fn simplify(points: &[Vec3], tolerance: f32) -> Vec<Vec3> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((start, end)) = stack.pop() {
+        let mut max_distance = 0.0;
+        let mut max_index = start;
+        for (i, &point) in points.iter().enumerate().take(end).skip(start + 1) {
+            let distance = distance_to_segment(point, points[start], points[end]);
+            if distance > max_distance {
+                max_distance = distance;
+                max_index = i;
+            }
+        }
+
+        if max_distance > tolerance {
+            keep[max_index] = true;
+            stack.push((start, max_index));
+            stack.push((max_index, end));
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(&p, k)| k.then_some(p))
+        .collect()
+}
+
+/// Distance from a point to a line segment
+fn distance_to_segment(point: Vec3, a: Vec3, b: Vec3) -> f32 {
+    let ab = b - a;
+    let length_sq = ab.length_squared();
+    if length_sq <= f32::EPSILON {
+        return point.distance(a);
+    }
+    let t = ((point - a).dot(ab) / length_sq).clamp(0.0, 1.0);
+    point.distance(a + ab * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simplify_straight_line() {
+        let points: Vec<Vec3> = (0..10).map(|i| Vec3::new(i as f32, 0.0, 0.0)).collect();
+        let simplified = simplify(&points, 0.01);
+
+        assert_eq!(simplified, vec![Vec3::ZERO, Vec3::new(9.0, 0.0, 0.0)]);
+    }
+
+    #[test]
+    fn test_recorder_to_path() {
+        let mut recorder = PathRecorder::new(0.5);
+        recorder.start();
+        for i in 0..20 {
+            let x = i as f32 * 0.1;
+            recorder.record(Vec3::new(x, 0.0, if x > 1.0 { x - 1.0 } else { 0.0 }));
+        }
+        recorder.stop();
+
+        let path = recorder.to_path("patrol", 0.05, false);
+        assert_eq!(path.points.len(), 3); // Start, corner, end
+
+        // Spline passes through its control points
+        assert!((path.sample(0.0) - path.points[0]).length() < 0.001);
+        assert!((path.sample(2.0) - path.points[2]).length() < 0.001);
+    }
+}
@@ -4,6 +4,8 @@
 
 use glam::Vec3;
 
+use super::PathResult;
+
 /// Output from a steering behavior
 #[derive(Debug, Clone, Copy, Default)]
 pub struct SteeringOutput {
@@ -228,6 +230,255 @@ impl SteeringBehavior for Wander {
     }
 }
 
+/// Follow path behavior - steer along waypoints and arrive at the last one
+///
+/// The agent seeks a point `lookahead` units further along the path than its
+/// own projection onto it, so corners are cut smoothly. Within `lookahead` of
+/// the end it switches to [`Arrive`]. Call [`FollowPath::update`] each frame
+/// so progress only moves forward along the path.
+#[derive(Debug, Clone)]
+pub struct FollowPath {
+    /// Waypoints in world coordinates
+    waypoints: Vec<Vec3>,
+    /// Path distance at each waypoint
+    distances: Vec<f32>,
+    /// First segment considered when projecting onto the path
+    segment: usize,
+    /// Distance along the path to seek ahead of the agent
+    pub lookahead: f32,
+    /// Maximum acceleration
+    pub max_acceleration: f32,
+    /// Maximum speed
+    pub max_speed: f32,
+    /// Slowing distance at the end of the path
+    pub slow_radius: f32,
+    /// Stopping distance at the end of the path
+    pub target_radius: f32,
+}
+
+impl FollowPath {
+    /// Create a new follow path behavior
+    #[must_use]
+    pub fn new(waypoints: Vec<Vec3>, max_acceleration: f32, max_speed: f32) -> Self {
+        let mut distances = Vec::with_capacity(waypoints.len());
+        let mut total = 0.0;
+        for (i, point) in waypoints.iter().enumerate() {
+            if i > 0 {
+                total += point.distance(waypoints[i - 1]);
+            }
+            distances.push(total);
+        }
+        Self {
+            waypoints,
+            distances,
+            segment: 0,
+            lookahead: 1.0,
+            max_acceleration,
+            max_speed,
+            slow_radius: 3.0,
+            target_radius: 0.2,
+        }
+    }
+
+    /// Follow a grid path; grid `y` maps to world Z at the given height
+    #[must_use]
+    pub fn from_grid_path(
+        path: &PathResult,
+        height: f32,
+        max_acceleration: f32,
+        max_speed: f32,
+    ) -> Self {
+        let waypoints = path
+            .waypoints
+            .iter()
+            .map(|p| Vec3::new(p.x, height, p.y))
+            .collect();
+        Self::new(waypoints, max_acceleration, max_speed)
+    }
+
+    /// Set the look-ahead distance
+    #[must_use]
+    pub fn with_lookahead(mut self, lookahead: f32) -> Self {
+        self.lookahead = lookahead.max(0.0);
+        self
+    }
+
+    /// Set the arrival slowing and stopping distances
+    #[must_use]
+    pub fn with_arrival(mut self, slow_radius: f32, target_radius: f32) -> Self {
+        self.slow_radius = slow_radius;
+        self.target_radius = target_radius;
+        self
+    }
+
+    /// Waypoints being followed
+    #[must_use]
+    pub fn waypoints(&self) -> &[Vec3] {
+        &self.waypoints
+    }
+
+    /// Total path length
+    #[must_use]
+    pub fn length(&self) -> f32 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    /// Index of the segment the agent is currently on
+    #[must_use]
+    pub fn current_segment(&self) -> usize {
+        self.segment
+    }
+
+    /// Advance progress along the path to the agent's projection
+    pub fn update(&mut self, position: Vec3) {
+        self.segment = self.project(position).0;
+    }
+
+    /// Restart from the first waypoint
+    pub fn reset(&mut self) {
+        self.segment = 0;
+    }
+
+    /// Check if the agent has reached the end of the path
+    #[must_use]
+    pub fn is_finished(&self, position: Vec3) -> bool {
+        self.waypoints
+            .last()
+            .is_none_or(|end| end.distance(position) <= self.target_radius)
+    }
+
+    /// Distance along the path of the agent's projection
+    #[must_use]
+    pub fn progress(&self, position: Vec3) -> f32 {
+        self.project(position).1
+    }
+
+    /// Point at a distance along the path, clamped to its ends
+    #[must_use]
+    pub fn point_at(&self, distance: f32) -> Vec3 {
+        let Some(&last) = self.waypoints.last() else {
+            return Vec3::ZERO;
+        };
+        let next = self.distances.partition_point(|&d| d <= distance);
+        if next == 0 {
+            return self.waypoints[0];
+        }
+        if next >= self.waypoints.len() {
+            return last;
+        }
+        let (start, end) = (self.distances[next - 1], self.distances[next]);
+        let t = (distance - start) / (end - start).max(f32::EPSILON);
+        self.waypoints[next - 1].lerp(self.waypoints[next], t)
+    }
+
+    /// Closest segment at or after the current one, and the path distance there
+    fn project(&self, position: Vec3) -> (usize, f32) {
+        let mut best = (
+            self.segment,
+            self.distances.get(self.segment).copied().unwrap_or(0.0),
+        );
+        let mut best_distance = f32::MAX;
+        for i in self.segment..self.waypoints.len().saturating_sub(1) {
+            let (a, b) = (self.waypoints[i], self.waypoints[i + 1]);
+            let ab = b - a;
+            let t =
+                ((position - a).dot(ab) / ab.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+            let distance = position.distance_squared(a + ab * t);
+            if distance < best_distance {
+                best_distance = distance;
+                best = (
+                    i,
+                    self.distances[i] + t * (self.distances[i + 1] - self.distances[i]),
+                );
+            }
+        }
+        best
+    }
+}
+
+impl SteeringBehavior for FollowPath {
+    fn calculate(&self, position: Vec3, velocity: Vec3) -> SteeringOutput {
+        let Some(&end) = self.waypoints.last() else {
+            return SteeringOutput::ZERO;
+        };
+        let ahead = self.progress(position) + self.lookahead;
+        if ahead >= self.length() {
+            let mut arrive = Arrive::new(end, self.max_acceleration, self.max_speed);
+            arrive.slow_radius = self.slow_radius;
+            arrive.target_radius = self.target_radius;
+            return arrive.calculate(position, velocity);
+        }
+
+        let desired = (self.point_at(ahead) - position).normalize_or_zero() * self.max_speed;
+        SteeringOutput {
+            linear: (desired - velocity).clamp_length_max(self.max_acceleration),
+            angular: 0.0,
+        }
+    }
+}
+
+/// Weighted sum of several behaviors, clamped to a maximum acceleration
+///
+/// Borrows its behaviors so stateful ones such as [`Wander`] or obstacle
+/// avoidance can be updated each frame before blending.
+#[derive(Default)]
+pub struct BlendedSteering<'a> {
+    /// Behaviors and their weights
+    behaviors: Vec<(&'a dyn SteeringBehavior, f32)>,
+    /// Maximum combined acceleration
+    pub max_acceleration: f32,
+}
+
+impl<'a> BlendedSteering<'a> {
+    /// Create an empty blender
+    #[must_use]
+    pub fn new(max_acceleration: f32) -> Self {
+        Self {
+            behaviors: Vec::new(),
+            max_acceleration,
+        }
+    }
+
+    /// Add a weighted behavior
+    #[must_use]
+    pub fn with(mut self, behavior: &'a dyn SteeringBehavior, weight: f32) -> Self {
+        self.add(behavior, weight);
+        self
+    }
+
+    /// Add a weighted behavior
+    pub fn add(&mut self, behavior: &'a dyn SteeringBehavior, weight: f32) {
+        self.behaviors.push((behavior, weight));
+    }
+
+    /// Number of blended behaviors
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.behaviors.len()
+    }
+
+    /// Whether no behaviors have been added
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.behaviors.is_empty()
+    }
+}
+
+impl SteeringBehavior for BlendedSteering<'_> {
+    fn calculate(&self, position: Vec3, velocity: Vec3) -> SteeringOutput {
+        let output = self
+            .behaviors
+            .iter()
+            .fold(SteeringOutput::ZERO, |sum, (behavior, weight)| {
+                sum.combine(behavior.calculate(position, velocity).scale(*weight))
+            });
+        SteeringOutput {
+            linear: output.linear.clamp_length_max(self.max_acceleration),
+            angular: output.angular,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -274,6 +525,38 @@ mod tests {
         assert!((output.linear - output2.linear).length() > 0.001);
     }
 
+    #[test]
+    fn test_follow_path() {
+        let path = PathResult {
+            waypoints: vec![
+                glam::Vec2::ZERO,
+                glam::Vec2::new(10.0, 0.0),
+                glam::Vec2::new(10.0, 10.0),
+            ],
+            length: 20.0,
+        };
+        let mut follow = FollowPath::from_grid_path(&path, 0.0, 5.0, 4.0).with_lookahead(2.0);
+        assert!((follow.length() - 20.0).abs() < 1e-4);
+
+        // Mid first segment, slightly off the path: steer ahead along +X
+        let output = follow.calculate(Vec3::new(5.0, 0.0, 0.5), Vec3::ZERO);
+        assert!(output.linear.x > 0.0);
+
+        // Near the corner the look-ahead point is already on the second segment
+        follow.update(Vec3::new(9.5, 0.0, 0.0));
+        let output = follow.calculate(Vec3::new(9.5, 0.0, 0.0), Vec3::X * 4.0);
+        assert!(output.linear.z > 0.0);
+        assert_eq!(follow.current_segment(), 0);
+
+        // Arriving at the end slows down
+        let near_end = Vec3::new(10.0, 0.0, 9.0);
+        follow.update(near_end);
+        assert_eq!(follow.current_segment(), 1);
+        let output = follow.calculate(near_end, Vec3::Z * 4.0);
+        assert!(output.linear.z < 0.0);
+        assert!(follow.is_finished(Vec3::new(10.0, 0.0, 10.0)));
+    }
+
     #[test]
     fn test_steering_output_combine() {
         let a = SteeringOutput {
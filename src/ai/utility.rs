@@ -0,0 +1,426 @@
+//! Utility AI
+//!
+//! Scores a set of actions by multiplying weighted considerations (response
+//! curves over named inputs such as distance, health or ammo) and picks the
+//! highest-scoring action each tick.
+
+use rustc_hash::FxHashMap;
+
+/// Response curve mapping a normalized input in `[0, 1]` to a score in `[0, 1]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResponseCurve {
+    /// `slope * x + offset`
+    Linear {
+        /// Slope of the line
+        slope: f32,
+        /// Value at `x = 0`
+        offset: f32,
+    },
+    /// `x ^ exponent`, flipped horizontally when `inverted`
+    Power {
+        /// Curve exponent
+        exponent: f32,
+        /// Evaluate `(1 - x) ^ exponent` instead
+        inverted: bool,
+    },
+    /// S-curve centered on `midpoint`
+    Logistic {
+        /// Steepness of the transition (negative values flip the curve)
+        steepness: f32,
+        /// Input at which the curve crosses 0.5
+        midpoint: f32,
+    },
+    /// 0 below `threshold`, 1 at or above it
+    Step {
+        /// Input at which the output switches on
+        threshold: f32,
+    },
+    /// Constant output regardless of input
+    Constant(f32),
+}
+
+impl ResponseCurve {
+    /// Identity curve
+    pub const LINEAR: Self = Self::Linear {
+        slope: 1.0,
+        offset: 0.0,
+    };
+
+    /// Descending line, 1 at `x = 0` and 0 at `x = 1`
+    pub const INVERSE: Self = Self::Linear {
+        slope: -1.0,
+        offset: 1.0,
+    };
+
+    /// Evaluate the curve at a normalized input
+    #[must_use]
+    pub fn evaluate(&self, x: f32) -> f32 {
+        let x = x.clamp(0.0, 1.0);
+        let y = match *self {
+            Self::Linear { slope, offset } => slope * x + offset,
+            Self::Power { exponent, inverted } => {
+                let x = if inverted { 1.0 - x } else { x };
+                x.powf(exponent)
+            }
+            Self::Logistic {
+                steepness,
+                midpoint,
+            } => 1.0 / (1.0 + (-steepness * (x - midpoint)).exp()),
+            Self::Step { threshold } => {
+                if x >= threshold {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Self::Constant(value) => value,
+        };
+        if y.is_finite() {
+            y.clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Default for ResponseCurve {
+    fn default() -> Self {
+        Self::LINEAR
+    }
+}
+
+/// One factor of an action's score: a curve over a single named input
+#[derive(Debug, Clone)]
+pub struct Consideration {
+    /// Name of the input read from the context
+    pub input: String,
+    /// Input value mapped to 0
+    pub min: f32,
+    /// Input value mapped to 1
+    pub max: f32,
+    /// Curve applied to the normalized input
+    pub curve: ResponseCurve,
+    /// How strongly this consideration affects the score (0 ignores it, 1 applies it fully)
+    pub weight: f32,
+}
+
+impl Consideration {
+    /// Create a linear consideration over `[min, max]`
+    #[must_use]
+    pub fn new(input: impl Into<String>, min: f32, max: f32) -> Self {
+        Self {
+            input: input.into(),
+            min,
+            max,
+            curve: ResponseCurve::LINEAR,
+            weight: 1.0,
+        }
+    }
+
+    /// Set the response curve
+    #[must_use]
+    pub fn with_curve(mut self, curve: ResponseCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Set the weight
+    #[must_use]
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Score a raw input value
+    #[must_use]
+    pub fn score(&self, value: f32) -> f32 {
+        let range = self.max - self.min;
+        let normalized = if range.abs() > f32::EPSILON {
+            (value - self.min) / range
+        } else if value >= self.max {
+            1.0
+        } else {
+            0.0
+        };
+        let score = self.curve.evaluate(normalized);
+        1.0 - self.weight * (1.0 - score)
+    }
+}
+
+/// Named input values read by considerations
+#[derive(Debug, Clone, Default)]
+pub struct UtilityContext {
+    values: FxHashMap<String, f32>,
+}
+
+impl UtilityContext {
+    /// Create an empty context
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set an input value
+    pub fn set(&mut self, name: impl Into<String>, value: f32) {
+        self.values.insert(name.into(), value);
+    }
+
+    /// Set an input value, builder style
+    #[must_use]
+    pub fn with(mut self, name: impl Into<String>, value: f32) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Get an input value, or 0 if it has not been set
+    #[must_use]
+    pub fn get(&self, name: &str) -> f32 {
+        self.values.get(name).copied().unwrap_or(0.0)
+    }
+
+    /// Remove all inputs
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+/// An action scored by its considerations
+#[derive(Debug, Clone)]
+pub struct UtilityAction {
+    /// Action name, returned when it is selected
+    pub name: String,
+    /// Considerations multiplied together to form the score
+    pub considerations: Vec<Consideration>,
+    /// Multiplier applied to the final score, used to prioritize actions
+    pub weight: f32,
+}
+
+impl UtilityAction {
+    /// Create an action with no considerations
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            considerations: Vec::new(),
+            weight: 1.0,
+        }
+    }
+
+    /// Add a consideration
+    #[must_use]
+    pub fn with_consideration(mut self, consideration: Consideration) -> Self {
+        self.considerations.push(consideration);
+        self
+    }
+
+    /// Set the score multiplier
+    #[must_use]
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight.max(0.0);
+        self
+    }
+
+    /// Score the action against a context
+    ///
+    /// Consideration scores are multiplied together, with a compensation factor
+    /// so that actions with many considerations are not penalized for their count.
+    #[must_use]
+    pub fn score(&self, context: &UtilityContext) -> f32 {
+        if self.considerations.is_empty() {
+            return self.weight;
+        }
+        let compensation = 1.0 - 1.0 / self.considerations.len() as f32;
+        let mut total = 1.0;
+        for consideration in &self.considerations {
+            let score = consideration.score(context.get(&consideration.input));
+            total *= score + (1.0 - score) * compensation * score;
+            if total <= 0.0 {
+                return 0.0;
+            }
+        }
+        total * self.weight
+    }
+}
+
+/// Selects the best-scoring action each tick
+#[derive(Debug, Clone, Default)]
+pub struct UtilityBrain {
+    actions: Vec<UtilityAction>,
+    current: Option<usize>,
+    momentum: f32,
+    threshold: f32,
+}
+
+impl UtilityBrain {
+    /// Create an empty brain
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an action
+    #[must_use]
+    pub fn with_action(mut self, action: UtilityAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Bonus multiplier applied to the current action to avoid dithering between near-equal scores
+    #[must_use]
+    pub fn with_momentum(mut self, momentum: f32) -> Self {
+        self.momentum = momentum.max(0.0);
+        self
+    }
+
+    /// Minimum score an action needs to be selected
+    #[must_use]
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold.max(0.0);
+        self
+    }
+
+    /// Add an action
+    pub fn add_action(&mut self, action: UtilityAction) {
+        self.actions.push(action);
+    }
+
+    /// All actions
+    #[must_use]
+    pub fn actions(&self) -> &[UtilityAction] {
+        &self.actions
+    }
+
+    /// Currently selected action
+    #[must_use]
+    pub fn current(&self) -> Option<&UtilityAction> {
+        self.current.map(|index| &self.actions[index])
+    }
+
+    /// Score every action, in insertion order
+    #[must_use]
+    pub fn scores(&self, context: &UtilityContext) -> Vec<f32> {
+        self.actions
+            .iter()
+            .enumerate()
+            .map(|(index, action)| self.scored(index, action, context))
+            .collect()
+    }
+
+    /// Re-evaluate all actions and select the best one
+    ///
+    /// Ties keep the earlier action. Returns `None` when no action reaches the threshold.
+    pub fn tick(&mut self, context: &UtilityContext) -> Option<&UtilityAction> {
+        let mut best: Option<(usize, f32)> = None;
+        for (index, action) in self.actions.iter().enumerate() {
+            let score = self.scored(index, action, context);
+            if score > 0.0
+                && score >= self.threshold
+                && best.is_none_or(|(_, best_score)| score > best_score)
+            {
+                best = Some((index, score));
+            }
+        }
+        self.current = best.map(|(index, _)| index);
+        self.current()
+    }
+
+    /// Forget the current action
+    pub fn reset(&mut self) {
+        self.current = None;
+    }
+
+    fn scored(&self, index: usize, action: &UtilityAction, context: &UtilityContext) -> f32 {
+        let score = action.score(context);
+        if self.current == Some(index) {
+            score * (1.0 + self.momentum)
+        } else {
+            score
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brain() -> UtilityBrain {
+        UtilityBrain::new()
+            .with_action(
+                UtilityAction::new("attack")
+                    .with_consideration(
+                        Consideration::new("distance", 0.0, 20.0)
+                            .with_curve(ResponseCurve::INVERSE),
+                    )
+                    .with_consideration(Consideration::new("ammo", 0.0, 30.0))
+                    .with_consideration(Consideration::new("health", 0.0, 100.0).with_curve(
+                        ResponseCurve::Logistic {
+                            steepness: 10.0,
+                            midpoint: 0.3,
+                        },
+                    )),
+            )
+            .with_action(UtilityAction::new("heal").with_consideration(
+                Consideration::new("health", 0.0, 100.0).with_curve(ResponseCurve::Power {
+                    exponent: 2.0,
+                    inverted: true,
+                }),
+            ))
+            .with_action(UtilityAction::new("idle").with_weight(0.1))
+    }
+
+    #[test]
+    fn test_curves() {
+        assert!((ResponseCurve::LINEAR.evaluate(0.25) - 0.25).abs() < 1e-6);
+        assert!((ResponseCurve::INVERSE.evaluate(0.25) - 0.75).abs() < 1e-6);
+        assert_eq!(ResponseCurve::Step { threshold: 0.5 }.evaluate(0.4), 0.0);
+        let logistic = ResponseCurve::Logistic {
+            steepness: 10.0,
+            midpoint: 0.5,
+        };
+        assert!((logistic.evaluate(0.5) - 0.5).abs() < 1e-6);
+        assert!(logistic.evaluate(0.9) > 0.9);
+        assert_eq!(
+            Consideration::new("x", 0.0, 10.0)
+                .with_weight(0.0)
+                .score(0.0),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_select_best_action() {
+        let mut brain = brain();
+        let close_and_armed = UtilityContext::new()
+            .with("distance", 2.0)
+            .with("ammo", 30.0)
+            .with("health", 90.0);
+        assert_eq!(
+            brain.tick(&close_and_armed).map(|a| a.name.as_str()),
+            Some("attack")
+        );
+
+        let wounded = close_and_armed.clone().with("health", 10.0);
+        assert_eq!(brain.tick(&wounded).map(|a| a.name.as_str()), Some("heal"));
+
+        let empty = UtilityContext::new()
+            .with("distance", 2.0)
+            .with("ammo", 0.0)
+            .with("health", 100.0);
+        assert_eq!(brain.tick(&empty).map(|a| a.name.as_str()), Some("idle"));
+    }
+
+    #[test]
+    fn test_momentum_keeps_current_action() {
+        let mut brain = UtilityBrain::new()
+            .with_action(UtilityAction::new("a").with_weight(0.5))
+            .with_action(UtilityAction::new("b").with_weight(0.55))
+            .with_momentum(0.25);
+        let context = UtilityContext::new();
+        assert_eq!(brain.tick(&context).map(|a| a.name.as_str()), Some("b"));
+
+        brain.actions[0].weight = 0.6;
+        assert_eq!(brain.tick(&context).map(|a| a.name.as_str()), Some("b"));
+        brain.actions[0].weight = 0.8;
+        assert_eq!(brain.tick(&context).map(|a| a.name.as_str()), Some("a"));
+    }
+}
@@ -0,0 +1,239 @@
+//! Blend trees
+//!
+//! Blend several clips with weights derived from state machine parameters:
+//! a 1D blend along one parameter (idle, walk, run by speed) or a 2D blend
+//! over two parameters (strafing by velocity direction). Clips are synced
+//! by normalized time so footsteps stay aligned.
+
+use glam::Vec2;
+
+use super::clip::AnimationClip;
+use super::pose::Pose;
+use super::skeleton::Skeleton;
+
+/// Clips blended along one parameter
+#[derive(Debug, Clone)]
+pub struct BlendTree1D {
+    /// Parameter name
+    pub parameter: String,
+    /// Clips and their thresholds, sorted by threshold
+    pub points: Vec<(f32, AnimationClip)>,
+}
+
+impl BlendTree1D {
+    /// Create an empty 1D blend over a parameter
+    #[must_use]
+    pub fn new(parameter: impl Into<String>) -> Self {
+        Self {
+            parameter: parameter.into(),
+            points: Vec::new(),
+        }
+    }
+
+    /// Add a clip at a parameter value
+    #[must_use]
+    pub fn with_clip(mut self, threshold: f32, clip: AnimationClip) -> Self {
+        let index = self.points.partition_point(|(t, _)| *t <= threshold);
+        self.points.insert(index, (threshold, clip));
+        self
+    }
+
+    /// Compute clip weights for a parameter value
+    ///
+    /// Interpolates between the two neighboring thresholds and clamps
+    /// outside the range.
+    #[must_use]
+    pub fn weights(&self, value: f32) -> Vec<f32> {
+        let mut weights = vec![0.0; self.points.len()];
+        let Some(last) = self.points.len().checked_sub(1) else {
+            return weights;
+        };
+        let upper = self.points.partition_point(|(t, _)| *t <= value);
+        if upper == 0 {
+            weights[0] = 1.0;
+        } else if upper > last {
+            weights[last] = 1.0;
+        } else {
+            let (a, b) = (self.points[upper - 1].0, self.points[upper].0);
+            let t = if b > a { (value - a) / (b - a) } else { 1.0 };
+            weights[upper - 1] = 1.0 - t;
+            weights[upper] = t;
+        }
+        weights
+    }
+}
+
+/// Clips placed on a plane of two parameters
+#[derive(Debug, Clone)]
+pub struct BlendTree2D {
+    /// Horizontal parameter name
+    pub x_parameter: String,
+    /// Vertical parameter name
+    pub y_parameter: String,
+    /// Clips and their positions
+    pub points: Vec<(Vec2, AnimationClip)>,
+}
+
+impl BlendTree2D {
+    /// Create an empty 2D blend over two parameters
+    #[must_use]
+    pub fn new(x_parameter: impl Into<String>, y_parameter: impl Into<String>) -> Self {
+        Self {
+            x_parameter: x_parameter.into(),
+            y_parameter: y_parameter.into(),
+            points: Vec::new(),
+        }
+    }
+
+    /// Add a clip at a position
+    #[must_use]
+    pub fn with_clip(mut self, position: Vec2, clip: AnimationClip) -> Self {
+        self.points.push((position, clip));
+        self
+    }
+
+    /// Compute clip weights for a parameter position
+    ///
+    /// Uses gradient band interpolation: each clip's influence falls off
+    /// towards every other clip, so a clip at the center (idle) and clips
+    /// around it (strafe directions) blend smoothly.
+    #[must_use]
+    pub fn weights(&self, position: Vec2) -> Vec<f32> {
+        let mut weights: Vec<f32> = self
+            .points
+            .iter()
+            .enumerate()
+            .map(|(i, (pi, _))| {
+                self.points
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, (pj, _))| {
+                        let edge = *pj - *pi;
+                        let length_sq = edge.length_squared();
+                        if length_sq <= f32::EPSILON {
+                            return 1.0;
+                        }
+                        1.0 - (position - *pi).dot(edge) / length_sq
+                    })
+                    .fold(1.0_f32, f32::min)
+                    .max(0.0)
+            })
+            .collect();
+
+        let total: f32 = weights.iter().sum();
+        if total > 0.0 {
+            for weight in &mut weights {
+                *weight /= total;
+            }
+        }
+        weights
+    }
+}
+
+/// A blend tree usable as a state machine state
+#[derive(Debug, Clone)]
+pub enum BlendTree {
+    /// Blend along one parameter
+    OneD(BlendTree1D),
+    /// Blend over two parameters
+    TwoD(BlendTree2D),
+}
+
+impl BlendTree {
+    /// Compute clip weights from named parameters
+    pub fn weights(&self, parameter: impl Fn(&str) -> f32) -> Vec<f32> {
+        match self {
+            Self::OneD(tree) => tree.weights(parameter(&tree.parameter)),
+            Self::TwoD(tree) => tree.weights(Vec2::new(
+                parameter(&tree.x_parameter),
+                parameter(&tree.y_parameter),
+            )),
+        }
+    }
+
+    /// Get the clips in point order
+    #[must_use]
+    pub fn clips(&self) -> Vec<&AnimationClip> {
+        match self {
+            Self::OneD(tree) => tree.points.iter().map(|(_, clip)| clip).collect(),
+            Self::TwoD(tree) => tree.points.iter().map(|(_, clip)| clip).collect(),
+        }
+    }
+
+    /// Get the cycle length for a set of weights
+    #[must_use]
+    pub fn duration(&self, weights: &[f32]) -> f32 {
+        self.clips()
+            .iter()
+            .zip(weights)
+            .map(|(clip, weight)| clip.duration * weight)
+            .sum()
+    }
+
+    /// Sample the weighted blend at a normalized time (0.0 to 1.0)
+    #[must_use]
+    pub fn sample(&self, weights: &[f32], normalized_time: f32, skeleton: &Skeleton) -> Pose {
+        let mut pose = Pose::from_skeleton(skeleton);
+        let mut total = 0.0;
+        for (clip, &weight) in self.clips().iter().zip(weights) {
+            if weight <= 0.0 {
+                continue;
+            }
+            total += weight;
+            let sampled = Pose::sample(clip, normalized_time * clip.duration, skeleton);
+            // Running normalized blend: the first clip replaces the bind pose
+            pose = pose.blend(&sampled, weight / total);
+        }
+        pose
+    }
+}
+
+impl From<BlendTree1D> for BlendTree {
+    fn from(tree: BlendTree1D) -> Self {
+        Self::OneD(tree)
+    }
+}
+
+impl From<BlendTree2D> for BlendTree {
+    fn from(tree: BlendTree2D) -> Self {
+        Self::TwoD(tree)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_1d_weights() {
+        let tree = BlendTree1D::new("speed")
+            .with_clip(4.0, AnimationClip::new("run"))
+            .with_clip(0.0, AnimationClip::new("idle"))
+            .with_clip(1.5, AnimationClip::new("walk"));
+
+        assert_eq!(tree.points[1].1.name, "walk");
+        assert_eq!(tree.weights(-1.0), vec![1.0, 0.0, 0.0]);
+        assert_eq!(tree.weights(9.0), vec![0.0, 0.0, 1.0]);
+        let w = tree.weights(2.75);
+        assert!((w[1] - 0.5).abs() < 1e-5 && (w[2] - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_2d_weights_at_points_and_between() {
+        let tree = BlendTree2D::new("x", "y")
+            .with_clip(Vec2::ZERO, AnimationClip::new("idle"))
+            .with_clip(Vec2::Y, AnimationClip::new("forward"))
+            .with_clip(Vec2::X, AnimationClip::new("right"))
+            .with_clip(-Vec2::X, AnimationClip::new("left"));
+
+        let w = tree.weights(Vec2::Y);
+        assert!((w[1] - 1.0).abs() < 1e-5);
+
+        // Diagonal between forward and right: left has no influence
+        let w = tree.weights(Vec2::new(0.5, 0.5));
+        assert!(w[3] < 1e-5);
+        assert!((w[1] - w[2]).abs() < 1e-5);
+        assert!((w.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+    }
+}
@@ -78,6 +78,26 @@ impl Channel {
     }
 }
 
+/// A named event on a clip's timeline (e.g. a footstep)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimationEvent {
+    /// Time in seconds
+    pub time: f32,
+    /// Event name
+    pub name: String,
+}
+
+impl AnimationEvent {
+    /// Create a new event
+    #[must_use]
+    pub fn new(time: f32, name: impl Into<String>) -> Self {
+        Self {
+            time,
+            name: name.into(),
+        }
+    }
+}
+
 /// A complete animation clip
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnimationClip {
@@ -89,6 +109,12 @@ pub struct AnimationClip {
     pub interpolation: Interpolation,
     /// Channels indexed by target (bone index or property name)
     pub channels: Vec<(usize, Channel)>,
+    /// Timeline events, sorted by time
+    #[serde(default)]
+    pub events: Vec<AnimationEvent>,
+    /// Clips in the same sync group crossfade phase-aligned
+    #[serde(default)]
+    pub sync_group: Option<String>,
 }
 
 impl AnimationClip {
@@ -100,9 +126,24 @@ impl AnimationClip {
             duration: 0.0,
             interpolation: Interpolation::Linear,
             channels: Vec::new(),
+            events: Vec::new(),
+            sync_group: None,
         }
     }
 
+    /// Put the clip in a sync group (e.g. "locomotion")
+    #[must_use]
+    pub fn with_sync_group(mut self, group: impl Into<String>) -> Self {
+        self.sync_group = Some(group.into());
+        self
+    }
+
+    /// Check if two clips share a sync group
+    #[must_use]
+    pub fn is_synced_with(&self, other: &Self) -> bool {
+        self.sync_group.is_some() && self.sync_group == other.sync_group
+    }
+
     /// Add a channel to the clip
     pub fn add_channel(&mut self, target: usize, channel: Channel) {
         let channel_duration = channel.duration();
@@ -110,6 +151,97 @@ impl AnimationClip {
         self.duration = self.duration.max(channel_duration);
     }
 
+    /// Add a named event at a time
+    pub fn add_event(&mut self, time: f32, name: impl Into<String>) {
+        let event = AnimationEvent::new(time, name);
+        let index = self.events.partition_point(|e| e.time <= time);
+        self.events.insert(index, event);
+    }
+
+    /// Get events with `from <= time < to`, in timeline order
+    ///
+    /// With `inclusive_end`, events exactly at `to` are included too.
+    pub fn events_between(
+        &self,
+        from: f32,
+        to: f32,
+        inclusive_end: bool,
+    ) -> impl Iterator<Item = &AnimationEvent> {
+        self.events
+            .iter()
+            .filter(move |e| e.time >= from && (e.time < to || (inclusive_end && e.time == to)))
+    }
+
+    /// Remove keyframes that interpolation reproduces within `tolerance`
+    ///
+    /// The tolerance is in world units for translation and scale, radians
+    /// for rotation, and absolute weight for morph targets. The first and
+    /// last keyframe of each channel are always kept, so the duration does
+    /// not change. Cubic spline clips are left untouched. Returns the
+    /// number of keyframes removed.
+    pub fn optimize(&mut self, tolerance: f32) -> usize {
+        let interpolation = self.interpolation;
+        if interpolation == Interpolation::CubicSpline {
+            return 0;
+        }
+        let mut removed = 0;
+        for (_, channel) in &mut self.channels {
+            removed += match channel {
+                Channel::Translation(keys) | Channel::Scale(keys) => reduce_keys(
+                    keys,
+                    tolerance,
+                    interpolation,
+                    |a, b, t| a.lerp(*b, t),
+                    |a, b| a.distance(*b),
+                ),
+                Channel::Rotation(keys) => reduce_keys(
+                    keys,
+                    tolerance,
+                    interpolation,
+                    |a, b, t| a.slerp(*b, t),
+                    |a, b| a.angle_between(*b),
+                ),
+                Channel::MorphWeights(keys) => reduce_keys(
+                    keys,
+                    tolerance,
+                    interpolation,
+                    |a, b, t| a.iter().zip(b).map(|(x, y)| x + (y - x) * t).collect(),
+                    |a, b| {
+                        a.iter()
+                            .zip(b)
+                            .map(|(x, y)| (x - y).abs())
+                            .fold(0.0, f32::max)
+                    },
+                ),
+            };
+        }
+        removed
+    }
+
+    /// Snap rotation keyframes to `bits` bits per component (2 to 16)
+    ///
+    /// Removes sub-precision noise from sampled or baked animation so that
+    /// `optimize` can drop more keyframes and serialized clips compress
+    /// better.
+    pub fn quantize_rotations(&mut self, bits: u32) {
+        let steps = ((1u32 << (bits.clamp(2, 16) - 1)) - 1) as f32;
+        let snap = |q: Quat| {
+            // Keep w positive so equal rotations snap to equal values
+            let q = if q.w < 0.0 { -q } else { q };
+            let v = (glam::Vec4::from(q) * steps).round() / steps;
+            Quat::from_vec4(v).normalize()
+        };
+        for (_, channel) in &mut self.channels {
+            if let Channel::Rotation(keys) = channel {
+                for key in keys.iter_mut() {
+                    key.value = snap(key.value);
+                    key.in_tangent = key.in_tangent.map(snap);
+                    key.out_tangent = key.out_tangent.map(snap);
+                }
+            }
+        }
+    }
+
     /// Sample translation at a given time
     #[must_use]
     pub fn sample_translation(&self, target: usize, time: f32) -> Option<Vec3> {
@@ -148,6 +280,28 @@ impl AnimationClip {
         }
         None
     }
+
+    /// Sample morph target weights at a given time
+    #[must_use]
+    pub fn sample_morph_weights(&self, target: usize, time: f32) -> Option<Vec<f32>> {
+        for (t, channel) in &self.channels {
+            if *t == target
+                && let Channel::MorphWeights(keys) = channel
+            {
+                return Some(sample_weights(keys, time, self.interpolation));
+            }
+        }
+        None
+    }
+
+    /// Get the target of the first morph weight channel
+    #[must_use]
+    pub fn morph_target(&self) -> Option<usize> {
+        self.channels
+            .iter()
+            .find(|(_, channel)| matches!(channel, Channel::MorphWeights(_)))
+            .map(|(target, _)| *target)
+    }
 }
 
 impl Default for AnimationClip {
@@ -156,6 +310,44 @@ impl Default for AnimationClip {
     }
 }
 
+/// Drop keyframes whose values are predicted within `tolerance` by
+/// interpolating between their kept neighbors
+fn reduce_keys<T: Clone>(
+    keys: &mut Vec<Keyframe<T>>,
+    tolerance: f32,
+    interpolation: Interpolation,
+    lerp: impl Fn(&T, &T, f32) -> T,
+    error: impl Fn(&T, &T) -> f32,
+) -> usize {
+    if keys.len() < 3 {
+        return 0;
+    }
+
+    let mut kept = vec![keys[0].clone()];
+    let mut anchor = 0;
+    for i in 1..keys.len() - 1 {
+        let (start, end) = (&keys[anchor], &keys[i + 1]);
+        let span = (end.time - start.time).max(f32::EPSILON);
+        // Removing key i must keep every key skipped since the anchor in tolerance
+        let redundant = keys[anchor + 1..=i].iter().all(|key| {
+            let predicted = match interpolation {
+                Interpolation::Step => start.value.clone(),
+                _ => lerp(&start.value, &end.value, (key.time - start.time) / span),
+            };
+            error(&predicted, &key.value) <= tolerance
+        });
+        if !redundant {
+            kept.push(keys[i].clone());
+            anchor = i;
+        }
+    }
+    kept.push(keys[keys.len() - 1].clone());
+
+    let removed = keys.len() - kept.len();
+    *keys = kept;
+    removed
+}
+
 /// Sample Vec3 keyframes at a given time
 fn sample_vec3(keyframes: &[Keyframe<Vec3>], time: f32, interp: Interpolation) -> Vec3 {
     if keyframes.is_empty() {
@@ -233,6 +425,54 @@ fn sample_quat(keyframes: &[Keyframe<Quat>], time: f32, interp: Interpolation) -
     keyframes.last().unwrap().value
 }
 
+/// Sample morph weight keyframes at a given time
+fn sample_weights(keyframes: &[Keyframe<Vec<f32>>], time: f32, interp: Interpolation) -> Vec<f32> {
+    let (Some(first), Some(last)) = (keyframes.first(), keyframes.last()) else {
+        return Vec::new();
+    };
+    if time <= first.time {
+        return first.value.clone();
+    }
+    if time >= last.time {
+        return last.value.clone();
+    }
+
+    let i = keyframes.partition_point(|k| k.time <= time).max(1) - 1;
+    let (k0, k1) = (&keyframes[i], &keyframes[i + 1]);
+    let dt = k1.time - k0.time;
+    let t = (time - k0.time) / dt;
+    match interp {
+        Interpolation::Step => k0.value.clone(),
+        Interpolation::Linear => k0
+            .value
+            .iter()
+            .zip(&k1.value)
+            .map(|(a, b)| a + (b - a) * t)
+            .collect(),
+        Interpolation::CubicSpline => {
+            let t2 = t * t;
+            let t3 = t2 * t;
+            let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+            let h10 = t3 - 2.0 * t2 + t;
+            let h01 = -2.0 * t3 + 3.0 * t2;
+            let h11 = t3 - t2;
+            k0.value
+                .iter()
+                .zip(&k1.value)
+                .enumerate()
+                .map(|(j, (a, b))| {
+                    let out_tan = k0.out_tangent.as_ref().and_then(|v| v.get(j)).copied();
+                    let in_tan = k1.in_tangent.as_ref().and_then(|v| v.get(j)).copied();
+                    a * h00
+                        + out_tan.unwrap_or(0.0) * dt * h10
+                        + b * h01
+                        + in_tan.unwrap_or(0.0) * dt * h11
+                })
+                .collect()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -254,4 +494,40 @@ mod tests {
         let pos = clip.sample_translation(0, 0.5).unwrap();
         assert!((pos.x - 5.0).abs() < 0.01);
     }
+
+    #[test]
+    fn test_morph_weight_sampling() {
+        let mut clip = AnimationClip::new("blink");
+        clip.add_channel(
+            3,
+            Channel::MorphWeights(vec![
+                Keyframe::new(0.0, vec![0.0, 1.0]),
+                Keyframe::new(0.5, vec![1.0, 0.0]),
+            ]),
+        );
+
+        assert_eq!(clip.morph_target(), Some(3));
+        assert_eq!(clip.sample_morph_weights(3, 0.25), Some(vec![0.5, 0.5]));
+        assert_eq!(clip.sample_morph_weights(3, 2.0), Some(vec![1.0, 0.0]));
+        assert_eq!(clip.sample_morph_weights(0, 0.25), None);
+    }
+
+    #[test]
+    fn test_optimize_removes_collinear_keys() {
+        let mut clip = AnimationClip::new("test");
+        // Straight line up to t=1, then a sharp turn
+        let mut keys: Vec<_> = (0..=10)
+            .map(|i| Keyframe::new(i as f32 * 0.1, Vec3::new(i as f32, 0.0, 0.0)))
+            .collect();
+        keys.push(Keyframe::new(1.5, Vec3::new(10.0, 5.0, 0.0)));
+        clip.add_channel(0, Channel::Translation(keys));
+
+        assert_eq!(clip.optimize(0.001), 9);
+        let Channel::Translation(keys) = &clip.channels[0].1 else {
+            unreachable!();
+        };
+        let times: Vec<f32> = keys.iter().map(|k| k.time).collect();
+        assert_eq!(times, vec![0.0, 1.0, 1.5]);
+        assert!((clip.sample_translation(0, 0.55).unwrap().x - 5.5).abs() < 0.001);
+    }
 }
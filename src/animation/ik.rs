@@ -0,0 +1,349 @@
+//! Inverse kinematics
+//!
+//! Constraints that adjust a sampled pose so end effectors reach targets:
+//! an analytic two-bone solver for arms and legs, and CCD for longer chains.
+//! Targets are in the skeleton's model space.
+
+use glam::{Mat4, Quat, Vec3};
+
+use super::pose::Pose;
+use super::skeleton::Skeleton;
+
+/// Minimum segment length and distance used to avoid degenerate solves
+const EPSILON: f32 = 1e-5;
+
+/// Analytic solver for a three-joint chain (e.g. hip, knee, ankle)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwoBoneIk {
+    /// Upper joint (shoulder or hip)
+    pub root: usize,
+    /// Middle joint (elbow or knee)
+    pub mid: usize,
+    /// End effector (wrist or ankle)
+    pub end: usize,
+    /// Model-space target for the end effector
+    pub target: Vec3,
+    /// Model-space point the middle joint bends towards
+    pub pole: Option<Vec3>,
+    /// Blend between the input pose (0.0) and the solved pose (1.0)
+    pub weight: f32,
+}
+
+impl TwoBoneIk {
+    /// Create a two-bone constraint
+    #[must_use]
+    pub fn new(root: usize, mid: usize, end: usize, target: Vec3) -> Self {
+        Self {
+            root,
+            mid,
+            end,
+            target,
+            pole: None,
+            weight: 1.0,
+        }
+    }
+
+    /// Set the pole target the middle joint bends towards
+    #[must_use]
+    pub fn with_pole(mut self, pole: Vec3) -> Self {
+        self.pole = Some(pole);
+        self
+    }
+
+    /// Set the blend weight
+    #[must_use]
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Solve the constraint, modifying the pose in place
+    pub fn solve(&self, pose: &mut Pose, skeleton: &Skeleton) {
+        let len = pose.bones.len();
+        if self.root >= len || self.mid >= len || self.end >= len || self.weight <= 0.0 {
+            return;
+        }
+        let original = [
+            pose.bones[self.root].rotation,
+            pose.bones[self.mid].rotation,
+        ];
+
+        let world = pose.world_matrices(skeleton);
+        let a = world[self.root].w_axis.truncate();
+        let b = world[self.mid].w_axis.truncate();
+        let c = world[self.end].w_axis.truncate();
+        let t = self.target;
+        let a_rot = rotation_of(world[self.root]);
+        let b_rot = rotation_of(world[self.mid]);
+
+        let lab = (b - a).length();
+        let lcb = (b - c).length();
+        if lab < EPSILON || lcb < EPSILON {
+            return;
+        }
+        let lat = (t - a)
+            .length()
+            .clamp(EPSILON, (lab + lcb) * (1.0 - EPSILON));
+
+        // Current and desired interior angles at the root and middle joints
+        let ac_ab_0 = angle_between(c - a, b - a);
+        let ba_bc_0 = angle_between(a - b, c - b);
+        let ac_at_0 = angle_between(c - a, t - a);
+        let ac_ab_1 = ((lcb * lcb - lab * lab - lat * lat) / (-2.0 * lab * lat))
+            .clamp(-1.0, 1.0)
+            .acos();
+        let ba_bc_1 = ((lat * lat - lab * lab - lcb * lcb) / (-2.0 * lab * lcb))
+            .clamp(-1.0, 1.0)
+            .acos();
+
+        let bend_hint = self.pole.map_or(b - a, |pole| pole - a);
+        let axis0 = (c - a)
+            .cross(bend_hint)
+            .try_normalize()
+            .or_else(|| (c - a).any_orthonormal_vector().try_normalize())
+            .unwrap_or(Vec3::X);
+        let axis1 = (c - a).cross(t - a).try_normalize().unwrap_or(axis0);
+
+        let r0 = Quat::from_axis_angle(axis0, ac_ab_1 - ac_ab_0);
+        let r1 = Quat::from_axis_angle(axis0, ba_bc_1 - ba_bc_0);
+        let r2 = Quat::from_axis_angle(axis1, ac_at_0);
+
+        let root = &mut pose.bones[self.root].rotation;
+        *root = (*root * (a_rot.inverse() * r0 * a_rot)).normalize();
+        let mid = &mut pose.bones[self.mid].rotation;
+        *mid = (*mid * (b_rot.inverse() * r1 * b_rot)).normalize();
+        // Swing towards the target after bending, using the bent root rotation
+        let a_rot = r0 * a_rot;
+        let root = &mut pose.bones[self.root].rotation;
+        *root = (*root * (a_rot.inverse() * r2 * a_rot)).normalize();
+
+        // Swing the chain around the root-target axis so the middle joint faces the pole
+        if let Some(pole) = self.pole {
+            let world = pose.world_matrices(skeleton);
+            let mid_pos = world[self.mid].w_axis.truncate();
+            if let Some(axis) = (t - a).try_normalize() {
+                let to_mid = (mid_pos - a).reject_from_normalized(axis);
+                let to_pole = (pole - a).reject_from_normalized(axis);
+                if to_mid.length() > EPSILON && to_pole.length() > EPSILON {
+                    let swing = Quat::from_rotation_arc(to_mid.normalize(), to_pole.normalize());
+                    rotate_world(pose, skeleton, &world, self.root, swing);
+                }
+            }
+        }
+
+        if self.weight < 1.0 {
+            for (index, before) in [self.root, self.mid].into_iter().zip(original) {
+                let solved = pose.bones[index].rotation;
+                pose.bones[index].rotation = before.slerp(solved, self.weight);
+            }
+        }
+    }
+}
+
+/// Cyclic coordinate descent solver for a chain of any length
+#[derive(Debug, Clone, PartialEq)]
+pub struct CcdIk {
+    /// Bones from the chain root to the end effector
+    pub chain: Vec<usize>,
+    /// Model-space target for the end effector
+    pub target: Vec3,
+    /// Maximum solver iterations
+    pub iterations: u32,
+    /// Stop once the end effector is this close to the target
+    pub tolerance: f32,
+}
+
+impl CcdIk {
+    /// Create a CCD constraint
+    #[must_use]
+    pub fn new(chain: Vec<usize>, target: Vec3) -> Self {
+        Self {
+            chain,
+            target,
+            iterations: 10,
+            tolerance: 0.001,
+        }
+    }
+
+    /// Set the maximum iteration count
+    #[must_use]
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Solve the constraint, modifying the pose in place
+    pub fn solve(&self, pose: &mut Pose, skeleton: &Skeleton) {
+        let Some(&end) = self.chain.last() else {
+            return;
+        };
+        if self.chain.iter().any(|&bone| bone >= pose.bones.len()) {
+            return;
+        }
+
+        for _ in 0..self.iterations {
+            for &joint in self.chain.iter().rev().skip(1) {
+                let world = pose.world_matrices(skeleton);
+                let joint_pos = world[joint].w_axis.truncate();
+                let end_pos = world[end].w_axis.truncate();
+                let (Some(to_end), Some(to_target)) = (
+                    (end_pos - joint_pos).try_normalize(),
+                    (self.target - joint_pos).try_normalize(),
+                ) else {
+                    continue;
+                };
+                let delta = Quat::from_rotation_arc(to_end, to_target);
+                rotate_world(pose, skeleton, &world, joint, delta);
+            }
+
+            let world = pose.world_matrices(skeleton);
+            if world[end].w_axis.truncate().distance(self.target) <= self.tolerance {
+                break;
+            }
+        }
+    }
+}
+
+/// An IK constraint applied after clip sampling
+#[derive(Debug, Clone, PartialEq)]
+pub enum IkConstraint {
+    /// Analytic two-bone solve
+    TwoBone(TwoBoneIk),
+    /// Iterative CCD solve
+    Ccd(CcdIk),
+}
+
+impl IkConstraint {
+    /// Solve the constraint, modifying the pose in place
+    pub fn solve(&self, pose: &mut Pose, skeleton: &Skeleton) {
+        match self {
+            Self::TwoBone(ik) => ik.solve(pose, skeleton),
+            Self::Ccd(ik) => ik.solve(pose, skeleton),
+        }
+    }
+
+    /// Set the model-space target
+    pub fn set_target(&mut self, target: Vec3) {
+        match self {
+            Self::TwoBone(ik) => ik.target = target,
+            Self::Ccd(ik) => ik.target = target,
+        }
+    }
+}
+
+impl From<TwoBoneIk> for IkConstraint {
+    fn from(ik: TwoBoneIk) -> Self {
+        Self::TwoBone(ik)
+    }
+}
+
+impl From<CcdIk> for IkConstraint {
+    fn from(ik: CcdIk) -> Self {
+        Self::Ccd(ik)
+    }
+}
+
+/// Ordered set of IK constraints for a character
+#[derive(Debug, Clone, Default)]
+pub struct IkRig {
+    /// Constraints, solved in order
+    pub constraints: Vec<IkConstraint>,
+}
+
+impl IkRig {
+    /// Create an empty rig
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a constraint and return its index
+    pub fn add(&mut self, constraint: impl Into<IkConstraint>) -> usize {
+        self.constraints.push(constraint.into());
+        self.constraints.len() - 1
+    }
+
+    /// Solve every constraint in order
+    pub fn solve(&self, pose: &mut Pose, skeleton: &Skeleton) {
+        for constraint in &self.constraints {
+            constraint.solve(pose, skeleton);
+        }
+    }
+}
+
+/// Get the rotation of a model-space matrix
+fn rotation_of(matrix: Mat4) -> Quat {
+    matrix.to_scale_rotation_translation().1
+}
+
+/// Unsigned angle between two vectors
+fn angle_between(a: Vec3, b: Vec3) -> f32 {
+    match (a.try_normalize(), b.try_normalize()) {
+        (Some(a), Some(b)) => a.dot(b).clamp(-1.0, 1.0).acos(),
+        _ => 0.0,
+    }
+}
+
+/// Apply a model-space rotation to a bone's local rotation
+pub(super) fn rotate_world(
+    pose: &mut Pose,
+    skeleton: &Skeleton,
+    world: &[Mat4],
+    bone: usize,
+    delta: Quat,
+) {
+    let parent_rot = skeleton.bones[bone]
+        .parent
+        .map_or(Quat::IDENTITY, |parent| rotation_of(world[parent]));
+    let world_rot = rotation_of(world[bone]);
+    pose.bones[bone].rotation = (parent_rot.inverse() * delta * world_rot).normalize();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::Bone;
+
+    /// A straight three-joint arm along +X with unit segments
+    fn arm() -> Skeleton {
+        let mut skeleton = Skeleton::new();
+        let shoulder = skeleton.add_bone(Bone::new("shoulder"));
+        let mut elbow = Bone::new("elbow");
+        elbow.translation = Vec3::X;
+        let elbow = skeleton.add_bone(elbow);
+        let mut wrist = Bone::new("wrist");
+        wrist.translation = Vec3::X;
+        let wrist = skeleton.add_bone(wrist);
+        skeleton.set_parent(elbow, shoulder);
+        skeleton.set_parent(wrist, elbow);
+        skeleton
+    }
+
+    #[test]
+    fn test_two_bone_reaches_target() {
+        let skeleton = arm();
+        let mut pose = Pose::from_skeleton(&skeleton);
+        // Slight bend so the bend plane is defined
+        pose.bones[1].rotation = Quat::from_rotation_z(0.1);
+
+        let target = Vec3::new(1.0, 1.0, 0.0);
+        TwoBoneIk::new(0, 1, 2, target).solve(&mut pose, &skeleton);
+
+        let world = pose.world_matrices(&skeleton);
+        assert!(world[2].w_axis.truncate().distance(target) < 0.01);
+    }
+
+    #[test]
+    fn test_ccd_reaches_target() {
+        let skeleton = arm();
+        let mut pose = Pose::from_skeleton(&skeleton);
+
+        let target = Vec3::new(0.5, 1.2, 0.0);
+        CcdIk::new(vec![0, 1, 2], target)
+            .with_iterations(30)
+            .solve(&mut pose, &skeleton);
+
+        let world = pose.world_matrices(&skeleton);
+        assert!(world[2].w_axis.truncate().distance(target) < 0.01);
+    }
+}
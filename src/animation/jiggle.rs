@@ -0,0 +1,224 @@
+//! Jiggle bones
+//!
+//! Spring-damper secondary motion for hair, tails, antennas, and similar
+//! dangling parts. Evaluated after sampling, IK, and other pose edits: each
+//! jiggle bone's tip lags behind its animated position and swings back.
+
+use glam::{Mat4, Quat, Vec3};
+
+use super::ik::rotate_world;
+use super::pose::Pose;
+use super::skeleton::Skeleton;
+
+/// Longest simulation step; larger frame times are split into substeps
+const MAX_STEP: f32 = 1.0 / 60.0;
+
+/// Spring settings for one bone
+#[derive(Debug, Clone, PartialEq)]
+pub struct JiggleBone {
+    /// Bone index
+    pub bone: usize,
+    /// Pull towards the animated pose
+    pub stiffness: f32,
+    /// Velocity damping per second
+    pub damping: f32,
+    /// World-space acceleration applied to the tip
+    pub gravity: Vec3,
+    /// Maximum swing away from the animated pose, in radians
+    pub max_angle: f32,
+    /// Tip in the bone's local space (defaults to its first child's offset)
+    pub tip: Option<Vec3>,
+}
+
+impl JiggleBone {
+    /// Create a jiggle bone with moderate springiness
+    #[must_use]
+    pub fn new(bone: usize) -> Self {
+        Self {
+            bone,
+            stiffness: 120.0,
+            damping: 8.0,
+            gravity: Vec3::ZERO,
+            max_angle: std::f32::consts::FRAC_PI_3,
+            tip: None,
+        }
+    }
+
+    /// Set stiffness and damping
+    #[must_use]
+    pub fn with_spring(mut self, stiffness: f32, damping: f32) -> Self {
+        self.stiffness = stiffness.max(0.0);
+        self.damping = damping.max(0.0);
+        self
+    }
+
+    /// Set the gravity applied to the tip
+    #[must_use]
+    pub fn with_gravity(mut self, gravity: Vec3) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// Set the swing limit in radians
+    #[must_use]
+    pub fn with_max_angle(mut self, max_angle: f32) -> Self {
+        self.max_angle = max_angle.max(0.0);
+        self
+    }
+
+    /// Set the tip offset in the bone's local space
+    #[must_use]
+    pub fn with_tip(mut self, tip: Vec3) -> Self {
+        self.tip = Some(tip);
+        self
+    }
+
+    /// Resolve the local tip offset
+    fn tip_offset(&self, skeleton: &Skeleton) -> Vec3 {
+        self.tip.unwrap_or_else(|| {
+            skeleton.bones[self.bone]
+                .children
+                .first()
+                .map_or(Vec3::Y * 0.1, |&child| skeleton.bones[child].translation)
+        })
+    }
+}
+
+/// Simulated state of one jiggle bone
+#[derive(Debug, Clone, Copy, Default)]
+struct Tip {
+    /// World-space tip position
+    position: Vec3,
+    /// World-space tip velocity
+    velocity: Vec3,
+}
+
+/// A set of jiggle bones for one character
+#[derive(Debug, Clone, Default)]
+pub struct JiggleRig {
+    /// Jiggle bones, parents before children
+    pub bones: Vec<JiggleBone>,
+    /// Simulated tips, empty until the first update
+    tips: Vec<Tip>,
+}
+
+impl JiggleRig {
+    /// Create an empty rig
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a jiggle bone
+    ///
+    /// Add parents before their children so chains swing as a whole.
+    pub fn add(&mut self, bone: JiggleBone) {
+        self.bones.push(bone);
+        self.tips.clear();
+    }
+
+    /// Snap every tip to the animated pose (e.g. after a teleport)
+    pub fn reset(&mut self) {
+        self.tips.clear();
+    }
+
+    /// Simulate and apply secondary motion to a pose
+    ///
+    /// `model` is the character's world matrix, so moving the character
+    /// makes the bones trail behind.
+    pub fn update(&mut self, pose: &mut Pose, skeleton: &Skeleton, model: Mat4, delta_time: f32) {
+        self.bones.retain(|b| b.bone < pose.bones.len());
+        let model_rotation = model.to_scale_rotation_translation().1;
+
+        if self.tips.len() != self.bones.len() {
+            let world = pose.world_matrices(skeleton);
+            self.tips = self
+                .bones
+                .iter()
+                .map(|b| Tip {
+                    position: (model * world[b.bone]).transform_point3(b.tip_offset(skeleton)),
+                    velocity: Vec3::ZERO,
+                })
+                .collect();
+            return;
+        }
+
+        let steps = (delta_time / MAX_STEP).ceil().max(1.0);
+        let dt = delta_time / steps;
+
+        for (jiggle, tip) in self.bones.iter().zip(self.tips.iter_mut()) {
+            let world = pose.world_matrices(skeleton);
+            let bone_world = model * world[jiggle.bone];
+            let origin = bone_world.w_axis.truncate();
+            let target = bone_world.transform_point3(jiggle.tip_offset(skeleton));
+            let length = origin.distance(target);
+            if length <= f32::EPSILON {
+                continue;
+            }
+
+            for _ in 0..steps as u32 {
+                let acceleration = (target - tip.position) * jiggle.stiffness
+                    - tip.velocity * jiggle.damping
+                    + jiggle.gravity;
+                tip.velocity += acceleration * dt;
+                tip.position += tip.velocity * dt;
+            }
+
+            // Keep the bone length and swing limit
+            let rest = (target - origin) / length;
+            let mut direction = (tip.position - origin).try_normalize().unwrap_or(rest);
+            let angle = rest.angle_between(direction);
+            if angle > jiggle.max_angle {
+                let axis = rest.cross(direction).try_normalize().unwrap_or(Vec3::X);
+                direction = Quat::from_axis_angle(axis, jiggle.max_angle) * rest;
+            }
+            tip.position = origin + direction * length;
+
+            let swing = Quat::from_rotation_arc(rest, direction);
+            let delta = model_rotation.inverse() * swing * model_rotation;
+            rotate_world(pose, skeleton, &world, jiggle.bone, delta);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::Bone;
+
+    fn tail() -> Skeleton {
+        let mut skeleton = Skeleton::new();
+        let root = skeleton.add_bone(Bone::new("root"));
+        let mut tip = Bone::new("tip");
+        tip.translation = Vec3::Y;
+        let tip = skeleton.add_bone(tip);
+        skeleton.set_parent(tip, root);
+        skeleton
+    }
+
+    #[test]
+    fn test_jiggle_trails_and_settles() {
+        let skeleton = tail();
+        let mut rig = JiggleRig::new();
+        rig.add(JiggleBone::new(0));
+
+        let mut pose = Pose::from_skeleton(&skeleton);
+        rig.update(&mut pose, &skeleton, Mat4::IDENTITY, 1.0 / 60.0);
+
+        // Move the character sideways: the tip lags, tilting the bone
+        let moved = Mat4::from_translation(Vec3::X * 0.5);
+        let mut pose = Pose::from_skeleton(&skeleton);
+        rig.update(&mut pose, &skeleton, moved, 1.0 / 60.0);
+        let tip = pose.world_matrices(&skeleton)[1].w_axis.truncate();
+        assert!(tip.x < -0.1);
+
+        // Standing still, the spring settles back to the animated pose
+        for _ in 0..300 {
+            let mut pose = Pose::from_skeleton(&skeleton);
+            rig.update(&mut pose, &skeleton, moved, 1.0 / 60.0);
+        }
+        let mut pose = Pose::from_skeleton(&skeleton);
+        rig.update(&mut pose, &skeleton, moved, 1.0 / 60.0);
+        assert!(pose.bones[0].rotation.angle_between(Quat::IDENTITY) < 0.01);
+    }
+}
@@ -0,0 +1,225 @@
+//! Animation layers
+//!
+//! Layers play extra clips on top of the base clip of an `AnimationPlayer`,
+//! either adding motion relative to the clip's first frame (recoil,
+//! breathing, flinches) or overriding the base pose, optionally limited to
+//! part of the skeleton with a bone mask.
+
+use super::clip::AnimationClip;
+use super::pose::Pose;
+use super::skeleton::Skeleton;
+
+/// Per-bone layer weights
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoneMask {
+    /// Weight per bone, indexed like `Skeleton::bones`
+    pub weights: Vec<f32>,
+}
+
+impl BoneMask {
+    /// Create a mask that affects no bones
+    #[must_use]
+    pub fn empty(bone_count: usize) -> Self {
+        Self {
+            weights: vec![0.0; bone_count],
+        }
+    }
+
+    /// Create a mask covering a bone and all of its descendants
+    #[must_use]
+    pub fn from_branch(skeleton: &Skeleton, root: usize) -> Self {
+        let mut mask = Self::empty(skeleton.bone_count());
+        mask.add_branch(skeleton, root, 1.0);
+        mask
+    }
+
+    /// Set the weight of a bone and all of its descendants
+    pub fn add_branch(&mut self, skeleton: &Skeleton, root: usize, weight: f32) {
+        let mut stack = vec![root];
+        while let Some(bone) = stack.pop() {
+            if let Some(w) = self.weights.get_mut(bone) {
+                *w = weight;
+                stack.extend(skeleton.bones[bone].children.iter().copied());
+            }
+        }
+    }
+
+    /// Get the weight of a bone (0.0 for bones outside the mask)
+    #[must_use]
+    pub fn weight(&self, bone: usize) -> f32 {
+        self.weights.get(bone).copied().unwrap_or(0.0)
+    }
+}
+
+/// How a layer combines with the layers below it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayerBlend {
+    /// Add the clip's motion relative to its first frame
+    #[default]
+    Additive,
+    /// Blend towards the clip's pose
+    Override,
+}
+
+/// A clip layered on top of the base animation
+#[derive(Debug, Clone)]
+pub struct AnimationLayer {
+    /// Layer clip
+    pub clip: AnimationClip,
+    /// How the layer combines with the pose below
+    pub blend: LayerBlend,
+    /// Layer weight (0.0 to 1.0)
+    pub weight: f32,
+    /// Optional per-bone weights
+    pub mask: Option<BoneMask>,
+    /// Playback speed multiplier
+    pub speed: f32,
+    /// Whether the clip loops
+    pub looping: bool,
+    /// Current playback time in seconds
+    time: f32,
+}
+
+impl AnimationLayer {
+    /// Create a looping additive layer
+    #[must_use]
+    pub fn additive(clip: AnimationClip) -> Self {
+        Self {
+            clip,
+            blend: LayerBlend::Additive,
+            weight: 1.0,
+            mask: None,
+            speed: 1.0,
+            looping: true,
+            time: 0.0,
+        }
+    }
+
+    /// Create a looping override layer
+    #[must_use]
+    pub fn overriding(clip: AnimationClip) -> Self {
+        Self {
+            blend: LayerBlend::Override,
+            ..Self::additive(clip)
+        }
+    }
+
+    /// Set the layer weight
+    #[must_use]
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Limit the layer to masked bones
+    #[must_use]
+    pub fn with_mask(mut self, mask: BoneMask) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Play the clip once instead of looping (e.g. a flinch)
+    #[must_use]
+    pub fn once(mut self) -> Self {
+        self.looping = false;
+        self
+    }
+
+    /// Get the playback time
+    #[must_use]
+    pub const fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Restart the clip from the beginning
+    pub fn restart(&mut self) {
+        self.time = 0.0;
+    }
+
+    /// Check if a non-looping layer reached its end
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.time >= self.clip.duration
+    }
+
+    /// Advance playback
+    pub fn update(&mut self, delta_time: f32) {
+        if self.clip.duration <= 0.0 {
+            return;
+        }
+        self.time += delta_time * self.speed;
+        self.time = if self.looping {
+            self.time.rem_euclid(self.clip.duration)
+        } else {
+            self.time.clamp(0.0, self.clip.duration)
+        };
+    }
+
+    /// Apply this layer to a pose
+    pub fn apply(&self, pose: &mut Pose, skeleton: &Skeleton) {
+        if self.weight <= 0.0 {
+            return;
+        }
+        let sampled = Pose::sample(&self.clip, self.time, skeleton);
+        let reference = match self.blend {
+            LayerBlend::Additive => Some(Pose::sample(&self.clip, 0.0, skeleton)),
+            LayerBlend::Override => None,
+        };
+
+        for (index, bone) in pose.bones.iter_mut().enumerate() {
+            let weight = self.weight * self.mask.as_ref().map_or(1.0, |m| m.weight(index));
+            if weight <= 0.0 {
+                continue;
+            }
+            let Some(target) = sampled.bones.get(index) else {
+                continue;
+            };
+            match reference.as_ref().and_then(|r| r.bones.get(index)) {
+                Some(base) => {
+                    let rotation = base.rotation.inverse() * target.rotation;
+                    bone.translation += (target.translation - base.translation) * weight;
+                    bone.rotation =
+                        (bone.rotation * glam::Quat::IDENTITY.slerp(rotation, weight)).normalize();
+                    bone.scale *= glam::Vec3::ONE.lerp(target.scale / base.scale, weight);
+                }
+                None => *bone = bone.lerp(target, weight),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::{Bone, Channel, Keyframe};
+    use glam::Vec3;
+
+    #[test]
+    fn test_additive_layer_respects_mask() {
+        let mut skeleton = Skeleton::new();
+        skeleton.add_bone(Bone::new("hips"));
+        let spine = skeleton.add_bone(Bone::new("spine"));
+        skeleton.set_parent(spine, 0);
+
+        let mut recoil = AnimationClip::new("recoil");
+        for bone in [0, 1] {
+            recoil.add_channel(
+                bone,
+                Channel::Translation(vec![
+                    Keyframe::new(0.0, Vec3::ZERO),
+                    Keyframe::new(1.0, Vec3::new(0.0, 0.0, -1.0)),
+                ]),
+            );
+        }
+
+        let mut layer = AnimationLayer::additive(recoil)
+            .with_weight(0.5)
+            .with_mask(BoneMask::from_branch(&skeleton, spine));
+        layer.update(1.0 - f32::EPSILON);
+
+        let mut pose = Pose::from_skeleton(&skeleton);
+        layer.apply(&mut pose, &skeleton);
+        assert_eq!(pose.bones[0].translation, Vec3::ZERO);
+        assert!((pose.bones[1].translation.z + 0.5).abs() < 0.01);
+    }
+}
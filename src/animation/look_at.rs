@@ -0,0 +1,213 @@
+//! Look-at and aim constraints
+//!
+//! Procedural constraints applied after clip evaluation that turn a bone
+//! chain (spine to head, or shoulder to hand) so an axis of its last bone
+//! points at a world-space target, within yaw and pitch limits and with
+//! smoothing, while the body animation keeps playing underneath.
+
+use glam::{Mat4, Quat, Vec3};
+
+use super::ik::rotate_world;
+use super::pose::Pose;
+use super::skeleton::Skeleton;
+
+/// Turns a bone chain toward a target
+#[derive(Debug, Clone, PartialEq)]
+pub struct LookAtConstraint {
+    /// Bones from the base of the chain to the aiming bone, with the share
+    /// of the rotation each takes
+    pub chain: Vec<(usize, f32)>,
+    /// Axis of the last bone that should point at the target, in its local
+    /// space
+    pub aim_axis: Vec3,
+    /// Largest turn left or right, in radians
+    pub max_yaw: f32,
+    /// Largest turn up or down, in radians
+    pub max_pitch: f32,
+    /// How fast the constraint follows target changes (per second; 0 snaps)
+    pub smoothing: f32,
+    /// Blend between the animated pose (0.0) and the constrained pose (1.0)
+    pub weight: f32,
+    /// World-space target, or `None` to relax back to the animation
+    pub target: Option<Vec3>,
+    /// Smoothed model-space rotation currently applied
+    current: Quat,
+}
+
+impl LookAtConstraint {
+    /// Create a constraint over a chain, shared evenly, aiming local +Z
+    #[must_use]
+    pub fn new(chain: &[usize]) -> Self {
+        let share = 1.0 / chain.len().max(1) as f32;
+        Self {
+            chain: chain.iter().map(|&bone| (bone, share)).collect(),
+            aim_axis: Vec3::Z,
+            max_yaw: 70f32.to_radians(),
+            max_pitch: 40f32.to_radians(),
+            smoothing: 8.0,
+            weight: 1.0,
+            target: None,
+            current: Quat::IDENTITY,
+        }
+    }
+
+    /// Set per-bone shares of the rotation, base first (normalized to sum to 1)
+    #[must_use]
+    pub fn with_shares(mut self, shares: &[f32]) -> Self {
+        let total: f32 = shares.iter().take(self.chain.len()).sum();
+        if total > 0.0 {
+            for ((_, share), &s) in self.chain.iter_mut().zip(shares) {
+                *share = s / total;
+            }
+        }
+        self
+    }
+
+    /// Set the aiming axis in the last bone's local space
+    #[must_use]
+    pub fn with_aim_axis(mut self, axis: Vec3) -> Self {
+        self.aim_axis = axis.try_normalize().unwrap_or(Vec3::Z);
+        self
+    }
+
+    /// Set the yaw and pitch limits in radians
+    #[must_use]
+    pub fn with_limits(mut self, max_yaw: f32, max_pitch: f32) -> Self {
+        self.max_yaw = max_yaw.max(0.0);
+        self.max_pitch = max_pitch.max(0.0);
+        self
+    }
+
+    /// Set the smoothing rate (0 snaps immediately)
+    #[must_use]
+    pub fn with_smoothing(mut self, smoothing: f32) -> Self {
+        self.smoothing = smoothing.max(0.0);
+        self
+    }
+
+    /// Set the blend weight
+    #[must_use]
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set or clear the world-space target
+    pub fn set_target(&mut self, target: Option<Vec3>) {
+        self.target = target;
+    }
+
+    /// Drop the smoothed rotation, e.g. after a teleport
+    pub fn reset(&mut self) {
+        self.current = Quat::IDENTITY;
+    }
+
+    /// Turn the chain toward the target, modifying the pose in place
+    ///
+    /// `model` is the skeleton's world transform. Limits are measured in
+    /// the model's frame (+Z forward, +Y up) relative to where the
+    /// animation already points the aiming bone.
+    pub fn update(&mut self, pose: &mut Pose, skeleton: &Skeleton, model: Mat4, delta_time: f32) {
+        let Some(&(aim_bone, _)) = self.chain.last() else {
+            return;
+        };
+        if self.chain.iter().any(|&(bone, _)| bone >= pose.bones.len()) {
+            return;
+        }
+
+        let world = pose.world_matrices(skeleton);
+        let aim_world = world[aim_bone];
+        let eye = aim_world.w_axis.truncate();
+        let forward = aim_world
+            .transform_vector3(self.aim_axis)
+            .try_normalize()
+            .unwrap_or(Vec3::Z);
+
+        let desired = self
+            .target
+            .map(|target| model.inverse().transform_point3(target) - eye)
+            .and_then(Vec3::try_normalize)
+            .map_or(Quat::IDENTITY, |direction| {
+                let (yaw, pitch) = yaw_pitch(forward);
+                let (target_yaw, target_pitch) = yaw_pitch(direction);
+                let delta_yaw = wrap_angle(target_yaw - yaw).clamp(-self.max_yaw, self.max_yaw);
+                let pitch = (pitch + (target_pitch - pitch).clamp(-self.max_pitch, self.max_pitch))
+                    .clamp(-1.5, 1.5);
+                Quat::from_rotation_arc(forward, direction_of(yaw + delta_yaw, pitch))
+            });
+
+        let t = if self.smoothing > 0.0 {
+            1.0 - (-self.smoothing * delta_time).exp()
+        } else {
+            1.0
+        };
+        self.current = self.current.slerp(desired, t).normalize();
+
+        let total = Quat::IDENTITY.slerp(self.current, self.weight);
+        for &(bone, share) in &self.chain {
+            let world = pose.world_matrices(skeleton);
+            let delta = Quat::IDENTITY.slerp(total, share);
+            rotate_world(pose, skeleton, &world, bone, delta);
+        }
+    }
+}
+
+/// Yaw around +Y (0 along +Z) and pitch above the horizon of a direction
+fn yaw_pitch(direction: Vec3) -> (f32, f32) {
+    (
+        direction.x.atan2(direction.z),
+        direction.y.clamp(-1.0, 1.0).asin(),
+    )
+}
+
+/// Direction for a yaw and pitch
+fn direction_of(yaw: f32, pitch: f32) -> Vec3 {
+    let (sin_yaw, cos_yaw) = yaw.sin_cos();
+    let (sin_pitch, cos_pitch) = pitch.sin_cos();
+    Vec3::new(sin_yaw * cos_pitch, sin_pitch, cos_yaw * cos_pitch)
+}
+
+/// Wrap an angle into [-pi, pi]
+fn wrap_angle(angle: f32) -> f32 {
+    use std::f32::consts::{PI, TAU};
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::Bone;
+
+    #[test]
+    fn test_look_at_respects_yaw_limit() {
+        let mut skeleton = Skeleton::new();
+        let mut neck = Bone::new("neck");
+        neck.translation = Vec3::new(0.0, 1.5, 0.0);
+        let neck = skeleton.add_bone(neck);
+        let mut head = Bone::new("head");
+        head.translation = Vec3::new(0.0, 0.2, 0.0);
+        let head = skeleton.add_bone(head);
+        skeleton.set_parent(head, neck);
+
+        let mut look = LookAtConstraint::new(&[neck, head])
+            .with_limits(std::f32::consts::FRAC_PI_4, 0.5)
+            .with_smoothing(0.0);
+
+        // Target straight to the right, beyond the 45 degree limit
+        look.set_target(Some(Vec3::new(10.0, 1.7, 0.0)));
+        let mut pose = Pose::from_skeleton(&skeleton);
+        look.update(&mut pose, &skeleton, Mat4::IDENTITY, 0.016);
+
+        let world = pose.world_matrices(&skeleton);
+        let forward = world[head].transform_vector3(Vec3::Z).normalize();
+        let (yaw, _) = yaw_pitch(forward);
+        assert!((yaw - std::f32::consts::FRAC_PI_4).abs() < 1e-3);
+
+        // Clearing the target relaxes back to the animated pose
+        look.set_target(None);
+        let mut pose = Pose::from_skeleton(&skeleton);
+        look.update(&mut pose, &skeleton, Mat4::IDENTITY, 0.016);
+        let world = pose.world_matrices(&skeleton);
+        assert!(world[head].transform_vector3(Vec3::Z).distance(Vec3::Z) < 1e-4);
+    }
+}
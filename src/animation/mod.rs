@@ -1,11 +1,33 @@
 //! Animation system
 //!
-//! Provides skeletal animation, animation clips, and playback control.
+//! Provides skeletal animation, animation clips, playback control,
+//! socket attachments, state machines, blend trees, inverse kinematics,
+//! look-at constraints, jiggle bones, and retargeting.
 
+mod blend_tree;
 mod clip;
+mod ik;
+mod jiggle;
+mod layer;
+mod look_at;
 mod player;
+mod pose;
+mod ragdoll;
+mod retarget;
 mod skeleton;
+mod socket;
+mod state_machine;
 
-pub use clip::{AnimationClip, Channel, Interpolation, Keyframe};
-pub use player::{AnimationPlayer, PlaybackState};
-pub use skeleton::{Bone, Skeleton, SkinningData};
+pub use blend_tree::{BlendTree, BlendTree1D, BlendTree2D};
+pub use clip::{AnimationClip, AnimationEvent, Channel, Interpolation, Keyframe};
+pub use ik::{CcdIk, IkConstraint, IkRig, TwoBoneIk};
+pub use jiggle::{JiggleBone, JiggleRig};
+pub use layer::{AnimationLayer, BoneMask, LayerBlend};
+pub use look_at::LookAtConstraint;
+pub use player::{AnimationPlayer, ClipEnd, LoopMode, PlaybackState, RootMotion, RootMotionConfig};
+pub use pose::{BoneTransform, Pose};
+pub use ragdoll::{GetUpClips, RagdollBlend, RagdollFacing};
+pub use retarget::{BoneMap, Retargeter};
+pub use skeleton::{Bone, Skeleton, SkinningData, Socket};
+pub use socket::{AttachedTo, resolve_attachments};
+pub use state_machine::{AnimationState, AnimationStateMachine, Condition, Transition};
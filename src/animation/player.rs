@@ -2,7 +2,13 @@
 //!
 //! Provides animation player for controlling clip playback.
 
-use super::clip::AnimationClip;
+use glam::{Quat, Vec3};
+
+use super::blend_tree::BlendTree;
+use super::clip::{AnimationClip, AnimationEvent};
+use super::layer::AnimationLayer;
+use super::pose::Pose;
+use super::skeleton::Skeleton;
 
 /// Playback state
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -16,8 +22,158 @@ pub enum PlaybackState {
     Stopped,
 }
 
-/// Animation player for controlling playback
+/// What happens when playback reaches the end of a clip
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoopMode {
+    /// Play once and stop on the last frame
+    Once,
+    /// Wrap around to the other end
+    #[default]
+    Loop,
+    /// Reverse direction at each end
+    PingPong,
+    /// Hold the last frame while staying in the playing state
+    ClampForever,
+}
+
+/// Reason an end-of-clip callback fired
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipEnd {
+    /// A looping clip wrapped around
+    Looped,
+    /// A ping-pong clip reversed direction
+    Bounced,
+    /// A `Once` or `ClampForever` clip reached its end
+    Finished,
+}
+
+/// Callback invoked with the reason and clip name at each clip end
+type EndCallback = Box<dyn FnMut(ClipEnd, &str) + Send + Sync>;
+
+/// Which parts of the root bone's motion are extracted from the pose
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RootMotionConfig {
+    /// Root bone index
+    pub bone: usize,
+    /// Extract vertical translation too (e.g. for jumps driven by animation)
+    pub extract_vertical: bool,
+    /// Extract rotation around the vertical axis
+    pub extract_rotation: bool,
+}
+
+impl RootMotionConfig {
+    /// Extract horizontal translation and rotation of a bone
+    #[must_use]
+    pub const fn new(bone: usize) -> Self {
+        Self {
+            bone,
+            extract_vertical: false,
+            extract_rotation: true,
+        }
+    }
+
+    /// Set whether vertical translation is extracted
+    #[must_use]
+    pub const fn with_vertical(mut self, extract_vertical: bool) -> Self {
+        self.extract_vertical = extract_vertical;
+        self
+    }
+
+    /// Set whether rotation is extracted
+    #[must_use]
+    pub const fn with_rotation(mut self, extract_rotation: bool) -> Self {
+        self.extract_rotation = extract_rotation;
+        self
+    }
+}
+
+/// Root bone movement extracted from the animation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RootMotion {
+    /// Translation in the clip's model space
+    pub translation: Vec3,
+    /// Rotation around the vertical axis
+    pub rotation: Quat,
+}
+
+impl RootMotion {
+    /// No movement
+    pub const IDENTITY: Self = Self {
+        translation: Vec3::ZERO,
+        rotation: Quat::IDENTITY,
+    };
+
+    /// Append another delta
+    #[must_use]
+    pub fn then(self, other: Self) -> Self {
+        Self {
+            translation: self.translation + other.translation,
+            rotation: (self.rotation * other.rotation).normalize(),
+        }
+    }
+
+    /// Interpolate towards another delta
+    #[must_use]
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Self {
+            translation: self.translation.lerp(other.translation, t),
+            rotation: self.rotation.slerp(other.rotation, t),
+        }
+    }
+}
+
+impl Default for RootMotion {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Blend tree driving the base pose, with its current weights
+#[derive(Debug, Clone)]
+struct ActiveBlend {
+    /// Blend tree
+    tree: BlendTree,
+    /// Clip weights from the last parameter update
+    weights: Vec<f32>,
+}
+
+impl ActiveBlend {
+    /// Placeholder clip that carries the blended cycle length
+    fn clip(&self) -> AnimationClip {
+        let mut clip = AnimationClip::new("blend tree");
+        clip.duration = self.tree.duration(&self.weights);
+        clip
+    }
+
+    /// Sample the blend at a time within the placeholder clip
+    fn sample(&self, clip: &AnimationClip, time: f32, skeleton: &Skeleton) -> Pose {
+        let normalized = if clip.duration > 0.0 {
+            time / clip.duration
+        } else {
+            0.0
+        };
+        self.tree.sample(&self.weights, normalized, skeleton)
+    }
+}
+
+/// Outgoing clip that is being faded out
 #[derive(Debug)]
+struct Crossfade {
+    /// Clip being faded out
+    clip: AnimationClip,
+    /// Blend tree being faded out, if the outgoing state was a blend
+    blend: Option<ActiveBlend>,
+    /// Playback time of the outgoing clip
+    time: f32,
+    /// Whether the outgoing clip is on the reverse leg of a ping-pong
+    reversed: bool,
+    /// Time since the crossfade started
+    elapsed: f32,
+    /// Total crossfade duration
+    duration: f32,
+}
+
+/// Animation player for controlling playback
 pub struct AnimationPlayer {
     /// Currently playing clip
     clip: Option<AnimationClip>,
@@ -25,12 +181,28 @@ pub struct AnimationPlayer {
     current_time: f32,
     /// Playback speed multiplier
     speed: f32,
-    /// Whether to loop the animation
-    looping: bool,
+    /// What happens at the end of the clip
+    loop_mode: LoopMode,
+    /// Whether a ping-pong clip is on its reverse leg
+    reversed: bool,
     /// Current playback state
     state: PlaybackState,
     /// Blend weight (for animation blending)
     weight: f32,
+    /// Clip being faded out during a crossfade
+    crossfade: Option<Crossfade>,
+    /// Root motion extraction settings (disabled when `None`)
+    root_motion_config: Option<RootMotionConfig>,
+    /// Root motion accumulated since the last `take_root_motion`
+    root_motion: RootMotion,
+    /// Events passed since the last `drain_events`
+    events: Vec<AnimationEvent>,
+    /// Layers applied on top of the base clip, bottom first
+    layers: Vec<AnimationLayer>,
+    /// Blend tree replacing the base clip's pose
+    blend: Option<ActiveBlend>,
+    /// Called when the clip loops, bounces or finishes
+    on_clip_end: Option<EndCallback>,
 }
 
 impl AnimationPlayer {
@@ -41,16 +213,213 @@ impl AnimationPlayer {
             clip: None,
             current_time: 0.0,
             speed: 1.0,
-            looping: true,
+            loop_mode: LoopMode::Loop,
+            reversed: false,
             state: PlaybackState::Stopped,
             weight: 1.0,
+            crossfade: None,
+            root_motion_config: None,
+            root_motion: RootMotion::IDENTITY,
+            events: Vec::new(),
+            layers: Vec::new(),
+            blend: None,
+            on_clip_end: None,
         }
     }
 
     /// Set the animation clip to play
+    ///
+    /// Playback starts at the beginning, or at the end for a negative speed.
     pub fn set_clip(&mut self, clip: AnimationClip) {
+        self.current_time = self.start_time(&clip);
+        self.reversed = false;
         self.clip = Some(clip);
-        self.current_time = 0.0;
+        self.crossfade = None;
+        self.blend = None;
+    }
+
+    /// Start playing a clip, blending from the current clip over `duration` seconds
+    ///
+    /// The outgoing clip keeps advancing while it fades out. Without a
+    /// current clip (or with a zero duration) this switches immediately.
+    /// Clips in the same sync group start and stay at the same normalized
+    /// time, so walk and run cycles keep their feet aligned.
+    pub fn crossfade_to(&mut self, clip: AnimationClip, duration: f32) {
+        match self.clip.take() {
+            Some(outgoing) if duration > 0.0 => {
+                let synced = outgoing.is_synced_with(&clip) && outgoing.duration > 0.0;
+                let start = if synced {
+                    self.current_time / outgoing.duration * clip.duration
+                } else {
+                    self.start_time(&clip)
+                };
+                self.crossfade = Some(Crossfade {
+                    clip: outgoing,
+                    blend: self.blend.take(),
+                    time: self.current_time,
+                    reversed: self.reversed,
+                    elapsed: 0.0,
+                    duration,
+                });
+                self.current_time = start;
+                self.reversed &= synced;
+                self.clip = Some(clip);
+            }
+            _ => self.set_clip(clip),
+        }
+        self.state = PlaybackState::Playing;
+    }
+
+    /// Play a blend tree as the base pose
+    ///
+    /// Weights start at all parameters being zero; update them with
+    /// `set_blend_parameters`. Root motion is not extracted from blend trees.
+    pub fn set_blend_tree(&mut self, tree: impl Into<BlendTree>) {
+        let blend = Self::start_blend(tree.into());
+        self.set_clip(blend.clip());
+        self.blend = Some(blend);
+    }
+
+    /// Start a blend tree, blending from the current clip over `duration` seconds
+    pub fn crossfade_to_blend_tree(&mut self, tree: impl Into<BlendTree>, duration: f32) {
+        let blend = Self::start_blend(tree.into());
+        self.crossfade_to(blend.clip(), duration);
+        self.blend = Some(blend);
+    }
+
+    /// Recompute the blend tree's weights from named parameters
+    ///
+    /// The cycle length follows the weighted clip durations while keeping
+    /// the normalized playback position.
+    pub fn set_blend_parameters(&mut self, parameter: impl Fn(&str) -> f32) {
+        let Some(blend) = &mut self.blend else {
+            return;
+        };
+        blend.weights = blend.tree.weights(parameter);
+        let duration = blend.tree.duration(&blend.weights);
+        if let Some(clip) = &mut self.clip {
+            if clip.duration > 0.0 {
+                self.current_time *= duration / clip.duration;
+            }
+            clip.duration = duration;
+        }
+    }
+
+    /// Get the blend tree's current clip weights
+    #[must_use]
+    pub fn blend_weights(&self) -> Option<&[f32]> {
+        self.blend.as_ref().map(|b| b.weights.as_slice())
+    }
+
+    /// Playback steps for the current and outgoing clip
+    ///
+    /// Synced clips advance by the same fraction of a cycle, with the
+    /// cycle length blended by the crossfade weight.
+    fn steps(&self, step: f32) -> (f32, f32) {
+        match (&self.clip, &self.crossfade) {
+            (Some(clip), Some(fade))
+                if clip.is_synced_with(&fade.clip)
+                    && clip.duration > 0.0
+                    && fade.clip.duration > 0.0 =>
+            {
+                let weight = self.crossfade_weight();
+                let cycle = fade.clip.duration + (clip.duration - fade.clip.duration) * weight;
+                let phase = step / cycle;
+                (phase * clip.duration, phase * fade.clip.duration)
+            }
+            _ => (step, step),
+        }
+    }
+
+    /// Time a clip starts at for the current playback direction
+    fn start_time(&self, clip: &AnimationClip) -> f32 {
+        if self.speed < 0.0 { clip.duration } else { 0.0 }
+    }
+
+    fn start_blend(tree: BlendTree) -> ActiveBlend {
+        let weights = tree.weights(|_| 0.0);
+        ActiveBlend { tree, weights }
+    }
+
+    /// Check if a crossfade is in progress
+    #[must_use]
+    pub const fn is_crossfading(&self) -> bool {
+        self.crossfade.is_some()
+    }
+
+    /// Get the weight of the incoming clip (1.0 when not crossfading)
+    #[must_use]
+    pub fn crossfade_weight(&self) -> f32 {
+        self.crossfade
+            .as_ref()
+            .map_or(1.0, |fade| (fade.elapsed / fade.duration).clamp(0.0, 1.0))
+    }
+
+    /// Sample the current pose, blending the outgoing clip during a crossfade
+    /// and applying layers in order
+    ///
+    /// Returns `None` if no clip is set.
+    #[must_use]
+    pub fn sample_pose(&self, skeleton: &Skeleton) -> Option<Pose> {
+        let clip = self.clip.as_ref()?;
+        let mut incoming = match &self.blend {
+            Some(blend) => blend.sample(clip, self.current_time, skeleton),
+            None => Pose::sample(clip, self.current_time, skeleton),
+        };
+        self.strip_root_motion(&mut incoming, clip, self.current_time);
+        let mut pose = match &self.crossfade {
+            Some(fade) => {
+                let mut outgoing = match &fade.blend {
+                    Some(blend) => blend.sample(&fade.clip, fade.time, skeleton),
+                    None => Pose::sample(&fade.clip, fade.time, skeleton),
+                };
+                self.strip_root_motion(&mut outgoing, &fade.clip, fade.time);
+                outgoing.blend(&incoming, self.crossfade_weight())
+            }
+            None => incoming,
+        };
+        for layer in &self.layers {
+            layer.apply(&mut pose, skeleton);
+        }
+        Some(pose)
+    }
+
+    /// Sample morph target weights, blending the outgoing clip during a
+    /// crossfade
+    ///
+    /// `target` selects the channel by its target (glTF node index); `None`
+    /// uses the first morph channel of the current clip. Returns `None` if
+    /// the current clip has no matching channel.
+    #[must_use]
+    pub fn sample_morph_weights(&self, target: Option<usize>) -> Option<Vec<f32>> {
+        let clip = self.clip.as_ref()?;
+        let target = target.or_else(|| clip.morph_target())?;
+        let incoming = clip.sample_morph_weights(target, self.current_time)?;
+        let Some(outgoing) = self
+            .crossfade
+            .as_ref()
+            .and_then(|fade| fade.clip.sample_morph_weights(target, fade.time))
+        else {
+            return Some(incoming);
+        };
+        let t = self.crossfade_weight();
+        Some(
+            incoming
+                .iter()
+                .enumerate()
+                .map(|(i, &w)| {
+                    let from = outgoing.get(i).copied().unwrap_or(0.0);
+                    from + (w - from) * t
+                })
+                .collect(),
+        )
+    }
+
+    /// Sample the current pose and write it to a skeleton
+    pub fn evaluate(&self, skeleton: &mut Skeleton) {
+        if let Some(pose) = self.sample_pose(skeleton) {
+            pose.apply(skeleton);
+        }
     }
 
     /// Start or resume playback
@@ -65,57 +434,217 @@ impl AnimationPlayer {
         self.state = PlaybackState::Paused;
     }
 
-    /// Stop playback and reset to beginning
+    /// Stop playback and reset to the start
     pub fn stop(&mut self) {
         self.state = PlaybackState::Stopped;
-        self.current_time = 0.0;
+        self.current_time = self.clip.as_ref().map_or(0.0, |clip| self.start_time(clip));
+        self.reversed = false;
+        self.crossfade = None;
     }
 
-    /// Seek to a specific time
+    /// Jump to a time in seconds without firing events or root motion
+    ///
+    /// Looping modes wrap the time into the clip, others clamp it.
     pub fn seek(&mut self, time: f32) {
         if let Some(clip) = &self.clip {
-            self.current_time = time.clamp(0.0, clip.duration);
+            self.current_time = match self.loop_mode {
+                LoopMode::Loop if clip.duration > 0.0 => time.rem_euclid(clip.duration),
+                _ => time.clamp(0.0, clip.duration),
+            };
         }
     }
 
+    /// Set a callback invoked when the clip loops, bounces or finishes
+    ///
+    /// Runs inside `update`, after the clip's events for the step are
+    /// queued.
+    pub fn on_clip_end(&mut self, callback: impl FnMut(ClipEnd, &str) + Send + Sync + 'static) {
+        self.on_clip_end = Some(Box::new(callback));
+    }
+
+    /// Remove the end-of-clip callback
+    pub fn clear_clip_end_callback(&mut self) {
+        self.on_clip_end = None;
+    }
+
     /// Update playback (call each frame)
     pub fn update(&mut self, delta_time: f32) {
         if self.state != PlaybackState::Playing {
             return;
         }
 
-        if let Some(clip) = &self.clip {
-            // Avoid division/modulo by zero for empty clips
-            if clip.duration <= 0.0 {
-                self.current_time = 0.0;
-                if !self.looping {
-                    self.state = PlaybackState::Stopped;
-                }
-                return;
-            }
+        for layer in &mut self.layers {
+            layer.update(delta_time);
+        }
 
-            self.current_time += delta_time * self.speed;
+        let (step, fade_step) = self.steps(delta_time * self.speed);
+        let progress = self.clip.as_ref().map(|clip| {
+            advance(
+                clip.duration,
+                self.current_time,
+                step,
+                self.loop_mode,
+                self.reversed,
+            )
+        });
 
-            if self.current_time >= clip.duration {
-                if self.looping {
-                    self.current_time %= clip.duration;
-                } else {
-                    self.current_time = clip.duration;
-                    self.state = PlaybackState::Stopped;
+        if let (Some(clip), Some(progress)) = (&self.clip, &progress) {
+            collect_events(clip, &progress.segments, &mut self.events);
+        }
+
+        if let Some(config) = self.root_motion_config {
+            let mut delta = match (&self.clip, &progress) {
+                (Some(clip), Some(progress)) => {
+                    root_motion_delta(clip, &config, &progress.segments)
                 }
+                _ => RootMotion::IDENTITY,
+            };
+            if let Some(fade) = &self.crossfade {
+                let outgoing = advance(
+                    fade.clip.duration,
+                    fade.time,
+                    fade_step,
+                    self.loop_mode,
+                    fade.reversed,
+                );
+                let outgoing = root_motion_delta(&fade.clip, &config, &outgoing.segments);
+                delta = outgoing.lerp(delta, self.crossfade_weight());
             }
+            self.root_motion = self.root_motion.then(delta);
+        }
 
-            if self.current_time < 0.0 {
-                if self.looping {
-                    self.current_time = clip.duration + self.current_time % clip.duration;
-                } else {
-                    self.current_time = 0.0;
-                    self.state = PlaybackState::Stopped;
-                }
+        if let Some(fade) = &mut self.crossfade {
+            fade.elapsed += delta_time;
+            if fade.elapsed >= fade.duration {
+                self.crossfade = None;
+            } else {
+                let outgoing = advance(
+                    fade.clip.duration,
+                    fade.time,
+                    fade_step,
+                    self.loop_mode,
+                    fade.reversed,
+                );
+                fade.time = outgoing.time;
+                fade.reversed = outgoing.reversed;
+            }
+        }
+
+        let (Some(clip), Some(progress)) = (&self.clip, progress) else {
+            return;
+        };
+        self.current_time = progress.time;
+        self.reversed = progress.reversed;
+        if progress.finished && self.loop_mode == LoopMode::Once {
+            self.state = PlaybackState::Stopped;
+        }
+        if let Some(callback) = &mut self.on_clip_end {
+            for end in progress.ends {
+                callback(end, &clip.name);
             }
         }
     }
 
+    /// Add a layer on top of the existing ones and return its index
+    pub fn add_layer(&mut self, layer: AnimationLayer) -> usize {
+        self.layers.push(layer);
+        self.layers.len() - 1
+    }
+
+    /// Remove a layer
+    pub fn remove_layer(&mut self, index: usize) -> Option<AnimationLayer> {
+        (index < self.layers.len()).then(|| self.layers.remove(index))
+    }
+
+    /// Get a layer
+    #[must_use]
+    pub fn layer(&self, index: usize) -> Option<&AnimationLayer> {
+        self.layers.get(index)
+    }
+
+    /// Get a mutable layer
+    pub fn layer_mut(&mut self, index: usize) -> Option<&mut AnimationLayer> {
+        self.layers.get_mut(index)
+    }
+
+    /// Set a layer's weight
+    pub fn set_layer_weight(&mut self, index: usize, weight: f32) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.weight = weight.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Get the number of layers
+    #[must_use]
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Take the events passed since the last call, in playback order
+    ///
+    /// Each event fires once per pass through the clip, including every
+    /// wrap of a looping clip. Only the current clip emits events during a
+    /// crossfade.
+    pub fn drain_events(&mut self) -> Vec<AnimationEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Enable root motion extraction
+    ///
+    /// The root bone's movement is removed from sampled poses and
+    /// accumulated for `take_root_motion`.
+    pub fn enable_root_motion(&mut self, config: RootMotionConfig) {
+        self.root_motion_config = Some(config);
+        self.root_motion = RootMotion::IDENTITY;
+    }
+
+    /// Disable root motion extraction
+    pub fn disable_root_motion(&mut self) {
+        self.root_motion_config = None;
+        self.root_motion = RootMotion::IDENTITY;
+    }
+
+    /// Get the root motion settings
+    #[must_use]
+    pub const fn root_motion_config(&self) -> Option<&RootMotionConfig> {
+        self.root_motion_config.as_ref()
+    }
+
+    /// Take the root motion accumulated since the last call
+    ///
+    /// Rotate the translation by the character's orientation and feed it to
+    /// the physics body or character controller.
+    pub fn take_root_motion(&mut self) -> RootMotion {
+        std::mem::take(&mut self.root_motion)
+    }
+
+    /// Pin the root bone to the clip's first frame for extracted components
+    fn strip_root_motion(&self, pose: &mut Pose, clip: &AnimationClip, time: f32) {
+        let Some(config) = self.root_motion_config else {
+            return;
+        };
+        let Some(bone) = pose.bones.get_mut(config.bone) else {
+            return;
+        };
+        if let Some(start) = clip.sample_translation(config.bone, 0.0) {
+            let y = if config.extract_vertical {
+                start.y
+            } else {
+                bone.translation.y
+            };
+            bone.translation = Vec3::new(start.x, y, start.z);
+        }
+        if config.extract_rotation
+            && let (Some(start), Some(current)) = (
+                clip.sample_rotation(config.bone, 0.0),
+                clip.sample_rotation(config.bone, time),
+            )
+        {
+            // Remove only the yaw change, keeping pitch and roll
+            bone.rotation = (yaw_of(start) * yaw_of(current).inverse() * current).normalize();
+        }
+    }
+
     /// Get current playback time
     #[must_use]
     pub const fn current_time(&self) -> f32 {
@@ -145,15 +674,37 @@ impl AnimationPlayer {
         self.speed = speed;
     }
 
-    /// Check if looping
+    /// Check if the clip wraps or bounces at its ends
     #[must_use]
     pub const fn is_looping(&self) -> bool {
-        self.looping
+        matches!(self.loop_mode, LoopMode::Loop | LoopMode::PingPong)
     }
 
-    /// Set looping
+    /// Set looping (`Loop` when true, `Once` when false)
     pub fn set_looping(&mut self, looping: bool) {
-        self.looping = looping;
+        self.set_loop_mode(if looping {
+            LoopMode::Loop
+        } else {
+            LoopMode::Once
+        });
+    }
+
+    /// Get the loop mode
+    #[must_use]
+    pub const fn loop_mode(&self) -> LoopMode {
+        self.loop_mode
+    }
+
+    /// Set what happens at the end of the clip
+    pub fn set_loop_mode(&mut self, mode: LoopMode) {
+        self.loop_mode = mode;
+        self.reversed = false;
+    }
+
+    /// Check if a ping-pong clip is currently playing backwards
+    #[must_use]
+    pub const fn is_reversed(&self) -> bool {
+        self.reversed
     }
 
     /// Get blend weight
@@ -188,6 +739,195 @@ impl AnimationPlayer {
     }
 }
 
+/// Root translation and yaw of a clip at a time
+fn sample_root(clip: &AnimationClip, config: &RootMotionConfig, time: f32) -> (Vec3, Quat) {
+    let translation = clip
+        .sample_translation(config.bone, time)
+        .unwrap_or(Vec3::ZERO);
+    let rotation = clip
+        .sample_rotation(config.bone, time)
+        .map_or(Quat::IDENTITY, yaw_of);
+    (translation, rotation)
+}
+
+/// Root motion between two times within one pass of a clip
+fn segment_delta(
+    clip: &AnimationClip,
+    config: &RootMotionConfig,
+    from: f32,
+    to: f32,
+) -> RootMotion {
+    let (t0, r0) = sample_root(clip, config, from);
+    let (t1, r1) = sample_root(clip, config, to);
+    let mut translation = t1 - t0;
+    if !config.extract_vertical {
+        translation.y = 0.0;
+    }
+    RootMotion {
+        translation,
+        rotation: if config.extract_rotation {
+            (r0.inverse() * r1).normalize()
+        } else {
+            Quat::IDENTITY
+        },
+    }
+}
+
+/// A stretch of a clip passed in one direction
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Segment {
+    /// Start time
+    from: f32,
+    /// End time (less than `from` when playing backwards)
+    to: f32,
+    /// Whether playback stops at `to`, so events exactly there fire
+    terminal: bool,
+}
+
+/// Result of advancing playback through a clip
+#[derive(Debug, Clone, Default)]
+struct Progress {
+    /// New playback time
+    time: f32,
+    /// Whether a ping-pong clip is now on its reverse leg
+    reversed: bool,
+    /// Stretches passed, in playback order
+    segments: Vec<Segment>,
+    /// Clip ends reached, in playback order
+    ends: Vec<ClipEnd>,
+    /// Whether playback reached a terminal end
+    finished: bool,
+}
+
+/// Advance playback by `step` seconds (negative to play backwards)
+fn advance(duration: f32, time: f32, step: f32, mode: LoopMode, reversed: bool) -> Progress {
+    let mut progress = Progress {
+        time,
+        reversed,
+        ..Default::default()
+    };
+    if duration <= 0.0 {
+        progress.time = 0.0;
+        progress.finished = mode == LoopMode::Once && step != 0.0;
+        return progress;
+    }
+
+    let mut remaining = step.abs();
+    let mut forward = (step > 0.0) != reversed;
+    while remaining > 0.0 {
+        let boundary = if forward { duration } else { 0.0 };
+        let distance = (boundary - progress.time).abs();
+        if remaining < distance {
+            let to = progress.time + if forward { remaining } else { -remaining };
+            progress.segments.push(Segment {
+                from: progress.time,
+                to,
+                terminal: false,
+            });
+            progress.time = to;
+            break;
+        }
+
+        let terminal = matches!(mode, LoopMode::Once | LoopMode::ClampForever);
+        if distance > 0.0 {
+            progress.segments.push(Segment {
+                from: progress.time,
+                to: boundary,
+                terminal,
+            });
+        }
+        remaining -= distance;
+        progress.time = boundary;
+        match mode {
+            LoopMode::Loop => {
+                progress.ends.push(ClipEnd::Looped);
+                progress.time = duration - boundary;
+            }
+            LoopMode::PingPong => {
+                progress.ends.push(ClipEnd::Bounced);
+                progress.reversed = !progress.reversed;
+                forward = !forward;
+            }
+            LoopMode::Once | LoopMode::ClampForever => {
+                // Holding at the end only reports the first arrival
+                if distance > 0.0 {
+                    progress.ends.push(ClipEnd::Finished);
+                }
+                progress.finished = true;
+                break;
+            }
+        }
+    }
+    progress
+}
+
+/// Root motion accumulated over the passed segments of a clip
+fn root_motion_delta(
+    clip: &AnimationClip,
+    config: &RootMotionConfig,
+    segments: &[Segment],
+) -> RootMotion {
+    segments
+        .iter()
+        .fold(RootMotion::IDENTITY, |delta, segment| {
+            delta.then(segment_delta(clip, config, segment.from, segment.to))
+        })
+}
+
+/// Collect events passed in the segments of a clip
+///
+/// Forward segments fire events in `[from, to)`, reverse ones in
+/// `(to, from]`. A segment where playback stops also fires events placed
+/// exactly at its end.
+fn collect_events(clip: &AnimationClip, segments: &[Segment], out: &mut Vec<AnimationEvent>) {
+    if clip.events.is_empty() {
+        return;
+    }
+    for segment in segments {
+        if segment.to >= segment.from {
+            out.extend(
+                clip.events_between(segment.from, segment.to, segment.terminal)
+                    .cloned(),
+            );
+        } else {
+            // Walk backwards, latest first
+            let start = out.len();
+            out.extend(
+                clip.events
+                    .iter()
+                    .filter(|e| {
+                        e.time <= segment.from
+                            && (e.time > segment.to || (segment.terminal && e.time == segment.to))
+                    })
+                    .cloned(),
+            );
+            out[start..].reverse();
+        }
+    }
+}
+
+/// Rotation around the vertical axis only
+fn yaw_of(rotation: Quat) -> Quat {
+    let forward = rotation * Vec3::Z;
+    Quat::from_rotation_y(forward.x.atan2(forward.z))
+}
+
+impl std::fmt::Debug for AnimationPlayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnimationPlayer")
+            .field("clip", &self.clip.as_ref().map(|clip| &clip.name))
+            .field("current_time", &self.current_time)
+            .field("speed", &self.speed)
+            .field("loop_mode", &self.loop_mode)
+            .field("reversed", &self.reversed)
+            .field("state", &self.state)
+            .field("weight", &self.weight)
+            .field("crossfade", &self.crossfade.is_some())
+            .field("layers", &self.layers.len())
+            .finish_non_exhaustive()
+    }
+}
+
 impl Default for AnimationPlayer {
     fn default() -> Self {
         Self::new()
@@ -227,4 +967,205 @@ mod tests {
         player.update(0.8);
         assert!(player.current_time() < 0.5); // Should have looped
     }
+
+    #[test]
+    fn test_root_motion_extracted_across_loop() {
+        let mut skeleton = Skeleton::new();
+        skeleton.add_bone(crate::animation::Bone::new("root"));
+
+        let mut walk = AnimationClip::new("walk");
+        walk.add_channel(
+            0,
+            Channel::Translation(vec![
+                Keyframe::new(0.0, Vec3::ZERO),
+                Keyframe::new(1.0, Vec3::new(0.0, 0.0, 2.0)),
+            ]),
+        );
+
+        let mut player = AnimationPlayer::new();
+        player.enable_root_motion(RootMotionConfig::new(0));
+        player.set_clip(walk);
+        player.play();
+        player.update(0.75);
+        player.update(0.5);
+
+        let motion = player.take_root_motion();
+        assert!((motion.translation.z - 2.5).abs() < 0.01);
+        assert_eq!(player.take_root_motion(), RootMotion::IDENTITY);
+
+        let pose = player.sample_pose(&skeleton).unwrap();
+        assert!(pose.bones[0].translation.length() < 0.001);
+    }
+
+    #[test]
+    fn test_events_fire_once_per_loop() {
+        let mut clip = AnimationClip::new("walk");
+        clip.add_channel(
+            0,
+            Channel::Translation(vec![
+                Keyframe::new(0.0, Vec3::ZERO),
+                Keyframe::new(1.0, Vec3::X),
+            ]),
+        );
+        clip.add_event(0.0, "left_foot");
+        clip.add_event(0.5, "right_foot");
+
+        let mut player = AnimationPlayer::new();
+        player.set_clip(clip);
+        player.play();
+
+        player.update(0.25);
+        player.update(0.25);
+        let names: Vec<_> = player.drain_events().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["left_foot"]);
+
+        // A large step wraps twice: right, left, right, left
+        player.update(1.75);
+        let names: Vec<_> = player.drain_events().into_iter().map(|e| e.name).collect();
+        assert_eq!(
+            names,
+            vec!["right_foot", "left_foot", "right_foot", "left_foot"]
+        );
+    }
+
+    #[test]
+    fn test_ping_pong_and_reverse() {
+        let mut clip = AnimationClip::new("wave");
+        clip.add_channel(
+            0,
+            Channel::Translation(vec![
+                Keyframe::new(0.0, Vec3::ZERO),
+                Keyframe::new(1.0, Vec3::X),
+            ]),
+        );
+        clip.add_event(0.5, "mid");
+        clip.add_event(1.0, "end");
+
+        let ends = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = ends.clone();
+        let mut player = AnimationPlayer::new();
+        player.set_loop_mode(LoopMode::PingPong);
+        player.on_clip_end(move |end, name| recorded.lock().unwrap().push((end, name.to_string())));
+        player.set_clip(clip.clone());
+        player.play();
+
+        // 0 -> 1 -> 0.75: mid on the way up, end once at the bounce
+        player.update(1.25);
+        assert!((player.current_time() - 0.75).abs() < 1e-5);
+        assert!(player.is_reversed());
+        let names: Vec<_> = player.drain_events().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["mid", "end"]);
+        assert_eq!(
+            *ends.lock().unwrap(),
+            vec![(ClipEnd::Bounced, "wave".to_string())]
+        );
+
+        // Negative speed plays a clip from its end and stops at the start
+        let mut player = AnimationPlayer::new();
+        player.set_loop_mode(LoopMode::Once);
+        player.set_speed(-1.0);
+        player.set_clip(clip);
+        player.play();
+        assert_eq!(player.current_time(), 1.0);
+        player.update(2.0);
+        assert_eq!(player.current_time(), 0.0);
+        assert_eq!(player.state(), PlaybackState::Stopped);
+        let names: Vec<_> = player.drain_events().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["end", "mid"]);
+    }
+
+    #[test]
+    fn test_clamp_forever_holds_last_frame() {
+        let mut clip = AnimationClip::new("open");
+        clip.add_channel(
+            0,
+            Channel::Translation(vec![
+                Keyframe::new(0.0, Vec3::ZERO),
+                Keyframe::new(1.0, Vec3::X),
+            ]),
+        );
+        clip.add_event(1.0, "opened");
+
+        let mut player = AnimationPlayer::new();
+        player.set_loop_mode(LoopMode::ClampForever);
+        player.set_clip(clip);
+        player.play();
+        player.update(1.5);
+        player.update(1.0);
+
+        assert!(player.is_playing());
+        assert_eq!(player.current_time(), 1.0);
+        assert_eq!(player.drain_events().len(), 1);
+
+        player.seek(0.25);
+        assert_eq!(player.current_time(), 0.25);
+        assert!(player.drain_events().is_empty());
+    }
+
+    #[test]
+    fn test_synced_crossfade_keeps_phase() {
+        let cycle = |name: &str, duration: f32| {
+            let mut clip = AnimationClip::new(name).with_sync_group("locomotion");
+            clip.add_channel(
+                0,
+                Channel::Translation(vec![
+                    Keyframe::new(0.0, Vec3::ZERO),
+                    Keyframe::new(duration, Vec3::X),
+                ]),
+            );
+            clip
+        };
+
+        let mut player = AnimationPlayer::new();
+        player.set_clip(cycle("walk", 1.0));
+        player.play();
+        player.update(0.25);
+
+        // Run starts at the walk's phase, not at zero
+        player.crossfade_to(cycle("run", 0.5), 0.5);
+        assert!((player.normalized_time() - 0.25).abs() < 1e-5);
+
+        for _ in 0..5 {
+            player.update(0.05);
+            let fade = player.crossfade.as_ref().unwrap();
+            let outgoing = fade.time / fade.clip.duration;
+            assert!((outgoing - player.normalized_time()).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_crossfade_blends_poses() {
+        let mut skeleton = Skeleton::new();
+        skeleton.add_bone(crate::animation::Bone::new("root"));
+
+        let mut walk = AnimationClip::new("walk");
+        walk.add_channel(
+            0,
+            Channel::Translation(vec![
+                Keyframe::new(0.0, Vec3::ZERO),
+                Keyframe::new(1.0, Vec3::ZERO),
+            ]),
+        );
+        let mut run = AnimationClip::new("run");
+        run.add_channel(
+            0,
+            Channel::Translation(vec![
+                Keyframe::new(0.0, Vec3::X),
+                Keyframe::new(1.0, Vec3::X),
+            ]),
+        );
+
+        let mut player = AnimationPlayer::new();
+        player.set_clip(walk);
+        player.play();
+        player.crossfade_to(run, 1.0);
+        player.update(0.5);
+
+        let pose = player.sample_pose(&skeleton).unwrap();
+        assert!((pose.bones[0].translation.x - 0.5).abs() < 0.01);
+
+        player.update(0.6);
+        assert!(!player.is_crossfading());
+        assert_eq!(player.crossfade_weight(), 1.0);
+    }
 }
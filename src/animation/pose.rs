@@ -0,0 +1,224 @@
+//! Skeleton poses
+//!
+//! A pose is a set of local bone transforms that can be sampled from
+//! clips, blended, and applied back onto a skeleton.
+
+use glam::{Mat4, Quat, Vec3};
+
+use super::clip::AnimationClip;
+use super::skeleton::{Bone, Skeleton};
+
+/// Local transform of a single bone
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoneTransform {
+    /// Local translation
+    pub translation: Vec3,
+    /// Local rotation
+    pub rotation: Quat,
+    /// Local scale
+    pub scale: Vec3,
+}
+
+impl BoneTransform {
+    /// Identity transform
+    pub const IDENTITY: Self = Self {
+        translation: Vec3::ZERO,
+        rotation: Quat::IDENTITY,
+        scale: Vec3::ONE,
+    };
+
+    /// Get the current local transform of a bone
+    #[must_use]
+    pub fn from_bone(bone: &Bone) -> Self {
+        Self {
+            translation: bone.translation,
+            rotation: bone.rotation,
+            scale: bone.scale,
+        }
+    }
+
+    /// Decompose a transformation matrix
+    #[must_use]
+    pub fn from_matrix(matrix: Mat4) -> Self {
+        let (scale, rotation, translation) = matrix.to_scale_rotation_translation();
+        Self {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    /// Get the transformation matrix
+    #[must_use]
+    pub fn matrix(&self) -> Mat4 {
+        Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
+    }
+
+    /// Interpolate towards another transform
+    #[must_use]
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            translation: self.translation.lerp(other.translation, t),
+            rotation: self.rotation.slerp(other.rotation, t),
+            scale: self.scale.lerp(other.scale, t),
+        }
+    }
+}
+
+impl Default for BoneTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Local transforms for every bone of a skeleton
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Pose {
+    /// One transform per bone, indexed like `Skeleton::bones`
+    pub bones: Vec<BoneTransform>,
+}
+
+impl Pose {
+    /// Create an identity pose with the given bone count
+    #[must_use]
+    pub fn new(bone_count: usize) -> Self {
+        Self {
+            bones: vec![BoneTransform::IDENTITY; bone_count],
+        }
+    }
+
+    /// Capture the current local transforms of a skeleton
+    #[must_use]
+    pub fn from_skeleton(skeleton: &Skeleton) -> Self {
+        Self {
+            bones: skeleton
+                .bones
+                .iter()
+                .map(BoneTransform::from_bone)
+                .collect(),
+        }
+    }
+
+    /// Build a pose from world-space bone matrices (e.g. ragdoll bodies)
+    #[must_use]
+    pub fn from_world_matrices(skeleton: &Skeleton, world: &[Mat4]) -> Self {
+        let bones = skeleton
+            .bones
+            .iter()
+            .enumerate()
+            .map(|(index, bone)| {
+                let Some(&matrix) = world.get(index) else {
+                    return BoneTransform::from_bone(bone);
+                };
+                let local = match bone.parent.and_then(|p| world.get(p)) {
+                    Some(parent) => parent.inverse() * matrix,
+                    None => matrix,
+                };
+                BoneTransform::from_matrix(local)
+            })
+            .collect();
+        Self { bones }
+    }
+
+    /// Sample a clip at a time, falling back to the skeleton's transforms
+    /// for bones and properties the clip does not animate
+    #[must_use]
+    pub fn sample(clip: &AnimationClip, time: f32, skeleton: &Skeleton) -> Self {
+        let mut pose = Self::from_skeleton(skeleton);
+        pose.sample_into(clip, time);
+        pose
+    }
+
+    /// Overwrite animated bones with values sampled from a clip
+    pub fn sample_into(&mut self, clip: &AnimationClip, time: f32) {
+        for (index, bone) in self.bones.iter_mut().enumerate() {
+            if let Some(translation) = clip.sample_translation(index, time) {
+                bone.translation = translation;
+            }
+            if let Some(rotation) = clip.sample_rotation(index, time) {
+                bone.rotation = rotation;
+            }
+            if let Some(scale) = clip.sample_scale(index, time) {
+                bone.scale = scale;
+            }
+        }
+    }
+
+    /// Interpolate towards another pose (0.0 = self, 1.0 = other)
+    #[must_use]
+    pub fn blend(&self, other: &Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        Self {
+            bones: self
+                .bones
+                .iter()
+                .zip(other.bones.iter())
+                .map(|(a, b)| a.lerp(b, t))
+                .collect(),
+        }
+    }
+
+    /// Compute model-space matrices for every bone using the skeleton hierarchy
+    #[must_use]
+    pub fn world_matrices(&self, skeleton: &Skeleton) -> Vec<Mat4> {
+        let mut world = vec![Mat4::IDENTITY; self.bones.len()];
+        let mut stack: Vec<(usize, Mat4)> = skeleton
+            .roots
+            .iter()
+            .map(|&r| (r, Mat4::IDENTITY))
+            .collect();
+        while let Some((index, parent)) = stack.pop() {
+            let Some(transform) = self.bones.get(index) else {
+                continue;
+            };
+            world[index] = parent * transform.matrix();
+            for &child in &skeleton.bones[index].children {
+                stack.push((child, world[index]));
+            }
+        }
+        world
+    }
+
+    /// Write the pose back onto a skeleton
+    pub fn apply(&self, skeleton: &mut Skeleton) {
+        for (bone, transform) in skeleton.bones.iter_mut().zip(self.bones.iter()) {
+            bone.translation = transform.translation;
+            bone.rotation = transform.rotation;
+            bone.scale = transform.scale;
+        }
+    }
+
+    /// Get the number of bones
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.bones.len()
+    }
+
+    /// Check if the pose is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bones.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_world_matrices_round_trip() {
+        let mut skeleton = Skeleton::new();
+        let mut root = Bone::new("root");
+        root.translation = Vec3::new(1.0, 0.0, 0.0);
+        skeleton.add_bone(root);
+        let mut child = Bone::new("child");
+        child.translation = Vec3::new(0.0, 2.0, 0.0);
+        let child_idx = skeleton.add_bone(child);
+        skeleton.set_parent(child_idx, 0);
+
+        let world = skeleton.compute_world_matrices();
+        let pose = Pose::from_world_matrices(&skeleton, &world);
+
+        assert!((pose.bones[child_idx].translation - Vec3::new(0.0, 2.0, 0.0)).length() < 0.001);
+    }
+}
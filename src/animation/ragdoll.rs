@@ -0,0 +1,177 @@
+//! Ragdoll to animation blending
+//!
+//! Blends a physics-driven ragdoll pose back into an animation clip,
+//! typically a get-up animation chosen from how the ragdoll landed.
+
+use glam::{Quat, Vec3};
+
+use super::clip::AnimationClip;
+use super::pose::Pose;
+use super::skeleton::Skeleton;
+
+/// How a ragdoll came to rest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RagdollFacing {
+    /// Lying on its back
+    FaceUp,
+    /// Lying on its front
+    FaceDown,
+}
+
+impl RagdollFacing {
+    /// Determine facing from the pelvis rotation
+    ///
+    /// `forward_axis` is the pelvis-local axis pointing out of the
+    /// character's front (usually `Vec3::Z` for glTF rigs).
+    #[must_use]
+    pub fn from_pelvis(rotation: Quat, forward_axis: Vec3) -> Self {
+        if (rotation * forward_axis).y >= 0.0 {
+            Self::FaceUp
+        } else {
+            Self::FaceDown
+        }
+    }
+}
+
+/// Get-up clips for each resting orientation
+#[derive(Debug, Clone)]
+pub struct GetUpClips {
+    /// Played when the ragdoll lies on its back
+    pub face_up: AnimationClip,
+    /// Played when the ragdoll lies on its front
+    pub face_down: AnimationClip,
+}
+
+impl GetUpClips {
+    /// Pick the clip matching a resting orientation
+    #[must_use]
+    pub fn select(&self, facing: RagdollFacing) -> &AnimationClip {
+        match facing {
+            RagdollFacing::FaceUp => &self.face_up,
+            RagdollFacing::FaceDown => &self.face_down,
+        }
+    }
+}
+
+/// Blend from a frozen ragdoll pose into a playing clip
+#[derive(Debug, Clone)]
+pub struct RagdollBlend {
+    /// Pose captured from the ragdoll when blending started
+    ragdoll_pose: Pose,
+    /// Clip being blended into
+    clip: AnimationClip,
+    /// Blend duration in seconds
+    duration: f32,
+    /// Time since blending started
+    elapsed: f32,
+}
+
+impl RagdollBlend {
+    /// Start blending from a ragdoll pose into a clip
+    #[must_use]
+    pub fn new(ragdoll_pose: Pose, clip: AnimationClip, duration: f32) -> Self {
+        Self {
+            ragdoll_pose,
+            clip,
+            duration: duration.max(0.0),
+            elapsed: 0.0,
+        }
+    }
+
+    /// Start a get-up blend, choosing the clip from the pelvis orientation
+    #[must_use]
+    pub fn get_up(
+        ragdoll_pose: Pose,
+        pelvis_rotation: Quat,
+        forward_axis: Vec3,
+        clips: &GetUpClips,
+        duration: f32,
+    ) -> Self {
+        let facing = RagdollFacing::from_pelvis(pelvis_rotation, forward_axis);
+        Self::new(ragdoll_pose, clips.select(facing).clone(), duration)
+    }
+
+    /// Advance the blend
+    pub fn update(&mut self, delta_time: f32) {
+        self.elapsed += delta_time;
+    }
+
+    /// Get the current blend weight towards the clip (0.0 to 1.0)
+    #[must_use]
+    pub fn weight(&self) -> f32 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Check if the blend has fully reached the clip
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.weight() >= 1.0
+    }
+
+    /// Get the clip being blended into
+    #[must_use]
+    pub fn clip(&self) -> &AnimationClip {
+        &self.clip
+    }
+
+    /// Get the clip playback time (the clip plays while blending in)
+    #[must_use]
+    pub fn clip_time(&self) -> f32 {
+        self.elapsed.min(self.clip.duration)
+    }
+
+    /// Evaluate the blended pose
+    #[must_use]
+    pub fn evaluate(&self, skeleton: &Skeleton) -> Pose {
+        let animated = Pose::sample(&self.clip, self.clip_time(), skeleton);
+        self.ragdoll_pose.blend(&animated, self.weight())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::{Bone, Channel, Keyframe};
+
+    #[test]
+    fn test_facing_from_pelvis() {
+        let face_up = RagdollFacing::from_pelvis(
+            Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2),
+            Vec3::Z,
+        );
+        let face_down =
+            RagdollFacing::from_pelvis(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2), Vec3::Z);
+
+        assert_eq!(face_up, RagdollFacing::FaceUp);
+        assert_eq!(face_down, RagdollFacing::FaceDown);
+    }
+
+    #[test]
+    fn test_blend_halfway() {
+        let mut skeleton = Skeleton::new();
+        skeleton.add_bone(Bone::new("pelvis"));
+
+        let mut clip = AnimationClip::new("get_up");
+        clip.add_channel(
+            0,
+            Channel::Translation(vec![
+                Keyframe::new(0.0, Vec3::new(0.0, 1.0, 0.0)),
+                Keyframe::new(2.0, Vec3::new(0.0, 1.0, 0.0)),
+            ]),
+        );
+
+        let mut blend = RagdollBlend::new(Pose::new(1), clip, 1.0);
+        blend.update(0.5);
+
+        let pose = blend.evaluate(&skeleton);
+        assert!((pose.bones[0].translation.y - 0.5).abs() < 0.001);
+        assert!(!blend.is_finished());
+
+        blend.update(0.5);
+        assert!(blend.is_finished());
+    }
+}
@@ -0,0 +1,315 @@
+//! Animation retargeting
+//!
+//! Maps clips authored for one skeleton onto another by bone name. Bone
+//! rotations are transferred relative to each skeleton's bind pose, and
+//! root translation is scaled by the ratio of the two skeletons' hip
+//! heights, so one locomotion set can drive characters of any proportions.
+
+use glam::{Quat, Vec3};
+
+use super::clip::{AnimationClip, Channel, Keyframe};
+use super::skeleton::Skeleton;
+
+/// Minimum hip height used to derive the proportions scale
+const MIN_HEIGHT: f32 = 1e-4;
+
+/// Source-to-target bone name pairs
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoneMap {
+    /// Explicit (source, target) name pairs
+    pub pairs: Vec<(String, String)>,
+    /// Map bones with identical names when no explicit pair covers them
+    pub match_names: bool,
+}
+
+impl BoneMap {
+    /// Create an empty map
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map every bone to the target bone of the same name
+    #[must_use]
+    pub fn by_name() -> Self {
+        Self {
+            pairs: Vec::new(),
+            match_names: true,
+        }
+    }
+
+    /// Add an explicit pair
+    #[must_use]
+    pub fn with_bone(mut self, source: impl Into<String>, target: impl Into<String>) -> Self {
+        self.pairs.push((source.into(), target.into()));
+        self
+    }
+
+    /// Get the target bone name for a source bone
+    #[must_use]
+    pub fn target_of<'a>(&'a self, source: &'a str) -> Option<&'a str> {
+        self.pairs
+            .iter()
+            .find(|(s, _)| s == source)
+            .map(|(_, t)| t.as_str())
+            .or(self.match_names.then_some(source))
+    }
+}
+
+/// Bind-pose data for one mapped bone
+#[derive(Debug, Clone, Copy)]
+struct MappedBone {
+    /// Bone index in the target skeleton
+    target: usize,
+    /// Left factor of the rotation transfer
+    pre: Quat,
+    /// Right factor of the rotation transfer
+    post: Quat,
+    /// Source bind translation
+    source_translation: Vec3,
+    /// Target bind translation
+    target_translation: Vec3,
+    /// Whether this is a topmost mapped bone, which keeps its translation
+    root: bool,
+}
+
+/// Transfers clips from a source skeleton to a target skeleton
+///
+/// Both skeletons must be in their bind (rest) pose when the retargeter
+/// is created.
+#[derive(Debug, Clone)]
+pub struct Retargeter {
+    /// Mapping per source bone index
+    bones: Vec<Option<MappedBone>>,
+    /// Scale applied to root translation
+    scale: f32,
+}
+
+impl Retargeter {
+    /// Build a retargeter from bind poses and a bone map
+    #[must_use]
+    pub fn new(source: &Skeleton, target: &Skeleton, map: &BoneMap) -> Self {
+        let source_world = world_rotations(source);
+        let target_world = world_rotations(target);
+        let parent_rotation = |skeleton: &Skeleton, world: &[Quat], bone: usize| {
+            skeleton.bones[bone]
+                .parent
+                .map_or(Quat::IDENTITY, |parent| world[parent])
+        };
+
+        let mapped: Vec<Option<usize>> = source
+            .bones
+            .iter()
+            .map(|bone| {
+                map.target_of(&bone.name)
+                    .and_then(|name| target.find_by_name(name))
+            })
+            .collect();
+
+        let bones = mapped
+            .iter()
+            .enumerate()
+            .map(|(index, &target_index)| {
+                let target_index = target_index?;
+                // Topmost mapped bone: no mapped ancestor
+                let mut root = true;
+                let mut parent = source.bones[index].parent;
+                while let Some(p) = parent {
+                    if mapped[p].is_some() {
+                        root = false;
+                        break;
+                    }
+                    parent = source.bones[p].parent;
+                }
+
+                // q_target = Tp^-1 * Sp * q_source * Sw^-1 * Tw
+                let pre = parent_rotation(target, &target_world, target_index).inverse()
+                    * parent_rotation(source, &source_world, index);
+                let post = source_world[index].inverse() * target_world[target_index];
+                Some(MappedBone {
+                    target: target_index,
+                    pre,
+                    post,
+                    source_translation: source.bones[index].translation,
+                    target_translation: target.bones[target_index].translation,
+                    root,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let scale = hip_height_ratio(source, target, &bones);
+        Self { bones, scale }
+    }
+
+    /// Override the root translation scale
+    #[must_use]
+    pub const fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Get the root translation scale
+    #[must_use]
+    pub const fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Get the target bone for a source bone
+    #[must_use]
+    pub fn target_bone(&self, source: usize) -> Option<usize> {
+        self.bones.get(source).copied().flatten().map(|b| b.target)
+    }
+
+    /// Get the number of mapped bones
+    #[must_use]
+    pub fn mapped_count(&self) -> usize {
+        self.bones.iter().flatten().count()
+    }
+
+    /// Convert a clip authored for the source skeleton
+    ///
+    /// Rotation channels of mapped bones are transferred, translation is
+    /// kept (and scaled) only for topmost mapped bones such as the hips,
+    /// and scale channels are copied. Unmapped bones, other translation
+    /// channels and morph weights are dropped. Events are kept.
+    #[must_use]
+    pub fn retarget(&self, clip: &AnimationClip) -> AnimationClip {
+        let mut out = AnimationClip::new(clip.name.clone());
+        out.interpolation = clip.interpolation;
+        out.events = clip.events.clone();
+        out.sync_group = clip.sync_group.clone();
+
+        for (source, channel) in &clip.channels {
+            let Some(bone) = self.bones.get(*source).copied().flatten() else {
+                continue;
+            };
+            let converted = match channel {
+                Channel::Rotation(keys) => Channel::Rotation(map_keys(keys, |q, tangent| {
+                    let q = bone.pre * q * bone.post;
+                    if tangent { q } else { q.normalize() }
+                })),
+                Channel::Translation(keys) if bone.root => {
+                    let scale = self.scale;
+                    Channel::Translation(map_keys(keys, |t, tangent| {
+                        if tangent {
+                            bone.pre * t * scale
+                        } else {
+                            bone.target_translation
+                                + bone.pre * (t - bone.source_translation) * scale
+                        }
+                    }))
+                }
+                Channel::Scale(keys) => Channel::Scale(keys.clone()),
+                Channel::Translation(_) | Channel::MorphWeights(_) => continue,
+            };
+            out.add_channel(bone.target, converted);
+        }
+        // Keep the source length even if trailing channels were dropped
+        out.duration = clip.duration;
+        out
+    }
+}
+
+/// Map keyframe values and tangents (flagged `true`) through `f`
+fn map_keys<T: Copy>(keys: &[Keyframe<T>], f: impl Fn(T, bool) -> T) -> Vec<Keyframe<T>> {
+    keys.iter()
+        .map(|key| Keyframe {
+            time: key.time,
+            value: f(key.value, false),
+            in_tangent: key.in_tangent.map(|t| f(t, true)),
+            out_tangent: key.out_tangent.map(|t| f(t, true)),
+        })
+        .collect()
+}
+
+/// World-space bind rotation of every bone
+fn world_rotations(skeleton: &Skeleton) -> Vec<Quat> {
+    skeleton
+        .compute_world_matrices()
+        .iter()
+        .map(|m| m.to_scale_rotation_translation().1)
+        .collect()
+}
+
+/// Ratio of target to source height of the first topmost mapped bone
+fn hip_height_ratio(source: &Skeleton, target: &Skeleton, bones: &[Option<MappedBone>]) -> f32 {
+    let Some((index, bone)) = bones
+        .iter()
+        .enumerate()
+        .find_map(|(i, b)| b.filter(|b| b.root).map(|b| (i, b)))
+    else {
+        return 1.0;
+    };
+    let source_height = source.compute_world_matrices()[index].w_axis.y;
+    let target_height = target.compute_world_matrices()[bone.target].w_axis.y;
+    if source_height.abs() < MIN_HEIGHT || target_height.abs() < MIN_HEIGHT {
+        1.0
+    } else {
+        target_height / source_height
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::Bone;
+
+    fn leg(names: [&str; 2], hip_height: f32, rest: Quat) -> Skeleton {
+        let mut skeleton = Skeleton::new();
+        let mut hips = Bone::new(names[0]);
+        hips.translation = Vec3::new(0.0, hip_height, 0.0);
+        let hips = skeleton.add_bone(hips);
+        let mut thigh = Bone::new(names[1]);
+        thigh.rotation = rest;
+        let thigh = skeleton.add_bone(thigh);
+        skeleton.set_parent(thigh, hips);
+        skeleton
+    }
+
+    #[test]
+    fn test_retarget_scales_root_and_keeps_rotation() {
+        let source = leg(["Hips", "LeftUpLeg"], 1.0, Quat::IDENTITY);
+        let target = leg(["pelvis", "thigh_l"], 0.5, Quat::IDENTITY);
+        let map = BoneMap::new()
+            .with_bone("Hips", "pelvis")
+            .with_bone("LeftUpLeg", "thigh_l");
+        let retargeter = Retargeter::new(&source, &target, &map);
+        assert_eq!(retargeter.mapped_count(), 2);
+        assert!((retargeter.scale() - 0.5).abs() < 1e-5);
+
+        let swing = Quat::from_rotation_x(0.5);
+        let mut clip = AnimationClip::new("walk");
+        clip.add_channel(
+            0,
+            Channel::Translation(vec![
+                Keyframe::new(0.0, Vec3::new(0.0, 1.0, 0.0)),
+                Keyframe::new(1.0, Vec3::new(0.0, 1.0, 2.0)),
+            ]),
+        );
+        clip.add_channel(1, Channel::Rotation(vec![Keyframe::new(0.0, swing)]));
+
+        let walk = retargeter.retarget(&clip);
+        let end = walk.sample_translation(0, 1.0).unwrap();
+        assert!((end - Vec3::new(0.0, 0.5, 1.0)).length() < 1e-5);
+        assert!(walk.sample_rotation(1, 0.0).unwrap().angle_between(swing) < 1e-4);
+    }
+
+    #[test]
+    fn test_retarget_compensates_bind_rotation() {
+        // The target's thigh is rotated 90 degrees at rest
+        let rest = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+        let source = leg(["Hips", "Thigh"], 1.0, Quat::IDENTITY);
+        let target = leg(["Hips", "Thigh"], 1.0, rest);
+        let retargeter = Retargeter::new(&source, &target, &BoneMap::by_name());
+
+        let mut clip = AnimationClip::new("idle");
+        clip.add_channel(
+            1,
+            Channel::Rotation(vec![Keyframe::new(0.0, Quat::IDENTITY)]),
+        );
+        let idle = retargeter.retarget(&clip);
+
+        // The source bind pose maps onto the target bind pose
+        assert!(idle.sample_rotation(1, 0.0).unwrap().angle_between(rest) < 1e-4);
+    }
+}
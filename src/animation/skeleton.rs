@@ -52,6 +52,46 @@ impl Default for Bone {
     }
 }
 
+/// A named attachment point with an offset from a bone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Socket {
+    /// Socket name
+    pub name: String,
+    /// Bone the socket follows
+    pub bone: usize,
+    /// Offset translation in bone space
+    pub translation: Vec3,
+    /// Offset rotation in bone space
+    pub rotation: Quat,
+}
+
+impl Socket {
+    /// Create a socket on a bone with no offset
+    #[must_use]
+    pub fn new(name: impl Into<String>, bone: usize) -> Self {
+        Self {
+            name: name.into(),
+            bone,
+            translation: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+        }
+    }
+
+    /// Set the offset from the bone
+    #[must_use]
+    pub fn with_offset(mut self, translation: Vec3, rotation: Quat) -> Self {
+        self.translation = translation;
+        self.rotation = rotation;
+        self
+    }
+
+    /// Get the offset matrix in bone space
+    #[must_use]
+    pub fn offset_matrix(&self) -> Mat4 {
+        Mat4::from_rotation_translation(self.rotation, self.translation)
+    }
+}
+
 /// A skeleton containing a hierarchy of bones
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Skeleton {
@@ -59,6 +99,9 @@ pub struct Skeleton {
     pub bones: Vec<Bone>,
     /// Root bone indices
     pub roots: Vec<usize>,
+    /// Named attachment sockets
+    #[serde(default)]
+    pub sockets: Vec<Socket>,
 }
 
 impl Skeleton {
@@ -68,6 +111,7 @@ impl Skeleton {
         Self {
             bones: Vec::new(),
             roots: Vec::new(),
+            sockets: Vec::new(),
         }
     }
 
@@ -139,6 +183,35 @@ impl Skeleton {
         self.bones.iter().position(|b| b.name == name)
     }
 
+    /// Add a socket, replacing any existing socket with the same name
+    ///
+    /// Returns `None` if the socket's bone does not exist.
+    pub fn add_socket(&mut self, socket: Socket) -> Option<usize> {
+        if socket.bone >= self.bones.len() {
+            return None;
+        }
+        if let Some(index) = self.find_socket(&socket.name) {
+            self.sockets[index] = socket;
+            return Some(index);
+        }
+        self.sockets.push(socket);
+        Some(self.sockets.len() - 1)
+    }
+
+    /// Find socket by name
+    #[must_use]
+    pub fn find_socket(&self, name: &str) -> Option<usize> {
+        self.sockets.iter().position(|s| s.name == name)
+    }
+
+    /// Get a socket's model-space matrix from precomputed bone world matrices
+    #[must_use]
+    pub fn socket_matrix(&self, name: &str, world_matrices: &[Mat4]) -> Option<Mat4> {
+        let socket = &self.sockets[self.find_socket(name)?];
+        let bone = world_matrices.get(socket.bone)?;
+        Some(*bone * socket.offset_matrix())
+    }
+
     /// Compute world matrices for all bones using hierarchy traversal
     ///
     /// This ensures correct calculation regardless of bone storage order.
@@ -255,6 +328,23 @@ mod tests {
         assert_eq!(skeleton.find_by_name("missing"), None);
     }
 
+    #[test]
+    fn test_socket_follows_bone() {
+        let mut skeleton = Skeleton::new();
+        let mut hand = Bone::new("hand");
+        hand.translation = Vec3::new(0.0, 1.0, 0.0);
+        let hand_idx = skeleton.add_bone(hand);
+
+        let socket = Socket::new("weapon", hand_idx).with_offset(Vec3::X, Quat::IDENTITY);
+        assert_eq!(skeleton.add_socket(socket), Some(0));
+        assert_eq!(skeleton.add_socket(Socket::new("bad", 5)), None);
+
+        let world = skeleton.compute_world_matrices();
+        let matrix = skeleton.socket_matrix("weapon", &world).unwrap();
+        let pos = matrix.w_axis.truncate();
+        assert!((pos - Vec3::new(1.0, 1.0, 0.0)).length() < 0.001);
+    }
+
     #[test]
     fn test_out_of_order_bones() {
         let mut skeleton = Skeleton::new();
@@ -0,0 +1,104 @@
+//! Socket attachments
+//!
+//! Lets entities follow a named socket on another entity's animated skeleton.
+
+use std::collections::HashMap;
+
+use glam::Mat4;
+use hecs::Entity;
+
+use super::Skeleton;
+use crate::ecs::{GlobalTransform, Transform, World};
+
+/// Component attaching an entity to a socket on another entity's skeleton
+///
+/// The attached entity's own `Transform` is applied as an offset from the socket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachedTo {
+    /// Entity owning the skeleton
+    pub entity: Entity,
+    /// Socket name on that skeleton
+    pub socket: String,
+}
+
+impl AttachedTo {
+    /// Create an attachment
+    #[must_use]
+    pub fn new(entity: Entity, socket: impl Into<String>) -> Self {
+        Self {
+            entity,
+            socket: socket.into(),
+        }
+    }
+}
+
+/// Update global transforms of attached entities
+///
+/// Run after animation has been applied to skeletons and after transform
+/// propagation, so sockets follow the current pose.
+pub fn resolve_attachments(world: &mut World) {
+    let attachments: Vec<(Entity, AttachedTo, Mat4)> = world
+        .query::<(&AttachedTo, Option<&Transform>)>()
+        .iter()
+        .map(|(entity, (attached, local))| {
+            let local = local.map_or(Mat4::IDENTITY, Transform::matrix);
+            (entity, attached.clone(), local)
+        })
+        .collect();
+
+    let mut bone_cache: HashMap<Entity, Vec<Mat4>> = HashMap::new();
+    let mut resolved = Vec::with_capacity(attachments.len());
+
+    for (entity, attached, local) in attachments {
+        let Ok(skeleton) = world.get::<Skeleton>(attached.entity) else {
+            continue;
+        };
+        let owner = world
+            .get::<GlobalTransform>(attached.entity)
+            .map_or(Mat4::IDENTITY, |g| g.matrix);
+        let bones = bone_cache
+            .entry(attached.entity)
+            .or_insert_with(|| skeleton.compute_world_matrices());
+        if let Some(socket) = skeleton.socket_matrix(&attached.socket, bones) {
+            resolved.push((entity, owner * socket * local));
+        }
+    }
+
+    for (entity, matrix) in resolved {
+        if let Ok(mut global) = world.get_mut::<GlobalTransform>(entity) {
+            global.matrix = matrix;
+        } else {
+            let _ = world.inner.insert_one(entity, GlobalTransform::new(matrix));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::{Bone, Socket};
+    use glam::{Quat, Vec3};
+
+    #[test]
+    fn test_attachment_follows_socket() {
+        let mut world = World::new();
+
+        let mut skeleton = Skeleton::new();
+        let mut head = Bone::new("head");
+        head.translation = Vec3::new(0.0, 2.0, 0.0);
+        let head_idx = skeleton.add_bone(head);
+        skeleton
+            .add_socket(Socket::new("hat", head_idx).with_offset(Vec3::Y * 0.5, Quat::IDENTITY));
+
+        let owner = world.spawn((
+            skeleton,
+            GlobalTransform::from_components(Vec3::new(5.0, 0.0, 0.0), Quat::IDENTITY, Vec3::ONE),
+        ));
+        let hat = world.spawn((AttachedTo::new(owner, "hat"),));
+
+        resolve_attachments(&mut world);
+
+        let global = world.get::<GlobalTransform>(hat).unwrap();
+        assert!((global.position() - Vec3::new(5.0, 2.5, 0.0)).length() < 0.001);
+    }
+}
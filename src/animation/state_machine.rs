@@ -0,0 +1,384 @@
+//! Animation state machine
+//!
+//! Named states with parameter-driven transitions that drive an
+//! `AnimationPlayer`, crossfading between clips on each transition.
+
+use std::collections::{HashMap, HashSet};
+
+use super::blend_tree::BlendTree;
+use super::clip::AnimationClip;
+use super::player::AnimationPlayer;
+
+/// A condition on a state machine parameter
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// Float parameter is greater than a threshold
+    Greater(String, f32),
+    /// Float parameter is less than a threshold
+    Less(String, f32),
+    /// Bool parameter equals a value
+    Bool(String, bool),
+    /// Trigger parameter is set (consumed when the transition fires)
+    Trigger(String),
+}
+
+/// A state holding a clip or blend tree and its playback settings
+#[derive(Debug, Clone)]
+pub struct AnimationState {
+    /// State name
+    pub name: String,
+    /// Clip played while in this state
+    pub clip: AnimationClip,
+    /// Blend tree played instead of the clip, weighted by float parameters
+    pub blend_tree: Option<BlendTree>,
+    /// Playback speed
+    pub speed: f32,
+    /// Whether the clip loops
+    pub looping: bool,
+}
+
+impl AnimationState {
+    /// Create a looping state at normal speed
+    #[must_use]
+    pub fn new(name: impl Into<String>, clip: AnimationClip) -> Self {
+        Self {
+            name: name.into(),
+            clip,
+            blend_tree: None,
+            speed: 1.0,
+            looping: true,
+        }
+    }
+
+    /// Create a looping state that plays a blend tree
+    #[must_use]
+    pub fn blend(name: impl Into<String>, tree: impl Into<BlendTree>) -> Self {
+        let name = name.into();
+        Self {
+            blend_tree: Some(tree.into()),
+            ..Self::new(name.clone(), AnimationClip::new(name))
+        }
+    }
+
+    /// Set the playback speed
+    #[must_use]
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Set whether the clip loops
+    #[must_use]
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+}
+
+/// A transition between two states
+#[derive(Debug, Clone)]
+pub struct Transition {
+    /// Source state name, or `None` to transition from any state
+    pub from: Option<String>,
+    /// Target state name
+    pub to: String,
+    /// All conditions must hold for the transition to fire
+    pub conditions: Vec<Condition>,
+    /// Crossfade duration in seconds
+    pub duration: f32,
+    /// Minimum normalized time of the source clip before the transition can fire
+    pub exit_time: Option<f32>,
+}
+
+impl Transition {
+    /// Create a transition between two named states
+    #[must_use]
+    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self {
+            from: Some(from.into()),
+            to: to.into(),
+            conditions: Vec::new(),
+            duration: 0.2,
+            exit_time: None,
+        }
+    }
+
+    /// Create a transition that can fire from any state
+    #[must_use]
+    pub fn from_any(to: impl Into<String>) -> Self {
+        Self {
+            from: None,
+            ..Self::new("", to)
+        }
+    }
+
+    /// Add a condition
+    #[must_use]
+    pub fn when(mut self, condition: Condition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Set the crossfade duration in seconds
+    #[must_use]
+    pub fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = duration.max(0.0);
+        self
+    }
+
+    /// Only fire once the source clip has reached a normalized time
+    #[must_use]
+    pub fn with_exit_time(mut self, normalized_time: f32) -> Self {
+        self.exit_time = Some(normalized_time);
+        self
+    }
+}
+
+/// State machine that drives an animation player from parameters
+#[derive(Debug, Clone, Default)]
+pub struct AnimationStateMachine {
+    /// All states
+    states: Vec<AnimationState>,
+    /// All transitions, checked in insertion order
+    transitions: Vec<Transition>,
+    /// Current state index
+    current: Option<usize>,
+    /// Float parameters
+    floats: HashMap<String, f32>,
+    /// Bool parameters
+    bools: HashMap<String, bool>,
+    /// Pending triggers
+    triggers: HashSet<String>,
+}
+
+impl AnimationStateMachine {
+    /// Create an empty state machine
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a state, returning its index
+    ///
+    /// The first state added becomes the entry state.
+    pub fn add_state(&mut self, state: AnimationState) -> usize {
+        self.states.push(state);
+        self.states.len() - 1
+    }
+
+    /// Add a transition
+    pub fn add_transition(&mut self, transition: Transition) {
+        self.transitions.push(transition);
+    }
+
+    /// Find a state index by name
+    #[must_use]
+    pub fn find_state(&self, name: &str) -> Option<usize> {
+        self.states.iter().position(|s| s.name == name)
+    }
+
+    /// Get the current state
+    #[must_use]
+    pub fn current_state(&self) -> Option<&AnimationState> {
+        self.current.and_then(|index| self.states.get(index))
+    }
+
+    /// Get the current state name
+    #[must_use]
+    pub fn current_state_name(&self) -> Option<&str> {
+        self.current_state().map(|s| s.name.as_str())
+    }
+
+    /// Set a float parameter
+    pub fn set_float(&mut self, name: impl Into<String>, value: f32) {
+        self.floats.insert(name.into(), value);
+    }
+
+    /// Get a float parameter (0.0 if unset)
+    #[must_use]
+    pub fn float(&self, name: &str) -> f32 {
+        self.floats.get(name).copied().unwrap_or(0.0)
+    }
+
+    /// Set a bool parameter
+    pub fn set_bool(&mut self, name: impl Into<String>, value: bool) {
+        self.bools.insert(name.into(), value);
+    }
+
+    /// Get a bool parameter (false if unset)
+    #[must_use]
+    pub fn bool(&self, name: &str) -> bool {
+        self.bools.get(name).copied().unwrap_or(false)
+    }
+
+    /// Set a trigger, consumed by the next transition that uses it
+    pub fn set_trigger(&mut self, name: impl Into<String>) {
+        self.triggers.insert(name.into());
+    }
+
+    /// Clear a pending trigger
+    pub fn reset_trigger(&mut self, name: &str) {
+        self.triggers.remove(name);
+    }
+
+    /// Jump to a state without blending
+    ///
+    /// Returns `false` if the state does not exist.
+    pub fn start(&mut self, player: &mut AnimationPlayer, name: &str) -> bool {
+        let Some(index) = self.find_state(name) else {
+            return false;
+        };
+        let state = &self.states[index];
+        match &state.blend_tree {
+            Some(tree) => player.set_blend_tree(tree.clone()),
+            None => player.set_clip(state.clip.clone()),
+        }
+        player.set_speed(state.speed);
+        player.set_looping(state.looping);
+        player.play();
+        self.current = Some(index);
+        true
+    }
+
+    /// Evaluate transitions and advance the player (call each frame)
+    ///
+    /// Enters the first state if none is active yet. At most one transition
+    /// fires per update. Blend tree states take their weights from the
+    /// float parameters.
+    pub fn update(&mut self, player: &mut AnimationPlayer, delta_time: f32) {
+        if self.current.is_none() {
+            if let Some(first) = self.states.first().map(|s| s.name.clone()) {
+                self.start(player, &first);
+            }
+        } else if let Some((target, duration)) = self.pending_transition(player) {
+            let state = &self.states[target];
+            match &state.blend_tree {
+                Some(tree) => player.crossfade_to_blend_tree(tree.clone(), duration),
+                None => player.crossfade_to(state.clip.clone(), duration),
+            }
+            player.set_speed(state.speed);
+            player.set_looping(state.looping);
+            self.current = Some(target);
+        }
+
+        if self
+            .current_state()
+            .is_some_and(|state| state.blend_tree.is_some())
+        {
+            player.set_blend_parameters(|name| self.float(name));
+        }
+        player.update(delta_time);
+    }
+
+    /// Find the first transition that can fire, consuming its triggers
+    fn pending_transition(&mut self, player: &AnimationPlayer) -> Option<(usize, f32)> {
+        let current = self.current?;
+        let current_name = &self.states[current].name;
+
+        let transition = self.transitions.iter().find(|t| {
+            let from_matches = t.from.as_ref().is_none_or(|from| from == current_name);
+            from_matches
+                && t.to != *current_name
+                && t.exit_time
+                    .is_none_or(|exit| player.normalized_time() >= exit)
+                && t.conditions.iter().all(|c| self.is_met(c))
+        })?;
+        let target = self.find_state(&transition.to)?;
+        let duration = transition.duration;
+
+        let consumed: Vec<String> = transition
+            .conditions
+            .iter()
+            .filter_map(|c| match c {
+                Condition::Trigger(name) => Some(name.clone()),
+                _ => None,
+            })
+            .collect();
+        for name in consumed {
+            self.triggers.remove(&name);
+        }
+
+        Some((target, duration))
+    }
+
+    /// Check a single condition against the current parameters
+    fn is_met(&self, condition: &Condition) -> bool {
+        match condition {
+            Condition::Greater(name, threshold) => self.float(name) > *threshold,
+            Condition::Less(name, threshold) => self.float(name) < *threshold,
+            Condition::Bool(name, value) => self.bool(name) == *value,
+            Condition::Trigger(name) => self.triggers.contains(name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn machine() -> AnimationStateMachine {
+        let mut machine = AnimationStateMachine::new();
+        machine.add_state(AnimationState::new("idle", AnimationClip::new("idle")));
+        machine.add_state(AnimationState::new("walk", AnimationClip::new("walk")));
+        machine.add_state(AnimationState::new("jump", AnimationClip::new("jump")));
+        machine.add_transition(
+            Transition::new("idle", "walk").when(Condition::Greater("speed".into(), 0.1)),
+        );
+        machine.add_transition(
+            Transition::new("walk", "idle").when(Condition::Less("speed".into(), 0.1)),
+        );
+        machine
+            .add_transition(Transition::from_any("jump").when(Condition::Trigger("jump".into())));
+        machine
+    }
+
+    #[test]
+    fn test_float_transitions() {
+        let mut machine = machine();
+        let mut player = AnimationPlayer::new();
+        machine.update(&mut player, 0.016);
+        assert_eq!(machine.current_state_name(), Some("idle"));
+
+        machine.set_float("speed", 2.0);
+        machine.update(&mut player, 0.016);
+        assert_eq!(machine.current_state_name(), Some("walk"));
+        assert!(player.is_crossfading());
+
+        machine.set_float("speed", 0.0);
+        machine.update(&mut player, 0.016);
+        assert_eq!(machine.current_state_name(), Some("idle"));
+    }
+
+    #[test]
+    fn test_blend_tree_state_follows_parameter() {
+        let mut walk = AnimationClip::new("walk");
+        walk.duration = 1.0;
+        let mut run = AnimationClip::new("run");
+        run.duration = 0.5;
+        let tree = crate::animation::BlendTree1D::new("speed")
+            .with_clip(1.0, walk)
+            .with_clip(3.0, run);
+
+        let mut machine = AnimationStateMachine::new();
+        machine.add_state(AnimationState::blend("locomotion", tree));
+        let mut player = AnimationPlayer::new();
+        machine.set_float("speed", 2.0);
+        machine.update(&mut player, 0.0);
+
+        assert_eq!(player.blend_weights(), Some([0.5, 0.5].as_slice()));
+        assert!((player.clip().unwrap().duration - 0.75).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_trigger_is_consumed() {
+        let mut machine = machine();
+        let mut player = AnimationPlayer::new();
+        machine.start(&mut player, "walk");
+        machine.set_float("speed", 1.0);
+        machine.set_trigger("jump");
+
+        machine.update(&mut player, 0.016);
+        assert_eq!(machine.current_state_name(), Some("jump"));
+        assert!(machine.triggers.is_empty());
+    }
+}
@@ -0,0 +1,284 @@
+//! BC1/BC3 texture block compression
+//!
+//! A fast endpoint-fit encoder for the import pipeline. Each 4x4 block is
+//! reduced to two RGB565 endpoints at the ends of its principal color axis;
+//! BC3 adds an interpolated alpha block. Quality is below offline
+//! compressors but good enough for albedo and UI textures.
+
+use serde::{Deserialize, Serialize};
+
+/// Pixel format of processed texture data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CompressedFormat {
+    /// Uncompressed RGBA8, 4 bytes per pixel
+    Rgba8,
+    /// BC1 (DXT1), 8 bytes per 4x4 block, opaque
+    Bc1,
+    /// BC3 (DXT5), 16 bytes per 4x4 block, with alpha
+    Bc3,
+}
+
+impl CompressedFormat {
+    /// Format tag stored in processed files
+    pub(super) const fn tag(self) -> u32 {
+        match self {
+            Self::Rgba8 => 0,
+            Self::Bc1 => 1,
+            Self::Bc3 => 2,
+        }
+    }
+
+    /// Parse a stored format tag
+    pub(super) const fn from_tag(tag: u32) -> Option<Self> {
+        match tag {
+            0 => Some(Self::Rgba8),
+            1 => Some(Self::Bc1),
+            2 => Some(Self::Bc3),
+            _ => None,
+        }
+    }
+
+    /// Bytes needed for an image of the given size
+    #[must_use]
+    pub const fn data_size(self, width: u32, height: u32) -> usize {
+        let blocks = (width.div_ceil(4) * height.div_ceil(4)) as usize;
+        match self {
+            Self::Rgba8 => (width * height * 4) as usize,
+            Self::Bc1 => blocks * 8,
+            Self::Bc3 => blocks * 16,
+        }
+    }
+
+    /// Matching wgpu format, in sRGB space for color textures
+    #[must_use]
+    pub const fn wgpu_format(self) -> wgpu::TextureFormat {
+        match self {
+            Self::Rgba8 => wgpu::TextureFormat::Rgba8UnormSrgb,
+            Self::Bc1 => wgpu::TextureFormat::Bc1RgbaUnormSrgb,
+            Self::Bc3 => wgpu::TextureFormat::Bc3RgbaUnormSrgb,
+        }
+    }
+}
+
+/// Compress RGBA8 pixels to BC1 or BC3 blocks, row by row
+///
+/// Edge blocks of sizes not divisible by four repeat the last row and column.
+pub(super) fn compress(rgba: &[u8], width: u32, height: u32, format: CompressedFormat) -> Vec<u8> {
+    let mut out = Vec::with_capacity(format.data_size(width, height));
+    for by in 0..height.div_ceil(4) {
+        for bx in 0..width.div_ceil(4) {
+            let mut block = [[0u8; 4]; 16];
+            for (i, texel) in block.iter_mut().enumerate() {
+                let x = (bx * 4 + i as u32 % 4).min(width - 1);
+                let y = (by * 4 + i as u32 / 4).min(height - 1);
+                let offset = ((y * width + x) * 4) as usize;
+                texel.copy_from_slice(&rgba[offset..offset + 4]);
+            }
+            match format {
+                CompressedFormat::Rgba8 => unreachable!("RGBA8 is not block compressed"),
+                CompressedFormat::Bc1 => out.extend_from_slice(&encode_color(&block)),
+                CompressedFormat::Bc3 => {
+                    out.extend_from_slice(&encode_alpha(&block));
+                    out.extend_from_slice(&encode_color(&block));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Pack an RGB color to RGB565
+fn to_565(color: [u8; 3]) -> u16 {
+    (u16::from(color[0]) >> 3) << 11 | (u16::from(color[1]) >> 2) << 5 | u16::from(color[2]) >> 3
+}
+
+/// Expand RGB565 back to RGB8
+fn from_565(packed: u16) -> [i32; 3] {
+    let r = i32::from((packed >> 11) & 0x1f);
+    let g = i32::from((packed >> 5) & 0x3f);
+    let b = i32::from(packed & 0x1f);
+    [
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+    ]
+}
+
+/// Encode the color half of a block in four-color mode
+fn encode_color(block: &[[u8; 4]; 16]) -> [u8; 8] {
+    let (min, max) = color_endpoints(block);
+    let (mut c0, mut c1) = (to_565(max), to_565(min));
+    if c0 < c1 {
+        std::mem::swap(&mut c0, &mut c1);
+    }
+    let mut out = [0u8; 8];
+    out[0..2].copy_from_slice(&c0.to_le_bytes());
+    out[2..4].copy_from_slice(&c1.to_le_bytes());
+    if c0 == c1 {
+        return out;
+    }
+
+    let (e0, e1) = (from_565(c0), from_565(c1));
+    let palette = [
+        e0,
+        e1,
+        std::array::from_fn(|c| (2 * e0[c] + e1[c]) / 3),
+        std::array::from_fn(|c| (e0[c] + 2 * e1[c]) / 3),
+    ];
+    let mut indices = 0u32;
+    for (i, texel) in block.iter().enumerate() {
+        let best = nearest(&palette, |entry| {
+            (0..3)
+                .map(|c| (entry[c] - i32::from(texel[c])).pow(2))
+                .sum()
+        });
+        indices |= (best as u32) << (i * 2);
+    }
+    out[4..8].copy_from_slice(&indices.to_le_bytes());
+    out
+}
+
+/// Pick endpoints at the extremes of the block's principal color axis
+///
+/// The axis comes from a few power iterations on the color covariance, so
+/// gradients whose channels run in opposite directions are still captured.
+fn color_endpoints(block: &[[u8; 4]; 16]) -> ([u8; 3], [u8; 3]) {
+    let colors = block.map(|t| [f32::from(t[0]), f32::from(t[1]), f32::from(t[2])]);
+    let mean: [f32; 3] = std::array::from_fn(|c| colors.iter().map(|p| p[c]).sum::<f32>() / 16.0);
+    let mut covariance = [[0.0f32; 3]; 3];
+    for color in &colors {
+        let d: [f32; 3] = std::array::from_fn(|c| color[c] - mean[c]);
+        for (i, row) in covariance.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value += d[i] * d[j];
+            }
+        }
+    }
+
+    // Start from the most varying channel's row, which is never orthogonal
+    // to the principal axis
+    let widest = (0..3)
+        .max_by(|&a, &b| covariance[a][a].total_cmp(&covariance[b][b]))
+        .unwrap_or(0);
+    let mut axis = covariance[widest];
+    for _ in 0..4 {
+        let next: [f32; 3] =
+            std::array::from_fn(|i| (0..3).map(|j| covariance[i][j] * axis[j]).sum());
+        let length = next.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if length < 1e-6 {
+            break;
+        }
+        axis = next.map(|v| v / length);
+    }
+
+    let project = |color: &[f32; 3]| (0..3).map(|c| color[c] * axis[c]).sum::<f32>();
+    let (mut lo, mut hi) = (0, 0);
+    for (i, color) in colors.iter().enumerate() {
+        if project(color) < project(&colors[lo]) {
+            lo = i;
+        }
+        if project(color) > project(&colors[hi]) {
+            hi = i;
+        }
+    }
+    let rgb = |i: usize| [block[i][0], block[i][1], block[i][2]];
+    (rgb(lo), rgb(hi))
+}
+
+/// Encode the BC3 alpha half of a block in eight-value mode
+fn encode_alpha(block: &[[u8; 4]; 16]) -> [u8; 8] {
+    let a0 = block.iter().map(|t| t[3]).max().unwrap_or(255);
+    let a1 = block.iter().map(|t| t[3]).min().unwrap_or(255);
+    let mut out = [0u8; 8];
+    out[0] = a0;
+    out[1] = a1;
+    if a0 == a1 {
+        return out;
+    }
+
+    let (a0, a1) = (i32::from(a0), i32::from(a1));
+    let palette: [i32; 8] = std::array::from_fn(|i| match i {
+        0 => a0,
+        1 => a1,
+        _ => ((8 - i as i32) * a0 + (i as i32 - 1) * a1) / 7,
+    });
+    let mut indices = 0u64;
+    for (i, texel) in block.iter().enumerate() {
+        let best = nearest(&palette, |entry| (entry - i32::from(texel[3])).abs());
+        indices |= (best as u64) << (i * 3);
+    }
+    out[2..8].copy_from_slice(&indices.to_le_bytes()[..6]);
+    out
+}
+
+/// Index of the palette entry with the lowest error
+fn nearest<T>(palette: &[T], error: impl Fn(&T) -> i32) -> usize {
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, entry)| error(entry))
+        .map_or(0, |(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decode one BC1 color block back to RGB, four-color mode only
+    fn decode_color(block: &[u8]) -> Vec<[i32; 3]> {
+        let c0 = from_565(u16::from_le_bytes([block[0], block[1]]));
+        let c1 = from_565(u16::from_le_bytes([block[2], block[3]]));
+        let palette = [
+            c0,
+            c1,
+            std::array::from_fn(|c| (2 * c0[c] + c1[c]) / 3),
+            std::array::from_fn(|c| (c0[c] + 2 * c1[c]) / 3),
+        ];
+        let indices = u32::from_le_bytes([block[4], block[5], block[6], block[7]]);
+        (0..16)
+            .map(|i| palette[(indices >> (i * 2) & 3) as usize])
+            .collect()
+    }
+
+    #[test]
+    fn test_bc1_gradient_stays_close() {
+        // 6x5 image: not a multiple of four, so edge blocks are padded
+        let (width, height) = (6, 5);
+        let rgba: Vec<u8> = (0..width * height)
+            .flat_map(|i| {
+                let x = (i % width) as u8;
+                [x * 40, 255 - x * 40, 128, 255]
+            })
+            .collect();
+
+        let data = compress(&rgba, width, height, CompressedFormat::Bc1);
+        assert_eq!(data.len(), CompressedFormat::Bc1.data_size(width, height));
+        assert_eq!(data.len(), 4 * 8);
+
+        let decoded = decode_color(&data[..8]);
+        for (i, color) in decoded.iter().enumerate() {
+            let x = (i % 4) as i32;
+            assert!((color[0] - x * 40).abs() <= 24, "texel {i}: {color:?}");
+            assert!((color[2] - 128).abs() <= 8, "texel {i}: {color:?}");
+        }
+    }
+
+    #[test]
+    fn test_bc3_alpha_block() {
+        let rgba: Vec<u8> = (0..16u8).flat_map(|i| [200, 100, 50, i * 17]).collect();
+        let data = compress(&rgba, 4, 4, CompressedFormat::Bc3);
+        assert_eq!(data.len(), 16);
+        assert_eq!((data[0], data[1]), (255, 0));
+
+        let indices =
+            u64::from_le_bytes([data[2], data[3], data[4], data[5], data[6], data[7], 0, 0]);
+        // Fully transparent and fully opaque texels hit the endpoints exactly
+        assert_eq!(indices & 7, 1);
+        assert_eq!(indices >> 45 & 7, 0);
+        // Solid color: identical endpoints, all indices zero
+        assert_eq!(
+            u32::from_le_bytes([data[12], data[13], data[14], data[15]]),
+            0
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! Derived-data cache
+//!
+//! Stores the output of expensive import steps on disk, keyed by a hash of
+//! the source content and import settings, so later launches skip the work.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::platform::{AppDirs, DataKind};
+
+/// FNV-1a offset basis
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+/// FNV-1a prime
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Key identifying one processed result
+///
+/// Stable across runs and platforms, so it can name files on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheKey(u64);
+
+impl CacheKey {
+    /// Build a key from a processor, its version, its settings, and the source bytes
+    ///
+    /// Bump `version` whenever the processor's output format changes.
+    #[must_use]
+    pub fn new(processor: &str, version: u32, settings: &str, source: &[u8]) -> Self {
+        let mut hash = FNV_OFFSET;
+        let version = version.to_le_bytes();
+        let parts: [&[u8]; 4] = [processor.as_bytes(), &version, settings.as_bytes(), source];
+        for part in parts {
+            hash = fnv1a(hash, &(part.len() as u64).to_le_bytes());
+            hash = fnv1a(hash, part);
+        }
+        Self(hash)
+    }
+
+    /// Get the raw hash value
+    #[must_use]
+    pub const fn value(&self) -> u64 {
+        self.0
+    }
+
+    /// Get the key as a hex string
+    #[must_use]
+    pub fn hex(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
+/// Fold bytes into an FNV-1a hash
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// On-disk cache of processed import results
+#[derive(Debug)]
+pub struct DerivedDataCache {
+    /// Directory holding cached entries
+    root: PathBuf,
+    /// Number of lookups that found an entry
+    hits: AtomicU64,
+    /// Number of lookups that missed
+    misses: AtomicU64,
+}
+
+impl DerivedDataCache {
+    /// Create a cache rooted at a directory
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Create a cache in the application's platform cache directory
+    #[must_use]
+    pub fn from_app_dirs(dirs: &AppDirs) -> Self {
+        Self::new(dirs.dir(DataKind::Cache).join("derived"))
+    }
+
+    /// Get the cache root directory
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Path of the file storing an entry
+    fn entry_path(&self, key: CacheKey) -> PathBuf {
+        let hex = key.hex();
+        self.root.join(&hex[..2]).join(format!("{hex}.bin"))
+    }
+
+    /// Look up a cached entry
+    #[must_use]
+    pub fn get(&self, key: CacheKey) -> Option<Vec<u8>> {
+        match std::fs::read(self.entry_path(key)) {
+            Ok(bytes) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(bytes)
+            }
+            Err(_) => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Store an entry
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be written
+    pub fn put(&self, key: CacheKey, bytes: &[u8]) -> io::Result<()> {
+        let path = self.entry_path(key);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let temp = path.with_extension("tmp");
+        std::fs::write(&temp, bytes)?;
+        std::fs::rename(&temp, &path)
+    }
+
+    /// Return a cached entry, or compute and store it on a miss
+    ///
+    /// Failing to write the cache is logged and does not fail the import.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from `compute` on a miss
+    pub fn get_or_compute<E>(
+        &self,
+        key: CacheKey,
+        compute: impl FnOnce() -> Result<Vec<u8>, E>,
+    ) -> Result<Vec<u8>, E> {
+        if let Some(bytes) = self.get(key) {
+            return Ok(bytes);
+        }
+        let bytes = compute()?;
+        if let Err(e) = self.put(key, &bytes) {
+            log::warn!("Failed to write derived data {}: {e}", key.hex());
+        }
+        Ok(bytes)
+    }
+
+    /// Check if an entry exists
+    #[must_use]
+    pub fn contains(&self, key: CacheKey) -> bool {
+        self.entry_path(key).is_file()
+    }
+
+    /// Remove an entry
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry exists but cannot be removed
+    pub fn remove(&self, key: CacheKey) -> io::Result<()> {
+        match std::fs::remove_file(self.entry_path(key)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Remove every entry
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory cannot be removed
+    pub fn clear(&self) -> io::Result<()> {
+        match std::fs::remove_dir_all(&self.root) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Number of lookups that found an entry
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of lookups that missed
+    #[must_use]
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_depends_on_settings() {
+        let a = CacheKey::new("tangents", 1, "flip_v=false", b"mesh");
+        let b = CacheKey::new("tangents", 1, "flip_v=true", b"mesh");
+        let c = CacheKey::new("tangents", 2, "flip_v=false", b"mesh");
+
+        assert_eq!(a, CacheKey::new("tangents", 1, "flip_v=false", b"mesh"));
+        assert_ne!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_get_or_compute_caches() {
+        let root = std::env::temp_dir().join(format!("engine_ddc_test_{}", std::process::id()));
+        let cache = DerivedDataCache::new(&root);
+        let key = CacheKey::new("navmesh", 1, "", b"level");
+
+        let first: Result<_, ()> = cache.get_or_compute(key, || Ok(vec![1, 2, 3]));
+        let second: Result<_, ()> = cache.get_or_compute(key, || panic!("should be cached"));
+
+        assert_eq!(first.unwrap(), second.unwrap());
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 1);
+        cache.clear().unwrap();
+    }
+}
@@ -0,0 +1,50 @@
+//! Asset change events
+//!
+//! Emitted by `Assets<T>` when assets are added, replaced, or removed.
+//! Events hold weak handles, so a queued event never keeps an asset alive.
+
+use super::handle::{AssetHandle, WeakAssetHandle};
+
+/// A change to an asset in storage
+#[derive(Debug)]
+pub enum AssetEvent<T> {
+    /// A new asset was added (including finished background loads)
+    Created(WeakAssetHandle<T>),
+    /// An existing asset was replaced, e.g. by a hot reload
+    Modified(WeakAssetHandle<T>),
+    /// An asset was removed (carries the removed ID)
+    Removed(u64),
+}
+
+impl<T> AssetEvent<T> {
+    /// Get the ID of the affected asset
+    #[must_use]
+    pub fn id(&self) -> u64 {
+        match self {
+            Self::Created(handle) | Self::Modified(handle) => handle.id(),
+            Self::Removed(id) => *id,
+        }
+    }
+
+    /// Get the handle for created or modified assets
+    ///
+    /// Returns `None` for removals and for assets that were dropped (or
+    /// replaced again) since the event was recorded.
+    #[must_use]
+    pub fn handle(&self) -> Option<AssetHandle<T>> {
+        match self {
+            Self::Created(handle) | Self::Modified(handle) => handle.upgrade(),
+            Self::Removed(_) => None,
+        }
+    }
+}
+
+impl<T> Clone for AssetEvent<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Created(handle) => Self::Created(handle.clone()),
+            Self::Modified(handle) => Self::Modified(handle.clone()),
+            Self::Removed(id) => Self::Removed(*id),
+        }
+    }
+}
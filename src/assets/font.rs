@@ -0,0 +1,459 @@
+//! Font loading and glyph atlas generation
+//!
+//! Parses TTF/OTF fonts and rasterizes glyphs into a single atlas texture.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use glam::Vec2;
+
+use crate::renderer::{Texture, TextureError};
+
+/// Default atlas width in pixels
+const ATLAS_WIDTH: u32 = 512;
+
+/// Padding between glyphs in the atlas (avoids bleeding when filtering)
+const GLYPH_PADDING: u32 = 1;
+
+/// Errors from font loading
+#[derive(Debug, Clone)]
+pub enum FontError {
+    /// Failed to read the font file
+    IoError(String),
+    /// Failed to parse font data
+    ParseError(String),
+    /// A glyph is wider than the atlas at the requested pixel size
+    GlyphTooLarge(char),
+}
+
+impl std::fmt::Display for FontError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IoError(e) => write!(f, "IO error: {e}"),
+            Self::ParseError(e) => write!(f, "Parse error: {e}"),
+            Self::GlyphTooLarge(c) => write!(f, "Glyph {c:?} does not fit the atlas"),
+        }
+    }
+}
+
+impl std::error::Error for FontError {}
+
+/// Placement and metrics of a rasterized glyph
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glyph {
+    /// Top-left position in the atlas (pixels)
+    pub atlas_position: (u32, u32),
+    /// Bitmap size (pixels)
+    pub size: (u32, u32),
+    /// Offset from the pen position to the bitmap's bottom-left corner
+    pub offset: Vec2,
+    /// Horizontal advance to the next glyph
+    pub advance: f32,
+}
+
+impl Glyph {
+    /// Get normalized UV rectangle (min, max) within an atlas of the given size
+    #[must_use]
+    pub fn uv_rect(&self, atlas_size: (u32, u32)) -> (Vec2, Vec2) {
+        let atlas = Vec2::new(atlas_size.0 as f32, atlas_size.1 as f32);
+        let min = Vec2::new(self.atlas_position.0 as f32, self.atlas_position.1 as f32) / atlas;
+        let max = min + Vec2::new(self.size.0 as f32, self.size.1 as f32) / atlas;
+        (min, max)
+    }
+}
+
+/// How glyphs are stored in the atlas
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GlyphMode {
+    /// Coverage bitmaps, sharp only near the rasterized size
+    #[default]
+    Raster,
+    /// Signed distance fields, sharp at any scale
+    Sdf {
+        /// Distance range in atlas pixels on each side of the edge
+        spread: f32,
+    },
+}
+
+/// CPU-side glyph atlas (RGBA, white with coverage or distance in alpha)
+#[derive(Debug, Clone)]
+pub struct FontAtlas {
+    /// Atlas width in pixels
+    pub width: u32,
+    /// Atlas height in pixels
+    pub height: u32,
+    /// RGBA pixel data
+    pub pixels: Vec<u8>,
+}
+
+/// A font rasterized at a fixed pixel size
+pub struct Font {
+    /// Font name (from file name)
+    pub name: String,
+    /// Pixel size glyphs were rasterized at
+    pub px_size: f32,
+    /// Distance from baseline to top of tallest glyph
+    pub ascent: f32,
+    /// Distance from baseline to bottom of lowest glyph (negative)
+    pub descent: f32,
+    /// Recommended distance between baselines
+    pub line_height: f32,
+    /// Rasterized glyphs
+    glyphs: HashMap<char, Glyph>,
+    /// Glyph atlas
+    atlas: FontAtlas,
+    /// What the atlas alpha channel holds
+    mode: GlyphMode,
+    /// Parsed font (kept for kerning and late rasterization)
+    inner: fontdue::Font,
+}
+
+impl Font {
+    /// Printable ASCII, rasterized by default
+    pub const DEFAULT_CHARSET: &'static str = " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+
+    /// Load a font file and rasterize the default charset
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed
+    pub fn from_file(path: impl AsRef<Path>, px_size: f32) -> Result<Self, FontError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| FontError::IoError(e.to_string()))?;
+        let name = path
+            .file_stem()
+            .and_then(|n| n.to_str())
+            .unwrap_or("font")
+            .to_string();
+        Self::from_bytes(name, &bytes, px_size, Self::DEFAULT_CHARSET)
+    }
+
+    /// Parse font bytes and rasterize the given characters
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a valid TTF/OTF font
+    pub fn from_bytes(
+        name: impl Into<String>,
+        bytes: &[u8],
+        px_size: f32,
+        charset: &str,
+    ) -> Result<Self, FontError> {
+        Self::build(name.into(), bytes, px_size, charset, GlyphMode::Raster)
+    }
+
+    /// Parse font bytes and generate signed distance field glyphs
+    ///
+    /// SDF glyphs stay crisp when scaled, and support outlines and soft
+    /// shadows in the text shader. `spread` is the distance range in atlas
+    /// pixels; 4-8 works well for a 32-48 px rasterization size.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a valid TTF/OTF font
+    pub fn from_bytes_sdf(
+        name: impl Into<String>,
+        bytes: &[u8],
+        px_size: f32,
+        charset: &str,
+        spread: f32,
+    ) -> Result<Self, FontError> {
+        let spread = spread.max(1.0);
+        Self::build(
+            name.into(),
+            bytes,
+            px_size,
+            charset,
+            GlyphMode::Sdf { spread },
+        )
+    }
+
+    /// Rasterize glyphs and pack them into an atlas
+    fn build(
+        name: String,
+        bytes: &[u8],
+        px_size: f32,
+        charset: &str,
+        mode: GlyphMode,
+    ) -> Result<Self, FontError> {
+        let inner = fontdue::Font::from_bytes(bytes, fontdue::FontSettings::default())
+            .map_err(|e| FontError::ParseError(e.to_string()))?;
+
+        let (ascent, descent, line_height) = inner
+            .horizontal_line_metrics(px_size)
+            .map_or((px_size, 0.0, px_size), |m| {
+                (m.ascent, m.descent, m.new_line_size)
+            });
+
+        let mut chars: Vec<char> = charset.chars().collect();
+        chars.sort_unstable();
+        chars.dedup();
+
+        let rasterized: Vec<(char, fontdue::Metrics, Vec<u8>)> = chars
+            .into_iter()
+            .map(|c| {
+                let (mut metrics, mut bitmap) = inner.rasterize(c, px_size);
+                if let GlyphMode::Sdf { spread } = mode
+                    && metrics.width > 0
+                    && metrics.height > 0
+                {
+                    let pad = spread.ceil() as usize;
+                    bitmap = distance_field(&bitmap, metrics.width, metrics.height, pad, spread);
+                    metrics.width += pad * 2;
+                    metrics.height += pad * 2;
+                    metrics.xmin -= pad as i32;
+                    metrics.ymin -= pad as i32;
+                }
+                (c, metrics, bitmap)
+            })
+            .collect();
+
+        let sizes: Vec<(u32, u32)> = rasterized
+            .iter()
+            .map(|(_, m, _)| (m.width as u32, m.height as u32))
+            .collect();
+        let (positions, atlas_height) = pack_glyphs(&sizes, ATLAS_WIDTH)
+            .map_err(|index| FontError::GlyphTooLarge(rasterized[index].0))?;
+
+        let mut atlas = FontAtlas {
+            width: ATLAS_WIDTH,
+            height: atlas_height,
+            pixels: vec![0; (ATLAS_WIDTH * atlas_height * 4) as usize],
+        };
+        let mut glyphs = HashMap::with_capacity(rasterized.len());
+
+        for ((c, metrics, bitmap), &(x, y)) in rasterized.iter().zip(positions.iter()) {
+            let (w, h) = (metrics.width as u32, metrics.height as u32);
+            for row in 0..h {
+                for col in 0..w {
+                    let coverage = bitmap[(row * w + col) as usize];
+                    let index = (((y + row) * atlas.width + x + col) * 4) as usize;
+                    atlas.pixels[index..index + 4].copy_from_slice(&[255, 255, 255, coverage]);
+                }
+            }
+
+            glyphs.insert(
+                *c,
+                Glyph {
+                    atlas_position: (x, y),
+                    size: (w, h),
+                    offset: Vec2::new(metrics.xmin as f32, metrics.ymin as f32),
+                    advance: metrics.advance_width,
+                },
+            );
+        }
+
+        Ok(Self {
+            name,
+            px_size,
+            ascent,
+            descent,
+            line_height,
+            glyphs,
+            atlas,
+            mode,
+            inner,
+        })
+    }
+
+    /// Get a rasterized glyph
+    #[must_use]
+    pub fn glyph(&self, c: char) -> Option<&Glyph> {
+        self.glyphs.get(&c)
+    }
+
+    /// Get the number of rasterized glyphs
+    #[must_use]
+    pub fn glyph_count(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Get the glyph atlas
+    #[must_use]
+    pub fn atlas(&self) -> &FontAtlas {
+        &self.atlas
+    }
+
+    /// Get what the atlas alpha channel holds
+    #[must_use]
+    pub const fn mode(&self) -> GlyphMode {
+        self.mode
+    }
+
+    /// Get the kerning adjustment between two characters
+    #[must_use]
+    pub fn kerning(&self, left: char, right: char) -> f32 {
+        self.inner
+            .horizontal_kern(left, right, self.px_size)
+            .unwrap_or(0.0)
+    }
+
+    /// Measure a single line of text (width, height) in pixels
+    #[must_use]
+    pub fn measure(&self, text: &str) -> Vec2 {
+        let mut width = 0.0;
+        let mut prev: Option<char> = None;
+        for c in text.chars() {
+            if let Some(p) = prev {
+                width += self.kerning(p, c);
+            }
+            width += self.glyphs.get(&c).map_or(0.0, |g| g.advance);
+            prev = Some(c);
+        }
+        Vec2::new(width, self.line_height)
+    }
+
+    /// Upload the glyph atlas to the GPU
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the texture cannot be created
+    pub fn create_texture(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Result<Texture, TextureError> {
+        Texture::from_rgba(
+            device,
+            queue,
+            &self.atlas.pixels,
+            (self.atlas.width, self.atlas.height),
+            Some(&self.name),
+        )
+    }
+}
+
+impl std::fmt::Debug for Font {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Font")
+            .field("name", &self.name)
+            .field("px_size", &self.px_size)
+            .field("glyph_count", &self.glyphs.len())
+            .field("atlas_size", &(self.atlas.width, self.atlas.height))
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
+/// Convert a coverage bitmap into a padded signed distance field
+///
+/// Distances are measured to the nearest texel on the other side of the
+/// edge, searched within `pad` texels, and mapped so 128 is the edge.
+fn distance_field(
+    coverage: &[u8],
+    width: usize,
+    height: usize,
+    pad: usize,
+    spread: f32,
+) -> Vec<u8> {
+    let out_width = width + pad * 2;
+    let out_height = height + pad * 2;
+    let inside = |x: isize, y: isize| {
+        x >= 0
+            && y >= 0
+            && (x as usize) < width
+            && (y as usize) < height
+            && coverage[y as usize * width + x as usize] >= 128
+    };
+
+    let radius = pad as isize;
+    let mut field = Vec::with_capacity(out_width * out_height);
+    for oy in 0..out_height as isize {
+        for ox in 0..out_width as isize {
+            let (x, y) = (ox - radius, oy - radius);
+            let here = inside(x, y);
+            let mut nearest = f32::MAX;
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
+                    let d2 = (dx * dx + dy * dy) as f32;
+                    if d2 < nearest && inside(x + dx, y + dy) != here {
+                        nearest = d2;
+                    }
+                }
+            }
+            // Edge lies halfway between texel centers
+            let distance = if nearest == f32::MAX {
+                spread
+            } else {
+                (nearest.sqrt() - 0.5).clamp(0.0, spread)
+            };
+            let signed = if here { distance } else { -distance };
+            let value = (0.5 + signed / (2.0 * spread)).clamp(0.0, 1.0);
+            field.push((value * 255.0).round() as u8);
+        }
+    }
+    field
+}
+
+/// Pack rectangles into rows of a fixed-width atlas
+///
+/// Returns the top-left position of each rectangle and the total
+/// atlas height, rounded up to a power of two, or the index of the first
+/// rectangle too wide to fit in a row.
+fn pack_glyphs(sizes: &[(u32, u32)], atlas_width: u32) -> Result<(Vec<(u32, u32)>, u32), usize> {
+    if let Some(index) = sizes
+        .iter()
+        .position(|&(w, _)| w + GLYPH_PADDING * 2 > atlas_width)
+    {
+        return Err(index);
+    }
+
+    let mut positions = Vec::with_capacity(sizes.len());
+    let mut x = GLYPH_PADDING;
+    let mut y = GLYPH_PADDING;
+    let mut row_height = 0;
+
+    for &(w, h) in sizes {
+        if x + w + GLYPH_PADDING > atlas_width {
+            x = GLYPH_PADDING;
+            y += row_height + GLYPH_PADDING;
+            row_height = 0;
+        }
+        positions.push((x, y));
+        x += w + GLYPH_PADDING;
+        row_height = row_height.max(h);
+    }
+
+    let height = (y + row_height + GLYPH_PADDING).next_power_of_two();
+    Ok((positions, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_glyphs_wraps_rows() {
+        let sizes = vec![(10, 12), (10, 8), (10, 10)];
+        let (positions, height) = pack_glyphs(&sizes, 25).unwrap();
+
+        assert_eq!(positions[0], (1, 1));
+        assert_eq!(positions[1], (12, 1));
+        assert_eq!(positions[2], (1, 14)); // Wrapped below tallest glyph
+        assert_eq!(height, 32);
+    }
+
+    #[test]
+    fn test_pack_glyphs_rejects_wide_glyph() {
+        let sizes = vec![(10, 12), (24, 8)];
+
+        assert_eq!(pack_glyphs(&sizes, 25), Err(1));
+    }
+
+    #[test]
+    fn test_distance_field_edge() {
+        // 4x1 bitmap: two covered texels on the left
+        let field = distance_field(&[255, 255, 0, 0], 4, 1, 2, 2.0);
+        let row = &field[2 * 8..3 * 8];
+
+        assert!(row[3] > 128); // Inside, next to the edge
+        assert!(row[4] < 128); // Outside, next to the edge
+        assert_eq!(row[7], 0); // Beyond the spread
+    }
+
+    #[test]
+    fn test_invalid_font_bytes() {
+        let result = Font::from_bytes("bad", &[0, 1, 2, 3], 16.0, "abc");
+        assert!(matches!(result, Err(FontError::ParseError(_))));
+    }
+}
@@ -1,12 +1,23 @@
 //! glTF 2.0 model loader
 //!
-//! Loads meshes, materials, and hierarchy from glTF/GLB files.
+//! Loads meshes, materials, cameras, lights, animations, and hierarchy from
+//! glTF/GLB files.
 
 use std::path::Path;
 
-use glam::{Quat, Vec3};
+use glam::{Mat3, Quat, Vec2, Vec3};
 
-use crate::renderer::{Material, Mesh, Vertex};
+use crate::animation::{AnimationClip, Channel, Interpolation, Keyframe};
+use crate::renderer::{
+    Camera, DirectionalLight, GpuLight, Material, Mesh, MorphTarget, MorphTargets, PointLight,
+    SpotLight, Vertex,
+};
+
+/// Keyframe reduction tolerance applied to imported animations
+const ANIMATION_TOLERANCE: f32 = 1e-4;
+
+/// Bits per component for imported rotation keyframes
+const ANIMATION_ROTATION_BITS: u32 = 16;
 
 /// Result type for glTF operations
 pub type GltfResult<T> = Result<T, GltfError>;
@@ -43,6 +54,8 @@ pub struct LoadedPrimitive {
     pub indices: Vec<u32>,
     /// Material index (if any)
     pub material_index: Option<usize>,
+    /// Morph targets, with one offset per vertex
+    pub morph_targets: Vec<MorphTarget>,
 }
 
 /// Loaded mesh with primitives
@@ -52,6 +65,37 @@ pub struct LoadedMesh {
     pub name: String,
     /// All primitives in this mesh
     pub primitives: Vec<LoadedPrimitive>,
+    /// Default morph target weights
+    pub weights: Vec<f32>,
+}
+
+/// UV transform from `KHR_texture_transform`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoadedTextureTransform {
+    /// UV offset
+    pub offset: Vec2,
+    /// Rotation in radians (counter-clockwise)
+    pub rotation: f32,
+    /// UV scale
+    pub scale: Vec2,
+}
+
+impl LoadedTextureTransform {
+    /// Get the UV transform matrix (scale, then rotate, then offset)
+    #[must_use]
+    pub fn matrix(&self) -> Mat3 {
+        let (sin, cos) = self.rotation.sin_cos();
+        let translation = Mat3::from_translation(self.offset);
+        let rotation =
+            Mat3::from_cols(Vec3::new(cos, -sin, 0.0), Vec3::new(sin, cos, 0.0), Vec3::Z);
+        translation * rotation * Mat3::from_scale(self.scale)
+    }
+
+    /// Transform a UV coordinate
+    #[must_use]
+    pub fn apply(&self, uv: Vec2) -> Vec2 {
+        self.matrix().transform_point2(uv)
+    }
 }
 
 /// Loaded material data
@@ -67,21 +111,203 @@ pub struct LoadedMaterial {
     pub roughness: f32,
     /// Base color texture path (if any)
     pub base_color_texture: Option<String>,
+    /// Base color UV transform (`KHR_texture_transform`)
+    pub base_color_transform: Option<LoadedTextureTransform>,
+    /// Emissive color factor
+    pub emissive: [f32; 3],
+    /// Emissive multiplier (`KHR_materials_emissive_strength`)
+    pub emissive_strength: f32,
+    /// Fraction of light transmitted through the surface (`KHR_materials_transmission`)
+    pub transmission: f32,
+    /// Clearcoat layer intensity (`KHR_materials_clearcoat`)
+    pub clearcoat: f32,
+    /// Clearcoat layer roughness (`KHR_materials_clearcoat`)
+    pub clearcoat_roughness: f32,
 }
 
 impl LoadedMaterial {
+    /// Get the emissive color scaled by its strength
+    #[must_use]
+    pub fn emissive_radiance(&self) -> Vec3 {
+        Vec3::from_array(self.emissive) * self.emissive_strength
+    }
+
     /// Convert to engine Material
+    ///
+    /// A glossy clearcoat raises the specular response of the base layer.
+    /// Emission and transmission have no equivalent in the engine material
+    /// and are kept on `LoadedMaterial` for the PBR pipeline.
     #[must_use]
     pub fn to_material(&self) -> Material {
+        let glossiness =
+            (1.0 - self.roughness).max(self.clearcoat * (1.0 - self.clearcoat_roughness));
         Material {
             color: Vec3::new(self.base_color[0], self.base_color[1], self.base_color[2]),
-            specular: 1.0 - self.roughness,
-            shininess: 32.0 * (1.0 - self.roughness) + 1.0,
+            specular: glossiness,
+            shininess: 32.0 * glossiness + 1.0,
             use_texture: self.base_color_texture.is_some(),
         }
     }
 }
 
+/// Projection of a loaded camera
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CameraProjection {
+    /// Perspective projection
+    Perspective {
+        /// Vertical field of view in radians
+        yfov: f32,
+        /// Aspect ratio, if the file specifies one
+        aspect_ratio: Option<f32>,
+        /// Near clipping plane
+        znear: f32,
+        /// Far clipping plane, `None` for infinite
+        zfar: Option<f32>,
+    },
+    /// Orthographic projection
+    Orthographic {
+        /// Horizontal half-extent
+        xmag: f32,
+        /// Vertical half-extent
+        ymag: f32,
+        /// Near clipping plane
+        znear: f32,
+        /// Far clipping plane
+        zfar: f32,
+    },
+}
+
+/// Loaded camera data
+#[derive(Debug, Clone)]
+pub struct LoadedCamera {
+    /// Camera name
+    pub name: String,
+    /// Projection parameters
+    pub projection: CameraProjection,
+}
+
+impl LoadedCamera {
+    /// Far plane used when the file specifies an infinite projection
+    const DEFAULT_FAR: f32 = 1000.0;
+
+    /// Convert to engine Camera placed at a node's world transform
+    ///
+    /// glTF cameras look down their local -Z axis. Orthographic cameras are
+    /// approximated with the default perspective field of view.
+    #[must_use]
+    pub fn to_camera(&self, position: Vec3, rotation: Quat) -> Camera {
+        let mut camera = Camera::look_at(
+            position,
+            position + rotation * Vec3::NEG_Z,
+            rotation * Vec3::Y,
+        );
+        match self.projection {
+            CameraProjection::Perspective {
+                yfov,
+                aspect_ratio,
+                znear,
+                zfar,
+            } => {
+                camera.fov = yfov;
+                camera.near = znear;
+                camera.far = zfar.unwrap_or(Self::DEFAULT_FAR);
+                if let Some(aspect) = aspect_ratio {
+                    camera.aspect = aspect;
+                }
+            }
+            CameraProjection::Orthographic {
+                xmag,
+                ymag,
+                znear,
+                zfar,
+            } => {
+                camera.near = znear;
+                camera.far = zfar;
+                if ymag > 0.0 {
+                    camera.aspect = xmag / ymag;
+                }
+            }
+        }
+        camera
+    }
+}
+
+/// Type of a loaded punctual light
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoadedLightKind {
+    /// Directional light
+    Directional,
+    /// Point light
+    Point,
+    /// Spot light
+    Spot {
+        /// Inner cone angle in radians
+        inner_cone_angle: f32,
+        /// Outer cone angle in radians
+        outer_cone_angle: f32,
+    },
+}
+
+/// Loaded light data (`KHR_lights_punctual`)
+#[derive(Debug, Clone)]
+pub struct LoadedLight {
+    /// Light name
+    pub name: String,
+    /// Light type
+    pub kind: LoadedLightKind,
+    /// Linear RGB color
+    pub color: Vec3,
+    /// Intensity (candela for point/spot, lux for directional)
+    pub intensity: f32,
+    /// Range after which the light has no effect, `None` for infinite
+    pub range: Option<f32>,
+}
+
+impl LoadedLight {
+    /// Get attenuation terms that fade the light to about 1% at its range
+    ///
+    /// Lights without a range keep the engine's default falloff.
+    #[must_use]
+    pub fn attenuation(&self) -> Option<(f32, f32, f32)> {
+        let range = self.range.filter(|range| *range > 0.0)?;
+        Some((1.0, 4.5 / range, 75.0 / (range * range)))
+    }
+
+    /// Convert to a GPU light placed at a node's world transform
+    ///
+    /// glTF lights point down their local -Z axis. Intensity is passed
+    /// through unchanged and the range is mapped by
+    /// [`attenuation`](Self::attenuation).
+    #[must_use]
+    pub fn to_gpu(&self, position: Vec3, rotation: Quat) -> GpuLight {
+        let direction = rotation * Vec3::NEG_Z;
+        match self.kind {
+            LoadedLightKind::Directional => {
+                DirectionalLight::new(direction, self.color, self.intensity).to_gpu()
+            }
+            LoadedLightKind::Point => {
+                let mut light = PointLight::new(position, self.color, self.intensity);
+                if let Some(attenuation) = self.attenuation() {
+                    light.attenuation = attenuation;
+                }
+                light.to_gpu()
+            }
+            LoadedLightKind::Spot {
+                inner_cone_angle,
+                outer_cone_angle,
+            } => {
+                let mut light = SpotLight::new(position, direction, self.color, self.intensity);
+                light.inner_angle = inner_cone_angle;
+                light.outer_angle = outer_cone_angle;
+                if let Some(attenuation) = self.attenuation() {
+                    light.attenuation = attenuation;
+                }
+                light.to_gpu()
+            }
+        }
+    }
+}
+
 /// Loaded node in the scene hierarchy
 #[derive(Debug, Clone)]
 pub struct LoadedNode {
@@ -95,6 +321,10 @@ pub struct LoadedNode {
     pub scale: Vec3,
     /// Mesh index (if this node has a mesh)
     pub mesh_index: Option<usize>,
+    /// Camera index (if this node has a camera)
+    pub camera_index: Option<usize>,
+    /// Light index (if this node has a light)
+    pub light_index: Option<usize>,
     /// Child node indices
     pub children: Vec<usize>,
 }
@@ -106,10 +336,16 @@ pub struct LoadedGltf {
     pub meshes: Vec<LoadedMesh>,
     /// All materials
     pub materials: Vec<LoadedMaterial>,
+    /// All cameras
+    pub cameras: Vec<LoadedCamera>,
+    /// All punctual lights
+    pub lights: Vec<LoadedLight>,
     /// All nodes
     pub nodes: Vec<LoadedNode>,
     /// Root node indices
     pub root_nodes: Vec<usize>,
+    /// Animation clips, with channels targeting node indices
+    pub animations: Vec<AnimationClip>,
 }
 
 /// Load a glTF or GLB file
@@ -126,18 +362,7 @@ pub fn load_gltf(path: impl AsRef<Path>) -> GltfResult<LoadedGltf> {
     // Load materials
     let materials: Vec<LoadedMaterial> = document
         .materials()
-        .map(|mat| {
-            let pbr = mat.pbr_metallic_roughness();
-            LoadedMaterial {
-                name: mat.name().unwrap_or("Unnamed").to_string(),
-                base_color: pbr.base_color_factor(),
-                metallic: pbr.metallic_factor(),
-                roughness: pbr.roughness_factor(),
-                base_color_texture: pbr
-                    .base_color_texture()
-                    .map(|info| format!("texture_{}", info.texture().index())),
-            }
-        })
+        .map(|mat| load_material(&mat))
         .collect();
 
     // Load meshes
@@ -152,10 +377,67 @@ pub fn load_gltf(path: impl AsRef<Path>) -> GltfResult<LoadedGltf> {
             LoadedMesh {
                 name: mesh.name().unwrap_or("Unnamed").to_string(),
                 primitives,
+                weights: mesh.weights().map(<[f32]>::to_vec).unwrap_or_default(),
             }
         })
         .collect();
 
+    // Load cameras
+    let cameras: Vec<LoadedCamera> = document
+        .cameras()
+        .map(|camera| {
+            let projection = match camera.projection() {
+                gltf::camera::Projection::Perspective(p) => CameraProjection::Perspective {
+                    yfov: p.yfov(),
+                    aspect_ratio: p.aspect_ratio(),
+                    znear: p.znear(),
+                    zfar: p.zfar(),
+                },
+                gltf::camera::Projection::Orthographic(o) => CameraProjection::Orthographic {
+                    xmag: o.xmag(),
+                    ymag: o.ymag(),
+                    znear: o.znear(),
+                    zfar: o.zfar(),
+                },
+            };
+            LoadedCamera {
+                name: camera.name().unwrap_or("Camera").to_string(),
+                projection,
+            }
+        })
+        .collect();
+
+    // Load punctual lights
+    let lights: Vec<LoadedLight> = document
+        .lights()
+        .map(|lights| {
+            lights
+                .map(|light| {
+                    let kind = match light.kind() {
+                        gltf::khr_lights_punctual::Kind::Directional => {
+                            LoadedLightKind::Directional
+                        }
+                        gltf::khr_lights_punctual::Kind::Point => LoadedLightKind::Point,
+                        gltf::khr_lights_punctual::Kind::Spot {
+                            inner_cone_angle,
+                            outer_cone_angle,
+                        } => LoadedLightKind::Spot {
+                            inner_cone_angle,
+                            outer_cone_angle,
+                        },
+                    };
+                    LoadedLight {
+                        name: light.name().unwrap_or("Light").to_string(),
+                        kind,
+                        color: Vec3::from_array(light.color()),
+                        intensity: light.intensity(),
+                        range: light.range(),
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
     // Load nodes
     let nodes: Vec<LoadedNode> = document
         .nodes()
@@ -167,6 +449,8 @@ pub fn load_gltf(path: impl AsRef<Path>) -> GltfResult<LoadedGltf> {
                 rotation: Quat::from_array(rotation),
                 scale: Vec3::from_array(scale),
                 mesh_index: node.mesh().map(|m| m.index()),
+                camera_index: node.camera().map(|c| c.index()),
+                light_index: node.light().map(|l| l.index()),
                 children: node.children().map(|c| c.index()).collect(),
             }
         })
@@ -192,14 +476,148 @@ pub fn load_gltf(path: impl AsRef<Path>) -> GltfResult<LoadedGltf> {
             .collect()
     };
 
+    // Load animations, removing redundant keyframes
+    let animations: Vec<AnimationClip> = document
+        .animations()
+        .map(|animation| {
+            let mut clip = load_animation(&animation, &buffers);
+            clip.quantize_rotations(ANIMATION_ROTATION_BITS);
+            clip.optimize(ANIMATION_TOLERANCE);
+            clip
+        })
+        .collect();
+
     Ok(LoadedGltf {
         meshes,
         materials,
+        cameras,
+        lights,
         nodes,
         root_nodes,
+        animations,
     })
 }
 
+/// Load an animation's channels into a clip
+///
+/// The clip uses the interpolation of its first sampler.
+fn load_animation(
+    animation: &gltf::Animation<'_>,
+    buffers: &[gltf::buffer::Data],
+) -> AnimationClip {
+    use gltf::animation::util::ReadOutputs;
+
+    let mut clip = AnimationClip::new(animation.name().unwrap_or("Animation"));
+    for (index, channel) in animation.channels().enumerate() {
+        let interpolation = match channel.sampler().interpolation() {
+            gltf::animation::Interpolation::Linear => Interpolation::Linear,
+            gltf::animation::Interpolation::Step => Interpolation::Step,
+            gltf::animation::Interpolation::CubicSpline => Interpolation::CubicSpline,
+        };
+        if index == 0 {
+            clip.interpolation = interpolation;
+        }
+        let cubic = interpolation == Interpolation::CubicSpline;
+
+        let reader = channel.reader(|buffer| Some(&buffers[buffer.index()]));
+        let (Some(inputs), Some(outputs)) = (reader.read_inputs(), reader.read_outputs()) else {
+            continue;
+        };
+        let times: Vec<f32> = inputs.collect();
+        let target = channel.target().node().index();
+
+        let converted = match outputs {
+            ReadOutputs::Translations(values) => Channel::Translation(keyframes(
+                &times,
+                values.map(Vec3::from_array).collect(),
+                cubic,
+            )),
+            ReadOutputs::Rotations(values) => Channel::Rotation(keyframes(
+                &times,
+                values.into_f32().map(Quat::from_array).collect(),
+                cubic,
+            )),
+            ReadOutputs::Scales(values) => Channel::Scale(keyframes(
+                &times,
+                values.map(Vec3::from_array).collect(),
+                cubic,
+            )),
+            ReadOutputs::MorphTargetWeights(values) => {
+                let weights: Vec<f32> = values.into_f32().collect();
+                let per_key = weights.len() / times.len().max(1) / if cubic { 3 } else { 1 };
+                let values = weights
+                    .chunks(per_key.max(1))
+                    .map(<[f32]>::to_vec)
+                    .collect();
+                Channel::MorphWeights(keyframes(&times, values, cubic))
+            }
+        };
+        clip.add_channel(target, converted);
+    }
+    clip
+}
+
+/// Pair sampler times with output values
+///
+/// Cubic spline outputs hold (in-tangent, value, out-tangent) per key.
+fn keyframes<T: Clone>(times: &[f32], values: Vec<T>, cubic: bool) -> Vec<Keyframe<T>> {
+    if cubic {
+        times
+            .iter()
+            .zip(values.chunks_exact(3))
+            .map(|(&time, v)| {
+                Keyframe::with_tangents(time, v[1].clone(), v[0].clone(), v[2].clone())
+            })
+            .collect()
+    } else {
+        times
+            .iter()
+            .zip(values)
+            .map(|(&time, value)| Keyframe::new(time, value))
+            .collect()
+    }
+}
+
+/// Load a single material, including supported extensions
+fn load_material(mat: &gltf::Material<'_>) -> LoadedMaterial {
+    let pbr = mat.pbr_metallic_roughness();
+    let base_color_info = pbr.base_color_texture();
+
+    // The gltf crate has no typed clearcoat support, so read the raw extension
+    let clearcoat = mat
+        .extensions()
+        .and_then(|ext| ext.get("KHR_materials_clearcoat"));
+    let clearcoat_value = |key: &str| {
+        clearcoat
+            .and_then(|c| c.get(key))
+            .and_then(serde_json::Value::as_f64)
+            .map_or(0.0, |v| v as f32)
+    };
+
+    LoadedMaterial {
+        name: mat.name().unwrap_or("Unnamed").to_string(),
+        base_color: pbr.base_color_factor(),
+        metallic: pbr.metallic_factor(),
+        roughness: pbr.roughness_factor(),
+        base_color_texture: base_color_info
+            .as_ref()
+            .map(|info| format!("texture_{}", info.texture().index())),
+        base_color_transform: base_color_info
+            .as_ref()
+            .and_then(|info| info.texture_transform())
+            .map(|t| LoadedTextureTransform {
+                offset: Vec2::from_array(t.offset()),
+                rotation: t.rotation(),
+                scale: Vec2::from_array(t.scale()),
+            }),
+        emissive: mat.emissive_factor(),
+        emissive_strength: mat.emissive_strength().unwrap_or(1.0),
+        transmission: mat.transmission().map_or(0.0, |t| t.transmission_factor()),
+        clearcoat: clearcoat_value("clearcoatFactor"),
+        clearcoat_roughness: clearcoat_value("clearcoatRoughnessFactor"),
+    }
+}
+
 /// Load a single primitive from a glTF mesh
 fn load_primitive(
     primitive: &gltf::Primitive<'_>,
@@ -243,10 +661,20 @@ fn load_primitive(
             (0..vertices.len() as u32).collect()
         });
 
+    // Read morph targets, filling missing attributes with zero offsets
+    let morph_targets = reader
+        .read_morph_targets()
+        .map(|(positions, normals, _)| MorphTarget {
+            positions: positions.map_or_else(|| vec![[0.0; 3]; vertices.len()], Iterator::collect),
+            normals: normals.map_or_else(Vec::new, Iterator::collect),
+        })
+        .collect();
+
     Some(LoadedPrimitive {
         vertices,
         indices,
         material_index: primitive.material().index(),
+        morph_targets,
     })
 }
 
@@ -257,4 +685,127 @@ impl LoadedPrimitive {
     pub fn to_mesh(&self) -> Mesh {
         Mesh::from_data(self.vertices.clone(), self.indices.clone())
     }
+
+    /// Get the morph targets component, if the primitive has any
+    #[must_use]
+    pub fn to_morph_targets(&self, weights: &[f32]) -> Option<MorphTargets> {
+        (!self.morph_targets.is_empty()).then(|| {
+            MorphTargets::new(self.vertices.clone(), self.morph_targets.clone())
+                .with_weights(weights)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::*;
+
+    fn light(kind: LoadedLightKind, range: Option<f32>) -> LoadedLight {
+        LoadedLight {
+            name: "Light".to_string(),
+            kind,
+            color: Vec3::new(1.0, 0.5, 0.25),
+            intensity: 40.0,
+            range,
+        }
+    }
+
+    #[test]
+    fn test_texture_transform_scales_then_rotates_then_offsets() {
+        let transform = LoadedTextureTransform {
+            offset: Vec2::new(0.5, 0.0),
+            rotation: FRAC_PI_2,
+            scale: Vec2::new(2.0, 1.0),
+        };
+
+        // (1, 0) scales to (2, 0), rotates a quarter turn to (0, -2), then offsets
+        assert!(transform.apply(Vec2::X).distance(Vec2::new(0.5, -2.0)) < 1e-5);
+        assert!(transform.apply(Vec2::Y).distance(Vec2::new(1.5, 0.0)) < 1e-5);
+        assert!(transform.apply(Vec2::ZERO).distance(Vec2::new(0.5, 0.0)) < 1e-5);
+
+        let identity = LoadedTextureTransform {
+            offset: Vec2::ZERO,
+            rotation: 0.0,
+            scale: Vec2::ONE,
+        };
+        assert!(identity.matrix().abs_diff_eq(Mat3::IDENTITY, 1e-6));
+    }
+
+    #[test]
+    fn test_perspective_camera_conversion() {
+        let loaded = LoadedCamera {
+            name: "Camera".to_string(),
+            projection: CameraProjection::Perspective {
+                yfov: 0.8,
+                aspect_ratio: Some(2.0),
+                znear: 0.5,
+                zfar: None,
+            },
+        };
+        let rotation = Quat::from_rotation_y(FRAC_PI_2);
+        let camera = loaded.to_camera(Vec3::new(1.0, 2.0, 3.0), rotation);
+
+        assert_eq!(camera.position, Vec3::new(1.0, 2.0, 3.0));
+        assert!(camera.direction.distance(Vec3::NEG_X) < 1e-5);
+        assert_eq!(camera.fov, 0.8);
+        assert_eq!(camera.aspect, 2.0);
+        assert_eq!(camera.near, 0.5);
+        assert_eq!(camera.far, LoadedCamera::DEFAULT_FAR);
+    }
+
+    #[test]
+    fn test_orthographic_camera_conversion() {
+        let loaded = LoadedCamera {
+            name: "Camera".to_string(),
+            projection: CameraProjection::Orthographic {
+                xmag: 4.0,
+                ymag: 2.0,
+                znear: 0.1,
+                zfar: 50.0,
+            },
+        };
+        let camera = loaded.to_camera(Vec3::ZERO, Quat::IDENTITY);
+
+        assert!(camera.direction.distance(Vec3::NEG_Z) < 1e-5);
+        assert_eq!(camera.fov, Camera::new().fov);
+        assert_eq!(camera.aspect, 2.0);
+        assert_eq!(camera.near, 0.1);
+        assert_eq!(camera.far, 50.0);
+    }
+
+    #[test]
+    fn test_light_intensity_and_range() {
+        let point = light(LoadedLightKind::Point, Some(10.0)).to_gpu(Vec3::Y, Quat::IDENTITY);
+        assert_eq!(point.intensity, 40.0);
+        assert_eq!(point.color, [1.0, 0.5, 0.25]);
+        assert_eq!(point.position, [0.0, 1.0, 0.0]);
+        let falloff = point.constant + point.linear * 10.0 + point.quadratic * 100.0;
+        assert!((1.0 / falloff - 0.0124).abs() < 1e-3);
+
+        // No range keeps the default falloff
+        let unbounded = light(LoadedLightKind::Point, None);
+        assert_eq!(unbounded.attenuation(), None);
+        let default = PointLight::new(Vec3::ZERO, Vec3::ONE, 1.0).attenuation;
+        let gpu = unbounded.to_gpu(Vec3::ZERO, Quat::IDENTITY);
+        assert_eq!((gpu.constant, gpu.linear, gpu.quadratic), default);
+
+        let spot = light(
+            LoadedLightKind::Spot {
+                inner_cone_angle: 0.2,
+                outer_cone_angle: 0.4,
+            },
+            Some(5.0),
+        )
+        .to_gpu(Vec3::ZERO, Quat::IDENTITY);
+        assert_eq!(spot.intensity, 40.0);
+        assert_eq!(spot.direction, [0.0, 0.0, -1.0]);
+        assert!((spot.linear - 0.9).abs() < 1e-6);
+        assert!((spot.outer_cone_cos - 0.4f32.cos()).abs() < 1e-6);
+
+        let sun = light(LoadedLightKind::Directional, Some(5.0)).to_gpu(Vec3::ZERO, Quat::IDENTITY);
+        assert_eq!(sun.intensity, 40.0);
+        assert_eq!(sun.quadratic, 0.0);
+    }
 }
@@ -36,6 +36,14 @@ impl<T> AssetHandle<T> {
         }
     }
 
+    /// Create a handle that reuses an existing asset ID (for replacements)
+    pub(crate) fn with_id(id: u64, value: T) -> Self {
+        Self {
+            id,
+            inner: Arc::new(value),
+        }
+    }
+
     /// Get the unique ID of this asset
     #[must_use]
     pub const fn id(&self) -> u64 {
@@ -0,0 +1,661 @@
+//! Offline asset import pipeline
+//!
+//! Converts source assets into engine formats ahead of time and stores
+//! them in a cache directory keyed by content hash, so runtime loading
+//! skips decoding and the editor can rebuild only what changed.
+
+use std::path::{Path, PathBuf};
+
+use super::block_compress::{self, CompressedFormat};
+use super::cache::{CacheKey, DerivedDataCache};
+use super::gltf::{LoadedMesh, LoadedPrimitive, load_gltf};
+use crate::renderer::{MorphTarget, Vertex};
+
+/// Default cache directory name
+pub const DEFAULT_CACHE_DIR: &str = ".cache";
+
+/// Processed texture file magic
+const TEXTURE_MAGIC: &[u8; 4] = b"ETEX";
+/// Processed mesh file magic
+const MESH_MAGIC: &[u8; 4] = b"EMSH";
+/// Version of the processed formats, part of every cache key
+const FORMAT_VERSION: u32 = 3;
+/// Marker for a primitive without a material
+const NO_MATERIAL: u32 = u32::MAX;
+
+/// Errors from importing assets
+#[derive(Debug, Clone)]
+pub enum ImportError {
+    /// Failed to read a source or write the cache
+    IoError(String),
+    /// Failed to decode a source or processed asset
+    DecodeError(String),
+    /// No importer handles this file type
+    Unsupported(String),
+}
+
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IoError(e) => write!(f, "IO error: {e}"),
+            Self::DecodeError(e) => write!(f, "Decode error: {e}"),
+            Self::Unsupported(e) => write!(f, "Unsupported asset: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// Kind of importable source asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+    /// PNG or JPEG image
+    Texture,
+    /// glTF or GLB model
+    Mesh,
+}
+
+impl ImportKind {
+    /// Detect the kind from a file extension
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "png" | "jpg" | "jpeg" => Some(Self::Texture),
+            "gltf" | "glb" => Some(Self::Mesh),
+            _ => None,
+        }
+    }
+
+    /// Name of the processor, part of the cache key
+    const fn processor(self) -> &'static str {
+        match self {
+            Self::Texture => "texture",
+            Self::Mesh => "mesh",
+        }
+    }
+}
+
+/// Result of importing one source file
+#[derive(Debug, Clone)]
+pub struct ImportedAsset {
+    /// Source file
+    pub source: PathBuf,
+    /// Asset kind
+    pub kind: ImportKind,
+    /// Cache key of the processed data
+    pub key: CacheKey,
+    /// Whether the processed data was already cached
+    pub cached: bool,
+}
+
+/// Texture with a pre-generated, block-compressed mip chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessedTexture {
+    /// Width of mip 0
+    pub width: u32,
+    /// Height of mip 0
+    pub height: u32,
+    /// Pixel format of every mip
+    pub format: CompressedFormat,
+    /// Pixel data for each mip, largest first
+    pub mips: Vec<Vec<u8>>,
+}
+
+impl ProcessedTexture {
+    /// Decode an image, generate its mip chain, and compress it
+    ///
+    /// Opaque images become BC1 and images with any transparency BC3.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a supported image
+    pub fn from_image_bytes(bytes: &[u8]) -> Result<Self, ImportError> {
+        let image = image::load_from_memory(bytes)
+            .map_err(|e| ImportError::DecodeError(e.to_string()))?
+            .to_rgba8();
+        let (width, height) = image.dimensions();
+        let opaque = image.pixels().all(|p| p[3] == 255);
+        let format = if opaque {
+            CompressedFormat::Bc1
+        } else {
+            CompressedFormat::Bc3
+        };
+
+        let mut mips = vec![block_compress::compress(
+            image.as_raw(),
+            width,
+            height,
+            format,
+        )];
+        let (mut w, mut h) = (width, height);
+        while w > 1 || h > 1 {
+            w = (w / 2).max(1);
+            h = (h / 2).max(1);
+            let mip = image::imageops::resize(&image, w, h, image::imageops::FilterType::Triangle);
+            mips.push(block_compress::compress(mip.as_raw(), w, h, format));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            format,
+            mips,
+        })
+    }
+
+    /// Serialize to the engine texture format
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(TEXTURE_MAGIC);
+        write_u32(&mut out, FORMAT_VERSION);
+        write_u32(&mut out, self.width);
+        write_u32(&mut out, self.height);
+        write_u32(&mut out, self.format.tag());
+        write_u32(&mut out, self.mips.len() as u32);
+        for mip in &self.mips {
+            write_u32(&mut out, mip.len() as u32);
+            out.extend_from_slice(mip);
+        }
+        out
+    }
+
+    /// Deserialize from the engine texture format
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is truncated or not a processed texture
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ImportError> {
+        let mut reader = Reader::new(bytes, TEXTURE_MAGIC)?;
+        let width = reader.u32()?;
+        let height = reader.u32()?;
+        let format = CompressedFormat::from_tag(reader.u32()?)
+            .ok_or_else(|| ImportError::DecodeError("unknown texture format".to_string()))?;
+        let mip_count = reader.count(4)?;
+        let mut mips = Vec::with_capacity(mip_count);
+        for _ in 0..mip_count {
+            let len = reader.u32()? as usize;
+            mips.push(reader.bytes(len)?.to_vec());
+        }
+        Ok(Self {
+            width,
+            height,
+            format,
+            mips,
+        })
+    }
+}
+
+/// Serialize meshes to the engine mesh format
+#[must_use]
+pub fn meshes_to_bytes(meshes: &[LoadedMesh]) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(MESH_MAGIC);
+    write_u32(&mut out, FORMAT_VERSION);
+    write_u32(&mut out, meshes.len() as u32);
+    for mesh in meshes {
+        write_u32(&mut out, mesh.name.len() as u32);
+        out.extend_from_slice(mesh.name.as_bytes());
+        write_u32(&mut out, mesh.primitives.len() as u32);
+        for primitive in &mesh.primitives {
+            write_u32(&mut out, primitive.vertices.len() as u32);
+            write_u32(&mut out, primitive.indices.len() as u32);
+            let material = primitive.material_index.map_or(NO_MATERIAL, |m| m as u32);
+            write_u32(&mut out, material);
+            out.extend_from_slice(bytemuck::cast_slice(&primitive.vertices));
+            out.extend_from_slice(bytemuck::cast_slice(&primitive.indices));
+            write_u32(&mut out, primitive.morph_targets.len() as u32);
+            for target in &primitive.morph_targets {
+                write_u32(&mut out, target.positions.len() as u32);
+                out.extend_from_slice(bytemuck::cast_slice(&target.positions));
+                write_u32(&mut out, target.normals.len() as u32);
+                out.extend_from_slice(bytemuck::cast_slice(&target.normals));
+            }
+        }
+        write_u32(&mut out, mesh.weights.len() as u32);
+        out.extend_from_slice(bytemuck::cast_slice(&mesh.weights));
+    }
+    out
+}
+
+/// Deserialize meshes from the engine mesh format
+///
+/// # Errors
+///
+/// Returns an error if the data is truncated or not a processed mesh
+pub fn meshes_from_bytes(bytes: &[u8]) -> Result<Vec<LoadedMesh>, ImportError> {
+    let mut reader = Reader::new(bytes, MESH_MAGIC)?;
+    // Smallest encodings: a mesh is three counts, a primitive four
+    let mesh_count = reader.count(12)?;
+    let mut meshes = Vec::with_capacity(mesh_count);
+    for _ in 0..mesh_count {
+        let name_len = reader.u32()? as usize;
+        let name = String::from_utf8(reader.bytes(name_len)?.to_vec())
+            .map_err(|e| ImportError::DecodeError(e.to_string()))?;
+        let primitive_count = reader.count(16)?;
+        let mut primitives = Vec::with_capacity(primitive_count);
+        for _ in 0..primitive_count {
+            let vertex_count = reader.u32()? as usize;
+            let index_count = reader.u32()? as usize;
+            let material = reader.u32()?;
+            let vertex_bytes =
+                reader.bytes(vertex_count.saturating_mul(std::mem::size_of::<Vertex>()))?;
+            let index_bytes = reader.bytes(index_count.saturating_mul(4))?;
+            let target_count = reader.count(8)?;
+            let mut morph_targets = Vec::with_capacity(target_count);
+            for _ in 0..target_count {
+                let count = reader.u32()? as usize;
+                let positions = reader.pod_vec(count)?;
+                let count = reader.u32()? as usize;
+                let normals = reader.pod_vec(count)?;
+                morph_targets.push(MorphTarget { positions, normals });
+            }
+            primitives.push(LoadedPrimitive {
+                vertices: vertex_bytes
+                    .chunks_exact(std::mem::size_of::<Vertex>())
+                    .map(bytemuck::pod_read_unaligned)
+                    .collect(),
+                indices: index_bytes
+                    .chunks_exact(4)
+                    .map(bytemuck::pod_read_unaligned)
+                    .collect(),
+                material_index: (material != NO_MATERIAL).then_some(material as usize),
+                morph_targets,
+            });
+        }
+        let weight_count = reader.u32()? as usize;
+        let weights = reader.pod_vec(weight_count)?;
+        meshes.push(LoadedMesh {
+            name,
+            primitives,
+            weights,
+        });
+    }
+    Ok(meshes)
+}
+
+/// Append a little-endian u32
+fn write_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Cursor over a processed asset file
+struct Reader<'a> {
+    /// Remaining bytes
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Check the magic and version and start reading after them
+    fn new(data: &'a [u8], magic: &[u8; 4]) -> Result<Self, ImportError> {
+        let mut reader = Self { data };
+        if reader.bytes(4)? != magic {
+            return Err(ImportError::DecodeError("bad magic".to_string()));
+        }
+        let version = reader.u32()?;
+        if version != FORMAT_VERSION {
+            return Err(ImportError::DecodeError(format!(
+                "unsupported version {version}"
+            )));
+        }
+        Ok(reader)
+    }
+
+    /// Read a fixed number of bytes
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], ImportError> {
+        if self.data.len() < len {
+            return Err(ImportError::DecodeError(
+                "unexpected end of data".to_string(),
+            ));
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    /// Read a little-endian u32
+    fn u32(&mut self) -> Result<u32, ImportError> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Read an element count, rejecting counts the remaining data can't hold
+    ///
+    /// `min_size` is the smallest encoding of one element, so corrupt files
+    /// fail here instead of reserving gigabytes.
+    fn count(&mut self, min_size: usize) -> Result<usize, ImportError> {
+        let count = self.u32()? as usize;
+        if count.saturating_mul(min_size) > self.data.len() {
+            return Err(ImportError::DecodeError(format!(
+                "count {count} exceeds remaining data"
+            )));
+        }
+        Ok(count)
+    }
+
+    /// Read `count` plain-data values
+    fn pod_vec<T: bytemuck::Pod>(&mut self, count: usize) -> Result<Vec<T>, ImportError> {
+        let size = std::mem::size_of::<T>();
+        Ok(self
+            .bytes(count.saturating_mul(size))?
+            .chunks_exact(size)
+            .map(bytemuck::pod_read_unaligned)
+            .collect())
+    }
+}
+
+/// Imports source assets into a content-addressed cache
+#[derive(Debug)]
+pub struct ImportPipeline {
+    /// Derived-data cache holding processed assets
+    cache: DerivedDataCache,
+}
+
+impl ImportPipeline {
+    /// Create a pipeline writing to a cache directory
+    #[must_use]
+    pub fn new(cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            cache: DerivedDataCache::new(cache_dir),
+        }
+    }
+
+    /// Get the underlying cache
+    #[must_use]
+    pub fn cache(&self) -> &DerivedDataCache {
+        &self.cache
+    }
+
+    /// Compute the cache key for a source file's contents
+    ///
+    /// Covers only `source` itself; `source_key` also hashes the files a
+    /// glTF references.
+    #[must_use]
+    pub fn key(kind: ImportKind, source: &[u8]) -> CacheKey {
+        CacheKey::new(kind.processor(), FORMAT_VERSION, "", source)
+    }
+
+    /// Compute the cache key for a source file and everything it references
+    ///
+    /// For glTF files the external buffers and images named by URI are
+    /// hashed too, so editing a `.bin` or texture invalidates the import.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source or a referenced file cannot be read
+    pub fn source_key(kind: ImportKind, path: &Path) -> Result<CacheKey, ImportError> {
+        let source = std::fs::read(path).map_err(|e| ImportError::IoError(e.to_string()))?;
+        Self::key_with_dependencies(kind, path, &source)
+    }
+
+    /// Hash `source` followed by each external dependency's URI and contents
+    fn key_with_dependencies(
+        kind: ImportKind,
+        path: &Path,
+        source: &[u8],
+    ) -> Result<CacheKey, ImportError> {
+        let uris = match kind {
+            ImportKind::Mesh => gltf_external_uris(source),
+            ImportKind::Texture => Vec::new(),
+        };
+        if uris.is_empty() {
+            return Ok(Self::key(kind, source));
+        }
+
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        let mut combined = source.to_vec();
+        for uri in uris {
+            let bytes = std::fs::read(base.join(percent_decode(&uri)))
+                .map_err(|e| ImportError::IoError(format!("{uri}: {e}")))?;
+            for part in [uri.as_bytes(), &bytes] {
+                combined.extend_from_slice(&(part.len() as u64).to_le_bytes());
+                combined.extend_from_slice(part);
+            }
+        }
+        Ok(Self::key(kind, &combined))
+    }
+
+    /// Import a single file, skipping the work if its content is cached
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file type is unsupported, cannot be read,
+    /// or fails to process
+    pub fn import_file(&self, path: impl AsRef<Path>) -> Result<ImportedAsset, ImportError> {
+        let path = path.as_ref();
+        let kind = ImportKind::from_path(path)
+            .ok_or_else(|| ImportError::Unsupported(path.display().to_string()))?;
+        let source = std::fs::read(path).map_err(|e| ImportError::IoError(e.to_string()))?;
+        let key = Self::key_with_dependencies(kind, path, &source)?;
+
+        let cached = self.cache.contains(key);
+        if !cached {
+            let processed = match kind {
+                ImportKind::Texture => ProcessedTexture::from_image_bytes(&source)?.to_bytes(),
+                ImportKind::Mesh => {
+                    let gltf =
+                        load_gltf(path).map_err(|e| ImportError::DecodeError(e.to_string()))?;
+                    meshes_to_bytes(&gltf.meshes)
+                }
+            };
+            self.cache
+                .put(key, &processed)
+                .map_err(|e| ImportError::IoError(e.to_string()))?;
+        }
+
+        Ok(ImportedAsset {
+            source: path.to_path_buf(),
+            kind,
+            key,
+            cached,
+        })
+    }
+
+    /// Import every supported file under a directory
+    ///
+    /// Unsupported files are skipped. Per-file failures are returned
+    /// alongside successes so one bad asset does not stop the import.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read
+    pub fn import_dir(
+        &self,
+        root: impl AsRef<Path>,
+    ) -> Result<Vec<Result<ImportedAsset, ImportError>>, ImportError> {
+        let mut results = Vec::new();
+        let mut stack = vec![root.as_ref().to_path_buf()];
+        while let Some(dir) = stack.pop() {
+            let entries =
+                std::fs::read_dir(&dir).map_err(|e| ImportError::IoError(e.to_string()))?;
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    if !path.ends_with(DEFAULT_CACHE_DIR) {
+                        stack.push(path);
+                    }
+                } else if ImportKind::from_path(&path).is_some() {
+                    results.push(self.import_file(&path));
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    /// Load a processed texture for a source file, if it is cached
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source cannot be read or the cached data is invalid
+    pub fn load_texture(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> Result<Option<ProcessedTexture>, ImportError> {
+        let key = Self::source_key(ImportKind::Texture, path.as_ref())?;
+        self.cache
+            .get(key)
+            .map(|bytes| ProcessedTexture::from_bytes(&bytes))
+            .transpose()
+    }
+
+    /// Load processed meshes for a source file, if they are cached
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source cannot be read or the cached data is invalid
+    pub fn load_meshes(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> Result<Option<Vec<LoadedMesh>>, ImportError> {
+        let key = Self::source_key(ImportKind::Mesh, path.as_ref())?;
+        self.cache
+            .get(key)
+            .map(|bytes| meshes_from_bytes(&bytes))
+            .transpose()
+    }
+}
+
+impl Default for ImportPipeline {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_DIR)
+    }
+}
+
+/// URIs of external buffers and images in a glTF or GLB file
+///
+/// Embedded `data:` URIs are skipped; they are already part of the source.
+fn gltf_external_uris(source: &[u8]) -> Vec<String> {
+    // GLB: 12-byte header, then a JSON chunk (length, type, data)
+    let json = if source.starts_with(b"glTF") {
+        let Some(header) = source.get(12..20) else {
+            return Vec::new();
+        };
+        let len = u32::from_le_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let Some(chunk) = source.get(20..20usize.saturating_add(len)) else {
+            return Vec::new();
+        };
+        chunk
+    } else {
+        source
+    };
+    let Ok(document) = serde_json::from_slice::<serde_json::Value>(json) else {
+        return Vec::new();
+    };
+
+    ["buffers", "images"]
+        .iter()
+        .filter_map(|key| document.get(key)?.as_array())
+        .flatten()
+        .filter_map(|entry| entry.get("uri")?.as_str())
+        .filter(|uri| !uri.starts_with("data:"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Decode `%XX` escapes in a relative URI
+fn percent_decode(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(hex) = uri.get(i + 1..i + 3)
+            && let Ok(value) = u8::from_str_radix(hex, 16)
+        {
+            out.push(value);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_texture_round_trip() {
+        let texture = ProcessedTexture {
+            width: 2,
+            height: 1,
+            format: CompressedFormat::Bc1,
+            mips: vec![vec![255; 8], vec![128; 8]],
+        };
+
+        let decoded = ProcessedTexture::from_bytes(&texture.to_bytes()).unwrap();
+        assert_eq!(decoded, texture);
+        assert!(ProcessedTexture::from_bytes(b"EMSH").is_err());
+    }
+
+    #[test]
+    fn test_mesh_round_trip() {
+        let meshes = vec![LoadedMesh {
+            name: "tri".to_string(),
+            primitives: vec![LoadedPrimitive {
+                vertices: vec![Vertex::new([0.0, 1.0, 2.0], [0.0, 1.0, 0.0], [0.5, 0.5]); 3],
+                indices: vec![0, 1, 2],
+                material_index: Some(1),
+                morph_targets: vec![MorphTarget {
+                    positions: vec![[0.0, 0.5, 0.0]; 3],
+                    normals: Vec::new(),
+                }],
+            }],
+            weights: vec![0.25],
+        }];
+
+        let decoded = meshes_from_bytes(&meshes_to_bytes(&meshes)).unwrap();
+        assert_eq!(decoded[0].name, "tri");
+        assert_eq!(decoded[0].primitives[0].indices, vec![0, 1, 2]);
+        assert_eq!(
+            decoded[0].primitives[0].vertices[2].position,
+            [0.0, 1.0, 2.0]
+        );
+        assert_eq!(decoded[0].primitives[0].material_index, Some(1));
+        assert_eq!(
+            decoded[0].primitives[0].morph_targets,
+            meshes[0].primitives[0].morph_targets
+        );
+        assert_eq!(decoded[0].weights, vec![0.25]);
+    }
+
+    #[test]
+    fn test_key_tracks_external_buffers() {
+        let dir = std::env::temp_dir().join(format!("engine_import_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let gltf = dir.join("tri.gltf");
+        std::fs::write(
+            &gltf,
+            br#"{"buffers":[{"uri":"tri%20data.bin","byteLength":4},{"uri":"data:,x"}],"images":[{"uri":"albedo.png"}]}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("tri data.bin"), [0u8; 4]).unwrap();
+        std::fs::write(dir.join("albedo.png"), b"png").unwrap();
+
+        let before = ImportPipeline::source_key(ImportKind::Mesh, &gltf).unwrap();
+        std::fs::write(dir.join("tri data.bin"), [1u8; 4]).unwrap();
+        let after = ImportPipeline::source_key(ImportKind::Mesh, &gltf).unwrap();
+        assert_ne!(before, after);
+
+        std::fs::remove_file(dir.join("albedo.png")).unwrap();
+        assert!(ImportPipeline::source_key(ImportKind::Mesh, &gltf).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_corrupt_counts_are_rejected() {
+        let mut bytes = MESH_MAGIC.to_vec();
+        write_u32(&mut bytes, FORMAT_VERSION);
+        write_u32(&mut bytes, u32::MAX);
+        assert!(meshes_from_bytes(&bytes).is_err());
+
+        let mut bytes = TEXTURE_MAGIC.to_vec();
+        for value in [FORMAT_VERSION, 4, 4, CompressedFormat::Bc1.tag(), u32::MAX] {
+            write_u32(&mut bytes, value);
+        }
+        assert!(ProcessedTexture::from_bytes(&bytes).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+//! Background loading state and progress tracking
+//!
+//! Types used by `AssetServer` to report how far along asset loading is.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+
+/// Global counter for generating unique load IDs
+static NEXT_LOAD_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Most threads used for background loads
+const MAX_LOAD_WORKERS: usize = 4;
+
+/// Work run on a loader thread
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Identifier for a tracked load request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LoadId(u64);
+
+impl LoadId {
+    /// Generate a new unique load ID
+    pub(crate) fn next() -> Self {
+        Self(NEXT_LOAD_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Get the raw ID value
+    #[must_use]
+    pub const fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Loading state of a tracked asset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadState {
+    /// Load is in progress
+    Loading,
+    /// Asset is loaded and available
+    Loaded,
+    /// Load failed with an error message
+    Failed(String),
+}
+
+impl LoadState {
+    /// Check if loading has finished (successfully or not)
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        !matches!(self, Self::Loading)
+    }
+}
+
+/// Aggregate progress over a set of loads
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadProgress {
+    /// Successfully loaded
+    pub loaded: usize,
+    /// Failed to load
+    pub failed: usize,
+    /// Total tracked loads
+    pub total: usize,
+}
+
+impl LoadProgress {
+    /// Get the finished fraction (0.0 to 1.0)
+    ///
+    /// Failed loads count as finished. Returns 1.0 if nothing is tracked.
+    #[must_use]
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            (self.loaded + self.failed) as f32 / self.total as f32
+        }
+    }
+
+    /// Check if every load has finished
+    #[must_use]
+    pub const fn is_done(&self) -> bool {
+        self.loaded + self.failed >= self.total
+    }
+
+    /// Record a single load state
+    pub(crate) fn record(&mut self, state: Option<&LoadState>) {
+        self.total += 1;
+        match state {
+            Some(LoadState::Loaded) => self.loaded += 1,
+            Some(LoadState::Failed(_)) => self.failed += 1,
+            Some(LoadState::Loading) | None => {}
+        }
+    }
+}
+
+/// A group of loads tracked together (e.g. everything a level needs)
+#[derive(Debug, Clone, Default)]
+pub struct LoadBatch {
+    /// Loads in this batch
+    ids: Vec<LoadId>,
+}
+
+impl LoadBatch {
+    /// Create an empty batch
+    #[must_use]
+    pub fn new() -> Self {
+        Self { ids: Vec::new() }
+    }
+
+    /// Add a load to the batch
+    pub fn add(&mut self, id: LoadId) {
+        if !self.ids.contains(&id) {
+            self.ids.push(id);
+        }
+    }
+
+    /// Get the loads in this batch
+    #[must_use]
+    pub fn ids(&self) -> &[LoadId] {
+        &self.ids
+    }
+
+    /// Get the number of loads in this batch
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Check if the batch is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+/// Small fixed pool of threads running background loads
+///
+/// Jobs run in submission order on whichever thread is free. The threads
+/// exit once the pool is dropped and the queue has drained.
+pub(crate) struct LoadWorkers {
+    /// Queue feeding the worker threads
+    sender: Sender<Job>,
+}
+
+impl LoadWorkers {
+    /// Start one worker per core, up to `MAX_LOAD_WORKERS`
+    pub(crate) fn new() -> Self {
+        let count = std::thread::available_parallelism()
+            .map_or(1, std::num::NonZero::get)
+            .min(MAX_LOAD_WORKERS);
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for _ in 0..count {
+            let receiver = Arc::clone(&receiver);
+            std::thread::spawn(move || {
+                loop {
+                    let job = {
+                        let Ok(receiver) = receiver.lock() else {
+                            return;
+                        };
+                        receiver.recv()
+                    };
+                    let Ok(job) = job else {
+                        return;
+                    };
+                    job();
+                }
+            });
+        }
+        Self { sender }
+    }
+
+    /// Queue a job for the next free worker
+    pub(crate) fn spawn(&self, job: impl FnOnce() + Send + 'static) {
+        let _ = self.sender.send(Box::new(job));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workers_run_every_job() {
+        let workers = LoadWorkers::new();
+        let (sender, receiver) = mpsc::channel();
+        for i in 0..16 {
+            let sender = sender.clone();
+            workers.spawn(move || {
+                let _ = sender.send(i);
+            });
+        }
+        drop(sender);
+
+        let mut done: Vec<i32> = receiver.iter().collect();
+        done.sort_unstable();
+        assert_eq!(done, (0..16).collect::<Vec<_>>());
+    }
+}
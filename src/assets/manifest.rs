@@ -0,0 +1,297 @@
+//! Stable asset IDs and manifest
+//!
+//! Assigns each asset file a UUID that survives moves and renames, and
+//! keeps a manifest mapping IDs to their current paths.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+use super::cache::CacheKey;
+use crate::core::SceneError;
+
+/// Counter mixed into generated UUIDs so IDs made in the same instant differ
+static UUID_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Stable identifier for an asset, independent of its path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct AssetUuid(u128);
+
+impl AssetUuid {
+    /// Generate a new random UUID (version 4 layout)
+    #[must_use]
+    pub fn generate() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        let counter = UUID_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let seed = nanos ^ u64::from(std::process::id()).rotate_left(32) ^ counter;
+
+        let high = splitmix64(seed);
+        let low = splitmix64(high ^ counter.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        let mut value = (u128::from(high) << 64) | u128::from(low);
+        // Set version 4 and the RFC 4122 variant bits
+        value = (value & !(0xF << 76)) | (0x4 << 76);
+        value = (value & !(0x3 << 62)) | (0x2 << 62);
+        Self(value)
+    }
+
+    /// Create from a raw value
+    #[must_use]
+    pub const fn from_u128(value: u128) -> Self {
+        Self(value)
+    }
+
+    /// Get the raw value
+    #[must_use]
+    pub const fn as_u128(&self) -> u128 {
+        self.0
+    }
+}
+
+/// SplitMix64 step, used to spread generator seeds
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+impl std::fmt::Display for AssetUuid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let v = self.0;
+        write!(
+            f,
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            (v >> 96) as u32,
+            (v >> 80) as u16,
+            (v >> 64) as u16,
+            (v >> 48) as u16,
+            v & 0xFFFF_FFFF_FFFF
+        )
+    }
+}
+
+impl std::str::FromStr for AssetUuid {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex: String = s.chars().filter(|&c| c != '-').collect();
+        if hex.len() != 32 {
+            return Err(format!("invalid asset UUID: {s}"));
+        }
+        u128::from_str_radix(&hex, 16)
+            .map(Self)
+            .map_err(|_| format!("invalid asset UUID: {s}"))
+    }
+}
+
+impl From<AssetUuid> for String {
+    fn from(uuid: AssetUuid) -> Self {
+        uuid.to_string()
+    }
+}
+
+impl TryFrom<String> for AssetUuid {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Component holding an entity's asset references by slot (e.g. `"mesh"`)
+///
+/// Spawned from serialized scenes and prefabs; resolve with
+/// `AssetServer::resolve`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetRefs(pub HashMap<String, AssetUuid>);
+
+impl AssetRefs {
+    /// Get the asset in a slot
+    #[must_use]
+    pub fn get(&self, slot: &str) -> Option<AssetUuid> {
+        self.0.get(slot).copied()
+    }
+}
+
+/// Manifest record for one asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Current path, relative to the asset root, with `/` separators
+    pub path: String,
+    /// Hash of the file contents when last scanned
+    pub content_hash: u64,
+}
+
+/// Mapping of stable asset IDs to paths
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AssetManifest {
+    /// Entries by ID
+    entries: HashMap<AssetUuid, ManifestEntry>,
+}
+
+impl AssetManifest {
+    /// Create an empty manifest
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Hash file contents for move detection
+    #[must_use]
+    pub fn content_hash(bytes: &[u8]) -> u64 {
+        CacheKey::new("content", 1, "", bytes).value()
+    }
+
+    /// Register a file, returning its stable ID
+    ///
+    /// A known path keeps its ID. An unknown path whose contents match an
+    /// entry that is missing from `existing` is treated as a move and keeps
+    /// that entry's ID. Otherwise a new ID is generated.
+    pub fn register(
+        &mut self,
+        path: &str,
+        bytes: &[u8],
+        existing: impl Fn(&str) -> bool,
+    ) -> AssetUuid {
+        let content_hash = Self::content_hash(bytes);
+
+        if let Some(uuid) = self.uuid_for_path(path) {
+            if let Some(entry) = self.entries.get_mut(&uuid) {
+                entry.content_hash = content_hash;
+            }
+            return uuid;
+        }
+
+        let moved = self
+            .entries
+            .iter()
+            .find(|(_, entry)| entry.content_hash == content_hash && !existing(&entry.path))
+            .map(|(&uuid, _)| uuid);
+        let uuid = moved.unwrap_or_else(AssetUuid::generate);
+        self.entries.insert(
+            uuid,
+            ManifestEntry {
+                path: path.to_string(),
+                content_hash,
+            },
+        );
+        uuid
+    }
+
+    /// Scan a directory, registering every file and pruning missing ones
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read
+    pub fn scan(&mut self, root: impl AsRef<Path>) -> Result<(), SceneError> {
+        let root = root.as_ref();
+        let mut files = Vec::new();
+        let mut stack = vec![root.to_path_buf()];
+        while let Some(dir) = stack.pop() {
+            let entries =
+                std::fs::read_dir(&dir).map_err(|e| SceneError::IoError(e.to_string()))?;
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    stack.push(path);
+                } else if let Ok(relative) = path.strip_prefix(root) {
+                    let relative = relative.to_string_lossy().replace('\\', "/");
+                    files.push((relative, path));
+                }
+            }
+        }
+
+        let exists = |p: &str| root.join(p).is_file();
+        for (relative, path) in &files {
+            if let Ok(bytes) = std::fs::read(path) {
+                self.register(relative, &bytes, exists);
+            }
+        }
+        self.entries.retain(|_, entry| exists(&entry.path));
+        Ok(())
+    }
+
+    /// Get the current path of an asset
+    #[must_use]
+    pub fn path(&self, uuid: AssetUuid) -> Option<&str> {
+        self.entries.get(&uuid).map(|e| e.path.as_str())
+    }
+
+    /// Get the ID of the asset at a path
+    #[must_use]
+    pub fn uuid_for_path(&self, path: &str) -> Option<AssetUuid> {
+        self.entries
+            .iter()
+            .find(|(_, entry)| entry.path == path)
+            .map(|(&uuid, _)| uuid)
+    }
+
+    /// Get the number of entries
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the manifest is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Save the manifest to a RON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written or serialization fails
+    pub fn save_ron(&self, path: impl AsRef<Path>) -> Result<(), SceneError> {
+        let ron_string = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| SceneError::SerializeError(e.to_string()))?;
+        std::fs::write(path, ron_string).map_err(|e| SceneError::IoError(e.to_string()))
+    }
+
+    /// Load a manifest from a RON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or deserialization fails
+    pub fn load_ron(path: impl AsRef<Path>) -> Result<Self, SceneError> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| SceneError::IoError(e.to_string()))?;
+        ron::from_str(&content).map_err(|e| SceneError::DeserializeError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uuid_round_trip() {
+        let uuid = AssetUuid::generate();
+        let text = uuid.to_string();
+
+        assert_eq!(text.len(), 36);
+        assert_eq!(text.parse::<AssetUuid>().unwrap(), uuid);
+        assert_ne!(AssetUuid::generate(), uuid);
+    }
+
+    #[test]
+    fn test_move_keeps_uuid() {
+        let mut manifest = AssetManifest::new();
+        let original = manifest.register("textures/rock.png", b"rock", |_| true);
+
+        // File moved: old path no longer exists, same contents at new path
+        let moved = manifest.register("props/rock.png", b"rock", |p| p != "textures/rock.png");
+
+        assert_eq!(moved, original);
+        assert_eq!(manifest.path(original), Some("props/rock.png"));
+        assert_eq!(manifest.len(), 1);
+    }
+}
@@ -1,14 +1,41 @@
 //! Asset management system
 //!
-//! Provides handle-based asset loading and storage.
+//! Provides handle-based asset loading and storage, glTF import, fonts,
+//! a derived-data cache for processed imports, and editor thumbnails.
 
+mod block_compress;
+mod cache;
+mod events;
+mod font;
 mod gltf;
 mod handle;
+mod import;
+mod loader;
+mod manifest;
 mod storage;
+mod streaming;
+mod thumbnail;
+mod vfs;
 
 pub use self::gltf::{
-    GltfError, GltfResult, LoadedGltf, LoadedMaterial, LoadedMesh, LoadedNode, LoadedPrimitive,
-    load_gltf,
+    CameraProjection, GltfError, GltfResult, LoadedCamera, LoadedGltf, LoadedLight,
+    LoadedLightKind, LoadedMaterial, LoadedMesh, LoadedNode, LoadedPrimitive,
+    LoadedTextureTransform, load_gltf,
 };
+pub use block_compress::CompressedFormat;
+pub use cache::{CacheKey, DerivedDataCache};
+pub use events::AssetEvent;
+pub use font::{Font, FontAtlas, FontError, Glyph, GlyphMode};
 pub use handle::{AssetHandle, WeakAssetHandle};
+pub use import::{
+    DEFAULT_CACHE_DIR, ImportError, ImportKind, ImportPipeline, ImportedAsset, ProcessedTexture,
+    meshes_from_bytes, meshes_to_bytes,
+};
+pub use loader::{LoadBatch, LoadId, LoadProgress, LoadState};
+pub use manifest::{AssetManifest, AssetRefs, AssetUuid, ManifestEntry};
 pub use storage::{AssetServer, Assets};
+pub use streaming::{AssetStreamer, StreamId, StreamKind, StreamLevel, StreamRequest};
+pub use thumbnail::{DEFAULT_THUMBNAIL_SIZE, Thumbnail, ThumbnailCache, ThumbnailError};
+pub use vfs::{
+    DirectorySource, MemorySource, PackSource, Vfs, VfsError, VfsSource, normalize_path,
+};
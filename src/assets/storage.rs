@@ -3,10 +3,32 @@
 //! Provides centralized storage for assets with path-based lookup.
 
 use std::any::{Any, TypeId};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
 
+use glam::Vec3;
+
+use super::events::AssetEvent;
+use super::font::{Font, FontError};
 use super::handle::AssetHandle;
+use super::loader::{LoadBatch, LoadId, LoadProgress, LoadState, LoadWorkers};
+use super::manifest::{AssetManifest, AssetUuid};
+use super::streaming::{AssetStreamer, StreamId, StreamRequest};
+use super::vfs::{Vfs, VfsError, VfsSource};
+use crate::renderer::{GpuMemoryBudget, GpuResource, Renderer};
+
+/// Asset type and path identifying a background load
+type LoadKey = (TypeId, PathBuf);
+
+/// Finished background load waiting to be inserted into storage
+type Completion = (
+    LoadKey,
+    Result<Box<dyn FnOnce(&mut AssetServer) + Send>, String>,
+);
+
+/// Most change events an `Assets<T>` queues before dropping the oldest
+const MAX_PENDING_EVENTS: usize = 1024;
 
 /// Type-erased asset entry
 struct AssetEntry {
@@ -22,6 +44,8 @@ pub struct Assets<T: Send + Sync + 'static> {
     assets: HashMap<u64, AssetEntry>,
     /// Path to handle ID mapping for deduplication
     path_to_id: HashMap<PathBuf, u64>,
+    /// Changes since events were last drained, oldest first
+    events: VecDeque<AssetEvent<T>>,
     /// Phantom data for type safety
     _marker: std::marker::PhantomData<T>,
 }
@@ -33,6 +57,7 @@ impl<T: Send + Sync + 'static> Assets<T> {
         Self {
             assets: HashMap::new(),
             path_to_id: HashMap::new(),
+            events: VecDeque::new(),
             _marker: std::marker::PhantomData,
         }
     }
@@ -50,6 +75,7 @@ impl<T: Send + Sync + 'static> Assets<T> {
                 path: None,
             },
         );
+        self.push_event(AssetEvent::Created(handle.downgrade()));
 
         handle
     }
@@ -77,10 +103,38 @@ impl<T: Send + Sync + 'static> Assets<T> {
                 path: Some(path),
             },
         );
+        self.push_event(AssetEvent::Created(handle.downgrade()));
 
         handle
     }
 
+    /// Replace an existing asset, keeping its ID
+    ///
+    /// Existing handles keep the old data; look the asset up again (e.g. on
+    /// an `AssetEvent::Modified`) to get the new version. Returns `None` if
+    /// no asset has the ID.
+    pub fn replace(&mut self, id: u64, asset: T) -> Option<AssetHandle<T>> {
+        let entry = self.assets.get_mut(&id)?;
+        let handle = AssetHandle::with_id(id, asset);
+        entry.data = Box::new(handle.clone());
+        self.push_event(AssetEvent::Modified(handle.downgrade()));
+        Some(handle)
+    }
+
+    /// Reload the asset at a path, replacing it if already present
+    pub fn reload_path(&mut self, asset: T, path: impl AsRef<Path>) -> AssetHandle<T> {
+        let path = path.as_ref();
+        if let Some(&id) = self.path_to_id.get(path)
+            && let Some(entry) = self.assets.get_mut(&id)
+        {
+            let handle = AssetHandle::with_id(id, asset);
+            entry.data = Box::new(handle.clone());
+            self.push_event(AssetEvent::Modified(handle.downgrade()));
+            return handle;
+        }
+        self.add_with_path(asset, path)
+    }
+
     /// Get an asset by its handle ID
     #[must_use]
     pub fn get(&self, id: u64) -> Option<AssetHandle<T>> {
@@ -107,14 +161,21 @@ impl<T: Send + Sync + 'static> Assets<T> {
     ///
     /// Returns true if the asset was removed
     pub fn remove(&mut self, id: u64) -> bool {
-        if let Some(entry) = self.assets.remove(&id) {
-            if let Some(path) = entry.path {
-                self.path_to_id.remove(&path);
-            }
-            true
-        } else {
-            false
+        self.take(id).is_some()
+    }
+
+    /// Remove an asset by ID, returning the stored handle
+    pub fn take(&mut self, id: u64) -> Option<AssetHandle<T>> {
+        let entry = self.assets.remove(&id)?;
+        if let Some(path) = entry.path {
+            self.path_to_id.remove(&path);
         }
+        self.push_event(AssetEvent::Removed(id));
+        entry
+            .data
+            .downcast::<AssetHandle<T>>()
+            .ok()
+            .map(|handle| *handle)
     }
 
     /// Get the number of stored assets
@@ -131,10 +192,30 @@ impl<T: Send + Sync + 'static> Assets<T> {
 
     /// Clear all assets
     pub fn clear(&mut self) {
+        let ids: Vec<u64> = self.assets.keys().copied().collect();
+        for id in ids {
+            self.push_event(AssetEvent::Removed(id));
+        }
         self.assets.clear();
         self.path_to_id.clear();
     }
 
+    /// Take all events recorded since the last drain (call once per frame)
+    ///
+    /// At most `MAX_PENDING_EVENTS` are kept between drains; older ones are
+    /// dropped, so storage that is never drained doesn't grow unbounded.
+    pub fn drain_events(&mut self) -> Vec<AssetEvent<T>> {
+        self.events.drain(..).collect()
+    }
+
+    /// Record a change event, dropping the oldest if the queue is full
+    fn push_event(&mut self, event: AssetEvent<T>) {
+        if self.events.len() == MAX_PENDING_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
     /// Iterate over all asset handles
     pub fn iter(&self) -> impl Iterator<Item = AssetHandle<T>> + '_ {
         self.assets
@@ -153,6 +234,24 @@ impl<T: Send + Sync + 'static> Default for Assets<T> {
 pub struct AssetServer {
     /// Type-erased storage for each asset type
     storages: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    /// State of every tracked load
+    load_states: HashMap<LoadId, LoadState>,
+    /// Path each tracked load was requested from
+    load_paths: HashMap<LoadId, PathBuf>,
+    /// Loads waiting on each running background load
+    in_flight: HashMap<LoadKey, Vec<LoadId>>,
+    /// Loads started since the server was last idle
+    wave: Vec<LoadId>,
+    /// Threads running background loads, started on first use
+    workers: Option<LoadWorkers>,
+    /// Background loads that finished since the last `update`
+    completed: Arc<Mutex<Vec<Completion>>>,
+    /// Virtual filesystem used to resolve asset paths
+    vfs: Arc<RwLock<Vfs>>,
+    /// Proximity-based streaming of texture mips and mesh LODs
+    streamer: AssetStreamer,
+    /// Stable asset IDs mapped to paths
+    manifest: AssetManifest,
 }
 
 impl AssetServer {
@@ -161,9 +260,42 @@ impl AssetServer {
     pub fn new() -> Self {
         Self {
             storages: HashMap::new(),
+            load_states: HashMap::new(),
+            load_paths: HashMap::new(),
+            in_flight: HashMap::new(),
+            wave: Vec::new(),
+            workers: None,
+            completed: Arc::new(Mutex::new(Vec::new())),
+            vfs: Arc::new(RwLock::new(Vfs::new())),
+            streamer: AssetStreamer::new(),
+            manifest: AssetManifest::new(),
         }
     }
 
+    /// Mount a source into the virtual filesystem
+    pub fn mount(&mut self, prefix: &str, source: impl VfsSource + 'static, priority: i32) {
+        if let Ok(mut vfs) = self.vfs.write() {
+            vfs.mount(prefix, source, priority);
+        }
+    }
+
+    /// Unmount every source under a prefix
+    pub fn unmount(&mut self, prefix: &str) -> usize {
+        self.vfs.write().map_or(0, |mut vfs| vfs.unmount(prefix))
+    }
+
+    /// Read a file through the virtual filesystem
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no mount contains the file or reading fails
+    pub fn read(&self, path: &str) -> Result<Vec<u8>, VfsError> {
+        self.vfs
+            .read()
+            .map_err(|e| VfsError::IoError(e.to_string()))?
+            .read(path)
+    }
+
     /// Get or create storage for a specific asset type
     pub fn get_storage<T: Send + Sync + 'static>(&mut self) -> &mut Assets<T> {
         let type_id = TypeId::of::<T>();
@@ -197,6 +329,271 @@ impl AssetServer {
     ) -> Option<AssetHandle<T>> {
         self.get_storage::<T>().get_by_path(path)
     }
+
+    /// Unload an asset, deferring GPU destruction until in-flight frames finish
+    ///
+    /// Returns true if the asset was removed.
+    pub fn unload<T: Send + Sync + 'static>(&mut self, id: u64, renderer: &Renderer) -> bool {
+        let Some(handle) = self.get_storage::<T>().take(id) else {
+            return false;
+        };
+        renderer.destroy_later(GpuResource::retain(handle));
+        true
+    }
+
+    /// Hot reload the asset at a path, deferring destruction of the old version
+    pub fn reload<T: Send + Sync + 'static>(
+        &mut self,
+        asset: T,
+        path: impl AsRef<Path>,
+        renderer: &Renderer,
+    ) -> AssetHandle<T> {
+        let storage = self.get_storage::<T>();
+        if let Some(old) = storage.get_by_path(&path) {
+            renderer.destroy_later(GpuResource::retain(old));
+        }
+        storage.reload_path(asset, path)
+    }
+
+    /// Take all events for an asset type since the last drain
+    pub fn drain_events<T: Send + Sync + 'static>(&mut self) -> Vec<AssetEvent<T>> {
+        self.get_storage::<T>().drain_events()
+    }
+
+    /// Load a font at a pixel size, reusing it if already loaded
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the font file cannot be read or parsed
+    pub fn load_font(
+        &mut self,
+        path: impl AsRef<Path>,
+        px_size: f32,
+    ) -> Result<AssetHandle<Font>, FontError> {
+        let path = path.as_ref();
+        // The same file rasterized at different sizes is a different asset
+        let key = PathBuf::from(format!("{}#{px_size}", path.display()));
+
+        if let Some(handle) = self.get_by_path::<Font>(&key) {
+            return Ok(handle);
+        }
+
+        let font = Font::from_file(path, px_size)?;
+        Ok(self.add_with_path(font, key))
+    }
+
+    /// Load an asset in the background using the given loader
+    ///
+    /// Loaders run on a small shared pool of threads. The asset becomes
+    /// available after a later call to `update` once loading finishes.
+    /// Paths that are already loaded complete immediately, and a path that
+    /// is still loading is not loaded twice; the new load finishes with it.
+    pub fn load<T, F>(&mut self, path: impl AsRef<Path>, loader: F) -> LoadId
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(&Path) -> Result<T, String> + Send + 'static,
+    {
+        self.prune_finished();
+        let path = path.as_ref().to_path_buf();
+        let id = LoadId::next();
+        self.load_paths.insert(id, path.clone());
+        self.wave.push(id);
+
+        if self.get_storage::<T>().contains_path(&path) {
+            self.load_states.insert(id, LoadState::Loaded);
+            return id;
+        }
+
+        self.load_states.insert(id, LoadState::Loading);
+        let key = (TypeId::of::<T>(), path.clone());
+        if let Some(waiting) = self.in_flight.get_mut(&key) {
+            waiting.push(id);
+            return id;
+        }
+        self.in_flight.insert(key.clone(), vec![id]);
+
+        let completed = Arc::clone(&self.completed);
+        self.workers
+            .get_or_insert_with(LoadWorkers::new)
+            .spawn(move || {
+                let result =
+                    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| loader(&path)))
+                        .unwrap_or_else(|_| Err("loader panicked".to_string()))
+                        .map(|asset| {
+                            Box::new(move |server: &mut AssetServer| {
+                                server.add_with_path(asset, path);
+                            })
+                                as Box<dyn FnOnce(&mut AssetServer) + Send>
+                        });
+                if let Ok(mut queue) = completed.lock() {
+                    queue.push((key, result));
+                }
+            });
+
+        id
+    }
+
+    /// Forget the previous wave of loads once every one of them has finished
+    ///
+    /// Failed loads are kept so batches can still report them.
+    fn prune_finished(&mut self) {
+        let idle = self
+            .wave
+            .iter()
+            .all(|id| self.load_states.get(id).is_none_or(LoadState::is_finished));
+        if !idle {
+            return;
+        }
+        for id in self.wave.drain(..) {
+            if self.load_states.get(&id) == Some(&LoadState::Loaded) {
+                self.load_states.remove(&id);
+                self.load_paths.remove(&id);
+            }
+        }
+    }
+
+    /// Load an asset from the virtual filesystem on a background thread
+    ///
+    /// `parse` converts the file bytes into the asset.
+    pub fn load_from_vfs<T, F>(&mut self, path: &str, parse: F) -> LoadId
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(&[u8]) -> Result<T, String> + Send + 'static,
+    {
+        let vfs = Arc::clone(&self.vfs);
+        let virtual_path = path.to_string();
+        self.load(path, move |_| {
+            let bytes = vfs
+                .read()
+                .map_err(|e| e.to_string())?
+                .read(&virtual_path)
+                .map_err(|e| e.to_string())?;
+            parse(&bytes)
+        })
+    }
+
+    /// Insert finished background loads into storage (call once per frame)
+    pub fn update(&mut self) {
+        let finished = match self.completed.lock() {
+            Ok(mut queue) => std::mem::take(&mut *queue),
+            Err(_) => return,
+        };
+
+        for (key, result) in finished {
+            let ids = self.in_flight.remove(&key).unwrap_or_default();
+            let state = match result {
+                Ok(insert) => {
+                    insert(self);
+                    LoadState::Loaded
+                }
+                Err(error) => {
+                    log::warn!("Failed to load asset {:?}: {error}", key.1);
+                    LoadState::Failed(error)
+                }
+            };
+            for id in ids {
+                self.load_states.insert(id, state.clone());
+            }
+        }
+    }
+
+    /// Get the state of a tracked load
+    ///
+    /// Successful loads are forgotten once a new load starts after all of
+    /// them have finished; fetch their assets with `get_loaded` before then.
+    #[must_use]
+    pub fn load_state(&self, id: LoadId) -> Option<&LoadState> {
+        self.load_states.get(&id)
+    }
+
+    /// Get the asset produced by a finished load
+    #[must_use]
+    pub fn get_loaded<T: Send + Sync + 'static>(&mut self, id: LoadId) -> Option<AssetHandle<T>> {
+        let path = self.load_paths.get(&id)?.clone();
+        self.get_by_path(path)
+    }
+
+    /// Get aggregate progress over loads started since the server was last idle
+    ///
+    /// The first load started after every earlier load has finished begins
+    /// a new count, so each loading screen starts from zero.
+    #[must_use]
+    pub fn progress(&self) -> LoadProgress {
+        let mut progress = LoadProgress::default();
+        for id in &self.wave {
+            progress.record(self.load_states.get(id));
+        }
+        progress
+    }
+
+    /// Set the manifest used to resolve stable asset IDs
+    pub fn set_manifest(&mut self, manifest: AssetManifest) {
+        self.manifest = manifest;
+    }
+
+    /// Get the asset manifest
+    #[must_use]
+    pub fn manifest(&self) -> &AssetManifest {
+        &self.manifest
+    }
+
+    /// Get the handle of a loaded asset by its stable ID
+    #[must_use]
+    pub fn resolve<T: Send + Sync + 'static>(&mut self, uuid: AssetUuid) -> Option<AssetHandle<T>> {
+        let path = self.manifest.path(uuid)?.to_string();
+        self.get_by_path(path)
+    }
+
+    /// Load an asset by its stable ID on a background thread
+    ///
+    /// Returns `None` if the ID is not in the manifest.
+    pub fn load_by_uuid<T, F>(&mut self, uuid: AssetUuid, loader: F) -> Option<LoadId>
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(&Path) -> Result<T, String> + Send + 'static,
+    {
+        let path = self.manifest.path(uuid)?.to_string();
+        Some(self.load(path, loader))
+    }
+
+    /// Get the asset streamer
+    #[must_use]
+    pub fn streamer(&self) -> &AssetStreamer {
+        &self.streamer
+    }
+
+    /// Get the mutable asset streamer
+    pub fn streamer_mut(&mut self) -> &mut AssetStreamer {
+        &mut self.streamer
+    }
+
+    /// Set a streamed asset's priority multiplier (higher loads sooner)
+    pub fn set_stream_priority(&mut self, id: StreamId, priority: f32) {
+        self.streamer.set_priority(id, priority);
+    }
+
+    /// Compute streaming loads for the camera position against a GPU budget
+    ///
+    /// Typically called once per frame with `Renderer::memory_budget_mut`.
+    pub fn update_streaming(
+        &mut self,
+        camera: Vec3,
+        budget: &mut GpuMemoryBudget,
+    ) -> Vec<StreamRequest> {
+        self.streamer.update(camera, budget)
+    }
+
+    /// Get progress for a batch of loads
+    ///
+    /// Successful loads the server has already forgotten count as loaded.
+    #[must_use]
+    pub fn batch_progress(&self, batch: &LoadBatch) -> LoadProgress {
+        let mut progress = LoadProgress::default();
+        for id in batch.ids() {
+            progress.record(Some(self.load_states.get(id).unwrap_or(&LoadState::Loaded)));
+        }
+        progress
+    }
 }
 
 impl Default for AssetServer {
@@ -230,6 +627,151 @@ mod tests {
         assert_eq!(*handle1.get(), 42);
     }
 
+    #[test]
+    fn test_background_load_progress() {
+        let mut server = AssetServer::new();
+        let mut batch = LoadBatch::new();
+        batch.add(server.load("data/answer.txt", |_| Ok(42_u32)));
+        batch.add(server.load::<u32, _>("data/missing.txt", |_| Err("not found".into())));
+
+        for _ in 0..200 {
+            server.update();
+            if server.batch_progress(&batch).is_done() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        let progress = server.batch_progress(&batch);
+        assert_eq!(progress.loaded, 1);
+        assert_eq!(progress.failed, 1);
+        assert!((progress.fraction() - 1.0).abs() < 0.001);
+
+        let handle = server.get_loaded::<u32>(batch.ids()[0]).unwrap();
+        assert_eq!(*handle.get(), 42);
+    }
+
+    /// Run `update` until `done` holds or about a second has passed
+    fn update_until(server: &mut AssetServer, done: impl Fn(&AssetServer) -> bool) {
+        for _ in 0..200 {
+            server.update();
+            if done(server) {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_in_flight_path_loads_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let runs = Arc::new(AtomicUsize::new(0));
+        let (release, gate) = std::sync::mpsc::channel::<()>();
+        let mut server = AssetServer::new();
+        let first = {
+            let runs = Arc::clone(&runs);
+            server.load("data/shared.txt", move |_| {
+                runs.fetch_add(1, Ordering::SeqCst);
+                let _ = gate.recv();
+                Ok(7_u32)
+            })
+        };
+        let second = {
+            let runs = Arc::clone(&runs);
+            server.load("data/shared.txt", move |_| {
+                runs.fetch_add(1, Ordering::SeqCst);
+                Ok(8_u32)
+            })
+        };
+        assert_eq!(server.load_state(second), Some(&LoadState::Loading));
+
+        let _ = release.send(());
+        update_until(&mut server, |server| server.progress().is_done());
+
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert_eq!(server.load_state(first), Some(&LoadState::Loaded));
+        assert_eq!(server.load_state(second), Some(&LoadState::Loaded));
+        assert_eq!(*server.get_loaded::<u32>(second).unwrap().get(), 7);
+        assert_eq!(server.get_storage::<u32>().len(), 1);
+    }
+
+    #[test]
+    fn test_progress_restarts_after_idle() {
+        let mut server = AssetServer::new();
+        let first = server.load("data/a.txt", |_| Ok(1_u32));
+        server.load::<u32, _>("data/b.txt", |_| Err("not found".into()));
+        update_until(&mut server, |server| server.progress().is_done());
+        assert_eq!(server.progress().total, 2);
+
+        let mut batch = LoadBatch::new();
+        batch.add(first);
+        let (release, gate) = std::sync::mpsc::channel::<()>();
+        batch.add(server.load("data/c.txt", move |_| {
+            let _ = gate.recv();
+            Ok(3_u32)
+        }));
+
+        // The finished wave is forgotten, so the new screen starts from zero
+        let progress = server.progress();
+        assert_eq!((progress.total, progress.loaded), (1, 0));
+        assert_eq!(server.load_state(first), None);
+        assert_eq!(server.batch_progress(&batch).loaded, 1);
+
+        let _ = release.send(());
+        update_until(&mut server, |server| server.progress().is_done());
+        assert_eq!(server.progress().fraction(), 1.0);
+        assert_eq!(server.batch_progress(&batch).loaded, 2);
+    }
+
+    #[test]
+    fn test_read_through_mounts() {
+        use crate::assets::MemorySource;
+
+        let mut server = AssetServer::new();
+        server.mount(
+            "base",
+            MemorySource::new().with_file("a.txt", b"base".to_vec()),
+            0,
+        );
+        server.mount(
+            "base",
+            MemorySource::new().with_file("a.txt", b"mod".to_vec()),
+            1,
+        );
+
+        assert_eq!(server.read("base/a.txt").unwrap(), b"mod");
+        assert_eq!(server.unmount("base"), 2);
+        assert!(server.read("base/a.txt").is_err());
+    }
+
+    #[test]
+    fn test_asset_events() {
+        let mut assets = Assets::<i32>::new();
+        let handle = assets.add_with_path(1, "value.ron");
+        let reloaded = assets.reload_path(2, "value.ron");
+        assets.remove(handle.id());
+
+        let events = assets.drain_events();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].handle().map(|h| *h.get()), Some(1));
+        assert_eq!(events[1].handle().map(|h| *h.get()), Some(2));
+        assert!(matches!(events[2], AssetEvent::Removed(id) if id == handle.id()));
+        assert_eq!(reloaded.id(), handle.id());
+        assert!(assets.drain_events().is_empty());
+
+        // Queued events neither keep removed assets alive nor grow unbounded
+        let id = assets.add(3).id();
+        assets.remove(id);
+        let events = assets.drain_events();
+        assert!(matches!(events[0], AssetEvent::Created(_)));
+        assert!(events[0].handle().is_none());
+        for value in 0..MAX_PENDING_EVENTS as i32 + 10 {
+            assets.add(value);
+        }
+        assert_eq!(assets.drain_events().len(), MAX_PENDING_EVENTS);
+    }
+
     #[test]
     fn test_asset_server() {
         let mut server = AssetServer::new();
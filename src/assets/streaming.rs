@@ -0,0 +1,348 @@
+//! Proximity-based asset streaming
+//!
+//! Decides which detail level of each streamable texture or mesh should be
+//! resident based on camera distance, priority, and a GPU memory budget.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use glam::Vec3;
+
+use crate::renderer::GpuMemoryBudget;
+
+/// Global counter for generating unique stream IDs
+static NEXT_STREAM_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Identifier for a streamable asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StreamId(u64);
+
+impl StreamId {
+    /// Generate a new unique stream ID
+    fn next() -> Self {
+        Self(NEXT_STREAM_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Get the raw ID value
+    #[must_use]
+    pub const fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Kind of streamed resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamKind {
+    /// Texture whose levels are mip chains of decreasing resolution
+    Texture,
+    /// Mesh whose levels are LODs of decreasing detail
+    Mesh,
+}
+
+/// One detail level of a streamable asset
+#[derive(Debug, Clone)]
+pub struct StreamLevel {
+    /// File holding this level
+    pub path: PathBuf,
+    /// Estimated GPU memory when resident
+    pub size_bytes: u64,
+    /// Maximum camera distance at which this level is wanted
+    pub max_distance: f32,
+}
+
+impl StreamLevel {
+    /// Create a detail level
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>, size_bytes: u64, max_distance: f32) -> Self {
+        Self {
+            path: path.into(),
+            size_bytes,
+            max_distance,
+        }
+    }
+}
+
+/// A level the caller must load after a streaming update
+///
+/// Report the result with `AssetStreamer::complete` or `AssetStreamer::fail`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamRequest {
+    /// Asset to load
+    pub id: StreamId,
+    /// Resource kind
+    pub kind: StreamKind,
+    /// Level index (0 is the most detailed)
+    pub level: usize,
+    /// File holding the level
+    pub path: PathBuf,
+}
+
+/// Streaming state of one asset
+#[derive(Debug, Clone)]
+struct StreamEntry {
+    /// Resource kind
+    kind: StreamKind,
+    /// World-space center
+    position: Vec3,
+    /// Bounding radius
+    radius: f32,
+    /// Levels from most to least detailed
+    levels: Vec<StreamLevel>,
+    /// Priority multiplier (higher loads sooner)
+    priority: f32,
+    /// Level currently resident
+    resident: Option<usize>,
+    /// Level currently being loaded
+    pending: Option<usize>,
+}
+
+impl StreamEntry {
+    /// Level wanted at a distance from the camera
+    fn desired_level(&self, distance: f32) -> usize {
+        self.levels
+            .iter()
+            .position(|level| distance <= level.max_distance)
+            .unwrap_or(self.levels.len() - 1)
+    }
+}
+
+/// Decides which asset levels to load and unload each frame
+#[derive(Debug, Clone)]
+pub struct AssetStreamer {
+    /// Registered assets
+    entries: HashMap<StreamId, StreamEntry>,
+    /// Maximum loads started per update
+    pub max_loads_per_update: usize,
+}
+
+impl AssetStreamer {
+    /// Create an empty streamer
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            max_loads_per_update: 4,
+        }
+    }
+
+    /// Register a streamable asset
+    ///
+    /// `levels` are ordered from most to least detailed. The least detailed
+    /// level is always kept resident. Returns `None` if `levels` is empty.
+    pub fn register(
+        &mut self,
+        kind: StreamKind,
+        position: Vec3,
+        radius: f32,
+        levels: Vec<StreamLevel>,
+    ) -> Option<StreamId> {
+        if levels.is_empty() {
+            return None;
+        }
+        let id = StreamId::next();
+        self.entries.insert(
+            id,
+            StreamEntry {
+                kind,
+                position,
+                radius,
+                levels,
+                priority: 1.0,
+                resident: None,
+                pending: None,
+            },
+        );
+        Some(id)
+    }
+
+    /// Stop streaming an asset, releasing its budget
+    ///
+    /// Returns the level that was resident, which the caller should drop.
+    pub fn unregister(&mut self, id: StreamId, budget: &mut GpuMemoryBudget) -> Option<usize> {
+        let entry = self.entries.remove(&id)?;
+        budget.release(id.0);
+        entry.resident
+    }
+
+    /// Move a streamable asset
+    pub fn set_position(&mut self, id: StreamId, position: Vec3) {
+        if let Some(entry) = self.entries.get_mut(&id) {
+            entry.position = position;
+        }
+    }
+
+    /// Set an asset's priority multiplier (higher loads sooner)
+    pub fn set_priority(&mut self, id: StreamId, priority: f32) {
+        if let Some(entry) = self.entries.get_mut(&id) {
+            entry.priority = priority.max(f32::EPSILON);
+        }
+    }
+
+    /// Get the level currently resident for an asset
+    #[must_use]
+    pub fn resident_level(&self, id: StreamId) -> Option<usize> {
+        self.entries.get(&id)?.resident
+    }
+
+    /// Get the number of registered assets
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no assets are registered
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Report that a level finished loading
+    ///
+    /// Returns the previously resident level, which the caller should drop.
+    pub fn complete(&mut self, id: StreamId, level: usize) -> Option<usize> {
+        let entry = self.entries.get_mut(&id)?;
+        if entry.pending != Some(level) {
+            return None;
+        }
+        entry.pending = None;
+        entry.resident.replace(level).filter(|&old| old != level)
+    }
+
+    /// Report that a level failed to load
+    pub fn fail(&mut self, id: StreamId, budget: &mut GpuMemoryBudget) {
+        if let Some(entry) = self.entries.get_mut(&id) {
+            entry.pending = None;
+            let bytes = entry.resident.map_or(0, |l| entry.levels[l].size_bytes);
+            budget.reserve(id.0, bytes);
+        }
+    }
+
+    /// Compute which levels to load for the current camera position
+    ///
+    /// Lower-detail loads are always issued so memory is freed. Higher-detail
+    /// loads go nearest-and-highest-priority first, falling back to the most
+    /// detailed level that fits in the budget.
+    pub fn update(&mut self, camera: Vec3, budget: &mut GpuMemoryBudget) -> Vec<StreamRequest> {
+        let mut wanted: Vec<(StreamId, usize, f32)> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.pending.is_none())
+            .filter_map(|(&id, entry)| {
+                let distance = (entry.position.distance(camera) - entry.radius).max(0.0);
+                let level = entry.desired_level(distance);
+                (entry.resident != Some(level)).then_some((id, level, distance / entry.priority))
+            })
+            .collect();
+        wanted.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+        let mut requests = Vec::new();
+        let mut loads = 0;
+
+        for (id, level, _) in wanted {
+            let entry = &self.entries[&id];
+            let level = match entry.resident {
+                // Nothing resident yet: start from the coarsest level
+                None => {
+                    if loads >= self.max_loads_per_update {
+                        continue;
+                    }
+                    let coarsest = entry.levels.len() - 1;
+                    budget.reserve(id.0, entry.levels[coarsest].size_bytes);
+                    loads += 1;
+                    coarsest
+                }
+                Some(resident) if level > resident => {
+                    budget.reserve(id.0, entry.levels[level].size_bytes);
+                    level
+                }
+                Some(resident) => {
+                    if loads >= self.max_loads_per_update {
+                        continue;
+                    }
+                    let Some(level) = (level..resident)
+                        .find(|&l| budget.try_reserve(id.0, entry.levels[l].size_bytes))
+                    else {
+                        continue;
+                    };
+                    loads += 1;
+                    level
+                }
+            };
+            requests.push(self.start_load(id, level));
+        }
+
+        requests
+    }
+
+    /// Mark a level as pending and build its load request
+    fn start_load(&mut self, id: StreamId, level: usize) -> StreamRequest {
+        let entry = self
+            .entries
+            .get_mut(&id)
+            .expect("stream entry exists for a wanted id");
+        entry.pending = Some(level);
+        StreamRequest {
+            id,
+            kind: entry.kind,
+            level,
+            path: entry.levels[level].path.clone(),
+        }
+    }
+}
+
+impl Default for AssetStreamer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texture_levels() -> Vec<StreamLevel> {
+        vec![
+            StreamLevel::new("rock_0.png", 64, 10.0),
+            StreamLevel::new("rock_1.png", 16, 50.0),
+            StreamLevel::new("rock_2.png", 4, f32::INFINITY),
+        ]
+    }
+
+    #[test]
+    fn test_streams_up_when_close() {
+        let mut streamer = AssetStreamer::new();
+        let mut budget = GpuMemoryBudget::new(1024);
+        let id = streamer
+            .register(StreamKind::Texture, Vec3::ZERO, 1.0, texture_levels())
+            .unwrap();
+
+        let requests = streamer.update(Vec3::new(5.0, 0.0, 0.0), &mut budget);
+        assert!(matches!(requests[..], [StreamRequest { level: 2, .. }]));
+        assert_eq!(streamer.complete(id, 2), None);
+
+        let requests = streamer.update(Vec3::new(5.0, 0.0, 0.0), &mut budget);
+        assert!(matches!(requests[..], [StreamRequest { level: 0, .. }]));
+        assert_eq!(streamer.complete(id, 0), Some(2));
+        assert_eq!(budget.used(), 64);
+    }
+
+    #[test]
+    fn test_budget_blocks_upgrade() {
+        let mut streamer = AssetStreamer::new();
+        let mut budget = GpuMemoryBudget::new(32);
+        let id = streamer
+            .register(StreamKind::Texture, Vec3::ZERO, 1.0, texture_levels())
+            .unwrap();
+
+        streamer.update(Vec3::ZERO, &mut budget);
+        streamer.complete(id, 2);
+
+        let requests = streamer.update(Vec3::ZERO, &mut budget);
+        assert!(matches!(requests[..], [StreamRequest { level: 1, .. }]));
+        streamer.complete(id, 1);
+
+        assert!(streamer.update(Vec3::ZERO, &mut budget).is_empty());
+        assert_eq!(streamer.resident_level(id), Some(1));
+    }
+}
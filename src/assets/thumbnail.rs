@@ -0,0 +1,249 @@
+//! Asset thumbnails
+//!
+//! Generates small previews for the asset browser by downscaling textures,
+//! rendering meshes offscreen, or filling material color swatches, and
+//! caches them in the derived-data cache keyed by source content.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use glam::Vec3;
+
+use super::cache::{CacheKey, DerivedDataCache};
+use crate::renderer::{Mesh, Renderer};
+
+/// Version of the thumbnail format, part of every cache key
+const THUMBNAIL_VERSION: u32 = 1;
+
+/// Default thumbnail edge length in pixels
+pub const DEFAULT_THUMBNAIL_SIZE: u32 = 96;
+
+/// Errors from generating thumbnails
+#[derive(Debug, Clone)]
+pub enum ThumbnailError {
+    /// Failed to decode the source asset
+    DecodeError(String),
+    /// The asset could not be rendered
+    RenderError(String),
+}
+
+impl std::fmt::Display for ThumbnailError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DecodeError(e) => write!(f, "Decode error: {e}"),
+            Self::RenderError(e) => write!(f, "Render error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ThumbnailError {}
+
+/// A small RGBA8 preview image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Thumbnail {
+    /// Width in pixels
+    pub width: u32,
+    /// Height in pixels
+    pub height: u32,
+    /// RGBA8 pixels, row-major
+    pub rgba: Vec<u8>,
+}
+
+impl Thumbnail {
+    /// Downscale an encoded image (PNG, JPEG, ...) to fit within `size`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be decoded
+    pub fn from_image_bytes(bytes: &[u8], size: u32) -> Result<Self, ThumbnailError> {
+        let image = image::load_from_memory(bytes)
+            .map_err(|e| ThumbnailError::DecodeError(e.to_string()))?;
+        let small = image.thumbnail(size, size).to_rgba8();
+        Ok(Self {
+            width: small.width(),
+            height: small.height(),
+            rgba: small.into_raw(),
+        })
+    }
+
+    /// Create a solid swatch for a material color
+    #[must_use]
+    pub fn swatch(color: Vec3, size: u32) -> Self {
+        let pixel = [
+            (color.x.clamp(0.0, 1.0) * 255.0) as u8,
+            (color.y.clamp(0.0, 1.0) * 255.0) as u8,
+            (color.z.clamp(0.0, 1.0) * 255.0) as u8,
+            255,
+        ];
+        Self {
+            width: size,
+            height: size,
+            rgba: pixel.repeat((size * size) as usize),
+        }
+    }
+
+    /// Serialize for the derived-data cache
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(8 + self.rgba.len());
+        bytes.extend_from_slice(&self.width.to_le_bytes());
+        bytes.extend_from_slice(&self.height.to_le_bytes());
+        bytes.extend_from_slice(&self.rgba);
+        bytes
+    }
+
+    /// Deserialize from the derived-data cache
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let width = u32::from_le_bytes(bytes.get(0..4)?.try_into().ok()?);
+        let height = u32::from_le_bytes(bytes.get(4..8)?.try_into().ok()?);
+        let rgba = bytes.get(8..)?.to_vec();
+        (rgba.len() == (width * height * 4) as usize).then_some(Self {
+            width,
+            height,
+            rgba,
+        })
+    }
+}
+
+/// In-memory and on-disk cache of asset thumbnails keyed by path
+#[derive(Debug)]
+pub struct ThumbnailCache {
+    /// On-disk storage keyed by source content
+    cache: DerivedDataCache,
+    /// Edge length of generated thumbnails
+    size: u32,
+    /// Thumbnails generated or loaded this session
+    thumbnails: HashMap<PathBuf, Thumbnail>,
+}
+
+impl ThumbnailCache {
+    /// Create a thumbnail cache backed by a derived-data cache
+    #[must_use]
+    pub fn new(cache: DerivedDataCache) -> Self {
+        Self {
+            cache,
+            size: DEFAULT_THUMBNAIL_SIZE,
+            thumbnails: HashMap::new(),
+        }
+    }
+
+    /// Set the thumbnail edge length
+    #[must_use]
+    pub fn with_size(mut self, size: u32) -> Self {
+        self.size = size.max(1);
+        self
+    }
+
+    /// Get the thumbnail edge length
+    #[must_use]
+    pub const fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Get a thumbnail generated earlier this session
+    #[must_use]
+    pub fn get(&self, path: &Path) -> Option<&Thumbnail> {
+        self.thumbnails.get(path)
+    }
+
+    /// Forget a thumbnail so it is regenerated (e.g. after the source changed)
+    pub fn invalidate(&mut self, path: &Path) {
+        self.thumbnails.remove(path);
+    }
+
+    /// Get or generate a texture thumbnail from the encoded source bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be decoded
+    pub fn texture(&mut self, path: &Path, bytes: &[u8]) -> Result<&Thumbnail, ThumbnailError> {
+        let size = self.size;
+        self.get_or_insert(path, "thumbnail_texture", bytes, || {
+            Thumbnail::from_image_bytes(bytes, size)
+        })
+    }
+
+    /// Get or render a mesh thumbnail offscreen
+    ///
+    /// `source` is the file the mesh came from and keys the disk cache.
+    /// The mesh must already be uploaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mesh has no bounds or is not uploaded
+    pub fn mesh(
+        &mut self,
+        path: &Path,
+        source: &[u8],
+        mesh: &Mesh,
+        renderer: &Renderer,
+    ) -> Result<&Thumbnail, ThumbnailError> {
+        let size = self.size;
+        self.get_or_insert(path, "thumbnail_mesh", source, || {
+            let rgba = renderer.render_thumbnail(mesh, size).ok_or_else(|| {
+                ThumbnailError::RenderError("mesh has no bounds or is not uploaded".into())
+            })?;
+            Ok(Thumbnail {
+                width: size,
+                height: size,
+                rgba,
+            })
+        })
+    }
+
+    /// Get or create a material swatch
+    pub fn material(&mut self, path: &Path, color: Vec3) -> &Thumbnail {
+        let size = self.size;
+        self.thumbnails
+            .entry(path.to_path_buf())
+            .or_insert_with(|| Thumbnail::swatch(color, size))
+    }
+
+    /// Look up memory, then disk, then generate and store on both
+    fn get_or_insert(
+        &mut self,
+        path: &Path,
+        processor: &str,
+        source: &[u8],
+        generate: impl FnOnce() -> Result<Thumbnail, ThumbnailError>,
+    ) -> Result<&Thumbnail, ThumbnailError> {
+        if !self.thumbnails.contains_key(path) {
+            let key = CacheKey::new(processor, THUMBNAIL_VERSION, &self.size.to_string(), source);
+            let thumbnail = match self.cache.get(key).and_then(|b| Thumbnail::from_bytes(&b)) {
+                Some(thumbnail) => thumbnail,
+                None => {
+                    let thumbnail = generate()?;
+                    if let Err(e) = self.cache.put(key, &thumbnail.to_bytes()) {
+                        log::warn!("Failed to cache thumbnail for {}: {e}", path.display());
+                    }
+                    thumbnail
+                }
+            };
+            self.thumbnails.insert(path.to_path_buf(), thumbnail);
+        }
+        Ok(&self.thumbnails[path])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_texture_thumbnail_is_cached() {
+        let root =
+            std::env::temp_dir().join(format!("engine_thumbnail_test_{}", std::process::id()));
+        let mut png = Vec::new();
+        image::RgbaImage::from_pixel(256, 128, image::Rgba([255, 0, 0, 255]))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let mut thumbnails = ThumbnailCache::new(DerivedDataCache::new(&root)).with_size(64);
+        let thumbnail = thumbnails.texture(Path::new("red.png"), &png).unwrap();
+        assert_eq!((thumbnail.width, thumbnail.height), (64, 32));
+
+        let mut reloaded = ThumbnailCache::new(DerivedDataCache::new(&root)).with_size(64);
+        reloaded.texture(Path::new("red.png"), &png).unwrap();
+        assert_eq!(reloaded.cache.hits(), 1);
+        reloaded.cache.clear().unwrap();
+    }
+}
@@ -0,0 +1,455 @@
+//! Virtual filesystem with mount points
+//!
+//! Resolves asset paths through mounted directories, pack archives, and
+//! in-memory sources in priority order, so mods and DLC can override
+//! base content.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Magic bytes at the start of a pack archive
+const PACK_MAGIC: &[u8; 4] = b"EPAK";
+
+/// Errors from virtual filesystem operations
+#[derive(Debug, Clone)]
+pub enum VfsError {
+    /// No mount contains the path
+    NotFound(String),
+    /// IO error reading from a source
+    IoError(String),
+    /// Malformed pack archive
+    InvalidPack(String),
+}
+
+impl std::fmt::Display for VfsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound(e) => write!(f, "Not found: {e}"),
+            Self::IoError(e) => write!(f, "IO error: {e}"),
+            Self::InvalidPack(e) => write!(f, "Invalid pack: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for VfsError {}
+
+/// A source of files that can be mounted into the VFS
+pub trait VfsSource: Send + Sync {
+    /// Read a file relative to the source root
+    ///
+    /// Returns `None` if the source does not contain the file.
+    fn read(&self, path: &str) -> Option<Result<Vec<u8>, VfsError>>;
+
+    /// Check if the source contains a file
+    fn contains(&self, path: &str) -> bool;
+}
+
+/// Files from a directory on disk
+#[derive(Debug, Clone)]
+pub struct DirectorySource {
+    /// Root directory
+    root: PathBuf,
+}
+
+impl DirectorySource {
+    /// Create a source rooted at a directory
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Resolve a relative path under the root, dropping `..`, `.` and
+    /// absolute components so it can never leave the root
+    fn resolve(&self, path: &str) -> PathBuf {
+        let mut full = self.root.clone();
+        for component in Path::new(path).components() {
+            if let std::path::Component::Normal(part) = component {
+                full.push(part);
+            }
+        }
+        full
+    }
+}
+
+impl VfsSource for DirectorySource {
+    fn read(&self, path: &str) -> Option<Result<Vec<u8>, VfsError>> {
+        let full = self.resolve(path);
+        if !full.is_file() {
+            return None;
+        }
+        Some(std::fs::read(full).map_err(|e| VfsError::IoError(e.to_string())))
+    }
+
+    fn contains(&self, path: &str) -> bool {
+        self.resolve(path).is_file()
+    }
+}
+
+/// Files held in memory (generated content, tests, embedded data)
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    /// File contents by normalized path
+    files: HashMap<String, Arc<[u8]>>,
+}
+
+impl MemorySource {
+    /// Create an empty memory source
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            files: HashMap::new(),
+        }
+    }
+
+    /// Add or replace a file
+    pub fn insert(&mut self, path: &str, bytes: impl Into<Arc<[u8]>>) {
+        self.files.insert(normalize_path(path), bytes.into());
+    }
+
+    /// Add a file (builder style)
+    #[must_use]
+    pub fn with_file(mut self, path: &str, bytes: impl Into<Arc<[u8]>>) -> Self {
+        self.insert(path, bytes);
+        self
+    }
+}
+
+impl VfsSource for MemorySource {
+    fn read(&self, path: &str) -> Option<Result<Vec<u8>, VfsError>> {
+        self.files.get(path).map(|bytes| Ok(bytes.to_vec()))
+    }
+
+    fn contains(&self, path: &str) -> bool {
+        self.files.contains_key(path)
+    }
+}
+
+/// Files from a pack archive
+///
+/// Layout: `EPAK`, entry count (u32 LE), then per entry the name length
+/// (u32 LE), UTF-8 name, data offset and length (u64 LE each), followed
+/// by the file data.
+#[derive(Debug, Clone)]
+pub struct PackSource {
+    /// Entire archive contents
+    data: Arc<[u8]>,
+    /// Byte range of each file
+    entries: HashMap<String, (usize, usize)>,
+}
+
+impl PackSource {
+    /// Open a pack archive from disk
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid pack
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, VfsError> {
+        let bytes = std::fs::read(path).map_err(|e| VfsError::IoError(e.to_string()))?;
+        Self::from_bytes(bytes)
+    }
+
+    /// Parse a pack archive from bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not a valid pack
+    pub fn from_bytes(bytes: impl Into<Arc<[u8]>>) -> Result<Self, VfsError> {
+        let data: Arc<[u8]> = bytes.into();
+        let mut reader = ByteReader::new(&data);
+
+        if reader.take(4)? != PACK_MAGIC {
+            return Err(VfsError::InvalidPack("bad magic".into()));
+        }
+
+        let count = reader.read_u32()? as usize;
+        let mut entries = HashMap::with_capacity(count);
+        for _ in 0..count {
+            let name_len = reader.read_u32()? as usize;
+            let name = std::str::from_utf8(reader.take(name_len)?)
+                .map_err(|e| VfsError::InvalidPack(e.to_string()))?
+                .to_string();
+            let offset = reader.read_u64()? as usize;
+            let len = reader.read_u64()? as usize;
+            if offset.checked_add(len).is_none_or(|end| end > data.len()) {
+                return Err(VfsError::InvalidPack(format!("entry out of range: {name}")));
+            }
+            entries.insert(normalize_path(&name), (offset, len));
+        }
+
+        Ok(Self { data, entries })
+    }
+
+    /// Build a pack archive from files
+    #[must_use]
+    pub fn build(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let header_len: usize = 8 + files.iter().map(|(name, _)| 20 + name.len()).sum::<usize>();
+
+        let mut out = Vec::new();
+        out.extend_from_slice(PACK_MAGIC);
+        out.extend_from_slice(&(files.len() as u32).to_le_bytes());
+
+        let mut offset = header_len;
+        for (name, bytes) in files {
+            out.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            out.extend_from_slice(name.as_bytes());
+            out.extend_from_slice(&(offset as u64).to_le_bytes());
+            out.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+            offset += bytes.len();
+        }
+        for (_, bytes) in files {
+            out.extend_from_slice(bytes);
+        }
+        out
+    }
+
+    /// Get the number of files in the pack
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the pack is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl VfsSource for PackSource {
+    fn read(&self, path: &str) -> Option<Result<Vec<u8>, VfsError>> {
+        self.entries
+            .get(path)
+            .map(|&(offset, len)| Ok(self.data[offset..offset + len].to_vec()))
+    }
+
+    fn contains(&self, path: &str) -> bool {
+        self.entries.contains_key(path)
+    }
+}
+
+/// Little-endian cursor over pack bytes
+struct ByteReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], VfsError> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or_else(|| VfsError::InvalidPack("unexpected end of data".into()))?;
+        let slice = &self.data[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, VfsError> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, VfsError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+}
+
+/// A source mounted under a path prefix
+struct Mount {
+    /// Virtual prefix (normalized, no trailing slash; empty for root)
+    prefix: String,
+    /// Higher priority mounts are searched first
+    priority: i32,
+    /// The mounted source
+    source: Box<dyn VfsSource>,
+}
+
+/// Virtual filesystem resolving paths through prioritized mounts
+#[derive(Default)]
+pub struct Vfs {
+    /// Mounts sorted by descending priority (latest mount wins ties)
+    mounts: Vec<Mount>,
+}
+
+impl Vfs {
+    /// Create an empty VFS
+    #[must_use]
+    pub fn new() -> Self {
+        Self { mounts: Vec::new() }
+    }
+
+    /// Mount a source under a prefix
+    ///
+    /// Several sources may share a prefix; the highest priority source
+    /// containing a file wins.
+    pub fn mount(&mut self, prefix: &str, source: impl VfsSource + 'static, priority: i32) {
+        let mount = Mount {
+            prefix: normalize_path(prefix),
+            priority,
+            source: Box::new(source),
+        };
+        let index = self
+            .mounts
+            .iter()
+            .position(|m| m.priority <= priority)
+            .unwrap_or(self.mounts.len());
+        self.mounts.insert(index, mount);
+    }
+
+    /// Remove every source mounted under a prefix
+    ///
+    /// Returns the number of mounts removed.
+    pub fn unmount(&mut self, prefix: &str) -> usize {
+        let prefix = normalize_path(prefix);
+        let before = self.mounts.len();
+        self.mounts.retain(|m| m.prefix != prefix);
+        before - self.mounts.len()
+    }
+
+    /// Get the number of mounts
+    #[must_use]
+    pub fn mount_count(&self) -> usize {
+        self.mounts.len()
+    }
+
+    /// Iterate over mounts that could contain a path, in priority order,
+    /// yielding each mount with the path relative to its prefix
+    fn candidates<'a>(&'a self, path: &'a str) -> impl Iterator<Item = (&'a Mount, &'a str)> + 'a {
+        self.mounts.iter().filter_map(move |mount| {
+            if mount.prefix.is_empty() {
+                Some((mount, path))
+            } else {
+                path.strip_prefix(mount.prefix.as_str())
+                    .and_then(|rest| rest.strip_prefix('/'))
+                    .map(|rest| (mount, rest))
+            }
+        })
+    }
+
+    /// Read a file through the mounts
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no mount contains the file or reading fails
+    pub fn read(&self, path: &str) -> Result<Vec<u8>, VfsError> {
+        let path = normalize_path(path);
+        self.candidates(&path)
+            .find_map(|(mount, relative)| mount.source.read(relative))
+            .unwrap_or_else(|| Err(VfsError::NotFound(path.clone())))
+    }
+
+    /// Read a file as UTF-8 text
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is missing or not valid UTF-8
+    pub fn read_to_string(&self, path: &str) -> Result<String, VfsError> {
+        String::from_utf8(self.read(path)?).map_err(|e| VfsError::IoError(e.to_string()))
+    }
+
+    /// Check if any mount contains a file
+    #[must_use]
+    pub fn exists(&self, path: &str) -> bool {
+        let path = normalize_path(path);
+        self.candidates(&path)
+            .any(|(mount, relative)| mount.source.contains(relative))
+    }
+}
+
+impl std::fmt::Debug for Vfs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.mounts.iter().map(|m| (&m.prefix, m.priority)))
+            .finish()
+    }
+}
+
+/// Normalize a virtual path: forward slashes, no leading/trailing slash,
+/// no `.` or `..` segments
+///
+/// `..` is dropped rather than resolved, so a path can never climb out of
+/// the mount it names.
+#[must_use]
+pub fn normalize_path(path: &str) -> String {
+    path.replace('\\', "/")
+        .split('/')
+        .filter(|segment| !segment.is_empty() && *segment != "." && *segment != "..")
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_override() {
+        let mut vfs = Vfs::new();
+        vfs.mount(
+            "data",
+            MemorySource::new()
+                .with_file("config.ron", b"base".to_vec())
+                .with_file("only_base.ron", b"base".to_vec()),
+            0,
+        );
+        vfs.mount(
+            "data",
+            MemorySource::new().with_file("config.ron", b"mod".to_vec()),
+            10,
+        );
+
+        assert_eq!(vfs.read("data/config.ron").unwrap(), b"mod");
+        assert_eq!(vfs.read("/data//only_base.ron").unwrap(), b"base");
+        assert!(matches!(
+            vfs.read("data/missing"),
+            Err(VfsError::NotFound(_))
+        ));
+
+        assert_eq!(vfs.unmount("data/"), 2);
+        assert!(!vfs.exists("data/config.ron"));
+    }
+
+    #[test]
+    fn test_parent_segments_stay_in_mount() {
+        assert_eq!(normalize_path("mods/../../etc/passwd"), "mods/etc/passwd");
+
+        let root = std::env::temp_dir().join(format!("engine_vfs_test_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("mods")).unwrap();
+        std::fs::write(root.join("secret.txt"), b"secret").unwrap();
+        std::fs::write(root.join("mods/level.ron"), b"level").unwrap();
+
+        let mut vfs = Vfs::new();
+        vfs.mount("mods", DirectorySource::new(root.join("mods")), 0);
+        assert_eq!(vfs.read("mods/level.ron").unwrap(), b"level");
+        assert!(vfs.read("mods/../secret.txt").is_err());
+        assert!(!vfs.exists("mods/../../secret.txt"));
+
+        let source = DirectorySource::new(root.join("mods"));
+        assert!(source.read("../secret.txt").is_none());
+        assert!(!source.contains("../secret.txt"));
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn test_pack_round_trip() {
+        let bytes = PackSource::build(&[("a.txt", &b"hello"[..]), ("dir/b.txt", &b"world"[..])]);
+        let pack = PackSource::from_bytes(bytes).unwrap();
+
+        let mut vfs = Vfs::new();
+        vfs.mount("dlc", pack, 0);
+
+        assert_eq!(vfs.read_to_string("dlc/a.txt").unwrap(), "hello");
+        assert_eq!(vfs.read_to_string("dlc/dir/b.txt").unwrap(), "world");
+        assert!(PackSource::from_bytes(b"nope".to_vec()).is_err());
+    }
+}
@@ -0,0 +1,534 @@
+//! Ambience beds and sweeteners
+//!
+//! Blends looping background beds by listener zone and time of day, and
+//! schedules randomized one-shot sweeteners around the listener.
+
+use glam::Vec3;
+
+use super::PlaybackState;
+use super::manager::AudioManager;
+
+/// Hours in a day, used to wrap time-of-day ranges
+const HOURS_PER_DAY: f32 = 24.0;
+
+/// Range of hours during which a sound is active
+///
+/// Ranges may wrap past midnight, e.g. 20.0 to 5.0 for night.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeRange {
+    /// Start hour (0.0 to 24.0)
+    pub start: f32,
+    /// End hour (0.0 to 24.0)
+    pub end: f32,
+}
+
+impl TimeRange {
+    /// Create a time range
+    #[must_use]
+    pub const fn new(start: f32, end: f32) -> Self {
+        Self { start, end }
+    }
+
+    /// Check if an hour falls inside the range
+    #[must_use]
+    pub fn contains(&self, hour: f32) -> bool {
+        let hour = hour.rem_euclid(HOURS_PER_DAY);
+        if self.start <= self.end {
+            hour >= self.start && hour < self.end
+        } else {
+            hour >= self.start || hour < self.end
+        }
+    }
+}
+
+/// A named box-shaped region that selects ambience
+#[derive(Debug, Clone)]
+pub struct AmbienceZone {
+    /// Zone name referenced by beds and sweeteners
+    pub name: String,
+    /// Minimum corner
+    pub min: Vec3,
+    /// Maximum corner
+    pub max: Vec3,
+}
+
+impl AmbienceZone {
+    /// Create a zone from its corners
+    #[must_use]
+    pub fn new(name: impl Into<String>, min: Vec3, max: Vec3) -> Self {
+        Self {
+            name: name.into(),
+            min: min.min(max),
+            max: min.max(max),
+        }
+    }
+
+    /// Check if a point is inside the zone
+    #[must_use]
+    pub fn contains(&self, point: Vec3) -> bool {
+        point.cmpge(self.min).all() && point.cmple(self.max).all()
+    }
+
+    /// Volume of the zone, used to prefer the innermost of nested zones
+    fn volume(&self) -> f32 {
+        let size = self.max - self.min;
+        size.x * size.y * size.z
+    }
+}
+
+/// Conditions under which an ambience element plays
+#[derive(Debug, Clone, Default)]
+struct Conditions {
+    /// Zones where the element plays (empty means everywhere)
+    zones: Vec<String>,
+    /// Hours during which the element plays
+    time: Option<TimeRange>,
+}
+
+impl Conditions {
+    /// Check if the element is active
+    fn matches(&self, zone: Option<&str>, hour: f32) -> bool {
+        let zone_ok =
+            self.zones.is_empty() || zone.is_some_and(|z| self.zones.iter().any(|n| n == z));
+        let time_ok = self.time.is_none_or(|t| t.contains(hour));
+        zone_ok && time_ok
+    }
+}
+
+/// A looping background sound
+#[derive(Debug, Clone)]
+pub struct AmbienceBed {
+    /// Name of the looping source in the `AudioManager`
+    pub sound: String,
+    /// Volume when fully faded in
+    pub volume: f32,
+    /// Seconds to fade fully in or out
+    pub fade_time: f32,
+    /// When this bed plays
+    conditions: Conditions,
+    /// Current fade weight (0.0 to 1.0)
+    weight: f32,
+}
+
+impl AmbienceBed {
+    /// Create a bed that plays everywhere at all times
+    #[must_use]
+    pub fn new(sound: impl Into<String>, volume: f32) -> Self {
+        Self {
+            sound: sound.into(),
+            volume,
+            fade_time: 2.0,
+            conditions: Conditions::default(),
+            weight: 0.0,
+        }
+    }
+
+    /// Restrict the bed to a zone (may be called several times)
+    #[must_use]
+    pub fn in_zone(mut self, zone: impl Into<String>) -> Self {
+        self.conditions.zones.push(zone.into());
+        self
+    }
+
+    /// Restrict the bed to a time of day
+    #[must_use]
+    pub fn during(mut self, time: TimeRange) -> Self {
+        self.conditions.time = Some(time);
+        self
+    }
+
+    /// Set the fade time in seconds
+    #[must_use]
+    pub fn with_fade_time(mut self, seconds: f32) -> Self {
+        self.fade_time = seconds;
+        self
+    }
+
+    /// Get the current fade weight
+    #[must_use]
+    pub const fn weight(&self) -> f32 {
+        self.weight
+    }
+}
+
+/// A randomized one-shot played around the listener
+#[derive(Debug, Clone)]
+pub struct Sweetener {
+    /// Candidate sounds, one picked at random per spawn
+    pub sounds: Vec<String>,
+    /// Volume before distance attenuation
+    pub volume: f32,
+    /// Seconds between spawns (min, max)
+    pub interval: (f32, f32),
+    /// Horizontal distance from the listener (min, max)
+    pub distance: (f32, f32),
+    /// Height offset from the listener (min, max)
+    pub height: (f32, f32),
+    /// When this sweetener plays
+    conditions: Conditions,
+    /// Seconds until the next spawn
+    timer: f32,
+}
+
+impl Sweetener {
+    /// Create a sweetener from candidate sounds
+    #[must_use]
+    pub fn new(sounds: Vec<String>, volume: f32) -> Self {
+        Self {
+            sounds,
+            volume,
+            interval: (5.0, 15.0),
+            distance: (10.0, 40.0),
+            height: (0.0, 5.0),
+            conditions: Conditions::default(),
+            timer: 0.0,
+        }
+    }
+
+    /// Restrict the sweetener to a zone (may be called several times)
+    #[must_use]
+    pub fn in_zone(mut self, zone: impl Into<String>) -> Self {
+        self.conditions.zones.push(zone.into());
+        self
+    }
+
+    /// Restrict the sweetener to a time of day
+    #[must_use]
+    pub fn during(mut self, time: TimeRange) -> Self {
+        self.conditions.time = Some(time);
+        self
+    }
+
+    /// Set the spawn interval range in seconds
+    #[must_use]
+    pub fn with_interval(mut self, min: f32, max: f32) -> Self {
+        self.interval = (min, max.max(min));
+        self
+    }
+
+    /// Set the horizontal distance range from the listener
+    #[must_use]
+    pub fn with_distance(mut self, min: f32, max: f32) -> Self {
+        self.distance = (min, max.max(min));
+        self
+    }
+
+    /// Set the height offset range from the listener
+    #[must_use]
+    pub fn with_height(mut self, min: f32, max: f32) -> Self {
+        self.height = (min, max.max(min));
+        self
+    }
+}
+
+/// A sweetener one-shot that should be played this frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweetenerSpawn {
+    /// Sound to play
+    pub sound: String,
+    /// World position of the sound
+    pub position: Vec3,
+    /// Volume before spatialization
+    pub volume: f32,
+}
+
+/// Blends ambience beds and schedules sweeteners
+#[derive(Debug, Clone)]
+pub struct AmbienceManager {
+    /// Zones used to classify the listener position
+    zones: Vec<AmbienceZone>,
+    /// Looping beds
+    beds: Vec<AmbienceBed>,
+    /// One-shot sweeteners
+    sweeteners: Vec<Sweetener>,
+    /// Zone the listener was in at the last update
+    current_zone: Option<String>,
+    /// Random state for sweetener scheduling
+    seed: u32,
+}
+
+impl AmbienceManager {
+    /// Create an empty ambience manager
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            zones: Vec::new(),
+            beds: Vec::new(),
+            sweeteners: Vec::new(),
+            current_zone: None,
+            seed: 0x9E37_79B9,
+        }
+    }
+
+    /// Set the random seed used for sweeteners
+    #[must_use]
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        self.seed = seed.max(1);
+        self
+    }
+
+    /// Add a zone
+    pub fn add_zone(&mut self, zone: AmbienceZone) {
+        self.zones.push(zone);
+    }
+
+    /// Add a looping bed
+    pub fn add_bed(&mut self, bed: AmbienceBed) {
+        self.beds.push(bed);
+    }
+
+    /// Add a sweetener
+    pub fn add_sweetener(&mut self, mut sweetener: Sweetener) {
+        sweetener.timer = self.random_range(sweetener.interval);
+        self.sweeteners.push(sweetener);
+    }
+
+    /// Get the beds
+    #[must_use]
+    pub fn beds(&self) -> &[AmbienceBed] {
+        &self.beds
+    }
+
+    /// Get the zone the listener was in at the last update
+    #[must_use]
+    pub fn current_zone(&self) -> Option<&str> {
+        self.current_zone.as_deref()
+    }
+
+    /// Find the innermost zone containing a point
+    #[must_use]
+    pub fn zone_at(&self, point: Vec3) -> Option<&AmbienceZone> {
+        self.zones
+            .iter()
+            .filter(|zone| zone.contains(point))
+            .min_by(|a, b| a.volume().total_cmp(&b.volume()))
+    }
+
+    /// Advance fades and sweetener timers
+    ///
+    /// Returns sweeteners to play this frame. Apply bed volumes to audio
+    /// with `apply`.
+    pub fn update(&mut self, listener: Vec3, hour: f32, dt: f32) -> Vec<SweetenerSpawn> {
+        self.current_zone = self.zone_at(listener).map(|z| z.name.clone());
+        let zone = self.current_zone.clone();
+
+        for bed in &mut self.beds {
+            let target = if bed.conditions.matches(zone.as_deref(), hour) {
+                1.0
+            } else {
+                0.0
+            };
+            let step = if bed.fade_time > 0.0 {
+                dt / bed.fade_time
+            } else {
+                1.0
+            };
+            bed.weight += (target - bed.weight).clamp(-step, step);
+        }
+
+        let mut spawns = Vec::new();
+        for index in 0..self.sweeteners.len() {
+            let sweetener = &mut self.sweeteners[index];
+            sweetener.timer -= dt;
+            if sweetener.timer > 0.0 {
+                continue;
+            }
+            let active =
+                sweetener.conditions.matches(zone.as_deref(), hour) && !sweetener.sounds.is_empty();
+            let sweetener = self.sweeteners[index].clone();
+            self.sweeteners[index].timer = self.random_range(sweetener.interval);
+            if !active {
+                continue;
+            }
+
+            let sound_index = (self.random() * sweetener.sounds.len() as f32) as usize;
+            let angle = self.random() * std::f32::consts::TAU;
+            let distance = self.random_range(sweetener.distance);
+            let height = self.random_range(sweetener.height);
+            spawns.push(SweetenerSpawn {
+                sound: sweetener.sounds[sound_index.min(sweetener.sounds.len() - 1)].clone(),
+                position: listener
+                    + Vec3::new(angle.cos() * distance, height, angle.sin() * distance),
+                volume: sweetener.volume,
+            });
+        }
+
+        spawns
+    }
+
+    /// Apply bed volumes to their looping sources
+    ///
+    /// Beds that faded out are paused; beds fading in are started again
+    /// whether they were paused or their sink stopped. Sources
+    /// are only touched when their state or volume actually changes, so
+    /// calling this every frame never restarts a stream.
+    pub fn apply(&self, audio: &mut AudioManager) {
+        for bed in &self.beds {
+            let Some(source) = audio.get(&bed.sound) else {
+                continue;
+            };
+            let state = source.state();
+            let current_volume = source.volume();
+
+            if bed.weight <= 0.0 {
+                if state == PlaybackState::Playing {
+                    audio.pause(&bed.sound);
+                }
+                continue;
+            }
+
+            if state != PlaybackState::Playing {
+                audio.play(&bed.sound);
+            }
+            let volume = bed.volume * bed.weight;
+            if (current_volume - volume).abs() > f32::EPSILON {
+                audio.set_volume(&bed.sound, volume);
+            }
+        }
+    }
+
+    /// Next random value in 0.0..1.0
+    fn random(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        // 24 bits fit an f32 mantissa exactly, so the result stays below 1.0
+        (self.seed >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Random value within a range
+    fn random_range(&mut self, (min, max): (f32, f32)) -> f32 {
+        min + self.random() * (max - min)
+    }
+}
+
+impl Default for AmbienceManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Forest and cave zones with a bed for each, and a night-only bed
+    fn manager() -> AmbienceManager {
+        let mut manager = AmbienceManager::new();
+        manager.add_zone(AmbienceZone::new(
+            "forest",
+            Vec3::splat(-100.0),
+            Vec3::splat(100.0),
+        ));
+        manager.add_zone(AmbienceZone::new("cave", Vec3::ZERO, Vec3::splat(10.0)));
+        manager.add_bed(
+            AmbienceBed::new("wind", 1.0)
+                .in_zone("forest")
+                .with_fade_time(1.0),
+        );
+        manager.add_bed(
+            AmbienceBed::new("drips", 1.0)
+                .in_zone("cave")
+                .with_fade_time(1.0),
+        );
+        manager.add_bed(
+            AmbienceBed::new("crickets", 1.0)
+                .during(TimeRange::new(20.0, 5.0))
+                .with_fade_time(0.0),
+        );
+        manager
+    }
+
+    fn weight(manager: &AmbienceManager, sound: &str) -> f32 {
+        manager
+            .beds()
+            .iter()
+            .find(|bed| bed.sound == sound)
+            .unwrap()
+            .weight()
+    }
+
+    #[test]
+    fn test_time_range_wraps_midnight() {
+        let night = TimeRange::new(20.0, 5.0);
+        assert!(night.contains(23.0));
+        assert!(night.contains(2.0));
+        assert!(night.contains(26.0));
+        assert!(!night.contains(12.0));
+    }
+
+    #[test]
+    fn test_beds_fade_by_zone() {
+        let mut manager = manager();
+        let forest = Vec3::new(50.0, 0.0, 50.0);
+        manager.update(forest, 12.0, 0.5);
+        assert_eq!(manager.current_zone(), Some("forest"));
+        assert!((weight(&manager, "wind") - 0.5).abs() < 1e-5);
+        assert_eq!(weight(&manager, "drips"), 0.0);
+
+        // The cave is nested inside the forest and wins
+        let cave = Vec3::splat(5.0);
+        manager.update(cave, 12.0, 0.25);
+        assert_eq!(manager.current_zone(), Some("cave"));
+        assert!((weight(&manager, "wind") - 0.25).abs() < 1e-5);
+        assert!((weight(&manager, "drips") - 0.25).abs() < 1e-5);
+
+        manager.update(cave, 12.0, 10.0);
+        assert_eq!(weight(&manager, "wind"), 0.0);
+        assert_eq!(weight(&manager, "drips"), 1.0);
+    }
+
+    #[test]
+    fn test_beds_follow_time_of_day() {
+        let mut manager = manager();
+        manager.update(Vec3::ZERO, 12.0, 0.1);
+        assert_eq!(weight(&manager, "crickets"), 0.0);
+        manager.update(Vec3::ZERO, 22.0, 0.1);
+        assert_eq!(weight(&manager, "crickets"), 1.0);
+        manager.update(Vec3::ZERO, 6.0, 0.1);
+        assert_eq!(weight(&manager, "crickets"), 0.0);
+    }
+
+    #[test]
+    fn test_sweetener_spawn_timing_and_distance() {
+        let mut manager = AmbienceManager::new().with_seed(7);
+        manager.add_sweetener(
+            Sweetener::new(vec!["bird".into(), "owl".into()], 0.8)
+                .with_interval(2.0, 4.0)
+                .with_distance(10.0, 20.0)
+                .with_height(1.0, 3.0),
+        );
+
+        let listener = Vec3::new(5.0, 0.0, -5.0);
+        let dt = 0.1;
+        let mut times = Vec::new();
+        for step in 1..=600 {
+            for spawn in manager.update(listener, 12.0, dt) {
+                times.push(step as f32 * dt);
+                let offset = spawn.position - listener;
+                let distance = offset.with_y(0.0).length();
+                assert!((10.0 - 1e-3..=20.0 + 1e-3).contains(&distance));
+                assert!((1.0..=3.0).contains(&offset.y));
+                assert!(spawn.sound == "bird" || spawn.sound == "owl");
+                assert_eq!(spawn.volume, 0.8);
+            }
+        }
+
+        // 60 seconds at one spawn every 2-4 seconds
+        assert!((15..=30).contains(&times.len()), "{} spawns", times.len());
+        assert!(times[0] <= 4.0 + dt);
+        for pair in times.windows(2) {
+            let gap = pair[1] - pair[0];
+            assert!(gap >= 2.0 - dt && gap <= 4.0 + dt, "gap {gap}");
+        }
+    }
+
+    #[test]
+    fn test_random_stays_below_one() {
+        let mut manager = AmbienceManager::new().with_seed(0xFFFF_FFFF);
+        for _ in 0..10_000 {
+            let value = manager.random();
+            assert!((0.0..1.0).contains(&value));
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Bus level and spectrum analysis
+//!
+//! Every bus keeps its most recent output in a small ring buffer. Games
+//! copy it out once a frame with `AudioManager::analyze_bus` to drive VU
+//! meters, audio-reactive visuals or simple beat detection.
+
+use std::f32::consts::{PI, TAU};
+
+/// Samples analyzed per update; also the FFT size
+pub const ANALYSIS_WINDOW: usize = 1024;
+
+/// Ring buffer of recent mono output, written by the audio thread
+pub(super) struct SampleTap {
+    /// Most recent samples
+    samples: Box<[f32; ANALYSIS_WINDOW]>,
+    /// Next index to write
+    cursor: usize,
+}
+
+impl SampleTap {
+    /// Create a silent tap
+    pub(super) fn new() -> Self {
+        Self {
+            samples: Box::new([0.0; ANALYSIS_WINDOW]),
+            cursor: 0,
+        }
+    }
+
+    /// Append interleaved stereo samples, mixed to mono
+    pub(super) fn push(&mut self, block: &[f32]) {
+        for frame in block.chunks_exact(2) {
+            self.samples[self.cursor] = (frame[0] + frame[1]) * 0.5;
+            self.cursor = (self.cursor + 1) % ANALYSIS_WINDOW;
+        }
+    }
+
+    /// Copy the window out, oldest sample first
+    fn copy_to(&self, out: &mut [f32]) {
+        let (newer, older) = self.samples.split_at(self.cursor);
+        out[..older.len()].copy_from_slice(older);
+        out[older.len()..].copy_from_slice(newer);
+    }
+}
+
+/// Level and spectrum of a bus's recent output
+#[derive(Debug, Clone)]
+pub struct BusAnalysis {
+    /// Root mean square level
+    rms: f32,
+    /// Largest absolute sample
+    peak: f32,
+    /// Magnitude of each frequency bin, up to half the sample rate
+    bins: Vec<f32>,
+    /// Sample rate the bins were computed at
+    sample_rate: u32,
+    /// Analyzed window, reused between updates
+    window: Vec<f32>,
+    /// Imaginary FFT scratch, reused between updates
+    imaginary: Vec<f32>,
+}
+
+impl BusAnalysis {
+    /// Create an empty analysis to update every frame
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            rms: 0.0,
+            peak: 0.0,
+            bins: vec![0.0; ANALYSIS_WINDOW / 2],
+            sample_rate: 1,
+            window: vec![0.0; ANALYSIS_WINDOW],
+            imaginary: vec![0.0; ANALYSIS_WINDOW],
+        }
+    }
+
+    /// Root mean square level (0..1 for unclipped output)
+    #[must_use]
+    pub const fn rms(&self) -> f32 {
+        self.rms
+    }
+
+    /// RMS level in decibels, floored at -100 dB for silence
+    #[must_use]
+    pub fn rms_db(&self) -> f32 {
+        (20.0 * self.rms.log10()).max(-100.0)
+    }
+
+    /// Largest absolute sample in the window
+    #[must_use]
+    pub const fn peak(&self) -> f32 {
+        self.peak
+    }
+
+    /// Magnitude of each frequency bin, lowest first
+    #[must_use]
+    pub fn bins(&self) -> &[f32] {
+        &self.bins
+    }
+
+    /// Center frequency of a bin in Hz
+    #[must_use]
+    pub fn bin_frequency(&self, bin: usize) -> f32 {
+        bin as f32 * self.sample_rate as f32 / ANALYSIS_WINDOW as f32
+    }
+
+    /// Average magnitude between two frequencies, e.g. 20..150 Hz for bass
+    #[must_use]
+    pub fn band(&self, low: f32, high: f32) -> f32 {
+        let per_bin = self.sample_rate as f32 / ANALYSIS_WINDOW as f32;
+        let first = ((low / per_bin).floor().max(0.0) as usize).min(self.bins.len());
+        let last = ((high / per_bin).ceil().max(0.0) as usize).clamp(first, self.bins.len());
+        let band = &self.bins[first..last];
+        if band.is_empty() {
+            return 0.0;
+        }
+        band.iter().sum::<f32>() / band.len() as f32
+    }
+
+    /// Recompute levels and bins from a tap
+    pub(super) fn update(&mut self, tap: &SampleTap, sample_rate: u32) {
+        tap.copy_to(&mut self.window);
+        self.sample_rate = sample_rate.max(1);
+        self.analyze();
+    }
+
+    /// Compute levels and bins from the copied window
+    fn analyze(&mut self) {
+        let mut sum = 0.0;
+        self.peak = 0.0;
+        for sample in &self.window {
+            sum += sample * sample;
+            self.peak = self.peak.max(sample.abs());
+        }
+        self.rms = (sum / ANALYSIS_WINDOW as f32).sqrt();
+
+        // Hann window to limit leakage between bins
+        for (i, sample) in self.window.iter_mut().enumerate() {
+            *sample *= 0.5 - 0.5 * (TAU * i as f32 / (ANALYSIS_WINDOW - 1) as f32).cos();
+        }
+        self.imaginary.fill(0.0);
+        fft(&mut self.window, &mut self.imaginary);
+
+        // Normalized so a full-scale sine peaks near 1.0
+        let scale = 4.0 / ANALYSIS_WINDOW as f32;
+        for (bin, magnitude) in self.bins.iter_mut().enumerate() {
+            let (re, im) = (self.window[bin], self.imaginary[bin]);
+            *magnitude = (re * re + im * im).sqrt() * scale;
+        }
+    }
+}
+
+impl Default for BusAnalysis {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// In-place iterative radix-2 FFT; lengths must be a power of two
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f32;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let tr = re[b] * cos - im[b] * sin;
+                let ti = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - tr;
+                im[b] = im[a] - ti;
+                re[a] += tr;
+                im[a] += ti;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sine_levels_and_peak_bin() {
+        let sample_rate = 8192;
+        let mut tap = SampleTap::new();
+        let block: Vec<f32> = (0..ANALYSIS_WINDOW)
+            .flat_map(|i| {
+                let value = (TAU * 1000.0 * i as f32 / sample_rate as f32).sin();
+                [value, value]
+            })
+            .collect();
+        tap.push(&block);
+
+        let mut analysis = BusAnalysis::new();
+        analysis.update(&tap, sample_rate);
+        assert!((analysis.rms() - std::f32::consts::FRAC_1_SQRT_2).abs() < 0.01);
+        assert!((analysis.peak() - 1.0).abs() < 0.01);
+
+        let loudest = (0..analysis.bins().len())
+            .max_by(|&a, &b| analysis.bins()[a].total_cmp(&analysis.bins()[b]))
+            .unwrap();
+        assert!((analysis.bin_frequency(loudest) - 1000.0).abs() < 10.0);
+        assert!(analysis.band(900.0, 1100.0) > analysis.band(3000.0, 3500.0));
+    }
+}
@@ -0,0 +1,349 @@
+//! Mixer buses
+//!
+//! Sounds play into one of a fixed set of buses. Each bus is a submix with
+//! its own volume, mute and effect slots, and feeds the master bus, so
+//! options menus can balance music, effects, UI and dialogue separately.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use rodio::Source;
+use rodio::mixer::{self, Mixer, MixerSource};
+use serde::{Deserialize, Serialize};
+
+use super::analysis::{BusAnalysis, SampleTap};
+use super::instance::AtomicF32;
+
+/// Number of effect slots on each bus
+pub const EFFECT_SLOTS: usize = 4;
+
+/// Frames processed per effect block
+const BLOCK_FRAMES: usize = 256;
+
+/// A mixer bus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Bus {
+    /// Final mix; every other bus feeds it
+    Master,
+    /// Background music
+    Music,
+    /// Sound effects
+    Sfx,
+    /// Interface sounds
+    Ui,
+    /// Dialogue and voice-over
+    Voice,
+}
+
+impl Bus {
+    /// Every bus, master first
+    pub const ALL: [Self; 5] = [Self::Master, Self::Music, Self::Sfx, Self::Ui, Self::Voice];
+
+    /// Bus this one mixes into, or `None` for the master bus
+    #[must_use]
+    pub const fn parent(self) -> Option<Self> {
+        match self {
+            Self::Master => None,
+            _ => Some(Self::Master),
+        }
+    }
+
+    /// Display name
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Master => "Master",
+            Self::Music => "Music",
+            Self::Sfx => "SFX",
+            Self::Ui => "UI",
+            Self::Voice => "Voice",
+        }
+    }
+
+    /// Position in [`Bus::ALL`]
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Stereo processor placed in a bus effect slot
+///
+/// Runs on the audio thread, one frame at a time.
+pub trait AudioEffect: Send {
+    /// Process one stereo frame
+    fn process(&mut self, frame: [f32; 2]) -> [f32; 2];
+
+    /// Adapt to the bus sample rate; called when placed in a slot
+    fn prepare(&mut self, _sample_rate: u32) {}
+
+    /// Clear internal state such as delay lines
+    fn reset(&mut self) {}
+
+    /// Change a named parameter, returning `false` if it is unknown
+    fn set_param(&mut self, _param: &str, _value: f32) -> bool {
+        false
+    }
+}
+
+/// Effect slots of one bus
+type EffectSlots = [Option<Box<dyn AudioEffect>>; EFFECT_SLOTS];
+
+/// Bus settings shared with the audio thread
+struct BusShared {
+    /// Bus volume
+    volume: AtomicF32,
+    /// Whether the bus is silenced
+    muted: AtomicBool,
+    /// Effects applied in slot order
+    effects: Mutex<EffectSlots>,
+    /// Recent output for analysis
+    tap: Mutex<SampleTap>,
+}
+
+impl BusShared {
+    /// Apply effects, volume and mute to interleaved stereo samples
+    fn process(&self, block: &mut [f32]) {
+        {
+            let mut effects = self.effects.lock().unwrap_or_else(PoisonError::into_inner);
+            for effect in effects.iter_mut().flatten() {
+                for frame in block.chunks_exact_mut(2) {
+                    let [left, right] = effect.process([frame[0], frame[1]]);
+                    frame[0] = left;
+                    frame[1] = right;
+                }
+            }
+        }
+
+        let gain = if self.muted.load(Ordering::Relaxed) {
+            0.0
+        } else {
+            self.volume.load()
+        };
+        for sample in block.iter_mut() {
+            *sample *= gain;
+        }
+
+        // Skip a block rather than stall the audio thread on a reader
+        if let Ok(mut tap) = self.tap.try_lock() {
+            tap.push(block);
+        }
+    }
+}
+
+/// One bus: its input mixer and shared settings
+struct BusChannel {
+    /// Mixer that sounds on this bus are added to
+    input: Mixer,
+    /// Settings read by the bus source
+    shared: Arc<BusShared>,
+}
+
+/// The bus graph feeding the output device
+pub(super) struct BusMixer {
+    /// Buses in [`Bus::ALL`] order
+    buses: Vec<BusChannel>,
+    /// Sample rate of every bus
+    sample_rate: u32,
+}
+
+impl BusMixer {
+    /// Build the bus graph and attach the master bus to `output`
+    pub(super) fn new(output: &Mixer, sample_rate: u32) -> Self {
+        let mut buses: Vec<BusChannel> = Vec::with_capacity(Bus::ALL.len());
+        for bus in Bus::ALL {
+            let (input, source) = mixer::mixer(2, sample_rate);
+            let shared = Arc::new(BusShared {
+                volume: AtomicF32::new(1.0),
+                muted: AtomicBool::new(false),
+                effects: Mutex::new(Default::default()),
+                tap: Mutex::new(SampleTap::new()),
+            });
+            let bus_source = BusSource::new(source, Arc::clone(&shared));
+            match bus.parent() {
+                Some(parent) => buses[parent.index()].input.add(bus_source),
+                None => output.add(bus_source),
+            }
+            buses.push(BusChannel { input, shared });
+        }
+        Self { buses, sample_rate }
+    }
+
+    /// Output sample rate
+    pub(super) const fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Mixer that sounds on `bus` are added to
+    pub(super) fn input(&self, bus: Bus) -> &Mixer {
+        &self.buses[bus.index()].input
+    }
+
+    /// Set the volume of a bus
+    pub(super) fn set_volume(&self, bus: Bus, volume: f32) {
+        self.buses[bus.index()].shared.volume.store(volume.max(0.0));
+    }
+
+    /// Get the volume of a bus
+    pub(super) fn volume(&self, bus: Bus) -> f32 {
+        self.buses[bus.index()].shared.volume.load()
+    }
+
+    /// Mute or unmute a bus
+    pub(super) fn set_muted(&self, bus: Bus, muted: bool) {
+        self.buses[bus.index()]
+            .shared
+            .muted
+            .store(muted, Ordering::Relaxed);
+    }
+
+    /// Check if a bus is muted
+    pub(super) fn is_muted(&self, bus: Bus) -> bool {
+        self.buses[bus.index()].shared.muted.load(Ordering::Relaxed)
+    }
+
+    /// Place an effect in a slot, returning the previous one
+    pub(super) fn set_effect(
+        &self,
+        bus: Bus,
+        slot: usize,
+        mut effect: Option<Box<dyn AudioEffect>>,
+    ) -> Option<Box<dyn AudioEffect>> {
+        if let Some(effect) = &mut effect {
+            effect.prepare(self.sample_rate);
+        }
+        let mut effects = self.buses[bus.index()]
+            .shared
+            .effects
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        effects
+            .get_mut(slot)
+            .and_then(|current| std::mem::replace(current, effect))
+    }
+
+    /// Update `analysis` from the recent output of a bus
+    pub(super) fn analyze(&self, bus: Bus, analysis: &mut BusAnalysis) {
+        let tap = self.buses[bus.index()]
+            .shared
+            .tap
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        analysis.update(&tap, self.sample_rate);
+    }
+
+    /// Change a parameter of the effect in a slot
+    pub(super) fn set_effect_param(&self, bus: Bus, slot: usize, param: &str, value: f32) -> bool {
+        let mut effects = self.buses[bus.index()]
+            .shared
+            .effects
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        effects
+            .get_mut(slot)
+            .and_then(Option::as_mut)
+            .is_some_and(|effect| effect.set_param(param, value))
+    }
+}
+
+/// Source reading a bus submix and applying its settings
+struct BusSource {
+    /// Submix of the sounds on the bus
+    input: MixerSource,
+    /// Bus settings
+    shared: Arc<BusShared>,
+    /// Processed interleaved samples
+    block: Vec<f32>,
+    /// Next sample of `block` to emit
+    cursor: usize,
+}
+
+impl BusSource {
+    /// Wrap a submix
+    fn new(input: MixerSource, shared: Arc<BusShared>) -> Self {
+        Self {
+            input,
+            shared,
+            block: vec![0.0; BLOCK_FRAMES * 2],
+            cursor: BLOCK_FRAMES * 2,
+        }
+    }
+}
+
+impl Iterator for BusSource {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.cursor >= self.block.len() {
+            // An empty submix keeps the bus alive with silence
+            for sample in &mut self.block {
+                *sample = self.input.next().unwrap_or(0.0);
+            }
+            self.shared.process(&mut self.block);
+            self.cursor = 0;
+        }
+        let sample = self.block[self.cursor];
+        self.cursor += 1;
+        Some(sample)
+    }
+}
+
+impl Source for BusSource {
+    fn current_span_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> rodio::ChannelCount {
+        2
+    }
+
+    fn sample_rate(&self) -> rodio::SampleRate {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Swaps the left and right channels
+    struct SwapChannels;
+
+    impl AudioEffect for SwapChannels {
+        fn process(&mut self, [left, right]: [f32; 2]) -> [f32; 2] {
+            [right, left]
+        }
+    }
+
+    #[test]
+    fn test_bus_hierarchy() {
+        assert_eq!(Bus::Master.parent(), None);
+        for bus in &Bus::ALL[1..] {
+            assert_eq!(bus.parent(), Some(Bus::Master));
+        }
+    }
+
+    #[test]
+    fn test_bus_applies_effects_and_volume() {
+        let mut effects: EffectSlots = Default::default();
+        effects[1] = Some(Box::new(SwapChannels));
+        let shared = BusShared {
+            volume: AtomicF32::new(0.5),
+            muted: AtomicBool::new(false),
+            effects: Mutex::new(effects),
+            tap: Mutex::new(SampleTap::new()),
+        };
+
+        let mut block = [1.0, 0.0, 0.5, 0.25];
+        shared.process(&mut block);
+        assert_eq!(block, [0.0, 0.5, 0.125, 0.25]);
+
+        shared.muted.store(true, Ordering::Relaxed);
+        shared.process(&mut block);
+        assert_eq!(block, [0.0; 4]);
+    }
+}
@@ -0,0 +1,186 @@
+//! Decoded audio clips
+//!
+//! Clips are decoded once into interleaved samples and shared between every
+//! instance playing them, so short effects can be fired many times a frame
+//! without touching the disk or the decoder.
+//!
+//! A clip can carry a loop region, so looping instances play an intro once
+//! and then repeat only the frames between the loop points.
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rodio::{Decoder, Source};
+
+use super::source::AudioError;
+
+/// Handle to a clip loaded into the `AudioManager`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClipHandle(pub(super) u32);
+
+/// Fully decoded, interleaved audio samples
+#[derive(Debug, Clone)]
+pub struct AudioClip {
+    /// Interleaved samples
+    samples: Arc<[f32]>,
+    /// Number of interleaved channels
+    channels: u16,
+    /// Frames per second
+    sample_rate: u32,
+    /// First frame of the loop region
+    loop_start: usize,
+    /// Frame the loop wraps at (exclusive); `None` loops at the end
+    loop_end: Option<usize>,
+}
+
+impl AudioClip {
+    /// Create a clip from interleaved samples
+    #[must_use]
+    pub fn from_samples(samples: impl Into<Arc<[f32]>>, channels: u16, sample_rate: u32) -> Self {
+        Self {
+            samples: samples.into(),
+            channels: channels.max(1),
+            sample_rate: sample_rate.max(1),
+            loop_start: 0,
+            loop_end: None,
+        }
+    }
+
+    /// Loop between two frames instead of over the whole clip
+    ///
+    /// Looping instances play up to `end` once, then wrap back to `start`
+    /// on the exact frame. Points are clamped to the clip and an empty
+    /// region falls back to looping the whole clip.
+    #[must_use]
+    pub fn with_loop_region(mut self, start: usize, end: usize) -> Self {
+        let frames = self.frames();
+        let end = end.min(frames);
+        if start < end {
+            self.loop_start = start;
+            self.loop_end = Some(end);
+        } else {
+            self.loop_start = 0;
+            self.loop_end = None;
+        }
+        self
+    }
+
+    /// Loop between two points in seconds
+    #[must_use]
+    pub fn with_loop_seconds(self, start: f32, end: f32) -> Self {
+        let rate = self.sample_rate as f32;
+        self.with_loop_region(
+            (start.max(0.0) * rate) as usize,
+            (end.max(0.0) * rate) as usize,
+        )
+    }
+
+    /// Frames a looping instance repeats
+    #[must_use]
+    pub fn loop_region(&self) -> Range<usize> {
+        self.loop_start..self.loop_end.unwrap_or_else(|| self.frames())
+    }
+
+    /// Decode a WAV, OGG, MP3 or FLAC file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or decoded
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, AudioError> {
+        let file = File::open(path).map_err(|e| AudioError::IoError(e.to_string()))?;
+        Self::decode(BufReader::new(file))
+    }
+
+    /// Decode an in-memory audio file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data cannot be decoded
+    pub fn from_bytes(bytes: Arc<[u8]>) -> Result<Self, AudioError> {
+        Self::decode(std::io::Cursor::new(bytes))
+    }
+
+    /// Decode every sample from a reader
+    fn decode<R: Read + Seek + Send + Sync + 'static>(reader: R) -> Result<Self, AudioError> {
+        let decoder = Decoder::new(reader).map_err(|e| AudioError::DecodeError(e.to_string()))?;
+        let channels = decoder.channels();
+        let sample_rate = decoder.sample_rate();
+        let samples: Vec<f32> = decoder.collect();
+        Ok(Self::from_samples(samples, channels, sample_rate))
+    }
+
+    /// Interleaved samples
+    #[must_use]
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    /// Number of interleaved channels
+    #[must_use]
+    pub const fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Frames per second
+    #[must_use]
+    pub const fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Number of frames (samples per channel)
+    #[must_use]
+    pub fn frames(&self) -> usize {
+        self.samples.len() / self.channels as usize
+    }
+
+    /// Length of the clip
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.frames() as f64 / f64::from(self.sample_rate))
+    }
+
+    /// Get the left/right sample pair of a frame
+    ///
+    /// Mono clips play on both sides; channels past the second are dropped.
+    #[must_use]
+    pub fn frame(&self, index: usize) -> Option<(f32, f32)> {
+        let channels = self.channels as usize;
+        let start = index.checked_mul(channels)?;
+        let frame = self.samples.get(start..start + channels)?;
+        Some(match frame {
+            [mono] => (*mono, *mono),
+            [left, right, ..] => (*left, *right),
+            [] => (0.0, 0.0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clip_frames() {
+        let clip = AudioClip::from_samples(vec![0.1, 0.2, 0.3, 0.4], 2, 4);
+        assert_eq!(clip.frames(), 2);
+        assert_eq!(clip.frame(1), Some((0.3, 0.4)));
+        assert_eq!(clip.frame(2), None);
+        assert_eq!(clip.duration(), Duration::from_millis(500));
+
+        let mono = AudioClip::from_samples(vec![0.5], 1, 44_100);
+        assert_eq!(mono.frame(0), Some((0.5, 0.5)));
+    }
+
+    #[test]
+    fn test_loop_region_clamps() {
+        let clip = AudioClip::from_samples(vec![0.0; 8], 1, 4);
+        assert_eq!(clip.loop_region(), 0..8);
+        assert_eq!(clip.clone().with_loop_region(2, 20).loop_region(), 2..8);
+        assert_eq!(clip.clone().with_loop_region(5, 5).loop_region(), 0..8);
+        assert_eq!(clip.with_loop_seconds(0.5, 1.5).loop_region(), 2..6);
+    }
+}
@@ -0,0 +1,61 @@
+//! Doppler pitch shifting
+//!
+//! Sounds approaching the listener play higher and receding sounds lower.
+//! Velocities usually come from physics bodies, e.g.
+//! `physics.get_linear_velocity(body)`.
+
+use glam::Vec3;
+
+/// Speed of sound in air, in units (meters) per second
+pub const SPEED_OF_SOUND: f32 = 343.0;
+
+/// Lowest and highest pitch multiplier doppler can produce
+const DOPPLER_RANGE: (f32, f32) = (0.25, 4.0);
+
+/// Pitch multiplier for a moving source heard by a moving listener
+///
+/// `scale` exaggerates (> 1) or softens (< 1) the effect; 0 disables it.
+#[must_use]
+pub fn doppler_factor(
+    source_position: Vec3,
+    source_velocity: Vec3,
+    listener_position: Vec3,
+    listener_velocity: Vec3,
+    speed_of_sound: f32,
+    scale: f32,
+) -> f32 {
+    let Some(direction) = (source_position - listener_position).try_normalize() else {
+        return 1.0;
+    };
+    if scale <= 0.0 || speed_of_sound <= 0.0 {
+        return 1.0;
+    }
+
+    // Speeds along the line between them; staying below the speed of sound
+    // keeps the formula finite.
+    let limit = speed_of_sound * 0.95;
+    let source_away = (source_velocity.dot(direction) * scale).clamp(-limit, limit);
+    let listener_toward = (listener_velocity.dot(direction) * scale).clamp(-limit, limit);
+    ((speed_of_sound + listener_toward) / (speed_of_sound + source_away))
+        .clamp(DOPPLER_RANGE.0, DOPPLER_RANGE.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doppler_factor() {
+        let listener = Vec3::ZERO;
+        let source = Vec3::new(10.0, 0.0, 0.0);
+        let toward = Vec3::new(-30.0, 0.0, 0.0);
+
+        let approaching = doppler_factor(source, toward, listener, Vec3::ZERO, SPEED_OF_SOUND, 1.0);
+        let receding = doppler_factor(source, -toward, listener, Vec3::ZERO, SPEED_OF_SOUND, 1.0);
+        assert!(approaching > 1.0);
+        assert!(receding < 1.0);
+
+        let disabled = doppler_factor(source, toward, listener, Vec3::ZERO, SPEED_OF_SOUND, 0.0);
+        assert_eq!(disabled, 1.0);
+    }
+}
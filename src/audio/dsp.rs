@@ -0,0 +1,362 @@
+//! Built-in bus effects
+//!
+//! A biquad low-pass filter for muffling (underwater, behind walls) and a
+//! Freeverb-style reverb with room presets. Both are tuned at runtime through
+//! `AudioManager::set_bus_effect_param`.
+
+use std::f32::consts::PI;
+
+use super::bus::AudioEffect;
+
+/// Sample rate effects are built for before `prepare` is called
+const DEFAULT_SAMPLE_RATE: u32 = 44_100;
+
+/// Biquad filter state for one channel
+#[derive(Debug, Clone, Copy, Default)]
+struct BiquadState {
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+/// Low-pass filter removing frequencies above a cutoff
+///
+/// Parameters: `cutoff` (Hz) and `resonance` (Q, 0.707 = flat).
+#[derive(Debug, Clone)]
+pub struct LowPass {
+    /// Cutoff frequency in Hz
+    cutoff: f32,
+    /// Filter Q
+    resonance: f32,
+    /// Output sample rate
+    sample_rate: u32,
+    /// Normalized coefficients (b0, b1, b2, a1, a2)
+    coefficients: [f32; 5],
+    /// Left and right channel state
+    state: [BiquadState; 2],
+}
+
+impl LowPass {
+    /// Create a filter with a cutoff in Hz and a flat response
+    #[must_use]
+    pub fn new(cutoff: f32) -> Self {
+        let mut filter = Self {
+            cutoff,
+            resonance: std::f32::consts::FRAC_1_SQRT_2,
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            coefficients: [1.0, 0.0, 0.0, 0.0, 0.0],
+            state: [BiquadState::default(); 2],
+        };
+        filter.update_coefficients();
+        filter
+    }
+
+    /// Set the resonance (Q)
+    #[must_use]
+    pub fn with_resonance(mut self, resonance: f32) -> Self {
+        self.resonance = resonance;
+        self.update_coefficients();
+        self
+    }
+
+    /// Get the cutoff frequency in Hz
+    #[must_use]
+    pub fn cutoff(&self) -> f32 {
+        self.cutoff
+    }
+
+    /// Set the cutoff frequency in Hz
+    pub fn set_cutoff(&mut self, cutoff: f32) {
+        self.cutoff = cutoff;
+        self.update_coefficients();
+    }
+
+    /// Recompute coefficients for the cutoff, Q and sample rate
+    fn update_coefficients(&mut self) {
+        let rate = self.sample_rate as f32;
+        let cutoff = self.cutoff.clamp(10.0, rate * 0.45);
+        let q = self.resonance.max(0.1);
+        let w0 = 2.0 * PI * cutoff / rate;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / (2.0 * q);
+        let a0 = 1.0 + alpha;
+        let b1 = (1.0 - cos) / a0;
+        self.coefficients = [b1 * 0.5, b1, b1 * 0.5, -2.0 * cos / a0, (1.0 - alpha) / a0];
+    }
+
+    /// Filter one sample of a channel
+    fn filter(&mut self, channel: usize, x: f32) -> f32 {
+        let [b0, b1, b2, a1, a2] = self.coefficients;
+        let s = &mut self.state[channel];
+        let y = b0 * x + b1 * s.x1 + b2 * s.x2 - a1 * s.y1 - a2 * s.y2;
+        s.x2 = s.x1;
+        s.x1 = x;
+        s.y2 = s.y1;
+        s.y1 = y;
+        y
+    }
+}
+
+impl AudioEffect for LowPass {
+    fn process(&mut self, [left, right]: [f32; 2]) -> [f32; 2] {
+        [self.filter(0, left), self.filter(1, right)]
+    }
+
+    fn prepare(&mut self, sample_rate: u32) {
+        self.sample_rate = sample_rate.max(1);
+        self.update_coefficients();
+        self.reset();
+    }
+
+    fn reset(&mut self) {
+        self.state = [BiquadState::default(); 2];
+    }
+
+    fn set_param(&mut self, param: &str, value: f32) -> bool {
+        match param {
+            "cutoff" => self.cutoff = value,
+            "resonance" => self.resonance = value,
+            _ => return false,
+        }
+        self.update_coefficients();
+        true
+    }
+}
+
+/// Room presets for [`Reverb`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReverbPreset {
+    /// Short, damped reflections
+    SmallRoom,
+    /// Bright, short tiled room
+    Bathroom,
+    /// Long, smooth tail
+    Hall,
+    /// Very long, dark tail
+    Cave,
+}
+
+impl ReverbPreset {
+    /// Room size, damping and wet level
+    const fn settings(self) -> (f32, f32, f32) {
+        match self {
+            Self::SmallRoom => (0.3, 0.6, 0.25),
+            Self::Bathroom => (0.5, 0.1, 0.3),
+            Self::Hall => (0.85, 0.3, 0.35),
+            Self::Cave => (0.95, 0.2, 0.45),
+        }
+    }
+}
+
+/// Feedback comb filter with damping in the loop
+#[derive(Debug, Clone)]
+struct Comb {
+    buffer: Vec<f32>,
+    index: usize,
+    store: f32,
+}
+
+impl Comb {
+    fn new(length: usize) -> Self {
+        Self {
+            buffer: vec![0.0; length.max(1)],
+            index: 0,
+            store: 0.0,
+        }
+    }
+
+    fn process(&mut self, input: f32, feedback: f32, damping: f32) -> f32 {
+        let output = self.buffer[self.index];
+        self.store = output * (1.0 - damping) + self.store * damping;
+        self.buffer[self.index] = input + self.store * feedback;
+        self.index = (self.index + 1) % self.buffer.len();
+        output
+    }
+}
+
+/// Schroeder all-pass diffuser
+#[derive(Debug, Clone)]
+struct AllPass {
+    buffer: Vec<f32>,
+    index: usize,
+}
+
+impl AllPass {
+    fn new(length: usize) -> Self {
+        Self {
+            buffer: vec![0.0; length.max(1)],
+            index: 0,
+        }
+    }
+
+    fn process(&mut self, input: f32) -> f32 {
+        let delayed = self.buffer[self.index];
+        self.buffer[self.index] = input + delayed * 0.5;
+        self.index = (self.index + 1) % self.buffer.len();
+        delayed - input
+    }
+}
+
+/// Comb delays in samples at 44.1 kHz
+const COMB_LENGTHS: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
+/// All-pass delays in samples at 44.1 kHz
+const ALLPASS_LENGTHS: [usize; 4] = [556, 441, 341, 225];
+/// Extra delay on the right channel for stereo width
+const STEREO_SPREAD: usize = 23;
+/// Input attenuation so the comb bank does not clip
+const INPUT_GAIN: f32 = 0.015;
+
+/// Freeverb-style stereo reverb
+///
+/// Parameters: `room_size`, `damping`, `wet`, `dry` and `width`, all 0-1.
+#[derive(Debug, Clone)]
+pub struct Reverb {
+    /// Tail length (0-1)
+    room_size: f32,
+    /// High-frequency absorption (0-1)
+    damping: f32,
+    /// Reverberated level
+    wet: f32,
+    /// Original signal level
+    dry: f32,
+    /// Stereo width (0 = mono tail)
+    width: f32,
+    /// Comb banks for left and right
+    combs: [Vec<Comb>; 2],
+    /// All-pass chains for left and right
+    allpasses: [Vec<AllPass>; 2],
+}
+
+impl Reverb {
+    /// Create a reverb from a room preset
+    #[must_use]
+    pub fn new(preset: ReverbPreset) -> Self {
+        let (room_size, damping, wet) = preset.settings();
+        let mut reverb = Self {
+            room_size,
+            damping,
+            wet,
+            dry: 1.0,
+            width: 1.0,
+            combs: [Vec::new(), Vec::new()],
+            allpasses: [Vec::new(), Vec::new()],
+        };
+        reverb.allocate(DEFAULT_SAMPLE_RATE);
+        reverb
+    }
+
+    /// Switch to another room preset, keeping dry level and width
+    pub fn set_preset(&mut self, preset: ReverbPreset) {
+        (self.room_size, self.damping, self.wet) = preset.settings();
+    }
+
+    /// Set the wet and dry levels
+    #[must_use]
+    pub fn with_mix(mut self, wet: f32, dry: f32) -> Self {
+        self.wet = wet;
+        self.dry = dry;
+        self
+    }
+
+    /// Size delay lines for a sample rate
+    fn allocate(&mut self, sample_rate: u32) {
+        let scale = sample_rate as f32 / DEFAULT_SAMPLE_RATE as f32;
+        let scaled = |length: usize| (length as f32 * scale) as usize;
+        for (channel, spread) in [0, STEREO_SPREAD].into_iter().enumerate() {
+            self.combs[channel] = COMB_LENGTHS
+                .iter()
+                .map(|&length| Comb::new(scaled(length + spread)))
+                .collect();
+            self.allpasses[channel] = ALLPASS_LENGTHS
+                .iter()
+                .map(|&length| AllPass::new(scaled(length + spread)))
+                .collect();
+        }
+    }
+}
+
+impl AudioEffect for Reverb {
+    fn process(&mut self, [left, right]: [f32; 2]) -> [f32; 2] {
+        let input = (left + right) * INPUT_GAIN;
+        let feedback = self.room_size.clamp(0.0, 1.0) * 0.28 + 0.7;
+        let damping = self.damping.clamp(0.0, 1.0) * 0.4;
+
+        let mut out = [0.0; 2];
+        for (channel, value) in out.iter_mut().enumerate() {
+            let mut sum: f32 = self.combs[channel]
+                .iter_mut()
+                .map(|comb| comb.process(input, feedback, damping))
+                .sum();
+            for allpass in &mut self.allpasses[channel] {
+                sum = allpass.process(sum);
+            }
+            *value = sum;
+        }
+
+        let wet = self.wet * 3.0;
+        let wet1 = wet * (self.width * 0.5 + 0.5);
+        let wet2 = wet * ((1.0 - self.width) * 0.5);
+        [
+            out[0] * wet1 + out[1] * wet2 + left * self.dry,
+            out[1] * wet1 + out[0] * wet2 + right * self.dry,
+        ]
+    }
+
+    fn prepare(&mut self, sample_rate: u32) {
+        self.allocate(sample_rate.max(1));
+    }
+
+    fn reset(&mut self) {
+        for comb in self.combs.iter_mut().flatten() {
+            comb.buffer.fill(0.0);
+            comb.store = 0.0;
+        }
+        for allpass in self.allpasses.iter_mut().flatten() {
+            allpass.buffer.fill(0.0);
+        }
+    }
+
+    fn set_param(&mut self, param: &str, value: f32) -> bool {
+        match param {
+            "room_size" => self.room_size = value,
+            "damping" => self.damping = value,
+            "wet" => self.wet = value,
+            "dry" => self.dry = value,
+            "width" => self.width = value,
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_low_pass_passes_dc_and_blocks_nyquist() {
+        let mut filter = LowPass::new(500.0);
+        let mut dc = 0.0;
+        let mut nyquist = 0.0;
+        for i in 0..2000 {
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+            [dc, nyquist] = filter.process([1.0, sign]);
+        }
+        assert!((dc - 1.0).abs() < 0.01);
+        assert!(nyquist.abs() < 0.01);
+        assert!(filter.set_param("cutoff", 1000.0));
+        assert!(!filter.set_param("unknown", 1.0));
+    }
+
+    #[test]
+    fn test_reverb_tail_outlasts_impulse() {
+        let mut reverb = Reverb::new(ReverbPreset::Hall).with_mix(1.0, 0.0);
+        reverb.process([1.0, 1.0]);
+        let energy: f32 = (0..8000).map(|_| reverb.process([0.0, 0.0])[0].abs()).sum();
+        assert!(energy > 0.0);
+
+        reverb.reset();
+        assert_eq!(reverb.process([0.0, 0.0]), [0.0, 0.0]);
+    }
+}
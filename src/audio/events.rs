@@ -0,0 +1,296 @@
+//! Data-driven sound events
+//!
+//! Maps named events to sound cues so common hookups need no game code:
+//! animation events (footsteps) play the cue with the same name, and
+//! physics collisions play an impact cue with volume scaled by impulse.
+//! Maps are usually loaded from RON:
+//!
+//! ```ron
+//! (
+//!     cues: {
+//!         "footstep": (clips: ["sounds/step1.wav", "sounds/step2.wav"], pitch_variance: 0.1),
+//!         "impact": (clips: ["sounds/thud.wav"], min_impulse: 1.0, max_impulse: 20.0),
+//!     },
+//!     default_impact: Some("impact"),
+//! )
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::bus::Bus;
+use super::clip::ClipHandle;
+use super::instance::{PlayParams, SoundInstance};
+use super::manager::AudioManager;
+use super::source::AudioError;
+use crate::animation::AnimationEvent;
+use crate::physics::{ColliderHandle, CollisionEvent};
+
+/// How one event sounds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundCue {
+    /// Clip paths; one is picked at random per play
+    pub clips: Vec<String>,
+    /// Bus to play on
+    pub bus: Bus,
+    /// Base volume
+    pub volume: f32,
+    /// Random volume change, as a fraction of `volume`
+    pub volume_variance: f32,
+    /// Random pitch change around 1.0
+    pub pitch_variance: f32,
+    /// Impulse below which collisions stay silent
+    pub min_impulse: f32,
+    /// Impulse at which collisions reach full volume
+    pub max_impulse: f32,
+}
+
+impl SoundCue {
+    /// Create a cue playing one of `clips` on the SFX bus
+    #[must_use]
+    pub fn new(clips: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            clips: clips.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Volume factor for a collision impulse, or `None` if too soft
+    #[must_use]
+    pub fn impulse_volume(&self, impulse: f32) -> Option<f32> {
+        if impulse < self.min_impulse {
+            return None;
+        }
+        let range = self.max_impulse - self.min_impulse;
+        if range <= 0.0 {
+            return Some(1.0);
+        }
+        Some(((impulse - self.min_impulse) / range).clamp(0.0, 1.0))
+    }
+}
+
+impl Default for SoundCue {
+    fn default() -> Self {
+        Self {
+            clips: Vec::new(),
+            bus: Bus::Sfx,
+            volume: 1.0,
+            volume_variance: 0.0,
+            pitch_variance: 0.0,
+            min_impulse: 0.0,
+            max_impulse: 0.0,
+        }
+    }
+}
+
+/// Event names mapped to cues
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundEventMap {
+    /// Cues by event name
+    pub cues: HashMap<String, SoundCue>,
+    /// Cue for collisions without a per-collider cue
+    pub default_impact: Option<String>,
+}
+
+impl SoundEventMap {
+    /// Parse a map from RON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a valid map
+    pub fn from_ron(text: &str) -> Result<Self, AudioError> {
+        ron::from_str(text).map_err(|e| AudioError::DecodeError(e.to_string()))
+    }
+
+    /// Load a map from a RON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AudioError> {
+        let text = std::fs::read_to_string(path).map_err(|e| AudioError::IoError(e.to_string()))?;
+        Self::from_ron(&text)
+    }
+
+    /// Add a cue
+    #[must_use]
+    pub fn with_cue(mut self, name: impl Into<String>, cue: SoundCue) -> Self {
+        self.cues.insert(name.into(), cue);
+        self
+    }
+}
+
+/// A cue with its clips loaded
+#[derive(Debug, Clone)]
+struct LoadedCue {
+    /// Cue settings
+    cue: SoundCue,
+    /// Loaded clip variants
+    clips: Vec<ClipHandle>,
+}
+
+/// Plays cues for animation and collision events
+#[derive(Debug)]
+pub struct AudioEvents {
+    /// Loaded cues by event name
+    cues: HashMap<String, LoadedCue>,
+    /// Cue for collisions without a per-collider cue
+    default_impact: Option<String>,
+    /// Per-collider impact cues, e.g. metal crates
+    collider_cues: HashMap<ColliderHandle, String>,
+    /// Random state for variants and variance
+    seed: u32,
+}
+
+impl AudioEvents {
+    /// Load every clip referenced by `map`
+    ///
+    /// Clips shared between cues are loaded once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a clip cannot be loaded
+    pub fn load(map: SoundEventMap, audio: &mut AudioManager) -> Result<Self, AudioError> {
+        let mut loaded: HashMap<String, ClipHandle> = HashMap::new();
+        let mut cues = HashMap::with_capacity(map.cues.len());
+        for (name, cue) in map.cues {
+            let mut clips = Vec::with_capacity(cue.clips.len());
+            for path in &cue.clips {
+                let handle = match loaded.get(path) {
+                    Some(handle) => *handle,
+                    None => {
+                        let handle = audio.load_clip(path)?;
+                        loaded.insert(path.clone(), handle);
+                        handle
+                    }
+                };
+                clips.push(handle);
+            }
+            cues.insert(name, LoadedCue { cue, clips });
+        }
+
+        Ok(Self {
+            cues,
+            default_impact: map.default_impact,
+            collider_cues: HashMap::new(),
+            seed: 0x9E37_79B9,
+        })
+    }
+
+    /// Set the random seed used for variants and variance
+    #[must_use]
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        self.seed = seed.max(1);
+        self
+    }
+
+    /// Play a different impact cue when a collider is involved
+    pub fn set_collider_cue(&mut self, collider: ColliderHandle, cue: impl Into<String>) {
+        self.collider_cues.insert(collider, cue.into());
+    }
+
+    /// Remove a collider's impact cue
+    pub fn clear_collider_cue(&mut self, collider: ColliderHandle) {
+        self.collider_cues.remove(&collider);
+    }
+
+    /// Check if a cue is mapped
+    #[must_use]
+    pub fn has_cue(&self, name: &str) -> bool {
+        self.cues.contains_key(name)
+    }
+
+    /// Play the cue named `name` at `volume` times its base volume
+    pub fn trigger(
+        &mut self,
+        audio: &mut AudioManager,
+        name: &str,
+        volume: f32,
+    ) -> Option<SoundInstance> {
+        let cue = self.cues.get(name)?;
+        if cue.clips.is_empty() {
+            return None;
+        }
+        let (clip_roll, volume_roll, pitch_roll) = (self.random(), self.random(), self.random());
+        let cue = &self.cues[name];
+        let index = ((clip_roll * cue.clips.len() as f32) as usize).min(cue.clips.len() - 1);
+        let settings = &cue.cue;
+        let volume =
+            volume * settings.volume * (1.0 + settings.volume_variance * (volume_roll * 2.0 - 1.0));
+        let pitch = 1.0 + settings.pitch_variance * (pitch_roll * 2.0 - 1.0);
+        let params = PlayParams::new()
+            .with_bus(settings.bus)
+            .with_volume(volume.max(0.0))
+            .with_pitch(pitch);
+        audio.play_clip_with(cue.clips[index], params)
+    }
+
+    /// Play the cue named after an animation event, if any
+    pub fn on_animation_event(
+        &mut self,
+        audio: &mut AudioManager,
+        event: &AnimationEvent,
+    ) -> Option<SoundInstance> {
+        self.trigger(audio, &event.name, 1.0)
+    }
+
+    /// Play an impact cue for a collision that just started
+    ///
+    /// Uses the cue of either collider if set, otherwise the default impact
+    /// cue, scaled by the collision impulse.
+    pub fn on_collision(
+        &mut self,
+        audio: &mut AudioManager,
+        event: &CollisionEvent,
+    ) -> Option<SoundInstance> {
+        if !event.started() {
+            return None;
+        }
+        let name = self
+            .collider_cues
+            .get(&event.collider1)
+            .or_else(|| self.collider_cues.get(&event.collider2))
+            .or(self.default_impact.as_ref())?
+            .clone();
+        let volume = self.cues.get(&name)?.cue.impulse_volume(event.impulse)?;
+        self.trigger(audio, &name, volume)
+    }
+
+    /// Next random value in 0.0..1.0
+    fn random(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        (self.seed as f32) / (u32::MAX as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_from_ron() {
+        let map = SoundEventMap::from_ron(
+            r#"(
+                cues: {
+                    "footstep": (clips: ["step.wav"], pitch_variance: 0.1),
+                    "impact": (clips: ["thud.wav"], bus: Sfx, min_impulse: 1.0, max_impulse: 11.0),
+                },
+                default_impact: Some("impact"),
+            )"#,
+        )
+        .unwrap();
+        assert_eq!(map.cues["footstep"].volume, 1.0);
+        assert_eq!(map.default_impact.as_deref(), Some("impact"));
+
+        let impact = &map.cues["impact"];
+        assert_eq!(impact.impulse_volume(0.5), None);
+        assert_eq!(impact.impulse_volume(6.0), Some(0.5));
+        assert_eq!(impact.impulse_volume(50.0), Some(1.0));
+    }
+}
@@ -0,0 +1,647 @@
+//! Playing sound instances
+//!
+//! Every call to `AudioManager::play_clip` starts a new player on the
+//! mixer and returns a [`SoundInstance`] handle. The handle and the player
+//! share lock-free state, so volume, pitch, pan, fades, pause and stop take
+//! effect on the audio thread without blocking the game loop.
+//!
+//! Loop wrap-around and delayed starts are resolved per frame inside the
+//! player, so they land on the exact sample regardless of how the mixer
+//! splits its buffers.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+
+use rodio::Source;
+
+use super::PlaybackState;
+use super::bus::Bus;
+use super::clip::AudioClip;
+
+/// Slowest supported playback rate
+const MIN_PITCH: f32 = 0.01;
+
+/// Low-pass cutoff (Hz) of a fully occluded sound
+const OCCLUDED_CUTOFF: f32 = 700.0;
+
+/// Low-pass cutoff (Hz) of an unobstructed sound
+const OPEN_CUTOFF: f32 = 20_000.0;
+
+/// Volume of a fully occluded sound
+const OCCLUDED_GAIN: f32 = 0.35;
+
+/// Seconds occlusion takes to fully change, to avoid zipper noise
+const OCCLUSION_GLIDE: f32 = 0.05;
+
+/// An `f32` that can be shared with the audio thread
+#[derive(Debug)]
+pub(super) struct AtomicF32(AtomicU32);
+
+impl AtomicF32 {
+    /// Create with an initial value
+    pub(super) fn new(value: f32) -> Self {
+        Self(AtomicU32::new(value.to_bits()))
+    }
+
+    /// Read the value
+    pub(super) fn load(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    /// Replace the value
+    pub(super) fn store(&self, value: f32) {
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// How a clip instance is started
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayParams {
+    /// Bus the sound plays on
+    pub bus: Bus,
+    /// Instance volume
+    pub volume: f32,
+    /// Whether the clip loops until stopped
+    pub looping: bool,
+    /// Playback rate (1.0 = normal, 2.0 = an octave up)
+    pub pitch: f32,
+    /// Stereo position (-1.0 = left, 0.0 = center, 1.0 = right)
+    pub pan: f32,
+    /// Seconds to fade in from silence (0.0 = start at full volume)
+    pub fade_in: f32,
+    /// Seconds of silence before the clip starts
+    pub delay: f32,
+}
+
+impl PlayParams {
+    /// Play once at full volume on the SFX bus
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            bus: Bus::Sfx,
+            volume: 1.0,
+            looping: false,
+            pitch: 1.0,
+            pan: 0.0,
+            fade_in: 0.0,
+            delay: 0.0,
+        }
+    }
+
+    /// Set the bus
+    #[must_use]
+    pub const fn with_bus(mut self, bus: Bus) -> Self {
+        self.bus = bus;
+        self
+    }
+
+    /// Set the instance volume
+    #[must_use]
+    pub const fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Loop until stopped
+    #[must_use]
+    pub const fn looping(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+
+    /// Set the playback rate
+    #[must_use]
+    pub const fn with_pitch(mut self, pitch: f32) -> Self {
+        self.pitch = pitch;
+        self
+    }
+
+    /// Set the stereo position
+    #[must_use]
+    pub const fn with_pan(mut self, pan: f32) -> Self {
+        self.pan = pan;
+        self
+    }
+
+    /// Fade in from silence over `seconds`
+    #[must_use]
+    pub const fn with_fade_in(mut self, seconds: f32) -> Self {
+        self.fade_in = seconds;
+        self
+    }
+
+    /// Start after `seconds`, counted in frames on the audio thread
+    ///
+    /// Use this to line sounds up sample-accurately, e.g. a loop after its
+    /// intro or a stinger on a beat.
+    #[must_use]
+    pub const fn with_delay(mut self, seconds: f32) -> Self {
+        self.delay = seconds;
+        self
+    }
+}
+
+impl Default for PlayParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// State shared between a handle and its player
+#[derive(Debug)]
+pub(super) struct InstanceState {
+    /// Instance volume
+    volume: AtomicF32,
+    /// Playback rate
+    pitch: AtomicF32,
+    /// Doppler multiplier on top of the pitch
+    doppler: AtomicF32,
+    /// Stereo position
+    pan: AtomicF32,
+    /// How blocked the sound is (0 = clear, 1 = fully occluded)
+    occlusion: AtomicF32,
+    /// Gain the current fade moves toward
+    fade_target: AtomicF32,
+    /// Length of the current fade in seconds
+    fade_seconds: AtomicF32,
+    /// Whether playback stops once the fade reaches silence
+    fade_stops: AtomicBool,
+    /// Bumped on every new fade so the player picks it up
+    fade_generation: AtomicU32,
+    /// Whether playback is paused
+    paused: AtomicBool,
+    /// Whether playback wraps around at the end
+    looping: AtomicBool,
+    /// Set by the handle to end playback
+    stopped: AtomicBool,
+    /// Set by the player once it has ended
+    finished: AtomicBool,
+    /// Frames played so far
+    position: AtomicU64,
+}
+
+/// Handle to a playing clip
+///
+/// Dropping the handle leaves the sound playing to the end; clones control
+/// the same instance.
+#[derive(Debug, Clone)]
+pub struct SoundInstance {
+    /// State shared with the player
+    state: Arc<InstanceState>,
+    /// Sample rate of the clip, for converting positions
+    sample_rate: u32,
+}
+
+impl InstanceState {
+    /// Create state for a new instance
+    fn new(params: &PlayParams) -> Self {
+        Self {
+            volume: AtomicF32::new(params.volume.max(0.0)),
+            pitch: AtomicF32::new(params.pitch.max(MIN_PITCH)),
+            doppler: AtomicF32::new(1.0),
+            pan: AtomicF32::new(params.pan.clamp(-1.0, 1.0)),
+            occlusion: AtomicF32::new(0.0),
+            fade_target: AtomicF32::new(1.0),
+            fade_seconds: AtomicF32::new(params.fade_in.max(0.0)),
+            fade_stops: AtomicBool::new(false),
+            fade_generation: AtomicU32::new(1),
+            paused: AtomicBool::new(false),
+            looping: AtomicBool::new(params.looping),
+            stopped: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+            position: AtomicU64::new(0),
+        }
+    }
+
+    /// Reinitialize recycled state for a new instance
+    fn reset(&self, params: &PlayParams) {
+        self.volume.store(params.volume.max(0.0));
+        self.pitch.store(params.pitch.max(MIN_PITCH));
+        self.doppler.store(1.0);
+        self.pan.store(params.pan.clamp(-1.0, 1.0));
+        self.occlusion.store(0.0);
+        self.fade_target.store(1.0);
+        self.fade_seconds.store(params.fade_in.max(0.0));
+        self.fade_stops.store(false, Ordering::Relaxed);
+        self.fade_generation.store(1, Ordering::Relaxed);
+        self.paused.store(false, Ordering::Relaxed);
+        self.looping.store(params.looping, Ordering::Relaxed);
+        self.stopped.store(false, Ordering::Relaxed);
+        self.finished.store(false, Ordering::Relaxed);
+        self.position.store(0, Ordering::Relaxed);
+    }
+}
+
+impl SoundInstance {
+    /// Create a handle and the player it controls
+    ///
+    /// Reuses `recycled` state from a finished instance when given.
+    pub(super) fn start(
+        clip: &AudioClip,
+        params: &PlayParams,
+        recycled: Option<Arc<InstanceState>>,
+    ) -> (Self, ClipPlayer) {
+        let state = match recycled {
+            Some(state) => {
+                state.reset(params);
+                state
+            }
+            None => Arc::new(InstanceState::new(params)),
+        };
+        let player = ClipPlayer {
+            clip: clip.clone(),
+            state: Arc::clone(&state),
+            cursor: 0.0,
+            delay: (params.delay.max(0.0) * clip.sample_rate() as f32).round() as u64,
+            fade_gain: if params.fade_in > 0.0 { 0.0 } else { 1.0 },
+            occlusion: 0.0,
+            filter_occlusion: 0.0,
+            filter_alpha: 1.0,
+            filter_state: [0.0; 2],
+            fade_step: 0.0,
+            fade_generation: 0,
+            pending: None,
+        };
+        let instance = Self {
+            state,
+            sample_rate: clip.sample_rate(),
+        };
+        (instance, player)
+    }
+
+    /// Set the instance volume (0.0 = silent, 1.0 = normal)
+    pub fn set_volume(&self, volume: f32) {
+        self.state.volume.store(volume.max(0.0));
+    }
+
+    /// Get the instance volume
+    #[must_use]
+    pub fn volume(&self) -> f32 {
+        self.state.volume.load()
+    }
+
+    /// Set the playback rate (1.0 = normal, 2.0 = an octave up)
+    pub fn set_pitch(&self, pitch: f32) {
+        self.state.pitch.store(pitch.max(MIN_PITCH));
+    }
+
+    /// Get the playback rate
+    #[must_use]
+    pub fn pitch(&self) -> f32 {
+        self.state.pitch.load()
+    }
+
+    /// Set the doppler multiplier applied on top of the pitch
+    ///
+    /// Usually set through `AudioManager::apply_doppler`.
+    pub fn set_doppler(&self, factor: f32) {
+        self.state.doppler.store(factor.max(MIN_PITCH));
+    }
+
+    /// Get the doppler multiplier
+    #[must_use]
+    pub fn doppler(&self) -> f32 {
+        self.state.doppler.load()
+    }
+
+    /// Set the stereo position (-1.0 = left, 0.0 = center, 1.0 = right)
+    pub fn set_pan(&self, pan: f32) {
+        self.state.pan.store(pan.clamp(-1.0, 1.0));
+    }
+
+    /// Get the stereo position
+    #[must_use]
+    pub fn pan(&self) -> f32 {
+        self.state.pan.load()
+    }
+
+    /// Set how blocked the sound is (0 = clear, 1 = fully occluded)
+    ///
+    /// Occluded sounds are muffled and quieter. Usually driven by
+    /// [`Occlusion`](super::Occlusion).
+    pub fn set_occlusion(&self, occlusion: f32) {
+        self.state.occlusion.store(occlusion.clamp(0.0, 1.0));
+    }
+
+    /// Get the occlusion amount
+    #[must_use]
+    pub fn occlusion(&self) -> f32 {
+        self.state.occlusion.load()
+    }
+
+    /// Fade to full volume over `seconds`, cancelling any fade out
+    pub fn fade_in(&self, seconds: f32) {
+        self.fade_to(1.0, seconds, false);
+    }
+
+    /// Fade to silence over `seconds`, then stop
+    pub fn fade_out(&self, seconds: f32) {
+        self.fade_to(0.0, seconds, true);
+    }
+
+    /// Start a fade on the audio thread
+    fn fade_to(&self, target: f32, seconds: f32, stop: bool) {
+        self.state.fade_target.store(target);
+        self.state.fade_seconds.store(seconds.max(0.0));
+        self.state.fade_stops.store(stop, Ordering::Relaxed);
+        self.state.fade_generation.fetch_add(1, Ordering::Release);
+    }
+
+    /// Pause playback
+    pub fn pause(&self) {
+        self.state.paused.store(true, Ordering::Relaxed);
+    }
+
+    /// Resume paused playback
+    pub fn resume(&self) {
+        self.state.paused.store(false, Ordering::Relaxed);
+    }
+
+    /// Stop playback (cannot be resumed)
+    pub fn stop(&self) {
+        self.state.stopped.store(true, Ordering::Relaxed);
+    }
+
+    /// Loop the clip until stopped
+    pub fn set_looping(&self, looping: bool) {
+        self.state.looping.store(looping, Ordering::Relaxed);
+    }
+
+    /// Check if the clip loops
+    #[must_use]
+    pub fn is_looping(&self) -> bool {
+        self.state.looping.load(Ordering::Relaxed)
+    }
+
+    /// Check if playback has ended, either at the end of the clip or by `stop`
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.state.finished.load(Ordering::Relaxed) || self.state.stopped.load(Ordering::Relaxed)
+    }
+
+    /// Get the current playback state
+    #[must_use]
+    pub fn state(&self) -> PlaybackState {
+        if self.is_finished() {
+            PlaybackState::Stopped
+        } else if self.state.paused.load(Ordering::Relaxed) {
+            PlaybackState::Paused
+        } else {
+            PlaybackState::Playing
+        }
+    }
+
+    /// Give back the shared state for reuse if nothing else refers to it
+    ///
+    /// Only succeeds once the player has been dropped by the mixer and no
+    /// other handle clones are alive.
+    pub(super) fn recycle(self) -> Option<Arc<InstanceState>> {
+        (Arc::strong_count(&self.state) == 1).then_some(self.state)
+    }
+
+    /// Playback position within the clip
+    #[must_use]
+    pub fn position(&self) -> Duration {
+        let frames = self.state.position.load(Ordering::Relaxed);
+        Duration::from_secs_f64(frames as f64 / f64::from(self.sample_rate))
+    }
+}
+
+/// Mixer source streaming a clip as stereo samples
+pub(super) struct ClipPlayer {
+    /// Clip being played
+    clip: AudioClip,
+    /// State shared with the handles
+    state: Arc<InstanceState>,
+    /// Read position in frames; fractional with non-unit pitch
+    cursor: f64,
+    /// Frames of silence left before playback starts
+    delay: u64,
+    /// Current fade gain
+    fade_gain: f32,
+    /// Occlusion gliding toward the handle's value
+    occlusion: f32,
+    /// Occlusion the filter coefficient was computed for
+    filter_occlusion: f32,
+    /// One-pole low-pass coefficient for the occlusion
+    filter_alpha: f32,
+    /// Low-pass state for left and right
+    filter_state: [f32; 2],
+    /// Fade gain change per frame
+    fade_step: f32,
+    /// Last fade generation picked up from the handle
+    fade_generation: u32,
+    /// Right sample of the current frame, not yet emitted
+    pending: Option<f32>,
+}
+
+impl ClipPlayer {
+    /// Read the stereo frame at the cursor and advance it by the pitch
+    fn read_frame(&mut self) -> Option<(f32, f32)> {
+        let frames = self.clip.frames();
+        let looping = self.state.looping.load(Ordering::Relaxed);
+        let region = self.clip.loop_region();
+        if looping && !region.is_empty() && self.cursor >= region.end as f64 {
+            // Keep the fractional overshoot so the wrap is seamless
+            let overshoot = (self.cursor - region.end as f64) % region.len() as f64;
+            self.cursor = region.start as f64 + overshoot;
+        }
+        if self.cursor >= frames as f64 {
+            return None;
+        }
+
+        // Linear interpolation between neighbouring frames
+        let index = self.cursor as usize;
+        let t = (self.cursor - index as f64) as f32;
+        let (l0, r0) = self.clip.frame(index)?;
+        let next = if looping && index + 1 >= region.end {
+            region.start
+        } else if index + 1 < frames {
+            index + 1
+        } else {
+            index
+        };
+        let (l1, r1) = self.clip.frame(next)?;
+
+        self.cursor += f64::from(self.state.pitch.load() * self.state.doppler.load());
+        self.state
+            .position
+            .store(self.cursor as u64, Ordering::Relaxed);
+        Some((l0 + (l1 - l0) * t, r0 + (r1 - r0) * t))
+    }
+
+    /// Glide toward the target occlusion and muffle the frame
+    fn occlude(&mut self, left: f32, right: f32) -> (f32, f32) {
+        let target = self.state.occlusion.load();
+        let step = 1.0 / (OCCLUSION_GLIDE * self.clip.sample_rate() as f32);
+        self.occlusion += (target - self.occlusion).clamp(-step, step);
+        if self.occlusion <= f32::EPSILON {
+            self.filter_state = [left, right];
+            return (left, right);
+        }
+
+        if (self.occlusion - self.filter_occlusion).abs() > 1e-3 || self.filter_alpha >= 1.0 {
+            self.filter_occlusion = self.occlusion;
+            let cutoff = OPEN_CUTOFF * (OCCLUDED_CUTOFF / OPEN_CUTOFF).powf(self.occlusion);
+            let rate = self.clip.sample_rate() as f32;
+            self.filter_alpha = 1.0 - (-2.0 * std::f32::consts::PI * cutoff / rate).exp();
+        }
+        for (state, input) in self.filter_state.iter_mut().zip([left, right]) {
+            *state += (input - *state) * self.filter_alpha;
+        }
+        (self.filter_state[0], self.filter_state[1])
+    }
+
+    /// Pick up new fades and advance the current one by a frame
+    fn advance_fade(&mut self) {
+        let generation = self.state.fade_generation.load(Ordering::Acquire);
+        let target = self.state.fade_target.load();
+        if generation != self.fade_generation {
+            self.fade_generation = generation;
+            let frames = self.state.fade_seconds.load() * self.clip.sample_rate() as f32;
+            self.fade_step = if frames >= 1.0 {
+                (target - self.fade_gain).abs() / frames
+            } else {
+                f32::INFINITY
+            };
+        }
+
+        if self.fade_gain < target {
+            self.fade_gain = (self.fade_gain + self.fade_step).min(target);
+        } else if self.fade_gain > target {
+            self.fade_gain = (self.fade_gain - self.fade_step).max(target);
+        }
+        if self.fade_gain <= 0.0 && self.state.fade_stops.load(Ordering::Relaxed) {
+            self.state.stopped.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+impl Iterator for ClipPlayer {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if let Some(right) = self.pending.take() {
+            return Some(right);
+        }
+        if self.state.stopped.load(Ordering::Relaxed) {
+            self.state.finished.store(true, Ordering::Relaxed);
+            return None;
+        }
+        let paused = self.state.paused.load(Ordering::Relaxed);
+        if paused || self.delay > 0 {
+            if !paused {
+                self.delay -= 1;
+            }
+            self.pending = Some(0.0);
+            return Some(0.0);
+        }
+
+        let Some((left, right)) = self.read_frame() else {
+            self.state.finished.store(true, Ordering::Relaxed);
+            return None;
+        };
+        self.advance_fade();
+        let (left, right) = self.occlude(left, right);
+
+        let gain = self.state.volume.load()
+            * self.fade_gain
+            * (1.0 - self.occlusion * (1.0 - OCCLUDED_GAIN));
+        let pan = self.state.pan.load();
+        let left_gain = (1.0 - pan).min(1.0);
+        let right_gain = (1.0 + pan).min(1.0);
+        self.pending = Some(right * gain * right_gain);
+        Some(left * gain * left_gain)
+    }
+}
+
+impl Source for ClipPlayer {
+    fn current_span_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> rodio::ChannelCount {
+        2
+    }
+
+    fn sample_rate(&self) -> rodio::SampleRate {
+        self.clip.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_player_applies_volume_and_finishes() {
+        let clip = AudioClip::from_samples(vec![1.0, -1.0], 1, 10);
+        let (instance, player) = SoundInstance::start(&clip, &PlayParams::new(), None);
+        instance.set_volume(0.5);
+
+        let samples: Vec<f32> = player.collect();
+        assert_eq!(samples, vec![0.5, 0.5, -0.5, -0.5]);
+        assert!(instance.is_finished());
+        assert_eq!(instance.state(), PlaybackState::Stopped);
+    }
+
+    #[test]
+    fn test_paused_player_emits_silence() {
+        let clip = AudioClip::from_samples(vec![1.0], 1, 10);
+        let (instance, mut player) = SoundInstance::start(&clip, &PlayParams::new(), None);
+        instance.pause();
+        assert_eq!(player.next(), Some(0.0));
+        assert_eq!(player.next(), Some(0.0));
+
+        instance.resume();
+        assert_eq!(player.next(), Some(1.0));
+        instance.stop();
+        player.next();
+        assert_eq!(player.next(), None);
+    }
+
+    #[test]
+    fn test_pitch_pan_and_fade_out() {
+        let clip = AudioClip::from_samples(vec![0.0, 1.0, 2.0, 3.0], 1, 4);
+        let params = PlayParams::new().with_pitch(2.0).with_pan(1.0);
+        let (instance, player) = SoundInstance::start(&clip, &params, None);
+        let samples: Vec<f32> = player.collect();
+        assert_eq!(samples, vec![0.0, 0.0, 0.0, 2.0]);
+        assert!(instance.is_finished());
+
+        let clip = AudioClip::from_samples(vec![1.0; 8], 1, 4);
+        let (instance, player) = SoundInstance::start(&clip, &PlayParams::new(), None);
+        instance.fade_out(0.5);
+        let left: Vec<f32> = player.step_by(2).collect();
+        assert_eq!(left, vec![0.5, 0.0]);
+        assert!(instance.is_finished());
+    }
+
+    #[test]
+    fn test_occlusion_muffles_and_attenuates() {
+        let clip = AudioClip::from_samples(vec![1.0; 200], 1, 1000);
+        let (instance, player) = SoundInstance::start(&clip, &PlayParams::new(), None);
+        instance.set_occlusion(1.0);
+        let left: Vec<f32> = player.step_by(2).collect();
+        assert!(left[0] < 1.0);
+        assert!((left[199] - OCCLUDED_GAIN).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_loop_region_and_delay() {
+        let clip =
+            AudioClip::from_samples(vec![0.0, 1.0, 2.0, 3.0, 4.0], 1, 4).with_loop_region(2, 4);
+        let params = PlayParams::new().looping().with_delay(0.5);
+        let (_, player) = SoundInstance::start(&clip, &params, None);
+        let left: Vec<f32> = player.step_by(2).take(9).collect();
+        assert_eq!(left, vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 2.0, 3.0, 2.0]);
+
+        let (_, player) = SoundInstance::start(&clip, &PlayParams::new(), None);
+        assert_eq!(player.step_by(2).count(), 5);
+    }
+}
@@ -4,9 +4,20 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
 
+use glam::Vec3;
 use rodio::{OutputStream, OutputStreamBuilder, mixer::Mixer};
 
+use super::analysis::BusAnalysis;
+use super::bus::{AudioEffect, Bus, BusMixer};
+use super::clip::{AudioClip, ClipHandle};
+use super::doppler::{SPEED_OF_SOUND, doppler_factor};
+use super::instance::{PlayParams, SoundInstance};
 use super::source::{AudioError, AudioSource};
+use super::synth::Synth;
+use super::voices::{VoiceLimit, VoiceManager};
+
+/// Voice key shared by every one-off synthesized sound
+const SYNTH_CLIP: ClipHandle = ClipHandle(u32::MAX);
 
 /// Manages audio output and all audio sources
 pub struct AudioManager {
@@ -18,6 +29,20 @@ pub struct AudioManager {
     sources: HashMap<String, AudioSource>,
     /// Per-source volume settings (before master volume applied)
     source_volumes: HashMap<String, f32>,
+    /// Decoded clips, indexed by handle
+    clips: Vec<AudioClip>,
+    /// Buses that clips play on
+    buses: BusMixer,
+    /// Voice limits and instance pooling
+    voices: VoiceManager,
+    /// Listener position for spatial effects
+    listener_position: Vec3,
+    /// Listener velocity for doppler
+    listener_velocity: Vec3,
+    /// Doppler exaggeration (0 disables doppler)
+    doppler_scale: f32,
+    /// Speed of sound used for doppler
+    speed_of_sound: f32,
     /// Master volume
     master_volume: f32,
     /// Whether audio is muted
@@ -36,12 +61,20 @@ impl AudioManager {
             .open_stream()
             .map_err(|_| AudioError::NoDevice)?;
         let mixer = stream.mixer().clone();
+        let buses = BusMixer::new(&mixer, stream.config().sample_rate());
 
         Ok(Self {
             _stream: stream,
             mixer,
             sources: HashMap::new(),
             source_volumes: HashMap::new(),
+            clips: Vec::new(),
+            buses,
+            voices: VoiceManager::default(),
+            listener_position: Vec3::ZERO,
+            listener_velocity: Vec3::ZERO,
+            doppler_scale: 1.0,
+            speed_of_sound: SPEED_OF_SOUND,
             master_volume: 1.0,
             muted: false,
         })
@@ -49,6 +82,8 @@ impl AudioManager {
 
     /// Load an audio file and store it with a name
     ///
+    /// Named sources stream from disk and play on the music bus.
+    ///
     /// # Errors
     ///
     /// Returns an error if the file cannot be loaded
@@ -58,7 +93,24 @@ impl AudioManager {
         path: impl AsRef<Path>,
     ) -> Result<(), AudioError> {
         let name = name.into();
-        let source = AudioSource::from_file(&self.mixer, path)?;
+        let source = AudioSource::from_file(self.buses.input(Bus::Music), path)?;
+        self.sources.insert(name.clone(), source);
+        self.source_volumes.insert(name, 1.0);
+        Ok(())
+    }
+
+    /// Load an audio file that loops forever and store it with a name
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be loaded
+    pub fn load_looping(
+        &mut self,
+        name: impl Into<String>,
+        path: impl AsRef<Path>,
+    ) -> Result<(), AudioError> {
+        let name = name.into();
+        let source = AudioSource::from_file_looping(self.buses.input(Bus::Music), path)?;
         self.sources.insert(name.clone(), source);
         self.source_volumes.insert(name, 1.0);
         Ok(())
@@ -71,19 +123,219 @@ impl AudioManager {
         bytes: Arc<[u8]>,
     ) -> Result<(), AudioError> {
         let name = name.into();
-        let source = AudioSource::from_bytes(&self.mixer, bytes, &name)?;
+        let source = AudioSource::from_bytes(self.buses.input(Bus::Music), bytes, &name)?;
         self.sources.insert(name.clone(), source);
         self.source_volumes.insert(name, 1.0);
         Ok(())
     }
 
+    /// Decode an audio file into a clip that can be played many times
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be loaded or decoded
+    pub fn load_clip(&mut self, path: impl AsRef<Path>) -> Result<ClipHandle, AudioError> {
+        let clip = AudioClip::from_file(path)?;
+        Ok(self.add_clip(clip))
+    }
+
+    /// Decode an in-memory audio file into a clip
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data cannot be decoded
+    pub fn load_clip_bytes(&mut self, bytes: Arc<[u8]>) -> Result<ClipHandle, AudioError> {
+        let clip = AudioClip::from_bytes(bytes)?;
+        Ok(self.add_clip(clip))
+    }
+
+    /// Add an already decoded clip
+    pub fn add_clip(&mut self, clip: AudioClip) -> ClipHandle {
+        self.clips.push(clip);
+        ClipHandle(self.clips.len() as u32 - 1)
+    }
+
+    /// Get a loaded clip
+    #[must_use]
+    pub fn clip(&self, handle: ClipHandle) -> Option<&AudioClip> {
+        self.clips.get(handle.0 as usize)
+    }
+
+    /// Start a new instance of a clip on the SFX bus
+    ///
+    /// Each call plays independently, so the same clip can overlap itself.
+    /// Returns `None` if the handle is unknown.
+    pub fn play_clip(&mut self, handle: ClipHandle) -> Option<SoundInstance> {
+        self.play_clip_with(handle, PlayParams::new())
+    }
+
+    /// Start a new instance of a clip with a bus, volume and looping
+    ///
+    /// Returns `None` if the handle is unknown or a voice limit rejected it.
+    pub fn play_clip_with(
+        &mut self,
+        handle: ClipHandle,
+        params: PlayParams,
+    ) -> Option<SoundInstance> {
+        let clip = self.clips.get(handle.0 as usize)?.clone();
+        self.start(handle, &clip, params)
+    }
+
+    /// Render a synthesized sound at the output rate and play it once
+    ///
+    /// The sound is rendered on every call; for sounds played often,
+    /// `add_clip(synth.render(..))` once and play the handle instead.
+    /// Returns `None` if a bus voice limit rejected it.
+    pub fn play_synth(&mut self, synth: &Synth, params: PlayParams) -> Option<SoundInstance> {
+        let clip = synth.render(self.buses.sample_rate());
+        self.start(SYNTH_CLIP, &clip, params)
+    }
+
+    /// Admit, start and track a new instance
+    fn start(
+        &mut self,
+        handle: ClipHandle,
+        clip: &AudioClip,
+        params: PlayParams,
+    ) -> Option<SoundInstance> {
+        if !self.voices.admit(handle, params.bus) {
+            return None;
+        }
+        let recycled = self.voices.recycled_state();
+        let (instance, player) = SoundInstance::start(clip, &params, recycled);
+        self.buses.input(params.bus).add(player);
+        self.voices.register(handle, params.bus, instance.clone());
+        Some(instance)
+    }
+
+    /// Limit simultaneous instances of a clip, or `None` for no limit
+    pub fn set_clip_voice_limit(&mut self, clip: ClipHandle, limit: Option<VoiceLimit>) {
+        self.voices.set_clip_limit(clip, limit);
+    }
+
+    /// Limit simultaneous instances on a bus, or `None` for no limit
+    pub fn set_bus_voice_limit(&mut self, bus: Bus, limit: Option<VoiceLimit>) {
+        self.voices.set_bus_limit(bus, limit);
+    }
+
+    /// Number of clip instances still playing
+    pub fn active_voices(&mut self) -> usize {
+        self.voices.active()
+    }
+
+    /// Number of instances of a clip still playing
+    pub fn active_voices_of(&mut self, clip: ClipHandle) -> usize {
+        self.voices.active_for_clip(clip)
+    }
+
+    /// Set the volume of a bus
+    ///
+    /// Setting the master bus is the same as `set_master_volume`.
+    pub fn set_bus_volume(&mut self, bus: Bus, volume: f32) {
+        if bus == Bus::Master {
+            self.set_master_volume(volume);
+        } else {
+            self.buses.set_volume(bus, volume);
+        }
+    }
+
+    /// Get the volume of a bus
+    #[must_use]
+    pub fn bus_volume(&self, bus: Bus) -> f32 {
+        self.buses.volume(bus)
+    }
+
+    /// Mute or unmute a bus and everything playing on it
+    pub fn set_bus_muted(&mut self, bus: Bus, muted: bool) {
+        if bus == Bus::Master {
+            self.muted = muted;
+        }
+        self.buses.set_muted(bus, muted);
+    }
+
+    /// Check if a bus is muted
+    #[must_use]
+    pub fn is_bus_muted(&self, bus: Bus) -> bool {
+        self.buses.is_muted(bus)
+    }
+
+    /// Place an effect in one of a bus's slots, returning the one it replaces
+    ///
+    /// Pass `None` to empty the slot. Slots past `EFFECT_SLOTS` are ignored.
+    pub fn set_bus_effect(
+        &mut self,
+        bus: Bus,
+        slot: usize,
+        effect: Option<Box<dyn AudioEffect>>,
+    ) -> Option<Box<dyn AudioEffect>> {
+        self.buses.set_effect(bus, slot, effect)
+    }
+
+    /// Change a parameter of the effect in a bus slot while it plays
+    ///
+    /// For example `set_bus_effect_param(Bus::Sfx, 0, "cutoff", 600.0)`
+    /// muffles effects when the player dives. Returns `false` if the slot is
+    /// empty or the effect has no such parameter.
+    pub fn set_bus_effect_param(&mut self, bus: Bus, slot: usize, param: &str, value: f32) -> bool {
+        self.buses.set_effect_param(bus, slot, param, value)
+    }
+
+    /// Update `analysis` with the level and spectrum of a bus's recent output
+    ///
+    /// Call once a frame with the same `BusAnalysis` to avoid allocating.
+    /// The window is post-volume, so a muted bus reads as silence.
+    pub fn analyze_bus(&self, bus: Bus, analysis: &mut BusAnalysis) {
+        self.buses.analyze(bus, analysis);
+    }
+
+    /// Set the listener position and velocity, usually from the camera or player
+    pub fn set_listener(&mut self, position: Vec3, velocity: Vec3) {
+        self.listener_position = position;
+        self.listener_velocity = velocity;
+    }
+
+    /// Get the listener position
+    #[must_use]
+    pub const fn listener_position(&self) -> Vec3 {
+        self.listener_position
+    }
+
+    /// Set the global doppler scale (0 disables, 1 is physical, > 1 exaggerates)
+    pub fn set_doppler_scale(&mut self, scale: f32) {
+        self.doppler_scale = scale.max(0.0);
+    }
+
+    /// Get the global doppler scale
+    #[must_use]
+    pub const fn doppler_scale(&self) -> f32 {
+        self.doppler_scale
+    }
+
+    /// Set the speed of sound used for doppler, in units per second
+    pub fn set_speed_of_sound(&mut self, speed: f32) {
+        self.speed_of_sound = speed.max(1.0);
+    }
+
+    /// Shift an instance's pitch for a source moving at `velocity`
+    ///
+    /// Call every frame for moving sources such as projectiles and vehicles;
+    /// instances that never get it play unshifted.
+    pub fn apply_doppler(&self, instance: &SoundInstance, position: Vec3, velocity: Vec3) {
+        instance.set_doppler(doppler_factor(
+            position,
+            velocity,
+            self.listener_position,
+            self.listener_velocity,
+            self.speed_of_sound,
+            self.doppler_scale,
+        ));
+    }
+
     /// Play an audio source by name
     pub fn play(&mut self, name: &str) -> bool {
         if let Some(source) = self.sources.get_mut(name) {
-            if !self.muted {
-                let source_vol = self.source_volumes.get(name).copied().unwrap_or(1.0);
-                source.set_volume(source_vol * self.master_volume);
-            }
+            let source_vol = self.source_volumes.get(name).copied().unwrap_or(1.0);
+            source.set_volume(source_vol);
             source.play();
             true
         } else {
@@ -118,17 +370,12 @@ impl AudioManager {
         }
     }
 
-    /// Set volume for a specific source
+    /// Set volume for a specific source (before bus volumes)
     pub fn set_volume(&mut self, name: &str, volume: f32) -> bool {
         if let Some(source) = self.sources.get_mut(name) {
             let vol = volume.max(0.0);
             self.source_volumes.insert(name.to_string(), vol);
-            let effective_volume = if self.muted {
-                0.0
-            } else {
-                vol * self.master_volume
-            };
-            source.set_volume(effective_volume);
+            source.set_volume(vol);
             true
         } else {
             false
@@ -138,12 +385,7 @@ impl AudioManager {
     /// Set the master volume (affects all sources)
     pub fn set_master_volume(&mut self, volume: f32) {
         self.master_volume = volume.max(0.0);
-        if !self.muted {
-            for (name, source) in &mut self.sources {
-                let source_vol = self.source_volumes.get(name).copied().unwrap_or(1.0);
-                source.set_volume(source_vol * self.master_volume);
-            }
-        }
+        self.buses.set_volume(Bus::Master, self.master_volume);
     }
 
     /// Get the master volume
@@ -155,18 +397,13 @@ impl AudioManager {
     /// Mute all audio
     pub fn mute(&mut self) {
         self.muted = true;
-        for source in self.sources.values_mut() {
-            source.set_volume(0.0);
-        }
+        self.buses.set_muted(Bus::Master, true);
     }
 
     /// Unmute all audio
     pub fn unmute(&mut self) {
         self.muted = false;
-        for (name, source) in &mut self.sources {
-            let source_vol = self.source_volumes.get(name).copied().unwrap_or(1.0);
-            source.set_volume(source_vol * self.master_volume);
-        }
+        self.buses.set_muted(Bus::Master, false);
     }
 
     /// Toggle mute state
@@ -223,6 +460,7 @@ impl std::fmt::Debug for AudioManager {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AudioManager")
             .field("source_count", &self.sources.len())
+            .field("clip_count", &self.clips.len())
             .field("master_volume", &self.master_volume)
             .field("muted", &self.muted)
             .finish()
@@ -3,8 +3,36 @@
 //! Built on top of the rodio audio library.
 //! Supports WAV, MP3, OGG, and FLAC formats.
 
+mod ambience;
+mod analysis;
+mod bus;
+mod clip;
+mod doppler;
+mod dsp;
+mod events;
+mod instance;
 mod manager;
+mod music;
+mod occlusion;
 mod source;
+mod spatial;
+mod synth;
+mod voices;
 
+pub use ambience::{
+    AmbienceBed, AmbienceManager, AmbienceZone, Sweetener, SweetenerSpawn, TimeRange,
+};
+pub use analysis::{ANALYSIS_WINDOW, BusAnalysis};
+pub use bus::{AudioEffect, Bus, EFFECT_SLOTS};
+pub use clip::{AudioClip, ClipHandle};
+pub use doppler::{SPEED_OF_SOUND, doppler_factor};
+pub use dsp::{LowPass, Reverb, ReverbPreset};
+pub use events::{AudioEvents, SoundCue, SoundEventMap};
+pub use instance::{PlayParams, SoundInstance};
 pub use manager::AudioManager;
-pub use source::{AudioSource, PlaybackState};
+pub use music::{MusicLayer, MusicSync, MusicSystem, MusicTrack, next_sync_time};
+pub use occlusion::Occlusion;
+pub use source::{AudioError, AudioSource, PlaybackState};
+pub use spatial::{AudioEmitter, AudioListener, distance_gain, spatial_pan, update_spatial_audio};
+pub use synth::{Envelope, Synth, Waveform};
+pub use voices::{VoiceLimit, VoicePolicy};
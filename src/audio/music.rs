@@ -0,0 +1,466 @@
+//! Dynamic music with intensity layers and a playlist
+//!
+//! Tracks are made of looping stems that fade in and out with a
+//! gameplay-set intensity. Track changes wait for the next beat or bar of
+//! the audio clock so transitions stay in time. Looping stems never end on
+//! their own, so a track's loop length and loop count decide when the
+//! playlist moves on.
+
+use std::collections::{HashMap, VecDeque};
+
+use super::PlaybackState;
+use super::manager::AudioManager;
+
+/// A stem of a music track, audible within an intensity range
+#[derive(Debug, Clone)]
+pub struct MusicLayer {
+    /// Name of the looping source in the `AudioManager`
+    pub sound: String,
+    /// Volume when fully faded in
+    pub volume: f32,
+    /// Intensity at which the layer starts fading in
+    pub fade_in_start: f32,
+    /// Intensity at which the layer is fully audible
+    pub fade_in_end: f32,
+}
+
+impl MusicLayer {
+    /// Create a layer that is always audible
+    #[must_use]
+    pub fn new(sound: impl Into<String>) -> Self {
+        Self {
+            sound: sound.into(),
+            volume: 1.0,
+            fade_in_start: 0.0,
+            fade_in_end: 0.0,
+        }
+    }
+
+    /// Fade the layer in between two intensity values
+    #[must_use]
+    pub fn with_intensity(mut self, start: f32, end: f32) -> Self {
+        self.fade_in_start = start;
+        self.fade_in_end = end.max(start);
+        self
+    }
+
+    /// Set the volume when fully faded in
+    #[must_use]
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Get the layer weight for an intensity (0.0 to 1.0)
+    #[must_use]
+    pub fn weight_at(&self, intensity: f32) -> f32 {
+        if self.fade_in_end <= self.fade_in_start {
+            return if intensity >= self.fade_in_start {
+                1.0
+            } else {
+                0.0
+            };
+        }
+        ((intensity - self.fade_in_start) / (self.fade_in_end - self.fade_in_start)).clamp(0.0, 1.0)
+    }
+}
+
+/// A music track made of synchronized stems
+#[derive(Debug, Clone)]
+pub struct MusicTrack {
+    /// Track name
+    pub name: String,
+    /// Tempo in beats per minute
+    pub bpm: f32,
+    /// Beats in one bar
+    pub beats_per_bar: u32,
+    /// Stems played together
+    pub layers: Vec<MusicLayer>,
+    /// Length of one pass of the stems in seconds, if known
+    pub length: Option<f32>,
+    /// Passes to play before the playlist advances
+    pub loops: u32,
+}
+
+impl MusicTrack {
+    /// Create a track with a tempo
+    #[must_use]
+    pub fn new(name: impl Into<String>, bpm: f32) -> Self {
+        Self {
+            name: name.into(),
+            bpm,
+            beats_per_bar: 4,
+            layers: Vec::new(),
+            length: None,
+            loops: 1,
+        }
+    }
+
+    /// Add a layer
+    #[must_use]
+    pub fn with_layer(mut self, layer: MusicLayer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Set the number of beats in a bar
+    #[must_use]
+    pub fn with_beats_per_bar(mut self, beats: u32) -> Self {
+        self.beats_per_bar = beats.max(1);
+        self
+    }
+
+    /// Set the stem length in seconds and how many times to loop it
+    ///
+    /// Without a length the track only ends when its stems stop, which
+    /// looping stems never do.
+    #[must_use]
+    pub fn with_length(mut self, seconds: f32, loops: u32) -> Self {
+        self.length = Some(seconds.max(0.0));
+        self.loops = loops.max(1);
+        self
+    }
+
+    /// Check if the track has played all its loops at a clock time
+    #[must_use]
+    pub fn finished_at(&self, clock: f32) -> Option<bool> {
+        self.length
+            .map(|length| clock >= length * self.loops as f32)
+    }
+
+    /// Get the length of one beat in seconds
+    #[must_use]
+    pub fn beat_length(&self) -> f32 {
+        60.0 / self.bpm.max(1.0)
+    }
+}
+
+/// When a track change takes effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MusicSync {
+    /// Switch right away
+    Immediate,
+    /// Switch on the next beat
+    NextBeat,
+    /// Switch on the next bar
+    #[default]
+    NextBar,
+}
+
+/// Get the first sync point at or after a time
+#[must_use]
+pub fn next_sync_time(clock: f32, track: &MusicTrack, sync: MusicSync) -> f32 {
+    let interval = match sync {
+        MusicSync::Immediate => return clock,
+        MusicSync::NextBeat => track.beat_length(),
+        MusicSync::NextBar => track.beat_length() * track.beats_per_bar as f32,
+    };
+    (clock / interval).ceil() * interval
+}
+
+/// A track change waiting for its sync point
+#[derive(Debug, Clone)]
+struct PendingTransition {
+    /// Track to switch to
+    track: String,
+    /// Clock time at which to switch
+    at: f32,
+}
+
+/// Plays layered music tracks with beat-synchronized transitions
+#[derive(Debug, Clone)]
+pub struct MusicSystem {
+    /// Registered tracks by name
+    tracks: HashMap<String, MusicTrack>,
+    /// Track currently playing
+    current: Option<String>,
+    /// Track change waiting for its sync point
+    pending: Option<PendingTransition>,
+    /// Tracks to play after the current one finishes
+    playlist: VecDeque<String>,
+    /// Current layer weights of the playing track
+    weights: Vec<f32>,
+    /// Gameplay intensity (0.0 calm to 1.0 full combat)
+    intensity: f32,
+    /// Seconds for a layer to fade fully in or out
+    pub layer_fade_time: f32,
+    /// Music volume multiplier
+    pub volume: f32,
+}
+
+impl MusicSystem {
+    /// Create an empty music system
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            tracks: HashMap::new(),
+            current: None,
+            pending: None,
+            playlist: VecDeque::new(),
+            weights: Vec::new(),
+            intensity: 0.0,
+            layer_fade_time: 2.0,
+            volume: 1.0,
+        }
+    }
+
+    /// Register a track
+    pub fn add_track(&mut self, track: MusicTrack) {
+        self.tracks.insert(track.name.clone(), track);
+    }
+
+    /// Set the gameplay intensity (clamped to 0.0 to 1.0)
+    pub fn set_intensity(&mut self, intensity: f32) {
+        self.intensity = intensity.clamp(0.0, 1.0);
+    }
+
+    /// Get the gameplay intensity
+    #[must_use]
+    pub const fn intensity(&self) -> f32 {
+        self.intensity
+    }
+
+    /// Get the name of the playing track
+    #[must_use]
+    pub fn current_track(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    /// Queue a track to play after the current one finishes
+    pub fn queue(&mut self, track: impl Into<String>) {
+        self.playlist.push_back(track.into());
+    }
+
+    /// Get the number of queued tracks
+    #[must_use]
+    pub fn queued(&self) -> usize {
+        self.playlist.len()
+    }
+
+    /// Switch to a track at the next sync point of the current track
+    ///
+    /// Returns `false` if the track is not registered.
+    pub fn play(&mut self, audio: &AudioManager, track: &str, sync: MusicSync) -> bool {
+        if !self.tracks.contains_key(track) {
+            return false;
+        }
+        let at = match self.current.as_ref().and_then(|name| self.tracks.get(name)) {
+            Some(current) => next_sync_time(self.clock(audio), current, sync),
+            None => 0.0,
+        };
+        self.pending = Some(PendingTransition {
+            track: track.to_string(),
+            at,
+        });
+        true
+    }
+
+    /// Stop the playing track and rewind its stems
+    ///
+    /// A later `play` of the same track starts it from the top rather than
+    /// resuming mid-bar.
+    pub fn stop(&mut self, audio: &mut AudioManager) {
+        if let Some(track) = self.current.take().and_then(|name| self.tracks.get(&name)) {
+            for layer in &track.layers {
+                audio.pause(&layer.sound);
+                if let Some(source) = audio.get_mut(&layer.sound) {
+                    source.rewind();
+                }
+            }
+        }
+        self.pending = None;
+        self.weights.clear();
+    }
+
+    /// Get the playback clock of the current track in seconds
+    ///
+    /// Read from the first stem's audio position, so it follows what is
+    /// actually heard rather than the frame clock.
+    #[must_use]
+    pub fn clock(&self, audio: &AudioManager) -> f32 {
+        self.current
+            .as_ref()
+            .and_then(|name| self.tracks.get(name))
+            .and_then(|track| track.layers.first())
+            .and_then(|layer| audio.get(&layer.sound))
+            .map_or(0.0, |source| source.position().as_secs_f32())
+    }
+
+    /// Advance transitions and layer fades, and apply volumes
+    pub fn update(&mut self, audio: &mut AudioManager, dt: f32) {
+        let clock = self.clock(audio);
+        let stems_stopped = self.stems_stopped(audio);
+        if let Some(next) = self.take_transition(clock, stems_stopped) {
+            self.start(audio, &next);
+        }
+
+        let Some(track) = self.current.as_ref().and_then(|name| self.tracks.get(name)) else {
+            return;
+        };
+        let step = if self.layer_fade_time > 0.0 {
+            dt / self.layer_fade_time
+        } else {
+            1.0
+        };
+        for (layer, weight) in track.layers.iter().zip(self.weights.iter_mut()) {
+            let target = layer.weight_at(self.intensity);
+            *weight += (target - *weight).clamp(-step, step);
+            audio.set_volume(&layer.sound, layer.volume * *weight * self.volume);
+        }
+    }
+
+    /// Pick the track to switch to this frame, if any
+    ///
+    /// When the current track has finished, the next playlist entry is
+    /// scheduled to start right away.
+    fn take_transition(&mut self, clock: f32, stems_stopped: bool) -> Option<String> {
+        if self.pending.is_none()
+            && self.current_finished(clock, stems_stopped)
+            && let Some(next) = self.playlist.pop_front()
+        {
+            self.pending = Some(PendingTransition {
+                track: next,
+                at: 0.0,
+            });
+        }
+
+        let pending = self.pending.as_ref()?;
+        if self.current.is_some() && clock < pending.at {
+            return None;
+        }
+        self.pending.take().map(|pending| pending.track)
+    }
+
+    /// Check if the current track has played out
+    ///
+    /// Tracks with a known length finish after their loops; others finish
+    /// once every stem has stopped.
+    fn current_finished(&self, clock: f32, stems_stopped: bool) -> bool {
+        self.current
+            .as_ref()
+            .and_then(|name| self.tracks.get(name))
+            .is_none_or(|track| track.finished_at(clock).unwrap_or(stems_stopped))
+    }
+
+    /// Check if every stem of the current track has stopped
+    fn stems_stopped(&self, audio: &AudioManager) -> bool {
+        let Some(track) = self.current.as_ref().and_then(|name| self.tracks.get(name)) else {
+            return true;
+        };
+        track.layers.iter().all(|layer| {
+            audio
+                .get(&layer.sound)
+                .is_none_or(|source| source.state() == PlaybackState::Stopped)
+        })
+    }
+
+    /// Stop the current track and start all stems of another together
+    fn start(&mut self, audio: &mut AudioManager, name: &str) {
+        self.stop(audio);
+        let Some(track) = self.tracks.get(name) else {
+            return;
+        };
+        self.weights = track
+            .layers
+            .iter()
+            .map(|layer| layer.weight_at(self.intensity))
+            .collect();
+        for (layer, weight) in track.layers.iter().zip(&self.weights) {
+            // `play` restores the stored source volume, so set it afterwards
+            audio.play(&layer.sound);
+            audio.set_volume(&layer.sound, layer.volume * weight * self.volume);
+        }
+        self.current = Some(name.to_string());
+    }
+}
+
+impl Default for MusicSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 120 bpm in 4/4: half-second beats and two-second bars
+    fn track(name: &str) -> MusicTrack {
+        MusicTrack::new(name, 120.0)
+            .with_layer(MusicLayer::new(format!("{name}_base")))
+            .with_layer(MusicLayer::new(format!("{name}_drums")).with_intensity(0.5, 1.0))
+            .with_length(8.0, 2)
+    }
+
+    #[test]
+    fn test_layer_weight_at() {
+        let always = MusicLayer::new("pad");
+        assert_eq!(always.weight_at(0.0), 1.0);
+
+        let combat = MusicLayer::new("drums").with_intensity(0.5, 1.0);
+        assert_eq!(combat.weight_at(0.25), 0.0);
+        assert!((combat.weight_at(0.75) - 0.5).abs() < 1e-6);
+        assert_eq!(combat.weight_at(1.0), 1.0);
+
+        let step = MusicLayer::new("stinger").with_intensity(0.8, 0.2);
+        assert_eq!(step.weight_at(0.79), 0.0);
+        assert_eq!(step.weight_at(0.8), 1.0);
+    }
+
+    #[test]
+    fn test_next_sync_time() {
+        let track = track("a");
+        assert_eq!(next_sync_time(3.1, &track, MusicSync::Immediate), 3.1);
+        assert_eq!(next_sync_time(3.1, &track, MusicSync::NextBeat), 3.5);
+        assert_eq!(next_sync_time(3.1, &track, MusicSync::NextBar), 4.0);
+        assert_eq!(next_sync_time(4.0, &track, MusicSync::NextBar), 4.0);
+
+        let waltz = track.with_beats_per_bar(3);
+        assert_eq!(next_sync_time(1.6, &waltz, MusicSync::NextBar), 3.0);
+    }
+
+    #[test]
+    fn test_playlist_hands_off_after_loops() {
+        let mut music = MusicSystem::new();
+        music.add_track(track("a"));
+        music.add_track(track("b"));
+        music.add_track(MusicTrack::new("c", 90.0));
+
+        music.queue("a");
+        music.queue("b");
+        assert_eq!(music.take_transition(0.0, true), Some("a".to_string()));
+        music.current = Some("a".to_string());
+        assert_eq!(music.queued(), 1);
+
+        // Looping stems never stop, so only the loop count ends the track
+        assert_eq!(music.take_transition(15.9, false), None);
+        assert_eq!(music.take_transition(16.0, false), Some("b".to_string()));
+        music.current = Some("b".to_string());
+        assert_eq!(music.queued(), 0);
+
+        // A track without a length waits for its stems to stop
+        music.queue("c");
+        assert_eq!(music.take_transition(16.0, false), Some("c".to_string()));
+        music.current = Some("c".to_string());
+        music.queue("a");
+        assert_eq!(music.take_transition(1000.0, false), None);
+        assert_eq!(music.take_transition(1000.0, true), Some("a".to_string()));
+    }
+
+    #[test]
+    fn test_pending_switch_waits_for_sync_point() {
+        let mut music = MusicSystem::new();
+        music.add_track(track("a"));
+        music.add_track(track("b"));
+        music.current = Some("a".to_string());
+        music.pending = Some(PendingTransition {
+            track: "b".to_string(),
+            at: 4.0,
+        });
+        music.queue("a");
+
+        assert_eq!(music.take_transition(3.9, false), None);
+        assert_eq!(music.take_transition(4.0, false), Some("b".to_string()));
+        assert_eq!(music.queued(), 1);
+    }
+}
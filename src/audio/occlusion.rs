@@ -0,0 +1,144 @@
+//! Sound occlusion
+//!
+//! Raycasts from the listener to each spatial sound through the physics
+//! world. Sounds behind geometry are muffled and turned down. Rays are only
+//! cast every `interval` seconds to keep the cost low; the players glide to
+//! the new value so the steps aren't audible.
+
+use glam::Vec3;
+
+use super::SoundInstance;
+use crate::physics::{CollisionFilter, Physics};
+
+/// Distance kept clear at the source end so its own collider doesn't block it
+const SOURCE_CLEARANCE: f32 = 0.1;
+
+/// Periodically occludes sounds blocked from the listener
+#[derive(Debug, Clone)]
+pub struct Occlusion {
+    /// Seconds between occlusion updates
+    interval: f32,
+    /// Occlusion applied to blocked sounds (0..1)
+    strength: f32,
+    /// Colliders that can block sound
+    filter: CollisionFilter,
+    /// Seconds until the next update
+    timer: f32,
+}
+
+impl Occlusion {
+    /// Create occlusion updating ten times a second at full strength
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            interval: 0.1,
+            strength: 1.0,
+            filter: CollisionFilter::new(),
+            timer: 0.0,
+        }
+    }
+
+    /// Set the seconds between updates
+    #[must_use]
+    pub fn with_interval(mut self, seconds: f32) -> Self {
+        self.interval = seconds.max(0.0);
+        self
+    }
+
+    /// Set how strongly blocked sounds are occluded (0..1)
+    #[must_use]
+    pub fn with_strength(mut self, strength: f32) -> Self {
+        self.strength = strength.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Restrict which colliders block sound, e.g. to skip the player's body
+    #[must_use]
+    pub fn with_filter(mut self, filter: CollisionFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Get the seconds between updates
+    #[must_use]
+    pub fn interval(&self) -> f32 {
+        self.interval
+    }
+
+    /// Get the occlusion applied to blocked sounds
+    #[must_use]
+    pub fn strength(&self) -> f32 {
+        self.strength
+    }
+
+    /// Check whether geometry lies between the listener and a source
+    #[must_use]
+    pub fn is_blocked(&self, physics: &Physics, listener: Vec3, source: Vec3) -> bool {
+        let offset = source - listener;
+        let distance = offset.length();
+        if distance <= SOURCE_CLEARANCE {
+            return false;
+        }
+        physics
+            .raycast_with(
+                listener,
+                offset / distance,
+                distance - SOURCE_CLEARANCE,
+                &self.filter,
+            )
+            .is_some()
+    }
+
+    /// Advance the timer and, when due, re-occlude each `(instance, position)`
+    ///
+    /// Returns whether rays were cast this call.
+    pub fn update<'a>(
+        &mut self,
+        physics: &Physics,
+        listener: Vec3,
+        sources: impl IntoIterator<Item = (&'a SoundInstance, Vec3)>,
+        dt: f32,
+    ) -> bool {
+        if !self.tick(dt) {
+            return false;
+        }
+        for (instance, position) in sources {
+            if instance.is_finished() {
+                continue;
+            }
+            let blocked = self.is_blocked(physics, listener, position);
+            instance.set_occlusion(if blocked { self.strength } else { 0.0 });
+        }
+        true
+    }
+
+    /// Advance the timer, returning whether an update is due
+    fn tick(&mut self, dt: f32) -> bool {
+        self.timer -= dt;
+        if self.timer > 0.0 {
+            return false;
+        }
+        self.timer = self.interval;
+        true
+    }
+}
+
+impl Default for Occlusion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_updates_at_interval() {
+        let mut occlusion = Occlusion::new().with_interval(0.25);
+        assert!(occlusion.tick(0.016));
+        assert!(!occlusion.tick(0.1));
+        assert!(!occlusion.tick(0.1));
+        assert!(occlusion.tick(0.1));
+    }
+}
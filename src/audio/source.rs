@@ -5,7 +5,7 @@ use std::io::BufReader;
 use std::path::Path;
 use std::sync::Arc;
 
-use rodio::{Decoder, Sink, mixer::Mixer};
+use rodio::{Decoder, Sink, Source, mixer::Mixer};
 
 /// Playback state of an audio source
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -61,6 +61,35 @@ impl AudioSource {
         })
     }
 
+    /// Create a looping audio source from a file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or decoded
+    pub fn from_file_looping(mixer: &Mixer, path: impl AsRef<Path>) -> Result<Self, AudioError> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        let file = File::open(path).map_err(|e| AudioError::IoError(e.to_string()))?;
+        let reader = BufReader::new(file);
+        let source = Decoder::new(reader).map_err(|e| AudioError::DecodeError(e.to_string()))?;
+
+        let sink = Sink::connect_new(mixer);
+        sink.append(source.repeat_infinite());
+        sink.pause();
+
+        Ok(Self {
+            sink,
+            state: PlaybackState::Paused,
+            looping: true,
+            name,
+        })
+    }
+
     /// Create an audio source from bytes
     pub fn from_bytes(
         mixer: &Mixer,
@@ -122,6 +151,19 @@ impl AudioSource {
         self.sink.speed()
     }
 
+    /// Get the playback position since the source started
+    #[must_use]
+    pub fn position(&self) -> std::time::Duration {
+        self.sink.get_pos()
+    }
+
+    /// Seek back to the start without changing the play state
+    ///
+    /// Returns `false` if the decoder cannot seek.
+    pub fn rewind(&mut self) -> bool {
+        self.sink.try_seek(std::time::Duration::ZERO).is_ok()
+    }
+
     /// Check if the audio has finished playing
     #[must_use]
     pub fn is_finished(&self) -> bool {
@@ -0,0 +1,286 @@
+//! Positional audio for ECS entities
+//!
+//! [`AudioEmitter`] and [`AudioListener`] components let entities play clips
+//! from where they are. [`update_spatial_audio`] reads their transforms each
+//! frame and drives distance attenuation, panning and doppler, so games
+//! don't have to push positions to the `AudioManager` by hand.
+
+use glam::{Mat4, Quat, Vec3};
+use hecs::Entity;
+
+use super::{AudioManager, ClipHandle, PlayParams, SoundInstance};
+use crate::ecs::{GlobalTransform, Transform, World};
+
+/// Component marking the entity the world is heard from
+///
+/// Usually the camera or the player. Only the first listener found is used.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AudioListener {
+    /// Position last frame, for velocity
+    previous_position: Option<Vec3>,
+}
+
+impl AudioListener {
+    /// Create a listener
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            previous_position: None,
+        }
+    }
+}
+
+/// Component playing a clip from the entity's position
+#[derive(Debug, Clone)]
+pub struct AudioEmitter {
+    /// Clip to play
+    pub clip: ClipHandle,
+    /// How the clip is started; the volume is scaled by distance
+    pub params: PlayParams,
+    /// Start playing on the first update
+    pub autoplay: bool,
+    /// Despawn the entity once the sound has finished
+    pub despawn_on_finish: bool,
+    /// Whether position affects volume, pan and pitch
+    pub spatial: bool,
+    /// Distance within which the sound is at full volume
+    pub min_distance: f32,
+    /// Distance beyond which the sound is silent
+    pub max_distance: f32,
+    /// Playing instance, if started
+    instance: Option<SoundInstance>,
+    /// Set by `play` until the next update starts the sound
+    play_requested: bool,
+    /// Whether the sound has been started at least once
+    started: bool,
+    /// Position last frame, for velocity
+    previous_position: Option<Vec3>,
+}
+
+impl AudioEmitter {
+    /// Create a spatial emitter that waits for `play`
+    #[must_use]
+    pub fn new(clip: ClipHandle) -> Self {
+        Self {
+            clip,
+            params: PlayParams::new(),
+            autoplay: false,
+            despawn_on_finish: false,
+            spatial: true,
+            min_distance: 1.0,
+            max_distance: 50.0,
+            instance: None,
+            play_requested: false,
+            started: false,
+            previous_position: None,
+        }
+    }
+
+    /// Set how the clip is started
+    #[must_use]
+    pub fn with_params(mut self, params: PlayParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Set the full-volume and silent distances
+    #[must_use]
+    pub fn with_distance(mut self, min: f32, max: f32) -> Self {
+        self.min_distance = min.max(0.0);
+        self.max_distance = max.max(self.min_distance);
+        self
+    }
+
+    /// Start playing on the first update
+    #[must_use]
+    pub fn autoplay(mut self) -> Self {
+        self.autoplay = true;
+        self
+    }
+
+    /// Despawn the entity once the sound has finished, e.g. one-shot effects
+    #[must_use]
+    pub fn despawn_on_finish(mut self) -> Self {
+        self.despawn_on_finish = true;
+        self
+    }
+
+    /// Play without attenuation, panning or doppler, e.g. UI or music
+    #[must_use]
+    pub fn non_spatial(mut self) -> Self {
+        self.spatial = false;
+        self
+    }
+
+    /// Start (or restart) the sound on the next update
+    pub fn play(&mut self) {
+        self.play_requested = true;
+    }
+
+    /// Stop the sound
+    pub fn stop(&mut self) {
+        if let Some(instance) = self.instance.take() {
+            instance.stop();
+        }
+        self.play_requested = false;
+    }
+
+    /// Get the playing instance
+    #[must_use]
+    pub fn instance(&self) -> Option<&SoundInstance> {
+        self.instance.as_ref()
+    }
+
+    /// Check if the sound is playing
+    #[must_use]
+    pub fn is_playing(&self) -> bool {
+        self.instance.as_ref().is_some_and(|i| !i.is_finished())
+    }
+
+    /// Check if the sound was started and has ended
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.started && !self.play_requested && !self.is_playing()
+    }
+}
+
+/// Volume multiplier for a sound `distance` away
+///
+/// Full volume up to `min`, falling off inversely with distance and faded
+/// linearly to silence at `max` so sounds don't pop out of range.
+#[must_use]
+pub fn distance_gain(distance: f32, min: f32, max: f32) -> f32 {
+    if distance <= min {
+        return 1.0;
+    }
+    if distance >= max {
+        return 0.0;
+    }
+    let inverse = if min > 0.0 { min / distance } else { 1.0 };
+    let fade = 1.0 - (distance - min) / (max - min);
+    inverse * fade
+}
+
+/// Stereo pan of a sound at `source` for a listener at `position` facing `rotation`
+///
+/// Sounds directly to the right pan to 1.0, to the left to -1.0.
+#[must_use]
+pub fn spatial_pan(position: Vec3, rotation: Quat, source: Vec3) -> f32 {
+    (source - position)
+        .try_normalize()
+        .map_or(0.0, |direction| direction.dot(rotation * Vec3::X))
+}
+
+/// World position and rotation of an entity
+fn world_pose(global: Option<&GlobalTransform>, local: Option<&Transform>) -> Option<(Vec3, Quat)> {
+    let matrix: Mat4 = match (global, local) {
+        (Some(global), _) => global.matrix,
+        (None, Some(local)) => local.matrix(),
+        (None, None) => return None,
+    };
+    let (_, rotation, position) = matrix.to_scale_rotation_translation();
+    Some((position, rotation))
+}
+
+/// Velocity from the position change since last frame
+fn track_velocity(previous: &mut Option<Vec3>, position: Vec3, dt: f32) -> Vec3 {
+    let velocity = match *previous {
+        Some(last) if dt > 0.0 => (position - last) / dt,
+        _ => Vec3::ZERO,
+    };
+    *previous = Some(position);
+    velocity
+}
+
+/// Sync listener and emitter transforms into the audio engine
+///
+/// Call once per frame after transforms are updated. Starts autoplay and
+/// requested sounds, updates volume, pan and doppler of spatial emitters,
+/// and despawns entities whose `despawn_on_finish` sound has ended.
+pub fn update_spatial_audio(world: &mut World, audio: &mut AudioManager, dt: f32) {
+    let mut listener_pose = (audio.listener_position(), Quat::IDENTITY);
+    if let Some((_, (listener, global, local))) = world
+        .query_mut::<(
+            &mut AudioListener,
+            Option<&GlobalTransform>,
+            Option<&Transform>,
+        )>()
+        .into_iter()
+        .next()
+        && let Some((position, rotation)) = world_pose(global, local)
+    {
+        let velocity = track_velocity(&mut listener.previous_position, position, dt);
+        audio.set_listener(position, velocity);
+        listener_pose = (position, rotation);
+    }
+    let (listener_position, listener_rotation) = listener_pose;
+
+    let mut finished: Vec<Entity> = Vec::new();
+    for (entity, (emitter, global, local)) in world.query_mut::<(
+        &mut AudioEmitter,
+        Option<&GlobalTransform>,
+        Option<&Transform>,
+    )>() {
+        if emitter.play_requested || (emitter.autoplay && !emitter.started) {
+            if let Some(old) = emitter.instance.take() {
+                old.stop();
+            }
+            emitter.instance = audio.play_clip_with(emitter.clip, emitter.params);
+            emitter.play_requested = false;
+            emitter.started = true;
+        }
+
+        let Some(instance) = &emitter.instance else {
+            if emitter.started && emitter.despawn_on_finish {
+                finished.push(entity);
+            }
+            continue;
+        };
+        if instance.is_finished() {
+            if emitter.despawn_on_finish {
+                finished.push(entity);
+            }
+            continue;
+        }
+        if !emitter.spatial {
+            continue;
+        }
+        let Some((position, _)) = world_pose(global, local) else {
+            continue;
+        };
+
+        let velocity = track_velocity(&mut emitter.previous_position, position, dt);
+        let distance = position.distance(listener_position);
+        let gain = distance_gain(distance, emitter.min_distance, emitter.max_distance);
+        instance.set_volume(emitter.params.volume * gain);
+        instance.set_pan(spatial_pan(listener_position, listener_rotation, position));
+        audio.apply_doppler(instance, position, velocity);
+    }
+
+    for entity in finished {
+        let _ = world.despawn(entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_gain() {
+        assert_eq!(distance_gain(0.5, 1.0, 10.0), 1.0);
+        assert_eq!(distance_gain(10.0, 1.0, 10.0), 0.0);
+        let near = distance_gain(2.0, 1.0, 10.0);
+        let far = distance_gain(5.0, 1.0, 10.0);
+        assert!(near > far && far > 0.0);
+    }
+
+    #[test]
+    fn test_spatial_pan_follows_listener_rotation() {
+        let source = Vec3::new(5.0, 0.0, 0.0);
+        assert!((spatial_pan(Vec3::ZERO, Quat::IDENTITY, source) - 1.0).abs() < 1e-5);
+        let turned = Quat::from_rotation_y(std::f32::consts::PI);
+        assert!((spatial_pan(Vec3::ZERO, turned, source) + 1.0).abs() < 1e-5);
+        assert_eq!(spatial_pan(source, Quat::IDENTITY, source), 0.0);
+    }
+}
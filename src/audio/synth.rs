@@ -0,0 +1,265 @@
+//! Procedural sound synthesis
+//!
+//! Renders simple oscillator sounds into clips without any asset files:
+//! retro blips, placeholder effects, or engine hums whose pitch is then
+//! driven at runtime through `SoundInstance::set_pitch`.
+
+use std::f32::consts::TAU;
+
+use super::clip::AudioClip;
+
+/// Oscillator shape
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Waveform {
+    /// Pure tone
+    #[default]
+    Sine,
+    /// Hollow, retro tone; see `Synth::with_duty`
+    Square,
+    /// Soft, flute-like tone
+    Triangle,
+    /// Bright, buzzy tone
+    Sawtooth,
+    /// Random levels, picked `frequency` times a second
+    Noise,
+}
+
+/// Attack, decay, sustain, release volume envelope
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Envelope {
+    /// Seconds to rise from silence to full volume
+    pub attack: f32,
+    /// Seconds to fall from full volume to the sustain level
+    pub decay: f32,
+    /// Level held until the note is released (0..1)
+    pub sustain: f32,
+    /// Seconds to fall to silence after the note is released
+    pub release: f32,
+}
+
+impl Envelope {
+    /// No shaping: full volume for the whole note
+    pub const FLAT: Self = Self::new(0.0, 0.0, 1.0, 0.0);
+
+    /// Short percussive blip
+    pub const PLUCK: Self = Self::new(0.002, 0.1, 0.0, 0.05);
+
+    /// Slow swell in and out
+    pub const PAD: Self = Self::new(0.3, 0.2, 0.7, 0.5);
+
+    /// Create an envelope
+    #[must_use]
+    pub const fn new(attack: f32, decay: f32, sustain: f32, release: f32) -> Self {
+        Self {
+            attack,
+            decay,
+            sustain,
+            release,
+        }
+    }
+
+    /// Volume `time` seconds into a note held for `held` seconds
+    #[must_use]
+    pub fn gain(&self, time: f32, held: f32) -> f32 {
+        if time < held {
+            return self.held_gain(time);
+        }
+        if self.release <= 0.0 {
+            return 0.0;
+        }
+        let released = 1.0 - (time - held) / self.release;
+        self.held_gain(held) * released.max(0.0)
+    }
+
+    /// Volume while the note is held
+    fn held_gain(&self, time: f32) -> f32 {
+        let sustain = self.sustain.clamp(0.0, 1.0);
+        if time < self.attack {
+            return time / self.attack;
+        }
+        let decayed = time - self.attack;
+        if decayed < self.decay {
+            return 1.0 - (1.0 - sustain) * decayed / self.decay;
+        }
+        sustain
+    }
+}
+
+impl Default for Envelope {
+    fn default() -> Self {
+        Self::FLAT
+    }
+}
+
+/// Description of a synthesized sound
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Synth {
+    /// Oscillator shape
+    pub waveform: Waveform,
+    /// Starting frequency in Hz
+    pub frequency: f32,
+    /// Frequency reached at the end of the note, for sweeps
+    pub end_frequency: Option<f32>,
+    /// Seconds the note is held before its release
+    pub duration: f32,
+    /// Volume envelope
+    pub envelope: Envelope,
+    /// Output volume
+    pub volume: f32,
+    /// Fraction of each square wave cycle spent high
+    pub duty: f32,
+    /// Seed for noise, so the same synth always renders the same sound
+    pub seed: u32,
+}
+
+impl Synth {
+    /// A flat tone of `frequency` Hz held for `duration` seconds
+    #[must_use]
+    pub const fn new(waveform: Waveform, frequency: f32, duration: f32) -> Self {
+        Self {
+            waveform,
+            frequency,
+            end_frequency: None,
+            duration,
+            envelope: Envelope::FLAT,
+            volume: 1.0,
+            duty: 0.5,
+            seed: 0x1234_5678,
+        }
+    }
+
+    /// Sweep the frequency to `end_frequency` over the note
+    ///
+    /// The sweep is exponential, so it sounds even across octaves.
+    #[must_use]
+    pub const fn with_sweep(mut self, end_frequency: f32) -> Self {
+        self.end_frequency = Some(end_frequency);
+        self
+    }
+
+    /// Set the volume envelope
+    #[must_use]
+    pub const fn with_envelope(mut self, envelope: Envelope) -> Self {
+        self.envelope = envelope;
+        self
+    }
+
+    /// Set the output volume
+    #[must_use]
+    pub const fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Set the square wave duty cycle (0.5 = even, 0.125 = thin and nasal)
+    #[must_use]
+    pub const fn with_duty(mut self, duty: f32) -> Self {
+        self.duty = duty;
+        self
+    }
+
+    /// Set the noise seed
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u32) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Length of the rendered sound, including the release
+    #[must_use]
+    pub fn length(&self) -> f32 {
+        self.duration.max(0.0) + self.envelope.release.max(0.0)
+    }
+
+    /// Frequency `time` seconds into the note
+    fn frequency_at(&self, time: f32) -> f32 {
+        let start = self.frequency.max(0.0);
+        match self.end_frequency {
+            Some(end) if self.duration > 0.0 && start > 0.0 && end > 0.0 => {
+                let t = (time / self.duration).min(1.0);
+                start * (end / start).powf(t)
+            }
+            _ => start,
+        }
+    }
+
+    /// Render into a mono clip
+    #[must_use]
+    pub fn render(&self, sample_rate: u32) -> AudioClip {
+        let sample_rate = sample_rate.max(1);
+        let rate = sample_rate as f32;
+        let frames = (self.length() * rate).ceil() as usize;
+        let mut samples = Vec::with_capacity(frames);
+        let mut phase = 0.0_f32;
+        let mut seed = self.seed.max(1);
+        let mut noise = 0.0;
+
+        for frame in 0..frames {
+            let time = frame as f32 / rate;
+            let value = match self.waveform {
+                Waveform::Sine => (phase * TAU).sin(),
+                Waveform::Square => {
+                    if phase < self.duty.clamp(0.0, 1.0) {
+                        1.0
+                    } else {
+                        -1.0
+                    }
+                }
+                Waveform::Triangle => 1.0 - 4.0 * (phase - 0.5).abs(),
+                Waveform::Sawtooth => 2.0 * phase - 1.0,
+                Waveform::Noise => {
+                    if frame == 0 {
+                        noise = random(&mut seed);
+                    }
+                    noise
+                }
+            };
+            samples.push(value * self.envelope.gain(time, self.duration) * self.volume);
+
+            phase += self.frequency_at(time) / rate;
+            if phase >= 1.0 {
+                phase = phase.fract();
+                if self.waveform == Waveform::Noise {
+                    noise = random(&mut seed);
+                }
+            }
+        }
+        AudioClip::from_samples(samples, 1, sample_rate)
+    }
+}
+
+/// Xorshift random level in -1..1
+fn random(seed: &mut u32) -> f32 {
+    *seed ^= *seed << 13;
+    *seed ^= *seed >> 17;
+    *seed ^= *seed << 5;
+    (*seed as f32) / (u32::MAX as f32) * 2.0 - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope_stages() {
+        let envelope = Envelope::new(0.1, 0.1, 0.5, 0.2);
+        assert!((envelope.gain(0.05, 1.0) - 0.5).abs() < 1e-5);
+        assert!((envelope.gain(0.15, 1.0) - 0.75).abs() < 1e-5);
+        assert!((envelope.gain(0.5, 1.0) - 0.5).abs() < 1e-5);
+        assert!((envelope.gain(1.1, 1.0) - 0.25).abs() < 1e-5);
+        assert_eq!(envelope.gain(1.3, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_render_length_and_shape() {
+        let synth = Synth::new(Waveform::Square, 1.0, 1.0).with_volume(0.5);
+        let clip = synth.render(4);
+        assert_eq!(clip.samples(), &[0.5, 0.5, -0.5, -0.5]);
+
+        let sweep = Synth::new(Waveform::Sine, 100.0, 1.0)
+            .with_sweep(400.0)
+            .with_envelope(Envelope::new(0.0, 0.0, 1.0, 0.5));
+        assert_eq!(sweep.render(100).frames(), 150);
+        assert!((sweep.frequency_at(0.5) - 200.0).abs() < 1e-2);
+    }
+}
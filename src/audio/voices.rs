@@ -0,0 +1,253 @@
+//! Voice management
+//!
+//! Limits how many instances of a clip or on a bus play at once. When a
+//! limit is reached a new sound is either rejected or steals the oldest
+//! playing voice. Finished instance state is pooled so rapid-fire sounds do
+//! not allocate per play.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::bus::Bus;
+use super::clip::ClipHandle;
+use super::instance::{InstanceState, SoundInstance};
+
+/// Seconds a stolen voice fades out over, to avoid clicks
+const STEAL_FADE: f32 = 0.02;
+
+/// Most instance states kept for reuse
+const MAX_POOLED: usize = 64;
+
+/// What happens when a voice limit is reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VoicePolicy {
+    /// Do not play the new sound
+    Reject,
+    /// Fade out the oldest voice and play the new sound
+    #[default]
+    StealOldest,
+}
+
+/// Maximum number of simultaneous voices and what to do beyond it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VoiceLimit {
+    /// Most voices allowed at once
+    pub max: usize,
+    /// Policy once `max` voices are playing
+    pub policy: VoicePolicy,
+}
+
+impl VoiceLimit {
+    /// Limit to `max` voices, stealing the oldest beyond it
+    #[must_use]
+    pub const fn new(max: usize) -> Self {
+        Self {
+            max,
+            policy: VoicePolicy::StealOldest,
+        }
+    }
+
+    /// Set the policy
+    #[must_use]
+    pub const fn with_policy(mut self, policy: VoicePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+/// A playing instance tracked for voice limits
+#[derive(Debug)]
+struct Voice {
+    /// Clip being played
+    clip: ClipHandle,
+    /// Bus it plays on
+    bus: Bus,
+    /// Handle controlling it
+    instance: SoundInstance,
+    /// Start order, lower is older
+    started: u64,
+}
+
+/// Outcome of checking one limit
+enum Admission {
+    /// Under the limit
+    Free,
+    /// Over the limit; steal the voice at this index
+    Steal(usize),
+    /// Over the limit and not allowed to steal
+    Reject,
+}
+
+/// Tracks playing voices, enforces limits and pools instance state
+#[derive(Debug, Default)]
+pub(super) struct VoiceManager {
+    /// Voices that have not been seen finishing yet
+    voices: Vec<Voice>,
+    /// Per-clip limits
+    clip_limits: HashMap<ClipHandle, VoiceLimit>,
+    /// Per-bus limits
+    bus_limits: HashMap<Bus, VoiceLimit>,
+    /// Recycled instance state
+    pool: Vec<Arc<InstanceState>>,
+    /// Start counter
+    started: u64,
+}
+
+impl VoiceManager {
+    /// Set or clear the voice limit of a clip
+    pub(super) fn set_clip_limit(&mut self, clip: ClipHandle, limit: Option<VoiceLimit>) {
+        match limit {
+            Some(limit) => self.clip_limits.insert(clip, limit),
+            None => self.clip_limits.remove(&clip),
+        };
+    }
+
+    /// Set or clear the voice limit of a bus
+    pub(super) fn set_bus_limit(&mut self, bus: Bus, limit: Option<VoiceLimit>) {
+        match limit {
+            Some(limit) => self.bus_limits.insert(bus, limit),
+            None => self.bus_limits.remove(&bus),
+        };
+    }
+
+    /// Number of voices still playing
+    pub(super) fn active(&mut self) -> usize {
+        self.collect_finished();
+        self.voices.len()
+    }
+
+    /// Number of playing voices of a clip
+    pub(super) fn active_for_clip(&mut self, clip: ClipHandle) -> usize {
+        self.collect_finished();
+        self.voices.iter().filter(|v| v.clip == clip).count()
+    }
+
+    /// Check the limits for a new voice, stealing voices if allowed
+    ///
+    /// Returns `false` if the new voice must not play.
+    pub(super) fn admit(&mut self, clip: ClipHandle, bus: Bus) -> bool {
+        self.collect_finished();
+        let clip_admission = self.check(self.clip_limits.get(&clip), |v| v.clip == clip);
+        let bus_admission = self.check(self.bus_limits.get(&bus), |v| v.bus == bus);
+
+        let mut victims = Vec::new();
+        for admission in [clip_admission, bus_admission] {
+            match admission {
+                Admission::Free => {}
+                Admission::Steal(index) => victims.push(index),
+                Admission::Reject => return false,
+            }
+        }
+        victims.sort_unstable();
+        victims.dedup();
+        for index in victims.into_iter().rev() {
+            let voice = self.voices.remove(index);
+            voice.instance.fade_out(STEAL_FADE);
+        }
+        true
+    }
+
+    /// Take pooled instance state, if any
+    pub(super) fn recycled_state(&mut self) -> Option<Arc<InstanceState>> {
+        self.pool.pop()
+    }
+
+    /// Track a started voice
+    pub(super) fn register(&mut self, clip: ClipHandle, bus: Bus, instance: SoundInstance) {
+        self.started += 1;
+        self.voices.push(Voice {
+            clip,
+            bus,
+            instance,
+            started: self.started,
+        });
+    }
+
+    /// Check one limit against the voices matching `filter`
+    fn check(&self, limit: Option<&VoiceLimit>, filter: impl Fn(&Voice) -> bool) -> Admission {
+        let Some(limit) = limit else {
+            return Admission::Free;
+        };
+        let mut count = 0;
+        let mut oldest: Option<(usize, u64)> = None;
+        for (index, voice) in self.voices.iter().enumerate() {
+            if filter(voice) {
+                count += 1;
+                if oldest.is_none_or(|(_, started)| voice.started < started) {
+                    oldest = Some((index, voice.started));
+                }
+            }
+        }
+        if count < limit.max {
+            return Admission::Free;
+        }
+        match (limit.policy, oldest) {
+            (VoicePolicy::StealOldest, Some((index, _))) => Admission::Steal(index),
+            _ => Admission::Reject,
+        }
+    }
+
+    /// Drop finished voices, pooling their state when unreferenced
+    fn collect_finished(&mut self) {
+        let mut index = 0;
+        while index < self.voices.len() {
+            if !self.voices[index].instance.is_finished() {
+                index += 1;
+                continue;
+            }
+            let voice = self.voices.swap_remove(index);
+            if self.pool.len() < MAX_POOLED
+                && let Some(state) = voice.instance.recycle()
+            {
+                self.pool.push(state);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::{AudioClip, PlayParams};
+
+    fn start(manager: &mut VoiceManager, clip: ClipHandle) -> Option<SoundInstance> {
+        if !manager.admit(clip, Bus::Sfx) {
+            return None;
+        }
+        let samples = AudioClip::from_samples(vec![0.0; 4], 1, 4);
+        let recycled = manager.recycled_state();
+        let (instance, _player) = SoundInstance::start(&samples, &PlayParams::new(), recycled);
+        manager.register(clip, Bus::Sfx, instance.clone());
+        Some(instance)
+    }
+
+    #[test]
+    fn test_steal_oldest_and_reject() {
+        let clip = ClipHandle(0);
+        let mut manager = VoiceManager::default();
+        manager.set_clip_limit(clip, Some(VoiceLimit::new(2)));
+
+        for _ in 0..3 {
+            assert!(start(&mut manager, clip).is_some());
+        }
+        assert_eq!(manager.active_for_clip(clip), 2);
+
+        manager.set_bus_limit(
+            Bus::Sfx,
+            Some(VoiceLimit::new(2).with_policy(VoicePolicy::Reject)),
+        );
+        assert!(start(&mut manager, clip).is_none());
+    }
+
+    #[test]
+    fn test_finished_state_is_pooled() {
+        let clip = ClipHandle(0);
+        let mut manager = VoiceManager::default();
+        let instance = start(&mut manager, clip).unwrap();
+        instance.stop();
+        drop(instance);
+
+        assert_eq!(manager.active(), 0);
+        assert!(manager.recycled_state().is_some());
+    }
+}
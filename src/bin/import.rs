@@ -0,0 +1,65 @@
+//! Offline asset import tool
+//!
+//! Processes every supported asset under a directory into the import cache.
+//!
+//! Usage: `import <source_dir> [--cache <cache_dir>]`
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use engine::assets::{DEFAULT_CACHE_DIR, ImportPipeline};
+
+fn main() -> ExitCode {
+    env_logger::init();
+
+    let mut args = std::env::args().skip(1);
+    let mut source = None;
+    let mut cache_dir = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--cache" => cache_dir = args.next().map(PathBuf::from),
+            _ => source = Some(PathBuf::from(arg)),
+        }
+    }
+
+    let Some(source) = source else {
+        eprintln!("Usage: import <source_dir> [--cache <cache_dir>]");
+        return ExitCode::FAILURE;
+    };
+    let cache_dir = cache_dir.unwrap_or_else(|| source.join(DEFAULT_CACHE_DIR));
+    let pipeline = ImportPipeline::new(&cache_dir);
+
+    let results = match pipeline.import_dir(&source) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("Failed to import {}: {e}", source.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (mut processed, mut cached, mut failed) = (0, 0, 0);
+    for result in results {
+        match result {
+            Ok(asset) if asset.cached => cached += 1,
+            Ok(asset) => {
+                println!("Processed {}", asset.source.display());
+                processed += 1;
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "Imported into {}: {processed} processed, {cached} up to date, {failed} failed",
+        cache_dir.display()
+    );
+
+    if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
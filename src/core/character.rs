@@ -0,0 +1,560 @@
+//! Ready-made player characters
+//!
+//! Templates that wire a character controller, camera controller, input
+//! action map, locomotion state machine and footstep audio together, so a
+//! playable character is one `spawn` call and one system per frame.
+
+use glam::{Quat, Vec2, Vec3};
+use hecs::Entity;
+
+use crate::animation::{
+    AnimationClip, AnimationPlayer, AnimationState, AnimationStateMachine, BlendTree1D, Condition,
+    Transition,
+};
+use crate::audio::AudioManager;
+use crate::ecs::{Name, Transform, World};
+use crate::input::{ActionMap, Input};
+use crate::physics::{CharacterController, Physics};
+use crate::renderer::Camera;
+
+/// Animation parameter holding the horizontal speed
+pub const SPEED_PARAMETER: &str = "speed";
+/// Animation parameter set while the character is airborne
+pub const AIRBORNE_PARAMETER: &str = "airborne";
+/// Animation event name that plays a footstep
+pub const FOOTSTEP_EVENT: &str = "footstep";
+
+/// Highest look angle above or below the horizon
+const MAX_PITCH: f32 = 1.5;
+/// Gap kept between a third-person camera and the wall it collides with
+const CAMERA_MARGIN: f32 = 0.2;
+
+/// Direction for a yaw and pitch (yaw 0 looks down -Z)
+fn look_direction(yaw: f32, pitch: f32) -> Vec3 {
+    Quat::from_rotation_y(yaw) * Quat::from_rotation_x(pitch) * Vec3::NEG_Z
+}
+
+/// Mouse-look camera at the character's eyes
+#[derive(Debug, Clone)]
+pub struct FirstPersonCamera {
+    /// Eye height above the feet
+    pub eye_height: f32,
+    /// Radians per pixel of mouse movement
+    pub sensitivity: f32,
+    /// Rotation around the Y axis
+    pub yaw: f32,
+    /// Rotation above the horizon
+    pub pitch: f32,
+}
+
+impl FirstPersonCamera {
+    /// Create a camera at the given eye height
+    #[must_use]
+    pub fn new(eye_height: f32) -> Self {
+        Self {
+            eye_height,
+            sensitivity: 0.0025,
+            yaw: 0.0,
+            pitch: 0.0,
+        }
+    }
+
+    /// Apply a mouse delta
+    pub fn look(&mut self, delta: Vec2) {
+        self.yaw -= delta.x * self.sensitivity;
+        self.pitch = (self.pitch - delta.y * self.sensitivity).clamp(-MAX_PITCH, MAX_PITCH);
+    }
+
+    /// Place a camera at the eyes of a character standing at `feet`
+    pub fn apply(&self, camera: &mut Camera, feet: Vec3) {
+        camera.position = feet + Vec3::Y * self.eye_height;
+        camera.direction = look_direction(self.yaw, self.pitch);
+    }
+}
+
+/// Orbit camera behind the character, pulled in by walls
+#[derive(Debug, Clone)]
+pub struct ThirdPersonCamera {
+    /// Height of the orbit pivot above the feet
+    pub pivot_height: f32,
+    /// Preferred distance from the pivot
+    pub distance: f32,
+    /// Distance range adjustable with the scroll wheel
+    pub zoom_range: (f32, f32),
+    /// Radians per pixel of mouse movement
+    pub sensitivity: f32,
+    /// Rotation around the Y axis
+    pub yaw: f32,
+    /// Rotation above the horizon
+    pub pitch: f32,
+}
+
+impl ThirdPersonCamera {
+    /// Create a camera orbiting at `distance`
+    #[must_use]
+    pub fn new(pivot_height: f32, distance: f32) -> Self {
+        Self {
+            pivot_height,
+            distance,
+            zoom_range: (1.0, distance * 2.0),
+            sensitivity: 0.0025,
+            yaw: 0.0,
+            pitch: -0.3,
+        }
+    }
+
+    /// Apply a mouse delta and scroll
+    pub fn look(&mut self, delta: Vec2, scroll: f32) {
+        self.yaw -= delta.x * self.sensitivity;
+        self.pitch = (self.pitch - delta.y * self.sensitivity).clamp(-MAX_PITCH, MAX_PITCH);
+        self.distance = (self.distance - scroll).clamp(self.zoom_range.0, self.zoom_range.1);
+    }
+
+    /// Place a camera behind a character, keeping it in front of walls
+    pub fn apply(
+        &self,
+        camera: &mut Camera,
+        feet: Vec3,
+        physics: &Physics,
+        character: &CharacterController,
+    ) {
+        let pivot = feet + Vec3::Y * self.pivot_height;
+        let direction = look_direction(self.yaw, self.pitch);
+        let distance = physics
+            .raycast_excluding(pivot, -direction, self.distance, character.collider)
+            .map_or(self.distance, |hit| (hit.distance - CAMERA_MARGIN).max(0.0));
+
+        camera.position = pivot - direction * distance;
+        camera.direction = direction;
+    }
+}
+
+/// How a character's camera is controlled
+#[derive(Debug, Clone)]
+pub enum CharacterCamera {
+    /// Camera at the eyes
+    FirstPerson(FirstPersonCamera),
+    /// Camera orbiting behind the character
+    ThirdPerson(ThirdPersonCamera),
+}
+
+impl CharacterCamera {
+    /// Get the camera yaw, which also steers movement
+    #[must_use]
+    pub const fn yaw(&self) -> f32 {
+        match self {
+            Self::FirstPerson(camera) => camera.yaw,
+            Self::ThirdPerson(camera) => camera.yaw,
+        }
+    }
+}
+
+/// Footstep sounds played by stride distance or animation events
+#[derive(Debug, Clone)]
+pub struct Footsteps {
+    /// Sounds played in turn
+    pub sounds: Vec<String>,
+    /// Distance walked between steps (ignored when animations send
+    /// footstep events)
+    pub stride: f32,
+    /// Distance walked since the last step
+    travelled: f32,
+    /// Index of the next sound
+    next: usize,
+}
+
+impl Footsteps {
+    /// Create footsteps cycling through `sounds`
+    #[must_use]
+    pub fn new(sounds: Vec<String>, stride: f32) -> Self {
+        Self {
+            sounds,
+            stride,
+            travelled: 0.0,
+            next: 0,
+        }
+    }
+
+    /// Advance by a walked distance, returning the sound to play if a
+    /// stride completed
+    pub fn advance(&mut self, distance: f32) -> Option<&str> {
+        if self.stride <= 0.0 {
+            return None;
+        }
+        self.travelled += distance;
+        if self.travelled < self.stride {
+            return None;
+        }
+        self.travelled %= self.stride;
+        self.step()
+    }
+
+    /// Take the next sound in turn
+    pub fn step(&mut self) -> Option<&str> {
+        if self.sounds.is_empty() {
+            return None;
+        }
+        let index = self.next % self.sounds.len();
+        self.next = index + 1;
+        Some(&self.sounds[index])
+    }
+
+    /// Restart the stride, e.g. after landing
+    pub fn reset(&mut self) {
+        self.travelled = 0.0;
+    }
+}
+
+/// Idle, walk and run clips for the locomotion state machine
+#[derive(Debug, Clone)]
+pub struct CharacterAnimations {
+    /// Clip played standing still
+    pub idle: AnimationClip,
+    /// Clip played at walking speed
+    pub walk: AnimationClip,
+    /// Clip played at sprinting speed
+    pub run: AnimationClip,
+    /// Clip played while airborne
+    pub fall: Option<AnimationClip>,
+}
+
+impl CharacterAnimations {
+    /// Create locomotion clips without a fall clip
+    #[must_use]
+    pub fn new(idle: AnimationClip, walk: AnimationClip, run: AnimationClip) -> Self {
+        Self {
+            idle,
+            walk,
+            run,
+            fall: None,
+        }
+    }
+
+    /// Set the clip played while airborne
+    #[must_use]
+    pub fn with_fall(mut self, fall: AnimationClip) -> Self {
+        self.fall = Some(fall);
+        self
+    }
+
+    /// Build a state machine blending the clips by speed
+    #[must_use]
+    pub fn state_machine(&self, walk_speed: f32, sprint_speed: f32) -> AnimationStateMachine {
+        let mut machine = AnimationStateMachine::new();
+        machine.add_state(AnimationState::blend(
+            "locomotion",
+            BlendTree1D::new(SPEED_PARAMETER)
+                .with_clip(0.0, self.idle.clone())
+                .with_clip(walk_speed, self.walk.clone())
+                .with_clip(sprint_speed, self.run.clone()),
+        ));
+        if let Some(fall) = &self.fall {
+            machine.add_state(AnimationState::new("fall", fall.clone()));
+            machine.add_transition(
+                Transition::new("locomotion", "fall")
+                    .when(Condition::Bool(AIRBORNE_PARAMETER.to_string(), true))
+                    .with_duration(0.15),
+            );
+            machine.add_transition(
+                Transition::new("fall", "locomotion")
+                    .when(Condition::Bool(AIRBORNE_PARAMETER.to_string(), false))
+                    .with_duration(0.1),
+            );
+        }
+        machine
+    }
+}
+
+/// Component tying a character's input, camera and footsteps together
+#[derive(Debug, Clone)]
+pub struct Character {
+    /// Input bindings
+    pub actions: ActionMap,
+    /// Camera controller
+    pub camera: CharacterCamera,
+    /// Footstep sounds
+    pub footsteps: Footsteps,
+    /// Whether this character reads input and drives the camera
+    pub controlled: bool,
+    /// Turn rate toward the movement direction in third person (radians
+    /// per second)
+    pub turn_speed: f32,
+    /// Whether the character was on the ground last frame
+    was_grounded: bool,
+}
+
+/// Settings for spawning a ready-made character
+#[derive(Debug, Clone)]
+pub struct CharacterTemplate {
+    /// Entity name
+    pub name: String,
+    /// Camera controller
+    pub camera: CharacterCamera,
+    /// Capsule height
+    pub height: f32,
+    /// Capsule radius
+    pub radius: f32,
+    /// Walking speed
+    pub walk_speed: f32,
+    /// Sprinting speed
+    pub sprint_speed: f32,
+    /// Jump speed
+    pub jump_speed: f32,
+    /// Input bindings
+    pub actions: ActionMap,
+    /// Locomotion clips (third-person characters usually have these)
+    pub animations: Option<CharacterAnimations>,
+    /// Footstep sounds
+    pub footsteps: Footsteps,
+}
+
+impl CharacterTemplate {
+    /// A first-person character: eye camera, WASD, no body animation
+    #[must_use]
+    pub fn first_person() -> Self {
+        Self {
+            name: "Player".to_string(),
+            camera: CharacterCamera::FirstPerson(FirstPersonCamera::new(1.65)),
+            height: 1.8,
+            radius: 0.35,
+            walk_speed: 4.0,
+            sprint_speed: 7.0,
+            jump_speed: 5.0,
+            actions: ActionMap::character(),
+            animations: None,
+            footsteps: Footsteps::new(Vec::new(), 1.6),
+        }
+    }
+
+    /// A third-person character: orbit camera, body turns toward movement
+    #[must_use]
+    pub fn third_person() -> Self {
+        Self {
+            camera: CharacterCamera::ThirdPerson(ThirdPersonCamera::new(1.5, 4.0)),
+            ..Self::first_person()
+        }
+    }
+
+    /// Set the entity name
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Set the capsule size
+    #[must_use]
+    pub fn with_size(mut self, height: f32, radius: f32) -> Self {
+        self.height = height;
+        self.radius = radius;
+        self
+    }
+
+    /// Set walk, sprint and jump speeds
+    #[must_use]
+    pub fn with_speeds(mut self, walk: f32, sprint: f32, jump: f32) -> Self {
+        self.walk_speed = walk;
+        self.sprint_speed = sprint;
+        self.jump_speed = jump;
+        self
+    }
+
+    /// Replace the input bindings
+    #[must_use]
+    pub fn with_actions(mut self, actions: ActionMap) -> Self {
+        self.actions = actions;
+        self
+    }
+
+    /// Set the locomotion clips
+    #[must_use]
+    pub fn with_animations(mut self, animations: CharacterAnimations) -> Self {
+        self.animations = Some(animations);
+        self
+    }
+
+    /// Set the footstep sounds
+    #[must_use]
+    pub fn with_footsteps(mut self, sounds: Vec<String>) -> Self {
+        self.footsteps.sounds = sounds;
+        self
+    }
+
+    /// Spawn the character with its feet at `position`
+    ///
+    /// The entity gets a `Name`, `Transform` (at the feet), `Character`
+    /// and `CharacterController`, plus an `AnimationPlayer` and
+    /// `AnimationStateMachine` when animations are set.
+    pub fn spawn(self, world: &mut World, physics: &mut Physics, position: Vec3) -> Entity {
+        let controller = CharacterController::spawn(physics, position, self.height, self.radius)
+            .with_speeds(self.walk_speed, self.sprint_speed)
+            .with_jump_speed(self.jump_speed);
+        let character = Character {
+            actions: self.actions,
+            camera: self.camera,
+            footsteps: self.footsteps,
+            controlled: true,
+            turn_speed: 10.0,
+            was_grounded: false,
+        };
+
+        let name = Name::new(self.name);
+        let transform = Transform::from_position(position);
+        match &self.animations {
+            Some(animations) => world.spawn((
+                name,
+                transform,
+                character,
+                controller,
+                AnimationPlayer::new(),
+                animations.state_machine(self.walk_speed, self.sprint_speed),
+            )),
+            None => world.spawn((name, transform, character, controller)),
+        }
+    }
+}
+
+/// Move, animate and play footsteps for every character
+///
+/// Controlled characters read input; the first one also drives `camera`.
+/// Call once per frame before `Physics::step`. Footsteps come from
+/// `footstep` animation events when the character is animated, otherwise
+/// from stride distance.
+pub fn update_characters(
+    world: &mut World,
+    physics: &mut Physics,
+    input: &Input,
+    camera: &mut Camera,
+    mut audio: Option<&mut AudioManager>,
+    dt: f32,
+) {
+    let mut camera_driven = false;
+
+    for (_, (transform, controller, character, player, machine)) in world.query_mut::<(
+        &mut Transform,
+        &mut CharacterController,
+        &mut Character,
+        Option<&mut AnimationPlayer>,
+        Option<&mut AnimationStateMachine>,
+    )>() {
+        let mut wish = Vec3::ZERO;
+        let mut jump = false;
+        if character.controlled {
+            let delta = input.mouse_delta();
+            match &mut character.camera {
+                CharacterCamera::FirstPerson(camera) => camera.look(delta),
+                CharacterCamera::ThirdPerson(camera) => camera.look(delta, input.scroll_delta().y),
+            }
+
+            let actions = &character.actions;
+            let local = Vec2::new(
+                actions.axis(input, ActionMap::MOVE_X),
+                actions.axis(input, ActionMap::MOVE_Z),
+            )
+            .clamp_length_max(1.0);
+            let speed = if actions.pressed(input, ActionMap::SPRINT) {
+                controller.sprint_speed
+            } else {
+                controller.walk_speed
+            };
+            let heading = Quat::from_rotation_y(character.camera.yaw());
+            wish = heading * Vec3::new(local.x, 0.0, -local.y) * speed;
+            jump = actions.just_pressed(input, ActionMap::JUMP);
+        }
+
+        controller.move_and_slide(physics, wish, jump, dt);
+        let velocity = controller.velocity();
+        let grounded = controller.is_grounded();
+
+        transform.position = controller.feet();
+        match &character.camera {
+            CharacterCamera::FirstPerson(camera) => {
+                transform.rotation = Quat::from_rotation_y(camera.yaw);
+            }
+            CharacterCamera::ThirdPerson(_) if velocity.length_squared() > 1e-4 => {
+                let target = Quat::from_rotation_y((-velocity.x).atan2(-velocity.z));
+                let t = (character.turn_speed * dt).min(1.0);
+                transform.rotation = transform.rotation.slerp(target, t);
+            }
+            CharacterCamera::ThirdPerson(_) => {}
+        }
+
+        if character.controlled && !camera_driven {
+            camera_driven = true;
+            match &character.camera {
+                CharacterCamera::FirstPerson(first) => first.apply(camera, transform.position),
+                CharacterCamera::ThirdPerson(third) => {
+                    third.apply(camera, transform.position, physics, controller);
+                }
+            }
+        }
+
+        let mut steps = 0;
+        if let (Some(player), Some(machine)) = (player, machine) {
+            machine.set_float(SPEED_PARAMETER, velocity.length());
+            machine.set_bool(AIRBORNE_PARAMETER, !grounded);
+            machine.update(player, dt);
+            steps = player
+                .drain_events()
+                .iter()
+                .filter(|event| event.name == FOOTSTEP_EVENT)
+                .count();
+            if !grounded {
+                steps = 0;
+            }
+        } else if grounded {
+            steps = usize::from(
+                character
+                    .footsteps
+                    .advance(velocity.length() * dt)
+                    .is_some(),
+            );
+        }
+        if grounded && !character.was_grounded {
+            // Landing always makes a sound
+            character.footsteps.reset();
+            steps = steps.max(1);
+        }
+        character.was_grounded = grounded;
+
+        for _ in 0..steps {
+            if let (Some(audio), Some(sound)) = (audio.as_deref_mut(), character.footsteps.step()) {
+                audio.play(sound);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_footsteps_by_stride() {
+        let mut footsteps = Footsteps::new(vec!["left".to_string(), "right".to_string()], 1.0);
+        assert_eq!(footsteps.advance(0.6), None);
+        assert_eq!(footsteps.advance(0.6), Some("left"));
+        assert_eq!(footsteps.advance(1.0), Some("right"));
+        assert_eq!(footsteps.advance(1.0), Some("left"));
+    }
+
+    #[test]
+    fn test_spawn_template() {
+        let mut world = World::new();
+        let mut physics = Physics::new();
+        let entity = CharacterTemplate::third_person().spawn(
+            &mut world,
+            &mut physics,
+            Vec3::new(1.0, 0.0, 2.0),
+        );
+
+        assert!(world.get::<Character>(entity).is_ok());
+        assert!(world.get::<AnimationPlayer>(entity).is_err());
+        let controller = world.get::<CharacterController>(entity).unwrap();
+        assert!((controller.feet() - Vec3::new(1.0, 0.0, 2.0)).length() < 1e-4);
+        assert_eq!(
+            physics.get_position(controller.body),
+            Some(Vec3::new(1.0, 0.9, 2.0))
+        );
+    }
+}
@@ -3,6 +3,11 @@
 use std::collections::VecDeque;
 use std::time::Duration;
 
+use hecs::Entity;
+
+use super::profiler::Profiler;
+use crate::ecs::{World, WorldStats};
+
 /// Frame statistics tracker
 #[derive(Debug)]
 pub struct FrameStats {
@@ -128,6 +133,10 @@ pub struct DebugInfo {
     pub enabled: bool,
     /// Frame statistics
     pub frame_stats: FrameStats,
+    /// Subsystem scope timings and budgets
+    pub profiler: Profiler,
+    /// ECS statistics lines, refreshed each frame while enabled
+    world_lines: Vec<String>,
     /// Custom debug lines
     custom_lines: Vec<String>,
 }
@@ -138,6 +147,8 @@ impl DebugInfo {
         Self {
             enabled: false,
             frame_stats: FrameStats::new(),
+            profiler: Profiler::new(),
+            world_lines: Vec::new(),
             custom_lines: Vec::new(),
         }
     }
@@ -152,6 +163,11 @@ impl DebugInfo {
         self.custom_lines.push(line.into());
     }
 
+    /// Add a custom debug line about an entity, prefixed with its name
+    pub fn add_entity_line(&mut self, world: &World, entity: Entity, detail: impl AsRef<str>) {
+        self.add_line(format!("{}: {}", world.label(entity), detail.as_ref()));
+    }
+
     /// Clear custom lines
     pub fn clear_lines(&mut self) {
         self.custom_lines.clear();
@@ -160,10 +176,17 @@ impl DebugInfo {
     /// Get all debug lines
     pub fn get_all_lines(&self) -> Vec<String> {
         let mut lines = vec![self.frame_stats.format_stats()];
+        lines.extend(self.profiler.lines());
+        lines.extend(self.world_lines.iter().cloned());
         lines.extend(self.custom_lines.iter().cloned());
         lines
     }
 
+    /// Show ECS statistics in the overlay
+    pub fn set_world_stats(&mut self, stats: &WorldStats) {
+        self.world_lines = stats.lines(5);
+    }
+
     /// Record a frame
     pub fn record_frame(&mut self, delta: Duration) {
         self.frame_stats.record_frame(delta);
@@ -0,0 +1,152 @@
+//! egui integration
+//!
+//! With the `egui` feature the engine runs an egui context next to the
+//! game for debug and editor panels. Window events are forwarded to it, a
+//! pass begins before `Game::update`, and the result is painted over the
+//! finished scene when the game calls `EngineContext::end_frame`.
+
+use std::sync::Arc;
+
+use winit::event::WindowEvent;
+use winit::window::Window;
+
+use crate::renderer::{RenderFrame, Renderer};
+
+/// egui context, window state and painter
+pub struct EguiLayer {
+    /// Context the game builds panels with
+    context: egui::Context,
+    /// Translates winit events into egui input
+    state: egui_winit::State,
+    /// Paints egui meshes with wgpu
+    painter: egui_wgpu::Renderer,
+    /// Window egui reads input from and sets the cursor on
+    window: Arc<Window>,
+    /// Whether a pass has begun and not yet ended
+    pass_open: bool,
+    /// Output of the last pass, waiting to be painted
+    output: Option<egui::FullOutput>,
+}
+
+impl EguiLayer {
+    /// Create the context for a window and the renderer drawing to it
+    pub(crate) fn new(window: Arc<Window>, renderer: &Renderer) -> Self {
+        let context = egui::Context::default();
+        let max_texture_side = renderer.device().limits().max_texture_dimension_2d as usize;
+        let state = egui_winit::State::new(
+            context.clone(),
+            egui::ViewportId::ROOT,
+            &window,
+            Some(window.scale_factor() as f32),
+            window.theme(),
+            Some(max_texture_side),
+        );
+        let painter =
+            egui_wgpu::Renderer::new(renderer.device(), renderer.surface_format(), None, 1, false);
+        Self {
+            context,
+            state,
+            painter,
+            window,
+            pass_open: false,
+            output: None,
+        }
+    }
+
+    /// Get the egui context
+    #[must_use]
+    pub fn context(&self) -> &egui::Context {
+        &self.context
+    }
+
+    /// Check if egui is using the pointer or keyboard, e.g. a panel is
+    /// hovered or a text field focused
+    #[must_use]
+    pub fn wants_input(&self) -> bool {
+        self.context.wants_pointer_input() || self.context.wants_keyboard_input()
+    }
+
+    /// Forward a window event, returning whether egui consumed it
+    pub(crate) fn on_window_event(&mut self, event: &WindowEvent) -> bool {
+        self.state.on_window_event(&self.window, event).consumed
+    }
+
+    /// Start a pass with the input gathered since the last one
+    pub(crate) fn begin_pass(&mut self) {
+        self.end_pass();
+        let input = self.state.take_egui_input(&self.window);
+        self.context.begin_pass(input);
+        self.pass_open = true;
+    }
+
+    /// Finish the open pass, keeping its output for `paint`
+    ///
+    /// Texture uploads from an unpainted earlier pass are carried over so
+    /// none are lost when a frame skips painting.
+    pub(crate) fn end_pass(&mut self) {
+        if !std::mem::take(&mut self.pass_open) {
+            return;
+        }
+        let mut output = self.context.end_pass();
+        let platform = std::mem::take(&mut output.platform_output);
+        self.state.handle_platform_output(&self.window, platform);
+        if let Some(previous) = self.output.take() {
+            let mut textures = previous.textures_delta;
+            textures.append(std::mem::take(&mut output.textures_delta));
+            output.textures_delta = textures;
+        }
+        self.output = Some(output);
+    }
+
+    /// Paint the last pass over a frame
+    pub(crate) fn paint(&mut self, renderer: &Renderer, frame: &mut RenderFrame) {
+        self.end_pass();
+        let Some(output) = self.output.take() else {
+            return;
+        };
+        let device = renderer.device();
+        let queue = renderer.queue();
+        let (width, height) = renderer.size();
+        let screen = egui_wgpu::ScreenDescriptor {
+            size_in_pixels: [width, height],
+            pixels_per_point: output.pixels_per_point,
+        };
+
+        for (id, delta) in &output.textures_delta.set {
+            self.painter.update_texture(device, queue, *id, delta);
+        }
+        let jobs = self
+            .context
+            .tessellate(output.shapes, output.pixels_per_point);
+        let (encoder, view) = frame.target();
+        let uploads = self
+            .painter
+            .update_buffers(device, queue, encoder, &jobs, &screen);
+        {
+            let mut pass = encoder
+                .begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("egui Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                })
+                .forget_lifetime();
+            self.painter.render(&mut pass, &jobs, &screen);
+        }
+        // Buffer uploads run before the frame's own commands
+        if !uploads.is_empty() {
+            queue.submit(uploads);
+        }
+        for id in &output.textures_delta.free {
+            self.painter.free_texture(id);
+        }
+    }
+}
@@ -12,9 +12,12 @@ use winit::{
 
 use crate::core::Time;
 use crate::core::debug::DebugInfo;
-use crate::ecs::World;
+#[cfg(feature = "egui")]
+use crate::core::egui_layer::EguiLayer;
+use crate::core::profiler::FrameBudget;
+use crate::ecs::{Schedule, Stage, System, World};
 use crate::input::Input;
-use crate::renderer::Renderer;
+use crate::renderer::{RenderFrame, Renderer};
 
 /// Engine configuration
 #[derive(Debug, Clone)]
@@ -29,6 +32,10 @@ pub struct EngineConfig {
     pub target_fps: u32,
     /// Enable VSync
     pub vsync: bool,
+    /// Per-subsystem frame budgets checked by the profiler
+    pub frame_budgets: Vec<FrameBudget>,
+    /// Consecutive over-budget frames before the profiler raises an alert
+    pub budget_alert_frames: u32,
 }
 
 impl Default for EngineConfig {
@@ -39,6 +46,8 @@ impl Default for EngineConfig {
             height: 720,
             target_fps: 60,
             vsync: true,
+            frame_budgets: Vec::new(),
+            budget_alert_frames: 3,
         }
     }
 }
@@ -68,6 +77,18 @@ impl EngineConfig {
         self.vsync = vsync;
         self
     }
+
+    /// Add a frame budget in milliseconds for a profiler scope
+    pub fn with_frame_budget(mut self, scope: impl Into<String>, budget_ms: f32) -> Self {
+        self.frame_budgets.push(FrameBudget::new(scope, budget_ms));
+        self
+    }
+
+    /// Set consecutive over-budget frames before an alert is raised
+    pub fn with_budget_alert_frames(mut self, frames: u32) -> Self {
+        self.budget_alert_frames = frames;
+        self
+    }
 }
 
 /// Game trait that users implement
@@ -96,6 +117,13 @@ pub struct EngineContext {
     pub input: Input,
     /// ECS world
     pub world: World,
+    /// Systems run around `Game::update` and before `Game::render`
+    ///
+    /// While systems run, `time` and `input` are moved into the world's
+    /// resources and moved back afterwards, so systems see and may change
+    /// the same values as `Game::update`. Games insert their own asset
+    /// server, physics world, and similar state as resources to share them.
+    pub schedule: Schedule,
     /// Debug information and stats
     pub debug: DebugInfo,
     /// Renderer (available after initialization)
@@ -104,6 +132,11 @@ pub struct EngineContext {
     window_size: PhysicalSize<u32>,
     /// Should the engine quit
     should_quit: bool,
+    /// Whether the window should accept IME composition
+    ime_allowed: bool,
+    /// egui debug UI (available after initialization)
+    #[cfg(feature = "egui")]
+    egui: Option<EguiLayer>,
 }
 
 impl EngineContext {
@@ -112,13 +145,43 @@ impl EngineContext {
             time: Time::new(),
             input: Input::new(),
             world: World::new(),
+            schedule: Self::default_schedule(),
             debug: DebugInfo::new(),
             renderer: None,
             window_size: PhysicalSize::new(width, height),
             should_quit: false,
+            ime_allowed: false,
+            #[cfg(feature = "egui")]
+            egui: None,
         }
     }
 
+    /// Schedule with the engine's own systems registered
+    ///
+    /// Transform propagation runs in `PostUpdate` as `"propagate_transforms"`,
+    /// so game systems can order themselves against it by name.
+    fn default_schedule() -> Schedule {
+        let mut schedule = Schedule::new();
+        let _ = schedule.add_system(
+            Stage::PostUpdate,
+            System::new("propagate_transforms", |world, _| {
+                world.propagate_transforms();
+            }),
+        );
+        schedule
+    }
+
+    /// Run systems with `time` and `input` lent to the world as resources
+    ///
+    /// A system that removes either resource resets it to its default.
+    fn run_systems(&mut self, run: impl FnOnce(&mut Schedule, &mut World)) {
+        self.world.insert_resource(std::mem::take(&mut self.time));
+        self.world.insert_resource(std::mem::take(&mut self.input));
+        run(&mut self.schedule, &mut self.world);
+        self.time = self.world.remove_resource().unwrap_or_default();
+        self.input = self.world.remove_resource().unwrap_or_default();
+    }
+
     /// Get the renderer
     pub fn renderer(&self) -> &Renderer {
         self.renderer.as_ref().expect("Renderer not initialized")
@@ -134,6 +197,39 @@ impl EngineContext {
         self.renderer.is_some()
     }
 
+    /// Submit and present a frame, drawing engine overlays such as egui
+    /// panels over it first
+    ///
+    /// Use this instead of `Renderer::end_frame` so the overlays show.
+    pub fn end_frame(&mut self, frame: RenderFrame) {
+        #[cfg(feature = "egui")]
+        let frame = self.paint_egui(frame);
+        self.renderer().end_frame(frame);
+    }
+
+    /// Paint this frame's egui output over the scene
+    #[cfg(feature = "egui")]
+    fn paint_egui(&mut self, mut frame: RenderFrame) -> RenderFrame {
+        if let (Some(egui), Some(renderer)) = (&mut self.egui, &self.renderer) {
+            egui.paint(renderer, &mut frame);
+        }
+        frame
+    }
+
+    /// Get the egui context for building debug panels during `Game::update`
+    /// or `Game::render`
+    #[cfg(feature = "egui")]
+    pub fn egui(&self) -> &egui::Context {
+        self.egui.as_ref().expect("egui not initialized").context()
+    }
+
+    /// Check if egui is using the mouse or keyboard, so the game can
+    /// ignore input meant for a panel
+    #[cfg(feature = "egui")]
+    pub fn egui_wants_input(&self) -> bool {
+        self.egui.as_ref().is_some_and(EguiLayer::wants_input)
+    }
+
     /// Get window width
     pub fn width(&self) -> u32 {
         self.window_size.width
@@ -149,6 +245,18 @@ impl EngineContext {
         self.window_size.width as f32 / self.window_size.height.max(1) as f32
     }
 
+    /// Enable input method composition while a text field is focused
+    ///
+    /// Leave it off otherwise so IME users' keys reach the game directly.
+    pub fn set_ime_allowed(&mut self, allowed: bool) {
+        self.ime_allowed = allowed;
+    }
+
+    /// Check if IME composition is enabled
+    pub fn ime_allowed(&self) -> bool {
+        self.ime_allowed
+    }
+
     /// Request engine shutdown
     pub fn quit(&mut self) {
         self.should_quit = true;
@@ -167,18 +275,28 @@ pub struct Engine<G: Game> {
     context: EngineContext,
     window: Option<Arc<Window>>,
     initialized: bool,
+    /// IME setting last applied to the window
+    ime_applied: bool,
 }
 
 impl<G: Game> Engine<G> {
     /// Create a new engine with the given game
     pub fn new(config: EngineConfig, game: G) -> Self {
-        let context = EngineContext::new(config.width, config.height);
+        let mut context = EngineContext::new(config.width, config.height);
+        for budget in &config.frame_budgets {
+            context.debug.profiler.set_budget(budget);
+        }
+        context
+            .debug
+            .profiler
+            .set_alert_frames(config.budget_alert_frames);
         Self {
             config,
             game,
             context,
             window: None,
             initialized: false,
+            ime_applied: false,
         }
     }
 
@@ -214,6 +332,10 @@ impl<G: Game> ApplicationHandler for Engine<G> {
         // Initialize renderer
         let renderer = pollster::block_on(Renderer::new(Arc::clone(&window), self.config.vsync));
 
+        #[cfg(feature = "egui")]
+        {
+            self.context.egui = Some(EguiLayer::new(Arc::clone(&window), &renderer));
+        }
         self.context.renderer = Some(renderer);
         self.window = Some(window);
 
@@ -231,6 +353,17 @@ impl<G: Game> ApplicationHandler for Engine<G> {
         _window_id: WindowId,
         event: WindowEvent,
     ) {
+        // Presses egui takes stay away from the game; releases always reach
+        // it so no key is left held
+        #[cfg(feature = "egui")]
+        let consumed = self
+            .context
+            .egui
+            .as_mut()
+            .is_some_and(|egui| egui.on_window_event(&event));
+        #[cfg(not(feature = "egui"))]
+        let consumed = false;
+
         match event {
             WindowEvent::CloseRequested => {
                 log::info!("Close requested, shutting down");
@@ -238,24 +371,31 @@ impl<G: Game> ApplicationHandler for Engine<G> {
                 event_loop.exit();
             }
 
-            WindowEvent::Resized(new_size) => {
-                if new_size.width > 0 && new_size.height > 0 {
-                    self.context.window_size = new_size;
-                    if let Some(renderer) = &mut self.context.renderer {
-                        renderer.resize(new_size.width, new_size.height);
-                    }
-                    self.game
-                        .on_resize(&mut self.context, new_size.width, new_size.height);
+            WindowEvent::Resized(new_size) if new_size.width > 0 && new_size.height > 0 => {
+                self.context.window_size = new_size;
+                if let Some(renderer) = &mut self.context.renderer {
+                    renderer.resize(new_size.width, new_size.height);
                 }
+                self.game
+                    .on_resize(&mut self.context, new_size.width, new_size.height);
             }
 
-            WindowEvent::KeyboardInput { event, .. } => {
+            WindowEvent::KeyboardInput { event, .. } if !consumed || !event.state.is_pressed() => {
                 if let winit::keyboard::PhysicalKey::Code(key_code) = event.physical_key {
                     self.context.input.process_keyboard(key_code, event.state);
                 }
+                if event.state.is_pressed()
+                    && let Some(text) = &event.text
+                {
+                    self.context.input.process_text(text);
+                }
             }
 
-            WindowEvent::MouseInput { state, button, .. } => {
+            WindowEvent::Ime(ime) if !consumed => {
+                self.context.input.process_ime(ime);
+            }
+
+            WindowEvent::MouseInput { state, button, .. } if !consumed || !state.is_pressed() => {
                 self.context.input.process_mouse_button(button, state);
             }
 
@@ -265,7 +405,7 @@ impl<G: Game> ApplicationHandler for Engine<G> {
                     .process_mouse_motion(glam::Vec2::new(position.x as f32, position.y as f32));
             }
 
-            WindowEvent::MouseWheel { delta, .. } => {
+            WindowEvent::MouseWheel { delta, .. } if !consumed => {
                 let scroll = match delta {
                     winit::event::MouseScrollDelta::LineDelta(x, y) => glam::Vec2::new(x, y),
                     winit::event::MouseScrollDelta::PixelDelta(pos) => {
@@ -282,8 +422,25 @@ impl<G: Game> ApplicationHandler for Engine<G> {
                 // Update debug stats
                 self.context.debug.record_frame(self.context.time.delta());
 
+                #[cfg(feature = "egui")]
+                if let Some(egui) = &mut self.context.egui {
+                    egui.begin_pass();
+                }
+
                 // Update game logic
+                let dt = self.context.time.delta_seconds();
+                self.context.run_systems(|schedule, world| {
+                    schedule.run_stage(Stage::PreUpdate, world, dt);
+                });
                 self.game.update(&mut self.context);
+                self.context
+                    .run_systems(|schedule, world| schedule.run_update(world, dt));
+                if self.context.ime_allowed != self.ime_applied
+                    && let Some(window) = &self.window
+                {
+                    window.set_ime_allowed(self.context.ime_allowed);
+                    self.ime_applied = self.context.ime_allowed;
+                }
 
                 // Check if should quit
                 if self.context.should_quit() {
@@ -293,8 +450,21 @@ impl<G: Game> ApplicationHandler for Engine<G> {
                 }
 
                 // Render
+                self.context.run_systems(|schedule, world| {
+                    schedule.run_stage(Stage::Render, world, dt);
+                });
                 self.game.render(&mut self.context);
 
+                // Check subsystem budgets
+                self.context.debug.profiler.end_frame();
+
+                // Refresh ECS stats for the overlay
+                if self.context.debug.enabled {
+                    let stats = self.context.world.stats();
+                    self.context.debug.set_world_stats(&stats);
+                }
+                self.context.world.end_frame();
+
                 // Clear per-frame input state
                 self.context.input.update();
 
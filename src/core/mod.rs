@@ -2,12 +2,26 @@
 //!
 //! Contains the main Engine struct and configuration
 
+mod character;
 mod debug;
+#[cfg(feature = "egui")]
+mod egui_layer;
 mod engine;
+mod profiler;
+mod report;
 mod scene;
 mod time;
 
+pub use character::{
+    AIRBORNE_PARAMETER, Character, CharacterAnimations, CharacterCamera, CharacterTemplate,
+    FOOTSTEP_EVENT, FirstPersonCamera, Footsteps, SPEED_PARAMETER, ThirdPersonCamera,
+    update_characters,
+};
 pub use debug::{DebugInfo, FrameStats};
+#[cfg(feature = "egui")]
+pub use egui_layer::EguiLayer;
 pub use engine::{Engine, EngineConfig, EngineContext, Game};
+pub use profiler::{BudgetAlert, FrameBudget, Profiler};
+pub use report::{SceneReport, SceneWarning};
 pub use scene::{Scene, SceneError, SerializedEntity};
 pub use time::Time;
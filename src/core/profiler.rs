@@ -0,0 +1,258 @@
+//! Hierarchical frame profiler with budget alerts
+//!
+//! Times nested subsystem scopes each frame and raises alerts when a scope
+//! stays over its budget for several consecutive frames.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Separator between nested scope names
+const PATH_SEPARATOR: char = '/';
+
+/// A per-subsystem frame budget
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameBudget {
+    /// Scope path, e.g. `"physics"` or `"render/particles"`
+    pub scope: String,
+    /// Allowed time per frame in milliseconds
+    pub budget_ms: f32,
+}
+
+impl FrameBudget {
+    /// Create a budget for a scope
+    #[must_use]
+    pub fn new(scope: impl Into<String>, budget_ms: f32) -> Self {
+        Self {
+            scope: scope.into(),
+            budget_ms,
+        }
+    }
+}
+
+/// Raised when a scope exceeds its budget for the configured number of frames
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetAlert {
+    /// Scope path
+    pub scope: String,
+    /// Budget in milliseconds
+    pub budget_ms: f32,
+    /// Time spent this frame in milliseconds
+    pub actual_ms: f32,
+    /// Consecutive frames over budget
+    pub frames: u32,
+}
+
+impl std::fmt::Display for BudgetAlert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} over budget for {} frames ({:.2}ms / {:.2}ms)",
+            self.scope, self.frames, self.actual_ms, self.budget_ms
+        )
+    }
+}
+
+/// Timing state of one scope
+#[derive(Debug, Clone, Default)]
+struct ScopeStats {
+    /// Time accumulated this frame
+    current: Duration,
+    /// Time spent last frame
+    last: Duration,
+    /// Budget, if any
+    budget: Option<Duration>,
+    /// Consecutive frames over budget
+    over_frames: u32,
+}
+
+/// Hierarchical scope profiler
+#[derive(Debug)]
+pub struct Profiler {
+    /// Stats by scope path
+    scopes: HashMap<String, ScopeStats>,
+    /// Open scopes (path, start time)
+    stack: Vec<(String, Instant)>,
+    /// Consecutive over-budget frames before alerting
+    alert_frames: u32,
+    /// Alerts raised since the last drain
+    alerts: Vec<BudgetAlert>,
+}
+
+impl Profiler {
+    /// Create a profiler with no budgets
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            scopes: HashMap::new(),
+            stack: Vec::new(),
+            alert_frames: 3,
+            alerts: Vec::new(),
+        }
+    }
+
+    /// Set a scope's budget
+    pub fn set_budget(&mut self, budget: &FrameBudget) {
+        let stats = self.scopes.entry(budget.scope.clone()).or_default();
+        stats.budget = Some(Duration::from_secs_f32(budget.budget_ms.max(0.0) / 1000.0));
+    }
+
+    /// Set how many consecutive over-budget frames raise an alert
+    pub fn set_alert_frames(&mut self, frames: u32) {
+        self.alert_frames = frames.max(1);
+    }
+
+    /// Open a scope nested inside the current one
+    pub fn begin(&mut self, name: &str) {
+        let path = match self.stack.last() {
+            Some((parent, _)) => format!("{parent}{PATH_SEPARATOR}{name}"),
+            None => name.to_string(),
+        };
+        self.stack.push((path, Instant::now()));
+    }
+
+    /// Close the innermost scope
+    pub fn end(&mut self) {
+        if let Some((path, start)) = self.stack.pop() {
+            self.record(&path, start.elapsed());
+        }
+    }
+
+    /// Time a closure as a nested scope
+    pub fn scope<R>(&mut self, name: &str, f: impl FnOnce() -> R) -> R {
+        self.begin(name);
+        let result = f();
+        self.end();
+        result
+    }
+
+    /// Add time to a scope path directly
+    pub fn record(&mut self, path: &str, elapsed: Duration) {
+        self.scopes.entry(path.to_string()).or_default().current += elapsed;
+    }
+
+    /// Finish the frame, checking budgets
+    pub fn end_frame(&mut self) {
+        self.stack.clear();
+        let mut paths: Vec<&String> = self.scopes.keys().collect();
+        paths.sort();
+        let paths: Vec<String> = paths.into_iter().cloned().collect();
+
+        for path in paths {
+            let stats = self.scopes.get_mut(&path).expect("path collected from map");
+            stats.last = std::mem::take(&mut stats.current);
+            let Some(budget) = stats.budget else {
+                continue;
+            };
+            if stats.last <= budget {
+                stats.over_frames = 0;
+                continue;
+            }
+            stats.over_frames += 1;
+            if stats.over_frames == self.alert_frames {
+                let alert = BudgetAlert {
+                    scope: path,
+                    budget_ms: budget.as_secs_f32() * 1000.0,
+                    actual_ms: stats.last.as_secs_f32() * 1000.0,
+                    frames: stats.over_frames,
+                };
+                log::warn!("Frame budget: {alert}");
+                self.alerts.push(alert);
+            }
+        }
+    }
+
+    /// Get last frame's time for a scope in milliseconds
+    #[must_use]
+    pub fn last_ms(&self, path: &str) -> f32 {
+        self.scopes
+            .get(path)
+            .map_or(0.0, |s| s.last.as_secs_f32() * 1000.0)
+    }
+
+    /// Check if a scope has been over budget long enough to alert
+    #[must_use]
+    pub fn is_over_budget(&self, path: &str) -> bool {
+        self.scopes
+            .get(path)
+            .is_some_and(|s| s.over_frames >= self.alert_frames)
+    }
+
+    /// Take all alerts raised since the last call
+    pub fn drain_alerts(&mut self) -> Vec<BudgetAlert> {
+        std::mem::take(&mut self.alerts)
+    }
+
+    /// Format last frame's timings as indented lines
+    ///
+    /// Scopes currently over budget are marked with `!` for on-screen highlighting.
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        let mut paths: Vec<&String> = self.scopes.keys().collect();
+        paths.sort();
+        paths
+            .into_iter()
+            .map(|path| {
+                let stats = &self.scopes[path];
+                let depth = path.matches(PATH_SEPARATOR).count();
+                let name = path.rsplit(PATH_SEPARATOR).next().unwrap_or(path);
+                let ms = stats.last.as_secs_f32() * 1000.0;
+                let marker = if self.is_over_budget(path) { " !" } else { "" };
+                match stats.budget {
+                    Some(budget) => format!(
+                        "{}{name}: {ms:.2}ms / {:.2}ms{marker}",
+                        "  ".repeat(depth),
+                        budget.as_secs_f32() * 1000.0
+                    ),
+                    None => format!("{}{name}: {ms:.2}ms", "  ".repeat(depth)),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alert_after_consecutive_frames() {
+        let mut profiler = Profiler::new();
+        profiler.set_budget(&FrameBudget::new("physics", 3.0));
+        profiler.set_alert_frames(2);
+
+        profiler.record("physics", Duration::from_millis(5));
+        profiler.end_frame();
+        assert!(profiler.drain_alerts().is_empty());
+
+        profiler.record("physics", Duration::from_millis(5));
+        profiler.end_frame();
+        let alerts = profiler.drain_alerts();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].scope, "physics");
+        assert!(profiler.is_over_budget("physics"));
+
+        profiler.record("physics", Duration::from_millis(1));
+        profiler.end_frame();
+        assert!(!profiler.is_over_budget("physics"));
+    }
+
+    #[test]
+    fn test_nested_scope_paths() {
+        let mut profiler = Profiler::new();
+        profiler.begin("render");
+        profiler.scope("particles", || {});
+        profiler.end();
+        profiler.end_frame();
+
+        let lines = profiler.lines();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("render:"));
+        assert!(lines[1].starts_with("  particles:"));
+    }
+}
@@ -0,0 +1,252 @@
+//! Scene statistics and content validation
+//!
+//! Scans a world for common content problems after loading.
+
+use hecs::Entity;
+
+use crate::assets::AssetHandle;
+use crate::ecs::{Name, Transform, World};
+use crate::physics::{ColliderHandle, RigidBodyHandle};
+use crate::renderer::{Material, Mesh, Renderer, Texture};
+
+/// Tolerance used when checking for non-uniform scale
+const SCALE_EPSILON: f32 = 1e-4;
+
+/// A single content problem found in a scene
+#[derive(Debug, Clone, PartialEq)]
+pub enum SceneWarning {
+    /// Mesh has no vertices or non-finite positions, so no bounds can be computed
+    MeshWithoutBounds {
+        /// Offending entity
+        entity: Entity,
+    },
+    /// Material expects a texture but the entity has none attached
+    MissingTexture {
+        /// Offending entity
+        entity: Entity,
+    },
+    /// Collider component present without a rigid body
+    ColliderWithoutBody {
+        /// Offending entity
+        entity: Entity,
+    },
+    /// Physics shape on a non-uniformly scaled transform
+    NonUniformScaledCollider {
+        /// Offending entity
+        entity: Entity,
+        /// The transform scale
+        scale: glam::Vec3,
+    },
+    /// Texture larger than the allowed dimension
+    OversizedTexture {
+        /// Offending entity
+        entity: Entity,
+        /// Texture width
+        width: u32,
+        /// Texture height
+        height: u32,
+    },
+}
+
+impl SceneWarning {
+    /// Get the entity this warning refers to
+    #[must_use]
+    pub const fn entity(&self) -> Entity {
+        match self {
+            Self::MeshWithoutBounds { entity }
+            | Self::MissingTexture { entity }
+            | Self::ColliderWithoutBody { entity }
+            | Self::NonUniformScaledCollider { entity, .. }
+            | Self::OversizedTexture { entity, .. } => *entity,
+        }
+    }
+}
+
+impl std::fmt::Display for SceneWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MeshWithoutBounds { entity } => {
+                write!(f, "{entity:?}: mesh has no valid bounds")
+            }
+            Self::MissingTexture { entity } => {
+                write!(f, "{entity:?}: material references a missing texture")
+            }
+            Self::ColliderWithoutBody { entity } => {
+                write!(f, "{entity:?}: collider has no rigid body")
+            }
+            Self::NonUniformScaledCollider { entity, scale } => {
+                write!(f, "{entity:?}: collider on non-uniform scale {scale}")
+            }
+            Self::OversizedTexture {
+                entity,
+                width,
+                height,
+            } => write!(f, "{entity:?}: texture is oversized ({width}x{height})"),
+        }
+    }
+}
+
+/// Statistics and validation results for a scene
+#[derive(Debug, Clone, Default)]
+pub struct SceneReport {
+    /// Total number of entities
+    pub entity_count: u32,
+    /// Entities with a mesh
+    pub mesh_count: usize,
+    /// Entities with a material
+    pub material_count: usize,
+    /// Entities with a collider
+    pub collider_count: usize,
+    /// Entities with a texture
+    pub texture_count: usize,
+    /// Problems found
+    pub warnings: Vec<SceneWarning>,
+}
+
+impl SceneReport {
+    /// Analyze a world using the renderer's texture size limit
+    #[must_use]
+    pub fn analyze(world: &World, renderer: &Renderer) -> Self {
+        let max_texture_size = renderer.device().limits().max_texture_dimension_2d;
+        Self::analyze_with_limits(world, max_texture_size)
+    }
+
+    /// Analyze a world with an explicit maximum texture dimension
+    #[must_use]
+    pub fn analyze_with_limits(world: &World, max_texture_size: u32) -> Self {
+        let mut report = Self {
+            entity_count: world.len(),
+            ..Default::default()
+        };
+
+        for (entity, mesh) in world.query::<&Mesh>().iter() {
+            report.mesh_count += 1;
+            if mesh.bounds().is_none() {
+                report
+                    .warnings
+                    .push(SceneWarning::MeshWithoutBounds { entity });
+            }
+        }
+
+        for (entity, (material, texture)) in world
+            .query::<(&Material, Option<&AssetHandle<Texture>>)>()
+            .iter()
+        {
+            report.material_count += 1;
+            if material.use_texture && texture.is_none() {
+                report
+                    .warnings
+                    .push(SceneWarning::MissingTexture { entity });
+            }
+        }
+
+        for (entity, (_, body, transform)) in world
+            .query::<(
+                &ColliderHandle,
+                Option<&RigidBodyHandle>,
+                Option<&Transform>,
+            )>()
+            .iter()
+        {
+            report.collider_count += 1;
+            if body.is_none() {
+                report
+                    .warnings
+                    .push(SceneWarning::ColliderWithoutBody { entity });
+            }
+            if let Some(transform) = transform {
+                let scale = transform.scale;
+                if (scale.x - scale.y).abs() > SCALE_EPSILON
+                    || (scale.x - scale.z).abs() > SCALE_EPSILON
+                {
+                    report
+                        .warnings
+                        .push(SceneWarning::NonUniformScaledCollider { entity, scale });
+                }
+            }
+        }
+
+        for (entity, texture) in world.query::<&AssetHandle<Texture>>().iter() {
+            report.texture_count += 1;
+            let (width, height) = (texture.width(), texture.height());
+            if width > max_texture_size || height > max_texture_size {
+                report.warnings.push(SceneWarning::OversizedTexture {
+                    entity,
+                    width,
+                    height,
+                });
+            }
+        }
+
+        report
+    }
+
+    /// Check if no problems were found
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    /// Format the report as lines, using entity names where available
+    #[must_use]
+    pub fn lines(&self, world: &World) -> Vec<String> {
+        let mut lines = vec![format!(
+            "Entities: {} | Meshes: {} | Materials: {} | Colliders: {} | Textures: {}",
+            self.entity_count,
+            self.mesh_count,
+            self.material_count,
+            self.collider_count,
+            self.texture_count
+        )];
+
+        for warning in &self.warnings {
+            match world.get::<Name>(warning.entity()) {
+                Ok(name) => lines.push(format!("[{}] {warning}", name.0)),
+                Err(_) => lines.push(warning.to_string()),
+            }
+        }
+
+        lines
+    }
+
+    /// Print all warnings to the log
+    pub fn log_warnings(&self) {
+        for warning in &self.warnings {
+            log::warn!("Scene content: {warning}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+
+    #[test]
+    fn test_empty_mesh_warning() {
+        let mut world = World::new();
+        let empty = world.spawn((Mesh::new(),));
+        world.spawn((Mesh::cube(),));
+
+        let report = SceneReport::analyze_with_limits(&world, 4096);
+        assert_eq!(report.mesh_count, 2);
+        assert_eq!(
+            report.warnings,
+            vec![SceneWarning::MeshWithoutBounds { entity: empty }]
+        );
+    }
+
+    #[test]
+    fn test_material_missing_texture() {
+        let mut world = World::new();
+        let mut material = Material::new(Vec3::ONE);
+        material.use_texture = true;
+        let entity = world.spawn((material,));
+
+        let report = SceneReport::analyze_with_limits(&world, 4096);
+        assert_eq!(
+            report.warnings,
+            vec![SceneWarning::MissingTexture { entity }]
+        );
+    }
+}
@@ -7,6 +7,7 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+use crate::assets::AssetUuid;
 use crate::ecs::{Transform, Velocity};
 
 /// A serializable entity with its components
@@ -14,6 +15,9 @@ use crate::ecs::{Transform, Velocity};
 pub struct SerializedEntity {
     /// Optional entity name
     pub name: Option<String>,
+    /// Tags for grouping and lookup
+    #[serde(default)]
+    pub tags: Vec<String>,
     /// Transform component
     pub transform: Option<Transform>,
     /// Velocity component
@@ -25,17 +29,22 @@ pub struct SerializedEntity {
     /// Custom data as key-value pairs
     #[serde(default)]
     pub custom_data: std::collections::HashMap<String, String>,
+    /// Asset references by slot, stored as stable IDs so they survive moves
+    #[serde(default)]
+    pub asset_refs: std::collections::HashMap<String, AssetUuid>,
 }
 
 impl Default for SerializedEntity {
     fn default() -> Self {
         Self {
             name: None,
+            tags: Vec::new(),
             transform: Some(Transform::default()),
             velocity: None,
             parent_index: None,
             children_indices: Vec::new(),
             custom_data: std::collections::HashMap::new(),
+            asset_refs: std::collections::HashMap::new(),
         }
     }
 }
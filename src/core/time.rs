@@ -3,7 +3,7 @@
 use std::time::{Duration, Instant};
 
 /// Tracks time between frames and total elapsed time
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Time {
     /// Time since engine started
     start_time: Instant,
@@ -57,6 +57,16 @@ impl Transform {
         self.rotation * Vec3::Y
     }
 
+    /// Combine with a child transform (`self` is the parent)
+    #[must_use]
+    pub fn mul_transform(&self, child: &Transform) -> Transform {
+        Transform {
+            position: self.position + self.rotation * (self.scale * child.position),
+            rotation: self.rotation * child.rotation,
+            scale: self.scale * child.scale,
+        }
+    }
+
     /// Translate by a delta
     pub fn translate(&mut self, delta: Vec3) {
         self.position += delta;
@@ -97,12 +107,99 @@ pub struct Velocity {
     pub angular: Vec3,
 }
 
-/// Name component for debugging
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Human-readable entity name, used for lookup and debug display
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Name(pub String);
 
 impl Name {
+    /// Create a name
     pub fn new(name: impl Into<String>) -> Self {
         Self(name.into())
     }
+
+    /// Get the name as a string slice
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// String tags for grouping entities, such as "enemy" or "interactable"
+///
+/// For tags only code needs, a zero-sized marker component queried with
+/// `World::query` is cheaper; these are for tags authored in scene files.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tags(pub Vec<String>);
+
+impl Tags {
+    /// Create an empty tag set
+    #[must_use]
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Add a tag, builder style
+    #[must_use]
+    pub fn with(mut self, tag: impl Into<String>) -> Self {
+        self.add(tag);
+        self
+    }
+
+    /// Add a tag if not already present
+    pub fn add(&mut self, tag: impl Into<String>) {
+        let tag = tag.into();
+        if !self.has(&tag) {
+            self.0.push(tag);
+        }
+    }
+
+    /// Remove a tag, returning whether it was present
+    pub fn remove(&mut self, tag: &str) -> bool {
+        let len = self.0.len();
+        self.0.retain(|t| t != tag);
+        self.0.len() != len
+    }
+
+    /// Check if a tag is present
+    #[must_use]
+    pub fn has(&self, tag: &str) -> bool {
+        self.0.iter().any(|t| t == tag)
+    }
+
+    /// Iterate over tags
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::World;
+
+    #[test]
+    fn test_find_by_name_and_tag() {
+        let mut world = World::new();
+        let player = world.spawn((Name::new("Player"), Tags::new().with("hero")));
+        let goblin = world.spawn((Name::new("Goblin"), Tags::new().with("enemy")));
+        let orc = world.spawn((Tags::new().with("enemy").with("enemy"),));
+
+        assert_eq!(world.find_by_name("Player"), Some(player));
+        assert_eq!(world.find_by_name("Nobody"), None);
+        let mut enemies = world.find_by_tag("enemy");
+        enemies.sort();
+        let mut expected = vec![goblin, orc];
+        expected.sort();
+        assert_eq!(enemies, expected);
+        assert_eq!(world.get::<Tags>(orc).unwrap().0.len(), 1);
+
+        assert_eq!(world.label(player), format!("Player ({player:?})"));
+        assert_eq!(world.label(orc), format!("{orc:?}"));
+    }
 }
@@ -0,0 +1,218 @@
+//! Typed event channels
+//!
+//! Lets systems communicate through queued events instead of sharing
+//! mutable state. Events stay readable for the frame they were sent in and
+//! the following one, so a reader that runs earlier in the frame than the
+//! writer still sees every event exactly once.
+
+use std::any::Any;
+
+/// Double-buffered queue of events of one type
+#[derive(Debug)]
+pub struct Events<T> {
+    /// Events sent during the previous frame
+    previous: Vec<T>,
+    /// Events sent during the current frame
+    current: Vec<T>,
+    /// Id of the first event in `previous`
+    previous_start: usize,
+    /// Id of the first event in `current`
+    current_start: usize,
+}
+
+impl<T> Events<T> {
+    /// Create an empty channel
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            previous: Vec::new(),
+            current: Vec::new(),
+            previous_start: 0,
+            current_start: 0,
+        }
+    }
+
+    /// Queue an event
+    pub fn send(&mut self, event: T) {
+        self.current.push(event);
+    }
+
+    /// Get a writer for queueing events
+    pub fn writer(&mut self) -> EventWriter<'_, T> {
+        EventWriter { events: self }
+    }
+
+    /// Create a reader that only sees events sent from now on
+    #[must_use]
+    pub fn reader(&self) -> EventReader<T> {
+        EventReader {
+            next: self.next_id(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Number of events still buffered
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.previous.len() + self.current.len()
+    }
+
+    /// Check if no events are buffered
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop all buffered events
+    pub fn clear(&mut self) {
+        self.previous_start = self.next_id();
+        self.current_start = self.previous_start;
+        self.previous.clear();
+        self.current.clear();
+    }
+
+    /// Finish the frame, dropping events sent two frames ago
+    pub fn update(&mut self) {
+        self.previous_start = self.current_start;
+        self.current_start = self.next_id();
+        self.previous = std::mem::take(&mut self.current);
+    }
+
+    /// Id the next sent event will get
+    fn next_id(&self) -> usize {
+        self.current_start + self.current.len()
+    }
+}
+
+impl<T> Default for Events<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Handle for queueing events into a channel
+#[derive(Debug)]
+pub struct EventWriter<'a, T> {
+    /// Channel being written to
+    events: &'a mut Events<T>,
+}
+
+impl<T> EventWriter<'_, T> {
+    /// Queue an event
+    pub fn send(&mut self, event: T) {
+        self.events.send(event);
+    }
+
+    /// Queue several events in order
+    pub fn send_batch(&mut self, events: impl IntoIterator<Item = T>) {
+        self.events.current.extend(events);
+    }
+}
+
+/// Cursor tracking which events a consumer has already seen
+///
+/// Each system keeps its own reader, so several systems can consume the
+/// same channel independently.
+#[derive(Debug)]
+pub struct EventReader<T> {
+    /// Id of the next unread event
+    next: usize,
+    /// Ties the cursor to one event type
+    _marker: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> EventReader<T> {
+    /// Create a reader that sees every event still buffered
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            next: 0,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Iterate over unread events, marking them read
+    pub fn read<'a>(&mut self, events: &'a Events<T>) -> impl Iterator<Item = &'a T> + use<'a, T> {
+        let start = self.next.max(events.previous_start);
+        let skip_previous = start - events.previous_start;
+        let skip_current = start.saturating_sub(events.current_start);
+        self.next = events.next_id();
+        events
+            .previous
+            .iter()
+            .skip(skip_previous)
+            .chain(events.current.iter().skip(skip_current))
+    }
+
+    /// Number of unread events in the channel
+    #[must_use]
+    pub fn unread(&self, events: &Events<T>) -> usize {
+        events.next_id() - self.next.max(events.previous_start)
+    }
+
+    /// Mark every buffered event as read without visiting them
+    pub fn skip_all(&mut self, events: &Events<T>) {
+        self.next = events.next_id();
+    }
+}
+
+impl<T> Default for EventReader<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Type-erased channel so the world can update every channel at frame end
+pub(crate) trait EventChannel: Any {
+    /// Finish the frame for this channel
+    fn update(&mut self);
+    /// Upcast for downcasting to the concrete channel
+    fn as_any(&self) -> &dyn Any;
+    /// Mutable upcast for downcasting to the concrete channel
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: 'static> EventChannel for Events<T> {
+    fn update(&mut self) {
+        Events::update(self);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_live_two_frames() {
+        let mut events = Events::new();
+        let mut early = EventReader::new();
+        let mut late = events.reader();
+
+        events.send(1);
+        events.writer().send_batch([2, 3]);
+        assert_eq!(late.read(&events).copied().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(late.unread(&events), 0);
+
+        // The early reader runs next frame and still catches up
+        events.update();
+        events.send(4);
+        assert_eq!(early.unread(&events), 4);
+        assert_eq!(early.read(&events).count(), 4);
+        assert_eq!(late.read(&events).copied().collect::<Vec<_>>(), [4]);
+
+        // Events from two frames ago are gone
+        events.update();
+        events.update();
+        assert!(events.is_empty());
+        let mut fresh = EventReader::new();
+        assert_eq!(fresh.read(&events).count(), 0);
+    }
+}
@@ -0,0 +1,152 @@
+//! Spawning glTF scenes into the world
+//!
+//! Flattens a loaded glTF node hierarchy into entities with transforms,
+//! hierarchy links, meshes, materials, cameras, and lights.
+
+use std::collections::HashMap;
+
+use glam::Mat4;
+use hecs::Entity;
+
+use super::components::{Name, Transform};
+use super::hierarchy::{Children, GlobalTransform, Parent};
+use super::world::World;
+use crate::assets::LoadedGltf;
+
+/// Spawn every node reachable from the scene roots
+///
+/// Returns a map from glTF node index to spawned entity.
+pub(crate) fn spawn_gltf(world: &mut World, gltf: &LoadedGltf) -> HashMap<usize, Entity> {
+    let mut entities = HashMap::new();
+    let mut stack: Vec<(usize, Option<Entity>, Mat4)> = gltf
+        .root_nodes
+        .iter()
+        .rev()
+        .map(|&root| (root, None, Mat4::IDENTITY))
+        .collect();
+
+    while let Some((index, parent, parent_matrix)) = stack.pop() {
+        let Some(node) = gltf.nodes.get(index) else {
+            continue;
+        };
+        if entities.contains_key(&index) {
+            log::warn!("glTF node {index} is referenced more than once, skipping");
+            continue;
+        }
+
+        let transform = Transform {
+            position: node.translation,
+            rotation: node.rotation,
+            scale: node.scale,
+        };
+        let global = parent_matrix * transform.matrix();
+        let entity = world.spawn((
+            transform,
+            GlobalTransform::new(global),
+            Name::new(node.name.clone()),
+        ));
+        entities.insert(index, entity);
+
+        if let Some(parent) = parent {
+            link(world, parent, entity);
+        }
+
+        if let Some(mesh) = node.mesh_index.and_then(|m| gltf.meshes.get(m)) {
+            for (primitive_index, primitive) in mesh.primitives.iter().enumerate() {
+                // Extra primitives become children so each entity has one mesh
+                let target = if primitive_index == 0 {
+                    entity
+                } else {
+                    let child = world.spawn((
+                        Transform::new(),
+                        GlobalTransform::new(global),
+                        Name::new(format!("{}#{primitive_index}", node.name)),
+                    ));
+                    link(world, entity, child);
+                    child
+                };
+
+                let _ = world.inner.insert_one(target, primitive.to_mesh());
+                if let Some(morph) = primitive.to_morph_targets(&mesh.weights) {
+                    let _ = world
+                        .inner
+                        .insert_one(target, morph.with_animation_target(index));
+                }
+                if let Some(material) = primitive.material_index.and_then(|m| gltf.materials.get(m))
+                {
+                    let _ = world.inner.insert_one(target, material.to_material());
+                }
+            }
+        }
+
+        if let Some(camera) = node.camera_index.and_then(|c| gltf.cameras.get(c)) {
+            let _ = world.inner.insert_one(entity, camera.clone());
+        }
+        if let Some(light) = node.light_index.and_then(|l| gltf.lights.get(l)) {
+            let _ = world.inner.insert_one(entity, light.clone());
+        }
+
+        for &child in node.children.iter().rev() {
+            stack.push((child, Some(entity), global));
+        }
+    }
+
+    entities
+}
+
+/// Add a parent-child link between two entities
+fn link(world: &mut World, parent: Entity, child: Entity) {
+    let _ = world.inner.insert_one(child, Parent::new(parent));
+    if let Ok(mut children) = world.get_mut::<Children>(parent) {
+        children.add(child);
+        return;
+    }
+    let _ = world.inner.insert_one(parent, Children::single(child));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::LoadedNode;
+    use glam::{Quat, Vec3};
+
+    fn node(name: &str, translation: Vec3, children: Vec<usize>) -> LoadedNode {
+        LoadedNode {
+            name: name.to_string(),
+            translation,
+            rotation: Quat::IDENTITY,
+            scale: Vec3::ONE,
+            mesh_index: None,
+            camera_index: None,
+            light_index: None,
+            children,
+        }
+    }
+
+    #[test]
+    fn test_spawn_hierarchy() {
+        let gltf = LoadedGltf {
+            meshes: Vec::new(),
+            materials: Vec::new(),
+            cameras: Vec::new(),
+            lights: Vec::new(),
+            nodes: vec![
+                node("Root", Vec3::X, vec![1]),
+                node("Child", Vec3::Y, Vec::new()),
+            ],
+            root_nodes: vec![0],
+            animations: Vec::new(),
+        };
+
+        let mut world = World::new();
+        let entities = spawn_gltf(&mut world, &gltf);
+
+        assert_eq!(entities.len(), 2);
+        let child = entities[&1];
+        assert_eq!(world.get::<Parent>(child).unwrap().entity(), entities[&0]);
+        assert_eq!(world.get::<Name>(child).unwrap().0, "Child");
+
+        let global = world.get::<GlobalTransform>(child).unwrap();
+        assert!((global.position() - Vec3::new(1.0, 1.0, 0.0)).length() < 0.001);
+    }
+}
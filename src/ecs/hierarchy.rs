@@ -4,8 +4,12 @@
 
 use glam::{Mat4, Quat, Vec3};
 use hecs::Entity;
+use rustc_hash::FxHashSet;
 use smallvec::SmallVec;
 
+use super::components::Transform;
+use super::world::World;
+
 /// Parent component - indicates this entity has a parent
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Parent(pub Entity);
@@ -149,6 +153,49 @@ impl Default for GlobalTransform {
     }
 }
 
+/// Compute `GlobalTransform` for every entity from its local `Transform`
+///
+/// Walks each hierarchy from its roots (entities with a `Transform` and no
+/// `Parent`), so children always see their parent's result from this frame.
+/// Entities without a `Transform` pass their parent's matrix through, and
+/// missing `GlobalTransform` components are inserted. Run once per frame
+/// after gameplay and physics have moved things, before rendering.
+pub fn propagate_transforms(world: &mut World) {
+    let mut stack: Vec<(Entity, Mat4)> = world
+        .query::<&Transform>()
+        .without::<&Parent>()
+        .iter()
+        .map(|(entity, _)| (entity, Mat4::IDENTITY))
+        .collect();
+
+    let mut visited = FxHashSet::default();
+    let mut resolved = Vec::new();
+
+    while let Some((entity, parent)) = stack.pop() {
+        // Guard against cycles left behind by bad Parent/Children edits
+        if !visited.insert(entity) {
+            continue;
+        }
+        let local = world
+            .get::<Transform>(entity)
+            .map_or(Mat4::IDENTITY, |t| t.matrix());
+        let global = parent * local;
+        resolved.push((entity, global));
+
+        if let Ok(children) = world.get::<Children>(entity) {
+            stack.extend(children.iter().map(|&child| (child, global)));
+        }
+    }
+
+    for (entity, matrix) in resolved {
+        if let Ok(mut global) = world.get_mut::<GlobalTransform>(entity) {
+            global.matrix = matrix;
+        } else {
+            let _ = world.inner.insert_one(entity, GlobalTransform::new(matrix));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,6 +220,31 @@ mod tests {
         assert_eq!(children.len(), 1);
     }
 
+    #[test]
+    fn test_propagate_transforms() {
+        let mut world = World::new();
+        let turret = world.spawn((Transform::from_position(Vec3::new(10.0, 0.0, 0.0)),));
+        let barrel = world.spawn((Transform::from_position(Vec3::new(0.0, 2.0, 0.0)),));
+        let muzzle = world.spawn((Transform::from_position(Vec3::new(0.0, 0.0, 3.0)),));
+        world.set_parent(barrel, turret);
+        world.set_parent(muzzle, barrel);
+
+        propagate_transforms(&mut world);
+        let pos = world.get::<GlobalTransform>(muzzle).unwrap().position();
+        assert!((pos - Vec3::new(10.0, 2.0, 3.0)).length() < 1e-4);
+
+        world.get_mut::<Transform>(turret).unwrap().position.x = -5.0;
+        propagate_transforms(&mut world);
+        let pos = world.get::<GlobalTransform>(muzzle).unwrap().position();
+        assert!((pos - Vec3::new(-5.0, 2.0, 3.0)).length() < 1e-4);
+
+        world.remove_parent(barrel);
+        propagate_transforms(&mut world);
+        let pos = world.get::<GlobalTransform>(muzzle).unwrap().position();
+        assert!((pos - Vec3::new(0.0, 2.0, 3.0)).length() < 1e-4);
+        assert!(world.get::<Children>(turret).unwrap().is_empty());
+    }
+
     #[test]
     fn test_global_transform() {
         let transform =
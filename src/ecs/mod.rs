@@ -3,9 +3,20 @@
 //! Built on top of the hecs ECS library
 
 mod components;
+mod events;
+mod gltf_scene;
 mod hierarchy;
+mod prefab;
+mod resources;
+mod schedule;
+mod stats;
 mod world;
 
-pub use components::{Name, Transform, Velocity};
-pub use hierarchy::{Children, GlobalTransform, Parent};
+pub use components::{Name, Tags, Transform, Velocity};
+pub use events::{EventReader, EventWriter, Events};
+pub use hierarchy::{Children, GlobalTransform, Parent, propagate_transforms};
+pub use prefab::Prefab;
+pub use resources::Resources;
+pub use schedule::{Schedule, ScheduleError, Stage, System};
+pub use stats::{ArchetypeStats, WorldStats};
 pub use world::World;
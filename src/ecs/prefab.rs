@@ -0,0 +1,247 @@
+//! Prefab assets
+//!
+//! A prefab is a reusable bundle of serialized entities that can be
+//! instantiated into a world many times.
+
+use std::fs;
+use std::path::Path;
+
+use glam::Mat4;
+use hecs::Entity;
+use serde::{Deserialize, Serialize};
+
+use super::components::{Name, Tags, Transform};
+use super::hierarchy::{Children, GlobalTransform, Parent};
+use super::world::World;
+use crate::assets::AssetRefs;
+use crate::core::{Scene, SceneError, SerializedEntity};
+
+/// A serialized bundle of entities with their hierarchy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Prefab {
+    /// Prefab name
+    pub name: String,
+    /// Entities in the prefab (parent/child links are indices into this list)
+    pub entities: Vec<SerializedEntity>,
+}
+
+impl Prefab {
+    /// Create a new empty prefab
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            entities: Vec::new(),
+        }
+    }
+
+    /// Create a prefab from a scene
+    #[must_use]
+    pub fn from_scene(scene: &Scene) -> Self {
+        Self {
+            name: scene.name.clone(),
+            entities: scene.entities.clone(),
+        }
+    }
+
+    /// Add an entity to the prefab
+    pub fn add_entity(&mut self, entity: SerializedEntity) -> usize {
+        let index = self.entities.len();
+        self.entities.push(entity);
+        index
+    }
+
+    /// Load a prefab from a RON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or deserialization fails
+    pub fn load_ron(path: impl AsRef<Path>) -> Result<Self, SceneError> {
+        let content = fs::read_to_string(path).map_err(|e| SceneError::IoError(e.to_string()))?;
+        ron::from_str(&content).map_err(|e| SceneError::DeserializeError(e.to_string()))
+    }
+
+    /// Save the prefab to a RON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written or serialization fails
+    pub fn save_ron(&self, path: impl AsRef<Path>) -> Result<(), SceneError> {
+        let ron_string = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| SceneError::SerializeError(e.to_string()))?;
+        fs::write(path, ron_string).map_err(|e| SceneError::IoError(e.to_string()))
+    }
+
+    /// Instantiate the prefab into a world
+    ///
+    /// Root entities (those without a parent) are placed relative to
+    /// `instance`. The hierarchy is rebuilt from each entity's
+    /// `parent_index`; `children_indices` is ignored. Returns the spawned
+    /// entities in prefab order.
+    pub fn instantiate(&self, world: &mut World, instance: Transform) -> Vec<Entity> {
+        let entities: Vec<Entity> = self
+            .entities
+            .iter()
+            .map(|serialized| {
+                let local = serialized.transform.unwrap_or_default();
+                let transform = if serialized.parent_index.is_none() {
+                    instance.mul_transform(&local)
+                } else {
+                    local
+                };
+
+                let entity = world.spawn((transform, GlobalTransform::new(transform.matrix())));
+                if let Some(name) = &serialized.name {
+                    let _ = world.inner.insert_one(entity, Name::new(name.clone()));
+                }
+                if !serialized.tags.is_empty() {
+                    let tags = Tags(serialized.tags.clone());
+                    let _ = world.inner.insert_one(entity, tags);
+                }
+                if let Some(velocity) = serialized.velocity {
+                    let _ = world.inner.insert_one(entity, velocity);
+                }
+                if !serialized.asset_refs.is_empty() {
+                    let refs = AssetRefs(serialized.asset_refs.clone());
+                    let _ = world.inner.insert_one(entity, refs);
+                }
+                entity
+            })
+            .collect();
+
+        // Link hierarchy once all entities exist, deriving children from parents
+        let mut children = vec![Vec::new(); entities.len()];
+        for (index, serialized) in self.entities.iter().enumerate() {
+            if let Some(parent) = serialized.parent_index
+                && parent != index
+                && let Some(&parent_entity) = entities.get(parent)
+            {
+                let _ = world
+                    .inner
+                    .insert_one(entities[index], Parent::new(parent_entity));
+                children[parent].push(index);
+            }
+        }
+        for (index, child_indices) in children.iter().enumerate() {
+            if !child_indices.is_empty() {
+                let linked = Children(child_indices.iter().map(|&c| entities[c]).collect());
+                let _ = world.inner.insert_one(entities[index], linked);
+            }
+        }
+
+        // Compose globals from the roots down so children are placed before
+        // the next propagation pass
+        let mut stack: Vec<(usize, Mat4)> = self
+            .entities
+            .iter()
+            .enumerate()
+            .filter(|(_, serialized)| serialized.parent_index.is_none_or(|p| p >= entities.len()))
+            .map(|(index, _)| (index, Mat4::IDENTITY))
+            .collect();
+        let mut visited = vec![false; entities.len()];
+        while let Some((index, parent)) = stack.pop() {
+            if std::mem::replace(&mut visited[index], true) {
+                continue;
+            }
+            let Ok(local) = world.get::<Transform>(entities[index]).map(|t| t.matrix()) else {
+                continue;
+            };
+            let global = parent * local;
+            if let Ok(mut transform) = world.get_mut::<GlobalTransform>(entities[index]) {
+                transform.matrix = global;
+            }
+            stack.extend(children[index].iter().map(|&child| (child, global)));
+        }
+
+        entities
+    }
+}
+
+impl Default for Prefab {
+    fn default() -> Self {
+        Self::new("Untitled")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::Assets;
+    use glam::Vec3;
+
+    #[test]
+    fn test_spawn_prefab_with_override() {
+        let mut prefab = Prefab::new("Enemy");
+        prefab.add_entity(SerializedEntity {
+            name: Some("Body".to_string()),
+            children_indices: vec![1],
+            ..Default::default()
+        });
+        prefab.add_entity(SerializedEntity {
+            name: Some("Weapon".to_string()),
+            transform: Some(Transform::from_position(Vec3::X)),
+            parent_index: Some(0),
+            ..Default::default()
+        });
+
+        let mut assets = Assets::<Prefab>::new();
+        let handle = assets.add(prefab);
+
+        let mut world = World::new();
+        let spawned =
+            world.spawn_prefab(&handle, Transform::from_position(Vec3::new(0.0, 5.0, 0.0)));
+
+        assert_eq!(spawned.len(), 2);
+        let root = world.get::<Transform>(spawned[0]).unwrap();
+        assert!((root.position.y - 5.0).abs() < 0.001);
+
+        // Child keeps its local transform and links to the root
+        let child = world.get::<Transform>(spawned[1]).unwrap();
+        assert!((child.position - Vec3::X).length() < 0.001);
+        assert_eq!(
+            world.get::<Parent>(spawned[1]).unwrap().entity(),
+            spawned[0]
+        );
+        assert_eq!(world.get::<Children>(spawned[0]).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_instantiate_composes_child_globals() {
+        let mut prefab = Prefab::new("Turret");
+        prefab.add_entity(SerializedEntity {
+            name: Some("Base".to_string()),
+            // Stale children list; the hierarchy comes from parent_index
+            children_indices: vec![2],
+            ..Default::default()
+        });
+        prefab.add_entity(SerializedEntity {
+            name: Some("Barrel".to_string()),
+            transform: Some(Transform::from_position(Vec3::new(0.0, 2.0, 0.0))),
+            parent_index: Some(0),
+            ..Default::default()
+        });
+        prefab.add_entity(SerializedEntity {
+            name: Some("Muzzle".to_string()),
+            transform: Some(Transform::from_position(Vec3::new(0.0, 0.0, 3.0))),
+            parent_index: Some(1),
+            ..Default::default()
+        });
+
+        let mut world = World::new();
+        let spawned = prefab.instantiate(&mut world, Transform::from_position(Vec3::X * 10.0));
+
+        let muzzle = world.get::<GlobalTransform>(spawned[2]).unwrap().position();
+        assert!((muzzle - Vec3::new(10.0, 2.0, 3.0)).length() < 1e-4);
+
+        let base_children = world.get::<Children>(spawned[0]).unwrap();
+        assert_eq!(
+            base_children.iter().copied().collect::<Vec<_>>(),
+            [spawned[1]]
+        );
+        let barrel_children = world.get::<Children>(spawned[1]).unwrap();
+        assert_eq!(
+            barrel_children.iter().copied().collect::<Vec<_>>(),
+            [spawned[2]]
+        );
+    }
+}
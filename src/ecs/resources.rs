@@ -0,0 +1,120 @@
+//! Global resources
+//!
+//! Singleton data stored on the world by type, so systems can reach shared
+//! state such as time, input, assets, or physics without extra arguments.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+/// Type-keyed store holding at most one value per type
+#[derive(Default)]
+pub struct Resources {
+    /// Values keyed by their type
+    values: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl Resources {
+    /// Create an empty store
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a resource, returning the one it replaced
+    pub fn insert<T: 'static>(&mut self, value: T) -> Option<T> {
+        self.values
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    /// Remove a resource and return it
+    pub fn remove<T: 'static>(&mut self) -> Option<T> {
+        self.values
+            .remove(&TypeId::of::<T>())
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    /// Check if a resource of type `T` is stored
+    #[must_use]
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Get a resource by type
+    #[must_use]
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Get a resource mutably by type
+    pub fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.values
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_mut())
+    }
+
+    /// Get a resource, inserting one made by `init` if missing
+    pub fn get_or_insert_with<T: 'static>(&mut self, init: impl FnOnce() -> T) -> &mut T {
+        self.values
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(init()))
+            .downcast_mut()
+            .expect("Resource stored under the wrong type")
+    }
+
+    /// Number of stored resources
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Check if no resources are stored
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Remove every resource
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+impl std::fmt::Debug for Resources {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Resources")
+            .field("len", &self.values.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Score(u32);
+
+    #[test]
+    fn test_resources_by_type() {
+        let mut resources = Resources::new();
+        assert!(resources.insert(Score(1)).is_none());
+        assert_eq!(resources.insert(Score(5)), Some(Score(1)));
+        resources.insert(0.5f32);
+
+        resources.get_mut::<Score>().unwrap().0 += 1;
+        assert_eq!(resources.get::<Score>(), Some(&Score(6)));
+        assert_eq!(resources.get::<f32>(), Some(&0.5));
+        assert!(resources.get::<u8>().is_none());
+
+        *resources.get_or_insert_with(|| 3u8) += 1;
+        assert_eq!(resources.get::<u8>(), Some(&4));
+        assert_eq!(resources.remove::<Score>(), Some(Score(6)));
+        assert!(!resources.contains::<Score>());
+        assert_eq!(resources.len(), 2);
+    }
+}
@@ -0,0 +1,374 @@
+//! System scheduler
+//!
+//! Groups systems into stages that run in a fixed order each frame, with
+//! named `before`/`after` constraints deciding the order inside a stage.
+
+use super::world::World;
+
+/// Frame stage a system runs in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Stage {
+    /// Before game logic: input mapping, spawning queued entities
+    PreUpdate,
+    /// Per-frame game logic
+    Update,
+    /// Fixed-rate simulation, run zero or more times per frame
+    FixedUpdate,
+    /// After game logic: transform propagation, cleanup
+    PostUpdate,
+    /// Preparing draw data, run just before the game renders
+    Render,
+}
+
+impl Stage {
+    /// All stages in execution order
+    pub const ALL: [Stage; 5] = [
+        Stage::PreUpdate,
+        Stage::Update,
+        Stage::FixedUpdate,
+        Stage::PostUpdate,
+        Stage::Render,
+    ];
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// System body: the world and the step length in seconds
+type SystemFn = Box<dyn FnMut(&mut World, f32)>;
+
+/// A named system with ordering constraints
+///
+/// Systems receive the world and the step length in seconds, which is the
+/// fixed timestep in `Stage::FixedUpdate` and the frame delta elsewhere.
+pub struct System {
+    /// Unique system name, used by ordering constraints
+    name: String,
+    /// Systems this one must run before
+    before: Vec<String>,
+    /// Systems this one must run after
+    after: Vec<String>,
+    /// System body
+    run: SystemFn,
+}
+
+impl System {
+    /// Create a system from a name and function
+    #[must_use]
+    pub fn new(name: impl Into<String>, run: impl FnMut(&mut World, f32) + 'static) -> Self {
+        Self {
+            name: name.into(),
+            before: Vec::new(),
+            after: Vec::new(),
+            run: Box::new(run),
+        }
+    }
+
+    /// Run before the named system when both are in the same stage
+    #[must_use]
+    pub fn before(mut self, name: impl Into<String>) -> Self {
+        self.before.push(name.into());
+        self
+    }
+
+    /// Run after the named system when both are in the same stage
+    #[must_use]
+    pub fn after(mut self, name: impl Into<String>) -> Self {
+        self.after.push(name.into());
+        self
+    }
+
+    /// Get the system name
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl std::fmt::Debug for System {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("System")
+            .field("name", &self.name)
+            .field("before", &self.before)
+            .field("after", &self.after)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Errors from registering systems
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// A system with this name is already registered
+    DuplicateSystem(String),
+    /// Ordering constraints form a cycle through these systems
+    Cycle(Vec<String>),
+}
+
+impl std::fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicateSystem(name) => write!(f, "System already registered: {name}"),
+            Self::Cycle(names) => write!(f, "System ordering cycle: {}", names.join(", ")),
+        }
+    }
+}
+
+impl std::error::Error for ScheduleError {}
+
+/// Systems registered into one stage, kept in execution order
+#[derive(Debug, Default)]
+struct StageSystems {
+    /// Systems sorted so every constraint is satisfied
+    systems: Vec<System>,
+}
+
+impl StageSystems {
+    /// Insert a system and re-sort, leaving the stage unchanged on a cycle
+    fn insert(&mut self, system: System) -> Result<(), ScheduleError> {
+        self.systems.push(system);
+        match sort_systems(&self.systems) {
+            Ok(order) => {
+                let mut slots: Vec<Option<System>> = self.systems.drain(..).map(Some).collect();
+                self.systems = order.into_iter().filter_map(|i| slots[i].take()).collect();
+                Ok(())
+            }
+            Err(err) => {
+                self.systems.pop();
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Topologically sort systems, keeping registration order where unconstrained
+///
+/// Constraints naming systems outside the list are ignored, so a system can
+/// order itself against optional systems that may not be registered.
+fn sort_systems(systems: &[System]) -> Result<Vec<usize>, ScheduleError> {
+    let index_of = |name: &str| systems.iter().position(|s| s.name == name);
+    let count = systems.len();
+    let mut edges: Vec<Vec<usize>> = vec![Vec::new(); count];
+    let mut incoming = vec![0usize; count];
+
+    for (i, system) in systems.iter().enumerate() {
+        for target in system.before.iter().filter_map(|n| index_of(n)) {
+            edges[i].push(target);
+            incoming[target] += 1;
+        }
+        for source in system.after.iter().filter_map(|n| index_of(n)) {
+            edges[source].push(i);
+            incoming[i] += 1;
+        }
+    }
+
+    let mut order = Vec::with_capacity(count);
+    let mut done = vec![false; count];
+    while order.len() < count {
+        // Lowest ready index first keeps registration order stable
+        let Some(next) = (0..count).find(|&i| !done[i] && incoming[i] == 0) else {
+            let names = (0..count)
+                .filter(|&i| !done[i])
+                .map(|i| systems[i].name.clone())
+                .collect();
+            return Err(ScheduleError::Cycle(names));
+        };
+        done[next] = true;
+        order.push(next);
+        for &target in &edges[next] {
+            incoming[target] -= 1;
+        }
+    }
+    Ok(order)
+}
+
+/// Runs registered systems stage by stage
+///
+/// `Stage::FixedUpdate` accumulates frame time and steps at a fixed rate,
+/// capped at `max_fixed_steps` per frame so a long hitch can't spiral.
+#[derive(Debug)]
+pub struct Schedule {
+    /// Systems per stage, indexed by `Stage`
+    stages: [StageSystems; 5],
+    /// Fixed update step in seconds
+    fixed_timestep: f32,
+    /// Most fixed steps run in a single frame
+    max_fixed_steps: u32,
+    /// Unsimulated time carried between frames
+    accumulator: f32,
+}
+
+impl Schedule {
+    /// Create an empty schedule stepping fixed updates at 60 Hz
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            stages: Default::default(),
+            fixed_timestep: 1.0 / 60.0,
+            max_fixed_steps: 8,
+            accumulator: 0.0,
+        }
+    }
+
+    /// Set the fixed update step in seconds
+    #[must_use]
+    pub fn with_fixed_timestep(mut self, step: f32) -> Self {
+        self.fixed_timestep = step.max(1e-4);
+        self
+    }
+
+    /// Set the most fixed steps run in a single frame
+    #[must_use]
+    pub fn with_max_fixed_steps(mut self, steps: u32) -> Self {
+        self.max_fixed_steps = steps.max(1);
+        self
+    }
+
+    /// Get the fixed update step in seconds
+    #[must_use]
+    pub fn fixed_timestep(&self) -> f32 {
+        self.fixed_timestep
+    }
+
+    /// Fraction of a fixed step left in the accumulator, for interpolation
+    #[must_use]
+    pub fn fixed_alpha(&self) -> f32 {
+        self.accumulator / self.fixed_timestep
+    }
+
+    /// Register a system into a stage
+    ///
+    /// Fails if the name is taken in any stage or the system's constraints
+    /// create a cycle, in which case the schedule is left unchanged.
+    pub fn add_system(&mut self, stage: Stage, system: System) -> Result<(), ScheduleError> {
+        if self.contains(&system.name) {
+            return Err(ScheduleError::DuplicateSystem(system.name));
+        }
+        self.stages[stage.index()].insert(system)
+    }
+
+    /// Remove a system by name, returning whether it was registered
+    pub fn remove_system(&mut self, name: &str) -> bool {
+        for stage in &mut self.stages {
+            if let Some(pos) = stage.systems.iter().position(|s| s.name == name) {
+                stage.systems.remove(pos);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Check whether a system is registered
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.stages
+            .iter()
+            .any(|stage| stage.systems.iter().any(|s| s.name == name))
+    }
+
+    /// Names of a stage's systems in execution order
+    pub fn system_names(&self, stage: Stage) -> impl Iterator<Item = &str> {
+        self.stages[stage.index()].systems.iter().map(System::name)
+    }
+
+    /// Run every system in one stage once
+    ///
+    /// `Stage::FixedUpdate` is run once with `dt` as given; use `run_update`
+    /// for accumulated fixed stepping.
+    pub fn run_stage(&mut self, stage: Stage, world: &mut World, dt: f32) {
+        for system in &mut self.stages[stage.index()].systems {
+            (system.run)(world, dt);
+        }
+    }
+
+    /// Run `Update`, as many `FixedUpdate` steps as `dt` covers, then `PostUpdate`
+    pub fn run_update(&mut self, world: &mut World, dt: f32) {
+        self.run_stage(Stage::Update, world, dt);
+
+        self.accumulator += dt.max(0.0);
+        let mut steps = 0;
+        while self.accumulator >= self.fixed_timestep && steps < self.max_fixed_steps {
+            let step = self.fixed_timestep;
+            self.run_stage(Stage::FixedUpdate, world, step);
+            self.accumulator -= step;
+            steps += 1;
+        }
+        // Drop time we couldn't catch up on rather than carrying it forward
+        self.accumulator = self.accumulator.min(self.fixed_timestep);
+
+        self.run_stage(Stage::PostUpdate, world, dt);
+    }
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn logger(log: &Rc<RefCell<Vec<&'static str>>>, name: &'static str) -> System {
+        let log = Rc::clone(log);
+        System::new(name, move |_, _| log.borrow_mut().push(name))
+    }
+
+    #[test]
+    fn test_stage_and_constraint_order() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system(Stage::PostUpdate, logger(&log, "cleanup"))
+            .unwrap();
+        schedule
+            .add_system(Stage::Update, logger(&log, "ai").after("movement"))
+            .unwrap();
+        schedule
+            .add_system(Stage::Update, logger(&log, "movement"))
+            .unwrap();
+        schedule
+            .add_system(Stage::Update, logger(&log, "input").before("movement"))
+            .unwrap();
+
+        let mut world = World::new();
+        schedule.run_update(&mut world, 0.0);
+        assert_eq!(*log.borrow(), ["input", "movement", "ai", "cleanup"]);
+
+        assert_eq!(
+            schedule.add_system(Stage::Update, logger(&log, "ai")),
+            Err(ScheduleError::DuplicateSystem("ai".to_string()))
+        );
+        let cyclic = logger(&log, "late").before("input").after("ai");
+        assert!(matches!(
+            schedule.add_system(Stage::Update, cyclic),
+            Err(ScheduleError::Cycle(_))
+        ));
+        assert!(!schedule.contains("late"));
+    }
+
+    #[test]
+    fn test_fixed_update_steps() {
+        let steps = Rc::new(RefCell::new(0));
+        let counter = Rc::clone(&steps);
+        let mut schedule = Schedule::new().with_fixed_timestep(0.1);
+        schedule
+            .add_system(
+                Stage::FixedUpdate,
+                System::new("physics", move |_, dt| {
+                    assert!((dt - 0.1).abs() < 1e-6);
+                    *counter.borrow_mut() += 1;
+                }),
+            )
+            .unwrap();
+
+        let mut world = World::new();
+        schedule.run_update(&mut world, 0.25);
+        assert_eq!(*steps.borrow(), 2);
+        schedule.run_update(&mut world, 0.06);
+        assert_eq!(*steps.borrow(), 3);
+    }
+}
@@ -0,0 +1,174 @@
+//! ECS statistics and memory introspection
+//!
+//! Reports archetype layout, estimated component memory, and entity churn.
+
+use std::any::TypeId;
+use std::collections::HashMap;
+
+/// Size and name of a registered component type
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ComponentInfo {
+    /// Short type name
+    pub name: &'static str,
+    /// Size of one component in bytes
+    pub size: usize,
+}
+
+impl ComponentInfo {
+    /// Describe a component type
+    pub fn of<T: 'static>() -> Self {
+        let full = std::any::type_name::<T>();
+        Self {
+            name: full.rsplit("::").next().unwrap_or(full),
+            size: std::mem::size_of::<T>(),
+        }
+    }
+}
+
+/// Spawn and despawn counters
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Churn {
+    /// Spawns since the last frame ended
+    pub spawns: u32,
+    /// Despawns since the last frame ended
+    pub despawns: u32,
+    /// Spawns in the last finished frame
+    pub last_spawns: u32,
+    /// Despawns in the last finished frame
+    pub last_despawns: u32,
+}
+
+impl Churn {
+    /// Roll the current counters into the last-frame values
+    pub fn end_frame(&mut self) {
+        self.last_spawns = std::mem::take(&mut self.spawns);
+        self.last_despawns = std::mem::take(&mut self.despawns);
+    }
+}
+
+/// Statistics for one archetype (a unique set of component types)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchetypeStats {
+    /// Entities stored in this archetype
+    pub entity_count: u32,
+    /// Names of registered component types, sorted
+    pub components: Vec<&'static str>,
+    /// Number of component types not registered with the world
+    pub unknown_components: usize,
+    /// Estimated bytes used by registered components
+    pub bytes: usize,
+}
+
+/// Statistics for a whole world
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorldStats {
+    /// Total live entities
+    pub entity_count: u32,
+    /// Non-empty archetypes, largest first
+    pub archetypes: Vec<ArchetypeStats>,
+    /// Archetypes with no entities (a sign of fragmentation)
+    pub empty_archetypes: usize,
+    /// Estimated bytes used by registered components
+    pub component_bytes: usize,
+    /// Entities spawned in the last frame
+    pub spawns_last_frame: u32,
+    /// Entities despawned in the last frame
+    pub despawns_last_frame: u32,
+}
+
+impl WorldStats {
+    /// Collect statistics from a hecs world
+    pub(crate) fn collect(
+        world: &hecs::World,
+        registry: &HashMap<TypeId, ComponentInfo>,
+        churn: Churn,
+    ) -> Self {
+        let mut stats = Self {
+            entity_count: world.len(),
+            spawns_last_frame: churn.last_spawns,
+            despawns_last_frame: churn.last_despawns,
+            ..Default::default()
+        };
+
+        for archetype in world.archetypes() {
+            if archetype.is_empty() {
+                stats.empty_archetypes += 1;
+                continue;
+            }
+            let mut archetype_stats = ArchetypeStats {
+                entity_count: archetype.len(),
+                components: Vec::new(),
+                unknown_components: 0,
+                bytes: 0,
+            };
+            for type_id in archetype.component_types() {
+                match registry.get(&type_id) {
+                    Some(info) => {
+                        archetype_stats.components.push(info.name);
+                        archetype_stats.bytes += info.size * archetype.len() as usize;
+                    }
+                    None => archetype_stats.unknown_components += 1,
+                }
+            }
+            archetype_stats.components.sort_unstable();
+            stats.component_bytes += archetype_stats.bytes;
+            stats.archetypes.push(archetype_stats);
+        }
+
+        stats
+            .archetypes
+            .sort_by_key(|archetype| std::cmp::Reverse(archetype.entity_count));
+        stats
+    }
+
+    /// Format a summary and the largest archetypes for the debug overlay
+    #[must_use]
+    pub fn lines(&self, max_archetypes: usize) -> Vec<String> {
+        let mut lines = vec![format!(
+            "ECS: {} entities | {} archetypes ({} empty) | {:.1} KiB | +{} -{} /frame",
+            self.entity_count,
+            self.archetypes.len(),
+            self.empty_archetypes,
+            self.component_bytes as f32 / 1024.0,
+            self.spawns_last_frame,
+            self.despawns_last_frame
+        )];
+        for archetype in self.archetypes.iter().take(max_archetypes) {
+            let unknown = if archetype.unknown_components > 0 {
+                format!(" +{} unregistered", archetype.unknown_components)
+            } else {
+                String::new()
+            };
+            lines.push(format!(
+                "  {} x [{}]{unknown}",
+                archetype.entity_count,
+                archetype.components.join(", ")
+            ));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ecs::{Name, Transform, World};
+
+    #[test]
+    fn test_archetype_counts_and_churn() {
+        let mut world = World::new();
+        world.spawn((Transform::new(),));
+        world.spawn((Transform::new(),));
+        let named = world.spawn((Transform::new(), Name::new("a")));
+        world.despawn(named).unwrap();
+        world.spawn((Transform::new(), Name::new("b")));
+        world.end_frame();
+
+        let stats = world.stats();
+        assert_eq!(stats.entity_count, 3);
+        assert_eq!(stats.archetypes[0].entity_count, 2);
+        assert_eq!(stats.archetypes[0].components, vec!["Transform"]);
+        assert_eq!(stats.archetypes[1].components, vec!["Name", "Transform"]);
+        assert_eq!(stats.spawns_last_frame, 4);
+        assert_eq!(stats.despawns_last_frame, 1);
+    }
+}
@@ -1,29 +1,129 @@
 //! World wrapper around hecs
 
+use std::any::TypeId;
+use std::collections::HashMap;
+
 use hecs::Entity;
 
+use super::components::{Name, Tags, Transform, Velocity};
+use super::events::{EventChannel, Events};
+use super::hierarchy::{Children, GlobalTransform, Parent};
+use super::prefab::Prefab;
+use super::resources::Resources;
+use super::stats::{Churn, ComponentInfo, WorldStats};
+use crate::assets::{AssetHandle, LoadedGltf};
+
 /// Game world containing all entities and components
 pub struct World {
     /// The underlying hecs world
     pub inner: hecs::World,
+    /// Component types known to `stats`
+    components: HashMap<TypeId, ComponentInfo>,
+    /// Spawn and despawn counters
+    churn: Churn,
+    /// Event channels, keyed by event type
+    events: HashMap<TypeId, Box<dyn EventChannel>>,
+    /// Singleton data shared between systems
+    resources: Resources,
 }
 
 impl World {
     /// Create a new empty world
     pub fn new() -> Self {
-        Self {
+        let mut world = Self {
             inner: hecs::World::new(),
-        }
+            components: HashMap::new(),
+            churn: Churn::default(),
+            events: HashMap::new(),
+            resources: Resources::new(),
+        };
+        world.register_component::<Transform>();
+        world.register_component::<GlobalTransform>();
+        world.register_component::<Velocity>();
+        world.register_component::<Name>();
+        world.register_component::<Tags>();
+        world.register_component::<Parent>();
+        world.register_component::<Children>();
+        world
+    }
+
+    /// Register a component type so `stats` can report its name and memory
+    pub fn register_component<T: hecs::Component>(&mut self) {
+        self.components
+            .insert(TypeId::of::<T>(), ComponentInfo::of::<T>());
     }
 
     /// Spawn an entity with the given components
     pub fn spawn(&mut self, components: impl hecs::DynamicBundle) -> Entity {
+        self.churn.spawns += 1;
         self.inner.spawn(components)
     }
 
     /// Despawn an entity
     pub fn despawn(&mut self, entity: Entity) -> Result<(), hecs::NoSuchEntity> {
-        self.inner.despawn(entity)
+        self.inner.despawn(entity)?;
+        self.churn.despawns += 1;
+        Ok(())
+    }
+
+    /// Get archetype, memory, and churn statistics
+    ///
+    /// Memory covers registered component types only, and churn counts
+    /// spawns and despawns made through this wrapper rather than `inner`.
+    pub fn stats(&self) -> WorldStats {
+        WorldStats::collect(&self.inner, &self.components, self.churn)
+    }
+
+    /// Finish the frame for churn tracking and event cleanup
+    ///
+    /// Events sent two frames ago are dropped from every channel.
+    pub fn end_frame(&mut self) {
+        self.churn.end_frame();
+        for channel in self.events.values_mut() {
+            channel.update();
+        }
+    }
+
+    /// Insert a resource, returning the one it replaced
+    pub fn insert_resource<T: 'static>(&mut self, value: T) -> Option<T> {
+        self.resources.insert(value)
+    }
+
+    /// Get a resource by type
+    pub fn resource<T: 'static>(&self) -> Option<&T> {
+        self.resources.get()
+    }
+
+    /// Get a resource mutably by type
+    pub fn resource_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.resources.get_mut()
+    }
+
+    /// Remove a resource and return it
+    pub fn remove_resource<T: 'static>(&mut self) -> Option<T> {
+        self.resources.remove()
+    }
+
+    /// Get the event channel for `T`, if any event of that type was added
+    pub fn events<T: 'static>(&self) -> Option<&Events<T>> {
+        self.events
+            .get(&TypeId::of::<T>())
+            .and_then(|channel| channel.as_any().downcast_ref())
+    }
+
+    /// Get the event channel for `T`, creating it if needed
+    pub fn events_mut<T: 'static>(&mut self) -> &mut Events<T> {
+        self.events
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(Events::<T>::new()))
+            .as_any_mut()
+            .downcast_mut()
+            .expect("Event channel stored under the wrong type")
+    }
+
+    /// Queue an event on the channel for `T`
+    pub fn send_event<T: 'static>(&mut self, event: T) {
+        self.events_mut::<T>().send(event);
     }
 
     /// Get a reference to a component
@@ -67,6 +167,98 @@ impl World {
         self.inner.query::<Q>()
     }
 
+    /// Instantiate a prefab, placing its root entities at `transform`
+    ///
+    /// Returns the spawned entities in prefab order.
+    pub fn spawn_prefab(
+        &mut self,
+        prefab: &AssetHandle<Prefab>,
+        transform: Transform,
+    ) -> Vec<Entity> {
+        prefab.instantiate(self, transform)
+    }
+
+    /// Spawn a loaded glTF scene, walking its root nodes
+    ///
+    /// Each node becomes an entity with `Transform`, `GlobalTransform`,
+    /// `Name`, hierarchy links, and its mesh, material, camera, or light.
+    /// Returns a map from glTF node index to entity.
+    pub fn spawn_gltf(&mut self, gltf: &LoadedGltf) -> HashMap<usize, Entity> {
+        super::gltf_scene::spawn_gltf(self, gltf)
+    }
+
+    /// Find the first entity with the given `Name`
+    pub fn find_by_name(&self, name: &str) -> Option<Entity> {
+        self.inner
+            .query::<&Name>()
+            .iter()
+            .find(|(_, n)| n.as_str() == name)
+            .map(|(entity, _)| entity)
+    }
+
+    /// Find every entity with the given `Name`
+    pub fn find_all_by_name(&self, name: &str) -> Vec<Entity> {
+        self.inner
+            .query::<&Name>()
+            .iter()
+            .filter(|(_, n)| n.as_str() == name)
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+
+    /// Find every entity whose `Tags` contain `tag`
+    pub fn find_by_tag(&self, tag: &str) -> Vec<Entity> {
+        self.inner
+            .query::<&Tags>()
+            .iter()
+            .filter(|(_, tags)| tags.has(tag))
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+
+    /// Describe an entity for logs and debug overlays
+    ///
+    /// Gives `"Player (42v0)"` for named entities and `"42v0"` otherwise.
+    pub fn label(&self, entity: Entity) -> String {
+        match self.get::<Name>(entity) {
+            Ok(name) => format!("{} ({entity:?})", name.as_str()),
+            Err(_) => format!("{entity:?}"),
+        }
+    }
+
+    /// Attach `child` under `parent`, keeping `Parent` and `Children` in sync
+    ///
+    /// Detaches the child from any previous parent first. The child's
+    /// `Transform` is then interpreted relative to the new parent.
+    pub fn set_parent(&mut self, child: Entity, parent: Entity) {
+        if child == parent {
+            return;
+        }
+        self.remove_parent(child);
+        let _ = self.inner.insert_one(child, Parent::new(parent));
+        if let Ok(mut children) = self.get_mut::<Children>(parent) {
+            children.add(child);
+        } else {
+            let _ = self.inner.insert_one(parent, Children::single(child));
+        }
+    }
+
+    /// Detach `child` from its parent, making it a hierarchy root
+    ///
+    /// Returns the previous parent, if any.
+    pub fn remove_parent(&mut self, child: Entity) -> Option<Entity> {
+        let parent = self.inner.remove_one::<Parent>(child).ok()?.entity();
+        if let Ok(mut children) = self.get_mut::<Children>(parent) {
+            children.remove(child);
+        }
+        Some(parent)
+    }
+
+    /// Recompute `GlobalTransform` for every entity from the hierarchy
+    pub fn propagate_transforms(&mut self) {
+        super::hierarchy::propagate_transforms(self);
+    }
+
     /// Query for entities with specific components (mutable)
     pub fn query_mut<Q: hecs::Query>(&mut self) -> hecs::QueryMut<'_, Q> {
         self.inner.query_mut::<Q>()
@@ -0,0 +1,208 @@
+//! Input action mapping
+//!
+//! Named actions and axes bound to keys and mouse buttons, so gameplay code
+//! asks for "jump" rather than a specific key.
+
+use rustc_hash::FxHashMap;
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+use super::Input;
+
+/// A physical input an action can be bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputBinding {
+    /// Keyboard key
+    Key(KeyCode),
+    /// Mouse button
+    Mouse(MouseButton),
+}
+
+impl InputBinding {
+    /// Check if the binding is held
+    #[must_use]
+    pub fn pressed(self, input: &Input) -> bool {
+        match self {
+            Self::Key(key) => input.is_key_pressed(key),
+            Self::Mouse(button) => input.is_mouse_button_pressed(button),
+        }
+    }
+
+    /// Check if the binding was pressed this frame
+    #[must_use]
+    pub fn just_pressed(self, input: &Input) -> bool {
+        match self {
+            Self::Key(key) => input.is_key_just_pressed(key),
+            Self::Mouse(button) => input.is_mouse_button_just_pressed(button),
+        }
+    }
+
+    /// Check if the binding was released this frame
+    #[must_use]
+    pub fn just_released(self, input: &Input) -> bool {
+        match self {
+            Self::Key(key) => input.is_key_just_released(key),
+            Self::Mouse(button) => input.is_mouse_button_just_released(button),
+        }
+    }
+}
+
+impl From<KeyCode> for InputBinding {
+    fn from(key: KeyCode) -> Self {
+        Self::Key(key)
+    }
+}
+
+impl From<MouseButton> for InputBinding {
+    fn from(button: MouseButton) -> Self {
+        Self::Mouse(button)
+    }
+}
+
+/// Named button actions and axes
+#[derive(Debug, Clone, Default)]
+pub struct ActionMap {
+    /// Bindings per button action
+    buttons: FxHashMap<String, Vec<InputBinding>>,
+    /// (negative, positive) binding pairs per axis
+    axes: FxHashMap<String, Vec<(InputBinding, InputBinding)>>,
+}
+
+impl ActionMap {
+    /// Horizontal movement axis of the character layout
+    pub const MOVE_X: &'static str = "move_x";
+    /// Forward movement axis of the character layout
+    pub const MOVE_Z: &'static str = "move_z";
+    /// Jump action of the character layout
+    pub const JUMP: &'static str = "jump";
+    /// Sprint action of the character layout
+    pub const SPRINT: &'static str = "sprint";
+    /// Interact action of the character layout
+    pub const INTERACT: &'static str = "interact";
+
+    /// Create an empty action map
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// WASD movement, Space to jump, Shift to sprint, E to interact
+    #[must_use]
+    pub fn character() -> Self {
+        Self::new()
+            .with_axis(Self::MOVE_X, KeyCode::KeyA, KeyCode::KeyD)
+            .with_axis(Self::MOVE_X, KeyCode::ArrowLeft, KeyCode::ArrowRight)
+            .with_axis(Self::MOVE_Z, KeyCode::KeyS, KeyCode::KeyW)
+            .with_axis(Self::MOVE_Z, KeyCode::ArrowDown, KeyCode::ArrowUp)
+            .with_button(Self::JUMP, KeyCode::Space)
+            .with_button(Self::SPRINT, KeyCode::ShiftLeft)
+            .with_button(Self::INTERACT, KeyCode::KeyE)
+    }
+
+    /// Builder form of [`Self::bind`]
+    #[must_use]
+    pub fn with_button(mut self, action: &str, binding: impl Into<InputBinding>) -> Self {
+        self.bind(action, binding);
+        self
+    }
+
+    /// Builder form of [`Self::bind_axis`]
+    #[must_use]
+    pub fn with_axis(
+        mut self,
+        axis: &str,
+        negative: impl Into<InputBinding>,
+        positive: impl Into<InputBinding>,
+    ) -> Self {
+        self.bind_axis(axis, negative, positive);
+        self
+    }
+
+    /// Add a binding to a button action
+    pub fn bind(&mut self, action: &str, binding: impl Into<InputBinding>) {
+        self.buttons
+            .entry(action.to_string())
+            .or_default()
+            .push(binding.into());
+    }
+
+    /// Add a (negative, positive) binding pair to an axis
+    pub fn bind_axis(
+        &mut self,
+        axis: &str,
+        negative: impl Into<InputBinding>,
+        positive: impl Into<InputBinding>,
+    ) {
+        self.axes
+            .entry(axis.to_string())
+            .or_default()
+            .push((negative.into(), positive.into()));
+    }
+
+    /// Remove all bindings of an action or axis
+    pub fn unbind(&mut self, name: &str) {
+        self.buttons.remove(name);
+        self.axes.remove(name);
+    }
+
+    /// Get the bindings of a button action
+    #[must_use]
+    pub fn bindings(&self, action: &str) -> &[InputBinding] {
+        self.buttons.get(action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Check if any binding of an action is held
+    #[must_use]
+    pub fn pressed(&self, input: &Input, action: &str) -> bool {
+        self.bindings(action).iter().any(|b| b.pressed(input))
+    }
+
+    /// Check if any binding of an action was pressed this frame
+    #[must_use]
+    pub fn just_pressed(&self, input: &Input, action: &str) -> bool {
+        self.bindings(action).iter().any(|b| b.just_pressed(input))
+    }
+
+    /// Check if any binding of an action was released this frame
+    #[must_use]
+    pub fn just_released(&self, input: &Input, action: &str) -> bool {
+        self.bindings(action).iter().any(|b| b.just_released(input))
+    }
+
+    /// Get an axis value in [-1, 1]
+    #[must_use]
+    pub fn axis(&self, input: &Input, axis: &str) -> f32 {
+        let Some(pairs) = self.axes.get(axis) else {
+            return 0.0;
+        };
+        let value: f32 = pairs
+            .iter()
+            .map(|(negative, positive)| {
+                f32::from(u8::from(positive.pressed(input)))
+                    - f32::from(u8::from(negative.pressed(input)))
+            })
+            .sum();
+        value.clamp(-1.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::event::ElementState;
+
+    #[test]
+    fn test_character_layout() {
+        let actions = ActionMap::character();
+        let mut input = Input::new();
+        input.process_keyboard(KeyCode::KeyW, ElementState::Pressed);
+        input.process_keyboard(KeyCode::ArrowUp, ElementState::Pressed);
+        input.process_keyboard(KeyCode::Space, ElementState::Pressed);
+
+        assert!((actions.axis(&input, ActionMap::MOVE_Z) - 1.0).abs() < 1e-6);
+        assert!(actions.axis(&input, ActionMap::MOVE_X).abs() < 1e-6);
+        assert!(actions.just_pressed(&input, ActionMap::JUMP));
+        assert!(!actions.pressed(&input, ActionMap::SPRINT));
+        assert!(!actions.pressed(&input, "missing"));
+    }
+}
@@ -1,5 +1,7 @@
 //! Input handling module
 
+mod actions;
 mod state;
 
-pub use state::Input;
+pub use actions::{ActionMap, InputBinding};
+pub use state::{Input, TextEvent};
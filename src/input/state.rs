@@ -2,11 +2,22 @@
 
 use glam::Vec2;
 use std::collections::HashSet;
-use winit::event::{ElementState, MouseButton};
+use winit::event::{ElementState, Ime, MouseButton};
 use winit::keyboard::KeyCode;
 
+/// Text editing input, in the order it arrived
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextEvent {
+    /// A key press, including OS key repeats
+    Key(KeyCode),
+    /// Characters typed on the keyboard
+    Text(String),
+    /// Input method composition
+    Ime(Ime),
+}
+
 /// Input state manager
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Input {
     /// Currently pressed keys
     pressed_keys: HashSet<KeyCode>,
@@ -26,6 +37,8 @@ pub struct Input {
     mouse_delta: Vec2,
     /// Scroll wheel delta this frame
     scroll_delta: Vec2,
+    /// Key presses, typed text and IME events this frame
+    text_events: Vec<TextEvent>,
 }
 
 impl Input {
@@ -41,6 +54,7 @@ impl Input {
             mouse_position: Vec2::ZERO,
             mouse_delta: Vec2::ZERO,
             scroll_delta: Vec2::ZERO,
+            text_events: Vec::new(),
         }
     }
 
@@ -52,6 +66,7 @@ impl Input {
         self.just_released_mouse_buttons.clear();
         self.mouse_delta = Vec2::ZERO;
         self.scroll_delta = Vec2::ZERO;
+        self.text_events.clear();
     }
 
     /// Process a keyboard event
@@ -62,6 +77,7 @@ impl Input {
                     self.just_pressed_keys.insert(key_code);
                 }
                 self.pressed_keys.insert(key_code);
+                self.text_events.push(TextEvent::Key(key_code));
             }
             ElementState::Released => {
                 self.pressed_keys.remove(&key_code);
@@ -70,6 +86,19 @@ impl Input {
         }
     }
 
+    /// Process text produced by a key press, ignoring control characters
+    pub fn process_text(&mut self, text: &str) {
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        if !text.is_empty() {
+            self.text_events.push(TextEvent::Text(text));
+        }
+    }
+
+    /// Process an input method event
+    pub fn process_ime(&mut self, ime: Ime) {
+        self.text_events.push(TextEvent::Ime(ime));
+    }
+
     /// Process a mouse button event
     pub fn process_mouse_button(&mut self, button: MouseButton, state: ElementState) {
         match state {
@@ -146,6 +175,28 @@ impl Input {
     pub fn scroll_delta(&self) -> Vec2 {
         self.scroll_delta
     }
+
+    /// Get key presses, typed text and IME events this frame, in order
+    pub fn text_events(&self) -> &[TextEvent] {
+        &self.text_events
+    }
+
+    /// Check if a shift key is held
+    pub fn is_shift_pressed(&self) -> bool {
+        self.is_key_pressed(KeyCode::ShiftLeft) || self.is_key_pressed(KeyCode::ShiftRight)
+    }
+
+    /// Check if a control (or command on macOS) key is held
+    pub fn is_control_pressed(&self) -> bool {
+        [
+            KeyCode::ControlLeft,
+            KeyCode::ControlRight,
+            KeyCode::SuperLeft,
+            KeyCode::SuperRight,
+        ]
+        .into_iter()
+        .any(|key| self.is_key_pressed(key))
+    }
 }
 
 impl Default for Input {
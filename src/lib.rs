@@ -9,6 +9,9 @@
 //! - Skeletal animation system
 //! - AI and navigation
 //! - UI widgets and layout
+//! - Per-platform data directories and file I/O
+//! - Optional deterministic fixed-point math (`fixed-math` feature)
+//! - Optional egui debug panels (`egui` feature)
 
 pub mod ai;
 pub mod animation;
@@ -17,11 +20,16 @@ pub mod audio;
 pub mod core;
 pub mod ecs;
 pub mod input;
+#[cfg(feature = "fixed-math")]
+pub mod math;
 pub mod physics;
+pub mod platform;
 pub mod renderer;
 pub mod ui;
 
 // Re-exports for convenience
+#[cfg(feature = "egui")]
+pub use egui;
 pub use glam;
 pub use hecs;
 pub use rapier3d;
@@ -7,11 +7,12 @@
 //! - UI (HUD elements)
 //! - Audio (System integration)
 //! - Animation (Procedural rotation)
+//! - Physics debug wireframes (F3, debug builds only)
 
 use engine::ai::{Arrive, SteeringBehavior};
-use engine::audio::AudioManager;
+use engine::audio::{AudioEvents, AudioManager, SoundCue, SoundEventMap};
 use engine::prelude::*;
-use engine::renderer::{EmitterConfig, ParticleEmitter, UiRect};
+use engine::renderer::{DebugDraw, EmitterConfig, ParticleEmitter, UiRect};
 
 /// Demo game with physics, AI, particles, and UI
 struct DemoGame {
@@ -32,11 +33,15 @@ struct DemoGame {
     cube_body: Option<RigidBodyHandle>,
     follower_body: Option<RigidBodyHandle>,
 
+    // Physics debug wireframes
+    debug_draw: DebugDraw,
+
     // Particles
     emitter: Option<ParticleEmitter>,
 
     // Audio
     audio: Option<AudioManager>,
+    audio_events: Option<AudioEvents>,
 
     // State
     camera_yaw: f32,
@@ -57,8 +62,14 @@ impl DemoGame {
             physics: Physics::new(),
             cube_body: None,
             follower_body: None,
+            debug_draw: {
+                let mut draw = DebugDraw::new();
+                draw.set_enabled(false);
+                draw
+            },
             emitter: None,
             audio: None,
+            audio_events: None,
             camera_yaw: 0.0,
             camera_pitch: 0.3,
             show_ui: true,
@@ -116,11 +127,22 @@ impl Game for DemoGame {
 
         // 5. Setup Audio
         self.audio = AudioManager::new().ok();
-        if let Some(audio) = &self.audio {
+        if let Some(audio) = &mut self.audio {
             log::info!(
                 "Audio system active. Master volume: {}",
                 audio.master_volume()
             );
+            let impact = SoundCue {
+                min_impulse: 1.0,
+                max_impulse: 10.0,
+                pitch_variance: 0.1,
+                ..SoundCue::new(["assets/sounds/impact.wav"])
+            };
+            let map = SoundEventMap {
+                default_impact: Some("impact".into()),
+                ..SoundEventMap::default().with_cue("impact", impact)
+            };
+            self.audio_events = AudioEvents::load(map, audio).ok();
         }
 
         self.camera.set_aspect(ctx.width(), ctx.height());
@@ -218,6 +240,28 @@ impl Game for DemoGame {
         // Physics step
         self.physics.step(dt);
 
+        // Report hard landings
+        for event in self.physics.drain_collision_events() {
+            if event.started() && event.impulse > 1.0 {
+                log::info!(
+                    "Impact: impulse {:.2} at {:?}",
+                    event.impulse,
+                    event.point().unwrap_or_default()
+                );
+                if let (Some(audio), Some(events)) = (&mut self.audio, &mut self.audio_events) {
+                    events.on_collision(audio, &event);
+                }
+            }
+        }
+
+        // Physics debug view
+        #[cfg(debug_assertions)]
+        if ctx.input.is_key_just_pressed(KeyCode::F3) {
+            self.debug_draw.toggle();
+        }
+        self.debug_draw.clear();
+        self.physics.debug_render(&mut self.debug_draw);
+
         // Update particle state
         if let Some(emitter) = &mut self.emitter {
             emitter.update(dt);
@@ -273,6 +317,10 @@ impl Game for DemoGame {
                 ctx.renderer().draw_particles(&mut render_pass, emitter);
             }
 
+            // Physics debug lines (empty unless toggled on)
+            ctx.renderer()
+                .draw_debug(&mut render_pass, &self.debug_draw);
+
             // 3. Draw UI HUD
             if self.show_ui {
                 let mut ui_rects = Vec::new();
@@ -315,7 +363,7 @@ impl Game for DemoGame {
             }
         }
 
-        ctx.renderer().end_frame(frame);
+        ctx.end_frame(frame);
     }
 }
 
@@ -0,0 +1,555 @@
+//! Q32.32 fixed-point scalar and vectors
+//!
+//! Arithmetic is pure integer, so results are bit-identical on every CPU.
+
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use glam::{Vec2, Vec3};
+use serde::{Deserialize, Serialize};
+
+/// Number of fractional bits
+const FRAC_BITS: u32 = 32;
+
+/// Fixed-point number with 32 integer and 32 fractional bits
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub struct Fixed(i64);
+
+impl Fixed {
+    /// Zero
+    pub const ZERO: Self = Self(0);
+    /// One
+    pub const ONE: Self = Self(1 << FRAC_BITS);
+    /// Negative one
+    pub const NEG_ONE: Self = Self(-(1 << FRAC_BITS));
+    /// One half
+    pub const HALF: Self = Self(1 << (FRAC_BITS - 1));
+    /// Largest representable value
+    pub const MAX: Self = Self(i64::MAX);
+    /// Smallest representable value
+    pub const MIN: Self = Self(i64::MIN);
+    /// Smallest positive value
+    pub const EPSILON: Self = Self(1);
+
+    /// Create from the raw Q32.32 bits
+    #[must_use]
+    pub const fn from_bits(bits: i64) -> Self {
+        Self(bits)
+    }
+
+    /// Get the raw Q32.32 bits
+    #[must_use]
+    pub const fn to_bits(self) -> i64 {
+        self.0
+    }
+
+    /// Create from an integer
+    #[must_use]
+    pub const fn from_int(value: i32) -> Self {
+        Self((value as i64) << FRAC_BITS)
+    }
+
+    /// Create from a float (rounded to nearest)
+    ///
+    /// Only use this for authored data; converting at runtime reintroduces
+    /// float behaviour into the simulation.
+    #[must_use]
+    pub fn from_f32(value: f32) -> Self {
+        Self((f64::from(value) * (1u64 << FRAC_BITS) as f64).round() as i64)
+    }
+
+    /// Convert to a float (for rendering and debugging)
+    #[must_use]
+    pub fn to_f32(self) -> f32 {
+        (self.0 as f64 / (1u64 << FRAC_BITS) as f64) as f32
+    }
+
+    /// Absolute value
+    #[must_use]
+    pub const fn abs(self) -> Self {
+        Self(self.0.saturating_abs())
+    }
+
+    /// Minimum of two values
+    #[must_use]
+    pub fn min(self, other: Self) -> Self {
+        Ord::min(self, other)
+    }
+
+    /// Maximum of two values
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        Ord::max(self, other)
+    }
+
+    /// Clamp to a range
+    #[must_use]
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        Ord::clamp(self, min, max)
+    }
+
+    /// Round towards negative infinity
+    #[must_use]
+    pub const fn floor(self) -> Self {
+        Self(self.0 & !((1 << FRAC_BITS) - 1))
+    }
+
+    /// Integer part (rounded towards negative infinity)
+    #[must_use]
+    pub const fn floor_to_int(self) -> i32 {
+        (self.0 >> FRAC_BITS) as i32
+    }
+
+    /// Check if the value is negative
+    #[must_use]
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    /// Square root (returns zero for negative input)
+    #[must_use]
+    pub fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return Self::ZERO;
+        }
+        // sqrt(x * 2^32) * 2^16 == sqrt(x * 2^64), computed in integers
+        let scaled = (self.0 as u128) << FRAC_BITS;
+        Self(isqrt_u128(scaled) as i64)
+    }
+}
+
+/// Integer square root using the digit-by-digit method
+fn isqrt_u128(value: u128) -> u128 {
+    let mut remainder = value;
+    let mut result: u128 = 0;
+    let mut bit: u128 = 1 << 126;
+
+    while bit > remainder {
+        bit >>= 2;
+    }
+
+    while bit != 0 {
+        if remainder >= result + bit {
+            remainder -= result + bit;
+            result = (result >> 1) + bit;
+        } else {
+            result >>= 1;
+        }
+        bit >>= 2;
+    }
+
+    result
+}
+
+impl Add for Fixed {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_add(rhs.0))
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_sub(rhs.0))
+    }
+}
+
+impl SubAssign for Fixed {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(((i128::from(self.0) * i128::from(rhs.0)) >> FRAC_BITS) as i64)
+    }
+}
+
+impl MulAssign for Fixed {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div for Fixed {
+    type Output = Self;
+
+    /// Division by zero saturates instead of panicking
+    fn div(self, rhs: Self) -> Self {
+        if rhs.0 == 0 {
+            return if self.0 >= 0 { Self::MAX } else { Self::MIN };
+        }
+        Self(((i128::from(self.0) << FRAC_BITS) / i128::from(rhs.0)) as i64)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(self.0.wrapping_neg())
+    }
+}
+
+impl From<i32> for Fixed {
+    fn from(value: i32) -> Self {
+        Self::from_int(value)
+    }
+}
+
+impl std::fmt::Display for Fixed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_f32())
+    }
+}
+
+/// 2D fixed-point vector mirroring `glam::Vec2`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct FixedVec2 {
+    /// X component
+    pub x: Fixed,
+    /// Y component
+    pub y: Fixed,
+}
+
+impl FixedVec2 {
+    /// All zeros
+    pub const ZERO: Self = Self::new(Fixed::ZERO, Fixed::ZERO);
+    /// All ones
+    pub const ONE: Self = Self::new(Fixed::ONE, Fixed::ONE);
+    /// Unit X
+    pub const X: Self = Self::new(Fixed::ONE, Fixed::ZERO);
+    /// Unit Y
+    pub const Y: Self = Self::new(Fixed::ZERO, Fixed::ONE);
+
+    /// Create a new vector
+    #[must_use]
+    pub const fn new(x: Fixed, y: Fixed) -> Self {
+        Self { x, y }
+    }
+
+    /// Create a vector with all components set to `value`
+    #[must_use]
+    pub const fn splat(value: Fixed) -> Self {
+        Self::new(value, value)
+    }
+
+    /// Convert from a float vector
+    #[must_use]
+    pub fn from_vec2(v: Vec2) -> Self {
+        Self::new(Fixed::from_f32(v.x), Fixed::from_f32(v.y))
+    }
+
+    /// Convert to a float vector
+    #[must_use]
+    pub fn to_vec2(self) -> Vec2 {
+        Vec2::new(self.x.to_f32(), self.y.to_f32())
+    }
+
+    /// Dot product
+    #[must_use]
+    pub fn dot(self, rhs: Self) -> Fixed {
+        self.x * rhs.x + self.y * rhs.y
+    }
+
+    /// Squared length
+    #[must_use]
+    pub fn length_squared(self) -> Fixed {
+        self.dot(self)
+    }
+
+    /// Length
+    #[must_use]
+    pub fn length(self) -> Fixed {
+        self.length_squared().sqrt()
+    }
+
+    /// Distance to another point
+    #[must_use]
+    pub fn distance(self, rhs: Self) -> Fixed {
+        (self - rhs).length()
+    }
+
+    /// Normalize, returning zero for zero-length vectors
+    #[must_use]
+    pub fn normalize_or_zero(self) -> Self {
+        let length = self.length();
+        if length == Fixed::ZERO {
+            Self::ZERO
+        } else {
+            Self::new(self.x / length, self.y / length)
+        }
+    }
+
+    /// Linear interpolation
+    #[must_use]
+    pub fn lerp(self, rhs: Self, t: Fixed) -> Self {
+        self + (rhs - self) * t
+    }
+}
+
+impl Add for FixedVec2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for FixedVec2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for FixedVec2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for FixedVec2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<Fixed> for FixedVec2 {
+    type Output = Self;
+
+    fn mul(self, rhs: Fixed) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Div<Fixed> for FixedVec2 {
+    type Output = Self;
+
+    fn div(self, rhs: Fixed) -> Self {
+        Self::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl Neg for FixedVec2 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/// 3D fixed-point vector mirroring `glam::Vec3`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct FixedVec3 {
+    /// X component
+    pub x: Fixed,
+    /// Y component
+    pub y: Fixed,
+    /// Z component
+    pub z: Fixed,
+}
+
+impl FixedVec3 {
+    /// All zeros
+    pub const ZERO: Self = Self::new(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO);
+    /// All ones
+    pub const ONE: Self = Self::new(Fixed::ONE, Fixed::ONE, Fixed::ONE);
+    /// Unit X
+    pub const X: Self = Self::new(Fixed::ONE, Fixed::ZERO, Fixed::ZERO);
+    /// Unit Y
+    pub const Y: Self = Self::new(Fixed::ZERO, Fixed::ONE, Fixed::ZERO);
+    /// Unit Z
+    pub const Z: Self = Self::new(Fixed::ZERO, Fixed::ZERO, Fixed::ONE);
+
+    /// Create a new vector
+    #[must_use]
+    pub const fn new(x: Fixed, y: Fixed, z: Fixed) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Create a vector with all components set to `value`
+    #[must_use]
+    pub const fn splat(value: Fixed) -> Self {
+        Self::new(value, value, value)
+    }
+
+    /// Convert from a float vector
+    #[must_use]
+    pub fn from_vec3(v: Vec3) -> Self {
+        Self::new(
+            Fixed::from_f32(v.x),
+            Fixed::from_f32(v.y),
+            Fixed::from_f32(v.z),
+        )
+    }
+
+    /// Convert to a float vector
+    #[must_use]
+    pub fn to_vec3(self) -> Vec3 {
+        Vec3::new(self.x.to_f32(), self.y.to_f32(), self.z.to_f32())
+    }
+
+    /// Dot product
+    #[must_use]
+    pub fn dot(self, rhs: Self) -> Fixed {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    /// Cross product
+    #[must_use]
+    pub fn cross(self, rhs: Self) -> Self {
+        Self::new(
+            self.y * rhs.z - self.z * rhs.y,
+            self.z * rhs.x - self.x * rhs.z,
+            self.x * rhs.y - self.y * rhs.x,
+        )
+    }
+
+    /// Squared length
+    #[must_use]
+    pub fn length_squared(self) -> Fixed {
+        self.dot(self)
+    }
+
+    /// Length
+    #[must_use]
+    pub fn length(self) -> Fixed {
+        self.length_squared().sqrt()
+    }
+
+    /// Distance to another point
+    #[must_use]
+    pub fn distance(self, rhs: Self) -> Fixed {
+        (self - rhs).length()
+    }
+
+    /// Normalize, returning zero for zero-length vectors
+    #[must_use]
+    pub fn normalize_or_zero(self) -> Self {
+        let length = self.length();
+        if length == Fixed::ZERO {
+            Self::ZERO
+        } else {
+            Self::new(self.x / length, self.y / length, self.z / length)
+        }
+    }
+
+    /// Scale down to at most `max` length
+    #[must_use]
+    pub fn clamp_length_max(self, max: Fixed) -> Self {
+        let length = self.length();
+        if length > max {
+            self * (max / length)
+        } else {
+            self
+        }
+    }
+
+    /// Linear interpolation
+    #[must_use]
+    pub fn lerp(self, rhs: Self, t: Fixed) -> Self {
+        self + (rhs - self) * t
+    }
+}
+
+impl Add for FixedVec3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for FixedVec3 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for FixedVec3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl SubAssign for FixedVec3 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<Fixed> for FixedVec3 {
+    type Output = Self;
+
+    fn mul(self, rhs: Fixed) -> Self {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Div<Fixed> for FixedVec3 {
+    type Output = Self;
+
+    fn div(self, rhs: Fixed) -> Self {
+        Self::new(self.x / rhs, self.y / rhs, self.z / rhs)
+    }
+}
+
+impl Neg for FixedVec3 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_arithmetic() {
+        let a = Fixed::from_int(3);
+        let b = Fixed::HALF;
+
+        assert_eq!(a * b, Fixed::from_f32(1.5));
+        assert_eq!(a / Fixed::from_int(2), Fixed::from_f32(1.5));
+        assert_eq!((a - Fixed::from_int(5)).floor_to_int(), -2);
+        assert_eq!(Fixed::from_int(9).sqrt(), Fixed::from_int(3));
+    }
+
+    #[test]
+    fn test_fixed_vec3_normalize() {
+        let v = FixedVec3::new(Fixed::from_int(3), Fixed::ZERO, Fixed::from_int(4));
+
+        assert_eq!(v.length(), Fixed::from_int(5));
+        let n = v.normalize_or_zero();
+        assert!((n.to_vec3() - Vec3::new(0.6, 0.0, 0.8)).length() < 1e-6);
+        assert_eq!(FixedVec3::ZERO.normalize_or_zero(), FixedVec3::ZERO);
+    }
+
+    #[test]
+    fn test_fixed_vec3_clamp_length_max() {
+        let v = FixedVec3::new(Fixed::from_int(3), Fixed::ZERO, Fixed::from_int(4));
+
+        assert_eq!(v.clamp_length_max(Fixed::from_int(10)), v);
+        let clamped = v.clamp_length_max(Fixed::ONE);
+        assert!((clamped.to_vec3() - Vec3::new(0.6, 0.0, 0.8)).length() < 1e-6);
+    }
+}
@@ -0,0 +1,8 @@
+//! Deterministic math module
+//!
+//! Fixed-point types mirroring the glam API, for lockstep simulation.
+//! Enabled with the `fixed-math` feature.
+
+mod fixed;
+
+pub use fixed::{Fixed, FixedVec2, FixedVec3};
@@ -0,0 +1,167 @@
+//! Kinematic character controller
+//!
+//! A capsule on a kinematic body that walks, jumps, slides along walls,
+//! climbs steps and snaps to the ground.
+
+use glam::{Quat, Vec3};
+use rapier3d::control::{CharacterAutostep, CharacterLength, KinematicCharacterController};
+
+use super::{ColliderHandle, Physics, RigidBodyHandle};
+
+/// Component moving a capsule through the physics world
+#[derive(Debug, Clone)]
+pub struct CharacterController {
+    /// Kinematic body
+    pub body: RigidBodyHandle,
+    /// Capsule collider
+    pub collider: ColliderHandle,
+    /// Capsule height, including the caps
+    pub height: f32,
+    /// Capsule radius
+    pub radius: f32,
+    /// Walking speed (units per second)
+    pub walk_speed: f32,
+    /// Sprinting speed (units per second)
+    pub sprint_speed: f32,
+    /// Initial upward speed of a jump
+    pub jump_speed: f32,
+    /// Downward acceleration
+    pub gravity: f32,
+    /// Rapier controller settings
+    kinematic: KinematicCharacterController,
+    /// Feet position after the last move
+    feet: Vec3,
+    /// Current vertical speed
+    vertical_velocity: f32,
+    /// Horizontal velocity applied on the last move
+    velocity: Vec3,
+    /// Whether the capsule touched the ground on the last move
+    grounded: bool,
+}
+
+impl CharacterController {
+    /// Create a kinematic capsule with its feet at `feet`
+    pub fn spawn(physics: &mut Physics, feet: Vec3, height: f32, radius: f32) -> Self {
+        let height = height.max(radius * 2.0);
+        let body = physics.create_kinematic_body(feet + Vec3::Y * height * 0.5, Quat::IDENTITY);
+        let collider = physics.add_capsule_collider(body, height * 0.5 - radius, radius, 1.0);
+        let kinematic = KinematicCharacterController {
+            offset: CharacterLength::Absolute(0.01),
+            autostep: Some(CharacterAutostep {
+                max_height: CharacterLength::Absolute(0.35),
+                min_width: CharacterLength::Absolute(0.2),
+                include_dynamic_bodies: false,
+            }),
+            snap_to_ground: Some(CharacterLength::Absolute(0.2)),
+            max_slope_climb_angle: 45f32.to_radians(),
+            min_slope_slide_angle: 30f32.to_radians(),
+            ..Default::default()
+        };
+
+        Self {
+            body,
+            collider,
+            height,
+            radius,
+            walk_speed: 4.0,
+            sprint_speed: 7.0,
+            jump_speed: 5.0,
+            gravity: 20.0,
+            kinematic,
+            feet,
+            vertical_velocity: 0.0,
+            velocity: Vec3::ZERO,
+            grounded: false,
+        }
+    }
+
+    /// Set walk and sprint speeds
+    #[must_use]
+    pub fn with_speeds(mut self, walk: f32, sprint: f32) -> Self {
+        self.walk_speed = walk;
+        self.sprint_speed = sprint;
+        self
+    }
+
+    /// Set the jump speed
+    #[must_use]
+    pub fn with_jump_speed(mut self, jump_speed: f32) -> Self {
+        self.jump_speed = jump_speed;
+        self
+    }
+
+    /// Set the highest step the character climbs automatically
+    #[must_use]
+    pub fn with_max_step(mut self, height: f32) -> Self {
+        self.kinematic.autostep = (height > 0.0).then_some(CharacterAutostep {
+            max_height: CharacterLength::Absolute(height),
+            min_width: CharacterLength::Absolute(self.radius),
+            include_dynamic_bodies: false,
+        });
+        self
+    }
+
+    /// Set the steepest slope (radians) the character can walk up
+    #[must_use]
+    pub fn with_max_slope(mut self, angle: f32) -> Self {
+        self.kinematic.max_slope_climb_angle = angle;
+        self
+    }
+
+    /// Move with a horizontal `wish` velocity, optionally starting a jump
+    ///
+    /// Jumps only start when grounded. Call once per frame or fixed step,
+    /// before `Physics::step`.
+    pub fn move_and_slide(&mut self, physics: &mut Physics, wish: Vec3, jump: bool, dt: f32) {
+        if self.grounded {
+            self.vertical_velocity = if jump { self.jump_speed } else { 0.0 };
+        }
+        self.vertical_velocity -= self.gravity * dt;
+
+        let horizontal = Vec3::new(wish.x, 0.0, wish.z);
+        let desired = (horizontal + Vec3::Y * self.vertical_velocity) * dt;
+        let Some((moved, center, grounded)) =
+            physics.move_shape(self.body, self.collider, desired, &self.kinematic, dt)
+        else {
+            return;
+        };
+
+        // Bumping a ceiling ends the jump
+        if self.vertical_velocity > 0.0 && moved.y < desired.y * 0.5 {
+            self.vertical_velocity = 0.0;
+        }
+        self.feet = center - Vec3::Y * self.height * 0.5;
+        self.grounded = grounded && self.vertical_velocity <= 0.0;
+        self.velocity = if dt > 0.0 {
+            Vec3::new(moved.x, 0.0, moved.z) / dt
+        } else {
+            Vec3::ZERO
+        };
+    }
+
+    /// Get the position of the character's feet after the last move
+    ///
+    /// The body reaches it on the next `Physics::step`.
+    #[must_use]
+    pub const fn feet(&self) -> Vec3 {
+        self.feet
+    }
+
+    /// Check if the character stood on the ground after the last move
+    #[must_use]
+    pub const fn is_grounded(&self) -> bool {
+        self.grounded
+    }
+
+    /// Get the horizontal velocity achieved on the last move
+    #[must_use]
+    pub const fn velocity(&self) -> Vec3 {
+        self.velocity
+    }
+
+    /// Get the current vertical speed
+    #[must_use]
+    pub const fn vertical_velocity(&self) -> f32 {
+        self.vertical_velocity
+    }
+}
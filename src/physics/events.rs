@@ -0,0 +1,304 @@
+//! Collision events
+//!
+//! Rapier reports contact begin/end from inside the pipeline step. The
+//! collector records those pairs and completes them with impulses and
+//! contact points from the narrow phase. New contacts are found after the
+//! solver has run, so started pairs are held for one tick until the solver
+//! has resolved them and their impulse is known.
+//! Overlaps involving a sensor collider are reported as trigger events.
+
+use std::sync::Mutex;
+
+use glam::Vec3;
+use rapier3d::prelude::*;
+
+use super::ColliderHandle;
+
+/// Whether a contact began or ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContactState {
+    /// The colliders started touching this step
+    Started,
+    /// The colliders stopped touching this step (or one was removed)
+    Stopped,
+}
+
+/// Contact begin/end between two colliders
+#[derive(Debug, Clone)]
+pub struct CollisionEvent {
+    /// Whether the contact started or stopped
+    pub state: ContactState,
+    /// First collider of the pair
+    pub collider1: ColliderHandle,
+    /// Second collider of the pair
+    pub collider2: ColliderHandle,
+    /// Total impulse applied by the solver on the tick the event is
+    /// reported (zero when stopped)
+    pub impulse: f32,
+    /// Contact normal pointing from the first collider to the second
+    pub normal: Vec3,
+    /// Contact points in world space (empty when stopped)
+    pub points: Vec<Vec3>,
+}
+
+impl CollisionEvent {
+    /// Check if the contact started this step
+    #[must_use]
+    pub fn started(&self) -> bool {
+        self.state == ContactState::Started
+    }
+
+    /// Check if either collider of the pair is `collider`
+    #[must_use]
+    pub fn involves(&self, collider: ColliderHandle) -> bool {
+        self.collider1 == collider || self.collider2 == collider
+    }
+
+    /// The other collider of the pair, if `collider` is part of it
+    #[must_use]
+    pub fn other(&self, collider: ColliderHandle) -> Option<ColliderHandle> {
+        if self.collider1 == collider {
+            Some(self.collider2)
+        } else if self.collider2 == collider {
+            Some(self.collider1)
+        } else {
+            None
+        }
+    }
+
+    /// First contact point, if any
+    #[must_use]
+    pub fn point(&self) -> Option<Vec3> {
+        self.points.first().copied()
+    }
+}
+
+/// A collider entering or leaving a sensor (trigger volume)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerEvent {
+    /// `Started` when the collider entered, `Stopped` when it left
+    pub state: ContactState,
+    /// The sensor collider
+    pub sensor: ColliderHandle,
+    /// The collider that entered or left
+    pub other: ColliderHandle,
+}
+
+impl TriggerEvent {
+    /// Check if the collider entered the sensor this step
+    #[must_use]
+    pub fn entered(&self) -> bool {
+        self.state == ContactState::Started
+    }
+
+    /// Check if the collider left the sensor this step
+    #[must_use]
+    pub fn exited(&self) -> bool {
+        self.state == ContactState::Stopped
+    }
+}
+
+/// Event handler recording contact changes during a step
+#[derive(Default)]
+pub(super) struct EventCollector {
+    /// Pairs whose contact changed, in report order
+    pending: Mutex<Vec<(ContactState, ColliderHandle, ColliderHandle)>>,
+    /// Sensor overlaps that changed, in report order
+    triggers: Mutex<Vec<TriggerEvent>>,
+    /// Started pairs waiting for the solver to resolve them
+    deferred: Vec<(ColliderHandle, ColliderHandle)>,
+}
+
+impl EventCollector {
+    /// Complete the recorded pairs with contact data from the finished step
+    ///
+    /// Pairs that started last tick are reported before this tick's stops,
+    /// and a pair that starts and stops within one tick is reported at once
+    /// with both events in order, so `Started` always precedes `Stopped`.
+    pub(super) fn drain(&mut self, narrow_phase: &NarrowPhase, out: &mut Vec<CollisionEvent>) {
+        for (collider1, collider2) in self.deferred.drain(..) {
+            out.push(Self::started(narrow_phase, collider1, collider2));
+        }
+        let pending = self
+            .pending
+            .get_mut()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        for (state, collider1, collider2) in pending.drain(..) {
+            match state {
+                ContactState::Started => self.deferred.push((collider1, collider2)),
+                ContactState::Stopped => {
+                    if let Some(index) = self.deferred.iter().position(|&(a, b)| {
+                        (a, b) == (collider1, collider2) || (b, a) == (collider1, collider2)
+                    }) {
+                        let (a, b) = self.deferred.remove(index);
+                        out.push(Self::started(narrow_phase, a, b));
+                    }
+                    out.push(CollisionEvent {
+                        state,
+                        collider1,
+                        collider2,
+                        impulse: 0.0,
+                        normal: Vec3::ZERO,
+                        points: Vec::new(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Build a started event from the pair's current contact data
+    fn started(
+        narrow_phase: &NarrowPhase,
+        collider1: ColliderHandle,
+        collider2: ColliderHandle,
+    ) -> CollisionEvent {
+        let mut event = CollisionEvent {
+            state: ContactState::Started,
+            collider1,
+            collider2,
+            impulse: 0.0,
+            normal: Vec3::ZERO,
+            points: Vec::new(),
+        };
+        if let Some(pair) = narrow_phase.contact_pair(collider1.0, collider2.0) {
+            event.collider1 = ColliderHandle(pair.collider1);
+            event.collider2 = ColliderHandle(pair.collider2);
+            event.impulse = pair.total_impulse_magnitude();
+            for manifold in &pair.manifolds {
+                if event.normal == Vec3::ZERO {
+                    let normal = manifold.data.normal;
+                    event.normal = Vec3::new(normal.x, normal.y, normal.z);
+                }
+                event.points.extend(
+                    manifold.data.solver_contacts.iter().map(|contact| {
+                        Vec3::new(contact.point.x, contact.point.y, contact.point.z)
+                    }),
+                );
+            }
+        }
+        event
+    }
+
+    /// Move the recorded trigger events into `out`
+    pub(super) fn drain_triggers(&self, out: &mut Vec<TriggerEvent>) {
+        out.append(
+            &mut self
+                .triggers
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        );
+    }
+}
+
+impl EventHandler for EventCollector {
+    fn handle_collision_event(
+        &self,
+        _bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        event: rapier3d::geometry::CollisionEvent,
+        _contact_pair: Option<&ContactPair>,
+    ) {
+        let state = if event.started() {
+            ContactState::Started
+        } else {
+            ContactState::Stopped
+        };
+        if event.sensor() {
+            let (collider1, collider2) = (event.collider1(), event.collider2());
+            // A removed collider is no longer in the set; assume the other one is the sensor
+            let first_is_sensor = colliders.get(collider1).map_or_else(
+                || colliders.get(collider2).is_none_or(|c| !c.is_sensor()),
+                Collider::is_sensor,
+            );
+            let (sensor, other) = if first_is_sensor {
+                (collider1, collider2)
+            } else {
+                (collider2, collider1)
+            };
+            self.triggers
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .push(TriggerEvent {
+                    state,
+                    sensor: ColliderHandle(sensor),
+                    other: ColliderHandle(other),
+                });
+            return;
+        }
+        self.pending
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push((
+                state,
+                ColliderHandle(event.collider1()),
+                ColliderHandle(event.collider2()),
+            ));
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        _dt: Real,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _contact_pair: &ContactPair,
+        _total_force_magnitude: Real,
+    ) {
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Quat;
+
+    use super::*;
+    use crate::physics::Physics;
+
+    #[test]
+    fn test_falling_box_reports_contact() {
+        let mut physics = Physics::new();
+        let ground = physics.create_static_body(Vec3::ZERO, Quat::IDENTITY);
+        let floor = physics.add_ground_plane(ground);
+        let body = physics.create_dynamic_body(Vec3::new(0.0, 1.0, 0.0), Quat::IDENTITY);
+        let block = physics.add_box_collider(body, Vec3::splat(0.5), 1.0);
+
+        let mut started = None;
+        for _ in 0..120 {
+            physics.step(1.0 / 60.0);
+            started = physics
+                .drain_collision_events()
+                .find(|event| event.started() && event.involves(block));
+            if started.is_some() {
+                break;
+            }
+        }
+
+        let event = started.expect("box never touched the ground");
+        assert_eq!(event.other(block), Some(floor));
+        assert!(event.impulse > 0.0);
+        let point = event.point().unwrap();
+        assert!((point.y - 0.1).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_same_tick_start_and_stop_keep_order() {
+        let a = ColliderHandle(rapier3d::geometry::ColliderHandle::from_raw_parts(0, 0));
+        let b = ColliderHandle(rapier3d::geometry::ColliderHandle::from_raw_parts(1, 0));
+        let narrow_phase = NarrowPhase::new();
+        let mut collector = EventCollector::default();
+        collector
+            .pending
+            .get_mut()
+            .unwrap()
+            .extend([(ContactState::Started, a, b), (ContactState::Stopped, b, a)]);
+
+        let mut events = Vec::new();
+        collector.drain(&narrow_phase, &mut events);
+        assert_eq!(events.len(), 2);
+        assert!(events[0].started() && events[0].involves(a) && events[0].involves(b));
+        assert!(!events[1].started());
+
+        events.clear();
+        collector.drain(&narrow_phase, &mut events);
+        assert!(events.is_empty());
+    }
+}
@@ -0,0 +1,162 @@
+//! Collision groups and query filters
+//!
+//! Colliders belong to a set of groups (bit flags) and only interact with
+//! colliders whose groups pass their filter, in both directions. The same
+//! groups drive [`CollisionFilter`], which restricts raycasts, shape casts and
+//! overlap queries, e.g. so bullets ignore their shooter and debris ignores
+//! other debris.
+
+use rapier3d::prelude::*;
+
+use super::{ColliderHandle, RigidBodyHandle};
+
+/// Every collision group
+pub const ALL_GROUPS: u32 = u32::MAX;
+
+/// Build rapier interaction groups from membership and filter bit masks
+pub(super) fn interaction_groups(memberships: u32, filter: u32) -> InteractionGroups {
+    InteractionGroups::new(
+        Group::from_bits_truncate(memberships),
+        Group::from_bits_truncate(filter),
+    )
+}
+
+/// Restricts which colliders a physics query can hit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionFilter {
+    /// Groups the query belongs to
+    pub memberships: u32,
+    /// Groups the query can hit
+    pub filter: u32,
+    /// Collider to skip, e.g. the caster's own
+    pub exclude_collider: Option<ColliderHandle>,
+    /// Body whose colliders are all skipped, e.g. the shooter
+    pub exclude_body: Option<RigidBodyHandle>,
+    /// Whether sensors can be hit
+    pub include_sensors: bool,
+}
+
+impl CollisionFilter {
+    /// Filter accepting every solid collider
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            memberships: ALL_GROUPS,
+            filter: ALL_GROUPS,
+            exclude_collider: None,
+            exclude_body: None,
+            include_sensors: false,
+        }
+    }
+
+    /// Set the groups the query belongs to and the groups it can hit
+    #[must_use]
+    pub const fn with_groups(mut self, memberships: u32, filter: u32) -> Self {
+        self.memberships = memberships;
+        self.filter = filter;
+        self
+    }
+
+    /// Skip one collider
+    #[must_use]
+    pub const fn excluding_collider(mut self, collider: ColliderHandle) -> Self {
+        self.exclude_collider = Some(collider);
+        self
+    }
+
+    /// Skip every collider attached to a body
+    #[must_use]
+    pub const fn excluding_body(mut self, body: RigidBodyHandle) -> Self {
+        self.exclude_body = Some(body);
+        self
+    }
+
+    /// Allow hitting sensors
+    #[must_use]
+    pub const fn with_sensors(mut self, include: bool) -> Self {
+        self.include_sensors = include;
+        self
+    }
+
+    /// Check a collider's groups against the filter
+    #[must_use]
+    pub fn accepts_groups(&self, memberships: u32, filter: u32) -> bool {
+        interaction_groups(self.memberships, self.filter)
+            .test(interaction_groups(memberships, filter))
+    }
+
+    /// Convert to a rapier query filter
+    pub(super) fn to_query_filter(self) -> QueryFilter<'static> {
+        let mut filter =
+            QueryFilter::default().groups(interaction_groups(self.memberships, self.filter));
+        if !self.include_sensors {
+            filter = filter.exclude_sensors();
+        }
+        if let Some(collider) = self.exclude_collider {
+            filter = filter.exclude_collider(collider.0);
+        }
+        if let Some(body) = self.exclude_body {
+            filter = filter.exclude_rigid_body(body.0);
+        }
+        filter
+    }
+}
+
+impl Default for CollisionFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{Quat, Vec3};
+
+    use super::*;
+    use crate::physics::Physics;
+
+    #[test]
+    fn test_raycast_skips_excluded_body() {
+        let mut physics = Physics::new();
+        let shooter = physics.create_kinematic_body(Vec3::ZERO, Quat::IDENTITY);
+        physics.add_sphere_collider(shooter, 0.5, 1.0);
+        let wall = physics.create_static_body(Vec3::new(5.0, 0.0, 0.0), Quat::IDENTITY);
+        let wall_collider = physics.add_box_collider(wall, Vec3::ONE, 1.0);
+        physics.step(1.0 / 60.0);
+
+        let hit = physics.raycast(Vec3::ZERO, Vec3::X, 10.0).unwrap();
+        assert!(hit.distance.abs() < 1e-3);
+
+        let filter = CollisionFilter::new().excluding_body(shooter);
+        let hit = physics
+            .raycast_with(Vec3::ZERO, Vec3::X, 10.0, &filter)
+            .unwrap();
+        assert_eq!(hit.collider, wall_collider);
+        assert!((hit.distance - 4.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_group_masks_disable_collision() {
+        const WORLD: u32 = 1 << 0;
+        const DEBRIS: u32 = 1 << 1;
+
+        let mut physics = Physics::new();
+        let ground = physics.create_static_body(Vec3::ZERO, Quat::IDENTITY);
+        let floor = physics.add_ground_plane(ground);
+        physics.set_collision_groups(floor, WORLD, ALL_GROUPS);
+        let body = physics.create_dynamic_body(Vec3::new(0.0, 1.0, 0.0), Quat::IDENTITY);
+        let debris = physics.add_box_collider(body, Vec3::splat(0.25), 1.0);
+        physics.set_collision_groups(debris, DEBRIS, DEBRIS);
+
+        for _ in 0..60 {
+            physics.step(1.0 / 60.0);
+        }
+
+        assert!(physics.get_position(body).unwrap().y < -1.0);
+        assert!(
+            !CollisionFilter::new()
+                .with_groups(DEBRIS, DEBRIS)
+                .accepts_groups(WORLD, ALL_GROUPS)
+        );
+    }
+}
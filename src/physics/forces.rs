@@ -0,0 +1,180 @@
+//! Explosions and force fields
+//!
+//! Explosions push every dynamic body in a radius once; force fields are
+//! persistent volumes (wind tunnels, attractors, repulsors) that push the
+//! bodies inside them on every step.
+
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+/// How strength fades with distance from the center
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Falloff {
+    /// Full strength everywhere inside the radius
+    Constant,
+    /// Fades linearly to zero at the radius
+    #[default]
+    Linear,
+    /// Fades quadratically to zero at the radius
+    Quadratic,
+}
+
+impl Falloff {
+    /// Strength multiplier at `distance` for a given `radius`
+    #[must_use]
+    pub fn factor(self, distance: f32, radius: f32) -> f32 {
+        if radius <= 0.0 || distance > radius {
+            return 0.0;
+        }
+        let t = 1.0 - distance / radius;
+        match self {
+            Self::Constant => 1.0,
+            Self::Linear => t,
+            Self::Quadratic => t * t,
+        }
+    }
+}
+
+/// Region a force field acts in
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ForceVolume {
+    /// Sphere around the field center
+    Sphere {
+        /// Sphere radius
+        radius: f32,
+    },
+    /// Axis-aligned box around the field center
+    Box {
+        /// Box half extents
+        half_extents: Vec3,
+    },
+}
+
+impl ForceVolume {
+    /// Check if a point relative to the volume center is inside
+    #[must_use]
+    pub fn contains(&self, offset: Vec3) -> bool {
+        match *self {
+            Self::Sphere { radius } => offset.length_squared() <= radius * radius,
+            Self::Box { half_extents } => offset.abs().cmple(half_extents).all(),
+        }
+    }
+
+    /// Distance at which falloff reaches zero
+    #[must_use]
+    pub fn reach(&self) -> f32 {
+        match *self {
+            Self::Sphere { radius } => radius,
+            Self::Box { half_extents } => half_extents.length(),
+        }
+    }
+}
+
+/// What a force field does to bodies inside it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ForceFieldKind {
+    /// Constant force in one direction, e.g. a wind tunnel
+    Directional(Vec3),
+    /// Force toward the center with this strength (negative repels)
+    Attractor(f32),
+}
+
+/// Handle to a force field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ForceFieldHandle(pub(super) usize);
+
+/// Persistent volume pushing the dynamic bodies inside it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ForceField {
+    /// Center of the volume
+    pub center: Vec3,
+    /// Shape of the volume
+    pub volume: ForceVolume,
+    /// Force applied inside the volume
+    pub kind: ForceFieldKind,
+    /// How the force fades toward the edge of the volume
+    pub falloff: Falloff,
+    /// Scale force by body mass so light and heavy bodies move alike
+    pub ignore_mass: bool,
+    /// Whether the field is applied
+    pub enabled: bool,
+}
+
+impl ForceField {
+    /// Create a constant-force volume
+    #[must_use]
+    pub const fn directional(center: Vec3, volume: ForceVolume, force: Vec3) -> Self {
+        Self {
+            center,
+            volume,
+            kind: ForceFieldKind::Directional(force),
+            falloff: Falloff::Constant,
+            ignore_mass: false,
+            enabled: true,
+        }
+    }
+
+    /// Create a field pulling bodies toward its center (negative repels)
+    #[must_use]
+    pub const fn attractor(center: Vec3, radius: f32, strength: f32) -> Self {
+        Self {
+            center,
+            volume: ForceVolume::Sphere { radius },
+            kind: ForceFieldKind::Attractor(strength),
+            falloff: Falloff::Linear,
+            ignore_mass: false,
+            enabled: true,
+        }
+    }
+
+    /// Set the falloff
+    #[must_use]
+    pub const fn with_falloff(mut self, falloff: Falloff) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    /// Treat the force as an acceleration, independent of body mass
+    #[must_use]
+    pub const fn with_ignore_mass(mut self, ignore_mass: bool) -> Self {
+        self.ignore_mass = ignore_mass;
+        self
+    }
+
+    /// Force on a body at `position` with `mass`, or `None` outside the volume
+    #[must_use]
+    pub fn force_at(&self, position: Vec3, mass: f32) -> Option<Vec3> {
+        let offset = position - self.center;
+        if !self.enabled || !self.volume.contains(offset) {
+            return None;
+        }
+        let scale = self.falloff.factor(offset.length(), self.volume.reach())
+            * if self.ignore_mass { mass } else { 1.0 };
+        let force = match self.kind {
+            ForceFieldKind::Directional(force) => force,
+            ForceFieldKind::Attractor(strength) => -offset.normalize_or_zero() * strength,
+        };
+        Some(force * scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_falloff_fades_to_edge() {
+        assert_eq!(Falloff::Constant.factor(0.9, 1.0), 1.0);
+        assert!((Falloff::Linear.factor(0.5, 1.0) - 0.5).abs() < 1e-6);
+        assert!((Falloff::Quadratic.factor(0.5, 1.0) - 0.25).abs() < 1e-6);
+        assert_eq!(Falloff::Linear.factor(1.5, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_attractor_pulls_toward_center() {
+        let field = ForceField::attractor(Vec3::ZERO, 10.0, 5.0).with_falloff(Falloff::Constant);
+        let force = field.force_at(Vec3::new(4.0, 0.0, 0.0), 1.0).unwrap();
+        assert!((force - Vec3::new(-5.0, 0.0, 0.0)).length() < 1e-5);
+        assert!(field.force_at(Vec3::new(20.0, 0.0, 0.0), 1.0).is_none());
+    }
+}
@@ -0,0 +1,339 @@
+//! Joints and constraints
+//!
+//! Describes joints between two rigid bodies: fixed welds, hinges, sliders,
+//! ball-and-socket joints, ropes and springs, with optional limits and a
+//! motor on the free axis. Create them with `Physics::create_joint`.
+
+use glam::Vec3;
+use rapier3d::prelude::*;
+
+/// Handle to a joint in the physics world
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JointHandle(pub ImpulseJointHandle);
+
+/// Kind of joint and its axis or length
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JointKind {
+    /// Bodies keep their relative pose
+    Fixed,
+    /// Hinge rotating about an axis (doors, wheels, pendulums)
+    Revolute {
+        /// Hinge axis in the first body's local space
+        axis: Vec3,
+    },
+    /// Slider translating along an axis (pistons, elevators)
+    Prismatic {
+        /// Slide axis in the first body's local space
+        axis: Vec3,
+    },
+    /// Ball-and-socket rotating freely about the anchor (ragdolls, chains)
+    Spherical,
+    /// Anchors stay at most `max_distance` apart
+    Rope {
+        /// Maximum distance between the anchors
+        max_distance: f32,
+    },
+    /// Anchors are pulled towards `rest_length` apart
+    Spring {
+        /// Distance the spring settles at
+        rest_length: f32,
+        /// Spring stiffness
+        stiffness: f32,
+        /// Spring damping
+        damping: f32,
+    },
+}
+
+/// Motor driving the free axis of a revolute or prismatic joint
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JointMotor {
+    /// Drive towards a target velocity (radians or units per second)
+    Velocity {
+        /// Target velocity
+        target: f32,
+        /// How strongly the velocity is enforced
+        factor: f32,
+    },
+    /// Drive towards a target angle or offset like a spring
+    Position {
+        /// Target angle (radians) or offset
+        target: f32,
+        /// Spring stiffness
+        stiffness: f32,
+        /// Spring damping
+        damping: f32,
+    },
+}
+
+/// Description of a joint between two bodies
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JointDesc {
+    /// Kind of joint
+    pub kind: JointKind,
+    /// Anchor in the first body's local space
+    pub anchor1: Vec3,
+    /// Anchor in the second body's local space
+    pub anchor2: Vec3,
+    /// Allowed range of the free axis (revolute and prismatic joints)
+    pub limits: Option<(f32, f32)>,
+    /// Motor on the free axis (revolute and prismatic joints)
+    pub motor: Option<JointMotor>,
+    /// Maximum force the motor may apply
+    pub max_motor_force: f32,
+    /// Whether the two bodies still collide with each other
+    pub collide_connected: bool,
+}
+
+impl JointDesc {
+    /// Create a joint description with anchors at both body origins
+    #[must_use]
+    pub fn new(kind: JointKind) -> Self {
+        Self {
+            kind,
+            anchor1: Vec3::ZERO,
+            anchor2: Vec3::ZERO,
+            limits: None,
+            motor: None,
+            max_motor_force: f32::MAX,
+            collide_connected: false,
+        }
+    }
+
+    /// Weld two bodies together
+    #[must_use]
+    pub fn fixed() -> Self {
+        Self::new(JointKind::Fixed)
+    }
+
+    /// Hinge about an axis
+    #[must_use]
+    pub fn revolute(axis: Vec3) -> Self {
+        Self::new(JointKind::Revolute { axis })
+    }
+
+    /// Slider along an axis
+    #[must_use]
+    pub fn prismatic(axis: Vec3) -> Self {
+        Self::new(JointKind::Prismatic { axis })
+    }
+
+    /// Ball-and-socket joint
+    #[must_use]
+    pub fn spherical() -> Self {
+        Self::new(JointKind::Spherical)
+    }
+
+    /// Rope with a maximum length
+    #[must_use]
+    pub fn rope(max_distance: f32) -> Self {
+        Self::new(JointKind::Rope { max_distance })
+    }
+
+    /// Spring with a rest length
+    #[must_use]
+    pub fn spring(rest_length: f32, stiffness: f32, damping: f32) -> Self {
+        Self::new(JointKind::Spring {
+            rest_length,
+            stiffness,
+            damping,
+        })
+    }
+
+    /// Set the anchors in each body's local space
+    #[must_use]
+    pub fn with_anchors(mut self, anchor1: Vec3, anchor2: Vec3) -> Self {
+        self.anchor1 = anchor1;
+        self.anchor2 = anchor2;
+        self
+    }
+
+    /// Limit the free axis to `[min, max]` (radians for hinges)
+    #[must_use]
+    pub fn with_limits(mut self, min: f32, max: f32) -> Self {
+        self.limits = Some((min.min(max), min.max(max)));
+        self
+    }
+
+    /// Drive the free axis with a motor
+    #[must_use]
+    pub fn with_motor(mut self, motor: JointMotor) -> Self {
+        self.motor = Some(motor);
+        self
+    }
+
+    /// Cap the force the motor may apply
+    #[must_use]
+    pub fn with_max_motor_force(mut self, force: f32) -> Self {
+        self.max_motor_force = force;
+        self
+    }
+
+    /// Let the connected bodies collide with each other
+    #[must_use]
+    pub fn with_collisions(mut self, enabled: bool) -> Self {
+        self.collide_connected = enabled;
+        self
+    }
+
+    /// Build the rapier joint
+    pub(super) fn build(&self) -> GenericJoint {
+        let anchor1 = point![self.anchor1.x, self.anchor1.y, self.anchor1.z];
+        let anchor2 = point![self.anchor2.x, self.anchor2.y, self.anchor2.z];
+        let mut joint: GenericJoint = match self.kind {
+            JointKind::Fixed => FixedJointBuilder::new()
+                .local_anchor1(anchor1)
+                .local_anchor2(anchor2)
+                .build()
+                .into(),
+            JointKind::Revolute { axis } => RevoluteJointBuilder::new(to_axis(axis))
+                .local_anchor1(anchor1)
+                .local_anchor2(anchor2)
+                .build()
+                .into(),
+            JointKind::Prismatic { axis } => PrismaticJointBuilder::new(to_axis(axis))
+                .local_anchor1(anchor1)
+                .local_anchor2(anchor2)
+                .build()
+                .into(),
+            JointKind::Spherical => SphericalJointBuilder::new()
+                .local_anchor1(anchor1)
+                .local_anchor2(anchor2)
+                .build()
+                .into(),
+            JointKind::Rope { max_distance } => RopeJointBuilder::new(max_distance)
+                .local_anchor1(anchor1)
+                .local_anchor2(anchor2)
+                .build()
+                .into(),
+            JointKind::Spring {
+                rest_length,
+                stiffness,
+                damping,
+            } => SpringJointBuilder::new(rest_length, stiffness, damping)
+                .local_anchor1(anchor1)
+                .local_anchor2(anchor2)
+                .build()
+                .into(),
+        };
+        joint.set_contacts_enabled(self.collide_connected);
+
+        if let Some(axis) = self.kind.free_axis() {
+            if let Some((min, max)) = self.limits {
+                joint.set_limits(axis, [min, max]);
+            }
+            if let Some(motor) = self.motor {
+                apply_motor(&mut joint, axis, motor, self.max_motor_force);
+            }
+        }
+        joint
+    }
+}
+
+impl JointKind {
+    /// Axis that limits and motors act on
+    fn free_axis(self) -> Option<JointAxis> {
+        match self {
+            Self::Revolute { .. } => Some(JointAxis::AngX),
+            Self::Prismatic { .. } => Some(JointAxis::LinX),
+            _ => None,
+        }
+    }
+}
+
+/// Free axis of an existing hinge (angular) or slider (linear) joint
+///
+/// Returns `None` for every other kind, whose axes are either locked or
+/// already driven by the joint itself (ropes and springs).
+pub(super) fn free_axis(joint: &GenericJoint) -> Option<JointAxis> {
+    if joint.locked_axes == JointAxesMask::LOCKED_REVOLUTE_AXES {
+        Some(JointAxis::AngX)
+    } else if joint.locked_axes == JointAxesMask::LOCKED_PRISMATIC_AXES {
+        Some(JointAxis::LinX)
+    } else {
+        None
+    }
+}
+
+/// Configure a motor on one axis of a joint
+pub(super) fn apply_motor(
+    joint: &mut GenericJoint,
+    axis: JointAxis,
+    motor: JointMotor,
+    max_force: f32,
+) {
+    match motor {
+        JointMotor::Velocity { target, factor } => {
+            joint.set_motor_velocity(axis, target, factor);
+        }
+        JointMotor::Position {
+            target,
+            stiffness,
+            damping,
+        } => {
+            joint.set_motor_position(axis, target, stiffness, damping);
+        }
+    }
+    joint.set_motor_max_force(axis, max_force);
+}
+
+fn to_axis(axis: Vec3) -> UnitVector<f32> {
+    let axis = axis.try_normalize().unwrap_or(Vec3::X);
+    UnitVector::new_unchecked(vector![axis.x, axis.y, axis.z])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::{Physics, RigidBodyHandle};
+    use glam::Quat;
+
+    /// Static anchor and a free box hinged to it about Y, without gravity
+    fn hinge(physics: &mut Physics, desc: &JointDesc) -> (RigidBodyHandle, JointHandle) {
+        let anchor = physics.create_static_body(Vec3::ZERO, Quat::IDENTITY);
+        let body = physics.create_dynamic_body(Vec3::ZERO, Quat::IDENTITY);
+        physics.add_box_collider(body, Vec3::new(1.0, 0.1, 0.1), 1.0);
+        let joint = physics.create_joint(anchor, body, desc);
+        (body, joint)
+    }
+
+    fn yaw(physics: &Physics, body: RigidBodyHandle) -> f32 {
+        let (axis, angle) = physics.get_rotation(body).unwrap().to_axis_angle();
+        angle * axis.y.signum()
+    }
+
+    #[test]
+    fn test_hinge_motor_spins_body() {
+        let mut physics = Physics::with_gravity(Vec3::ZERO);
+        let (body, joint) = hinge(&mut physics, &JointDesc::revolute(Vec3::Y));
+        physics.set_joint_motor(
+            joint,
+            JointMotor::Velocity {
+                target: 1.0,
+                factor: 100.0,
+            },
+            1000.0,
+        );
+        for _ in 0..30 {
+            physics.step(1.0 / 60.0);
+        }
+        let angle = yaw(&physics, body);
+        assert!(angle > 0.3 && angle < 0.6, "angle {angle}");
+        assert!(physics.get_position(body).unwrap().length() < 1e-3);
+    }
+
+    #[test]
+    fn test_hinge_limits_clamp_angle() {
+        let mut physics = Physics::with_gravity(Vec3::ZERO);
+        let desc = JointDesc::revolute(Vec3::Y).with_motor(JointMotor::Velocity {
+            target: 3.0,
+            factor: 100.0,
+        });
+        let (body, joint) = hinge(&mut physics, &desc);
+        physics.set_joint_limits(joint, -0.25, 0.25);
+        for _ in 0..120 {
+            physics.step(1.0 / 60.0);
+        }
+        let angle = yaw(&physics, body);
+        assert!((angle - 0.25).abs() < 0.05, "angle {angle}");
+    }
+}
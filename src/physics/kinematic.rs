@@ -0,0 +1,207 @@
+//! Animation-driven kinematic bodies
+//!
+//! Drives position-based kinematic bodies from animated transforms or bones
+//! each fixed step, so they push and carry dynamic bodies instead of
+//! teleporting through them.
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use glam::Mat4;
+use hecs::Entity;
+
+use super::{Physics, RigidBodyHandle};
+use crate::animation::Skeleton;
+use crate::ecs::{GlobalTransform, Transform, World};
+
+/// What a kinematic body follows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KinematicSource {
+    /// The entity's own global transform (doors, elevators)
+    Transform,
+    /// A bone on another entity's skeleton (boss limbs)
+    Bone {
+        /// Entity owning the skeleton
+        entity: Entity,
+        /// Bone index
+        bone: usize,
+    },
+    /// A named socket on another entity's skeleton
+    Socket {
+        /// Entity owning the skeleton
+        entity: Entity,
+        /// Socket name
+        socket: String,
+    },
+}
+
+/// Component driving a kinematic body from an animated source
+#[derive(Debug, Clone)]
+pub struct KinematicDriver {
+    /// Body to drive (must be kinematic position-based)
+    pub body: RigidBodyHandle,
+    /// What the body follows
+    pub source: KinematicSource,
+    /// Offset from the source
+    pub offset: Transform,
+}
+
+impl KinematicDriver {
+    /// Drive a body from the entity's own transform
+    #[must_use]
+    pub fn from_transform(body: RigidBodyHandle) -> Self {
+        Self {
+            body,
+            source: KinematicSource::Transform,
+            offset: Transform::new(),
+        }
+    }
+
+    /// Drive a body from a bone
+    #[must_use]
+    pub fn from_bone(body: RigidBodyHandle, entity: Entity, bone: usize) -> Self {
+        Self {
+            body,
+            source: KinematicSource::Bone { entity, bone },
+            offset: Transform::new(),
+        }
+    }
+
+    /// Drive a body from a socket
+    #[must_use]
+    pub fn from_socket(body: RigidBodyHandle, entity: Entity, socket: impl Into<String>) -> Self {
+        Self {
+            body,
+            source: KinematicSource::Socket {
+                entity,
+                socket: socket.into(),
+            },
+            offset: Transform::new(),
+        }
+    }
+
+    /// Set the offset from the source
+    #[must_use]
+    pub fn with_offset(mut self, offset: Transform) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+/// Get an entity's world matrix, preferring its global transform
+fn world_matrix(world: &World, entity: Entity) -> Option<Mat4> {
+    if let Ok(global) = world.get::<GlobalTransform>(entity) {
+        return Some(global.matrix);
+    }
+    world.get::<Transform>(entity).ok().map(|t| t.matrix())
+}
+
+/// Get a skeleton's bone world matrices, computing them once per entity
+fn bone_matrices<'a>(
+    world: &World,
+    entity: Entity,
+    cache: &'a mut HashMap<Entity, Vec<Mat4>>,
+) -> Option<&'a [Mat4]> {
+    if let Entry::Vacant(entry) = cache.entry(entity) {
+        let skeleton = world.get::<Skeleton>(entity).ok()?;
+        entry.insert(skeleton.compute_world_matrices());
+    }
+    cache.get(&entity).map(Vec::as_slice)
+}
+
+/// Resolve the target world matrix for a driver
+fn target_matrix(
+    world: &World,
+    entity: Entity,
+    source: &KinematicSource,
+    cache: &mut HashMap<Entity, Vec<Mat4>>,
+) -> Option<Mat4> {
+    match source {
+        KinematicSource::Transform => world_matrix(world, entity),
+        KinematicSource::Bone { entity, bone } => {
+            let bones = bone_matrices(world, *entity, cache)?;
+            Some(world_matrix(world, *entity)? * *bones.get(*bone)?)
+        }
+        KinematicSource::Socket { entity, socket } => {
+            let bones = bone_matrices(world, *entity, cache)?;
+            let skeleton = world.get::<Skeleton>(*entity).ok()?;
+            Some(world_matrix(world, *entity)? * skeleton.socket_matrix(socket, bones)?)
+        }
+    }
+}
+
+/// Set next kinematic targets for every driven body
+///
+/// Call after animation is applied and before `Physics::step`. Rapier
+/// derives the body velocity from the move, so dynamic bodies in the way are
+/// pushed and carried. With a fixed timestep one `step` may run several
+/// ticks or none, and the whole move happens on the first of them; to spread
+/// it evenly, advance animation per tick and call this from
+/// `Physics::step_with`.
+pub fn drive_kinematic_bodies(world: &World, physics: &mut Physics) {
+    let mut bones = HashMap::new();
+    for (entity, driver) in world.query::<&KinematicDriver>().iter() {
+        let Some(target) = target_matrix(world, entity, &driver.source, &mut bones) else {
+            continue;
+        };
+        let (_, rotation, position) =
+            (target * driver.offset.matrix()).to_scale_rotation_translation();
+        physics.set_kinematic_pose(driver.body, position, rotation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{Quat, Vec3};
+
+    use super::*;
+    use crate::animation::Bone;
+
+    #[test]
+    fn test_drives_body_from_transform() {
+        let mut physics = Physics::new();
+        let body = physics.create_kinematic_body(Vec3::ZERO, Quat::IDENTITY);
+        let mut world = World::new();
+        let rotation = Quat::from_rotation_y(0.5);
+        world.spawn((
+            Transform::from_position_rotation(Vec3::new(1.0, 2.0, 3.0), rotation),
+            KinematicDriver::from_transform(body),
+        ));
+
+        drive_kinematic_bodies(&world, &mut physics);
+        physics.step(1.0 / 60.0);
+
+        let position = physics.get_position(body).unwrap();
+        assert!(position.distance(Vec3::new(1.0, 2.0, 3.0)) < 1e-4);
+        assert!(physics.get_rotation(body).unwrap().angle_between(rotation) < 1e-4);
+    }
+
+    #[test]
+    fn test_drives_body_from_bone_with_offset() {
+        let mut skeleton = Skeleton::new();
+        let root = skeleton.add_bone(Bone::new("root"));
+        let mut arm = Bone::new("arm");
+        arm.translation = Vec3::new(0.0, 1.0, 0.0);
+        arm.rotation = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+        let arm = skeleton.add_bone(arm);
+        skeleton.set_parent(arm, root);
+
+        let mut physics = Physics::new();
+        let first = physics.create_kinematic_body(Vec3::ZERO, Quat::IDENTITY);
+        let second = physics.create_kinematic_body(Vec3::ZERO, Quat::IDENTITY);
+        let mut world = World::new();
+        let owner = world.spawn((Transform::from_position(Vec3::new(5.0, 0.0, 0.0)), skeleton));
+        let offset = Transform::from_position(Vec3::new(2.0, 0.0, 0.0));
+        world.spawn((KinematicDriver::from_bone(first, owner, arm).with_offset(offset),));
+        world.spawn((KinematicDriver::from_bone(second, owner, root),));
+
+        drive_kinematic_bodies(&world, &mut physics);
+        physics.step(1.0 / 60.0);
+
+        // The arm is rotated a quarter turn about Z, so its local +X offset points up
+        let position = physics.get_position(first).unwrap();
+        assert!(position.distance(Vec3::new(5.0, 3.0, 0.0)) < 1e-4);
+        let position = physics.get_position(second).unwrap();
+        assert!(position.distance(Vec3::new(5.0, 0.0, 0.0)) < 1e-4);
+    }
+}
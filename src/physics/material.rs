@@ -0,0 +1,159 @@
+//! Physics materials
+//!
+//! Per-collider friction and restitution. When two colliders touch, each
+//! coefficient is combined using the stronger of the two colliders' combine
+//! rules (`Max` > `Multiply` > `Min` > `Average`), so an ice floor can stay
+//! slippery under any crate and a bouncy pad can bounce every ball.
+
+use rapier3d::prelude::*;
+
+/// How two colliders' coefficients are merged at a contact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CombineRule {
+    /// Mean of both coefficients
+    #[default]
+    Average,
+    /// Smaller coefficient
+    Min,
+    /// Product of both coefficients
+    Multiply,
+    /// Larger coefficient
+    Max,
+}
+
+impl CombineRule {
+    /// Convert to the rapier combine rule
+    pub(super) fn to_rapier(self) -> CoefficientCombineRule {
+        match self {
+            Self::Average => CoefficientCombineRule::Average,
+            Self::Min => CoefficientCombineRule::Min,
+            Self::Multiply => CoefficientCombineRule::Multiply,
+            Self::Max => CoefficientCombineRule::Max,
+        }
+    }
+
+    /// Convert from the rapier combine rule
+    pub(super) fn from_rapier(rule: CoefficientCombineRule) -> Self {
+        match rule {
+            CoefficientCombineRule::Average => Self::Average,
+            CoefficientCombineRule::Min => Self::Min,
+            CoefficientCombineRule::Multiply => Self::Multiply,
+            CoefficientCombineRule::Max => Self::Max,
+        }
+    }
+}
+
+/// Surface properties of a collider
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhysicsMaterial {
+    /// Friction coefficient (0 = frictionless)
+    pub friction: f32,
+    /// Restitution coefficient (0 = no bounce, 1 = perfectly elastic)
+    pub restitution: f32,
+    /// How friction is combined with the other collider
+    pub friction_combine: CombineRule,
+    /// How restitution is combined with the other collider
+    pub restitution_combine: CombineRule,
+}
+
+impl PhysicsMaterial {
+    /// Default material used for new colliders
+    pub const DEFAULT: Self = Self::new(0.5, 0.0);
+    /// Nearly frictionless surface
+    pub const ICE: Self = Self::new(0.02, 0.05).with_friction_combine(CombineRule::Min);
+    /// Grippy, moderately bouncy surface
+    pub const RUBBER: Self = Self::new(0.9, 0.8);
+    /// Surface that bounces anything landing on it
+    pub const BOUNCY: Self = Self::new(0.5, 1.0).with_restitution_combine(CombineRule::Max);
+
+    /// Create a material with average combine rules
+    #[must_use]
+    pub const fn new(friction: f32, restitution: f32) -> Self {
+        Self {
+            friction,
+            restitution,
+            friction_combine: CombineRule::Average,
+            restitution_combine: CombineRule::Average,
+        }
+    }
+
+    /// Set the friction combine rule
+    #[must_use]
+    pub const fn with_friction_combine(mut self, rule: CombineRule) -> Self {
+        self.friction_combine = rule;
+        self
+    }
+
+    /// Set the restitution combine rule
+    #[must_use]
+    pub const fn with_restitution_combine(mut self, rule: CombineRule) -> Self {
+        self.restitution_combine = rule;
+        self
+    }
+
+    /// Apply this material to a rapier collider
+    pub(super) fn apply(&self, collider: &mut Collider) {
+        collider.set_friction(self.friction.max(0.0));
+        collider.set_restitution(self.restitution.max(0.0));
+        collider.set_friction_combine_rule(self.friction_combine.to_rapier());
+        collider.set_restitution_combine_rule(self.restitution_combine.to_rapier());
+    }
+
+    /// Read the material of a rapier collider
+    pub(super) fn of(collider: &Collider) -> Self {
+        Self {
+            friction: collider.friction(),
+            restitution: collider.restitution(),
+            friction_combine: CombineRule::from_rapier(collider.friction_combine_rule()),
+            restitution_combine: CombineRule::from_rapier(collider.restitution_combine_rule()),
+        }
+    }
+}
+
+impl Default for PhysicsMaterial {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{Quat, Vec3};
+
+    use super::*;
+    use crate::physics::Physics;
+
+    /// Highest point a ball reaches after bouncing off the ground, or zero
+    /// if it never bounces
+    fn rebound_height(material: PhysicsMaterial) -> f32 {
+        let mut physics = Physics::new();
+        let ground = physics.create_static_body(Vec3::ZERO, Quat::IDENTITY);
+        physics.add_ground_plane(ground);
+        let body = physics.create_dynamic_body(Vec3::new(0.0, 2.0, 0.0), Quat::IDENTITY);
+        let ball = physics.add_sphere_collider(body, 0.25, 1.0);
+        physics.set_material(ball, material);
+        assert_eq!(physics.material(ball), Some(material));
+
+        let mut bounced = false;
+        let mut peak = 0.0_f32;
+        for _ in 0..180 {
+            physics.step(1.0 / 60.0);
+            let velocity = physics.get_linear_velocity(body).unwrap();
+            bounced |= velocity.y > 0.0;
+            if bounced {
+                peak = peak.max(physics.get_position(body).unwrap().y);
+            }
+        }
+        peak
+    }
+
+    #[test]
+    fn test_max_restitution_bounces_higher() {
+        let bouncy = rebound_height(PhysicsMaterial::BOUNCY);
+        let dull = rebound_height(PhysicsMaterial::DEFAULT);
+
+        // The ground has no restitution, so only `Max` keeps the full bounce
+        assert!(bouncy > 1.5, "bouncy ball peaked at {bouncy}");
+        assert!(dull < 0.5, "default ball peaked at {dull}");
+    }
+}
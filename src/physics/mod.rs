@@ -2,6 +2,26 @@
 //!
 //! Built on top of rapier3d
 
+mod character;
+mod events;
+mod filter;
+mod forces;
+mod joints;
+mod kinematic;
+mod material;
+mod query;
+mod snapshot;
+mod vehicle;
 mod world;
 
-pub use world::{ColliderHandle, Physics, RaycastHit, RigidBodyHandle};
+pub use character::CharacterController;
+pub use events::{CollisionEvent, ContactState, TriggerEvent};
+pub use filter::{ALL_GROUPS, CollisionFilter};
+pub use forces::{Falloff, ForceField, ForceFieldHandle, ForceFieldKind, ForceVolume};
+pub use joints::{JointDesc, JointHandle, JointKind, JointMotor};
+pub use kinematic::{KinematicDriver, KinematicSource, drive_kinematic_bodies};
+pub use material::{CombineRule, PhysicsMaterial};
+pub use query::QueryWorld;
+pub use snapshot::PhysicsSnapshot;
+pub use vehicle::{Vehicle, WheelDesc};
+pub use world::{CastShape, ColliderHandle, Physics, RaycastHit, RigidBodyHandle, ShapeCastHit};
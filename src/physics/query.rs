@@ -0,0 +1,542 @@
+//! Query-only physics snapshot
+//!
+//! A read-only copy of collider shapes and positions with its own bounding
+//! volume hierarchy. AI perception can raycast and overlap against it from
+//! worker threads between physics steps without touching the simulation.
+
+use glam::Vec3;
+use rapier3d::parry::bounding_volume::{Aabb, BoundingVolume};
+use rapier3d::parry::query::{self, RayCast, RayIntersection};
+use rapier3d::parry::shape::Ball;
+use rapier3d::prelude::*;
+
+use super::filter::CollisionFilter;
+use super::{ColliderHandle, RaycastHit, RigidBodyHandle};
+
+/// Maximum colliders per leaf node
+const LEAF_SIZE: usize = 4;
+
+/// A collider copied into the snapshot
+#[derive(Clone)]
+struct GhostCollider {
+    /// Original collider handle
+    handle: ColliderHandle,
+    /// Body the collider is attached to
+    body: Option<RigidBodyHandle>,
+    /// Collision group memberships and filter
+    groups: (u32, u32),
+    /// Shared shape (reference counted, not deep-copied)
+    shape: SharedShape,
+    /// World position at snapshot time
+    position: Isometry<f32>,
+    /// World bounds at snapshot time
+    aabb: Aabb,
+}
+
+impl GhostCollider {
+    /// Check the collider against a collision filter (sensors are never in the snapshot)
+    fn accepted_by(&self, filter: &CollisionFilter) -> bool {
+        filter.exclude_collider != Some(self.handle)
+            && (filter.exclude_body.is_none() || self.body != filter.exclude_body)
+            && filter.accepts_groups(self.groups.0, self.groups.1)
+    }
+}
+
+/// Node of the bounding volume hierarchy
+#[derive(Debug, Clone, Copy)]
+enum Node {
+    /// Range of colliders
+    Leaf {
+        /// Bounds of all colliders in the range
+        aabb: Aabb,
+        /// First collider index
+        start: usize,
+        /// One past the last collider index
+        end: usize,
+    },
+    /// Two child nodes
+    Branch {
+        /// Bounds of both children
+        aabb: Aabb,
+        /// Left child index
+        left: usize,
+        /// Right child index
+        right: usize,
+    },
+}
+
+impl Node {
+    const fn aabb(&self) -> &Aabb {
+        match self {
+            Self::Leaf { aabb, .. } | Self::Branch { aabb, .. } => aabb,
+        }
+    }
+}
+
+/// Read-only snapshot of the physics world for queries
+///
+/// Build one with `Physics::query_world` after each step and share it
+/// behind an `Arc`. Sensors are left out, so perception rays pass through
+/// trigger volumes.
+#[derive(Clone, Default)]
+pub struct QueryWorld {
+    /// Colliders, ordered so every leaf covers a contiguous range
+    colliders: Vec<GhostCollider>,
+    /// Hierarchy nodes; the root is the first node
+    nodes: Vec<Node>,
+}
+
+impl QueryWorld {
+    /// Build a snapshot from a collider set
+    pub(super) fn build(collider_set: &ColliderSet) -> Self {
+        let colliders = collider_set
+            .iter()
+            .filter(|(_, collider)| !collider.is_sensor())
+            .map(|(handle, collider)| GhostCollider {
+                handle: ColliderHandle(handle),
+                body: collider.parent().map(RigidBodyHandle),
+                groups: (
+                    collider.collision_groups().memberships.bits(),
+                    collider.collision_groups().filter.bits(),
+                ),
+                shape: collider.shared_shape().clone(),
+                position: *collider.position(),
+                aabb: collider.compute_aabb(),
+            })
+            .collect();
+
+        let mut world = Self {
+            colliders,
+            nodes: Vec::new(),
+        };
+        if !world.colliders.is_empty() {
+            world.build_node(0, world.colliders.len());
+        }
+        world
+    }
+
+    /// Build the node covering a collider range, returning its index
+    fn build_node(&mut self, start: usize, end: usize) -> usize {
+        let items = &mut self.colliders[start..end];
+        let aabb = items
+            .iter()
+            .skip(1)
+            .fold(items[0].aabb, |acc, c| acc.merged(&c.aabb));
+
+        let index = self.nodes.len();
+        if end - start <= LEAF_SIZE {
+            self.nodes.push(Node::Leaf { aabb, start, end });
+            return index;
+        }
+
+        // Split at the median along the longest axis
+        let extents = aabb.extents();
+        let axis = (0..3)
+            .max_by(|a, b| extents[*a].total_cmp(&extents[*b]))
+            .unwrap_or(0);
+        items.sort_unstable_by(|a, b| a.aabb.center()[axis].total_cmp(&b.aabb.center()[axis]));
+
+        self.nodes.push(Node::Leaf { aabb, start, end });
+        let mid = start + (end - start) / 2;
+        let left = self.build_node(start, mid);
+        let right = self.build_node(mid, end);
+        self.nodes[index] = Node::Branch { aabb, left, right };
+        index
+    }
+
+    /// Get the number of colliders in the snapshot
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.colliders.len()
+    }
+
+    /// Check if the snapshot has no colliders
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.colliders.is_empty()
+    }
+
+    /// Cast a ray and return the first hit
+    #[must_use]
+    pub fn raycast(&self, origin: Vec3, direction: Vec3, max_distance: f32) -> Option<RaycastHit> {
+        self.raycast_filtered(origin, direction, max_distance, |_| true)
+    }
+
+    /// Cast a ray against colliders accepted by `filter`
+    #[must_use]
+    pub fn raycast_filtered(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        filter: impl Fn(ColliderHandle) -> bool,
+    ) -> Option<RaycastHit> {
+        self.cast_ray(origin, direction, max_distance, |collider| {
+            filter(collider.handle)
+        })
+    }
+
+    /// Cast a ray against colliders accepted by a collision filter
+    #[must_use]
+    pub fn raycast_with(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        filter: &CollisionFilter,
+    ) -> Option<RaycastHit> {
+        self.cast_ray(origin, direction, max_distance, |collider| {
+            collider.accepted_by(filter)
+        })
+    }
+
+    /// Nearest ray hit among colliders passing `accept`
+    fn cast_ray(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        accept: impl Fn(&GhostCollider) -> bool,
+    ) -> Option<RaycastHit> {
+        let ray = Ray::new(
+            point![origin.x, origin.y, origin.z],
+            vector![direction.x, direction.y, direction.z],
+        );
+        let mut best: Option<(ColliderHandle, RayIntersection)> = None;
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            let limit = best.map_or(max_distance, |(_, hit)| hit.time_of_impact);
+            if node.aabb().cast_local_ray(&ray, limit, true).is_none() {
+                continue;
+            }
+            match *node {
+                Node::Leaf { start, end, .. } => {
+                    for collider in &self.colliders[start..end] {
+                        if !accept(collider) {
+                            continue;
+                        }
+                        let limit = best.map_or(max_distance, |(_, hit)| hit.time_of_impact);
+                        if let Some(hit) = collider.shape.cast_ray_and_get_normal(
+                            &collider.position,
+                            &ray,
+                            limit,
+                            true,
+                        ) {
+                            best = Some((collider.handle, hit));
+                        }
+                    }
+                }
+                Node::Branch { left, right, .. } => {
+                    stack.push(left);
+                    stack.push(right);
+                }
+            }
+        }
+
+        best.map(|(collider, hit)| to_hit(collider, &ray, &hit))
+    }
+
+    /// Cast a ray and return where it enters every collider, nearest first
+    #[must_use]
+    pub fn raycast_all(&self, origin: Vec3, direction: Vec3, max_distance: f32) -> Vec<RaycastHit> {
+        let ray = Ray::new(
+            point![origin.x, origin.y, origin.z],
+            vector![direction.x, direction.y, direction.z],
+        );
+        let mut hits = Vec::new();
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if node
+                .aabb()
+                .cast_local_ray(&ray, max_distance, true)
+                .is_none()
+            {
+                continue;
+            }
+            match *node {
+                Node::Leaf { start, end, .. } => {
+                    for collider in &self.colliders[start..end] {
+                        if let Some(hit) = collider.shape.cast_ray_and_get_normal(
+                            &collider.position,
+                            &ray,
+                            max_distance,
+                            true,
+                        ) {
+                            hits.push(to_hit(collider.handle, &ray, &hit));
+                        }
+                    }
+                }
+                Node::Branch { left, right, .. } => {
+                    stack.push(left);
+                    stack.push(right);
+                }
+            }
+        }
+
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// Check if nothing blocks the segment between two points
+    ///
+    /// `ignore` excludes colliders such as the observer's and target's own.
+    #[must_use]
+    pub fn line_of_sight(&self, from: Vec3, to: Vec3, ignore: &[ColliderHandle]) -> bool {
+        let delta = to - from;
+        let distance = delta.length();
+        if distance <= f32::EPSILON {
+            return true;
+        }
+        self.raycast_filtered(from, delta / distance, distance, |handle| {
+            !ignore.contains(&handle)
+        })
+        .is_none()
+    }
+
+    /// Find colliders whose bounds intersect a box (broadphase only)
+    #[must_use]
+    pub fn overlap_aabb(&self, min: Vec3, max: Vec3) -> Vec<ColliderHandle> {
+        let bounds = Aabb::new(point![min.x, min.y, min.z], point![max.x, max.y, max.z]);
+        let mut hits = Vec::new();
+        self.visit_aabb(&bounds, |collider| hits.push(collider.handle));
+        hits
+    }
+
+    /// Find colliders whose bounds intersect a box, restricted by a collision filter
+    #[must_use]
+    pub fn overlap_aabb_with(
+        &self,
+        min: Vec3,
+        max: Vec3,
+        filter: &CollisionFilter,
+    ) -> Vec<ColliderHandle> {
+        let bounds = Aabb::new(point![min.x, min.y, min.z], point![max.x, max.y, max.z]);
+        let mut hits = Vec::new();
+        self.visit_aabb(&bounds, |collider| {
+            if collider.accepted_by(filter) {
+                hits.push(collider.handle);
+            }
+        });
+        hits
+    }
+
+    /// Find colliders overlapping a sphere, restricted by a collision filter
+    #[must_use]
+    pub fn overlap_sphere_with(
+        &self,
+        center: Vec3,
+        radius: f32,
+        filter: &CollisionFilter,
+    ) -> Vec<ColliderHandle> {
+        self.overlap_sphere_where(center, radius, |collider| collider.accepted_by(filter))
+    }
+
+    /// Find colliders overlapping a sphere
+    #[must_use]
+    pub fn overlap_sphere(&self, center: Vec3, radius: f32) -> Vec<ColliderHandle> {
+        self.overlap_sphere_where(center, radius, |_| true)
+    }
+
+    /// Colliders passing `accept` that overlap a sphere
+    fn overlap_sphere_where(
+        &self,
+        center: Vec3,
+        radius: f32,
+        accept: impl Fn(&GhostCollider) -> bool,
+    ) -> Vec<ColliderHandle> {
+        let ball = Ball::new(radius);
+        let position = Isometry::translation(center.x, center.y, center.z);
+        let bounds = ball.aabb(&position);
+        let mut hits = Vec::new();
+        self.visit_aabb(&bounds, |collider| {
+            if !accept(collider) {
+                return;
+            }
+            let overlaps = query::intersection_test(
+                &position,
+                &ball,
+                &collider.position,
+                collider.shape.as_ref(),
+            );
+            if overlaps.is_ok_and(|hit| hit) {
+                hits.push(collider.handle);
+            }
+        });
+        hits
+    }
+
+    /// Call `visit` for every collider whose bounds intersect `bounds`
+    fn visit_aabb(&self, bounds: &Aabb, mut visit: impl FnMut(&GhostCollider)) {
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.aabb().intersects(bounds) {
+                continue;
+            }
+            match *node {
+                Node::Leaf { start, end, .. } => {
+                    for collider in &self.colliders[start..end] {
+                        if collider.aabb.intersects(bounds) {
+                            visit(collider);
+                        }
+                    }
+                }
+                Node::Branch { left, right, .. } => {
+                    stack.push(left);
+                    stack.push(right);
+                }
+            }
+        }
+    }
+}
+
+/// Convert a ray intersection into a hit
+fn to_hit(collider: ColliderHandle, ray: &Ray, hit: &RayIntersection) -> RaycastHit {
+    let point = ray.point_at(hit.time_of_impact);
+    RaycastHit {
+        collider,
+        point: Vec3::new(point.x, point.y, point.z),
+        normal: Vec3::new(hit.normal.x, hit.normal.y, hit.normal.z),
+        distance: hit.time_of_impact,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Quat;
+
+    use super::*;
+    use crate::physics::Physics;
+
+    /// Static spheres of radius 0.5 at the given centers
+    fn spheres(centers: &[Vec3]) -> (Physics, Vec<ColliderHandle>) {
+        let mut physics = Physics::new();
+        let handles = centers
+            .iter()
+            .map(|&center| {
+                let body = physics.create_static_body(center, Quat::IDENTITY);
+                physics.add_sphere_collider(body, 0.5, 1.0)
+            })
+            .collect();
+        physics.step(1.0 / 60.0);
+        (physics, handles)
+    }
+
+    #[test]
+    fn test_raycast_finds_nearest_across_leaves() {
+        // Far spheres first, so the nearest is neither first in storage nor in the first leaf
+        let centers: Vec<Vec3> = (1..=12)
+            .rev()
+            .map(|i| Vec3::new(i as f32 * 2.0, 0.0, 0.0))
+            .collect();
+        let (physics, handles) = spheres(&centers);
+        let world = physics.query_world();
+        assert_eq!(world.len(), 12);
+        assert!(world.len() > LEAF_SIZE * 2);
+
+        let hit = world.raycast(Vec3::ZERO, Vec3::X, 100.0).unwrap();
+        assert_eq!(hit.collider, handles[11]);
+        assert!((hit.distance - 1.5).abs() < 1e-4);
+
+        let hit = world
+            .raycast(Vec3::new(30.0, 0.0, 0.0), Vec3::NEG_X, 100.0)
+            .unwrap();
+        assert_eq!(hit.collider, handles[0]);
+        assert!((hit.distance - 5.5).abs() < 1e-4);
+
+        let all = world.raycast_all(Vec3::ZERO, Vec3::X, 100.0);
+        assert_eq!(all.len(), 12);
+        assert!(
+            all.windows(2)
+                .all(|pair| pair[0].distance <= pair[1].distance)
+        );
+        assert!(world.raycast(Vec3::ZERO, Vec3::X, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_sensors_are_left_out() {
+        let mut physics = Physics::new();
+        let zone = physics.create_static_body(Vec3::new(2.0, 0.0, 0.0), Quat::IDENTITY);
+        physics.add_box_sensor(zone, Vec3::ONE);
+        let wall = physics.create_static_body(Vec3::new(6.0, 0.0, 0.0), Quat::IDENTITY);
+        let wall_collider = physics.add_box_collider(wall, Vec3::ONE, 1.0);
+        physics.step(1.0 / 60.0);
+
+        let world = physics.query_world();
+        assert_eq!(world.len(), 1);
+        let hit = world.raycast(Vec3::ZERO, Vec3::X, 100.0).unwrap();
+        assert_eq!(hit.collider, wall_collider);
+        assert!(
+            world
+                .overlap_sphere(Vec3::new(2.0, 0.0, 0.0), 0.5)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_line_of_sight_ignores_listed_colliders() {
+        let (physics, handles) = spheres(&[Vec3::new(5.0, 0.0, 0.0), Vec3::new(10.0, 0.0, 0.0)]);
+        let world = physics.query_world();
+        let eye = Vec3::ZERO;
+
+        assert!(world.line_of_sight(eye, Vec3::new(3.0, 0.0, 0.0), &[]));
+        assert!(!world.line_of_sight(eye, Vec3::new(10.0, 0.0, 0.0), &[]));
+        assert!(!world.line_of_sight(eye, Vec3::new(10.0, 0.0, 0.0), &[handles[1]]));
+        assert!(world.line_of_sight(eye, Vec3::new(10.0, 0.0, 0.0), &handles));
+        assert!(world.line_of_sight(eye, Vec3::new(5.0, 3.0, 0.0).normalize() * 20.0, &[]));
+    }
+
+    #[test]
+    fn test_overlap_sphere_is_exact_and_aabb_is_coarse() {
+        let (physics, handles) = spheres(&[Vec3::new(1.2, 1.2, 0.0), Vec3::new(0.8, 0.0, 0.0)]);
+        let world = physics.query_world();
+
+        // The corner sphere's bounds reach into the box but the sphere itself stays out
+        let mut coarse = world.overlap_aabb(Vec3::splat(-1.0), Vec3::splat(1.0));
+        coarse.sort_by_key(|handle| handle.0.into_raw_parts());
+        assert_eq!(coarse, handles);
+        assert_eq!(world.overlap_sphere(Vec3::ZERO, 1.0), [handles[1]]);
+
+        let skip_near = CollisionFilter::new().excluding_collider(handles[1]);
+        assert!(
+            world
+                .overlap_sphere_with(Vec3::ZERO, 1.0, &skip_near)
+                .is_empty()
+        );
+        assert_eq!(
+            world.overlap_aabb_with(Vec3::splat(-1.0), Vec3::splat(1.0), &skip_near),
+            [handles[0]]
+        );
+    }
+
+    #[test]
+    fn test_empty_world() {
+        let world = QueryWorld::default();
+        assert!(world.is_empty());
+        assert!(world.raycast(Vec3::ZERO, Vec3::X, 100.0).is_none());
+        assert!(world.raycast_all(Vec3::ZERO, Vec3::X, 100.0).is_empty());
+        assert!(world.line_of_sight(Vec3::ZERO, Vec3::X, &[]));
+        assert!(world.overlap_sphere(Vec3::ZERO, 10.0).is_empty());
+        assert!(
+            world
+                .overlap_aabb(Vec3::splat(-10.0), Vec3::splat(10.0))
+                .is_empty()
+        );
+
+        let physics = Physics::new();
+        assert!(physics.query_world().is_empty());
+    }
+}
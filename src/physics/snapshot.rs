@@ -0,0 +1,140 @@
+//! Physics snapshots
+//!
+//! A full copy of the simulation state (bodies, velocities, colliders,
+//! joints, contact caches and force fields) that can be restored later or
+//! serialized with serde for save games, rollback networking and replays.
+//! Transient collision and trigger events are not included.
+
+use rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::forces::ForceField;
+
+/// Captured physics state, created by `Physics::snapshot`
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PhysicsSnapshot {
+    /// Gravity vector
+    pub(super) gravity: glam::Vec3,
+    /// Integration parameters
+    pub(super) integration_parameters: IntegrationParameters,
+    /// Island manager
+    pub(super) island_manager: IslandManager,
+    /// Broad phase
+    pub(super) broad_phase: DefaultBroadPhase,
+    /// Narrow phase, including contact caches used for warm starting
+    pub(super) narrow_phase: NarrowPhase,
+    /// Rigid body set
+    pub(super) rigid_body_set: RigidBodySet,
+    /// Collider set
+    pub(super) collider_set: ColliderSet,
+    /// Impulse joint set
+    pub(super) impulse_joint_set: ImpulseJointSet,
+    /// Multibody joint set
+    pub(super) multibody_joint_set: MultibodyJointSet,
+    /// CCD solver
+    pub(super) ccd_solver: CCDSolver,
+    /// Query pipeline
+    pub(super) query_pipeline: QueryPipeline,
+    /// Force field slots
+    pub(super) force_fields: Vec<Option<ForceField>>,
+    /// Fixed timestep, if any
+    pub(super) fixed_timestep: Option<f32>,
+    /// Unsimulated time carried over to the next step
+    pub(super) accumulator: f32,
+    /// Simulation ticks run so far
+    pub(super) tick: u64,
+}
+
+impl PhysicsSnapshot {
+    /// Simulation tick the snapshot was taken at
+    #[must_use]
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Number of rigid bodies captured
+    #[must_use]
+    pub fn body_count(&self) -> usize {
+        self.rigid_body_set.len()
+    }
+}
+
+impl std::fmt::Debug for PhysicsSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PhysicsSnapshot")
+            .field("tick", &self.tick)
+            .field("bodies", &self.rigid_body_set.len())
+            .field("colliders", &self.collider_set.len())
+            .field("joints", &self.impulse_joint_set.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{Quat, Vec3};
+
+    use crate::physics::{Physics, RigidBodyHandle};
+
+    /// Ground with a few boxes dropping onto it, stepped at a fixed rate
+    fn scene() -> (Physics, Vec<RigidBodyHandle>) {
+        let mut physics = Physics::new();
+        physics.set_fixed_timestep(Some(1.0 / 60.0));
+        let ground = physics.create_static_body(Vec3::ZERO, Quat::IDENTITY);
+        physics.add_ground_plane(ground);
+        let bodies = (0..3)
+            .map(|i| {
+                let position = Vec3::new(i as f32 * 0.3, 1.0 + i as f32 * 1.2, 0.0);
+                let body = physics.create_dynamic_body(position, Quat::from_rotation_z(0.3));
+                physics.add_box_collider(body, Vec3::splat(0.5), 1.0);
+                body
+            })
+            .collect();
+        (physics, bodies)
+    }
+
+    fn run(physics: &mut Physics, bodies: &[RigidBodyHandle], ticks: usize) -> Vec<[u32; 3]> {
+        for _ in 0..ticks {
+            physics.step(1.0 / 60.0);
+        }
+        bodies
+            .iter()
+            .map(|&body| {
+                physics
+                    .get_position(body)
+                    .unwrap()
+                    .to_array()
+                    .map(f32::to_bits)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_restore_replays_identically() {
+        let (mut physics, bodies) = scene();
+        run(&mut physics, &bodies, 30);
+        let snapshot = physics.snapshot();
+        assert_eq!(snapshot.tick(), 30);
+        assert_eq!(snapshot.body_count(), 4);
+
+        let first = run(&mut physics, &bodies, 60);
+        physics.restore(&snapshot);
+        assert_eq!(physics.tick_count(), 30);
+        let second = run(&mut physics, &bodies, 60);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_snapshot_serde_round_trip() {
+        let (mut physics, bodies) = scene();
+        run(&mut physics, &bodies, 30);
+        let snapshot = physics.snapshot();
+        let expected = run(&mut physics, &bodies, 60);
+
+        let text = ron::to_string(&snapshot).unwrap();
+        let loaded: super::PhysicsSnapshot = ron::from_str(&text).unwrap();
+        assert_eq!(loaded.tick(), snapshot.tick());
+        physics.restore(&loaded);
+        assert_eq!(run(&mut physics, &bodies, 60), expected);
+    }
+}
@@ -0,0 +1,312 @@
+//! Raycast vehicles
+//!
+//! A dynamic chassis held up by raycast wheels. Each wheel casts a ray down
+//! from its mount point and pushes the chassis with a spring/damper
+//! suspension; driven wheels apply engine force, every wheel brakes, and
+//! steered wheels turn with the steering input.
+
+use glam::{Quat, Vec3};
+use rapier3d::control::{DynamicRayCastVehicleController, WheelTuning};
+use rapier3d::prelude::*;
+
+use super::{ColliderHandle, Physics, RigidBodyHandle};
+
+/// Mount point and suspension of one wheel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WheelDesc {
+    /// Suspension mount point, relative to the chassis
+    pub position: Vec3,
+    /// Wheel radius
+    pub radius: f32,
+    /// Suspension length at rest
+    pub rest_length: f32,
+    /// Suspension spring stiffness
+    pub stiffness: f32,
+    /// Suspension damping while compressing
+    pub compression_damping: f32,
+    /// Suspension damping while extending
+    pub relaxation_damping: f32,
+    /// Tire friction; lower values slide more
+    pub friction: f32,
+    /// Whether the wheel turns with the steering input
+    pub steered: bool,
+    /// Whether the engine drives this wheel
+    pub driven: bool,
+}
+
+impl WheelDesc {
+    /// Create an undriven, unsteered wheel with default suspension
+    #[must_use]
+    pub const fn new(position: Vec3, radius: f32) -> Self {
+        Self {
+            position,
+            radius,
+            rest_length: 0.3,
+            stiffness: 30.0,
+            compression_damping: 2.5,
+            relaxation_damping: 3.5,
+            friction: 10.5,
+            steered: false,
+            driven: false,
+        }
+    }
+
+    /// Set the suspension rest length, stiffness and damping
+    #[must_use]
+    pub const fn with_suspension(mut self, rest_length: f32, stiffness: f32, damping: f32) -> Self {
+        self.rest_length = rest_length;
+        self.stiffness = stiffness;
+        self.compression_damping = damping;
+        self.relaxation_damping = damping;
+        self
+    }
+
+    /// Set the tire friction
+    #[must_use]
+    pub const fn with_friction(mut self, friction: f32) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Turn the wheel with the steering input
+    #[must_use]
+    pub const fn steered(mut self) -> Self {
+        self.steered = true;
+        self
+    }
+
+    /// Drive the wheel with the engine
+    #[must_use]
+    pub const fn driven(mut self) -> Self {
+        self.driven = true;
+        self
+    }
+}
+
+/// A chassis body driven by raycast wheels
+///
+/// The chassis faces -Z with +Y up, matching the camera convention.
+pub struct Vehicle {
+    /// Chassis body
+    pub chassis: RigidBodyHandle,
+    /// Chassis collider
+    pub collider: ColliderHandle,
+    /// Engine force at full throttle, per driven wheel
+    pub max_engine_force: f32,
+    /// Brake force at full brake, per wheel
+    pub max_brake: f32,
+    /// Steering angle at full lock (radians)
+    pub max_steer_angle: f32,
+    /// Rapier vehicle controller
+    controller: DynamicRayCastVehicleController,
+    /// Wheel settings, in the controller's wheel order
+    wheels: Vec<WheelDesc>,
+    /// Throttle input in [-1, 1]
+    throttle: f32,
+    /// Brake input in [0, 1]
+    brake: f32,
+    /// Steering input in [-1, 1], positive turns left
+    steer: f32,
+}
+
+impl Vehicle {
+    /// Create a box chassis without wheels
+    pub fn spawn(
+        physics: &mut Physics,
+        position: Vec3,
+        rotation: Quat,
+        half_extents: Vec3,
+        density: f32,
+    ) -> Self {
+        let chassis = physics.create_dynamic_body(position, rotation);
+        let collider = physics.add_box_collider(chassis, half_extents, density);
+        let mut controller = DynamicRayCastVehicleController::new(chassis.0);
+        controller.index_up_axis = 1;
+        controller.index_forward_axis = 2;
+
+        Self {
+            chassis,
+            collider,
+            max_engine_force: 30.0,
+            max_brake: 2.0,
+            max_steer_angle: 0.5,
+            controller,
+            wheels: Vec::new(),
+            throttle: 0.0,
+            brake: 0.0,
+            steer: 0.0,
+        }
+    }
+
+    /// Add a wheel
+    #[must_use]
+    pub fn with_wheel(mut self, wheel: WheelDesc) -> Self {
+        self.add_wheel(wheel);
+        self
+    }
+
+    /// Set the engine force, brake force and steering angle limits
+    #[must_use]
+    pub fn with_limits(mut self, engine_force: f32, brake: f32, steer_angle: f32) -> Self {
+        self.max_engine_force = engine_force;
+        self.max_brake = brake;
+        self.max_steer_angle = steer_angle;
+        self
+    }
+
+    /// Add a wheel
+    pub fn add_wheel(&mut self, wheel: WheelDesc) {
+        let tuning = WheelTuning {
+            suspension_stiffness: wheel.stiffness,
+            suspension_compression: wheel.compression_damping,
+            suspension_damping: wheel.relaxation_damping,
+            friction_slip: wheel.friction,
+            ..WheelTuning::default()
+        };
+        self.controller.add_wheel(
+            point![wheel.position.x, wheel.position.y, wheel.position.z],
+            -Vector::y(),
+            Vector::x(),
+            wheel.rest_length,
+            wheel.radius,
+            &tuning,
+        );
+        self.wheels.push(wheel);
+    }
+
+    /// Number of wheels
+    #[must_use]
+    pub fn wheel_count(&self) -> usize {
+        self.wheels.len()
+    }
+
+    /// Set the driver inputs
+    ///
+    /// Throttle is in [-1, 1] (negative reverses), brake in [0, 1] and
+    /// steering in [-1, 1] (positive turns left).
+    pub fn set_input(&mut self, throttle: f32, brake: f32, steer: f32) {
+        self.throttle = throttle.clamp(-1.0, 1.0);
+        self.brake = brake.clamp(0.0, 1.0);
+        self.steer = steer.clamp(-1.0, 1.0);
+    }
+
+    /// Apply the inputs and suspension forces
+    ///
+    /// Call once per simulation tick with the tick length. With a fixed
+    /// timestep, run it from `Physics::step_with` rather than once per frame,
+    /// since a frame may simulate several ticks or none.
+    pub fn update(&mut self, physics: &mut Physics, dt: f32) {
+        // Rapier's wheel forward is axle x suspension = -Z, the chassis front
+        let engine = self.throttle * self.max_engine_force;
+        let brake = self.brake * self.max_brake;
+        let steering = self.steer * self.max_steer_angle;
+        for (wheel, desc) in self.controller.wheels_mut().iter_mut().zip(&self.wheels) {
+            wheel.engine_force = if desc.driven { engine } else { 0.0 };
+            wheel.brake = brake;
+            wheel.steering = if desc.steered { steering } else { 0.0 };
+        }
+        physics.update_vehicle(&mut self.controller, dt);
+    }
+
+    /// Forward speed in units per second (negative when reversing)
+    #[must_use]
+    pub fn speed(&self) -> f32 {
+        -self.controller.current_vehicle_speed
+    }
+
+    /// Check if any wheel touches the ground
+    #[must_use]
+    pub fn is_grounded(&self) -> bool {
+        self.controller
+            .wheels()
+            .iter()
+            .any(|wheel| wheel.raycast_info().is_in_contact)
+    }
+
+    /// World-space center of a wheel, for placing its mesh
+    #[must_use]
+    pub fn wheel_position(&self, index: usize) -> Option<Vec3> {
+        let center = self.controller.wheels().get(index)?.center();
+        Some(Vec3::new(center.x, center.y, center.z))
+    }
+
+    /// World-space ground contact of a wheel, if it touches anything
+    #[must_use]
+    pub fn wheel_contact(&self, index: usize) -> Option<Vec3> {
+        let info = self.controller.wheels().get(index)?.raycast_info();
+        info.is_in_contact.then(|| {
+            let p = info.contact_point_ws;
+            Vec3::new(p.x, p.y, p.z)
+        })
+    }
+
+    /// Current suspension length of a wheel
+    #[must_use]
+    pub fn suspension_length(&self, index: usize) -> Option<f32> {
+        self.controller
+            .wheels()
+            .get(index)
+            .map(|wheel| wheel.raycast_info().suspension_length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Four-wheeled car on a ground plane facing -Z, rear-wheel drive
+    fn car(physics: &mut Physics) -> Vehicle {
+        let ground = physics.create_static_body(Vec3::ZERO, Quat::IDENTITY);
+        physics.add_ground_plane(ground);
+        let mut vehicle = Vehicle::spawn(
+            physics,
+            Vec3::new(0.0, 1.0, 0.0),
+            Quat::IDENTITY,
+            Vec3::new(0.8, 0.2, 1.6),
+            10.0,
+        );
+        for (x, z) in [(-0.8, 1.2), (0.8, 1.2), (-0.8, -1.2), (0.8, -1.2)] {
+            let wheel = WheelDesc::new(Vec3::new(x, -0.1, z), 0.3);
+            vehicle.add_wheel(if z < 0.0 {
+                wheel.steered()
+            } else {
+                wheel.driven()
+            });
+        }
+        vehicle
+    }
+
+    /// Step the vehicle and world together for `ticks` ticks
+    fn run(physics: &mut Physics, vehicle: &mut Vehicle, ticks: usize) {
+        for _ in 0..ticks {
+            physics.step_with(1.0 / 60.0, |physics, dt| vehicle.update(physics, dt));
+        }
+    }
+
+    #[test]
+    fn test_vehicle_settles_and_drives() {
+        let mut physics = Physics::new();
+        let mut vehicle = car(&mut physics);
+        run(&mut physics, &mut vehicle, 120);
+
+        assert!(vehicle.is_grounded());
+        assert_eq!(vehicle.wheel_count(), 4);
+        for index in 0..vehicle.wheel_count() {
+            let length = vehicle.suspension_length(index).unwrap();
+            assert!(length > 0.0 && length <= 0.3, "suspension length {length}");
+            assert!(vehicle.wheel_contact(index).unwrap().y.abs() < 0.2);
+        }
+        assert!(vehicle.speed().abs() < 0.1);
+
+        vehicle.set_input(1.0, 0.0, 0.0);
+        run(&mut physics, &mut vehicle, 60);
+        assert!(vehicle.speed() > 0.5, "speed {}", vehicle.speed());
+        assert!(physics.get_position(vehicle.chassis).unwrap().z < -0.5);
+
+        // Facing -Z, left is -X
+        vehicle.set_input(1.0, 0.0, 1.0);
+        run(&mut physics, &mut vehicle, 60);
+        assert!(physics.get_position(vehicle.chassis).unwrap().x < -0.1);
+        assert!(vehicle.is_grounded());
+    }
+}
@@ -1,9 +1,20 @@
 //! Physics simulation using rapier3d
 
-use glam::{Quat, Vec3};
+use glam::{Quat, Vec3, Vec4};
 use nalgebra::UnitQuaternion;
+use rapier3d::parry::query::{ShapeCastOptions, ShapeCastStatus};
 use rapier3d::prelude::*;
 
+use super::events::{CollisionEvent, EventCollector, TriggerEvent};
+use super::filter::{CollisionFilter, interaction_groups};
+use super::forces::{Falloff, ForceField, ForceFieldHandle};
+use super::joints::{self, JointDesc, JointHandle, JointMotor};
+use super::material::PhysicsMaterial;
+use super::query::QueryWorld;
+use super::snapshot::PhysicsSnapshot;
+use crate::assets::LoadedPrimitive;
+use crate::renderer::{DebugDraw, Mesh, Vertex};
+
 /// Handle to a rigid body in the physics world
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct RigidBodyHandle(pub rapier3d::dynamics::RigidBodyHandle);
@@ -23,6 +34,29 @@ fn rapier_to_quat(uq: &UnitQuaternion<f32>) -> Quat {
     Quat::from_xyzw(q.i, q.j, q.k, q.w)
 }
 
+/// Convert a rapier3d vector to glam Vec3
+fn rapier_to_vec3(v: &Vector<f32>) -> Vec3 {
+    Vec3::new(v.x, v.y, v.z)
+}
+
+/// Most fixed ticks run by one `step`; older backlog is dropped
+const MAX_FIXED_TICKS: u32 = 8;
+
+/// Debug color for awake dynamic bodies
+const DEBUG_DYNAMIC: Vec4 = Vec4::new(0.2, 0.9, 0.3, 1.0);
+/// Debug color for sleeping dynamic bodies
+const DEBUG_SLEEPING: Vec4 = Vec4::new(0.3, 0.4, 0.9, 1.0);
+/// Debug color for kinematic bodies
+const DEBUG_KINEMATIC: Vec4 = Vec4::new(0.9, 0.8, 0.2, 1.0);
+/// Debug color for fixed bodies and detached colliders
+const DEBUG_FIXED: Vec4 = Vec4::new(0.6, 0.6, 0.6, 1.0);
+/// Debug color for sensors
+const DEBUG_SENSOR: Vec4 = Vec4::new(0.2, 0.8, 0.9, 0.6);
+/// Debug color for contact points and normals
+const DEBUG_CONTACT: Vec4 = Vec4::new(1.0, 0.2, 0.2, 1.0);
+/// Debug color for joint anchors
+const DEBUG_JOINT: Vec4 = Vec4::new(0.9, 0.3, 0.9, 1.0);
+
 /// Physics world manager
 pub struct Physics {
     /// Gravity vector
@@ -49,6 +83,20 @@ pub struct Physics {
     query_pipeline: QueryPipeline,
     /// Integration parameters
     integration_parameters: IntegrationParameters,
+    /// Contact changes recorded during the current step
+    event_collector: EventCollector,
+    /// Collision events of the last step
+    collision_events: Vec<CollisionEvent>,
+    /// Trigger events of the last step
+    trigger_events: Vec<TriggerEvent>,
+    /// Force field slots, `None` once removed
+    force_fields: Vec<Option<ForceField>>,
+    /// Fixed timestep for deterministic stepping
+    fixed_timestep: Option<f32>,
+    /// Unsimulated time carried over to the next step
+    accumulator: f32,
+    /// Simulation ticks run so far
+    tick: u64,
 }
 
 impl Physics {
@@ -72,12 +120,58 @@ impl Physics {
             ccd_solver: CCDSolver::new(),
             query_pipeline: QueryPipeline::new(),
             integration_parameters: IntegrationParameters::default(),
+            event_collector: EventCollector::default(),
+            collision_events: Vec::new(),
+            trigger_events: Vec::new(),
+            force_fields: Vec::new(),
+            fixed_timestep: None,
+            accumulator: 0.0,
+            tick: 0,
         }
     }
 
     /// Step the physics simulation
+    ///
+    /// With a fixed timestep, `dt` is accumulated and the world advances in
+    /// whole ticks of exactly that length, so the same inputs always produce
+    /// the same simulation.
     pub fn step(&mut self, dt: f32) {
+        self.step_with(dt, |_, _| {});
+    }
+
+    /// Step the simulation, calling `before_tick` ahead of every tick
+    ///
+    /// With a fixed timestep one `step` may run several ticks, or none.
+    /// Per-tick controllers such as `Vehicle::update` and
+    /// `drive_kinematic_bodies` belong in `before_tick`, which receives the
+    /// tick length, so they run exactly once per simulated tick.
+    pub fn step_with(&mut self, dt: f32, mut before_tick: impl FnMut(&mut Self, f32)) {
+        self.collision_events.clear();
+        self.trigger_events.clear();
+
+        let Some(fixed) = self.fixed_timestep else {
+            before_tick(self, dt);
+            self.advance(dt);
+            return;
+        };
+        self.accumulator += dt;
+        let mut ticks = 0;
+        while self.accumulator >= fixed {
+            if ticks == MAX_FIXED_TICKS {
+                self.accumulator = 0.0;
+                break;
+            }
+            self.accumulator -= fixed;
+            before_tick(self, fixed);
+            self.advance(fixed);
+            ticks += 1;
+        }
+    }
+
+    /// Advance the simulation by exactly one tick of `dt`
+    fn advance(&mut self, dt: f32) {
         self.integration_parameters.dt = dt;
+        self.apply_force_fields(dt);
 
         self.pipeline.step(
             &vector![self.gravity.x, self.gravity.y, self.gravity.z],
@@ -92,8 +186,161 @@ impl Physics {
             &mut self.ccd_solver,
             Some(&mut self.query_pipeline),
             &(),
-            &(),
+            &self.event_collector,
         );
+
+        self.event_collector
+            .drain(&self.narrow_phase, &mut self.collision_events);
+        self.event_collector
+            .drain_triggers(&mut self.trigger_events);
+        self.tick += 1;
+    }
+
+    /// Step in fixed ticks of `timestep` seconds, or pass `None` to use the
+    /// frame delta directly
+    ///
+    /// Fixed stepping is reproducible on the same build and platform; enable
+    /// the `deterministic-physics` feature for identical results across
+    /// platforms.
+    pub fn set_fixed_timestep(&mut self, timestep: Option<f32>) {
+        self.fixed_timestep = timestep.filter(|t| *t > 0.0);
+        self.accumulator = 0.0;
+    }
+
+    /// Get the fixed timestep, if deterministic stepping is enabled
+    #[must_use]
+    pub fn fixed_timestep(&self) -> Option<f32> {
+        self.fixed_timestep
+    }
+
+    /// Fraction of a fixed tick left unsimulated, for interpolating rendering
+    #[must_use]
+    pub fn interpolation_alpha(&self) -> f32 {
+        self.fixed_timestep
+            .map_or(1.0, |fixed| self.accumulator / fixed)
+    }
+
+    /// Number of simulation ticks run so far
+    #[must_use]
+    pub fn tick_count(&self) -> u64 {
+        self.tick
+    }
+
+    /// Capture the full simulation state
+    #[must_use]
+    pub fn snapshot(&self) -> PhysicsSnapshot {
+        PhysicsSnapshot {
+            gravity: self.gravity,
+            integration_parameters: self.integration_parameters,
+            island_manager: self.island_manager.clone(),
+            broad_phase: self.broad_phase.clone(),
+            narrow_phase: self.narrow_phase.clone(),
+            rigid_body_set: self.rigid_body_set.clone(),
+            collider_set: self.collider_set.clone(),
+            impulse_joint_set: self.impulse_joint_set.clone(),
+            multibody_joint_set: self.multibody_joint_set.clone(),
+            ccd_solver: self.ccd_solver.clone(),
+            query_pipeline: self.query_pipeline.clone(),
+            force_fields: self.force_fields.clone(),
+            fixed_timestep: self.fixed_timestep,
+            accumulator: self.accumulator,
+            tick: self.tick,
+        }
+    }
+
+    /// Replace the simulation state with a snapshot
+    ///
+    /// Handles created before the snapshot stay valid; pending events are
+    /// discarded.
+    pub fn restore(&mut self, snapshot: &PhysicsSnapshot) {
+        let snapshot = snapshot.clone();
+        self.gravity = snapshot.gravity;
+        self.integration_parameters = snapshot.integration_parameters;
+        self.island_manager = snapshot.island_manager;
+        self.broad_phase = snapshot.broad_phase;
+        self.narrow_phase = snapshot.narrow_phase;
+        self.rigid_body_set = snapshot.rigid_body_set;
+        self.collider_set = snapshot.collider_set;
+        self.impulse_joint_set = snapshot.impulse_joint_set;
+        self.multibody_joint_set = snapshot.multibody_joint_set;
+        self.ccd_solver = snapshot.ccd_solver;
+        self.query_pipeline = snapshot.query_pipeline;
+        self.force_fields = snapshot.force_fields;
+        self.fixed_timestep = snapshot.fixed_timestep;
+        self.accumulator = snapshot.accumulator;
+        self.tick = snapshot.tick;
+        self.event_collector = EventCollector::default();
+        self.collision_events.clear();
+        self.trigger_events.clear();
+    }
+
+    /// Take the contacts that started or stopped during the last step
+    ///
+    /// Events are replaced on every step, so drain them after each `step`.
+    /// Started contacts arrive one tick after they form, once the solver
+    /// has resolved them, so their impulse reflects the hit.
+    pub fn drain_collision_events(&mut self) -> std::vec::Drain<'_, CollisionEvent> {
+        self.collision_events.drain(..)
+    }
+
+    /// Take the sensor enter/exit events of the last step
+    ///
+    /// Events are replaced on every step, so drain them after each `step`.
+    pub fn drain_trigger_events(&mut self) -> std::vec::Drain<'_, TriggerEvent> {
+        self.trigger_events.drain(..)
+    }
+
+    /// Colliders currently overlapping a sensor
+    #[must_use]
+    pub fn sensor_overlaps(&self, sensor: ColliderHandle) -> Vec<ColliderHandle> {
+        self.narrow_phase
+            .intersection_pairs_with(sensor.0)
+            .filter(|&(_, _, intersecting)| intersecting)
+            .map(|(a, b, _)| ColliderHandle(if a == sensor.0 { b } else { a }))
+            .collect()
+    }
+
+    /// Set the collision groups of a collider
+    ///
+    /// Two colliders interact only if each one's `groups` intersect the
+    /// other's `filter`. Both default to every group.
+    pub fn set_collision_groups(&mut self, collider: ColliderHandle, groups: u32, filter: u32) {
+        if let Some(collider) = self.collider_set.get_mut(collider.0) {
+            collider.set_collision_groups(interaction_groups(groups, filter));
+        }
+    }
+
+    /// Set the friction, restitution and combine rules of a collider
+    pub fn set_material(&mut self, collider: ColliderHandle, material: PhysicsMaterial) {
+        if let Some(collider) = self.collider_set.get_mut(collider.0) {
+            material.apply(collider);
+        }
+    }
+
+    /// Get the surface material of a collider
+    #[must_use]
+    pub fn material(&self, collider: ColliderHandle) -> Option<PhysicsMaterial> {
+        self.collider_set.get(collider.0).map(PhysicsMaterial::of)
+    }
+
+    /// Get the collision groups and filter of a collider
+    #[must_use]
+    pub fn collision_groups(&self, collider: ColliderHandle) -> Option<(u32, u32)> {
+        self.collider_set.get(collider.0).map(|collider| {
+            let groups = collider.collision_groups();
+            (groups.memberships.bits(), groups.filter.bits())
+        })
+    }
+
+    /// Enable or disable collision events for a collider
+    ///
+    /// Colliders created through `Physics` report events by default.
+    pub fn set_collision_events(&mut self, collider: ColliderHandle, enabled: bool) {
+        if let Some(collider) = self.collider_set.get_mut(collider.0) {
+            let mut events = collider.active_events();
+            events.set(ActiveEvents::COLLISION_EVENTS, enabled);
+            collider.set_active_events(events);
+        }
     }
 
     /// Create a static rigid body (doesn't move)
@@ -140,6 +387,7 @@ impl Physics {
     ) -> ColliderHandle {
         let collider = ColliderBuilder::cuboid(half_extents.x, half_extents.y, half_extents.z)
             .density(density)
+            .active_events(ActiveEvents::COLLISION_EVENTS)
             .build();
 
         ColliderHandle(self.collider_set.insert_with_parent(
@@ -156,7 +404,10 @@ impl Physics {
         radius: f32,
         density: f32,
     ) -> ColliderHandle {
-        let collider = ColliderBuilder::ball(radius).density(density).build();
+        let collider = ColliderBuilder::ball(radius)
+            .density(density)
+            .active_events(ActiveEvents::COLLISION_EVENTS)
+            .build();
 
         ColliderHandle(self.collider_set.insert_with_parent(
             collider,
@@ -175,6 +426,213 @@ impl Physics {
     ) -> ColliderHandle {
         let collider = ColliderBuilder::capsule_y(half_height, radius)
             .density(density)
+            .active_events(ActiveEvents::COLLISION_EVENTS)
+            .build();
+
+        ColliderHandle(self.collider_set.insert_with_parent(
+            collider,
+            body.0,
+            &mut self.rigid_body_set,
+        ))
+    }
+
+    /// Add a Y-aligned cylinder collider to a rigid body
+    pub fn add_cylinder_collider(
+        &mut self,
+        body: RigidBodyHandle,
+        half_height: f32,
+        radius: f32,
+        density: f32,
+    ) -> ColliderHandle {
+        let collider = ColliderBuilder::cylinder(half_height, radius)
+            .density(density)
+            .active_events(ActiveEvents::COLLISION_EVENTS)
+            .build();
+
+        ColliderHandle(self.collider_set.insert_with_parent(
+            collider,
+            body.0,
+            &mut self.rigid_body_set,
+        ))
+    }
+
+    /// Add a collider shaped as the convex hull of a point cloud
+    ///
+    /// Points are in the body's local space. Returns `None` if the points
+    /// are degenerate (fewer than four, or all coplanar).
+    pub fn add_convex_hull_collider(
+        &mut self,
+        body: RigidBodyHandle,
+        points: &[Vec3],
+        density: f32,
+    ) -> Option<ColliderHandle> {
+        let points: Vec<_> = points.iter().map(|p| point![p.x, p.y, p.z]).collect();
+        // `ColliderBuilder::convex_hull` panics on degenerate input
+        let (vertices, indices) = rapier3d::parry::transformation::try_convex_hull(&points).ok()?;
+        let shape = SharedShape::convex_mesh(vertices, &indices)?;
+        if shape.mass_properties(1.0).mass() <= f32::EPSILON {
+            return None;
+        }
+        let collider = ColliderBuilder::new(shape)
+            .density(density)
+            .active_events(ActiveEvents::COLLISION_EVENTS)
+            .build();
+
+        Some(ColliderHandle(self.collider_set.insert_with_parent(
+            collider,
+            body.0,
+            &mut self.rigid_body_set,
+        )))
+    }
+
+    /// Add a triangle mesh collider matching a render mesh
+    ///
+    /// Trimeshes are hollow and massless, so use them for static level
+    /// geometry; dynamic bodies should use `add_convex_decomposition_collider`.
+    /// Returns `None` if the mesh has no valid triangles.
+    pub fn add_trimesh_collider(
+        &mut self,
+        body: RigidBodyHandle,
+        mesh: &Mesh,
+    ) -> Option<ColliderHandle> {
+        self.insert_trimesh(body, &mesh.vertices, &mesh.indices)
+    }
+
+    /// Add a triangle mesh collider from an imported glTF primitive
+    pub fn add_primitive_collider(
+        &mut self,
+        body: RigidBodyHandle,
+        primitive: &LoadedPrimitive,
+    ) -> Option<ColliderHandle> {
+        self.insert_trimesh(body, &primitive.vertices, &primitive.indices)
+    }
+
+    /// Add a compound of convex pieces approximating a render mesh
+    ///
+    /// Unlike a trimesh the result is solid and has mass, so it suits dynamic
+    /// bodies. Decomposition is slow; do it at load time.
+    pub fn add_convex_decomposition_collider(
+        &mut self,
+        body: RigidBodyHandle,
+        mesh: &Mesh,
+        density: f32,
+    ) -> Option<ColliderHandle> {
+        let (points, triangles) = mesh_geometry(&mesh.vertices, &mesh.indices);
+        if triangles.is_empty() {
+            return None;
+        }
+        let collider = ColliderBuilder::convex_decomposition(&points, &triangles)
+            .density(density)
+            .active_events(ActiveEvents::COLLISION_EVENTS)
+            .build();
+
+        Some(ColliderHandle(self.collider_set.insert_with_parent(
+            collider,
+            body.0,
+            &mut self.rigid_body_set,
+        )))
+    }
+
+    fn insert_trimesh(
+        &mut self,
+        body: RigidBodyHandle,
+        vertices: &[Vertex],
+        indices: &[u32],
+    ) -> Option<ColliderHandle> {
+        let (points, triangles) = mesh_geometry(vertices, indices);
+        if triangles.is_empty() {
+            return None;
+        }
+        let collider = ColliderBuilder::trimesh(points, triangles)
+            .active_events(ActiveEvents::COLLISION_EVENTS)
+            .build();
+
+        Some(ColliderHandle(self.collider_set.insert_with_parent(
+            collider,
+            body.0,
+            &mut self.rigid_body_set,
+        )))
+    }
+
+    /// Add a heightfield collider for terrain
+    ///
+    /// `heights` is row-major with `rows` along Z and `columns` along X. The
+    /// field is centered on the body and spans `size.x` by `size.z`, with
+    /// heights multiplied by `size.y`; `Mesh::heightfield` builds the matching
+    /// render mesh. Returns `None` if the grid is smaller than 2x2 or
+    /// `heights` has the wrong length.
+    pub fn add_heightfield_collider(
+        &mut self,
+        body: RigidBodyHandle,
+        heights: &[f32],
+        columns: usize,
+        rows: usize,
+        size: Vec3,
+    ) -> Option<ColliderHandle> {
+        if columns < 2 || rows < 2 || heights.len() != columns * rows {
+            return None;
+        }
+        let heights = nalgebra::DMatrix::from_row_slice(rows, columns, heights);
+        let collider = ColliderBuilder::heightfield(heights, vector![size.x, size.y, size.z])
+            .active_events(ActiveEvents::COLLISION_EVENTS)
+            .build();
+
+        Some(ColliderHandle(self.collider_set.insert_with_parent(
+            collider,
+            body.0,
+            &mut self.rigid_body_set,
+        )))
+    }
+
+    /// Offset a collider from its body's origin
+    ///
+    /// Use this to line colliders up with meshes whose pivot is not at the center.
+    pub fn set_collider_offset(
+        &mut self,
+        collider: ColliderHandle,
+        translation: Vec3,
+        rotation: Quat,
+    ) {
+        if let Some(collider) = self.collider_set.get_mut(collider.0) {
+            collider.set_position_wrt_parent(Isometry::from_parts(
+                nalgebra::Translation3::new(translation.x, translation.y, translation.z),
+                quat_to_rapier(rotation),
+            ));
+        }
+    }
+
+    /// Add a box-shaped sensor (trigger volume) to a rigid body
+    ///
+    /// Sensors report overlaps through `drain_trigger_events` without
+    /// producing contact forces, and are ignored by raycasts and shape casts.
+    pub fn add_box_sensor(&mut self, body: RigidBodyHandle, half_extents: Vec3) -> ColliderHandle {
+        self.insert_sensor(
+            body,
+            ColliderBuilder::cuboid(half_extents.x, half_extents.y, half_extents.z),
+        )
+    }
+
+    /// Add a sphere-shaped sensor (trigger volume) to a rigid body
+    pub fn add_sphere_sensor(&mut self, body: RigidBodyHandle, radius: f32) -> ColliderHandle {
+        self.insert_sensor(body, ColliderBuilder::ball(radius))
+    }
+
+    /// Turn an existing collider into a sensor or back into a solid collider
+    pub fn set_sensor(&mut self, collider: ColliderHandle, sensor: bool) {
+        if let Some(collider) = self.collider_set.get_mut(collider.0) {
+            collider.set_sensor(sensor);
+            if sensor {
+                // Let sensors on static bodies detect kinematic characters
+                collider.set_active_collision_types(ActiveCollisionTypes::all());
+            }
+        }
+    }
+
+    fn insert_sensor(&mut self, body: RigidBodyHandle, builder: ColliderBuilder) -> ColliderHandle {
+        let collider = builder
+            .sensor(true)
+            .active_events(ActiveEvents::COLLISION_EVENTS)
+            .active_collision_types(ActiveCollisionTypes::all())
             .build();
 
         ColliderHandle(self.collider_set.insert_with_parent(
@@ -186,7 +644,9 @@ impl Physics {
 
     /// Add a ground plane collider
     pub fn add_ground_plane(&mut self, body: RigidBodyHandle) -> ColliderHandle {
-        let collider = ColliderBuilder::cuboid(100.0, 0.1, 100.0).build();
+        let collider = ColliderBuilder::cuboid(100.0, 0.1, 100.0)
+            .active_events(ActiveEvents::COLLISION_EVENTS)
+            .build();
 
         ColliderHandle(self.collider_set.insert_with_parent(
             collider,
@@ -217,6 +677,16 @@ impl Physics {
         }
     }
 
+    /// Set the position and rotation of a kinematic body for the next step
+    pub fn set_kinematic_pose(&mut self, body: RigidBodyHandle, position: Vec3, rotation: Quat) {
+        if let Some(rb) = self.rigid_body_set.get_mut(body.0) {
+            rb.set_next_kinematic_position(Isometry::from_parts(
+                nalgebra::Translation3::new(position.x, position.y, position.z),
+                quat_to_rapier(rotation),
+            ));
+        }
+    }
+
     /// Apply a force to a dynamic body
     pub fn apply_force(&mut self, body: RigidBodyHandle, force: Vec3) {
         if let Some(rb) = self.rigid_body_set.get_mut(body.0) {
@@ -224,6 +694,93 @@ impl Physics {
         }
     }
 
+    /// Push every dynamic body within `radius` of `center` away from it
+    ///
+    /// `strength` is the impulse at the center, scaled down by `falloff`
+    /// toward the edge. Returns the number of bodies pushed.
+    pub fn apply_explosion(
+        &mut self,
+        center: Vec3,
+        radius: f32,
+        strength: f32,
+        falloff: Falloff,
+    ) -> usize {
+        let mut pushed = 0;
+        for (_, rb) in self.rigid_body_set.iter_mut() {
+            if !rb.is_dynamic() {
+                continue;
+            }
+            let com = rb.center_of_mass();
+            let offset = Vec3::new(com.x, com.y, com.z) - center;
+            let factor = falloff.factor(offset.length(), radius);
+            if factor <= 0.0 {
+                continue;
+            }
+            // Bodies at the exact center are thrown upward
+            let direction = offset.try_normalize().unwrap_or(Vec3::Y);
+            let impulse = direction * strength * factor;
+            rb.apply_impulse(vector![impulse.x, impulse.y, impulse.z], true);
+            pushed += 1;
+        }
+        pushed
+    }
+
+    /// Add a force field applied on every step
+    pub fn add_force_field(&mut self, field: ForceField) -> ForceFieldHandle {
+        if let Some(index) = self.force_fields.iter().position(Option::is_none) {
+            self.force_fields[index] = Some(field);
+            ForceFieldHandle(index)
+        } else {
+            self.force_fields.push(Some(field));
+            ForceFieldHandle(self.force_fields.len() - 1)
+        }
+    }
+
+    /// Remove a force field
+    pub fn remove_force_field(&mut self, field: ForceFieldHandle) -> Option<ForceField> {
+        self.force_fields.get_mut(field.0)?.take()
+    }
+
+    /// Get a force field
+    #[must_use]
+    pub fn force_field(&self, field: ForceFieldHandle) -> Option<&ForceField> {
+        self.force_fields.get(field.0)?.as_ref()
+    }
+
+    /// Get a force field mutably, e.g. to move or toggle it
+    pub fn force_field_mut(&mut self, field: ForceFieldHandle) -> Option<&mut ForceField> {
+        self.force_fields.get_mut(field.0)?.as_mut()
+    }
+
+    /// Push bodies inside force fields for one step
+    fn apply_force_fields(&mut self, dt: f32) {
+        if self
+            .force_fields
+            .iter()
+            .all(|f| f.is_none_or(|f| !f.enabled))
+        {
+            return;
+        }
+        for (_, rb) in self.rigid_body_set.iter_mut() {
+            if !rb.is_dynamic() {
+                continue;
+            }
+            let com = rb.center_of_mass();
+            let position = Vec3::new(com.x, com.y, com.z);
+            let mass = rb.mass();
+            let force: Vec3 = self
+                .force_fields
+                .iter()
+                .flatten()
+                .filter_map(|field| field.force_at(position, mass))
+                .sum();
+            if force != Vec3::ZERO {
+                let impulse = force * dt;
+                rb.apply_impulse(vector![impulse.x, impulse.y, impulse.z], true);
+            }
+        }
+    }
+
     /// Apply an impulse to a dynamic body
     pub fn apply_impulse(&mut self, body: RigidBodyHandle, impulse: Vec3) {
         if let Some(rb) = self.rigid_body_set.get_mut(body.0) {
@@ -246,32 +803,581 @@ impl Physics {
         })
     }
 
-    /// Cast a ray and return the first hit
+    /// Wake a sleeping body so it is simulated again
+    pub fn wake_up(&mut self, body: RigidBodyHandle) {
+        if let Some(rb) = self.rigid_body_set.get_mut(body.0) {
+            rb.wake_up(true);
+        }
+    }
+
+    /// Put a body to sleep until something touches or wakes it
+    pub fn sleep(&mut self, body: RigidBodyHandle) {
+        if let Some(rb) = self.rigid_body_set.get_mut(body.0) {
+            rb.sleep();
+        }
+    }
+
+    /// Check if a body is sleeping
+    #[must_use]
+    pub fn is_sleeping(&self, body: RigidBodyHandle) -> bool {
+        self.rigid_body_set
+            .get(body.0)
+            .is_some_and(|rb| rb.is_sleeping())
+    }
+
+    /// Allow or forbid a body from falling asleep when it comes to rest
+    ///
+    /// Forbidding sleep also wakes the body.
+    pub fn set_can_sleep(&mut self, body: RigidBodyHandle, can_sleep: bool) {
+        if let Some(rb) = self.rigid_body_set.get_mut(body.0) {
+            let activation = rb.activation_mut();
+            if can_sleep {
+                activation.normalized_linear_threshold =
+                    RigidBodyActivation::default_normalized_linear_threshold();
+                activation.angular_threshold = RigidBodyActivation::default_angular_threshold();
+            } else {
+                activation.normalized_linear_threshold = -1.0;
+                activation.angular_threshold = -1.0;
+                rb.wake_up(true);
+            }
+        }
+    }
+
+    /// Check if a body is allowed to fall asleep
+    #[must_use]
+    pub fn can_sleep(&self, body: RigidBodyHandle) -> bool {
+        self.rigid_body_set
+            .get(body.0)
+            .is_some_and(|rb| rb.activation().normalized_linear_threshold >= 0.0)
+    }
+
+    /// Set how quickly a body's linear velocity decays (0 = no damping)
+    pub fn set_linear_damping(&mut self, body: RigidBodyHandle, damping: f32) {
+        if let Some(rb) = self.rigid_body_set.get_mut(body.0) {
+            rb.set_linear_damping(damping.max(0.0));
+        }
+    }
+
+    /// Get the linear damping of a body
+    #[must_use]
+    pub fn linear_damping(&self, body: RigidBodyHandle) -> Option<f32> {
+        self.rigid_body_set
+            .get(body.0)
+            .map(|rb| rb.linear_damping())
+    }
+
+    /// Set how quickly a body's angular velocity decays (0 = no damping)
+    pub fn set_angular_damping(&mut self, body: RigidBodyHandle, damping: f32) {
+        if let Some(rb) = self.rigid_body_set.get_mut(body.0) {
+            rb.set_angular_damping(damping.max(0.0));
+        }
+    }
+
+    /// Get the angular damping of a body
+    #[must_use]
+    pub fn angular_damping(&self, body: RigidBodyHandle) -> Option<f32> {
+        self.rigid_body_set
+            .get(body.0)
+            .map(|rb| rb.angular_damping())
+    }
+
+    /// Scale the world gravity applied to a body
+    ///
+    /// Use values below 1 for floaty objects and 0 for zero-g.
+    pub fn set_gravity_scale(&mut self, body: RigidBodyHandle, scale: f32) {
+        if let Some(rb) = self.rigid_body_set.get_mut(body.0) {
+            rb.set_gravity_scale(scale, true);
+        }
+    }
+
+    /// Get the gravity scale of a body
+    #[must_use]
+    pub fn gravity_scale(&self, body: RigidBodyHandle) -> Option<f32> {
+        self.rigid_body_set.get(body.0).map(|rb| rb.gravity_scale())
+    }
+
+    /// Cast a ray and return the first hit (sensors are ignored)
     pub fn raycast(&self, origin: Vec3, direction: Vec3, max_distance: f32) -> Option<RaycastHit> {
+        self.raycast_with(origin, direction, max_distance, &CollisionFilter::new())
+    }
+
+    /// Cast a ray ignoring one collider, e.g. the caster's own body
+    pub fn raycast_excluding(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        exclude: ColliderHandle,
+    ) -> Option<RaycastHit> {
+        self.raycast_with(
+            origin,
+            direction,
+            max_distance,
+            &CollisionFilter::new().excluding_collider(exclude),
+        )
+    }
+
+    /// Cast a ray against colliders accepted by `filter`
+    pub fn raycast_with(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        filter: &CollisionFilter,
+    ) -> Option<RaycastHit> {
         let ray = Ray::new(
             point![origin.x, origin.y, origin.z],
             vector![direction.x, direction.y, direction.z],
         );
 
         self.query_pipeline
-            .cast_ray(
+            .cast_ray_and_get_normal(
                 &self.rigid_body_set,
                 &self.collider_set,
                 &ray,
                 max_distance,
                 true,
-                QueryFilter::default(),
+                filter.to_query_filter(),
             )
-            .map(|(handle, distance)| {
-                let point = ray.point_at(distance);
+            .map(|(handle, intersection)| {
+                let point = ray.point_at(intersection.time_of_impact);
+                let normal = intersection.normal;
                 RaycastHit {
                     collider: ColliderHandle(handle),
                     point: Vec3::new(point.x, point.y, point.z),
-                    distance,
+                    normal: Vec3::new(normal.x, normal.y, normal.z),
+                    distance: intersection.time_of_impact,
                 }
             })
     }
 
+    /// Sweep a shape along a direction and return the first hit
+    ///
+    /// Unlike a raycast this catches thin geometry the shape would clip, so
+    /// fast projectiles and movers can stop before tunneling through walls.
+    pub fn shape_cast(
+        &self,
+        shape: CastShape,
+        position: Vec3,
+        rotation: Quat,
+        direction: Vec3,
+        max_distance: f32,
+    ) -> Option<ShapeCastHit> {
+        self.shape_cast_with(
+            shape,
+            position,
+            rotation,
+            direction,
+            max_distance,
+            &CollisionFilter::new(),
+        )
+    }
+
+    /// Sweep a shape ignoring one collider, e.g. the caster's own body
+    pub fn shape_cast_excluding(
+        &self,
+        shape: CastShape,
+        position: Vec3,
+        rotation: Quat,
+        direction: Vec3,
+        max_distance: f32,
+        exclude: ColliderHandle,
+    ) -> Option<ShapeCastHit> {
+        self.shape_cast_with(
+            shape,
+            position,
+            rotation,
+            direction,
+            max_distance,
+            &CollisionFilter::new().excluding_collider(exclude),
+        )
+    }
+
+    /// Sweep a shape against colliders accepted by `filter`
+    pub fn shape_cast_with(
+        &self,
+        shape: CastShape,
+        position: Vec3,
+        rotation: Quat,
+        direction: Vec3,
+        max_distance: f32,
+        filter: &CollisionFilter,
+    ) -> Option<ShapeCastHit> {
+        let direction = direction.normalize_or_zero();
+        if direction == Vec3::ZERO {
+            return None;
+        }
+        let shape_pos = Isometry::from_parts(
+            nalgebra::Translation3::new(position.x, position.y, position.z),
+            quat_to_rapier(rotation),
+        );
+        let options = ShapeCastOptions {
+            max_time_of_impact: max_distance,
+            target_distance: 0.0,
+            stop_at_penetration: true,
+            compute_impact_geometry_on_penetration: true,
+        };
+
+        self.query_pipeline
+            .cast_shape(
+                &self.rigid_body_set,
+                &self.collider_set,
+                &shape_pos,
+                &vector![direction.x, direction.y, direction.z],
+                shape.to_shape().as_ref(),
+                options,
+                filter.to_query_filter(),
+            )
+            .map(|(handle, hit)| ShapeCastHit {
+                collider: ColliderHandle(handle),
+                distance: hit.time_of_impact,
+                position: position + direction * hit.time_of_impact,
+                point: Vec3::new(hit.witness1.x, hit.witness1.y, hit.witness1.z),
+                normal: Vec3::new(hit.normal1.x, hit.normal1.y, hit.normal1.z),
+                penetrating: hit.status == ShapeCastStatus::PenetratingOrWithinTargetDist,
+            })
+    }
+
+    /// Find every collider overlapping a shape placed at a pose
+    ///
+    /// Like raycasts, this sees the world as of the last `step`.
+    #[must_use]
+    pub fn overlap_shape(
+        &self,
+        shape: CastShape,
+        position: Vec3,
+        rotation: Quat,
+        filter: &CollisionFilter,
+    ) -> Vec<ColliderHandle> {
+        let shape_pos = Isometry::from_parts(
+            nalgebra::Translation3::new(position.x, position.y, position.z),
+            quat_to_rapier(rotation),
+        );
+        let mut hits = Vec::new();
+        self.query_pipeline.intersections_with_shape(
+            &self.rigid_body_set,
+            &self.collider_set,
+            &shape_pos,
+            shape.to_shape().as_ref(),
+            filter.to_query_filter(),
+            |handle| {
+                hits.push(ColliderHandle(handle));
+                true
+            },
+        );
+        hits
+    }
+
+    /// Find every collider overlapping a sphere
+    #[must_use]
+    pub fn overlap_sphere(
+        &self,
+        center: Vec3,
+        radius: f32,
+        filter: &CollisionFilter,
+    ) -> Vec<ColliderHandle> {
+        self.overlap_shape(CastShape::Sphere(radius), center, Quat::IDENTITY, filter)
+    }
+
+    /// Find every collider overlapping an oriented box
+    #[must_use]
+    pub fn overlap_box(
+        &self,
+        center: Vec3,
+        rotation: Quat,
+        half_extents: Vec3,
+        filter: &CollisionFilter,
+    ) -> Vec<ColliderHandle> {
+        self.overlap_shape(CastShape::Box(half_extents), center, rotation, filter)
+    }
+
+    /// Find every collider overlapping a capsule aligned with its local Y axis
+    #[must_use]
+    pub fn overlap_capsule(
+        &self,
+        center: Vec3,
+        rotation: Quat,
+        half_height: f32,
+        radius: f32,
+        filter: &CollisionFilter,
+    ) -> Vec<ColliderHandle> {
+        self.overlap_shape(
+            CastShape::Capsule {
+                half_height,
+                radius,
+            },
+            center,
+            rotation,
+            filter,
+        )
+    }
+
+    /// Find every collider containing a point
+    #[must_use]
+    pub fn colliders_at_point(&self, point: Vec3, filter: &CollisionFilter) -> Vec<ColliderHandle> {
+        let mut hits = Vec::new();
+        self.query_pipeline.intersections_with_point(
+            &self.rigid_body_set,
+            &self.collider_set,
+            &point![point.x, point.y, point.z],
+            filter.to_query_filter(),
+            |handle| {
+                hits.push(ColliderHandle(handle));
+                true
+            },
+        );
+        hits
+    }
+
+    /// Check if a collider contains a point, using its current pose
+    #[must_use]
+    pub fn contains_point(&self, collider: ColliderHandle, point: Vec3) -> bool {
+        self.collider_set.get(collider.0).is_some_and(|c| {
+            c.shape()
+                .contains_point(c.position(), &point![point.x, point.y, point.z])
+        })
+    }
+
+    /// Cast a vehicle's wheel rays and apply suspension and tire forces
+    pub(super) fn update_vehicle(
+        &mut self,
+        controller: &mut rapier3d::control::DynamicRayCastVehicleController,
+        dt: f32,
+    ) {
+        let filter = QueryFilter::default()
+            .exclude_rigid_body(controller.chassis)
+            .exclude_sensors();
+        controller.update_vehicle(
+            dt,
+            &mut self.rigid_body_set,
+            &self.collider_set,
+            &self.query_pipeline,
+            filter,
+        );
+    }
+
+    /// Sweep a kinematic body's collider by `desired`, sliding along and
+    /// stepping over obstacles, and queue the resulting translation
+    ///
+    /// Returns the applied translation, the body's new position and whether
+    /// the shape ended up on the ground.
+    pub(super) fn move_shape(
+        &mut self,
+        body: RigidBodyHandle,
+        collider: ColliderHandle,
+        desired: Vec3,
+        controller: &rapier3d::control::KinematicCharacterController,
+        dt: f32,
+    ) -> Option<(Vec3, Vec3, bool)> {
+        let shape = self.collider_set.get(collider.0)?;
+        let rb = self.rigid_body_set.get(body.0)?;
+        let position = *rb.next_position();
+        let movement = controller.move_shape(
+            dt,
+            &self.rigid_body_set,
+            &self.collider_set,
+            &self.query_pipeline,
+            shape.shape(),
+            &shape
+                .position_wrt_parent()
+                .map_or(position, |local| position * local),
+            vector![desired.x, desired.y, desired.z],
+            QueryFilter::default()
+                .exclude_sensors()
+                .exclude_rigid_body(body.0),
+            |_| {},
+        );
+
+        let translation = Vec3::new(
+            movement.translation.x,
+            movement.translation.y,
+            movement.translation.z,
+        );
+        let target = position.translation.vector + movement.translation;
+        let rb = self.rigid_body_set.get_mut(body.0)?;
+        rb.set_next_kinematic_translation(target);
+        Some((
+            translation,
+            Vec3::new(target.x, target.y, target.z),
+            movement.grounded,
+        ))
+    }
+
+    /// Get the world bounds (min, max) of every non-sensor collider on a
+    /// fixed body or without a body
+    ///
+    /// Used to detect level geometry changes, e.g. for navmesh rebuilds.
+    #[must_use]
+    pub fn static_collider_bounds(&self) -> Vec<(ColliderHandle, Vec3, Vec3)> {
+        self.collider_set
+            .iter()
+            .filter(|(_, collider)| {
+                !collider.is_sensor()
+                    && collider
+                        .parent()
+                        .and_then(|body| self.rigid_body_set.get(body))
+                        .is_none_or(RigidBody::is_fixed)
+            })
+            .map(|(handle, collider)| {
+                let aabb = collider.compute_aabb();
+                (
+                    ColliderHandle(handle),
+                    Vec3::new(aabb.mins.x, aabb.mins.y, aabb.mins.z),
+                    Vec3::new(aabb.maxs.x, aabb.maxs.y, aabb.maxs.z),
+                )
+            })
+            .collect()
+    }
+
+    /// Snapshot collider shapes and positions for off-thread queries
+    ///
+    /// Take the snapshot after `step` and share it behind an `Arc`; it
+    /// stays valid (and unchanged) while the simulation keeps running.
+    #[must_use]
+    pub fn query_world(&self) -> QueryWorld {
+        QueryWorld::build(&self.collider_set)
+    }
+
+    /// Connect two bodies with a joint
+    pub fn create_joint(
+        &mut self,
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+        joint: &JointDesc,
+    ) -> JointHandle {
+        JointHandle(
+            self.impulse_joint_set
+                .insert(body1.0, body2.0, joint.build(), true),
+        )
+    }
+
+    /// Remove a joint
+    pub fn remove_joint(&mut self, joint: JointHandle) {
+        self.impulse_joint_set.remove(joint.0, true);
+    }
+
+    /// Replace the motor of a revolute or prismatic joint
+    ///
+    /// Does nothing for other joint kinds.
+    pub fn set_joint_motor(&mut self, joint: JointHandle, motor: JointMotor, max_force: f32) {
+        if let Some(joint) = self.impulse_joint_set.get_mut(joint.0)
+            && let Some(axis) = joints::free_axis(&joint.data)
+        {
+            joints::apply_motor(&mut joint.data, axis, motor, max_force);
+            let (body1, body2) = (joint.body1, joint.body2);
+            self.wake_joint_bodies(body1, body2);
+        }
+    }
+
+    /// Change the limits of a revolute or prismatic joint
+    ///
+    /// Does nothing for other joint kinds.
+    pub fn set_joint_limits(&mut self, joint: JointHandle, min: f32, max: f32) {
+        if let Some(joint) = self.impulse_joint_set.get_mut(joint.0)
+            && let Some(axis) = joints::free_axis(&joint.data)
+        {
+            joint.data.set_limits(axis, [min.min(max), min.max(max)]);
+            let (body1, body2) = (joint.body1, joint.body2);
+            self.wake_joint_bodies(body1, body2);
+        }
+    }
+
+    /// Wake both bodies of a joint so a changed motor or limit takes effect
+    fn wake_joint_bodies(
+        &mut self,
+        body1: rapier3d::dynamics::RigidBodyHandle,
+        body2: rapier3d::dynamics::RigidBodyHandle,
+    ) {
+        for body in [body1, body2] {
+            if let Some(rb) = self.rigid_body_set.get_mut(body) {
+                rb.wake_up(true);
+            }
+        }
+    }
+
+    /// Emit collider wireframes, contacts and joint anchors
+    ///
+    /// Bodies are colored by type, with sleeping bodies dimmed to blue.
+    /// Shapes without a dedicated wireframe are drawn as their bounding box.
+    pub fn debug_render(&self, draw: &mut DebugDraw) {
+        if !draw.is_enabled() {
+            return;
+        }
+
+        for (_, collider) in self.collider_set.iter() {
+            let color = if collider.is_sensor() {
+                DEBUG_SENSOR
+            } else {
+                match collider.parent().and_then(|h| self.rigid_body_set.get(h)) {
+                    Some(rb) if rb.is_dynamic() && rb.is_sleeping() => DEBUG_SLEEPING,
+                    Some(rb) if rb.is_dynamic() => DEBUG_DYNAMIC,
+                    Some(rb) if rb.is_kinematic() => DEBUG_KINEMATIC,
+                    _ => DEBUG_FIXED,
+                }
+            };
+
+            let pose = collider.position();
+            let center = rapier_to_vec3(&pose.translation.vector);
+            let rotation = rapier_to_quat(&pose.rotation);
+            match collider.shape().as_typed_shape() {
+                TypedShape::Ball(ball) => draw.sphere(center, rotation, ball.radius, color),
+                TypedShape::Cuboid(cuboid) => draw.cuboid(
+                    center,
+                    rotation,
+                    rapier_to_vec3(&cuboid.half_extents),
+                    color,
+                ),
+                TypedShape::Capsule(capsule) => {
+                    let a = pose * capsule.segment.a;
+                    let b = pose * capsule.segment.b;
+                    draw.capsule(
+                        rapier_to_vec3(&a.coords),
+                        rapier_to_vec3(&b.coords),
+                        capsule.radius,
+                        color,
+                    );
+                }
+                _ => {
+                    let aabb = collider.compute_aabb();
+                    draw.aabb(
+                        rapier_to_vec3(&aabb.mins.coords),
+                        rapier_to_vec3(&aabb.maxs.coords),
+                        color,
+                    );
+                }
+            }
+        }
+
+        for pair in self.narrow_phase.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+            for manifold in &pair.manifolds {
+                let normal = rapier_to_vec3(&manifold.data.normal);
+                for contact in &manifold.data.solver_contacts {
+                    let point = rapier_to_vec3(&contact.point.coords);
+                    draw.point(point, 0.1, DEBUG_CONTACT);
+                    draw.arrow(point, point + normal * 0.3, DEBUG_CONTACT);
+                }
+            }
+        }
+
+        for (_, joint) in self.impulse_joint_set.iter() {
+            let (Some(rb1), Some(rb2)) = (
+                self.rigid_body_set.get(joint.body1),
+                self.rigid_body_set.get(joint.body2),
+            ) else {
+                continue;
+            };
+            let anchor1 = rapier_to_vec3(&(rb1.position() * joint.data.local_anchor1()).coords);
+            let anchor2 = rapier_to_vec3(&(rb2.position() * joint.data.local_anchor2()).coords);
+            draw.line(rapier_to_vec3(rb1.translation()), anchor1, DEBUG_JOINT);
+            draw.line(rapier_to_vec3(rb2.translation()), anchor2, DEBUG_JOINT);
+            draw.point(anchor1, 0.15, DEBUG_JOINT);
+            draw.point(anchor2, 0.15, DEBUG_JOINT);
+        }
+    }
+
     /// Remove a rigid body and its colliders
     pub fn remove_body(&mut self, body: RigidBodyHandle) {
         self.rigid_body_set.remove(
@@ -285,6 +1391,23 @@ impl Physics {
     }
 }
 
+/// Convert render vertices and indices to collider points and triangles
+///
+/// Trailing indices that do not form a full triangle, and triangles
+/// referencing missing vertices, are dropped.
+fn mesh_geometry(vertices: &[Vertex], indices: &[u32]) -> (Vec<Point<f32>>, Vec<[u32; 3]>) {
+    let points = vertices
+        .iter()
+        .map(|v| point![v.position[0], v.position[1], v.position[2]])
+        .collect();
+    let triangles = indices
+        .chunks_exact(3)
+        .map(|t| [t[0], t[1], t[2]])
+        .filter(|t| t.iter().all(|&i| (i as usize) < vertices.len()))
+        .collect();
+    (points, triangles)
+}
+
 impl Default for Physics {
     fn default() -> Self {
         Self::new()
@@ -298,6 +1421,315 @@ pub struct RaycastHit {
     pub collider: ColliderHandle,
     /// The point of intersection
     pub point: Vec3,
+    /// Surface normal at the point of intersection
+    pub normal: Vec3,
     /// Distance from ray origin
     pub distance: f32,
 }
+
+/// Shape swept by `Physics::shape_cast`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CastShape {
+    /// Sphere of the given radius
+    Sphere(f32),
+    /// Box with the given half extents
+    Box(Vec3),
+    /// Y-aligned capsule
+    Capsule {
+        /// Half height of the cylindrical part
+        half_height: f32,
+        /// Radius of the caps
+        radius: f32,
+    },
+}
+
+impl CastShape {
+    fn to_shape(self) -> SharedShape {
+        match self {
+            Self::Sphere(radius) => SharedShape::ball(radius),
+            Self::Box(half_extents) => {
+                SharedShape::cuboid(half_extents.x, half_extents.y, half_extents.z)
+            }
+            Self::Capsule {
+                half_height,
+                radius,
+            } => SharedShape::capsule_y(half_height, radius),
+        }
+    }
+}
+
+/// Result of a shape cast
+#[derive(Debug, Clone)]
+pub struct ShapeCastHit {
+    /// The collider that was hit
+    pub collider: ColliderHandle,
+    /// Distance travelled before impact (the time of impact for a unit direction)
+    pub distance: f32,
+    /// Position of the shape at impact
+    pub position: Vec3,
+    /// Contact point on the hit collider
+    pub point: Vec3,
+    /// Surface normal of the hit collider at the contact point
+    pub normal: Vec3,
+    /// The shape already overlapped the collider at its start position
+    pub penetrating: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shape_cast_hits_box() {
+        let mut physics = Physics::new();
+        let ground = physics.create_static_body(Vec3::ZERO, Quat::IDENTITY);
+        physics.add_ground_plane(ground);
+        let wall = physics.create_static_body(Vec3::new(5.0, 1.1, 0.0), Quat::IDENTITY);
+        let wall_collider = physics.add_box_collider(wall, Vec3::ONE, 1.0);
+        physics.step(1.0 / 60.0);
+
+        let sphere = CastShape::Sphere(0.5);
+        let start = Vec3::new(0.0, 1.1, 0.0);
+        let hit = physics
+            .shape_cast(sphere, start, Quat::IDENTITY, Vec3::X, 10.0)
+            .unwrap();
+        assert_eq!(hit.collider, wall_collider);
+        assert!((hit.distance - 3.5).abs() < 1e-3);
+        assert!((hit.normal - Vec3::NEG_X).length() < 1e-3);
+        assert!((hit.point.x - 4.0).abs() < 1e-3);
+        assert!(!hit.penetrating);
+
+        let inside = Vec3::new(5.0, 1.1, 0.0);
+        let hit = physics
+            .shape_cast(sphere, inside, Quat::IDENTITY, Vec3::X, 10.0)
+            .unwrap();
+        assert!(hit.penetrating);
+        assert!(hit.distance.abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_step_with_runs_hook_per_tick() {
+        let mut physics = Physics::new();
+        physics.set_fixed_timestep(Some(0.1));
+        let mut ticks = Vec::new();
+        physics.step_with(0.35, |physics, dt| ticks.push((physics.tick_count(), dt)));
+        assert_eq!(ticks, [(0, 0.1), (1, 0.1), (2, 0.1)]);
+        assert_eq!(physics.tick_count(), 3);
+    }
+
+    #[test]
+    fn test_mesh_colliders_from_cube() {
+        let mut physics = Physics::new();
+        let level = physics.create_static_body(Vec3::ZERO, Quat::IDENTITY);
+        let trimesh = physics.add_trimesh_collider(level, &Mesh::cube()).unwrap();
+        let crate_body = physics.create_dynamic_body(Vec3::new(5.0, 0.0, 0.0), Quat::IDENTITY);
+        let hull = physics
+            .add_convex_decomposition_collider(crate_body, &Mesh::cube(), 1.0)
+            .unwrap();
+        physics.step(1.0 / 60.0);
+
+        let hit = physics
+            .raycast(Vec3::new(0.0, 5.0, 0.0), Vec3::NEG_Y, 10.0)
+            .unwrap();
+        assert_eq!(hit.collider, trimesh);
+        assert!((hit.distance - 4.5).abs() < 1e-3);
+
+        let center = physics.get_position(crate_body).unwrap();
+        assert!(physics.contains_point(hull, center));
+        assert!(!physics.contains_point(hull, center + Vec3::X));
+    }
+
+    #[test]
+    fn test_sensor_reports_enter_and_exit() {
+        let mut physics = Physics::with_gravity(Vec3::ZERO);
+        let zone = physics.create_static_body(Vec3::ZERO, Quat::IDENTITY);
+        let sensor = physics.add_box_sensor(zone, Vec3::ONE);
+        let body = physics.create_dynamic_body(Vec3::new(-3.0, 0.0, 0.0), Quat::IDENTITY);
+        let ball = physics.add_sphere_collider(body, 0.25, 1.0);
+        physics.set_linear_velocity(body, Vec3::new(6.0, 0.0, 0.0));
+
+        let mut events = Vec::new();
+        let mut overlapped = false;
+        for _ in 0..120 {
+            physics.step(1.0 / 60.0);
+            events.extend(physics.drain_trigger_events());
+            overlapped |= physics.sensor_overlaps(sensor) == [ball];
+        }
+
+        assert!(overlapped);
+        assert_eq!(events.len(), 2);
+        assert!(events[0].entered());
+        assert!(events[1].exited());
+        assert!(
+            events
+                .iter()
+                .all(|event| event.sensor == sensor && event.other == ball)
+        );
+        assert!(physics.sensor_overlaps(sensor).is_empty());
+    }
+
+    #[test]
+    fn test_cylinder_hull_and_offset() {
+        let mut physics = Physics::new();
+        let body = physics.create_static_body(Vec3::ZERO, Quat::IDENTITY);
+        let cylinder = physics.add_cylinder_collider(body, 1.0, 0.5, 1.0);
+        physics.set_collider_offset(cylinder, Vec3::new(0.0, 0.0, 3.0), Quat::IDENTITY);
+        let corners = [
+            Vec3::new(-1.0, -1.0, -1.0),
+            Vec3::new(1.0, -1.0, -1.0),
+            Vec3::new(0.0, -1.0, 1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        ];
+        let hull = physics
+            .add_convex_hull_collider(body, &corners, 1.0)
+            .unwrap();
+        physics.step(1.0 / 60.0);
+
+        assert!(physics.contains_point(cylinder, Vec3::new(0.0, 0.9, 3.0)));
+        assert!(!physics.contains_point(cylinder, Vec3::new(0.0, 0.9, 0.0)));
+        assert!(physics.contains_point(hull, Vec3::new(0.0, -0.5, 0.0)));
+        assert!(!physics.contains_point(hull, Vec3::new(0.0, 0.9, 0.5)));
+
+        let segment = [Vec3::ZERO, Vec3::X];
+        assert!(
+            physics
+                .add_convex_hull_collider(body, &segment, 1.0)
+                .is_none()
+        );
+        let coplanar = [Vec3::ZERO, Vec3::X, Vec3::Z, Vec3::new(1.0, 0.0, 1.0)];
+        assert!(
+            physics
+                .add_convex_hull_collider(body, &coplanar, 1.0)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_heightfield_collider() {
+        let mut physics = Physics::new();
+        let terrain = physics.create_static_body(Vec3::ZERO, Quat::IDENTITY);
+        let size = Vec3::new(10.0, 2.0, 10.0);
+        assert!(
+            physics
+                .add_heightfield_collider(terrain, &[0.0; 8], 3, 3, size)
+                .is_none()
+        );
+        assert!(
+            physics
+                .add_heightfield_collider(terrain, &[0.0; 3], 3, 1, size)
+                .is_none()
+        );
+
+        // Flat at height 0.5, scaled by `size.y`
+        let field = physics
+            .add_heightfield_collider(terrain, &[0.5; 9], 3, 3, size)
+            .unwrap();
+        physics.step(1.0 / 60.0);
+
+        let hit = physics
+            .raycast(Vec3::new(2.0, 5.0, -3.0), Vec3::NEG_Y, 10.0)
+            .unwrap();
+        assert_eq!(hit.collider, field);
+        assert!((hit.point.y - 1.0).abs() < 1e-3);
+        assert!(
+            physics
+                .raycast(Vec3::new(6.0, 5.0, 0.0), Vec3::NEG_Y, 10.0)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_overlap_and_point_queries() {
+        let mut physics = Physics::new();
+        let near = physics.create_static_body(Vec3::ZERO, Quat::IDENTITY);
+        let near_box = physics.add_box_collider(near, Vec3::splat(0.5), 1.0);
+        let far = physics.create_static_body(Vec3::new(4.0, 0.0, 0.0), Quat::IDENTITY);
+        let far_ball = physics.add_sphere_collider(far, 0.5, 1.0);
+        let trigger = physics.add_sphere_sensor(near, 1.0);
+        physics.step(1.0 / 60.0);
+
+        let filter = CollisionFilter::new();
+        assert_eq!(physics.overlap_sphere(Vec3::X, 0.75, &filter), [near_box]);
+        let mut both = physics.overlap_box(
+            Vec3::new(2.0, 0.0, 0.0),
+            Quat::IDENTITY,
+            Vec3::new(2.0, 0.5, 0.5),
+            &filter,
+        );
+        both.sort_by_key(|handle| handle.0.into_raw_parts());
+        assert_eq!(both, [near_box, far_ball]);
+
+        assert_eq!(
+            physics.colliders_at_point(Vec3::new(4.2, 0.0, 0.0), &filter),
+            [far_ball]
+        );
+        let with_sensors = filter.with_sensors(true);
+        let inside_sensor = Vec3::new(0.0, 0.0, 0.8);
+        assert!(
+            physics
+                .colliders_at_point(inside_sensor, &filter)
+                .is_empty()
+        );
+        assert_eq!(
+            physics.colliders_at_point(inside_sensor, &with_sensors),
+            [trigger]
+        );
+
+        assert!(physics.contains_point(far_ball, Vec3::new(4.4, 0.0, 0.0)));
+        assert!(!physics.contains_point(far_ball, Vec3::new(4.6, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_gravity_scale_and_damping() {
+        let mut physics = Physics::new();
+        let floating = physics.create_dynamic_body(Vec3::ZERO, Quat::IDENTITY);
+        physics.add_sphere_collider(floating, 0.5, 1.0);
+        physics.set_gravity_scale(floating, 0.0);
+        let damped = physics.create_dynamic_body(Vec3::new(5.0, 0.0, 0.0), Quat::IDENTITY);
+        physics.add_sphere_collider(damped, 0.5, 1.0);
+        physics.set_gravity_scale(damped, 0.0);
+        physics.set_linear_damping(damped, 5.0);
+        physics.set_linear_velocity(damped, Vec3::X);
+
+        for _ in 0..60 {
+            physics.step(1.0 / 60.0);
+        }
+
+        assert_eq!(physics.gravity_scale(floating), Some(0.0));
+        assert!(physics.get_position(floating).unwrap().length() < 1e-5);
+        assert_eq!(physics.linear_damping(damped), Some(5.0));
+        assert!(physics.get_linear_velocity(damped).unwrap().x < 0.05);
+    }
+
+    #[test]
+    fn test_sleep_control() {
+        let mut physics = Physics::new();
+        let body = physics.create_dynamic_body(Vec3::new(0.0, 5.0, 0.0), Quat::IDENTITY);
+        physics.add_sphere_collider(body, 0.5, 1.0);
+
+        physics.sleep(body);
+        assert!(physics.is_sleeping(body));
+        physics.step(1.0 / 60.0);
+        assert_eq!(physics.get_position(body), Some(Vec3::new(0.0, 5.0, 0.0)));
+
+        physics.wake_up(body);
+        physics.set_can_sleep(body, false);
+        assert!(!physics.can_sleep(body));
+        physics.step(1.0 / 60.0);
+        assert!(physics.get_position(body).unwrap().y < 5.0);
+    }
+
+    #[test]
+    fn test_mesh_collider_without_triangles() {
+        let mut physics = Physics::new();
+        let body = physics.create_static_body(Vec3::ZERO, Quat::IDENTITY);
+        let mut mesh = Mesh::cube();
+        mesh.indices.clear();
+        assert!(physics.add_trimesh_collider(body, &mesh).is_none());
+        assert!(
+            physics
+                .add_convex_decomposition_collider(body, &mesh, 1.0)
+                .is_none()
+        );
+    }
+}
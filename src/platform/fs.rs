@@ -0,0 +1,411 @@
+//! Per-platform data directories and file I/O
+//!
+//! Resolves save, config, and cache directories for the current OS so
+//! games never hardcode paths. On wasm there is no filesystem; files are
+//! persisted in the browser's `localStorage` with the same API, and
+//! "background" operations complete immediately since wasm has no threads.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+/// Category of application data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataKind {
+    /// Save games and user content
+    Save,
+    /// Settings and key bindings
+    Config,
+    /// Regenerable data (shader caches, thumbnails)
+    Cache,
+    /// Screenshots
+    Screenshots,
+}
+
+impl DataKind {
+    /// Subdirectory name inside the base directory
+    const fn subdirectory(self) -> &'static str {
+        match self {
+            Self::Save => "saves",
+            Self::Config => "config",
+            Self::Cache => "cache",
+            Self::Screenshots => "screenshots",
+        }
+    }
+}
+
+/// Resolved data directories for an application
+#[derive(Debug, Clone)]
+pub struct AppDirs {
+    /// Application name used as the directory name
+    app_name: String,
+    /// Base directory for persistent data
+    data_dir: PathBuf,
+    /// Base directory for configuration
+    config_dir: PathBuf,
+    /// Base directory for caches
+    cache_dir: PathBuf,
+}
+
+impl AppDirs {
+    /// Resolve directories for an application on the current platform
+    #[must_use]
+    pub fn new(app_name: impl Into<String>) -> Self {
+        let app_name = app_name.into();
+        let (data, config, cache) = platform_base_dirs();
+        Self {
+            data_dir: data.join(&app_name),
+            config_dir: config.join(&app_name),
+            cache_dir: cache.join(&app_name),
+            app_name,
+        }
+    }
+
+    /// Use a single root for every kind (portable installs, tests)
+    #[must_use]
+    pub fn with_root(app_name: impl Into<String>, root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        Self {
+            app_name: app_name.into(),
+            data_dir: root.clone(),
+            config_dir: root.clone(),
+            cache_dir: root,
+        }
+    }
+
+    /// Get the application name
+    #[must_use]
+    pub fn app_name(&self) -> &str {
+        &self.app_name
+    }
+
+    /// Get the directory for a kind of data
+    #[must_use]
+    pub fn dir(&self, kind: DataKind) -> PathBuf {
+        let base = match kind {
+            DataKind::Save | DataKind::Screenshots => &self.data_dir,
+            DataKind::Config => &self.config_dir,
+            DataKind::Cache => &self.cache_dir,
+        };
+        base.join(kind.subdirectory())
+    }
+
+    /// Resolve a relative file path for a kind of data
+    ///
+    /// Absolute paths and `..` components are stripped so callers cannot
+    /// escape the data directory.
+    #[must_use]
+    pub fn path(&self, kind: DataKind, relative: impl AsRef<Path>) -> PathBuf {
+        let mut path = self.dir(kind);
+        for component in relative.as_ref().components() {
+            if let std::path::Component::Normal(part) = component {
+                path.push(part);
+            }
+        }
+        path
+    }
+
+    /// Read a file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file does not exist or cannot be read
+    pub fn read(&self, kind: DataKind, relative: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+        backend::read(&self.path(kind, relative))
+    }
+
+    /// Write a file, creating parent directories
+    ///
+    /// The data is written to a temporary file first and renamed into
+    /// place, so a crash mid-write never leaves a truncated save.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written
+    pub fn write(
+        &self,
+        kind: DataKind,
+        relative: impl AsRef<Path>,
+        bytes: &[u8],
+    ) -> io::Result<()> {
+        backend::write(&self.path(kind, relative), bytes)
+    }
+
+    /// Delete a file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be removed
+    pub fn remove(&self, kind: DataKind, relative: impl AsRef<Path>) -> io::Result<()> {
+        backend::remove(&self.path(kind, relative))
+    }
+
+    /// Check if a file exists
+    #[must_use]
+    pub fn exists(&self, kind: DataKind, relative: impl AsRef<Path>) -> bool {
+        backend::exists(&self.path(kind, relative))
+    }
+
+    /// Read a file on a background thread
+    pub fn read_async(&self, kind: DataKind, relative: impl AsRef<Path>) -> IoTask<Vec<u8>> {
+        let path = self.path(kind, relative);
+        IoTask::spawn(move || backend::read(&path))
+    }
+
+    /// Write a file on a background thread
+    pub fn write_async(
+        &self,
+        kind: DataKind,
+        relative: impl AsRef<Path>,
+        bytes: Vec<u8>,
+    ) -> IoTask<()> {
+        let path = self.path(kind, relative);
+        IoTask::spawn(move || backend::write(&path, &bytes))
+    }
+}
+
+/// A pending background file operation
+#[derive(Debug)]
+pub struct IoTask<T> {
+    /// Receives the result when the operation finishes
+    receiver: Receiver<io::Result<T>>,
+}
+
+impl<T: Send + 'static> IoTask<T> {
+    /// Run an operation on a background thread
+    #[cfg(not(target_arch = "wasm32"))]
+    fn spawn(operation: impl FnOnce() -> io::Result<T> + Send + 'static) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(operation());
+        });
+        Self { receiver }
+    }
+
+    /// Run an operation inline; wasm has no threads to spawn
+    #[cfg(target_arch = "wasm32")]
+    fn spawn(operation: impl FnOnce() -> io::Result<T> + Send + 'static) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let _ = sender.send(operation());
+        Self { receiver }
+    }
+
+    /// Check for completion without blocking
+    ///
+    /// Returns `None` while the operation is still running.
+    pub fn poll(&self) -> Option<io::Result<T>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                Some(Err(io::Error::other("I/O task ended without a result")))
+            }
+        }
+    }
+
+    /// Block until the operation finishes
+    ///
+    /// # Errors
+    ///
+    /// Returns the operation's error, or an error if the task was lost
+    pub fn wait(self) -> io::Result<T> {
+        self.receiver
+            .recv()
+            .unwrap_or_else(|_| Err(io::Error::other("I/O task ended without a result")))
+    }
+}
+
+/// Base (data, config, cache) directories for the current OS
+#[cfg(target_os = "windows")]
+fn platform_base_dirs() -> (PathBuf, PathBuf, PathBuf) {
+    let roaming = env_path("APPDATA").unwrap_or_else(|| PathBuf::from("."));
+    let local = env_path("LOCALAPPDATA").unwrap_or_else(|| roaming.clone());
+    (roaming.clone(), roaming, local)
+}
+
+/// Base (data, config, cache) directories for the current OS
+#[cfg(target_os = "macos")]
+fn platform_base_dirs() -> (PathBuf, PathBuf, PathBuf) {
+    let home = env_path("HOME").unwrap_or_else(|| PathBuf::from("."));
+    let support = home.join("Library/Application Support");
+    (support.clone(), support, home.join("Library/Caches"))
+}
+
+/// Base (data, config, cache) directories for the current OS
+#[cfg(target_arch = "wasm32")]
+fn platform_base_dirs() -> (PathBuf, PathBuf, PathBuf) {
+    let root = PathBuf::from("/");
+    (root.clone(), root.clone(), root)
+}
+
+/// Base (data, config, cache) directories for the current OS
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_arch = "wasm32")))]
+fn platform_base_dirs() -> (PathBuf, PathBuf, PathBuf) {
+    let home = env_path("HOME").unwrap_or_else(|| PathBuf::from("."));
+    (
+        env_path("XDG_DATA_HOME").unwrap_or_else(|| home.join(".local/share")),
+        env_path("XDG_CONFIG_HOME").unwrap_or_else(|| home.join(".config")),
+        env_path("XDG_CACHE_HOME").unwrap_or_else(|| home.join(".cache")),
+    )
+}
+
+/// Read an absolute path from an environment variable
+#[cfg(not(target_arch = "wasm32"))]
+fn env_path(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+}
+
+/// Native filesystem backend
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    use std::io;
+    use std::path::{Path, PathBuf};
+
+    pub fn read(path: &Path) -> io::Result<Vec<u8>> {
+        std::fs::read(path)
+    }
+
+    /// Sibling temp file for `path`, unique per full file name
+    ///
+    /// `save.dat` and `save.json` must not share a temp file, so the suffix
+    /// is appended rather than replacing the extension.
+    pub fn temp_path(path: &Path) -> PathBuf {
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        PathBuf::from(temp)
+    }
+
+    pub fn write(path: &Path, bytes: &[u8]) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let temp = temp_path(path);
+        std::fs::write(&temp, bytes)?;
+        std::fs::rename(&temp, path)
+    }
+
+    pub fn remove(path: &Path) -> io::Result<()> {
+        std::fs::remove_file(path)
+    }
+
+    pub fn exists(path: &Path) -> bool {
+        path.is_file()
+    }
+}
+
+/// Browser `localStorage` backend for wasm, where there is no filesystem
+///
+/// Each file is one storage entry keyed by its full path, with the bytes
+/// hex-encoded since storage values are strings. Writes replace the entry
+/// in a single call, so they are atomic like the native rename.
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    use std::io;
+    use std::path::Path;
+
+    fn storage() -> io::Result<web_sys::Storage> {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "localStorage unavailable"))
+    }
+
+    fn key(path: &Path) -> String {
+        path.to_string_lossy().into_owned()
+    }
+
+    fn js_error(error: &web_sys::wasm_bindgen::JsValue) -> io::Error {
+        io::Error::other(format!("{error:?}"))
+    }
+
+    fn encode(bytes: &[u8]) -> String {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+        let mut text = String::with_capacity(bytes.len() * 2);
+        for byte in bytes {
+            text.push(char::from(DIGITS[usize::from(byte >> 4)]));
+            text.push(char::from(DIGITS[usize::from(byte & 0xf)]));
+        }
+        text
+    }
+
+    fn decode(text: &str) -> io::Result<Vec<u8>> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "corrupt storage entry");
+        if text.len() % 2 != 0 {
+            return Err(invalid());
+        }
+        (0..text.len())
+            .step_by(2)
+            .map(|i| {
+                text.get(i..i + 2)
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                    .ok_or_else(invalid)
+            })
+            .collect()
+    }
+
+    pub fn read(path: &Path) -> io::Result<Vec<u8>> {
+        let text = storage()?
+            .get_item(&key(path))
+            .map_err(|e| js_error(&e))?
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+        decode(&text)
+    }
+
+    pub fn write(path: &Path, bytes: &[u8]) -> io::Result<()> {
+        storage()?
+            .set_item(&key(path), &encode(bytes))
+            .map_err(|e| js_error(&e))
+    }
+
+    pub fn remove(path: &Path) -> io::Result<()> {
+        let storage = storage()?;
+        let key = key(path);
+        if storage.get_item(&key).map_err(|e| js_error(&e))?.is_none() {
+            return Err(io::Error::from(io::ErrorKind::NotFound));
+        }
+        storage.remove_item(&key).map_err(|e| js_error(&e))
+    }
+
+    pub fn exists(path: &Path) -> bool {
+        storage().is_ok_and(|s| s.get_item(&key(path)).is_ok_and(|v| v.is_some()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_cannot_escape() {
+        let dirs = AppDirs::with_root("game", "/tmp/game");
+        let path = dirs.path(DataKind::Save, "../../etc/passwd");
+
+        assert_eq!(path, PathBuf::from("/tmp/game/saves/etc/passwd"));
+    }
+
+    #[test]
+    fn test_write_read_round_trip() {
+        let root = std::env::temp_dir().join(format!("engine_fs_test_{}", std::process::id()));
+        let dirs = AppDirs::with_root("game", &root);
+
+        dirs.write(DataKind::Config, "settings.ron", b"volume: 0.5")
+            .unwrap();
+        assert!(dirs.exists(DataKind::Config, "settings.ron"));
+
+        let task = dirs.read_async(DataKind::Config, "settings.ron");
+        assert_eq!(task.wait().unwrap(), b"volume: 0.5");
+
+        dirs.remove(DataKind::Config, "settings.ron").unwrap();
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn test_temp_path_keeps_extension() {
+        let dat = backend::temp_path(Path::new("/saves/save.dat"));
+        let json = backend::temp_path(Path::new("/saves/save.json"));
+
+        assert_eq!(dat, PathBuf::from("/saves/save.dat.tmp"));
+        assert_ne!(dat, json);
+    }
+}
@@ -0,0 +1,7 @@
+//! Platform abstraction module
+//!
+//! Per-OS data directories and file I/O.
+
+pub mod fs;
+
+pub use fs::{AppDirs, DataKind, IoTask};
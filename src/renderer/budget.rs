@@ -0,0 +1,127 @@
+//! GPU memory budget
+//!
+//! Tracks estimated GPU memory used by streamed resources against a budget.
+
+use std::collections::HashMap;
+
+/// Default budget for streamed resources (512 MiB)
+const DEFAULT_BUDGET_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Estimated GPU memory reservations keyed by resource
+#[derive(Debug, Clone)]
+pub struct GpuMemoryBudget {
+    /// Maximum bytes that may be reserved
+    budget_bytes: u64,
+    /// Bytes reserved per resource key
+    reservations: HashMap<u64, u64>,
+    /// Total reserved bytes
+    used_bytes: u64,
+}
+
+impl GpuMemoryBudget {
+    /// Create a budget with a byte limit
+    #[must_use]
+    pub fn new(budget_bytes: u64) -> Self {
+        Self {
+            budget_bytes,
+            reservations: HashMap::new(),
+            used_bytes: 0,
+        }
+    }
+
+    /// Get the byte limit
+    #[must_use]
+    pub const fn budget(&self) -> u64 {
+        self.budget_bytes
+    }
+
+    /// Set the byte limit
+    ///
+    /// Existing reservations are kept even if they now exceed the budget.
+    pub fn set_budget(&mut self, budget_bytes: u64) {
+        self.budget_bytes = budget_bytes;
+    }
+
+    /// Get the total reserved bytes
+    #[must_use]
+    pub const fn used(&self) -> u64 {
+        self.used_bytes
+    }
+
+    /// Get the bytes still available
+    #[must_use]
+    pub const fn available(&self) -> u64 {
+        self.budget_bytes.saturating_sub(self.used_bytes)
+    }
+
+    /// Get the used fraction of the budget
+    #[must_use]
+    pub fn usage(&self) -> f32 {
+        if self.budget_bytes == 0 {
+            return 1.0;
+        }
+        self.used_bytes as f32 / self.budget_bytes as f32
+    }
+
+    /// Check if reservations exceed the budget
+    #[must_use]
+    pub const fn is_over_budget(&self) -> bool {
+        self.used_bytes > self.budget_bytes
+    }
+
+    /// Get the bytes reserved for a resource
+    #[must_use]
+    pub fn reserved(&self, key: u64) -> u64 {
+        self.reservations.get(&key).copied().unwrap_or(0)
+    }
+
+    /// Reserve bytes for a resource if they fit, replacing its previous reservation
+    ///
+    /// Returns `false` and leaves the budget unchanged if they do not fit.
+    pub fn try_reserve(&mut self, key: u64, bytes: u64) -> bool {
+        let without = self.used_bytes - self.reserved(key);
+        if without + bytes > self.budget_bytes {
+            return false;
+        }
+        self.reserve(key, bytes);
+        true
+    }
+
+    /// Reserve bytes for a resource regardless of the budget
+    pub fn reserve(&mut self, key: u64, bytes: u64) {
+        let previous = self.reservations.insert(key, bytes).unwrap_or(0);
+        self.used_bytes = self.used_bytes - previous + bytes;
+    }
+
+    /// Release a resource's reservation, returning the freed bytes
+    pub fn release(&mut self, key: u64) -> u64 {
+        let freed = self.reservations.remove(&key).unwrap_or(0);
+        self.used_bytes -= freed;
+        freed
+    }
+}
+
+impl Default for GpuMemoryBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_BUDGET_BYTES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_within_budget() {
+        let mut budget = GpuMemoryBudget::new(100);
+
+        assert!(budget.try_reserve(1, 60));
+        assert!(!budget.try_reserve(2, 50));
+        assert!(budget.try_reserve(1, 20));
+        assert!(budget.try_reserve(2, 50));
+        assert_eq!(budget.used(), 70);
+
+        assert_eq!(budget.release(1), 20);
+        assert_eq!(budget.available(), 50);
+    }
+}
@@ -1,6 +1,6 @@
 //! Main renderer implementation
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use bytemuck::{Pod, Zeroable};
 use glam::{Mat4, Vec3};
@@ -8,9 +8,14 @@ use wgpu::util::DeviceExt;
 use winit::window::Window;
 
 use super::Camera;
+use super::budget::GpuMemoryBudget;
+use super::debug_draw::{DebugDraw, DebugVertex};
+use super::deferred::{DeletionQueue, GpuResource};
 use super::material::MaterialUniform;
 use super::mesh::{Mesh, Vertex};
+use super::text::{GlyphInstance, TextStyle, TextStyleUniform};
 use super::texture::Texture;
+use crate::assets::Font;
 
 /// Uniform buffer for camera data
 #[repr(C)]
@@ -134,9 +139,14 @@ pub struct Renderer {
     light_uniform: LightUniform,
     light_buffer: wgpu::Buffer,
     particle_pipeline: wgpu::RenderPipeline,
+    debug_line_pipeline: wgpu::RenderPipeline,
     ui_pipeline: wgpu::RenderPipeline,
     ui_screen_size_buffer: wgpu::Buffer,
     ui_screen_size_bind_group: wgpu::BindGroup,
+    text_pipeline: wgpu::RenderPipeline,
+    text_bind_group_layout: wgpu::BindGroupLayout,
+    memory_budget: GpuMemoryBudget,
+    deletion_queue: Mutex<DeletionQueue>,
     /// Clear color
     pub clear_color: wgpu::Color,
 }
@@ -210,6 +220,7 @@ impl Renderer {
             desired_maximum_frame_latency: 2,
         };
         surface.configure(&device, &config);
+        let frames_in_flight = config.desired_maximum_frame_latency;
 
         // Create depth texture
         let (depth_texture, depth_view) = Self::create_depth_texture(&device, size.0, size.1);
@@ -477,6 +488,54 @@ impl Renderer {
             cache: None,
         });
 
+        // Create debug line pipeline
+        let debug_line_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Debug Line Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("debug_line.wgsl").into()),
+        });
+
+        let debug_line_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Debug Line Pipeline"),
+            layout: Some(&particle_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &debug_line_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<DebugVertex>() as u64,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![
+                        0 => Float32x3, // position
+                        1 => Float32x4, // color
+                    ],
+                }],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &debug_line_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::LineList,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
         // Create UI pipeline
         let ui_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("UI Shader"),
@@ -562,6 +621,96 @@ impl Renderer {
             cache: None,
         });
 
+        // Create text pipeline (raster and SDF glyphs)
+        let text_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Text Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("text.wgsl").into()),
+        });
+
+        let text_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Text Bind Group Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            multisampled: false,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ],
+            });
+
+        let text_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Text Pipeline Layout"),
+            bind_group_layouts: &[&ui_screen_size_bind_group_layout, &text_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let text_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Text Pipeline"),
+            layout: Some(&text_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &text_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<GlyphInstance>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![
+                        0 => Float32x2, // position
+                        1 => Float32x2, // size
+                        2 => Float32x2, // uv_min
+                        3 => Float32x2, // uv_max
+                        4 => Float32x4, // color
+                    ],
+                }],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &text_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Always,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
         Self {
             surface,
             device,
@@ -579,9 +728,14 @@ impl Renderer {
             light_uniform,
             light_buffer,
             particle_pipeline,
+            debug_line_pipeline,
             ui_pipeline,
             ui_screen_size_buffer,
             ui_screen_size_bind_group,
+            text_pipeline,
+            text_bind_group_layout,
+            memory_budget: GpuMemoryBudget::default(),
+            deletion_queue: Mutex::new(DeletionQueue::new(frames_in_flight)),
             clear_color: wgpu::Color {
                 r: 0.1,
                 g: 0.1,
@@ -674,7 +828,7 @@ impl Renderer {
             .create_buffer_init(&wgpu::util::BufferInitDescriptor {
                 label: Some("Vertex Buffer"),
                 contents: bytemuck::cast_slice(&mesh.vertices),
-                usage: wgpu::BufferUsages::VERTEX,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
             });
 
         let index_buffer = self
@@ -689,6 +843,19 @@ impl Renderer {
         mesh.index_buffer = Some(index_buffer);
     }
 
+    /// Rewrite an uploaded mesh's vertex buffer in place
+    ///
+    /// The vertex count must not have changed since the upload; use
+    /// `upload_mesh` otherwise.
+    pub fn update_mesh_vertices(&self, mesh: &Mesh) {
+        if let Some(buffer) = &mesh.vertex_buffer
+            && buffer.size() == std::mem::size_of_val(mesh.vertices.as_slice()) as u64
+        {
+            self.queue
+                .write_buffer(buffer, 0, bytemuck::cast_slice(&mesh.vertices));
+        }
+    }
+
     /// Create a model bind group for rendering
     pub fn create_model_bind_group(&self, transform: Mat4) -> (wgpu::Buffer, wgpu::BindGroup) {
         let uniform = ModelUniform::from_transform(transform);
@@ -719,6 +886,135 @@ impl Renderer {
             .write_buffer(buffer, 0, bytemuck::cast_slice(&[uniform]));
     }
 
+    /// Render a mesh offscreen into a square RGBA8 image framed on its bounds
+    ///
+    /// Used for editor thumbnails. Blocks until the GPU finishes. Returns
+    /// `None` if the mesh is not uploaded or has no bounds.
+    pub fn render_thumbnail(&self, mesh: &Mesh, size: u32) -> Option<Vec<u8>> {
+        let (min, max) = mesh.bounds()?;
+        if !mesh.is_uploaded() || size == 0 {
+            return None;
+        }
+
+        // Frame the bounding sphere from a three-quarter view
+        let center = (min + max) * 0.5;
+        let radius = ((max - min).length() * 0.5).max(0.001);
+        let fov = std::f32::consts::FRAC_PI_4;
+        let distance = radius / (fov * 0.5).sin();
+        let eye = center + Vec3::new(1.0, 0.8, 1.0).normalize() * distance;
+        let mut camera = Camera::look_at(eye, center, Vec3::Y);
+        camera.fov = fov;
+        camera.aspect = 1.0;
+        camera.near = (distance - radius).max(0.001);
+        camera.far = distance + radius * 2.0;
+        let mut uniform = CameraUniform::new();
+        uniform.update(&camera);
+        self.queue
+            .write_buffer(&self.camera_buffer, 0, bytemuck::cast_slice(&[uniform]));
+
+        let extent = wgpu::Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        };
+        let color = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Thumbnail Color"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let color_view = color.create_view(&wgpu::TextureViewDescriptor::default());
+        let (_depth, depth_view) = Self::create_depth_texture(&self.device, size, size);
+        let (_model_buffer, model_bind_group) = self.create_model_bind_group(Mat4::IDENTITY);
+
+        let bytes_per_row = (size * 4).div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Thumbnail Readback"),
+            size: u64::from(bytes_per_row * size),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Thumbnail Encoder"),
+            });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Thumbnail Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &color_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            self.draw_mesh(&mut render_pass, mesh, &model_bind_group);
+        }
+        encoder.copy_texture_to_buffer(
+            color.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &readback,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(size),
+                },
+            },
+            extent,
+        );
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        // Restore the scene camera for the next frame
+        self.queue.write_buffer(
+            &self.camera_buffer,
+            0,
+            bytemuck::cast_slice(&[self.camera_uniform]),
+        );
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        self.device.poll(wgpu::Maintain::Wait);
+
+        let swap_red_blue = matches!(
+            self.config.format,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+        );
+        let mut rgba = Vec::with_capacity((size * size * 4) as usize);
+        {
+            let data = slice.get_mapped_range();
+            for row in data.chunks(bytes_per_row as usize) {
+                for pixel in row[..(size * 4) as usize].chunks_exact(4) {
+                    if swap_red_blue {
+                        rgba.extend_from_slice(&[pixel[2], pixel[1], pixel[0], pixel[3]]);
+                    } else {
+                        rgba.extend_from_slice(pixel);
+                    }
+                }
+            }
+        }
+        readback.unmap();
+        Some(rgba)
+    }
+
     /// Begin a render frame
     pub fn begin_frame(&self) -> Option<RenderFrame> {
         let output = match self.surface.get_current_texture() {
@@ -754,6 +1050,24 @@ impl Renderer {
     pub fn end_frame(&self, frame: RenderFrame) {
         self.queue.submit(std::iter::once(frame.encoder.finish()));
         frame.output.present();
+        if let Ok(mut queue) = self.deletion_queue.lock() {
+            queue.end_frame();
+        }
+    }
+
+    /// Free a GPU resource once no in-flight frame can still reference it
+    ///
+    /// Use this instead of dropping buffers, textures, bind groups, or
+    /// asset handles that may have been recorded into the current frame.
+    pub fn destroy_later(&self, resource: impl Into<GpuResource>) {
+        if let Ok(mut queue) = self.deletion_queue.lock() {
+            queue.push(resource);
+        }
+    }
+
+    /// Get the number of resources waiting for deferred destruction
+    pub fn pending_destructions(&self) -> usize {
+        self.deletion_queue.lock().map_or(0, |queue| queue.len())
     }
 
     /// Create a render pass
@@ -816,6 +1130,28 @@ impl Renderer {
         &self.queue
     }
 
+    /// Get the surface size in pixels
+    #[must_use]
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Get the format of the surface textures
+    #[must_use]
+    pub fn surface_format(&self) -> wgpu::TextureFormat {
+        self.config.format
+    }
+
+    /// Get the GPU memory budget for streamed resources
+    pub fn memory_budget(&self) -> &GpuMemoryBudget {
+        &self.memory_budget
+    }
+
+    /// Get the mutable GPU memory budget for streamed resources
+    pub fn memory_budget_mut(&mut self) -> &mut GpuMemoryBudget {
+        &mut self.memory_budget
+    }
+
     /// Draw particles
     pub fn draw_particles<'a>(
         &'a self,
@@ -838,6 +1174,26 @@ impl Renderer {
         render_pass.draw(0..6, 0..emitter.particle_count() as u32);
     }
 
+    /// Draw queued debug lines
+    pub fn draw_debug<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>, draw: &DebugDraw) {
+        if draw.is_empty() {
+            return;
+        }
+
+        let buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Temp Debug Line Buffer"),
+                contents: bytemuck::cast_slice(draw.vertices()),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+
+        render_pass.set_pipeline(&self.debug_line_pipeline);
+        render_pass.set_bind_group(0, &self.global_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, buffer.slice(..));
+        render_pass.draw(0..draw.vertices().len() as u32, 0..1);
+    }
+
     /// Draw UI rectangles
     pub fn draw_ui<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>, rects: &[UiRect]) {
         if rects.is_empty() {
@@ -859,6 +1215,80 @@ impl Renderer {
         // Draw 6 vertices per instance
         render_pass.draw(0..6, 0..rects.len() as u32);
     }
+
+    /// Draw glyph quads from `layout_text` using a font's atlas texture
+    pub fn draw_text<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        font: &Font,
+        atlas: &Texture,
+        glyphs: &[GlyphInstance],
+        style: &TextStyle,
+    ) {
+        self.draw_glyph_quads(render_pass, atlas, glyphs, style.uniform(font));
+    }
+
+    /// Draw full-color textured quads, such as rich text icons or UI images
+    pub fn draw_icons<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        atlas: &Texture,
+        icons: &[GlyphInstance],
+    ) {
+        self.draw_glyph_quads(render_pass, atlas, icons, TextStyleUniform::image());
+    }
+
+    /// Draw quads with the text pipeline
+    fn draw_glyph_quads<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        atlas: &Texture,
+        glyphs: &[GlyphInstance],
+        uniform: TextStyleUniform,
+    ) {
+        if glyphs.is_empty() {
+            return;
+        }
+
+        let style_buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Temp Text Style Buffer"),
+                contents: bytemuck::cast_slice(&[uniform]),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Text Bind Group"),
+            layout: &self.text_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&atlas.view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&atlas.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: style_buffer.as_entire_binding(),
+                },
+            ],
+        });
+        let buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Temp Text Buffer"),
+                contents: bytemuck::cast_slice(glyphs),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+
+        render_pass.set_pipeline(&self.text_pipeline);
+        render_pass.set_bind_group(0, &self.ui_screen_size_bind_group, &[]);
+        render_pass.set_bind_group(1, &bind_group, &[]);
+        render_pass.set_vertex_buffer(0, buffer.slice(..));
+        render_pass.draw(0..6, 0..glyphs.len() as u32);
+    }
 }
 
 /// UI Rect for rendering
@@ -876,3 +1306,11 @@ pub struct RenderFrame {
     view: wgpu::TextureView,
     encoder: wgpu::CommandEncoder,
 }
+
+impl RenderFrame {
+    /// Get the encoder and the surface view, for extra passes over the frame
+    #[cfg(feature = "egui")]
+    pub(crate) fn target(&mut self) -> (&mut wgpu::CommandEncoder, &wgpu::TextureView) {
+        (&mut self.encoder, &self.view)
+    }
+}
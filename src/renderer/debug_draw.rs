@@ -0,0 +1,217 @@
+//! Debug line drawing
+//!
+//! Immediate-mode wireframe primitives collected on the CPU each frame and
+//! drawn as a line list on top of the scene.
+
+use bytemuck::{Pod, Zeroable};
+use glam::{Quat, Vec3, Vec4};
+
+/// Segments used to approximate circles
+const CIRCLE_SEGMENTS: usize = 16;
+
+/// A single debug line vertex
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+pub struct DebugVertex {
+    /// World position
+    pub position: [f32; 3],
+    /// Color (RGBA)
+    pub color: [f32; 4],
+}
+
+/// Collects debug lines for a single frame
+#[derive(Debug, Clone)]
+pub struct DebugDraw {
+    vertices: Vec<DebugVertex>,
+    enabled: bool,
+}
+
+impl DebugDraw {
+    /// Create an empty, enabled debug drawer
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            vertices: Vec::new(),
+            enabled: true,
+        }
+    }
+
+    /// Check whether drawing is enabled
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enable or disable drawing; disabling also clears queued lines
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.vertices.clear();
+        }
+    }
+
+    /// Flip the enabled state and return the new value
+    pub fn toggle(&mut self) -> bool {
+        self.set_enabled(!self.enabled);
+        self.enabled
+    }
+
+    /// Remove all queued lines
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+    }
+
+    /// Queued vertices, two per line
+    #[must_use]
+    pub fn vertices(&self) -> &[DebugVertex] {
+        &self.vertices
+    }
+
+    /// Number of queued lines
+    #[must_use]
+    pub fn line_count(&self) -> usize {
+        self.vertices.len() / 2
+    }
+
+    /// Check if no lines are queued
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Draw a line segment
+    pub fn line(&mut self, start: Vec3, end: Vec3, color: Vec4) {
+        if !self.enabled {
+            return;
+        }
+        let color = color.to_array();
+        self.vertices.push(DebugVertex {
+            position: start.to_array(),
+            color,
+        });
+        self.vertices.push(DebugVertex {
+            position: end.to_array(),
+            color,
+        });
+    }
+
+    /// Draw a ray from `origin` along `direction` (not normalized)
+    pub fn ray(&mut self, origin: Vec3, direction: Vec3, color: Vec4) {
+        self.line(origin, origin + direction, color);
+    }
+
+    /// Draw a small axis-aligned cross marking a point
+    pub fn point(&mut self, position: Vec3, size: f32, color: Vec4) {
+        let h = size * 0.5;
+        self.line(position - Vec3::X * h, position + Vec3::X * h, color);
+        self.line(position - Vec3::Y * h, position + Vec3::Y * h, color);
+        self.line(position - Vec3::Z * h, position + Vec3::Z * h, color);
+    }
+
+    /// Draw an axis-aligned box
+    pub fn aabb(&mut self, min: Vec3, max: Vec3, color: Vec4) {
+        let center = (min + max) * 0.5;
+        self.cuboid(center, Quat::IDENTITY, (max - min) * 0.5, color);
+    }
+
+    /// Draw an oriented box
+    pub fn cuboid(&mut self, center: Vec3, rotation: Quat, half_extents: Vec3, color: Vec4) {
+        let corner = |i: usize| {
+            let sign = Vec3::new(
+                if i & 1 == 0 { -1.0 } else { 1.0 },
+                if i & 2 == 0 { -1.0 } else { 1.0 },
+                if i & 4 == 0 { -1.0 } else { 1.0 },
+            );
+            center + rotation * (sign * half_extents)
+        };
+        for i in 0..8 {
+            for bit in [1, 2, 4] {
+                if i & bit == 0 {
+                    self.line(corner(i), corner(i | bit), color);
+                }
+            }
+        }
+    }
+
+    /// Draw a circle around `normal`
+    pub fn circle(&mut self, center: Vec3, normal: Vec3, radius: f32, color: Vec4) {
+        let normal = normal.try_normalize().unwrap_or(Vec3::Y);
+        let (u, v) = normal.any_orthonormal_pair();
+        let at = |i: usize| {
+            let angle = i as f32 / CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+            center + (u * angle.cos() + v * angle.sin()) * radius
+        };
+        for i in 0..CIRCLE_SEGMENTS {
+            self.line(at(i), at(i + 1), color);
+        }
+    }
+
+    /// Draw a sphere as three orthogonal circles
+    pub fn sphere(&mut self, center: Vec3, rotation: Quat, radius: f32, color: Vec4) {
+        self.circle(center, rotation * Vec3::X, radius, color);
+        self.circle(center, rotation * Vec3::Y, radius, color);
+        self.circle(center, rotation * Vec3::Z, radius, color);
+    }
+
+    /// Draw a capsule between two segment end points
+    pub fn capsule(&mut self, a: Vec3, b: Vec3, radius: f32, color: Vec4) {
+        let axis = (b - a).try_normalize().unwrap_or(Vec3::Y);
+        let (u, v) = axis.any_orthonormal_pair();
+        self.sphere(a, Quat::from_rotation_arc(Vec3::Y, axis), radius, color);
+        self.sphere(b, Quat::from_rotation_arc(Vec3::Y, axis), radius, color);
+        for side in [u, -u, v, -v] {
+            self.line(a + side * radius, b + side * radius, color);
+        }
+    }
+
+    /// Draw a line with a small arrow head at `end`
+    pub fn arrow(&mut self, start: Vec3, end: Vec3, color: Vec4) {
+        self.line(start, end, color);
+        let delta = end - start;
+        let length = delta.length();
+        if length <= f32::EPSILON {
+            return;
+        }
+        let dir = delta / length;
+        let (u, v) = dir.any_orthonormal_pair();
+        let head = length * 0.2;
+        for side in [u, -u, v, -v] {
+            self.line(end, end - dir * head + side * head * 0.5, color);
+        }
+    }
+}
+
+impl Default for DebugDraw {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitives_emit_lines() {
+        let mut draw = DebugDraw::new();
+        draw.line(Vec3::ZERO, Vec3::X, Vec4::ONE);
+        assert_eq!(draw.line_count(), 1);
+
+        draw.aabb(Vec3::ZERO, Vec3::ONE, Vec4::ONE);
+        assert_eq!(draw.line_count(), 13);
+
+        draw.clear();
+        assert!(draw.is_empty());
+    }
+
+    #[test]
+    fn test_disabled_draw_ignores_lines() {
+        let mut draw = DebugDraw::new();
+        draw.point(Vec3::ZERO, 1.0, Vec4::ONE);
+        assert!(!draw.toggle());
+        assert!(draw.is_empty());
+
+        draw.sphere(Vec3::ZERO, Quat::IDENTITY, 1.0, Vec4::ONE);
+        assert!(draw.is_empty());
+    }
+}
@@ -0,0 +1,160 @@
+//! Deferred GPU resource destruction
+//!
+//! Holds resources that may still be referenced by in-flight frames and
+//! frees them once enough frames have been submitted.
+
+use std::any::Any;
+use std::collections::VecDeque;
+
+use super::mesh::Mesh;
+use super::texture::Texture;
+
+/// A GPU resource waiting to be freed
+pub enum GpuResource {
+    /// A buffer, destroyed explicitly when released
+    Buffer(wgpu::Buffer),
+    /// A texture, destroyed explicitly when released
+    Texture(wgpu::Texture),
+    /// A bind group
+    BindGroup(wgpu::BindGroup),
+    /// Any other value kept alive until it is safe to drop (e.g. an asset handle)
+    Retained(Box<dyn Any + Send + Sync>),
+}
+
+impl GpuResource {
+    /// Keep an arbitrary value alive until the GPU is done with it
+    #[must_use]
+    pub fn retain(value: impl Any + Send + Sync) -> Self {
+        Self::Retained(Box::new(value))
+    }
+
+    /// Free the resource
+    fn release(self) {
+        match self {
+            Self::Buffer(buffer) => buffer.destroy(),
+            Self::Texture(texture) => texture.destroy(),
+            Self::BindGroup(_) | Self::Retained(_) => {}
+        }
+    }
+}
+
+impl std::fmt::Debug for GpuResource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Buffer(_) => write!(f, "GpuResource::Buffer"),
+            Self::Texture(_) => write!(f, "GpuResource::Texture"),
+            Self::BindGroup(_) => write!(f, "GpuResource::BindGroup"),
+            Self::Retained(_) => write!(f, "GpuResource::Retained"),
+        }
+    }
+}
+
+impl From<wgpu::Buffer> for GpuResource {
+    fn from(buffer: wgpu::Buffer) -> Self {
+        Self::Buffer(buffer)
+    }
+}
+
+impl From<wgpu::Texture> for GpuResource {
+    fn from(texture: wgpu::Texture) -> Self {
+        Self::Texture(texture)
+    }
+}
+
+impl From<wgpu::BindGroup> for GpuResource {
+    fn from(bind_group: wgpu::BindGroup) -> Self {
+        Self::BindGroup(bind_group)
+    }
+}
+
+impl From<Texture> for GpuResource {
+    fn from(texture: Texture) -> Self {
+        Self::Texture(texture.texture)
+    }
+}
+
+impl From<Mesh> for GpuResource {
+    fn from(mesh: Mesh) -> Self {
+        Self::retain(mesh)
+    }
+}
+
+/// Queue of resources released after a fixed number of frames
+#[derive(Debug)]
+pub struct DeletionQueue {
+    /// Frames submitted so far
+    frame: u64,
+    /// Frames a resource must wait before it is freed
+    frames_in_flight: u64,
+    /// Resources tagged with the frame they were queued in
+    pending: VecDeque<(u64, GpuResource)>,
+}
+
+impl DeletionQueue {
+    /// Create a queue that waits `frames_in_flight` frames before freeing
+    #[must_use]
+    pub fn new(frames_in_flight: u32) -> Self {
+        Self {
+            frame: 0,
+            frames_in_flight: u64::from(frames_in_flight),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Queue a resource for destruction
+    pub fn push(&mut self, resource: impl Into<GpuResource>) {
+        self.pending.push_back((self.frame, resource.into()));
+    }
+
+    /// Advance one frame and free resources that are old enough
+    ///
+    /// Returns the number of resources freed.
+    pub fn end_frame(&mut self) -> usize {
+        self.frame += 1;
+        let mut freed = 0;
+        while let Some((queued, _)) = self.pending.front()
+            && self.frame - queued > self.frames_in_flight
+        {
+            if let Some((_, resource)) = self.pending.pop_front() {
+                resource.release();
+                freed += 1;
+            }
+        }
+        freed
+    }
+
+    /// Free everything immediately (e.g. after waiting for the device to idle)
+    pub fn flush(&mut self) {
+        for (_, resource) in self.pending.drain(..) {
+            resource.release();
+        }
+    }
+
+    /// Get the number of resources waiting to be freed
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Check if nothing is waiting to be freed
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resources_wait_for_frames_in_flight() {
+        let mut queue = DeletionQueue::new(2);
+        queue.push(GpuResource::retain(42_u32));
+
+        assert_eq!(queue.end_frame(), 0);
+        assert_eq!(queue.end_frame(), 0);
+        assert_eq!(queue.end_frame(), 1);
+        assert!(queue.is_empty());
+    }
+}
@@ -189,11 +189,89 @@ impl Mesh {
         Self::from_data(vertices, indices)
     }
 
+    /// Create a terrain mesh from a grid of heights
+    ///
+    /// `heights` is row-major with `rows` along Z and `columns` along X. The
+    /// grid is centered at the origin and spans `size.x` by `size.z`, with
+    /// heights multiplied by `size.y`, matching `Physics::add_heightfield_collider`.
+    /// Returns an empty mesh if the grid is smaller than 2x2 or `heights` has
+    /// the wrong length.
+    pub fn heightfield(heights: &[f32], columns: usize, rows: usize, size: Vec3) -> Self {
+        if columns < 2 || rows < 2 || heights.len() != columns * rows {
+            return Self::new();
+        }
+
+        let dx = size.x / (columns - 1) as f32;
+        let dz = size.z / (rows - 1) as f32;
+        let height = |c: usize, r: usize| heights[r * columns + c] * size.y;
+
+        let mut vertices = Vec::with_capacity(columns * rows);
+        for r in 0..rows {
+            for c in 0..columns {
+                let u = c as f32 / (columns - 1) as f32;
+                let v = r as f32 / (rows - 1) as f32;
+                // Central differences, one-sided at the edges
+                let (left, right) = (c.saturating_sub(1), (c + 1).min(columns - 1));
+                let (back, front) = (r.saturating_sub(1), (r + 1).min(rows - 1));
+                let slope_x = (height(right, r) - height(left, r)) / ((right - left) as f32 * dx);
+                let slope_z = (height(c, front) - height(c, back)) / ((front - back) as f32 * dz);
+                let normal = Vec3::new(-slope_x, 1.0, -slope_z).normalize();
+
+                vertices.push(Vertex::new(
+                    [(u - 0.5) * size.x, height(c, r), (v - 0.5) * size.z],
+                    normal.into(),
+                    [u, v],
+                ));
+            }
+        }
+
+        let mut indices = Vec::with_capacity((columns - 1) * (rows - 1) * 6);
+        for r in 0..rows - 1 {
+            for c in 0..columns - 1 {
+                let current = (r * columns + c) as u32;
+                let below = current + columns as u32;
+
+                indices.push(current);
+                indices.push(below);
+                indices.push(current + 1);
+
+                indices.push(current + 1);
+                indices.push(below);
+                indices.push(below + 1);
+            }
+        }
+
+        Self::from_data(vertices, indices)
+    }
+
     /// Get the number of indices
     pub fn index_count(&self) -> u32 {
         self.indices.len() as u32
     }
 
+    /// Compute the axis-aligned bounding box as (min, max)
+    ///
+    /// Returns `None` if the mesh has no vertices or contains non-finite positions.
+    pub fn bounds(&self) -> Option<(Vec3, Vec3)> {
+        let mut min = Vec3::splat(f32::MAX);
+        let mut max = Vec3::splat(f32::MIN);
+
+        for vertex in &self.vertices {
+            let position = Vec3::from_array(vertex.position);
+            if !position.is_finite() {
+                return None;
+            }
+            min = min.min(position);
+            max = max.max(position);
+        }
+
+        if self.vertices.is_empty() {
+            None
+        } else {
+            Some((min, max))
+        }
+    }
+
     /// Check if the mesh has been uploaded to GPU
     pub fn is_uploaded(&self) -> bool {
         self.vertex_buffer.is_some() && self.index_buffer.is_some()
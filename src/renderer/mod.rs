@@ -2,24 +2,44 @@
 //!
 //! 3D rendering with wgpu
 
+mod budget;
 mod camera;
 mod context;
+mod debug_draw;
+mod deferred;
 mod lights;
 mod material;
 mod mesh;
+mod morph;
 mod particles;
 mod postprocess;
+mod rich_text;
 mod shadow;
 mod skybox;
+mod text;
 mod texture;
+mod virtual_texture;
 
+pub use budget::GpuMemoryBudget;
 pub use camera::Camera;
 pub use context::{Light, ModelUniform, RenderFrame, Renderer, UiRect};
+pub use debug_draw::{DebugDraw, DebugVertex};
+pub use deferred::{DeletionQueue, GpuResource};
 pub use lights::{DirectionalLight, GpuLight, LightManager, LightStorage, PointLight, SpotLight};
 pub use material::{Material, MaterialUniform};
 pub use mesh::{Mesh, Vertex};
+pub use morph::{MorphTarget, MorphTargets, apply_morph_animation};
 pub use particles::{EmitterConfig, Particle, ParticleEmitter};
 pub use postprocess::{FullscreenQuad, PostProcessConfig, PostProcessUniform, RenderTarget};
+pub use rich_text::{
+    RichTextFonts, RichTextLayout, TextSpan, UiAtlas, layout_rich_text, measure_rich_text,
+    parse_color, parse_rich_text,
+};
 pub use shadow::{ShadowConfig, ShadowMap, ShadowUniform};
 pub use skybox::{GradientSky, GradientSkyUniform, Skybox, SkyboxUniform};
+pub use text::{GlyphInstance, TextStyle, TextStyleUniform, layout_text};
 pub use texture::{Texture, TextureError};
+pub use virtual_texture::{
+    FEEDBACK_EMPTY, PageId, PageLoader, VIRTUAL_TEXTURE_WGSL, VirtualTexture, VirtualTextureConfig,
+    VirtualTextureGpu,
+};
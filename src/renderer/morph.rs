@@ -0,0 +1,198 @@
+//! Morph targets (blend shapes)
+//!
+//! Blends per-vertex position and normal offsets into a mesh on the CPU
+//! and rewrites its vertex buffer, e.g. for facial animation.
+
+use glam::Vec3;
+
+use super::Renderer;
+use super::mesh::{Mesh, Vertex};
+use crate::animation::AnimationPlayer;
+use crate::ecs::World;
+
+/// Weights below this are treated as zero
+const WEIGHT_EPSILON: f32 = 1e-4;
+
+/// Per-vertex offsets of one morph target
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MorphTarget {
+    /// Position offset per vertex
+    pub positions: Vec<[f32; 3]>,
+    /// Normal offset per vertex (empty when the target has none)
+    pub normals: Vec<[f32; 3]>,
+}
+
+/// Component holding a mesh's morph targets and current weights
+///
+/// Sits next to the `Mesh` it deforms. The mesh's vertices are rebuilt
+/// from the undeformed base whenever the weights change.
+#[derive(Debug, Clone)]
+pub struct MorphTargets {
+    /// Undeformed vertices
+    base: Vec<Vertex>,
+    /// Targets, in weight order
+    targets: Vec<MorphTarget>,
+    /// Current weight per target
+    weights: Vec<f32>,
+    /// Animation channel target (glTF node index) driving the weights
+    pub animation_target: Option<usize>,
+    /// Whether weights changed since the mesh was last rebuilt
+    dirty: bool,
+}
+
+impl MorphTargets {
+    /// Create morph targets over a mesh's undeformed vertices
+    #[must_use]
+    pub fn new(base: Vec<Vertex>, targets: Vec<MorphTarget>) -> Self {
+        let weights = vec![0.0; targets.len()];
+        Self {
+            base,
+            targets,
+            weights,
+            animation_target: None,
+            dirty: true,
+        }
+    }
+
+    /// Set default weights
+    #[must_use]
+    pub fn with_weights(mut self, weights: &[f32]) -> Self {
+        self.set_weights(weights);
+        self
+    }
+
+    /// Take weights from the animation channel targeting this node
+    #[must_use]
+    pub const fn with_animation_target(mut self, target: usize) -> Self {
+        self.animation_target = Some(target);
+        self
+    }
+
+    /// Get the number of targets
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Check if there are no targets
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Get the targets
+    #[must_use]
+    pub fn targets(&self) -> &[MorphTarget] {
+        &self.targets
+    }
+
+    /// Get the current weights
+    #[must_use]
+    pub fn weights(&self) -> &[f32] {
+        &self.weights
+    }
+
+    /// Set weights; extra values are ignored and missing ones stay as they are
+    pub fn set_weights(&mut self, weights: &[f32]) {
+        for (current, &weight) in self.weights.iter_mut().zip(weights) {
+            if (*current - weight).abs() > f32::EPSILON {
+                *current = weight;
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Set a single weight
+    pub fn set_weight(&mut self, index: usize, weight: f32) {
+        if let Some(current) = self.weights.get_mut(index)
+            && (*current - weight).abs() > f32::EPSILON
+        {
+            *current = weight;
+            self.dirty = true;
+        }
+    }
+
+    /// Compute the deformed vertices for the current weights
+    #[must_use]
+    pub fn blend(&self) -> Vec<Vertex> {
+        let mut vertices = self.base.clone();
+        for (target, &weight) in self.targets.iter().zip(&self.weights) {
+            if weight.abs() < WEIGHT_EPSILON {
+                continue;
+            }
+            for (vertex, offset) in vertices.iter_mut().zip(&target.positions) {
+                let position =
+                    Vec3::from_array(vertex.position) + Vec3::from_array(*offset) * weight;
+                vertex.position = position.to_array();
+            }
+            for (vertex, offset) in vertices.iter_mut().zip(&target.normals) {
+                let normal = Vec3::from_array(vertex.normal) + Vec3::from_array(*offset) * weight;
+                vertex.normal = normal.to_array();
+            }
+        }
+        if self.targets.iter().any(|t| !t.normals.is_empty()) {
+            for vertex in &mut vertices {
+                vertex.normal = Vec3::from_array(vertex.normal)
+                    .normalize_or_zero()
+                    .to_array();
+            }
+        }
+        vertices
+    }
+
+    /// Rewrite the mesh's vertices if the weights changed
+    ///
+    /// Returns `true` if the mesh was modified and needs re-uploading.
+    pub fn apply(&mut self, mesh: &mut Mesh) -> bool {
+        if !self.dirty || mesh.vertices.len() != self.base.len() {
+            return false;
+        }
+        mesh.vertices = self.blend();
+        self.dirty = false;
+        true
+    }
+}
+
+/// Forward animated morph weights to meshes and re-upload changed ones
+///
+/// Entities with an `AnimationPlayer`, `MorphTargets` and `Mesh` take the
+/// weights of the clip channel matching `MorphTargets::animation_target`
+/// (any morph channel when unset). Call after updating animation.
+pub fn apply_morph_animation(world: &mut World, renderer: &Renderer) {
+    for (_, (player, morph, mesh)) in
+        world.query_mut::<(Option<&AnimationPlayer>, &mut MorphTargets, &mut Mesh)>()
+    {
+        if let Some(player) = player
+            && let Some(weights) = player.sample_morph_weights(morph.animation_target)
+        {
+            morph.set_weights(&weights);
+        }
+        if morph.apply(mesh) {
+            renderer.update_mesh_vertices(mesh);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blend_targets() {
+        let base = vec![Vertex::new([0.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0]); 2];
+        let smile = MorphTarget {
+            positions: vec![[1.0, 0.0, 0.0], [0.0, 2.0, 0.0]],
+            normals: Vec::new(),
+        };
+        let mut morph = MorphTargets::new(base.clone(), vec![smile]);
+        let mut mesh = Mesh::from_data(base, vec![0, 1, 0]);
+
+        assert!(morph.apply(&mut mesh));
+        assert!(!morph.apply(&mut mesh));
+
+        morph.set_weights(&[0.5, 9.0]);
+        assert!(morph.apply(&mut mesh));
+        assert_eq!(mesh.vertices[0].position, [0.5, 0.0, 0.0]);
+        assert_eq!(mesh.vertices[1].position, [0.0, 1.0, 0.0]);
+    }
+}
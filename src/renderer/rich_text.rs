@@ -0,0 +1,564 @@
+//! Rich text markup
+//!
+//! Parses a small markup language and lays it out with word wrapping:
+//! `[color=#rrggbb]..[/color]` (or a color name), `[b]..[/b]` for the bold
+//! face, `[icon=name]` for an image from the UI atlas, and `[br]` or `\n`
+//! for line breaks. `[[` writes a literal bracket and unknown tags are kept
+//! as text.
+
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use super::text::{GlyphInstance, TextStyle, glyph_quad};
+use crate::assets::Font;
+
+/// A run of uniformly styled content
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextSpan {
+    /// Text with an optional color override
+    Text {
+        /// Text content
+        text: String,
+        /// Color override (RGBA), `None` uses the style color
+        color: Option<[f32; 4]>,
+        /// Whether to use the bold face
+        bold: bool,
+    },
+    /// Inline image from the UI atlas
+    Icon(String),
+    /// Forced line break
+    LineBreak,
+}
+
+/// A markup tag
+enum Tag {
+    Bold,
+    EndBold,
+    Color([f32; 4]),
+    EndColor,
+    Icon(String),
+    Break,
+}
+
+impl Tag {
+    fn parse(tag: &str) -> Option<Self> {
+        match tag {
+            "b" => Some(Self::Bold),
+            "/b" => Some(Self::EndBold),
+            "/color" => Some(Self::EndColor),
+            "br" => Some(Self::Break),
+            _ => {
+                if let Some(color) = tag.strip_prefix("color=") {
+                    parse_color(color).map(Self::Color)
+                } else {
+                    tag.strip_prefix("icon=")
+                        .filter(|name| !name.is_empty())
+                        .map(|name| Self::Icon(name.to_string()))
+                }
+            }
+        }
+    }
+}
+
+/// Parse a `#rrggbb`/`#rrggbbaa` hex color or a basic color name
+#[must_use]
+pub fn parse_color(value: &str) -> Option<[f32; 4]> {
+    let named = match value {
+        "white" => Some([1.0, 1.0, 1.0, 1.0]),
+        "black" => Some([0.0, 0.0, 0.0, 1.0]),
+        "red" => Some([1.0, 0.2, 0.2, 1.0]),
+        "green" => Some([0.2, 1.0, 0.2, 1.0]),
+        "blue" => Some([0.3, 0.5, 1.0, 1.0]),
+        "yellow" => Some([1.0, 0.9, 0.2, 1.0]),
+        "gray" => Some([0.6, 0.6, 0.6, 1.0]),
+        _ => None,
+    };
+    if named.is_some() {
+        return named;
+    }
+
+    let hex = value.strip_prefix('#')?;
+    if !(hex.len() == 6 || hex.len() == 8) || !hex.is_ascii() {
+        return None;
+    }
+    let mut color = [1.0; 4];
+    for (i, channel) in color.iter_mut().enumerate().take(hex.len() / 2) {
+        let byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+        *channel = f32::from(byte) / 255.0;
+    }
+    Some(color)
+}
+
+/// Parse markup into spans
+#[must_use]
+pub fn parse_rich_text(markup: &str) -> Vec<TextSpan> {
+    fn flush(spans: &mut Vec<TextSpan>, text: &mut String, colors: &[[f32; 4]], bold: u32) {
+        if !text.is_empty() {
+            spans.push(TextSpan::Text {
+                text: std::mem::take(text),
+                color: colors.last().copied(),
+                bold: bold > 0,
+            });
+        }
+    }
+
+    let mut spans = Vec::new();
+    let mut text = String::new();
+    let mut colors: Vec<[f32; 4]> = Vec::new();
+    let mut bold = 0u32;
+    let mut rest = markup;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix("[[") {
+            text.push('[');
+            rest = after;
+            continue;
+        }
+        if c == '['
+            && let Some(end) = rest.find(']')
+            && let Some(tag) = Tag::parse(&rest[1..end])
+        {
+            flush(&mut spans, &mut text, &colors, bold);
+            match tag {
+                Tag::Bold => bold += 1,
+                Tag::EndBold => bold = bold.saturating_sub(1),
+                Tag::Color(color) => colors.push(color),
+                Tag::EndColor => {
+                    colors.pop();
+                }
+                Tag::Icon(name) => spans.push(TextSpan::Icon(name)),
+                Tag::Break => spans.push(TextSpan::LineBreak),
+            }
+            rest = &rest[end + 1..];
+            continue;
+        }
+        if c == '\n' {
+            flush(&mut spans, &mut text, &colors, bold);
+            spans.push(TextSpan::LineBreak);
+        } else {
+            text.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    flush(&mut spans, &mut text, &colors, bold);
+
+    spans
+}
+
+/// Named image regions in the UI atlas texture
+#[derive(Debug, Clone, Default)]
+pub struct UiAtlas {
+    /// Atlas width in pixels
+    pub width: u32,
+    /// Atlas height in pixels
+    pub height: u32,
+    /// Regions as (x, y, width, height) in pixels
+    regions: HashMap<String, [u32; 4]>,
+}
+
+impl UiAtlas {
+    /// Create an empty atlas description
+    #[must_use]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            regions: HashMap::new(),
+        }
+    }
+
+    /// Add a named region
+    #[must_use]
+    pub fn with_region(mut self, name: impl Into<String>, region: [u32; 4]) -> Self {
+        self.add_region(name, region);
+        self
+    }
+
+    /// Add or replace a named region
+    pub fn add_region(&mut self, name: impl Into<String>, region: [u32; 4]) {
+        self.regions.insert(name.into(), region);
+    }
+
+    /// Get a region in pixels
+    #[must_use]
+    pub fn region(&self, name: &str) -> Option<[u32; 4]> {
+        self.regions.get(name).copied()
+    }
+
+    /// Get a region's UV rectangle (min, max)
+    #[must_use]
+    pub fn uv_rect(&self, name: &str) -> Option<(Vec2, Vec2)> {
+        let [x, y, w, h] = self.region(name)?;
+        let size = Vec2::new(self.width.max(1) as f32, self.height.max(1) as f32);
+        let min = Vec2::new(x as f32, y as f32) / size;
+        let max = Vec2::new((x + w) as f32, (y + h) as f32) / size;
+        Some((min, max))
+    }
+}
+
+/// Fonts and images used to lay out rich text
+#[derive(Debug, Clone, Copy)]
+pub struct RichTextFonts<'a> {
+    /// Regular face
+    pub regular: &'a Font,
+    /// Bold face (falls back to the regular face)
+    pub bold: Option<&'a Font>,
+    /// Atlas for inline icons
+    pub icons: Option<&'a UiAtlas>,
+}
+
+impl<'a> RichTextFonts<'a> {
+    /// Use a single face without icons
+    #[must_use]
+    pub const fn new(regular: &'a Font) -> Self {
+        Self {
+            regular,
+            bold: None,
+            icons: None,
+        }
+    }
+
+    /// Set the bold face
+    #[must_use]
+    pub const fn with_bold(mut self, bold: &'a Font) -> Self {
+        self.bold = Some(bold);
+        self
+    }
+
+    /// Set the icon atlas
+    #[must_use]
+    pub const fn with_icons(mut self, icons: &'a UiAtlas) -> Self {
+        self.icons = Some(icons);
+        self
+    }
+
+    /// Get the face for a span
+    fn face(&self, bold: bool) -> &'a Font {
+        if bold {
+            self.bold.unwrap_or(self.regular)
+        } else {
+            self.regular
+        }
+    }
+}
+
+/// Laid out rich text, split by the texture each quad samples
+///
+/// Draw `regular` and `bold` with `Renderer::draw_text` using their font
+/// atlases, and `icons` with `Renderer::draw_icons` using the UI atlas.
+#[derive(Debug, Clone, Default)]
+pub struct RichTextLayout {
+    /// Glyphs from the regular face
+    pub regular: Vec<GlyphInstance>,
+    /// Glyphs from the bold face
+    pub bold: Vec<GlyphInstance>,
+    /// Icon quads
+    pub icons: Vec<GlyphInstance>,
+    /// Size of the laid out text in pixels
+    pub size: Vec2,
+}
+
+/// One layout unit after flattening spans
+enum Atom<'s> {
+    Char {
+        c: char,
+        color: Option<[f32; 4]>,
+        bold: bool,
+    },
+    Icon(&'s str),
+    Break,
+}
+
+fn flatten(spans: &[TextSpan]) -> Vec<Atom<'_>> {
+    let mut atoms = Vec::new();
+    for span in spans {
+        match span {
+            TextSpan::Text { text, color, bold } => {
+                atoms.extend(text.chars().map(|c| Atom::Char {
+                    c,
+                    color: *color,
+                    bold: *bold,
+                }));
+            }
+            TextSpan::Icon(name) => atoms.push(Atom::Icon(name)),
+            TextSpan::LineBreak => atoms.push(Atom::Break),
+        }
+    }
+    atoms
+}
+
+/// Lays out atoms line by line
+struct Layouter<'a> {
+    fonts: RichTextFonts<'a>,
+    style: &'a TextStyle,
+    origin: Vec2,
+    max_width: Option<f32>,
+    line_height: f32,
+    pen_x: f32,
+    line: u32,
+    width: f32,
+    wrapped: bool,
+    output: Option<RichTextLayout>,
+}
+
+impl Layouter<'_> {
+    fn scale(&self, font: &Font) -> f32 {
+        self.style.size / font.px_size
+    }
+
+    fn icon_size(&self, name: &str) -> Option<Vec2> {
+        let [_, _, w, h] = self.fonts.icons?.region(name)?;
+        let height = self.style.size;
+        Some(Vec2::new(height * w as f32 / h.max(1) as f32, height))
+    }
+
+    fn atom_width(&self, atom: &Atom<'_>, prev: Option<(char, bool)>) -> f32 {
+        match atom {
+            Atom::Char { c, bold, .. } => {
+                let font = self.fonts.face(*bold);
+                let kerning = match prev {
+                    Some((p, prev_bold)) if prev_bold == *bold => font.kerning(p, *c),
+                    _ => 0.0,
+                };
+                (kerning + font.glyph(*c).map_or(0.0, |g| g.advance)) * self.scale(font)
+            }
+            Atom::Icon(name) => self.icon_size(name).map_or(0.0, |s| s.x),
+            Atom::Break => 0.0,
+        }
+    }
+
+    fn new_line(&mut self) {
+        self.width = self.width.max(self.pen_x);
+        self.pen_x = 0.0;
+        self.line += 1;
+        self.wrapped = false;
+    }
+
+    fn wrap(&mut self) {
+        self.new_line();
+        self.wrapped = true;
+    }
+
+    fn line_top(&self) -> f32 {
+        self.origin.y + self.line as f32 * self.line_height
+    }
+
+    fn emit(&mut self, atom: &Atom<'_>, prev: Option<(char, bool)>) {
+        let advance = self.atom_width(atom, prev);
+        let top = self.line_top();
+        if let Some(output) = self.output.as_mut() {
+            match atom {
+                Atom::Char { c, color, bold } => {
+                    let font = self.fonts.face(*bold);
+                    let scale = self.style.size / font.px_size;
+                    let kerning = match prev {
+                        Some((p, prev_bold)) if prev_bold == *bold => font.kerning(p, *c),
+                        _ => 0.0,
+                    };
+                    let pen = Vec2::new(
+                        self.origin.x + self.pen_x + kerning * scale,
+                        top + self.fonts.regular.ascent * self.style.size
+                            / self.fonts.regular.px_size,
+                    );
+                    let color = color.unwrap_or(self.style.color);
+                    if let Some(glyph) = font.glyph(*c)
+                        && let Some(quad) = glyph_quad(font, glyph, pen, scale, color)
+                    {
+                        if *bold && self.fonts.bold.is_some() {
+                            output.bold.push(quad);
+                        } else {
+                            output.regular.push(quad);
+                        }
+                    }
+                }
+                Atom::Icon(name) => {
+                    if let Some(atlas) = self.fonts.icons
+                        && let Some((uv_min, uv_max)) = atlas.uv_rect(name)
+                    {
+                        let size = Vec2::new(advance, self.style.size);
+                        let y = top + (self.line_height - size.y) * 0.5;
+                        output.icons.push(GlyphInstance {
+                            position: [self.origin.x + self.pen_x, y],
+                            size: size.into(),
+                            uv_min: uv_min.into(),
+                            uv_max: uv_max.into(),
+                            color: [1.0, 1.0, 1.0, self.style.color[3]],
+                        });
+                    }
+                }
+                Atom::Break => {}
+            }
+        }
+        self.pen_x += advance;
+    }
+
+    fn run(mut self, atoms: &[Atom<'_>]) -> RichTextLayout {
+        let mut i = 0;
+        let mut prev: Option<(char, bool)> = None;
+        while i < atoms.len() {
+            match &atoms[i] {
+                Atom::Break => {
+                    self.new_line();
+                    prev = None;
+                    i += 1;
+                }
+                Atom::Char { c, bold, .. } if c.is_whitespace() => {
+                    // Spaces never start a wrapped line
+                    if !(self.wrapped && self.pen_x == 0.0) {
+                        self.emit(&atoms[i], prev);
+                    }
+                    prev = Some((*c, *bold));
+                    i += 1;
+                }
+                Atom::Icon(_) => {
+                    let width = self.atom_width(&atoms[i], None);
+                    if self.wraps(width) {
+                        self.wrap();
+                    }
+                    self.emit(&atoms[i], None);
+                    prev = None;
+                    i += 1;
+                }
+                Atom::Char { .. } => {
+                    // A word runs until whitespace, an icon, or a break
+                    let end = atoms[i..]
+                        .iter()
+                        .position(|a| !matches!(a, Atom::Char { c, .. } if !c.is_whitespace()))
+                        .map_or(atoms.len(), |n| i + n);
+                    let mut width = 0.0;
+                    let mut word_prev = prev;
+                    for atom in &atoms[i..end] {
+                        width += self.atom_width(atom, word_prev);
+                        if let Atom::Char { c, bold, .. } = atom {
+                            word_prev = Some((*c, *bold));
+                        }
+                    }
+                    if self.wraps(width) {
+                        self.wrap();
+                        prev = None;
+                    }
+                    for atom in &atoms[i..end] {
+                        self.emit(atom, prev);
+                        if let Atom::Char { c, bold, .. } = atom {
+                            prev = Some((*c, *bold));
+                        }
+                    }
+                    i = end;
+                }
+            }
+        }
+
+        self.width = self.width.max(self.pen_x);
+        let mut output = self.output.take().unwrap_or_default();
+        let lines = if atoms.is_empty() { 0 } else { self.line + 1 };
+        output.size = Vec2::new(self.width, lines as f32 * self.line_height);
+        output
+    }
+
+    fn wraps(&self, width: f32) -> bool {
+        self.max_width
+            .is_some_and(|max| self.pen_x > 0.0 && self.pen_x + width > max)
+    }
+}
+
+fn layout(
+    spans: &[TextSpan],
+    fonts: RichTextFonts<'_>,
+    position: Vec2,
+    max_width: Option<f32>,
+    style: &TextStyle,
+    emit: bool,
+) -> RichTextLayout {
+    let line_height = fonts
+        .bold
+        .map_or(0.0, |bold| bold.line_height * style.size / bold.px_size)
+        .max(fonts.regular.line_height * style.size / fonts.regular.px_size);
+    let layouter = Layouter {
+        fonts,
+        style,
+        origin: position,
+        max_width,
+        line_height,
+        pen_x: 0.0,
+        line: 0,
+        width: 0.0,
+        wrapped: false,
+        output: emit.then(RichTextLayout::default),
+    };
+    layouter.run(&flatten(spans))
+}
+
+/// Lay out spans with their top-left corner at `position`, wrapping words
+/// at `max_width` pixels if given
+#[must_use]
+pub fn layout_rich_text(
+    spans: &[TextSpan],
+    fonts: RichTextFonts<'_>,
+    position: Vec2,
+    max_width: Option<f32>,
+    style: &TextStyle,
+) -> RichTextLayout {
+    layout(spans, fonts, position, max_width, style, true)
+}
+
+/// Measure spans without generating quads
+#[must_use]
+pub fn measure_rich_text(
+    spans: &[TextSpan],
+    fonts: RichTextFonts<'_>,
+    max_width: Option<f32>,
+    style: &TextStyle,
+) -> Vec2 {
+    layout(spans, fonts, Vec2::ZERO, max_width, style, false).size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_markup() {
+        let spans = parse_rich_text("Hi [b]bold [color=#ff0000]red[/color][/b][icon=coin]\nx");
+        assert_eq!(
+            spans,
+            vec![
+                TextSpan::Text {
+                    text: "Hi ".into(),
+                    color: None,
+                    bold: false
+                },
+                TextSpan::Text {
+                    text: "bold ".into(),
+                    color: None,
+                    bold: true
+                },
+                TextSpan::Text {
+                    text: "red".into(),
+                    color: Some([1.0, 0.0, 0.0, 1.0]),
+                    bold: true
+                },
+                TextSpan::Icon("coin".into()),
+                TextSpan::LineBreak,
+                TextSpan::Text {
+                    text: "x".into(),
+                    color: None,
+                    bold: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_tags_are_literal() {
+        let spans = parse_rich_text("[[b] [foo] [color=nope]");
+        assert_eq!(
+            spans,
+            vec![TextSpan::Text {
+                text: "[b] [foo] [color=nope]".into(),
+                color: None,
+                bold: false
+            }]
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! Text layout and styling
+//!
+//! Lays out strings into glyph quads for the text pipeline. Raster fonts
+//! draw coverage directly; SDF fonts stay sharp at any size and support
+//! outlines and soft drop shadows.
+
+use bytemuck::{Pod, Zeroable};
+use glam::Vec2;
+
+use crate::assets::{Font, Glyph, GlyphMode};
+
+/// One glyph quad in screen pixels
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+pub struct GlyphInstance {
+    /// Top-left corner in pixels
+    pub position: [f32; 2],
+    /// Size in pixels
+    pub size: [f32; 2],
+    /// Atlas UV of the top-left corner
+    pub uv_min: [f32; 2],
+    /// Atlas UV of the bottom-right corner
+    pub uv_max: [f32; 2],
+    /// Fill color (RGBA)
+    pub color: [f32; 4],
+}
+
+/// Appearance of a block of text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextStyle {
+    /// Fill color (RGBA)
+    pub color: [f32; 4],
+    /// Font size in pixels
+    pub size: f32,
+    /// Outline color (RGBA, SDF fonts only)
+    pub outline_color: [f32; 4],
+    /// Outline width in atlas pixels (SDF fonts only, at most the font's spread)
+    pub outline_width: f32,
+    /// Shadow color (RGBA); fully transparent disables the shadow
+    pub shadow_color: [f32; 4],
+    /// Shadow offset in screen pixels
+    pub shadow_offset: Vec2,
+    /// Shadow blur in atlas pixels (SDF fonts only)
+    pub shadow_softness: f32,
+}
+
+impl TextStyle {
+    /// Create a plain white style at a pixel size
+    #[must_use]
+    pub const fn new(size: f32) -> Self {
+        Self {
+            color: [1.0, 1.0, 1.0, 1.0],
+            size,
+            outline_color: [0.0, 0.0, 0.0, 1.0],
+            outline_width: 0.0,
+            shadow_color: [0.0, 0.0, 0.0, 0.0],
+            shadow_offset: Vec2::ZERO,
+            shadow_softness: 0.0,
+        }
+    }
+
+    /// Set the fill color
+    #[must_use]
+    pub const fn with_color(mut self, color: [f32; 4]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Add an outline
+    #[must_use]
+    pub const fn with_outline(mut self, color: [f32; 4], width: f32) -> Self {
+        self.outline_color = color;
+        self.outline_width = width;
+        self
+    }
+
+    /// Add a drop shadow
+    #[must_use]
+    pub const fn with_shadow(mut self, color: [f32; 4], offset: Vec2, softness: f32) -> Self {
+        self.shadow_color = color;
+        self.shadow_offset = offset;
+        self.shadow_softness = softness;
+        self
+    }
+
+    /// Build the shader uniform for a font
+    #[must_use]
+    pub fn uniform(&self, font: &Font) -> TextStyleUniform {
+        let (mode, spread) = match font.mode() {
+            GlyphMode::Raster => (0, 1.0),
+            GlyphMode::Sdf { spread } => (1, spread),
+        };
+        TextStyleUniform {
+            outline_color: self.outline_color,
+            shadow_color: self.shadow_color,
+            shadow_offset: self.shadow_offset.into(),
+            outline_width: self.outline_width,
+            shadow_softness: self.shadow_softness,
+            spread,
+            mode,
+            _padding: [0; 2],
+        }
+    }
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self::new(16.0)
+    }
+}
+
+/// Text style uniform for the text shader
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct TextStyleUniform {
+    /// Outline color
+    pub outline_color: [f32; 4],
+    /// Shadow color
+    pub shadow_color: [f32; 4],
+    /// Shadow offset in screen pixels
+    pub shadow_offset: [f32; 2],
+    /// Outline width in atlas pixels
+    pub outline_width: f32,
+    /// Shadow blur in atlas pixels
+    pub shadow_softness: f32,
+    /// Distance range of the SDF in atlas pixels
+    pub spread: f32,
+    /// 0 for raster glyphs, 1 for SDF glyphs, 2 for color images
+    pub mode: u32,
+    /// Padding to 16-byte alignment
+    _padding: [u32; 2],
+}
+
+impl TextStyleUniform {
+    /// Uniform for full-color image quads such as UI icons
+    #[must_use]
+    pub fn image() -> Self {
+        Self {
+            spread: 1.0,
+            mode: 2,
+            ..Zeroable::zeroed()
+        }
+    }
+}
+
+/// Lay out text into glyph quads with its top-left corner at `position`
+///
+/// Handles kerning and `\n` line breaks. Glyphs are scaled from the font's
+/// rasterized size to `style.size`.
+#[must_use]
+pub fn layout_text(
+    font: &Font,
+    text: &str,
+    position: Vec2,
+    style: &TextStyle,
+) -> Vec<GlyphInstance> {
+    let scale = style.size / font.px_size;
+    let mut glyphs = Vec::with_capacity(text.len());
+    let mut pen = Vec2::new(position.x, position.y + font.ascent * scale);
+    let mut prev: Option<char> = None;
+
+    for c in text.chars() {
+        if c == '\n' {
+            pen.x = position.x;
+            pen.y += font.line_height * scale;
+            prev = None;
+            continue;
+        }
+        if let Some(p) = prev {
+            pen.x += font.kerning(p, c) * scale;
+        }
+        prev = Some(c);
+        let Some(glyph) = font.glyph(c) else {
+            continue;
+        };
+        glyphs.extend(glyph_quad(font, glyph, pen, scale, style.color));
+        pen.x += glyph.advance * scale;
+    }
+
+    glyphs
+}
+
+/// Build the quad for a glyph whose baseline origin is at `pen`
+///
+/// Returns `None` for glyphs without coverage, such as spaces.
+pub(super) fn glyph_quad(
+    font: &Font,
+    glyph: &Glyph,
+    pen: Vec2,
+    scale: f32,
+    color: [f32; 4],
+) -> Option<GlyphInstance> {
+    if glyph.size.0 == 0 || glyph.size.1 == 0 {
+        return None;
+    }
+    let size = Vec2::new(glyph.size.0 as f32, glyph.size.1 as f32) * scale;
+    // Glyph offsets are y-up from the baseline; screen space is y-down
+    let top_left = Vec2::new(
+        pen.x + glyph.offset.x * scale,
+        pen.y - (glyph.offset.y * scale + size.y),
+    );
+    let (uv_min, uv_max) = glyph.uv_rect((font.atlas().width, font.atlas().height));
+    Some(GlyphInstance {
+        position: top_left.into(),
+        size: size.into(),
+        uv_min: uv_min.into(),
+        uv_max: uv_max.into(),
+        color,
+    })
+}
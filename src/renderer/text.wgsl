@@ -0,0 +1,118 @@
+// Text shader for raster and signed distance field glyphs
+
+struct TextStyle {
+    outline_color: vec4<f32>,
+    shadow_color: vec4<f32>,
+    shadow_offset: vec2<f32>,
+    outline_width: f32,
+    shadow_softness: f32,
+    spread: f32,
+    mode: u32,
+}
+
+@group(0) @binding(0) var<uniform> screen_size: vec2<f32>;
+
+@group(1) @binding(0) var atlas: texture_2d<f32>;
+@group(1) @binding(1) var atlas_sampler: sampler;
+@group(1) @binding(2) var<uniform> style: TextStyle;
+
+struct GlyphInput {
+    @location(0) position: vec2<f32>,
+    @location(1) size: vec2<f32>,
+    @location(2) uv_min: vec2<f32>,
+    @location(3) uv_max: vec2<f32>,
+    @location(4) color: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    // Atlas UV change per screen pixel, for shadow offsets
+    @location(2) uv_per_pixel: vec2<f32>,
+    @location(3) uv_min: vec2<f32>,
+    @location(4) uv_max: vec2<f32>,
+}
+
+@vertex
+fn vs_main(
+    in: GlyphInput,
+    @builtin(vertex_index) vertex_index: u32,
+) -> VertexOutput {
+    var out: VertexOutput;
+
+    let offsets = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 0.0)
+    );
+    let offset = offsets[vertex_index % 6u];
+
+    let pixel_pos = in.position + offset * in.size;
+    let ndc_x = (pixel_pos.x / screen_size.x) * 2.0 - 1.0;
+    let ndc_y = 1.0 - (pixel_pos.y / screen_size.y) * 2.0;
+
+    out.clip_position = vec4<f32>(ndc_x, ndc_y, 0.0, 1.0);
+    out.uv = mix(in.uv_min, in.uv_max, offset);
+    out.color = in.color;
+    out.uv_per_pixel = (in.uv_max - in.uv_min) / max(in.size, vec2<f32>(1.0));
+    out.uv_min = in.uv_min;
+    out.uv_max = in.uv_max;
+
+    return out;
+}
+
+// Sample the atlas alpha, treating texels outside the glyph as empty
+fn sample_glyph(uv: vec2<f32>, uv_min: vec2<f32>, uv_max: vec2<f32>) -> f32 {
+    let value = textureSample(atlas, atlas_sampler, clamp(uv, uv_min, uv_max)).a;
+    let inside = all(uv >= uv_min) && all(uv <= uv_max);
+    return select(0.0, value, inside);
+}
+
+// Premultiplied "over" compositing
+fn over(top: vec4<f32>, bottom: vec4<f32>) -> vec4<f32> {
+    let alpha = top.a + bottom.a * (1.0 - top.a);
+    let rgb = top.rgb * top.a + bottom.rgb * bottom.a * (1.0 - top.a);
+    return vec4<f32>(rgb / max(alpha, 0.0001), alpha);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let shadow_uv = in.uv - style.shadow_offset * in.uv_per_pixel;
+    let value = sample_glyph(in.uv, in.uv_min, in.uv_max);
+    let shadow_value = sample_glyph(shadow_uv, in.uv_min, in.uv_max);
+
+    if (style.mode == 2u) {
+        // Color image such as an inline icon
+        let uv = clamp(in.uv, in.uv_min, in.uv_max);
+        return textureSample(atlas, atlas_sampler, uv) * in.color;
+    }
+
+    var text: vec4<f32>;
+    var shadow: vec4<f32>;
+    if (style.mode == 0u) {
+        // Raster coverage
+        text = vec4<f32>(in.color.rgb, in.color.a * value);
+        shadow = vec4<f32>(style.shadow_color.rgb, style.shadow_color.a * shadow_value);
+    } else {
+        // Signed distance: 0.5 is the edge, one atlas pixel is 1 / (2 * spread)
+        let unit = 1.0 / (2.0 * style.spread);
+        let aa = max(fwidth(value), 0.0001);
+        let fill = smoothstep(0.5 - aa, 0.5 + aa, value);
+
+        let outline_edge = 0.5 - style.outline_width * unit;
+        let outlined = smoothstep(outline_edge - aa, outline_edge + aa, value);
+        let color = mix(style.outline_color, in.color, fill);
+        let alpha = select(fill * in.color.a, outlined * color.a, style.outline_width > 0.0);
+        text = vec4<f32>(color.rgb, alpha);
+
+        let soft = style.shadow_softness * unit + aa;
+        let shadow_alpha = smoothstep(0.5 - soft, 0.5 + soft, shadow_value);
+        shadow = vec4<f32>(style.shadow_color.rgb, style.shadow_color.a * shadow_alpha);
+    }
+
+    return over(text, shadow);
+}
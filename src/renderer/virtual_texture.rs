@@ -0,0 +1,590 @@
+//! Sparse virtual texturing
+//!
+//! A single large logical texture split into fixed-size pages. A feedback
+//! pass reports which pages are visible, pages load asynchronously into a
+//! physical atlas of fixed size, and a page table texture maps virtual pages
+//! to atlas slots, falling back to the nearest resident coarser mip.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::sync::mpsc::{Receiver, Sender, channel};
+
+/// WGSL helpers for page table lookup and feedback output
+pub const VIRTUAL_TEXTURE_WGSL: &str = include_str!("virtual_texture.wgsl");
+
+/// Feedback value meaning "no page requested"
+pub const FEEDBACK_EMPTY: u32 = u32::MAX;
+
+/// Loads the texels of one page (RGBA8, `page_size * page_size` pixels)
+pub type PageLoader = Arc<dyn Fn(PageId) -> Result<Vec<u8>, String> + Send + Sync>;
+
+/// Virtual texture dimensions and atlas size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VirtualTextureConfig {
+    /// Width and height of the logical texture in texels
+    pub virtual_size: u32,
+    /// Width and height of one page in texels
+    pub page_size: u32,
+    /// Pages per side of the physical atlas
+    pub atlas_pages: u32,
+    /// Maximum page loads in flight at once
+    pub max_pending_loads: usize,
+}
+
+impl VirtualTextureConfig {
+    /// Create a config for a 64k texture with 128 px pages and a 4k atlas
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            virtual_size: 65536,
+            page_size: 128,
+            atlas_pages: 32,
+            max_pending_loads: 16,
+        }
+    }
+
+    /// Set the logical texture size
+    #[must_use]
+    pub const fn with_virtual_size(mut self, virtual_size: u32) -> Self {
+        self.virtual_size = virtual_size;
+        self
+    }
+
+    /// Set the page size
+    #[must_use]
+    pub const fn with_page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Set the atlas size in pages per side
+    #[must_use]
+    pub const fn with_atlas_pages(mut self, atlas_pages: u32) -> Self {
+        self.atlas_pages = atlas_pages;
+        self
+    }
+
+    /// Get the number of pages per side at mip 0
+    #[must_use]
+    pub const fn pages_per_side(&self) -> u32 {
+        self.virtual_size / self.page_size
+    }
+
+    /// Get the number of mip levels in the page table
+    #[must_use]
+    pub const fn mip_count(&self) -> u8 {
+        (self.pages_per_side().ilog2() + 1) as u8
+    }
+
+    /// Get the atlas width and height in texels
+    #[must_use]
+    pub const fn atlas_size(&self) -> u32 {
+        self.atlas_pages * self.page_size
+    }
+
+    /// Get the VRAM used by the atlas in bytes
+    #[must_use]
+    pub const fn atlas_bytes(&self) -> u64 {
+        let size = self.atlas_size() as u64;
+        size * size * 4
+    }
+}
+
+impl Default for VirtualTextureConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A page of the virtual texture at a mip level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PageId {
+    /// Mip level (0 is the finest)
+    pub mip: u8,
+    /// Page column at this mip
+    pub x: u32,
+    /// Page row at this mip
+    pub y: u32,
+}
+
+impl PageId {
+    /// Create a page ID
+    #[must_use]
+    pub const fn new(mip: u8, x: u32, y: u32) -> Self {
+        Self { mip, x, y }
+    }
+
+    /// Get the page covering this one at the next coarser mip
+    #[must_use]
+    pub const fn parent(&self) -> Self {
+        Self::new(self.mip + 1, self.x / 2, self.y / 2)
+    }
+
+    /// Pack into a feedback value (`mip << 24 | y << 12 | x`)
+    #[must_use]
+    pub const fn pack(&self) -> u32 {
+        ((self.mip as u32) << 24) | ((self.y & 0xFFF) << 12) | (self.x & 0xFFF)
+    }
+
+    /// Unpack a feedback value
+    #[must_use]
+    pub const fn unpack(value: u32) -> Option<Self> {
+        if value == FEEDBACK_EMPTY {
+            return None;
+        }
+        Some(Self::new(
+            (value >> 24) as u8,
+            value & 0xFFF,
+            (value >> 12) & 0xFFF,
+        ))
+    }
+}
+
+/// A resident page's location in the atlas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Slot {
+    /// Atlas column
+    x: u32,
+    /// Atlas row
+    y: u32,
+    /// Frame the page was last requested
+    last_used: u64,
+}
+
+/// CPU state of a virtual texture: residency, LRU eviction, and page table
+pub struct VirtualTexture {
+    /// Dimensions and limits
+    config: VirtualTextureConfig,
+    /// Loads page texels
+    loader: PageLoader,
+    /// Resident pages and their atlas slots
+    resident: HashMap<PageId, Slot>,
+    /// Unused atlas slots
+    free_slots: Vec<(u32, u32)>,
+    /// Pages currently loading
+    pending: HashSet<PageId>,
+    /// Sends finished loads back from worker threads
+    sender: Sender<(PageId, Result<Vec<u8>, String>)>,
+    /// Receives finished loads
+    receiver: Receiver<(PageId, Result<Vec<u8>, String>)>,
+    /// Pages loaded this update, waiting for upload into the atlas
+    uploads: Vec<(PageId, u32, u32, Vec<u8>)>,
+    /// Page table entries per mip, packed as RGBA8 (atlas x, atlas y, mip, valid)
+    table: Vec<Vec<[u8; 4]>>,
+    /// Whether the page table must be rebuilt
+    table_dirty: bool,
+    /// Frame counter for LRU
+    frame: u64,
+}
+
+impl VirtualTexture {
+    /// Create a virtual texture with a page loader
+    ///
+    /// The coarsest mip (a single page) is requested immediately so every
+    /// lookup has a fallback.
+    #[must_use]
+    pub fn new(config: VirtualTextureConfig, loader: PageLoader) -> Self {
+        let (sender, receiver) = channel();
+        let free_slots = (0..config.atlas_pages)
+            .rev()
+            .flat_map(|y| (0..config.atlas_pages).rev().map(move |x| (x, y)))
+            .collect();
+        let table = (0..config.mip_count())
+            .map(|mip| {
+                let side = (config.pages_per_side() >> mip).max(1) as usize;
+                vec![[0; 4]; side * side]
+            })
+            .collect();
+        let mut texture = Self {
+            config,
+            loader,
+            resident: HashMap::new(),
+            free_slots,
+            pending: HashSet::new(),
+            sender,
+            receiver,
+            uploads: Vec::new(),
+            table,
+            table_dirty: true,
+            frame: 0,
+        };
+        let root = PageId::new(config.mip_count() - 1, 0, 0);
+        texture.request(root);
+        texture
+    }
+
+    /// Get the config
+    #[must_use]
+    pub const fn config(&self) -> &VirtualTextureConfig {
+        &self.config
+    }
+
+    /// Check if a page is resident in the atlas
+    #[must_use]
+    pub fn is_resident(&self, page: PageId) -> bool {
+        self.resident.contains_key(&page)
+    }
+
+    /// Get the number of resident pages
+    #[must_use]
+    pub fn resident_count(&self) -> usize {
+        self.resident.len()
+    }
+
+    /// Get the number of pages loading
+    #[must_use]
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Process feedback values read back from the feedback pass
+    ///
+    /// Requested pages (and their coarser ancestors) are marked as used, and
+    /// missing ones are queued for loading, coarsest first.
+    pub fn process_feedback(&mut self, feedback: &[u32]) {
+        self.frame += 1;
+        let mut wanted: HashSet<PageId> = HashSet::new();
+        for page in feedback.iter().filter_map(|&value| PageId::unpack(value)) {
+            let mut page = page;
+            while page.mip < self.config.mip_count() && wanted.insert(page) {
+                page = page.parent();
+            }
+        }
+
+        let mut missing = Vec::new();
+        for page in wanted {
+            match self.resident.get_mut(&page) {
+                Some(slot) => slot.last_used = self.frame,
+                None => missing.push(page),
+            }
+        }
+        missing.sort_by_key(|page| std::cmp::Reverse(page.mip));
+        for page in missing {
+            self.request(page);
+        }
+    }
+
+    /// Start loading a page on a worker thread
+    fn request(&mut self, page: PageId) {
+        if self.pending.len() >= self.config.max_pending_loads
+            || self.resident.contains_key(&page)
+            || !self.pending.insert(page)
+        {
+            return;
+        }
+        let loader = Arc::clone(&self.loader);
+        let sender = self.sender.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send((page, loader(page)));
+        });
+    }
+
+    /// Move finished loads into the atlas, evicting least recently used pages
+    pub fn update(&mut self) {
+        let expected = (self.config.page_size * self.config.page_size * 4) as usize;
+        while let Ok((page, result)) = self.receiver.try_recv() {
+            self.pending.remove(&page);
+            let texels = match result {
+                Ok(texels) if texels.len() == expected => texels,
+                Ok(texels) => {
+                    log::warn!(
+                        "Virtual texture page {page:?} has {} bytes, expected {expected}",
+                        texels.len()
+                    );
+                    continue;
+                }
+                Err(error) => {
+                    log::warn!("Failed to load virtual texture page {page:?}: {error}");
+                    continue;
+                }
+            };
+            let Some((x, y)) = self.allocate_slot() else {
+                continue;
+            };
+            self.resident.insert(
+                page,
+                Slot {
+                    x,
+                    y,
+                    last_used: self.frame,
+                },
+            );
+            self.uploads.push((page, x, y, texels));
+            self.table_dirty = true;
+        }
+    }
+
+    /// Take a free slot, evicting the least recently used page if needed
+    ///
+    /// The coarsest mip is never evicted.
+    fn allocate_slot(&mut self) -> Option<(u32, u32)> {
+        if let Some(slot) = self.free_slots.pop() {
+            return Some(slot);
+        }
+        let root_mip = self.config.mip_count() - 1;
+        let (&victim, slot) = self
+            .resident
+            .iter()
+            .filter(|(page, _)| page.mip != root_mip)
+            .min_by_key(|(page, slot)| (slot.last_used, page.mip))?;
+        let slot = (slot.x, slot.y);
+        self.resident.remove(&victim);
+        self.table_dirty = true;
+        Some(slot)
+    }
+
+    /// Rebuild page table entries from the coarsest mip down
+    ///
+    /// Each entry points at its own page if resident, otherwise at the
+    /// entry of its parent.
+    fn rebuild_table(&mut self) {
+        let mip_count = self.config.mip_count();
+        for mip in (0..mip_count).rev() {
+            let side = (self.config.pages_per_side() >> mip).max(1);
+            for y in 0..side {
+                for x in 0..side {
+                    let page = PageId::new(mip, x, y);
+                    let entry = match self.resident.get(&page) {
+                        Some(slot) => [slot.x as u8, slot.y as u8, mip, 255],
+                        None if mip + 1 < mip_count => {
+                            let parent_side = (side / 2).max(1);
+                            self.table[mip as usize + 1][((y / 2) * parent_side + x / 2) as usize]
+                        }
+                        None => [0; 4],
+                    };
+                    self.table[mip as usize][(y * side + x) as usize] = entry;
+                }
+            }
+        }
+    }
+
+    /// Get the page table entry for a page (atlas x, atlas y, mapped mip, valid)
+    #[must_use]
+    pub fn table_entry(&mut self, page: PageId) -> [u8; 4] {
+        if self.table_dirty {
+            self.rebuild_table();
+            self.table_dirty = false;
+        }
+        let side = (self.config.pages_per_side() >> page.mip).max(1);
+        self.table
+            .get(page.mip as usize)
+            .and_then(|level| level.get((page.y * side + page.x) as usize))
+            .copied()
+            .unwrap_or([0; 4])
+    }
+
+    /// Upload loaded pages and the page table to the GPU
+    pub fn upload(&mut self, queue: &wgpu::Queue, gpu: &VirtualTextureGpu) {
+        let page_size = self.config.page_size;
+        for (_, x, y, texels) in self.uploads.drain(..) {
+            write_region(
+                queue,
+                &gpu.atlas,
+                0,
+                (x * page_size, y * page_size),
+                (page_size, page_size),
+                &texels,
+            );
+        }
+
+        if !self.table_dirty {
+            return;
+        }
+        self.rebuild_table();
+        self.table_dirty = false;
+        for (mip, level) in self.table.iter().enumerate() {
+            let side = (self.config.pages_per_side() >> mip).max(1);
+            write_region(
+                queue,
+                &gpu.page_table,
+                mip as u32,
+                (0, 0),
+                (side, side),
+                bytemuck::cast_slice(level.as_slice()),
+            );
+        }
+    }
+}
+
+impl std::fmt::Debug for VirtualTexture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VirtualTexture")
+            .field("config", &self.config)
+            .field("resident", &self.resident.len())
+            .field("pending", &self.pending.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Write RGBA8 texels into a texture region
+fn write_region(
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    mip_level: u32,
+    origin: (u32, u32),
+    size: (u32, u32),
+    texels: &[u8],
+) {
+    queue.write_texture(
+        wgpu::ImageCopyTexture {
+            texture,
+            mip_level,
+            origin: wgpu::Origin3d {
+                x: origin.0,
+                y: origin.1,
+                z: 0,
+            },
+            aspect: wgpu::TextureAspect::All,
+        },
+        texels,
+        wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: Some(size.0 * 4),
+            rows_per_image: Some(size.1),
+        },
+        wgpu::Extent3d {
+            width: size.0,
+            height: size.1,
+            depth_or_array_layers: 1,
+        },
+    );
+}
+
+/// GPU resources for a virtual texture
+#[derive(Debug)]
+pub struct VirtualTextureGpu {
+    /// Physical page atlas (RGBA8 sRGB)
+    pub atlas: wgpu::Texture,
+    /// Atlas view
+    pub atlas_view: wgpu::TextureView,
+    /// Page table (RGBA8 uint, one texel per page, one mip per page mip)
+    pub page_table: wgpu::Texture,
+    /// Page table view
+    pub page_table_view: wgpu::TextureView,
+    /// Feedback target (R32 uint packed page IDs)
+    pub feedback: wgpu::Texture,
+    /// Feedback view
+    pub feedback_view: wgpu::TextureView,
+    /// Feedback target size
+    pub feedback_size: (u32, u32),
+}
+
+impl VirtualTextureGpu {
+    /// Create the atlas, page table, and a feedback target
+    ///
+    /// The feedback pass is usually rendered at a fraction of the screen
+    /// resolution (e.g. 1/8) to keep readback cheap.
+    #[must_use]
+    pub fn new(
+        device: &wgpu::Device,
+        config: &VirtualTextureConfig,
+        feedback_size: (u32, u32),
+    ) -> Self {
+        let atlas = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Virtual Texture Atlas"),
+            size: wgpu::Extent3d {
+                width: config.atlas_size(),
+                height: config.atlas_size(),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let page_table = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Virtual Texture Page Table"),
+            size: wgpu::Extent3d {
+                width: config.pages_per_side(),
+                height: config.pages_per_side(),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: u32::from(config.mip_count()),
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Uint,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let feedback = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Virtual Texture Feedback"),
+            size: wgpu::Extent3d {
+                width: feedback_size.0,
+                height: feedback_size.1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R32Uint,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        Self {
+            atlas_view: atlas.create_view(&wgpu::TextureViewDescriptor::default()),
+            atlas,
+            page_table_view: page_table.create_view(&wgpu::TextureViewDescriptor::default()),
+            page_table,
+            feedback_view: feedback.create_view(&wgpu::TextureViewDescriptor::default()),
+            feedback,
+            feedback_size,
+        }
+    }
+
+    /// Get the GPU memory used by the atlas and page table in bytes
+    #[must_use]
+    pub fn memory_bytes(config: &VirtualTextureConfig) -> u64 {
+        let pages = u64::from(config.pages_per_side());
+        config.atlas_bytes() + pages * pages * 4 * 4 / 3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_texture() -> VirtualTexture {
+        let config = VirtualTextureConfig::new()
+            .with_virtual_size(64)
+            .with_page_size(16)
+            .with_atlas_pages(2);
+        VirtualTexture::new(config, Arc::new(|_| Ok(vec![0; 16 * 16 * 4])))
+    }
+
+    fn wait_for_loads(texture: &mut VirtualTexture) {
+        while texture.pending_count() > 0 {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            texture.update();
+        }
+    }
+
+    #[test]
+    fn test_feedback_round_trip() {
+        let page = PageId::new(3, 100, 200);
+        assert_eq!(PageId::unpack(page.pack()), Some(page));
+        assert_eq!(PageId::unpack(FEEDBACK_EMPTY), None);
+    }
+
+    #[test]
+    fn test_pages_fall_back_and_evict() {
+        let mut texture = small_texture();
+        wait_for_loads(&mut texture);
+        // 4x4 pages at mip 0, so mips 0..=2 with the root at mip 2
+        assert!(texture.is_resident(PageId::new(2, 0, 0)));
+        assert_eq!(texture.table_entry(PageId::new(0, 3, 3))[2], 2);
+
+        texture.process_feedback(&[PageId::new(0, 0, 0).pack()]);
+        wait_for_loads(&mut texture);
+        assert_eq!(texture.table_entry(PageId::new(0, 0, 0))[2], 0);
+
+        // The atlas holds 4 pages; requesting a distant page evicts old ones
+        texture.process_feedback(&[PageId::new(0, 3, 3).pack()]);
+        wait_for_loads(&mut texture);
+        assert!(texture.resident_count() <= 4);
+        assert!(texture.is_resident(PageId::new(0, 3, 3)));
+        assert!(texture.is_resident(PageId::new(2, 0, 0)));
+    }
+}
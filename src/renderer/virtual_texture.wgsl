@@ -0,0 +1,50 @@
+// Virtual texture helpers
+//
+// Bind the page table (texture_2d<u32>) and atlas (texture_2d<f32>) and set
+// the constants below to match VirtualTextureConfig.
+
+struct VirtualTextureParams {
+    // Pages per side at mip 0
+    pages_per_side: f32,
+    // Page size in texels
+    page_size: f32,
+    // Atlas size in texels
+    atlas_size: f32,
+    // Number of page table mips
+    mip_count: f32,
+}
+
+// Mip level of the virtual texture at a UV, from screen-space derivatives
+fn vt_mip_level(uv: vec2<f32>, params: VirtualTextureParams) -> f32 {
+    let texels = uv * params.pages_per_side * params.page_size;
+    let dx = dpdx(texels);
+    let dy = dpdy(texels);
+    let rho = max(dot(dx, dx), dot(dy, dy));
+    return clamp(0.5 * log2(rho), 0.0, params.mip_count - 1.0);
+}
+
+// Packed page ID for the feedback pass (mip << 24 | y << 12 | x)
+fn vt_feedback(uv: vec2<f32>, params: VirtualTextureParams) -> u32 {
+    let mip = u32(floor(vt_mip_level(uv, params)));
+    let pages = max(u32(params.pages_per_side) >> mip, 1u);
+    let page = min(vec2<u32>(fract(uv) * f32(pages)), vec2<u32>(pages - 1u));
+    return (mip << 24u) | ((page.y & 0xFFFu) << 12u) | (page.x & 0xFFFu);
+}
+
+// Translate a virtual UV to an atlas UV through the page table
+fn vt_atlas_uv(
+    page_table: texture_2d<u32>,
+    uv: vec2<f32>,
+    params: VirtualTextureParams,
+) -> vec2<f32> {
+    let mip = u32(floor(vt_mip_level(uv, params)));
+    let pages = max(u32(params.pages_per_side) >> mip, 1u);
+    let page = min(vec2<u32>(fract(uv) * f32(pages)), vec2<u32>(pages - 1u));
+    let entry = textureLoad(page_table, vec2<i32>(page), i32(mip));
+
+    // The entry may point at a coarser resident page
+    let mapped_pages = f32(max(u32(params.pages_per_side) >> entry.z, 1u));
+    let in_page = fract(fract(uv) * mapped_pages);
+    let slot = vec2<f32>(f32(entry.x), f32(entry.y));
+    return (slot + in_page) * params.page_size / params.atlas_size;
+}
@@ -0,0 +1,588 @@
+//! Editor asset browser panel
+//!
+//! Shows an asset directory as a folder tree and a grid of tiles, with
+//! search and type filters. Tiles can be dragged out of the panel to spawn
+//! the asset into the scene.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use glam::Vec2;
+
+use super::draw::UiDrawList;
+use super::rect::Rect;
+use super::widget::{Widget, WidgetState};
+use crate::renderer::{RichTextFonts, UiRect};
+
+/// Height of one folder tree row in pixels
+const TREE_ROW_HEIGHT: f32 = 20.0;
+/// Indentation per folder tree level in pixels
+const TREE_INDENT: f32 = 12.0;
+/// Gap between grid tiles in pixels
+const TILE_SPACING: f32 = 8.0;
+
+/// Kind of asset, detected from the file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetType {
+    /// Image file
+    Texture,
+    /// glTF or GLB model
+    Mesh,
+    /// Material definition
+    Material,
+    /// Sound file
+    Audio,
+    /// Font file
+    Font,
+    /// Scene or prefab
+    Scene,
+    /// Anything else
+    Other,
+}
+
+impl AssetType {
+    /// Detect the type from a file extension
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("png" | "jpg" | "jpeg" | "tga" | "bmp" | "hdr") => Self::Texture,
+            Some("gltf" | "glb" | "obj") => Self::Mesh,
+            Some("mat") => Self::Material,
+            Some("wav" | "ogg" | "mp3" | "flac") => Self::Audio,
+            Some("ttf" | "otf") => Self::Font,
+            Some("ron" | "scene" | "prefab") => Self::Scene,
+            _ => Self::Other,
+        }
+    }
+
+    /// Tile color used when no thumbnail is available
+    #[must_use]
+    pub const fn color(&self) -> [f32; 4] {
+        match self {
+            Self::Texture => [0.3, 0.5, 0.8, 1.0],
+            Self::Mesh => [0.4, 0.7, 0.4, 1.0],
+            Self::Material => [0.8, 0.5, 0.3, 1.0],
+            Self::Audio => [0.7, 0.4, 0.7, 1.0],
+            Self::Font => [0.6, 0.6, 0.3, 1.0],
+            Self::Scene => [0.3, 0.7, 0.7, 1.0],
+            Self::Other => [0.4, 0.4, 0.4, 1.0],
+        }
+    }
+}
+
+/// A file shown in the browser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrowserEntry {
+    /// Path relative to the browser root
+    pub path: PathBuf,
+    /// Detected asset type
+    pub kind: AssetType,
+}
+
+impl BrowserEntry {
+    /// Get the file name for display
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+    }
+}
+
+/// A folder in the directory tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectoryNode {
+    /// Folder name
+    pub name: String,
+    /// Path relative to the browser root
+    pub path: PathBuf,
+    /// Sub-folders, sorted by name
+    pub children: Vec<DirectoryNode>,
+    /// Whether sub-folders are shown
+    pub expanded: bool,
+}
+
+impl DirectoryNode {
+    /// Find a node by relative path
+    fn find_mut(&mut self, path: &Path) -> Option<&mut Self> {
+        if self.path == path {
+            return Some(self);
+        }
+        self.children.iter_mut().find_map(|c| c.find_mut(path))
+    }
+}
+
+/// An asset dragged out of the browser and released over the scene
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetDrop {
+    /// Path relative to the browser root
+    pub path: PathBuf,
+    /// Asset type
+    pub kind: AssetType,
+    /// Screen position where the asset was released
+    pub position: Vec2,
+}
+
+/// Asset browser panel
+#[derive(Debug, Clone)]
+pub struct AssetBrowser {
+    /// Panel rectangle
+    pub rect: Rect,
+    /// Edge length of grid tiles in pixels
+    pub tile_size: f32,
+    /// Directory being browsed
+    root: PathBuf,
+    /// Folder tree
+    tree: DirectoryNode,
+    /// All files under the root
+    entries: Vec<BrowserEntry>,
+    /// Folder whose files are shown
+    current_dir: PathBuf,
+    /// Case-insensitive name filter (searches all folders when set)
+    search: String,
+    /// Types to show (all when empty)
+    filters: HashSet<AssetType>,
+    /// Selected file
+    selected: Option<PathBuf>,
+    /// File being dragged and the current cursor position
+    drag: Option<(PathBuf, Vec2)>,
+    /// Drop waiting to be taken by the game
+    dropped: Option<AssetDrop>,
+    /// Current interaction state
+    state: WidgetState,
+}
+
+impl AssetBrowser {
+    /// Create a browser for a directory and scan it
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>, rect: Rect) -> Self {
+        let mut browser = Self {
+            rect,
+            tile_size: 72.0,
+            root: root.into(),
+            tree: DirectoryNode {
+                name: String::new(),
+                path: PathBuf::new(),
+                children: Vec::new(),
+                expanded: true,
+            },
+            entries: Vec::new(),
+            current_dir: PathBuf::new(),
+            search: String::new(),
+            filters: HashSet::new(),
+            selected: None,
+            drag: None,
+            dropped: None,
+            state: WidgetState::Normal,
+        };
+        browser.refresh();
+        browser
+    }
+
+    /// Rescan the directory, keeping expanded folders open
+    pub fn refresh(&mut self) {
+        let mut expanded = HashSet::new();
+        collect_expanded(&self.tree, &mut expanded);
+        self.entries.clear();
+        let name = self
+            .root
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.tree = scan_dir(
+            &self.root,
+            Path::new(""),
+            name,
+            &expanded,
+            &mut self.entries,
+        );
+        self.tree.expanded = true;
+        self.entries.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+
+    /// Get the root directory
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Get the folder tree
+    #[must_use]
+    pub const fn tree(&self) -> &DirectoryNode {
+        &self.tree
+    }
+
+    /// Get the folder whose files are shown
+    #[must_use]
+    pub fn current_dir(&self) -> &Path {
+        &self.current_dir
+    }
+
+    /// Show the files of a folder
+    pub fn open_dir(&mut self, path: impl Into<PathBuf>) {
+        self.current_dir = path.into();
+    }
+
+    /// Set the search text
+    pub fn set_search(&mut self, search: impl Into<String>) {
+        self.search = search.into();
+    }
+
+    /// Show or hide a type (with no types enabled, everything is shown)
+    pub fn toggle_filter(&mut self, kind: AssetType) {
+        if !self.filters.remove(&kind) {
+            self.filters.insert(kind);
+        }
+    }
+
+    /// Get the selected file
+    #[must_use]
+    pub fn selected(&self) -> Option<&Path> {
+        self.selected.as_deref()
+    }
+
+    /// Get the absolute path of a browser entry
+    #[must_use]
+    pub fn absolute_path(&self, entry: &BrowserEntry) -> PathBuf {
+        self.root.join(&entry.path)
+    }
+
+    /// Get the files matching the current folder, search, and filters
+    #[must_use]
+    pub fn visible_entries(&self) -> Vec<&BrowserEntry> {
+        let search = self.search.to_lowercase();
+        self.entries
+            .iter()
+            .filter(|e| self.filters.is_empty() || self.filters.contains(&e.kind))
+            .filter(|e| {
+                if search.is_empty() {
+                    e.path.parent() == Some(self.current_dir.as_path())
+                } else {
+                    e.name().to_lowercase().contains(&search)
+                }
+            })
+            .collect()
+    }
+
+    /// Get visible folder rows as (depth, node), honoring expanded state
+    #[must_use]
+    pub fn tree_rows(&self) -> Vec<(usize, &DirectoryNode)> {
+        let mut rows = Vec::new();
+        let mut stack = vec![(0, &self.tree)];
+        while let Some((depth, node)) = stack.pop() {
+            rows.push((depth, node));
+            if node.expanded {
+                stack.extend(node.children.iter().rev().map(|c| (depth + 1, c)));
+            }
+        }
+        rows
+    }
+
+    /// Take the asset dropped outside the panel since the last call
+    pub fn take_drop(&mut self) -> Option<AssetDrop> {
+        self.dropped.take()
+    }
+
+    /// Get the file being dragged and the cursor position
+    #[must_use]
+    pub fn dragging(&self) -> Option<(&Path, Vec2)> {
+        self.drag.as_ref().map(|(path, pos)| (path.as_path(), *pos))
+    }
+
+    /// Width of the folder tree column
+    fn tree_width(&self) -> f32 {
+        self.rect.size.x * 0.3
+    }
+
+    /// Screen rectangle (min, size) of a tree row
+    fn tree_row_rect(&self, row: usize, depth: usize, parent_size: Vec2) -> (Vec2, Vec2) {
+        let (min, _) = self.rect.bounds(parent_size);
+        let indent = depth as f32 * TREE_INDENT;
+        (
+            min + Vec2::new(indent, row as f32 * TREE_ROW_HEIGHT),
+            Vec2::new(self.tree_width() - indent, TREE_ROW_HEIGHT - 2.0),
+        )
+    }
+
+    /// Screen rectangle (min, size) of a grid tile
+    fn tile_rect(&self, index: usize, parent_size: Vec2) -> (Vec2, Vec2) {
+        let (min, _) = self.rect.bounds(parent_size);
+        let grid_origin = min + Vec2::new(self.tree_width() + TILE_SPACING, TILE_SPACING);
+        let grid_width = self.rect.size.x - self.tree_width() - TILE_SPACING;
+        let stride = self.tile_size + TILE_SPACING;
+        let columns = ((grid_width / stride).floor() as usize).max(1);
+        let (column, row) = (index % columns, index / columns);
+        (
+            grid_origin + Vec2::new(column as f32, row as f32) * stride,
+            Vec2::splat(self.tile_size),
+        )
+    }
+
+    /// Find the entry under a screen position
+    fn entry_at(&self, position: Vec2, parent_size: Vec2) -> Option<&BrowserEntry> {
+        self.visible_entries()
+            .into_iter()
+            .enumerate()
+            .find(|(index, _)| {
+                let (min, size) = self.tile_rect(*index, parent_size);
+                point_in(position, min, size)
+            })
+            .map(|(_, entry)| entry)
+    }
+
+    /// Find the folder under a screen position
+    fn dir_at(&self, position: Vec2, parent_size: Vec2) -> Option<PathBuf> {
+        self.tree_rows()
+            .into_iter()
+            .enumerate()
+            .find(|(row, (depth, _))| {
+                let (min, size) = self.tree_row_rect(*row, *depth, parent_size);
+                point_in(position, min, size)
+            })
+            .map(|(_, (_, node))| node.path.clone())
+    }
+
+    /// Build rectangles for the panel, folder rows, tiles, and drag preview
+    #[must_use]
+    pub fn ui_rects(&self, parent_size: Vec2) -> Vec<UiRect> {
+        let (min, _) = self.rect.bounds(parent_size);
+        let mut rects = vec![UiRect {
+            position: min.into(),
+            size: self.rect.size.into(),
+            color: self.rect.style.background_color,
+        }];
+
+        for (row, (depth, node)) in self.tree_rows().into_iter().enumerate() {
+            let (position, size) = self.tree_row_rect(row, depth, parent_size);
+            let color = if node.path == self.current_dir {
+                [0.35, 0.45, 0.6, 1.0]
+            } else {
+                [0.25, 0.25, 0.25, 1.0]
+            };
+            rects.push(UiRect {
+                position: position.into(),
+                size: size.into(),
+                color,
+            });
+        }
+
+        for (index, entry) in self.visible_entries().into_iter().enumerate() {
+            let (position, size) = self.tile_rect(index, parent_size);
+            if self.selected.as_ref() == Some(&entry.path) {
+                rects.push(UiRect {
+                    position: (position - 2.0).into(),
+                    size: (size + 4.0).into(),
+                    color: [1.0, 1.0, 1.0, 1.0],
+                });
+            }
+            rects.push(UiRect {
+                position: position.into(),
+                size: size.into(),
+                color: entry.kind.color(),
+            });
+        }
+
+        if let Some((path, cursor)) = &self.drag {
+            let kind = AssetType::from_path(path);
+            let size = Vec2::splat(self.tile_size * 0.5);
+            let mut color = kind.color();
+            color[3] = 0.6;
+            rects.push(UiRect {
+                position: (*cursor - size * 0.5).into(),
+                size: size.into(),
+                color,
+            });
+        }
+
+        rects
+    }
+
+    /// Get the screen rectangle (min, size) of a visible entry's tile
+    ///
+    /// Use this to draw thumbnails over the tiles.
+    #[must_use]
+    pub fn tile_bounds(&self, path: &Path, parent_size: Vec2) -> Option<(Vec2, Vec2)> {
+        self.visible_entries()
+            .iter()
+            .position(|e| e.path == path)
+            .map(|index| self.tile_rect(index, parent_size))
+    }
+}
+
+impl Widget for AssetBrowser {
+    fn rect(&self) -> &Rect {
+        &self.rect
+    }
+
+    fn rect_mut(&mut self) -> &mut Rect {
+        &mut self.rect
+    }
+
+    fn state(&self) -> WidgetState {
+        self.state
+    }
+
+    fn on_mouse_move(&mut self, position: Vec2, parent_size: Vec2) {
+        if let Some((_, cursor)) = &mut self.drag {
+            *cursor = position;
+            return;
+        }
+        self.state = if self.rect.contains(position, parent_size) {
+            WidgetState::Hovered
+        } else {
+            WidgetState::Normal
+        };
+    }
+
+    fn on_mouse_down(&mut self, position: Vec2, parent_size: Vec2) -> bool {
+        if !self.rect.contains(position, parent_size) {
+            return false;
+        }
+        self.state = WidgetState::Pressed;
+
+        if let Some(path) = self.entry_at(position, parent_size).map(|e| e.path.clone()) {
+            self.selected = Some(path.clone());
+            self.drag = Some((path, position));
+        } else if let Some(dir) = self.dir_at(position, parent_size) {
+            if let Some(node) = self.tree.find_mut(&dir)
+                && self.current_dir == dir
+            {
+                node.expanded = !node.expanded;
+            }
+            self.current_dir = dir;
+            self.search.clear();
+        }
+        true
+    }
+
+    fn on_mouse_up(&mut self, position: Vec2, parent_size: Vec2) -> bool {
+        self.state = WidgetState::Normal;
+        let Some((path, _)) = self.drag.take() else {
+            return false;
+        };
+        if self.rect.contains(position, parent_size) {
+            return false;
+        }
+        self.dropped = Some(AssetDrop {
+            kind: AssetType::from_path(&path),
+            path,
+            position,
+        });
+        true
+    }
+
+    fn draw(&self, draw: &mut UiDrawList, _fonts: RichTextFonts<'_>, parent_size: Vec2) {
+        draw.batch_mut().rects.extend(self.ui_rects(parent_size));
+    }
+}
+
+/// Check if a point lies inside a rectangle given by min and size
+fn point_in(point: Vec2, min: Vec2, size: Vec2) -> bool {
+    point.cmpge(min).all() && point.cmple(min + size).all()
+}
+
+/// Record the paths of expanded folders
+fn collect_expanded(node: &DirectoryNode, expanded: &mut HashSet<PathBuf>) {
+    if node.expanded {
+        expanded.insert(node.path.clone());
+    }
+    for child in &node.children {
+        collect_expanded(child, expanded);
+    }
+}
+
+/// Recursively scan a directory into a tree node and a flat file list
+fn scan_dir(
+    absolute: &Path,
+    relative: &Path,
+    name: String,
+    expanded: &HashSet<PathBuf>,
+    entries: &mut Vec<BrowserEntry>,
+) -> DirectoryNode {
+    let mut node = DirectoryNode {
+        name,
+        path: relative.to_path_buf(),
+        children: Vec::new(),
+        expanded: expanded.contains(relative),
+    };
+    let Ok(read_dir) = std::fs::read_dir(absolute) else {
+        return node;
+    };
+    for entry in read_dir.flatten() {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        if file_name.starts_with('.') {
+            continue;
+        }
+        let child_relative = relative.join(&file_name);
+        match entry.file_type() {
+            Ok(kind) if kind.is_dir() => node.children.push(scan_dir(
+                &entry.path(),
+                &child_relative,
+                file_name,
+                expanded,
+                entries,
+            )),
+            Ok(_) => entries.push(BrowserEntry {
+                kind: AssetType::from_path(&child_relative),
+                path: child_relative,
+            }),
+            Err(_) => {}
+        }
+    }
+    node.children.sort_by(|a, b| a.name.cmp(&b.name));
+    node
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn browser(name: &str) -> AssetBrowser {
+        let root =
+            std::env::temp_dir().join(format!("engine_browser_{name}_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("textures")).unwrap();
+        std::fs::write(root.join("textures/grass.png"), b"").unwrap();
+        std::fs::write(root.join("textures/stone.png"), b"").unwrap();
+        std::fs::write(root.join("tree.glb"), b"").unwrap();
+        AssetBrowser::new(root, Rect::new(0.0, 0.0, 400.0, 300.0))
+    }
+
+    #[test]
+    fn test_search_and_filters() {
+        let mut browser = browser("search");
+        assert_eq!(browser.visible_entries().len(), 1);
+
+        browser.open_dir("textures");
+        assert_eq!(browser.visible_entries().len(), 2);
+
+        browser.set_search("GRASS");
+        assert_eq!(browser.visible_entries()[0].name(), "grass.png");
+
+        browser.set_search("");
+        browser.open_dir("");
+        browser.toggle_filter(AssetType::Texture);
+        assert!(browser.visible_entries().is_empty());
+        std::fs::remove_dir_all(browser.root()).unwrap();
+    }
+
+    #[test]
+    fn test_drag_out_produces_drop() {
+        let mut browser = browser("drag");
+        let parent = Vec2::new(800.0, 600.0);
+        let (tile, size) = browser.tile_bounds(Path::new("tree.glb"), parent).unwrap();
+
+        assert!(browser.on_mouse_down(tile + size * 0.5, parent));
+        browser.on_mouse_move(Vec2::new(600.0, 400.0), parent);
+        assert!(browser.on_mouse_up(Vec2::new(600.0, 400.0), parent));
+
+        let drop = browser.take_drop().unwrap();
+        assert_eq!(drop.path, Path::new("tree.glb"));
+        assert_eq!(drop.kind, AssetType::Mesh);
+        std::fs::remove_dir_all(browser.root()).unwrap();
+    }
+}
@@ -0,0 +1,480 @@
+//! Drag-and-drop between widgets
+//!
+//! A widget becomes draggable by returning a payload from
+//! `Widget::drag_payload`, and a drop target by accepting payloads in
+//! `Widget::accepts_drop`. `DragDrop` watches the mouse over a list of
+//! widgets, draws a ghost of the payload under the cursor, and on release
+//! hands it to the target's `on_drop`. `Slot` is a ready-made cell for
+//! inventories and skill bars.
+
+use glam::Vec2;
+
+use super::draw::{
+    ACCENT_COLOR, OVERLAY_LAYER, UiDrawList, UiTextureId, plain_text, push_widget_rect,
+};
+use super::rect::{Anchor, Rect, RectStyle};
+use super::widget::{Widget, WidgetState, input_order};
+use crate::renderer::{RichTextFonts, TextStyle};
+
+/// Pixels the cursor must travel after a press before a drag starts
+const DRAG_THRESHOLD: f32 = 4.0;
+
+/// Opacity of the ghost following the cursor
+const GHOST_ALPHA: f32 = 0.6;
+
+/// Width of the outline around the target under the cursor
+const TARGET_OUTLINE: f32 = 2.0;
+
+/// What is being dragged
+#[derive(Debug, Clone, PartialEq)]
+pub struct DragPayload {
+    /// Category that drop targets filter on, e.g. "item" or "skill"
+    pub kind: String,
+    /// Game-defined identifier of the dragged thing
+    pub id: u64,
+    /// Text shown when there is no icon
+    pub label: String,
+    /// Texture and UV (min, max) region shown on slots and the ghost
+    pub icon: Option<(UiTextureId, Vec2, Vec2)>,
+}
+
+impl DragPayload {
+    /// Create a payload of a kind
+    #[must_use]
+    pub fn new(kind: impl Into<String>, id: u64) -> Self {
+        Self {
+            kind: kind.into(),
+            id,
+            label: String::new(),
+            icon: None,
+        }
+    }
+
+    /// Set the text shown when there is no icon
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Show a UV region of a texture as the icon
+    #[must_use]
+    pub fn with_icon(mut self, texture: UiTextureId, uv_min: Vec2, uv_max: Vec2) -> Self {
+        self.icon = Some((texture, uv_min, uv_max));
+        self
+    }
+}
+
+/// Draw a payload's icon, or its label, inside a rect
+fn push_payload(
+    draw: &mut UiDrawList,
+    fonts: RichTextFonts<'_>,
+    payload: &DragPayload,
+    (min, size): (Vec2, Vec2),
+    alpha: f32,
+) {
+    if let Some((texture, uv_min, uv_max)) = payload.icon {
+        draw.push_image(
+            texture,
+            (min, size),
+            (uv_min, uv_max),
+            [1.0, 1.0, 1.0, alpha],
+        );
+    } else {
+        draw.push_text(
+            &plain_text(&payload.label),
+            fonts,
+            (min, size),
+            &TextStyle::default().with_color([1.0, 1.0, 1.0, alpha]),
+            Anchor::Center,
+            true,
+        );
+    }
+}
+
+/// A payload dropped onto a target
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropEvent {
+    /// Index of the widget the drag started on
+    pub source: usize,
+    /// Index of the widget that took the drop
+    pub target: usize,
+    /// What was dropped
+    pub payload: DragPayload,
+}
+
+/// A drag in progress
+#[derive(Debug, Clone)]
+struct ActiveDrag {
+    /// Widget the drag started on
+    source: usize,
+    /// What is being carried
+    payload: DragPayload,
+    /// Cursor minus the source's corner at the start, so the ghost
+    /// keeps the grab point
+    grab_offset: Vec2,
+    /// Size of the ghost
+    size: Vec2,
+    /// Last cursor position
+    cursor: Vec2,
+    /// Widget under the cursor that accepts the payload, with its bounds
+    target: Option<(usize, Vec2, Vec2)>,
+}
+
+/// Tracks a drag across a list of widgets
+#[derive(Debug, Clone, Default)]
+pub struct DragDrop {
+    /// Widget pressed on and where, before the drag threshold is crossed
+    pressed: Option<(usize, Vec2)>,
+    /// Drag in progress
+    active: Option<ActiveDrag>,
+}
+
+impl DragDrop {
+    /// Create an idle tracker
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if a payload is being dragged
+    #[must_use]
+    pub fn is_dragging(&self) -> bool {
+        self.active.is_some()
+    }
+
+    /// Get the payload being dragged
+    #[must_use]
+    pub fn payload(&self) -> Option<&DragPayload> {
+        self.active.as_ref().map(|drag| &drag.payload)
+    }
+
+    /// Get the index of the widget that would take a drop right now
+    #[must_use]
+    pub fn target(&self) -> Option<usize> {
+        self.active.as_ref()?.target.map(|(index, _, _)| index)
+    }
+
+    /// Handle mouse button down, returning whether a draggable widget was hit
+    ///
+    /// The topmost widget under the cursor, by `input_order`, is the one hit.
+    pub fn on_mouse_down(
+        &mut self,
+        widgets: &[&mut dyn Widget],
+        position: Vec2,
+        parent_size: Vec2,
+    ) -> bool {
+        self.pressed = input_order(widgets)
+            .into_iter()
+            .find(|&index| widgets[index].rect().contains(position, parent_size))
+            .filter(|&index| widgets[index].drag_payload().is_some())
+            .map(|index| (index, position));
+        self.pressed.is_some()
+    }
+
+    /// Handle mouse movement, starting a drag once the press has moved far
+    /// enough and tracking the target under the cursor
+    pub fn on_mouse_move(
+        &mut self,
+        widgets: &[&mut dyn Widget],
+        position: Vec2,
+        parent_size: Vec2,
+    ) {
+        if let Some((source, start)) = self.pressed
+            && position.distance(start) >= DRAG_THRESHOLD
+        {
+            self.pressed = None;
+            if let Some(widget) = widgets.get(source)
+                && let Some(payload) = widget.drag_payload()
+            {
+                let (min, _) = widget.rect().bounds(parent_size);
+                self.active = Some(ActiveDrag {
+                    source,
+                    payload,
+                    grab_offset: start - min,
+                    size: widget.rect().size,
+                    cursor: position,
+                    target: None,
+                });
+            }
+        }
+
+        let Some(drag) = &mut self.active else {
+            return;
+        };
+        drag.cursor = position;
+        drag.target = input_order(widgets)
+            .into_iter()
+            .find(|&index| {
+                index != drag.source && widgets[index].rect().contains(position, parent_size)
+            })
+            .filter(|&index| widgets[index].accepts_drop(&drag.payload))
+            .map(|index| {
+                let rect = widgets[index].rect();
+                (index, rect.absolute_position(parent_size), rect.size)
+            });
+    }
+
+    /// Handle mouse button up, delivering the payload to the target
+    ///
+    /// The source is told whether the drop was taken either way, so it can
+    /// clear or restore what it showed.
+    pub fn on_mouse_up(
+        &mut self,
+        widgets: &mut [&mut dyn Widget],
+        position: Vec2,
+        parent_size: Vec2,
+    ) -> Option<DropEvent> {
+        self.pressed = None;
+        self.on_mouse_move(widgets, position, parent_size);
+        let drag = self.active.take()?;
+        let target = drag.target.map(|(index, _, _)| index);
+        let dropped = target.is_some_and(|index| widgets[index].on_drop(&drag.payload));
+        if let Some(source) = widgets.get_mut(drag.source) {
+            source.on_drag_end(&drag.payload, dropped);
+        }
+        Some(DropEvent {
+            source: drag.source,
+            target: target.filter(|_| dropped)?,
+            payload: drag.payload,
+        })
+    }
+
+    /// Abandon the current drag, e.g. when the menu closes
+    pub fn cancel(&mut self, widgets: &mut [&mut dyn Widget]) {
+        self.pressed = None;
+        if let Some(drag) = self.active.take()
+            && let Some(source) = widgets.get_mut(drag.source)
+        {
+            source.on_drag_end(&drag.payload, false);
+        }
+    }
+
+    /// Add the target outline and the ghost under the cursor, on the
+    /// overlay layer
+    pub fn draw(&self, draw: &mut UiDrawList, fonts: RichTextFonts<'_>) {
+        let Some(drag) = &self.active else {
+            return;
+        };
+        let z = draw.z();
+        draw.set_z(OVERLAY_LAYER);
+        if let Some((_, min, size)) = drag.target {
+            let outline = RectStyle::default()
+                .with_background([0.0; 4])
+                .with_border_color(ACCENT_COLOR)
+                .with_border_width(TARGET_OUTLINE);
+            draw.push_styled_rect(min, size, &outline);
+        }
+        let min = drag.cursor - drag.grab_offset;
+        draw.push_rect(min, drag.size, [0.2, 0.2, 0.2, GHOST_ALPHA * 0.5]);
+        push_payload(draw, fonts, &drag.payload, (min, drag.size), GHOST_ALPHA);
+        draw.set_z(z);
+    }
+}
+
+/// A cell holding at most one payload, for inventories and skill bars
+///
+/// Dragging a filled slot onto another moves the payload there; what the
+/// target held before is kept for `take_replaced`, so the game can swap it
+/// back into the source.
+#[derive(Debug, Clone)]
+pub struct Slot {
+    /// Rectangle
+    pub rect: Rect,
+    /// Payload shown in the slot
+    item: Option<DragPayload>,
+    /// Payload kinds the slot takes; empty takes any
+    accepts: Vec<String>,
+    /// Payload replaced by the last drop
+    replaced: Option<DragPayload>,
+    /// Current state
+    state: WidgetState,
+}
+
+impl Slot {
+    /// Create an empty slot that takes any payload
+    #[must_use]
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            rect,
+            item: None,
+            accepts: Vec::new(),
+            replaced: None,
+            state: WidgetState::Normal,
+        }
+    }
+
+    /// Start with a payload in the slot
+    #[must_use]
+    pub fn with_item(mut self, item: DragPayload) -> Self {
+        self.item = Some(item);
+        self
+    }
+
+    /// Only take payloads of these kinds
+    #[must_use]
+    pub fn with_accepts<S: Into<String>>(mut self, kinds: impl IntoIterator<Item = S>) -> Self {
+        self.accepts = kinds.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Get the payload in the slot
+    #[must_use]
+    pub fn item(&self) -> Option<&DragPayload> {
+        self.item.as_ref()
+    }
+
+    /// Replace the payload in the slot, returning the old one
+    pub fn set_item(&mut self, item: Option<DragPayload>) -> Option<DragPayload> {
+        std::mem::replace(&mut self.item, item)
+    }
+
+    /// Take the payload a drop pushed out of the slot (resets after check)
+    pub fn take_replaced(&mut self) -> Option<DragPayload> {
+        self.replaced.take()
+    }
+
+    /// Disable or enable the slot
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.state = if disabled {
+            WidgetState::Disabled
+        } else {
+            WidgetState::Normal
+        };
+    }
+}
+
+impl Widget for Slot {
+    fn rect(&self) -> &Rect {
+        &self.rect
+    }
+
+    fn rect_mut(&mut self) -> &mut Rect {
+        &mut self.rect
+    }
+
+    fn state(&self) -> WidgetState {
+        self.state
+    }
+
+    fn on_mouse_move(&mut self, position: Vec2, parent_size: Vec2) {
+        if self.state == WidgetState::Disabled {
+            return;
+        }
+        self.state = if self.rect.contains(position, parent_size) {
+            WidgetState::Hovered
+        } else {
+            WidgetState::Normal
+        };
+    }
+
+    fn on_mouse_down(&mut self, position: Vec2, parent_size: Vec2) -> bool {
+        self.state != WidgetState::Disabled && self.rect.contains(position, parent_size)
+    }
+
+    fn on_mouse_up(&mut self, _position: Vec2, _parent_size: Vec2) -> bool {
+        false
+    }
+
+    fn draw(&self, draw: &mut UiDrawList, fonts: RichTextFonts<'_>, parent_size: Vec2) {
+        let min = self.rect.absolute_position(parent_size);
+        push_widget_rect(draw, min, self.rect.size, &self.rect.style, self.state);
+        if let Some(item) = &self.item {
+            let alpha = if self.state == WidgetState::Disabled {
+                0.5
+            } else {
+                1.0
+            };
+            push_payload(draw, fonts, item, (min, self.rect.size), alpha);
+        }
+    }
+
+    fn drag_payload(&self) -> Option<DragPayload> {
+        self.item
+            .clone()
+            .filter(|_| self.state != WidgetState::Disabled)
+    }
+
+    fn accepts_drop(&self, payload: &DragPayload) -> bool {
+        self.state != WidgetState::Disabled
+            && (self.accepts.is_empty() || self.accepts.contains(&payload.kind))
+    }
+
+    fn on_drop(&mut self, payload: &DragPayload) -> bool {
+        if !self.accepts_drop(payload) {
+            return false;
+        }
+        self.replaced = self.item.replace(payload.clone());
+        true
+    }
+
+    fn on_drag_end(&mut self, payload: &DragPayload, dropped: bool) {
+        if dropped && self.item.as_ref() == Some(payload) {
+            self.item = None;
+        }
+    }
+
+    fn tooltip(&self) -> Option<&str> {
+        self.item
+            .as_ref()
+            .map(|item| item.label.as_str())
+            .filter(|label| !label.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARENT: Vec2 = Vec2::new(800.0, 600.0);
+
+    fn drag(
+        tracker: &mut DragDrop,
+        widgets: &mut [&mut dyn Widget],
+        from: Vec2,
+        to: Vec2,
+    ) -> Option<DropEvent> {
+        tracker.on_mouse_down(widgets, from, PARENT);
+        tracker.on_mouse_move(widgets, (from + to) * 0.5, PARENT);
+        tracker.on_mouse_move(widgets, to, PARENT);
+        tracker.on_mouse_up(widgets, to, PARENT)
+    }
+
+    #[test]
+    fn test_drag_moves_item_between_slots() {
+        let sword = DragPayload::new("item", 7).with_label("Sword");
+        let mut bag = Slot::new(Rect::new(0.0, 0.0, 40.0, 40.0)).with_item(sword.clone());
+        let mut hand = Slot::new(Rect::new(100.0, 0.0, 40.0, 40.0));
+        let mut tracker = DragDrop::new();
+
+        let mut widgets: [&mut dyn Widget; 2] = [&mut bag, &mut hand];
+        let event = drag(
+            &mut tracker,
+            &mut widgets,
+            Vec2::new(20.0, 20.0),
+            Vec2::new(120.0, 20.0),
+        )
+        .unwrap();
+        assert_eq!((event.source, event.target), (0, 1));
+        assert_eq!(event.payload, sword);
+        assert!(bag.item().is_none());
+        assert_eq!(hand.item(), Some(&sword));
+        assert!(!tracker.is_dragging());
+    }
+
+    #[test]
+    fn test_rejected_drop_and_click_keep_item() {
+        let potion = DragPayload::new("item", 3);
+        let mut bag = Slot::new(Rect::new(0.0, 0.0, 40.0, 40.0)).with_item(potion.clone());
+        let mut skills = Slot::new(Rect::new(100.0, 0.0, 40.0, 40.0)).with_accepts(["skill"]);
+        let mut tracker = DragDrop::new();
+        let mut widgets: [&mut dyn Widget; 2] = [&mut bag, &mut skills];
+
+        let from = Vec2::new(20.0, 20.0);
+        assert!(drag(&mut tracker, &mut widgets, from, Vec2::new(120.0, 20.0)).is_none());
+        assert!(drag(&mut tracker, &mut widgets, from, from + Vec2::ONE).is_none());
+        assert!(!tracker.is_dragging());
+        assert_eq!(bag.item(), Some(&potion));
+        assert!(skills.item().is_none());
+    }
+}
@@ -0,0 +1,500 @@
+//! Widget draw data
+//!
+//! Widgets write their quads, images and glyphs into a [`UiDrawList`] each
+//! frame, which is then drawn with the renderer's UI and text pipelines.
+//! Images are textured quads batched per [`UiTextureId`]. Text is
+//! laid out with the rich text layouter, so it wraps inside the widget's
+//! rect and can be aligned to any [`Anchor`].
+
+use glam::Vec2;
+
+use super::rect::{Anchor, RectStyle};
+use super::widget::{Widget, WidgetState};
+use crate::renderer::{
+    GlyphInstance, Renderer, RichTextFonts, RichTextLayout, TextSpan, TextStyle, Texture, UiRect,
+    layout_rich_text, measure_rich_text,
+};
+
+/// Slot of an image texture in [`UiTextures::images`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UiTextureId(pub u32);
+
+/// Textured quads sharing one texture
+#[derive(Debug, Clone)]
+pub struct ImageBatch {
+    /// Texture the quads sample
+    pub texture: UiTextureId,
+    /// Quads with UVs and tint
+    pub quads: Vec<GlyphInstance>,
+}
+
+/// Atlas textures matching the fonts the draw list was built with
+#[derive(Debug, Clone, Copy)]
+pub struct UiTextures<'a> {
+    /// Atlas of the regular face
+    pub regular: &'a Texture,
+    /// Atlas of the bold face, if it differs
+    pub bold: Option<&'a Texture>,
+    /// UI atlas for inline icons
+    pub icons: Option<&'a Texture>,
+    /// Image textures, indexed by [`UiTextureId`]
+    pub images: &'a [&'a Texture],
+}
+
+impl<'a> UiTextures<'a> {
+    /// Use a single font atlas without icons
+    #[must_use]
+    pub const fn new(regular: &'a Texture) -> Self {
+        Self {
+            regular,
+            bold: None,
+            icons: None,
+            images: &[],
+        }
+    }
+
+    /// Set the bold face atlas
+    #[must_use]
+    pub const fn with_bold(mut self, bold: &'a Texture) -> Self {
+        self.bold = Some(bold);
+        self
+    }
+
+    /// Set the icon atlas
+    #[must_use]
+    pub const fn with_icons(mut self, icons: &'a Texture) -> Self {
+        self.icons = Some(icons);
+        self
+    }
+
+    /// Set the image textures
+    #[must_use]
+    pub const fn with_images(mut self, images: &'a [&'a Texture]) -> Self {
+        self.images = images;
+        self
+    }
+}
+
+/// Layer of modal windows, above ordinary widgets
+pub const MODAL_LAYER: i32 = 100;
+
+/// Layer of open popups such as dropdown lists
+pub const POPUP_LAYER: i32 = 200;
+
+/// Layer of tooltips and drag ghosts, above everything
+pub const OVERLAY_LAYER: i32 = 300;
+
+/// Quads and glyphs sharing one layer and clip rect
+#[derive(Debug, Clone, Default)]
+pub struct UiDrawBatch {
+    /// Layer; higher layers draw later
+    pub z: i32,
+    /// Screen (min, size) that drawing is cut to, if any
+    pub clip: Option<(Vec2, Vec2)>,
+    /// Solid rectangles, drawn first
+    pub rects: Vec<UiRect>,
+    /// Textured quads, drawn over the rectangles
+    pub images: Vec<ImageBatch>,
+    /// Glyphs and icons, drawn over the rectangles
+    pub text: RichTextLayout,
+}
+
+impl UiDrawBatch {
+    /// Check if the batch draws nothing
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+            && self.images.is_empty()
+            && self.text.regular.is_empty()
+            && self.text.bold.is_empty()
+            && self.text.icons.is_empty()
+    }
+}
+
+/// Quads and glyphs for one frame of UI
+///
+/// Everything pushed lands in a batch tagged with the current layer and
+/// clip rect. Batches draw in layer order, and in push order within a
+/// layer, each cut to its clip rect with a scissor.
+#[derive(Debug, Clone, Default)]
+pub struct UiDrawList {
+    /// Batches in push order
+    batches: Vec<UiDrawBatch>,
+    /// Nested clip rects, each already cut to the one before
+    clips: Vec<(Vec2, Vec2)>,
+    /// Current layer
+    z: i32,
+}
+
+impl UiDrawList {
+    /// Create an empty draw list
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove everything and return to layer 0 without clipping
+    pub fn clear(&mut self) {
+        self.batches.clear();
+        self.clips.clear();
+        self.z = 0;
+    }
+
+    /// Check if there is nothing to draw
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.batches.iter().all(UiDrawBatch::is_empty)
+    }
+
+    /// Get the batches in push order
+    #[must_use]
+    pub fn batches(&self) -> &[UiDrawBatch] {
+        &self.batches
+    }
+
+    /// Get the current layer
+    #[must_use]
+    pub fn z(&self) -> i32 {
+        self.z
+    }
+
+    /// Draw what follows on a layer
+    pub fn set_z(&mut self, z: i32) {
+        self.z = z;
+    }
+
+    /// Get the current clip rect as screen (min, size)
+    #[must_use]
+    pub fn clip(&self) -> Option<(Vec2, Vec2)> {
+        self.clips.last().copied()
+    }
+
+    /// Cut what follows to a rect, inside any clip already pushed
+    pub fn push_clip(&mut self, min: Vec2, size: Vec2) {
+        let (min, max) = match self.clip() {
+            Some((outer_min, outer_size)) => {
+                (min.max(outer_min), (min + size).min(outer_min + outer_size))
+            }
+            None => (min, min + size),
+        };
+        self.clips.push((min, (max - min).max(Vec2::ZERO)));
+    }
+
+    /// Restore the clip rect from before the last `push_clip`
+    pub fn pop_clip(&mut self) {
+        self.clips.pop();
+    }
+
+    /// Get the batch for the current layer and clip rect
+    pub fn batch_mut(&mut self) -> &mut UiDrawBatch {
+        let (z, clip) = (self.z, self.clip());
+        let reuse = self
+            .batches
+            .last()
+            .is_some_and(|batch| batch.z == z && batch.clip == clip);
+        if !reuse {
+            self.batches.push(UiDrawBatch {
+                z,
+                clip,
+                ..UiDrawBatch::default()
+            });
+        }
+        let last = self.batches.len() - 1;
+        &mut self.batches[last]
+    }
+
+    /// Add a solid rectangle
+    pub fn push_rect(&mut self, min: Vec2, size: Vec2, color: [f32; 4]) {
+        self.batch_mut().rects.push(UiRect {
+            position: min.into(),
+            size: size.into(),
+            color,
+        });
+    }
+
+    /// Add a textured quad showing the `uv` (min, max) region of a texture
+    ///
+    /// `tint` multiplies the texture color; use white to draw it unchanged.
+    pub fn push_image(
+        &mut self,
+        texture: UiTextureId,
+        (min, size): (Vec2, Vec2),
+        (uv_min, uv_max): (Vec2, Vec2),
+        tint: [f32; 4],
+    ) {
+        let quad = GlyphInstance {
+            position: min.into(),
+            size: size.into(),
+            uv_min: uv_min.into(),
+            uv_max: uv_max.into(),
+            color: tint,
+        };
+        let images = &mut self.batch_mut().images;
+        match images.last_mut() {
+            Some(batch) if batch.texture == texture => batch.quads.push(quad),
+            _ => images.push(ImageBatch {
+                texture,
+                quads: vec![quad],
+            }),
+        }
+    }
+
+    /// Add a rectangle with its style's background and border
+    ///
+    /// Corner radii are not drawn yet.
+    pub fn push_styled_rect(&mut self, min: Vec2, size: Vec2, style: &RectStyle) {
+        self.push_styled_rect_with(min, size, style, style.background_color);
+    }
+
+    /// Add a styled rectangle with a different background color
+    fn push_styled_rect_with(
+        &mut self,
+        min: Vec2,
+        size: Vec2,
+        style: &RectStyle,
+        background: [f32; 4],
+    ) {
+        self.push_rect(min, size, background);
+        let border = style.border_width.min(size.x * 0.5).min(size.y * 0.5);
+        if border <= 0.0 || style.border_color[3] <= 0.0 {
+            return;
+        }
+        let color = style.border_color;
+        let inner = size.y - border * 2.0;
+        self.push_rect(min, Vec2::new(size.x, border), color);
+        self.push_rect(
+            Vec2::new(min.x, min.y + size.y - border),
+            Vec2::new(size.x, border),
+            color,
+        );
+        self.push_rect(
+            Vec2::new(min.x, min.y + border),
+            Vec2::new(border, inner),
+            color,
+        );
+        self.push_rect(
+            Vec2::new(min.x + size.x - border, min.y + border),
+            Vec2::new(border, inner),
+            color,
+        );
+    }
+
+    /// Lay out text inside a rect given by (min, size), aligned to `align`
+    ///
+    /// Wrapped text breaks at the rect width; the laid out block as a whole
+    /// is aligned.
+    pub fn push_text(
+        &mut self,
+        spans: &[TextSpan],
+        fonts: RichTextFonts<'_>,
+        (min, size): (Vec2, Vec2),
+        style: &TextStyle,
+        align: Anchor,
+        wrap: bool,
+    ) {
+        let wrap_width = wrap.then_some(size.x);
+        let measured = measure_rich_text(spans, fonts, wrap_width, style);
+        let (ox, oy) = align.offset();
+        let position = min + (size - measured) * Vec2::new(ox, oy);
+        let layout = layout_rich_text(spans, fonts, position.round(), wrap_width, style);
+        let text = &mut self.batch_mut().text;
+        text.regular.extend(layout.regular);
+        text.bold.extend(layout.bold);
+        text.icons.extend(layout.icons);
+        text.size = text.size.max(layout.size);
+    }
+
+    /// Add a widget's quads and text on the widget's layer
+    pub fn push_widget(
+        &mut self,
+        widget: &dyn Widget,
+        fonts: RichTextFonts<'_>,
+        parent_size: Vec2,
+    ) {
+        let z = std::mem::replace(&mut self.z, widget.z_index());
+        widget.draw(self, fonts, parent_size);
+        self.z = z;
+    }
+
+    /// Draw the batches by layer, each as rectangles, then images, then
+    /// the text over them
+    ///
+    /// `fonts` must be the fonts the list was built with.
+    pub fn draw<'a>(
+        &self,
+        renderer: &'a Renderer,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        fonts: RichTextFonts<'_>,
+        textures: UiTextures<'_>,
+    ) {
+        let style = TextStyle::default();
+        let (width, height) = renderer.size();
+        let mut order: Vec<&UiDrawBatch> = self.batches.iter().collect();
+        order.sort_by_key(|batch| batch.z);
+        for batch in order {
+            if batch.is_empty() {
+                continue;
+            }
+            let Some([x, y, w, h]) = scissor_rect(batch.clip, (width, height)) else {
+                continue;
+            };
+            render_pass.set_scissor_rect(x, y, w, h);
+            renderer.draw_ui(render_pass, &batch.rects);
+            for images in &batch.images {
+                if let Some(texture) = textures.images.get(images.texture.0 as usize) {
+                    renderer.draw_icons(render_pass, texture, &images.quads);
+                }
+            }
+            renderer.draw_text(
+                render_pass,
+                fonts.regular,
+                textures.regular,
+                &batch.text.regular,
+                &style,
+            );
+            renderer.draw_text(
+                render_pass,
+                fonts.bold.unwrap_or(fonts.regular),
+                textures.bold.unwrap_or(textures.regular),
+                &batch.text.bold,
+                &style,
+            );
+            if let Some(icons) = textures.icons {
+                renderer.draw_icons(render_pass, icons, &batch.text.icons);
+            }
+        }
+        render_pass.set_scissor_rect(0, 0, width, height);
+    }
+}
+
+/// Scissor `[x, y, width, height]` in target pixels for a clip rect
+///
+/// Returns `None` when the clip covers nothing on the target.
+fn scissor_rect(clip: Option<(Vec2, Vec2)>, (width, height): (u32, u32)) -> Option<[u32; 4]> {
+    let target = Vec2::new(width as f32, height as f32);
+    let (min, size) = clip.unwrap_or((Vec2::ZERO, target));
+    let max = (min + size).clamp(Vec2::ZERO, target).ceil();
+    let min = min.clamp(Vec2::ZERO, target).floor();
+    let size = max - min;
+    (size.x >= 1.0 && size.y >= 1.0).then_some([
+        min.x as u32,
+        min.y as u32,
+        size.x as u32,
+        size.y as u32,
+    ])
+}
+
+/// Highlight for checked and selected parts of widgets
+pub(super) const ACCENT_COLOR: [f32; 4] = [0.0, 0.9, 0.9, 1.0];
+
+/// Outline drawn around the focused widget
+pub(super) const FOCUS_COLOR: [f32; 4] = [1.0, 0.8, 0.2, 1.0];
+
+/// Width of the focus outline
+const FOCUS_WIDTH: f32 = 2.0;
+
+/// A single unstyled span of text
+pub(super) fn plain_text(text: &str) -> [TextSpan; 1] {
+    [TextSpan::Text {
+        text: text.to_string(),
+        color: None,
+        bold: false,
+    }]
+}
+
+/// Outline a focused widget
+pub(super) fn push_focus_ring(draw: &mut UiDrawList, min: Vec2, size: Vec2) {
+    let ring = RectStyle::default()
+        .with_border_color(FOCUS_COLOR)
+        .with_border_width(FOCUS_WIDTH);
+    let offset = Vec2::splat(FOCUS_WIDTH);
+    draw.push_styled_rect_with(min - offset, size + offset * 2.0, &ring, [0.0; 4]);
+}
+
+/// Background color of an interactive widget in a state
+pub(super) fn state_color(color: [f32; 4], state: WidgetState) -> [f32; 4] {
+    let [r, g, b, a] = color;
+    match state {
+        WidgetState::Normal => color,
+        WidgetState::Hovered => [r + 0.1, g + 0.1, b + 0.1, a],
+        WidgetState::Pressed => [r * 0.7, g * 0.7, b * 0.7, a],
+        WidgetState::Disabled => [r, g, b, a * 0.5],
+    }
+}
+
+/// Add a widget background in its interaction state
+pub(super) fn push_widget_rect(
+    draw: &mut UiDrawList,
+    min: Vec2,
+    size: Vec2,
+    style: &RectStyle,
+    state: WidgetState,
+) {
+    draw.push_styled_rect_with(min, size, style, state_color(style.background_color, state));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_styled_rect_border() {
+        let mut draw = UiDrawList::new();
+        let style = RectStyle::default().with_border_width(2.0);
+        draw.push_styled_rect(Vec2::new(10.0, 10.0), Vec2::new(100.0, 40.0), &style);
+        let rects = &draw.batches()[0].rects;
+        assert_eq!(rects.len(), 5);
+        assert_eq!(rects[2].position, [10.0, 48.0]);
+        assert_eq!(rects[3].size, [2.0, 36.0]);
+
+        draw.clear();
+        assert!(draw.is_empty());
+        draw.push_styled_rect(Vec2::ZERO, Vec2::ONE, &style.with_border_width(0.0));
+        assert_eq!(draw.batches()[0].rects.len(), 1);
+    }
+
+    #[test]
+    fn test_layers_and_nested_clips() {
+        let mut draw = UiDrawList::new();
+        draw.push_rect(Vec2::ZERO, Vec2::ONE, [1.0; 4]);
+        draw.push_clip(Vec2::new(10.0, 10.0), Vec2::new(100.0, 100.0));
+        draw.push_clip(Vec2::new(50.0, 0.0), Vec2::new(100.0, 30.0));
+        draw.push_rect(Vec2::ZERO, Vec2::ONE, [1.0; 4]);
+        assert_eq!(
+            draw.clip(),
+            Some((Vec2::new(50.0, 10.0), Vec2::new(60.0, 20.0)))
+        );
+        draw.pop_clip();
+        draw.pop_clip();
+        draw.set_z(POPUP_LAYER);
+        draw.push_rect(Vec2::ZERO, Vec2::ONE, [1.0; 4]);
+        draw.set_z(0);
+        draw.push_rect(Vec2::ZERO, Vec2::ONE, [1.0; 4]);
+
+        let batches = draw.batches();
+        assert_eq!(batches.len(), 4);
+        assert_eq!(batches[2].z, POPUP_LAYER);
+        assert_eq!(batches[3].clip, None);
+        assert_eq!(
+            scissor_rect(batches[1].clip, (80, 600)),
+            Some([50, 10, 30, 20])
+        );
+        assert_eq!(
+            scissor_rect(Some((Vec2::splat(900.0), Vec2::ONE)), (800, 600)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_images_batch_by_texture() {
+        let mut draw = UiDrawList::new();
+        let quad = (Vec2::ZERO, Vec2::splat(16.0));
+        let uv = (Vec2::ZERO, Vec2::ONE);
+        draw.push_image(UiTextureId(0), quad, uv, [1.0; 4]);
+        draw.push_image(UiTextureId(0), quad, uv, [1.0; 4]);
+        draw.push_image(UiTextureId(1), quad, uv, [1.0; 4]);
+        let images = &draw.batches()[0].images;
+        assert_eq!(images.len(), 2);
+        assert_eq!(images[0].quads.len(), 2);
+    }
+}
@@ -0,0 +1,496 @@
+//! Dropdown widget
+//!
+//! A box showing the selected item that opens a popup list below it (or
+//! above, near the bottom of the screen). While open the dropdown takes
+//! every click, so a click elsewhere only closes it, and moves to the popup
+//! layer so the list draws over neighbouring widgets.
+
+use glam::Vec2;
+use winit::keyboard::KeyCode;
+
+use super::draw::{
+    ACCENT_COLOR, POPUP_LAYER, UiDrawList, plain_text, push_focus_ring, push_widget_rect,
+};
+use super::rect::{Anchor, Rect};
+use super::widget::{Widget, WidgetState};
+use crate::renderer::{RichTextFonts, TextStyle};
+
+/// Gap between an item's text and the box edge
+const ITEM_PADDING: f32 = 8.0;
+
+/// A box that picks one item from a popup list
+#[derive(Debug, Clone)]
+pub struct Dropdown {
+    /// Rectangle of the closed box
+    pub rect: Rect,
+    /// Item captions, top to bottom
+    pub items: Vec<String>,
+    /// Text shown when nothing is selected
+    pub placeholder: String,
+    /// Most rows the popup shows before scrolling
+    pub max_visible: usize,
+    /// Selected item
+    selected: Option<usize>,
+    /// Whether the popup is showing
+    open: bool,
+    /// Row under the mouse or keyboard cursor in the popup
+    highlighted: Option<usize>,
+    /// First row shown in the popup
+    scroll: usize,
+    /// Row the mouse was pressed on
+    pressed: Option<usize>,
+    /// Current state
+    state: WidgetState,
+    /// Whether the dropdown has keyboard focus
+    focused: bool,
+    /// Whether the selection changed since last checked
+    changed: bool,
+}
+
+impl Dropdown {
+    /// Create a closed dropdown with nothing selected
+    #[must_use]
+    pub fn new(items: impl IntoIterator<Item = impl Into<String>>, rect: Rect) -> Self {
+        Self {
+            rect,
+            items: items.into_iter().map(Into::into).collect(),
+            placeholder: String::new(),
+            max_visible: 8,
+            selected: None,
+            open: false,
+            highlighted: None,
+            scroll: 0,
+            pressed: None,
+            state: WidgetState::Normal,
+            focused: false,
+            changed: false,
+        }
+    }
+
+    /// Start with an item selected
+    #[must_use]
+    pub fn with_selected(mut self, index: usize) -> Self {
+        self.selected = (index < self.items.len()).then_some(index);
+        self
+    }
+
+    /// Set the text shown when nothing is selected
+    #[must_use]
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Set how many rows the popup shows before scrolling
+    #[must_use]
+    pub fn with_max_visible(mut self, rows: usize) -> Self {
+        self.max_visible = rows.max(1);
+        self
+    }
+
+    /// Get the selected item index
+    #[must_use]
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Get the selected item caption
+    #[must_use]
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selected
+            .and_then(|i| self.items.get(i))
+            .map(String::as_str)
+    }
+
+    /// Select an item as if picked from the popup
+    pub fn select(&mut self, index: usize) {
+        if self.is_disabled() || index >= self.items.len() || self.selected == Some(index) {
+            return;
+        }
+        self.selected = Some(index);
+        self.changed = true;
+    }
+
+    /// Check if the user changed the selection (resets after check)
+    pub fn was_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
+    /// Check if the popup is showing
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Show the popup with the selected item highlighted
+    pub fn open(&mut self) {
+        if self.is_disabled() || self.items.is_empty() {
+            return;
+        }
+        self.open = true;
+        self.highlight(self.selected.unwrap_or(0));
+    }
+
+    /// Hide the popup
+    pub fn close(&mut self) {
+        self.open = false;
+        self.pressed = None;
+    }
+
+    /// Disable or enable the dropdown
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.state = if disabled {
+            WidgetState::Disabled
+        } else {
+            WidgetState::Normal
+        };
+        if disabled {
+            self.focused = false;
+            self.close();
+        }
+    }
+
+    /// Check if the dropdown is disabled
+    #[must_use]
+    pub fn is_disabled(&self) -> bool {
+        self.state == WidgetState::Disabled
+    }
+
+    /// Check if the dropdown takes input at a point instead of what is beneath
+    ///
+    /// While open this is every point, so outside clicks close the popup.
+    #[must_use]
+    pub fn captures(&self, position: Vec2, parent_size: Vec2) -> bool {
+        self.open || self.rect.contains(position, parent_size)
+    }
+
+    /// Number of rows the popup shows
+    fn visible_rows(&self) -> usize {
+        self.items.len().min(self.max_visible)
+    }
+
+    /// Screen (min, size) of the popup, flipped above the box if it would
+    /// leave the screen
+    fn popup_bounds(&self, parent_size: Vec2) -> (Vec2, Vec2) {
+        let min = self.rect.absolute_position(parent_size);
+        let size = Vec2::new(
+            self.rect.size.x,
+            self.rect.size.y * self.visible_rows() as f32,
+        );
+        let below = min.y + self.rect.size.y;
+        let y = if below + size.y > parent_size.y && min.y - size.y >= 0.0 {
+            min.y - size.y
+        } else {
+            below
+        };
+        (Vec2::new(min.x, y), size)
+    }
+
+    /// Find the popup row under a screen position
+    fn row_at(&self, position: Vec2, parent_size: Vec2) -> Option<usize> {
+        let (min, size) = self.popup_bounds(parent_size);
+        if !self.open
+            || self.rect.size.y <= 0.0
+            || !(position.cmpge(min).all() && position.cmplt(min + size).all())
+        {
+            return None;
+        }
+        let row = self.scroll + ((position.y - min.y) / self.rect.size.y) as usize;
+        (row < self.items.len()).then_some(row)
+    }
+
+    /// Highlight a row, scrolling it into view
+    fn highlight(&mut self, index: usize) {
+        let index = index.min(self.items.len().saturating_sub(1));
+        self.highlighted = Some(index);
+        let rows = self.visible_rows();
+        if index < self.scroll {
+            self.scroll = index;
+        } else if index >= self.scroll + rows {
+            self.scroll = index + 1 - rows;
+        }
+    }
+
+    /// Move the keyboard cursor by `step` rows, stopping at the ends
+    fn step(&mut self, step: isize) {
+        if self.items.is_empty() {
+            return;
+        }
+        let last = self.items.len() as isize - 1;
+        let current = if self.open {
+            self.highlighted
+        } else {
+            self.selected
+        };
+        let next = current.map_or(0, |i| (i as isize + step).clamp(0, last)) as usize;
+        if self.open {
+            self.highlight(next);
+        } else {
+            self.select(next);
+        }
+    }
+}
+
+impl Widget for Dropdown {
+    fn rect(&self) -> &Rect {
+        &self.rect
+    }
+
+    fn rect_mut(&mut self) -> &mut Rect {
+        &mut self.rect
+    }
+
+    fn state(&self) -> WidgetState {
+        self.state
+    }
+
+    fn on_mouse_move(&mut self, position: Vec2, parent_size: Vec2) {
+        if self.is_disabled() {
+            return;
+        }
+        if let Some(row) = self.row_at(position, parent_size) {
+            self.highlighted = Some(row);
+        }
+        if self.state != WidgetState::Pressed {
+            self.state = if self.rect.contains(position, parent_size) {
+                WidgetState::Hovered
+            } else {
+                WidgetState::Normal
+            };
+        }
+    }
+
+    fn on_mouse_down(&mut self, position: Vec2, parent_size: Vec2) -> bool {
+        if self.is_disabled() {
+            return false;
+        }
+        if self.rect.contains(position, parent_size) {
+            self.focused = true;
+            self.state = WidgetState::Pressed;
+            return true;
+        }
+        if self.open {
+            self.pressed = self.row_at(position, parent_size);
+            if self.pressed.is_none() {
+                self.close();
+            }
+            return true;
+        }
+        self.focused = false;
+        false
+    }
+
+    fn on_mouse_up(&mut self, position: Vec2, parent_size: Vec2) -> bool {
+        if self.state == WidgetState::Pressed {
+            let inside = self.rect.contains(position, parent_size);
+            self.state = if inside {
+                WidgetState::Hovered
+            } else {
+                WidgetState::Normal
+            };
+            if inside {
+                if self.open {
+                    self.close();
+                } else {
+                    self.open();
+                }
+            }
+            return inside;
+        }
+        let Some(pressed) = self.pressed.take() else {
+            return false;
+        };
+        if self.row_at(position, parent_size) == Some(pressed) {
+            self.select(pressed);
+            self.close();
+        }
+        true
+    }
+
+    fn draw(&self, draw: &mut UiDrawList, fonts: RichTextFonts<'_>, parent_size: Vec2) {
+        let min = self.rect.absolute_position(parent_size);
+        let size = self.rect.size;
+        push_widget_rect(draw, min, size, &self.rect.style, self.state);
+        if self.focused {
+            push_focus_ring(draw, min, size);
+        }
+
+        let mut color = [1.0; 4];
+        let caption = match self.selected_text() {
+            Some(text) => text,
+            None => {
+                color[3] = 0.5;
+                &self.placeholder
+            }
+        };
+        if self.is_disabled() {
+            color[3] *= 0.5;
+        }
+        let arrow = size.y;
+        let padding = Vec2::new(ITEM_PADDING, 0.0);
+        draw.push_text(
+            &plain_text(caption),
+            fonts,
+            (
+                min + padding,
+                (size - padding - Vec2::new(arrow, 0.0)).max(Vec2::ZERO),
+            ),
+            &TextStyle::default().with_color(color),
+            Anchor::MiddleLeft,
+            false,
+        );
+        draw.push_text(
+            &plain_text(if self.open { "^" } else { "v" }),
+            fonts,
+            (min + Vec2::new(size.x - arrow, 0.0), Vec2::splat(arrow)),
+            &TextStyle::default().with_color(color),
+            Anchor::Center,
+            false,
+        );
+
+        if !self.open {
+            return;
+        }
+        let (popup_min, popup_size) = self.popup_bounds(parent_size);
+        draw.push_styled_rect(popup_min, popup_size, &self.rect.style);
+        let rows = self.items.iter().enumerate().skip(self.scroll);
+        for (slot, (index, item)) in rows.take(self.visible_rows()).enumerate() {
+            let row_min = popup_min + Vec2::new(0.0, size.y * slot as f32);
+            if self.highlighted == Some(index) {
+                draw.push_rect(row_min, size, [0.35, 0.35, 0.4, 1.0]);
+            }
+            let color = if self.selected == Some(index) {
+                ACCENT_COLOR
+            } else {
+                [1.0; 4]
+            };
+            draw.push_text(
+                &plain_text(item),
+                fonts,
+                (row_min + padding, (size - padding).max(Vec2::ZERO)),
+                &TextStyle::default().with_color(color),
+                Anchor::MiddleLeft,
+                false,
+            );
+        }
+    }
+
+    fn on_key_down(&mut self, key: KeyCode) -> bool {
+        if !self.focused {
+            return false;
+        }
+        match key {
+            KeyCode::ArrowUp => self.step(-1),
+            KeyCode::ArrowDown => self.step(1),
+            KeyCode::Home if self.open => self.highlight(0),
+            KeyCode::End if self.open => self.highlight(self.items.len().saturating_sub(1)),
+            KeyCode::Escape if self.open => self.close(),
+            KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space => {
+                self.activate();
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn is_focusable(&self) -> bool {
+        !self.is_disabled() && !self.items.is_empty()
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused && !self.is_disabled();
+        if !self.focused {
+            self.close();
+        }
+    }
+
+    fn activate(&mut self) -> bool {
+        if self.is_disabled() || self.items.is_empty() {
+            return false;
+        }
+        if self.open {
+            if let Some(index) = self.highlighted {
+                self.select(index);
+            }
+            self.close();
+        } else {
+            self.open();
+        }
+        true
+    }
+
+    fn z_index(&self) -> i32 {
+        if self.open {
+            self.rect.z.max(POPUP_LAYER)
+        } else {
+            self.rect.z
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARENT: Vec2 = Vec2::new(800.0, 600.0);
+
+    fn resolutions() -> Dropdown {
+        Dropdown::new(
+            ["1280x720", "1920x1080", "2560x1440", "3840x2160"],
+            Rect::new(10.0, 10.0, 200.0, 30.0),
+        )
+        .with_selected(1)
+    }
+
+    #[test]
+    fn test_mouse_opens_and_picks() {
+        let mut dropdown = resolutions();
+        let closed_box = Vec2::new(50.0, 20.0);
+        dropdown.on_mouse_down(closed_box, PARENT);
+        dropdown.on_mouse_up(closed_box, PARENT);
+        assert!(dropdown.is_open());
+
+        // Third popup row, below the box
+        let row = Vec2::new(50.0, 10.0 + 30.0 * 3.5);
+        dropdown.on_mouse_move(row, PARENT);
+        assert!(dropdown.on_mouse_down(row, PARENT));
+        dropdown.on_mouse_up(row, PARENT);
+        assert!(!dropdown.is_open());
+        assert_eq!(dropdown.selected_text(), Some("2560x1440"));
+        assert!(dropdown.was_changed());
+
+        // Clicking elsewhere while open only closes
+        dropdown.open();
+        assert!(dropdown.on_mouse_down(Vec2::new(500.0, 500.0), PARENT));
+        assert!(!dropdown.is_open());
+        assert_eq!(dropdown.selected(), Some(2));
+    }
+
+    #[test]
+    fn test_keyboard_navigation() {
+        let mut dropdown = resolutions().with_max_visible(2);
+        dropdown.set_focused(true);
+        assert!(dropdown.on_key_down(KeyCode::ArrowDown));
+        assert_eq!(dropdown.selected(), Some(2));
+        assert!(dropdown.was_changed());
+
+        dropdown.on_key_down(KeyCode::Enter);
+        assert!(dropdown.is_open());
+        dropdown.on_key_down(KeyCode::ArrowDown);
+        dropdown.on_key_down(KeyCode::ArrowDown);
+        assert_eq!(dropdown.scroll, 2);
+        assert_eq!(dropdown.selected(), Some(2));
+        dropdown.on_key_down(KeyCode::Space);
+        assert!(!dropdown.is_open());
+        assert_eq!(dropdown.selected(), Some(3));
+
+        dropdown.on_key_down(KeyCode::Enter);
+        dropdown.on_key_down(KeyCode::ArrowUp);
+        dropdown.on_key_down(KeyCode::Escape);
+        assert_eq!(dropdown.selected(), Some(3));
+    }
+}
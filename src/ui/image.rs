@@ -0,0 +1,162 @@
+//! Image widget
+//!
+//! Shows a texture, or a region of an atlas, inside a rect. Useful for
+//! icons, portraits and minimaps; the texture itself is supplied at draw
+//! time through `UiTextures::images`.
+
+use glam::Vec2;
+
+use super::draw::{UiDrawList, UiTextureId};
+use super::rect::Rect;
+use super::widget::{Widget, WidgetState};
+use crate::renderer::{RichTextFonts, UiAtlas};
+
+/// A textured rectangle
+#[derive(Debug, Clone)]
+pub struct Image {
+    /// Rectangle
+    pub rect: Rect,
+    /// Texture slot to sample
+    pub texture: UiTextureId,
+    /// UV of the top-left corner
+    pub uv_min: Vec2,
+    /// UV of the bottom-right corner
+    pub uv_max: Vec2,
+    /// Color multiplied with the texture (RGBA)
+    pub tint: [f32; 4],
+    /// Width over height to keep, letterboxing inside the rect
+    pub aspect: Option<f32>,
+    /// Tooltip markup shown while hovered
+    pub tooltip: Option<String>,
+}
+
+impl Image {
+    /// Show a whole texture stretched over the rect
+    #[must_use]
+    pub fn new(texture: UiTextureId, rect: Rect) -> Self {
+        Self {
+            rect,
+            texture,
+            uv_min: Vec2::ZERO,
+            uv_max: Vec2::ONE,
+            tint: [1.0; 4],
+            aspect: None,
+            tooltip: None,
+        }
+    }
+
+    /// Show a named region of an atlas, keeping its aspect ratio
+    ///
+    /// Returns `None` if the atlas has no such region.
+    #[must_use]
+    pub fn from_atlas(
+        texture: UiTextureId,
+        atlas: &UiAtlas,
+        name: &str,
+        rect: Rect,
+    ) -> Option<Self> {
+        let (uv_min, uv_max) = atlas.uv_rect(name)?;
+        let [_, _, width, height] = atlas.region(name)?;
+        Some(
+            Self::new(texture, rect)
+                .with_uv(uv_min, uv_max)
+                .with_aspect(width as f32 / height.max(1) as f32),
+        )
+    }
+
+    /// Show only a UV region of the texture
+    #[must_use]
+    pub fn with_uv(mut self, uv_min: Vec2, uv_max: Vec2) -> Self {
+        self.uv_min = uv_min;
+        self.uv_max = uv_max;
+        self
+    }
+
+    /// Set the tint
+    #[must_use]
+    pub fn with_tint(mut self, tint: [f32; 4]) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    /// Show a tooltip (rich text markup) while hovered
+    #[must_use]
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Keep a width over height ratio instead of stretching
+    #[must_use]
+    pub fn with_aspect(mut self, aspect: f32) -> Self {
+        self.aspect = (aspect > 0.0).then_some(aspect);
+        self
+    }
+
+    /// Screen (min, size) of the image inside a rect at `min` of `size`
+    #[must_use]
+    pub fn fitted(&self, min: Vec2, size: Vec2) -> (Vec2, Vec2) {
+        let Some(aspect) = self.aspect else {
+            return (min, size);
+        };
+        let fitted = if size.x / size.y.max(f32::EPSILON) > aspect {
+            Vec2::new(size.y * aspect, size.y)
+        } else {
+            Vec2::new(size.x, size.x / aspect)
+        };
+        (min + (size - fitted) * 0.5, fitted)
+    }
+}
+
+impl Widget for Image {
+    fn rect(&self) -> &Rect {
+        &self.rect
+    }
+
+    fn rect_mut(&mut self) -> &mut Rect {
+        &mut self.rect
+    }
+
+    fn state(&self) -> WidgetState {
+        WidgetState::Normal
+    }
+
+    fn on_mouse_move(&mut self, _position: Vec2, _parent_size: Vec2) {}
+    fn on_mouse_down(&mut self, _position: Vec2, _parent_size: Vec2) -> bool {
+        false
+    }
+    fn on_mouse_up(&mut self, _position: Vec2, _parent_size: Vec2) -> bool {
+        false
+    }
+
+    fn draw(&self, draw: &mut UiDrawList, _fonts: RichTextFonts<'_>, parent_size: Vec2) {
+        let min = self.rect.absolute_position(parent_size);
+        draw.push_image(
+            self.texture,
+            self.fitted(min, self.rect.size),
+            (self.uv_min, self.uv_max),
+            self.tint,
+        );
+    }
+
+    fn tooltip(&self) -> Option<&str> {
+        self.tooltip.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atlas_region_keeps_aspect() {
+        let atlas = UiAtlas::new(64, 64).with_region("portrait", [0, 0, 32, 16]);
+        let image = Image::from_atlas(UiTextureId(0), &atlas, "portrait", Rect::default()).unwrap();
+        assert_eq!(image.uv_max, Vec2::new(0.5, 0.25));
+
+        let (min, size) = image.fitted(Vec2::ZERO, Vec2::new(100.0, 100.0));
+        assert_eq!(size, Vec2::new(100.0, 50.0));
+        assert_eq!(min, Vec2::new(0.0, 25.0));
+        assert!(Image::from_atlas(UiTextureId(0), &atlas, "missing", Rect::default()).is_none());
+    }
+}
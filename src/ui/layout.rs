@@ -0,0 +1,398 @@
+//! Flexbox-style layout containers
+//!
+//! A [`FlexLayout`] places a list of [`FlexItem`]s inside a container rect
+//! along a row or column, growing, shrinking and aligning them. Call
+//! [`FlexLayout::apply`] whenever the window is resized to move the child
+//! widgets, instead of computing every `Rect` by hand.
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use super::rect::{Anchor, Rect};
+use super::widget::Widget;
+
+/// Main axis of a layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Direction {
+    /// Left to right
+    #[default]
+    Row,
+    /// Top to bottom
+    Column,
+}
+
+/// Distribution of free space along the main axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Justify {
+    /// Pack items at the start
+    #[default]
+    Start,
+    /// Pack items in the middle
+    Center,
+    /// Pack items at the end
+    End,
+    /// First and last items at the edges, equal space between the rest
+    SpaceBetween,
+    /// Equal space around every item
+    SpaceAround,
+}
+
+/// Placement of items along the cross axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Align {
+    /// Top of a row, left of a column
+    Start,
+    /// Centered
+    Center,
+    /// Bottom of a row, right of a column
+    End,
+    /// Fill the container
+    #[default]
+    Stretch,
+}
+
+/// Spacing on each side of a box
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Edges {
+    /// Space above
+    pub top: f32,
+    /// Space to the right
+    pub right: f32,
+    /// Space below
+    pub bottom: f32,
+    /// Space to the left
+    pub left: f32,
+}
+
+impl Edges {
+    /// No spacing
+    pub const ZERO: Self = Self::all(0.0);
+
+    /// The same spacing on every side
+    #[must_use]
+    pub const fn all(value: f32) -> Self {
+        Self {
+            top: value,
+            right: value,
+            bottom: value,
+            left: value,
+        }
+    }
+
+    /// Horizontal spacing on the left and right, vertical on the top and bottom
+    #[must_use]
+    pub const fn symmetric(horizontal: f32, vertical: f32) -> Self {
+        Self {
+            top: vertical,
+            right: horizontal,
+            bottom: vertical,
+            left: horizontal,
+        }
+    }
+
+    /// Offset of the top-left corner
+    #[must_use]
+    pub const fn min(&self) -> Vec2 {
+        Vec2::new(self.left, self.top)
+    }
+
+    /// Total horizontal and vertical spacing
+    #[must_use]
+    pub fn total(&self) -> Vec2 {
+        Vec2::new(self.left + self.right, self.top + self.bottom)
+    }
+}
+
+/// Size and flex settings of one child
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlexItem {
+    /// Preferred size before growing or shrinking
+    pub basis: Vec2,
+    /// Share of free space taken when the container is larger
+    pub grow: f32,
+    /// Share of overflow given up when the container is smaller
+    pub shrink: f32,
+    /// Space around the item
+    pub margin: Edges,
+    /// Cross axis placement, overriding the container's
+    pub align: Option<Align>,
+}
+
+impl FlexItem {
+    /// A fixed-size item that shrinks when space runs out
+    #[must_use]
+    pub const fn new(width: f32, height: f32) -> Self {
+        Self {
+            basis: Vec2::new(width, height),
+            grow: 0.0,
+            shrink: 1.0,
+            margin: Edges::ZERO,
+            align: None,
+        }
+    }
+
+    /// Use a widget's current size as the preferred size
+    #[must_use]
+    pub fn from_widget(widget: &dyn Widget) -> Self {
+        let size = widget.rect().size;
+        Self::new(size.x, size.y)
+    }
+
+    /// Set the grow factor
+    #[must_use]
+    pub const fn with_grow(mut self, grow: f32) -> Self {
+        self.grow = grow;
+        self
+    }
+
+    /// Set the shrink factor
+    #[must_use]
+    pub const fn with_shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink;
+        self
+    }
+
+    /// Set the margin
+    #[must_use]
+    pub const fn with_margin(mut self, margin: Edges) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Override the container's cross axis alignment
+    #[must_use]
+    pub const fn with_align(mut self, align: Align) -> Self {
+        self.align = Some(align);
+        self
+    }
+}
+
+/// A row or column container
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlexLayout {
+    /// Main axis
+    pub direction: Direction,
+    /// Free space distribution along the main axis
+    pub justify: Justify,
+    /// Cross axis placement
+    pub align: Align,
+    /// Space inside the container edges
+    pub padding: Edges,
+    /// Space between adjacent items
+    pub gap: f32,
+}
+
+impl FlexLayout {
+    /// A left-to-right flex container
+    #[must_use]
+    pub const fn row() -> Self {
+        Self {
+            direction: Direction::Row,
+            justify: Justify::Start,
+            align: Align::Stretch,
+            padding: Edges::ZERO,
+            gap: 0.0,
+        }
+    }
+
+    /// A top-to-bottom flex container
+    #[must_use]
+    pub const fn column() -> Self {
+        Self {
+            direction: Direction::Column,
+            ..Self::row()
+        }
+    }
+
+    /// A horizontal stack: items keep their size, `gap` apart, top-aligned
+    #[must_use]
+    pub const fn hstack(gap: f32) -> Self {
+        Self::row().with_gap(gap).with_align(Align::Start)
+    }
+
+    /// A vertical stack: items keep their size, `gap` apart, left-aligned
+    #[must_use]
+    pub const fn vstack(gap: f32) -> Self {
+        Self::column().with_gap(gap).with_align(Align::Start)
+    }
+
+    /// Set the main axis distribution
+    #[must_use]
+    pub const fn with_justify(mut self, justify: Justify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    /// Set the cross axis placement
+    #[must_use]
+    pub const fn with_align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Set the padding
+    #[must_use]
+    pub const fn with_padding(mut self, padding: Edges) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Set the gap between items
+    #[must_use]
+    pub const fn with_gap(mut self, gap: f32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Main and cross components of a vector
+    fn split(&self, v: Vec2) -> (f32, f32) {
+        match self.direction {
+            Direction::Row => (v.x, v.y),
+            Direction::Column => (v.y, v.x),
+        }
+    }
+
+    /// Vector from main and cross components
+    fn join(&self, main: f32, cross: f32) -> Vec2 {
+        match self.direction {
+            Direction::Row => Vec2::new(main, cross),
+            Direction::Column => Vec2::new(cross, main),
+        }
+    }
+
+    /// Compute each item's (min, size) inside a container at `min` of `size`
+    #[must_use]
+    pub fn compute(&self, min: Vec2, size: Vec2, items: &[FlexItem]) -> Vec<(Vec2, Vec2)> {
+        if items.is_empty() {
+            return Vec::new();
+        }
+        let (inner_main, inner_cross) = self.split((size - self.padding.total()).max(Vec2::ZERO));
+        let gaps = self.gap * (items.len() - 1) as f32;
+
+        // Resolve main sizes from the basis, growing or shrinking to fit
+        let mut mains: Vec<f32> = items
+            .iter()
+            .map(|i| self.split(i.basis).0.max(0.0))
+            .collect();
+        let margins: f32 = items.iter().map(|i| self.split(i.margin.total()).0).sum();
+        let mut free = inner_main - gaps - margins - mains.iter().sum::<f32>();
+        if free > 0.0 {
+            let grow: f32 = items.iter().map(|i| i.grow.max(0.0)).sum();
+            if grow > 0.0 {
+                for (main, item) in mains.iter_mut().zip(items) {
+                    *main += free * item.grow.max(0.0) / grow;
+                }
+                free = 0.0;
+            }
+        } else if free < 0.0 {
+            let weight: f32 = mains
+                .iter()
+                .zip(items)
+                .map(|(main, item)| main * item.shrink.max(0.0))
+                .sum();
+            if weight > 0.0 {
+                for (main, item) in mains.iter_mut().zip(items) {
+                    *main = (*main + free * *main * item.shrink.max(0.0) / weight).max(0.0);
+                }
+            }
+            free = 0.0;
+        }
+
+        let count = items.len() as f32;
+        let (mut pen, spacing) = match self.justify {
+            Justify::Start => (0.0, 0.0),
+            Justify::Center => (free * 0.5, 0.0),
+            Justify::End => (free, 0.0),
+            Justify::SpaceBetween if items.len() > 1 => (0.0, free / (count - 1.0)),
+            Justify::SpaceBetween => (0.0, 0.0),
+            Justify::SpaceAround => (free / count * 0.5, free / count),
+        };
+
+        let origin = min + self.padding.min();
+        items
+            .iter()
+            .zip(mains)
+            .map(|(item, main)| {
+                let (margin_before, margin_cross_before) = self.split(item.margin.min());
+                let (margin_main, margin_cross) = self.split(item.margin.total());
+                let available = (inner_cross - margin_cross).max(0.0);
+                let basis_cross = self.split(item.basis).1.clamp(0.0, available);
+                let (cross_offset, cross) = match item.align.unwrap_or(self.align) {
+                    Align::Start => (0.0, basis_cross),
+                    Align::Center => ((available - basis_cross) * 0.5, basis_cross),
+                    Align::End => (available - basis_cross, basis_cross),
+                    Align::Stretch => (0.0, available),
+                };
+
+                let position =
+                    origin + self.join(pen + margin_before, margin_cross_before + cross_offset);
+                pen += main + margin_main + self.gap + spacing;
+                (position, self.join(main, cross))
+            })
+            .collect()
+    }
+
+    /// Lay out widgets inside a container rect
+    ///
+    /// Widgets are re-anchored to the top-left, so call this again whenever
+    /// the container or window is resized.
+    pub fn apply(
+        &self,
+        container: &Rect,
+        parent_size: Vec2,
+        items: &[FlexItem],
+        widgets: &mut [&mut dyn Widget],
+    ) {
+        let min = container.absolute_position(parent_size);
+        for ((position, size), widget) in self
+            .compute(min, container.size, items)
+            .into_iter()
+            .zip(widgets.iter_mut())
+        {
+            let rect = widget.rect_mut();
+            rect.anchor = Anchor::TopLeft;
+            rect.position = position;
+            rect.size = size;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_grow_and_stretch() {
+        let layout = FlexLayout::row()
+            .with_padding(Edges::all(10.0))
+            .with_gap(5.0);
+        let items = [
+            FlexItem::new(50.0, 20.0),
+            FlexItem::new(50.0, 20.0).with_grow(1.0),
+        ];
+        let rects = layout.compute(Vec2::ZERO, Vec2::new(200.0, 60.0), &items);
+        assert_eq!(rects[0], (Vec2::new(10.0, 10.0), Vec2::new(50.0, 40.0)));
+        assert_eq!(rects[1], (Vec2::new(65.0, 10.0), Vec2::new(125.0, 40.0)));
+    }
+
+    #[test]
+    fn test_column_justify_center_and_shrink() {
+        let layout = FlexLayout::vstack(0.0)
+            .with_justify(Justify::Center)
+            .with_align(Align::Center);
+        let items = [FlexItem::new(20.0, 10.0), FlexItem::new(40.0, 10.0)];
+        let rects = layout.compute(Vec2::ZERO, Vec2::new(100.0, 100.0), &items);
+        assert_eq!(rects[0], (Vec2::new(40.0, 40.0), Vec2::new(20.0, 10.0)));
+        assert_eq!(rects[1], (Vec2::new(30.0, 50.0), Vec2::new(40.0, 10.0)));
+
+        let rects = FlexLayout::row().compute(
+            Vec2::ZERO,
+            Vec2::new(60.0, 10.0),
+            &[FlexItem::new(40.0, 10.0), FlexItem::new(80.0, 10.0)],
+        );
+        assert_eq!(rects[0].1.x, 20.0);
+        assert_eq!(rects[1], (Vec2::new(20.0, 0.0), Vec2::new(40.0, 10.0)));
+    }
+}
@@ -0,0 +1,658 @@
+//! Declarative UI layouts
+//!
+//! A [`UiDocument`] describes a widget tree in RON: each node names its
+//! widget, an optional ID, its flex sizing inside the parent and the
+//! [`FlexLayout`] for its own children. A [`UiScreen`] builds the widgets,
+//! lays them out for a window size and finds them again by ID. Documents
+//! are ordinary assets, so screens rebuild themselves from the
+//! `AssetEvent::Modified` a hot reload produces; [`MarkupWatcher`] provides
+//! that reload by polling the file's modification time.
+//!
+//! ```ron
+//! (
+//!     root: (
+//!         layout: (direction: Column, justify: Center, align: Center, gap: 8.0),
+//!         children: [
+//!             (widget: Label("[b]Main Menu[/b]"), size: (240.0, 40.0)),
+//!             (id: Some("play"), widget: Button("Play"), size: (240.0, 40.0)),
+//!             (id: Some("quit"), widget: Button("Quit"), size: (240.0, 40.0)),
+//!         ],
+//!     ),
+//! )
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use glam::Vec2;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use winit::keyboard::KeyCode;
+
+use super::draw::{UiDrawList, UiTextureId};
+use super::dropdown::Dropdown;
+use super::image::Image;
+use super::layout::{Align, Edges, FlexItem, FlexLayout};
+use super::progress::ProgressBar;
+use super::rect::Rect;
+use super::text_input::TextInput;
+use super::toggle::{Checkbox, RadioGroup};
+use super::widget::{Button, Label, Panel, Widget, input_order};
+use crate::assets::{AssetEvent, AssetHandle, AssetServer};
+use crate::core::SceneError;
+use crate::renderer::RichTextFonts;
+
+/// The widget a node creates
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum UiNodeKind {
+    /// Layout only; children are placed inside it
+    #[default]
+    Container,
+    /// A panel with an optional title
+    Panel(Option<String>),
+    /// Rich text markup
+    Label(String),
+    /// A button with a caption
+    Button(String),
+    /// A checkbox with a caption and initial value
+    Checkbox(String, bool),
+    /// Radio options
+    RadioGroup(Vec<String>),
+    /// Dropdown items and the initially selected one
+    Dropdown(Vec<String>, Option<usize>),
+    /// A text field with placeholder text
+    TextInput(String),
+    /// A bar at an initial value
+    ProgressBar(f32),
+    /// A texture slot
+    Image(u32),
+}
+
+/// One node of a widget tree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiNode {
+    /// Name to look the widget up by
+    pub id: Option<String>,
+    /// Widget to create
+    pub widget: UiNodeKind,
+    /// Size before growing or shrinking
+    pub size: Vec2,
+    /// Share of the parent's free space to take
+    pub grow: f32,
+    /// Share of the parent's overflow to give up
+    pub shrink: f32,
+    /// Space kept around the node
+    pub margin: Edges,
+    /// Cross axis placement overriding the parent's
+    pub align: Option<Align>,
+    /// Tooltip markup, for widgets that show one
+    pub tooltip: Option<String>,
+    /// Layer of the widget
+    pub z: i32,
+    /// Whether descendants are cut to this node's rect
+    pub clip: bool,
+    /// Layout of the children inside this node
+    pub layout: FlexLayout,
+    /// Child nodes
+    pub children: Vec<UiNode>,
+}
+
+impl Default for UiNode {
+    fn default() -> Self {
+        Self {
+            id: None,
+            widget: UiNodeKind::Container,
+            size: Vec2::ZERO,
+            grow: 0.0,
+            shrink: 1.0,
+            margin: Edges::ZERO,
+            align: None,
+            tooltip: None,
+            z: 0,
+            clip: false,
+            layout: FlexLayout::default(),
+            children: Vec::new(),
+        }
+    }
+}
+
+impl UiNode {
+    /// Flex sizing of the node inside its parent
+    fn item(&self) -> FlexItem {
+        let mut item = FlexItem::new(self.size.x, self.size.y)
+            .with_grow(self.grow)
+            .with_shrink(self.shrink)
+            .with_margin(self.margin);
+        item.align = self.align;
+        item
+    }
+}
+
+/// A widget tree loaded from markup
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UiDocument {
+    /// Node filling the whole screen
+    pub root: UiNode,
+}
+
+impl UiDocument {
+    /// Parse a document from RON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a valid document
+    pub fn from_ron(text: &str) -> Result<Self, SceneError> {
+        ron::from_str(text).map_err(|e| SceneError::DeserializeError(e.to_string()))
+    }
+
+    /// Load a document from a RON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SceneError> {
+        let text = std::fs::read_to_string(path).map_err(|e| SceneError::IoError(e.to_string()))?;
+        Self::from_ron(&text)
+    }
+}
+
+/// A widget built from a node
+#[derive(Debug, Clone)]
+pub enum UiElement {
+    /// Layout-only node
+    Container(Rect),
+    /// See [`Panel`]
+    Panel(Panel),
+    /// See [`Label`]
+    Label(Label),
+    /// See [`Button`]
+    Button(Button),
+    /// See [`Checkbox`]
+    Checkbox(Checkbox),
+    /// See [`RadioGroup`]
+    RadioGroup(RadioGroup),
+    /// See [`Dropdown`]
+    Dropdown(Dropdown),
+    /// See [`TextInput`]
+    TextInput(TextInput),
+    /// See [`ProgressBar`]
+    ProgressBar(ProgressBar),
+    /// See [`Image`]
+    Image(Image),
+}
+
+impl UiElement {
+    /// Create the widget for a node
+    fn build(node: &UiNode, rect: Rect) -> Self {
+        let tooltip = node.tooltip.clone();
+        match &node.widget {
+            UiNodeKind::Container => Self::Container(rect),
+            UiNodeKind::Panel(title) => {
+                let mut panel = Panel::new(rect);
+                panel.title.clone_from(title);
+                Self::Panel(panel)
+            }
+            UiNodeKind::Label(text) => Self::Label(Label::rich(text, rect)),
+            UiNodeKind::Button(text) => {
+                let mut button = Button::new(text, rect);
+                button.tooltip = tooltip;
+                Self::Button(button)
+            }
+            UiNodeKind::Checkbox(text, checked) => {
+                let mut checkbox = Checkbox::new(text, rect).with_checked(*checked);
+                checkbox.tooltip = tooltip;
+                Self::Checkbox(checkbox)
+            }
+            UiNodeKind::RadioGroup(options) => {
+                Self::RadioGroup(RadioGroup::new(options.iter().cloned(), rect))
+            }
+            UiNodeKind::Dropdown(items, selected) => {
+                let dropdown = Dropdown::new(items.iter().cloned(), rect);
+                Self::Dropdown(match selected {
+                    Some(index) => dropdown.with_selected(*index),
+                    None => dropdown,
+                })
+            }
+            UiNodeKind::TextInput(placeholder) => {
+                Self::TextInput(TextInput::new(rect).with_placeholder(placeholder))
+            }
+            UiNodeKind::ProgressBar(value) => {
+                Self::ProgressBar(ProgressBar::new(rect).with_value(*value))
+            }
+            UiNodeKind::Image(texture) => {
+                let mut image = Image::new(UiTextureId(*texture), rect);
+                image.tooltip = tooltip;
+                Self::Image(image)
+            }
+        }
+    }
+
+    /// Get the widget, or `None` for a container
+    #[must_use]
+    pub fn widget(&self) -> Option<&dyn Widget> {
+        match self {
+            Self::Container(_) => None,
+            Self::Panel(w) => Some(w),
+            Self::Label(w) => Some(w),
+            Self::Button(w) => Some(w),
+            Self::Checkbox(w) => Some(w),
+            Self::RadioGroup(w) => Some(w),
+            Self::Dropdown(w) => Some(w),
+            Self::TextInput(w) => Some(w),
+            Self::ProgressBar(w) => Some(w),
+            Self::Image(w) => Some(w),
+        }
+    }
+
+    /// Get the widget mutably, or `None` for a container
+    pub fn widget_mut(&mut self) -> Option<&mut dyn Widget> {
+        match self {
+            Self::Container(_) => None,
+            Self::Panel(w) => Some(w),
+            Self::Label(w) => Some(w),
+            Self::Button(w) => Some(w),
+            Self::Checkbox(w) => Some(w),
+            Self::RadioGroup(w) => Some(w),
+            Self::Dropdown(w) => Some(w),
+            Self::TextInput(w) => Some(w),
+            Self::ProgressBar(w) => Some(w),
+            Self::Image(w) => Some(w),
+        }
+    }
+
+    /// Get the element's rectangle
+    #[must_use]
+    pub fn rect(&self) -> &Rect {
+        match self {
+            Self::Container(rect) => rect,
+            Self::Panel(w) => &w.rect,
+            Self::Label(w) => &w.rect,
+            Self::Button(w) => &w.rect,
+            Self::Checkbox(w) => &w.rect,
+            Self::RadioGroup(w) => &w.rect,
+            Self::Dropdown(w) => &w.rect,
+            Self::TextInput(w) => &w.rect,
+            Self::ProgressBar(w) => &w.rect,
+            Self::Image(w) => &w.rect,
+        }
+    }
+
+    /// Get the element's rectangle mutably
+    pub fn rect_mut(&mut self) -> &mut Rect {
+        match self {
+            Self::Container(rect) => rect,
+            Self::Panel(w) => &mut w.rect,
+            Self::Label(w) => &mut w.rect,
+            Self::Button(w) => &mut w.rect,
+            Self::Checkbox(w) => &mut w.rect,
+            Self::RadioGroup(w) => &mut w.rect,
+            Self::Dropdown(w) => &mut w.rect,
+            Self::TextInput(w) => &mut w.rect,
+            Self::ProgressBar(w) => &mut w.rect,
+            Self::Image(w) => &mut w.rect,
+        }
+    }
+}
+
+/// Widgets built from a document, laid out for a screen size
+#[derive(Debug, Clone)]
+pub struct UiScreen {
+    /// Document the widgets came from
+    document: UiDocument,
+    /// Asset the document was loaded as, for hot reload
+    source: Option<u64>,
+    /// Elements in tree order, parents before children
+    elements: Vec<UiElement>,
+    /// Element index by node ID
+    ids: FxHashMap<String, usize>,
+    /// For clipping elements, the index just past their last descendant
+    clip_ends: Vec<Option<usize>>,
+    /// Screen size of the last layout
+    size: Vec2,
+}
+
+impl UiScreen {
+    /// Build the widgets of a document for a screen size
+    #[must_use]
+    pub fn new(document: UiDocument, size: Vec2) -> Self {
+        let mut screen = Self {
+            document,
+            source: None,
+            elements: Vec::new(),
+            ids: FxHashMap::default(),
+            clip_ends: Vec::new(),
+            size,
+        };
+        screen.rebuild();
+        screen
+    }
+
+    /// Build the widgets of a loaded document, following its hot reloads
+    #[must_use]
+    pub fn from_asset(handle: &AssetHandle<UiDocument>, size: Vec2) -> Self {
+        let mut screen = Self::new(handle.get().clone(), size);
+        screen.source = Some(handle.id());
+        screen
+    }
+
+    /// Get the document the widgets came from
+    #[must_use]
+    pub fn document(&self) -> &UiDocument {
+        &self.document
+    }
+
+    /// Get an element by ID
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&UiElement> {
+        self.ids.get(id).map(|&index| &self.elements[index])
+    }
+
+    /// Get an element by ID mutably
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut UiElement> {
+        self.ids.get(id).map(|&index| &mut self.elements[index])
+    }
+
+    /// Get a button by ID
+    pub fn button(&mut self, id: &str) -> Option<&mut Button> {
+        match self.get_mut(id)? {
+            UiElement::Button(button) => Some(button),
+            _ => None,
+        }
+    }
+
+    /// Get every element in tree order
+    #[must_use]
+    pub fn elements(&self) -> &[UiElement] {
+        &self.elements
+    }
+
+    /// Get every widget, in draw order, for navigation, tooltips or drag-and-drop
+    pub fn widgets_mut(&mut self) -> Vec<&mut dyn Widget> {
+        self.elements
+            .iter_mut()
+            .filter_map(UiElement::widget_mut)
+            .collect()
+    }
+
+    /// Replace the document, rebuilding every widget
+    pub fn set_document(&mut self, document: UiDocument) {
+        self.document = document;
+        self.rebuild();
+    }
+
+    /// Rebuild from the new document if a hot reload replaced it
+    ///
+    /// Returns whether the widgets were rebuilt; their state is reset.
+    pub fn apply_events(&mut self, events: &[AssetEvent<UiDocument>]) -> bool {
+        let Some(source) = self.source else {
+            return false;
+        };
+        let reloaded = events.iter().rev().find_map(|event| match event {
+            AssetEvent::Modified(handle) if handle.id() == source => handle.upgrade(),
+            _ => None,
+        });
+        let Some(handle) = reloaded else {
+            return false;
+        };
+        self.set_document(handle.get().clone());
+        true
+    }
+
+    /// Lay the widgets out again for a new screen size, keeping their state
+    pub fn resize(&mut self, size: Vec2) {
+        self.size = size;
+        let mut rects = Vec::with_capacity(self.elements.len());
+        place(&self.document.root, Vec2::ZERO, size, &mut rects);
+        for (element, (min, size)) in self.elements.iter_mut().zip(rects) {
+            let rect = element.rect_mut();
+            rect.position = min;
+            rect.size = size;
+        }
+    }
+
+    /// Create the elements and ID table from the document
+    fn rebuild(&mut self) {
+        let mut rects = Vec::new();
+        place(&self.document.root, Vec2::ZERO, self.size, &mut rects);
+        self.elements.clear();
+        self.ids.clear();
+        self.clip_ends.clear();
+        clip_ends(&self.document.root, &mut self.clip_ends);
+        let mut rects = rects.into_iter();
+        visit(&self.document.root, &mut |node| {
+            let (min, size) = rects.next().unwrap_or_default();
+            let rect = Rect::new(min.x, min.y, size.x, size.y).with_z(node.z);
+            if let Some(id) = &node.id
+                && self.ids.insert(id.clone(), self.elements.len()).is_some()
+            {
+                log::warn!("Duplicate UI id '{id}'; the last one wins");
+            }
+            self.elements.push(UiElement::build(node, rect));
+        });
+    }
+
+    /// Forward mouse movement to every widget
+    pub fn on_mouse_move(&mut self, position: Vec2) {
+        let size = self.size;
+        for widget in self.widgets_mut() {
+            widget.on_mouse_move(position, size);
+        }
+    }
+
+    /// Forward a mouse press in `input_order`
+    ///
+    /// Returns whether a widget took it; widgets beneath lose focus.
+    pub fn on_mouse_down(&mut self, position: Vec2) -> bool {
+        let size = self.size;
+        let mut widgets = self.widgets_mut();
+        let mut taken = false;
+        for index in input_order(&widgets) {
+            let widget = &mut widgets[index];
+            if taken {
+                widget.set_focused(false);
+            } else {
+                taken = widget.on_mouse_down(position, size);
+            }
+        }
+        taken
+    }
+
+    /// Forward a mouse release to every widget
+    pub fn on_mouse_up(&mut self, position: Vec2) -> bool {
+        let size = self.size;
+        let mut taken = false;
+        for widget in self.widgets_mut() {
+            taken |= widget.on_mouse_up(position, size);
+        }
+        taken
+    }
+
+    /// Forward a key press to the focused widgets
+    pub fn on_key_down(&mut self, key: KeyCode) -> bool {
+        let mut taken = false;
+        for widget in self.widgets_mut() {
+            taken |= widget.on_key_down(key);
+        }
+        taken
+    }
+
+    /// Add every widget to a draw list, parents first, on their layers and
+    /// cut to any clipping ancestors
+    pub fn draw(&self, draw: &mut UiDrawList, fonts: RichTextFonts<'_>) {
+        let mut clips: Vec<usize> = Vec::new();
+        for (index, element) in self.elements.iter().enumerate() {
+            while clips.last().is_some_and(|&end| index >= end) {
+                clips.pop();
+                draw.pop_clip();
+            }
+            if let Some(widget) = element.widget() {
+                draw.push_widget(widget, fonts, self.size);
+            }
+            if let Some(end) = self.clip_ends[index] {
+                let rect = element.rect();
+                draw.push_clip(rect.absolute_position(self.size), rect.size);
+                clips.push(end);
+            }
+        }
+        for _ in clips {
+            draw.pop_clip();
+        }
+    }
+}
+
+/// Visit nodes in tree order, parents before children
+fn visit<'a>(node: &'a UiNode, f: &mut impl FnMut(&'a UiNode)) {
+    f(node);
+    for child in &node.children {
+        visit(child, f);
+    }
+}
+
+/// Push, in tree order, the end of each clipping node's subtree
+fn clip_ends(node: &UiNode, out: &mut Vec<Option<usize>>) {
+    let index = out.len();
+    out.push(None);
+    for child in &node.children {
+        clip_ends(child, out);
+    }
+    if node.clip {
+        out[index] = Some(out.len());
+    }
+}
+
+/// Push the (min, size) of a node and its descendants in tree order
+fn place(node: &UiNode, min: Vec2, size: Vec2, out: &mut Vec<(Vec2, Vec2)>) {
+    out.push((min, size));
+    let items: Vec<FlexItem> = node.children.iter().map(UiNode::item).collect();
+    let rects = node.layout.compute(min, size, &items);
+    for (child, (child_min, child_size)) in node.children.iter().zip(rects) {
+        place(child, child_min, child_size, out);
+    }
+}
+
+/// Reloads a markup file into the asset server when it changes on disk
+#[derive(Debug, Clone)]
+pub struct MarkupWatcher {
+    /// File to watch, also its asset path
+    path: PathBuf,
+    /// Modification time of the last version loaded
+    modified: Option<SystemTime>,
+}
+
+impl MarkupWatcher {
+    /// Watch a file, treating its current version as already loaded
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let modified = modified_time(&path);
+        Self { path, modified }
+    }
+
+    /// Get the watched path
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reload the file if it changed, emitting `AssetEvent::Modified`
+    ///
+    /// A file that fails to parse is logged and skipped, so a half-saved
+    /// edit keeps the previous layout. Returns whether it reloaded.
+    pub fn poll(&mut self, server: &mut AssetServer) -> bool {
+        let modified = modified_time(&self.path);
+        if modified.is_none() || modified == self.modified {
+            return false;
+        }
+        self.modified = modified;
+        match UiDocument::load(&self.path) {
+            Ok(document) => {
+                server
+                    .get_storage::<UiDocument>()
+                    .reload_path(document, &self.path);
+                true
+            }
+            Err(error) => {
+                log::warn!("Failed to reload {}: {error}", self.path.display());
+                false
+            }
+        }
+    }
+}
+
+/// Modification time of a file, if it exists
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MENU: &str = r#"(
+        root: (
+            layout: (direction: Column, justify: Center, align: Center, gap: 10.0),
+            children: [
+                (widget: Label("Main Menu"), size: (200.0, 40.0)),
+                (id: Some("play"), widget: Button("Play"), size: (200.0, 40.0)),
+                (
+                    layout: (direction: Row, gap: 10.0),
+                    size: (200.0, 20.0),
+                    children: [
+                        (id: Some("music"), widget: Checkbox("Music", true), grow: 1.0),
+                        (id: Some("volume"), widget: ProgressBar(0.5), grow: 1.0),
+                    ],
+                ),
+            ],
+        ),
+    )"#;
+
+    #[test]
+    fn test_document_builds_and_lays_out() {
+        let document = UiDocument::from_ron(MENU).unwrap();
+        let mut screen = UiScreen::new(document, Vec2::new(800.0, 600.0));
+        assert_eq!(screen.elements().len(), 6);
+
+        let play = screen.get("play").unwrap().rect();
+        assert_eq!(play.position, Vec2::new(300.0, 290.0));
+        assert_eq!(play.size, Vec2::new(200.0, 40.0));
+        let volume = screen.get("volume").unwrap().rect();
+        assert_eq!(volume.position, Vec2::new(405.0, 340.0));
+        assert_eq!(volume.size, Vec2::new(95.0, 20.0));
+        assert!(matches!(
+            screen.get("music"),
+            Some(UiElement::Checkbox(checkbox)) if checkbox.is_checked()
+        ));
+
+        screen.on_mouse_down(Vec2::new(310.0, 300.0));
+        screen.on_mouse_up(Vec2::new(310.0, 300.0));
+        assert!(screen.button("play").unwrap().was_clicked());
+
+        let mut clipped = UiDocument::from_ron(MENU).unwrap();
+        clipped.root.children[2].clip = true;
+        screen.set_document(clipped);
+        assert_eq!(screen.clip_ends, [None, None, None, Some(6), None, None]);
+
+        screen.resize(Vec2::new(1000.0, 600.0));
+        assert_eq!(screen.get("play").unwrap().rect().position.x, 400.0);
+        assert!(UiDocument::from_ron("(root: (widget: Slider))").is_err());
+    }
+
+    #[test]
+    fn test_hot_reload_rebuilds_screen() {
+        let mut server = AssetServer::new();
+        let document = UiDocument::from_ron(MENU).unwrap();
+        let handle = server.add_with_path(document, "menu.ron");
+        let mut screen = UiScreen::from_asset(&handle, Vec2::new(800.0, 600.0));
+        server.drain_events::<UiDocument>();
+
+        let mut edited = UiDocument::from_ron(MENU).unwrap();
+        edited.root.children[1].id = Some("start".to_string());
+        server
+            .get_storage::<UiDocument>()
+            .reload_path(edited, "menu.ron");
+        assert!(screen.apply_events(&server.drain_events::<UiDocument>()));
+        assert!(screen.get("play").is_none());
+        assert!(screen.get("start").is_some());
+    }
+}
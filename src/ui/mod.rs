@@ -1,9 +1,38 @@
 //! UI system for 2D interface elements
 //!
-//! Provides widgets, layout, and event handling.
+//! Provides widgets, layout, event handling, and draw data for rendering.
 
+mod asset_browser;
+mod drag_drop;
+mod draw;
+mod dropdown;
+mod image;
+mod layout;
+mod markup;
+mod navigation;
+mod progress;
 mod rect;
+mod text_input;
+mod toggle;
+mod tooltip;
 mod widget;
+mod window;
 
+pub use asset_browser::{AssetBrowser, AssetDrop, AssetType, BrowserEntry, DirectoryNode};
+pub use drag_drop::{DragDrop, DragPayload, DropEvent, Slot};
+pub use draw::{
+    ImageBatch, MODAL_LAYER, OVERLAY_LAYER, POPUP_LAYER, UiDrawBatch, UiDrawList, UiTextureId,
+    UiTextures,
+};
+pub use dropdown::Dropdown;
+pub use image::Image;
+pub use layout::{Align, Direction, Edges, FlexItem, FlexLayout, Justify};
+pub use markup::{MarkupWatcher, UiDocument, UiElement, UiNode, UiNodeKind, UiScreen};
+pub use navigation::{GamepadNav, NavCommand, NavDirection, NavEvent, UiNavigator, find_neighbor};
+pub use progress::{FillDirection, ProgressBar};
 pub use rect::{Anchor, Rect, RectStyle};
-pub use widget::{Button, Label, Panel, Widget, WidgetState};
+pub use text_input::{TextFilter, TextInput};
+pub use toggle::{Checkbox, RadioGroup};
+pub use tooltip::{Tooltips, popup_position};
+pub use widget::{Button, Label, Panel, Widget, WidgetState, input_order};
+pub use window::Window;
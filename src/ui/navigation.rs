@@ -0,0 +1,339 @@
+//! Directional focus navigation
+//!
+//! Moves focus between widgets with a D-pad, stick or arrow keys by
+//! searching for the nearest focusable widget in the pressed direction,
+//! with A/B (or Enter/Escape) mapped to activate and back. Console-style
+//! menus work without any per-screen wiring of neighbours.
+
+use glam::Vec2;
+use winit::keyboard::KeyCode;
+
+use super::widget::Widget;
+use crate::input::Input;
+
+/// Stick deflection below which it counts as centered
+const STICK_DEADZONE: f32 = 0.5;
+
+/// Weight of sideways distance when picking a neighbour
+const PERPENDICULAR_WEIGHT: f32 = 2.0;
+
+/// A direction to move focus in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavDirection {
+    /// Toward the top of the screen
+    Up,
+    /// Toward the bottom of the screen
+    Down,
+    /// Toward the left
+    Left,
+    /// Toward the right
+    Right,
+}
+
+impl NavDirection {
+    /// Screen-space unit vector (y down)
+    #[must_use]
+    pub const fn vector(self) -> Vec2 {
+        match self {
+            Self::Up => Vec2::NEG_Y,
+            Self::Down => Vec2::Y,
+            Self::Left => Vec2::NEG_X,
+            Self::Right => Vec2::X,
+        }
+    }
+
+    /// Dominant direction of a stick or D-pad vector in screen space (y down)
+    ///
+    /// Returns `None` inside the deadzone.
+    #[must_use]
+    pub fn from_vector(vector: Vec2) -> Option<Self> {
+        if vector.length() < STICK_DEADZONE {
+            return None;
+        }
+        Some(if vector.x.abs() > vector.y.abs() {
+            if vector.x > 0.0 {
+                Self::Right
+            } else {
+                Self::Left
+            }
+        } else if vector.y > 0.0 {
+            Self::Down
+        } else {
+            Self::Up
+        })
+    }
+}
+
+/// A navigation action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavCommand {
+    /// Move focus to a neighbouring widget
+    Move(NavDirection),
+    /// Activate the focused widget (A / Enter)
+    Activate,
+    /// Leave the menu (B / Escape)
+    Back,
+}
+
+/// Gamepad state sampled once a frame
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GamepadNav {
+    /// Stick or D-pad direction in screen space (y down)
+    pub direction: Vec2,
+    /// Whether the activate button (A / Cross) is held
+    pub activate: bool,
+    /// Whether the back button (B / Circle) is held
+    pub back: bool,
+}
+
+/// What a navigation command did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavEvent {
+    /// Focus moved to the widget at this index
+    Focused(usize),
+    /// The widget at this index was activated
+    Activated(usize),
+    /// Back was pressed; the game decides what to close
+    Back,
+}
+
+/// Index of the nearest rect from `from` in a direction
+///
+/// Rects are (min, size). Only rects whose center lies ahead of the
+/// current center count; sideways distance is penalized so aligned
+/// neighbours win over diagonal ones.
+#[must_use]
+pub fn find_neighbor(
+    rects: &[(Vec2, Vec2)],
+    from: usize,
+    direction: NavDirection,
+    candidate: impl Fn(usize) -> bool,
+) -> Option<usize> {
+    let (min, size) = rects.get(from)?;
+    let origin = *min + *size * 0.5;
+    let axis = direction.vector();
+    rects
+        .iter()
+        .enumerate()
+        .filter(|&(index, _)| index != from && candidate(index))
+        .filter_map(|(index, (min, size))| {
+            let offset = *min + *size * 0.5 - origin;
+            let ahead = offset.dot(axis);
+            if ahead <= 0.0 {
+                return None;
+            }
+            let sideways = (offset - axis * ahead).length();
+            Some((index, ahead + sideways * PERPENDICULAR_WEIGHT))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(index, _)| index)
+}
+
+/// Tracks focus across a list of widgets and turns input into commands
+#[derive(Debug, Clone)]
+pub struct UiNavigator {
+    /// Index of the focused widget
+    focused: Option<usize>,
+    /// Seconds a direction is held before it repeats
+    repeat_delay: f32,
+    /// Seconds between repeats while held
+    repeat_interval: f32,
+    /// Direction currently held
+    held: Option<NavDirection>,
+    /// Seconds until the held direction repeats
+    repeat_timer: f32,
+    /// Gamepad state last frame, for button edges
+    previous: GamepadNav,
+}
+
+impl UiNavigator {
+    /// Create a navigator with nothing focused
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            focused: None,
+            repeat_delay: 0.4,
+            repeat_interval: 0.1,
+            held: None,
+            repeat_timer: 0.0,
+            previous: GamepadNav::default(),
+        }
+    }
+
+    /// Set how long a held direction waits before repeating, and how fast
+    #[must_use]
+    pub fn with_repeat(mut self, delay: f32, interval: f32) -> Self {
+        self.repeat_delay = delay.max(0.0);
+        self.repeat_interval = interval.max(0.01);
+        self
+    }
+
+    /// Get the focused widget index
+    #[must_use]
+    pub fn focused(&self) -> Option<usize> {
+        self.focused
+    }
+
+    /// Turn gamepad state into a command, repeating held directions
+    pub fn poll_gamepad(&mut self, pad: GamepadNav, dt: f32) -> Option<NavCommand> {
+        let previous = std::mem::replace(&mut self.previous, pad);
+        if pad.activate && !previous.activate {
+            return Some(NavCommand::Activate);
+        }
+        if pad.back && !previous.back {
+            return Some(NavCommand::Back);
+        }
+
+        let direction = NavDirection::from_vector(pad.direction);
+        if direction != self.held {
+            self.held = direction;
+            self.repeat_timer = self.repeat_delay;
+            return direction.map(NavCommand::Move);
+        }
+        let held = self.held?;
+        self.repeat_timer -= dt;
+        if self.repeat_timer > 0.0 {
+            return None;
+        }
+        self.repeat_timer += self.repeat_interval;
+        Some(NavCommand::Move(held))
+    }
+
+    /// Turn arrow keys, Enter and Escape into a command
+    #[must_use]
+    pub fn poll_keyboard(input: &Input) -> Option<NavCommand> {
+        let keys = [
+            (KeyCode::ArrowUp, NavCommand::Move(NavDirection::Up)),
+            (KeyCode::ArrowDown, NavCommand::Move(NavDirection::Down)),
+            (KeyCode::ArrowLeft, NavCommand::Move(NavDirection::Left)),
+            (KeyCode::ArrowRight, NavCommand::Move(NavDirection::Right)),
+            (KeyCode::Enter, NavCommand::Activate),
+            (KeyCode::Escape, NavCommand::Back),
+        ];
+        keys.into_iter()
+            .find(|(key, _)| input.is_key_just_pressed(*key))
+            .map(|(_, command)| command)
+    }
+
+    /// Focus a widget directly, e.g. the default button when a menu opens
+    pub fn focus(&mut self, widgets: &mut [&mut dyn Widget], index: Option<usize>) {
+        for (i, widget) in widgets.iter_mut().enumerate() {
+            widget.set_focused(Some(i) == index);
+        }
+        self.focused = index.filter(|&i| widgets.get(i).is_some_and(|w| w.is_focused()));
+    }
+
+    /// Apply a command to the widgets
+    ///
+    /// With nothing focused, the first move focuses the first focusable
+    /// widget.
+    pub fn apply(
+        &mut self,
+        command: NavCommand,
+        widgets: &mut [&mut dyn Widget],
+        parent_size: Vec2,
+    ) -> Option<NavEvent> {
+        // Keep in sync with clicks that moved focus
+        if let Some(index) = widgets.iter().position(|w| w.is_focused()) {
+            self.focused = Some(index);
+        }
+
+        match command {
+            NavCommand::Back => Some(NavEvent::Back),
+            NavCommand::Activate => {
+                let index = self.focused?;
+                widgets
+                    .get_mut(index)?
+                    .activate()
+                    .then_some(NavEvent::Activated(index))
+            }
+            NavCommand::Move(direction) => {
+                let focusable: Vec<bool> = widgets.iter().map(|w| w.is_focusable()).collect();
+                let target = match self.focused {
+                    Some(from) => {
+                        let rects: Vec<(Vec2, Vec2)> = widgets
+                            .iter()
+                            .map(|w| (w.rect().absolute_position(parent_size), w.rect().size))
+                            .collect();
+                        find_neighbor(&rects, from, direction, |i| focusable[i])?
+                    }
+                    None => focusable.iter().position(|&f| f)?,
+                };
+                self.focus(widgets, Some(target));
+                Some(NavEvent::Focused(target))
+            }
+        }
+    }
+}
+
+impl Default for UiNavigator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::{Button, Rect};
+
+    #[test]
+    fn test_find_neighbor_prefers_aligned() {
+        let rects = [
+            (Vec2::new(0.0, 0.0), Vec2::splat(10.0)),
+            (Vec2::new(0.0, 50.0), Vec2::splat(10.0)),
+            (Vec2::new(30.0, 30.0), Vec2::splat(10.0)),
+            (Vec2::new(0.0, -50.0), Vec2::splat(10.0)),
+        ];
+        assert_eq!(
+            find_neighbor(&rects, 0, NavDirection::Down, |_| true),
+            Some(1)
+        );
+        assert_eq!(
+            find_neighbor(&rects, 0, NavDirection::Right, |_| true),
+            Some(2)
+        );
+        assert_eq!(
+            find_neighbor(&rects, 0, NavDirection::Down, |i| i != 1),
+            Some(2)
+        );
+        assert_eq!(find_neighbor(&rects, 3, NavDirection::Up, |_| true), None);
+    }
+
+    #[test]
+    fn test_gamepad_navigation_and_repeat() {
+        let mut play = Button::new("Play", Rect::new(0.0, 0.0, 100.0, 30.0));
+        let mut quit = Button::new("Quit", Rect::new(0.0, 40.0, 100.0, 30.0));
+        let mut widgets: [&mut dyn Widget; 2] = [&mut play, &mut quit];
+        let parent = Vec2::new(800.0, 600.0);
+        let mut nav = UiNavigator::new().with_repeat(0.5, 0.1);
+
+        let down = GamepadNav {
+            direction: Vec2::Y,
+            ..Default::default()
+        };
+        let command = nav.poll_gamepad(down, 0.016).unwrap();
+        assert_eq!(
+            nav.apply(command, &mut widgets, parent),
+            Some(NavEvent::Focused(0))
+        );
+        assert_eq!(nav.poll_gamepad(down, 0.3), None);
+        let command = nav.poll_gamepad(down, 0.3).unwrap();
+        assert_eq!(
+            nav.apply(command, &mut widgets, parent),
+            Some(NavEvent::Focused(1))
+        );
+
+        let press = GamepadNav {
+            activate: true,
+            ..Default::default()
+        };
+        let command = nav.poll_gamepad(press, 0.016).unwrap();
+        assert_eq!(
+            nav.apply(command, &mut widgets, parent),
+            Some(NavEvent::Activated(1))
+        );
+        assert_eq!(nav.poll_gamepad(press, 0.016), None);
+        assert!(quit.was_clicked());
+    }
+}
@@ -0,0 +1,297 @@
+//! Progress bar widget
+//!
+//! A bar filled to a fraction in one of four directions. The shown fill
+//! can glide toward the value instead of jumping, which reads well on
+//! health bars, and an indeterminate mode sweeps a segment back and forth
+//! for loads of unknown length.
+
+use glam::Vec2;
+
+use super::draw::{ACCENT_COLOR, UiDrawList, plain_text};
+use super::rect::{Anchor, Rect};
+use super::widget::{Widget, WidgetState};
+use crate::renderer::{RichTextFonts, TextStyle};
+
+/// Fraction of the bar covered by the indeterminate segment
+const SWEEP_WIDTH: f32 = 0.3;
+
+/// Side the bar fills from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillDirection {
+    /// Grows rightward from the left edge
+    #[default]
+    LeftToRight,
+    /// Grows leftward from the right edge
+    RightToLeft,
+    /// Grows downward from the top edge
+    TopToBottom,
+    /// Grows upward from the bottom edge
+    BottomToTop,
+}
+
+/// A bar showing a value from 0 to 1
+#[derive(Debug, Clone)]
+pub struct ProgressBar {
+    /// Rectangle; its style draws the background
+    pub rect: Rect,
+    /// Side the bar fills from
+    pub direction: FillDirection,
+    /// Fill color (RGBA)
+    pub fill_color: [f32; 4],
+    /// Text drawn centered over the bar
+    pub label: Option<String>,
+    /// Value in 0..=1
+    value: f32,
+    /// Fill currently drawn, gliding toward `value`
+    shown: f32,
+    /// Fraction of the gap to `value` closed per second; 0 snaps
+    smoothing: f32,
+    /// Whether to sweep a segment instead of showing the value
+    indeterminate: bool,
+    /// Seconds the sweep has run
+    time: f32,
+    /// Seconds for one sweep across and back
+    sweep_period: f32,
+}
+
+impl ProgressBar {
+    /// Create an empty bar filling left to right
+    #[must_use]
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            rect,
+            direction: FillDirection::LeftToRight,
+            fill_color: ACCENT_COLOR,
+            label: None,
+            value: 0.0,
+            shown: 0.0,
+            smoothing: 0.0,
+            indeterminate: false,
+            time: 0.0,
+            sweep_period: 1.5,
+        }
+    }
+
+    /// Start at a value
+    #[must_use]
+    pub fn with_value(mut self, value: f32) -> Self {
+        self.value = value.clamp(0.0, 1.0);
+        self.shown = self.value;
+        self
+    }
+
+    /// Set the side the bar fills from
+    #[must_use]
+    pub fn with_direction(mut self, direction: FillDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Set the fill color
+    #[must_use]
+    pub fn with_fill_color(mut self, color: [f32; 4]) -> Self {
+        self.fill_color = color;
+        self
+    }
+
+    /// Draw text centered over the bar
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Glide the fill toward new values, closing `rate` of the gap per second
+    #[must_use]
+    pub fn with_smoothing(mut self, rate: f32) -> Self {
+        self.smoothing = rate.max(0.0);
+        self
+    }
+
+    /// Sweep a segment instead of showing a value
+    #[must_use]
+    pub fn indeterminate(mut self) -> Self {
+        self.indeterminate = true;
+        self
+    }
+
+    /// Get the value
+    #[must_use]
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Get the fill being drawn, which lags the value when smoothing
+    #[must_use]
+    pub fn shown(&self) -> f32 {
+        self.shown
+    }
+
+    /// Set the value, clamped to 0..=1
+    pub fn set_value(&mut self, value: f32) {
+        self.value = value.clamp(0.0, 1.0);
+        if self.smoothing <= 0.0 {
+            self.shown = self.value;
+        }
+    }
+
+    /// Switch between the sweep and showing the value
+    pub fn set_indeterminate(&mut self, indeterminate: bool) {
+        self.indeterminate = indeterminate;
+        self.time = 0.0;
+    }
+
+    /// Check if the bar sweeps instead of showing a value
+    #[must_use]
+    pub fn is_indeterminate(&self) -> bool {
+        self.indeterminate
+    }
+
+    /// Advance the glide and the sweep
+    pub fn update(&mut self, dt: f32) {
+        if self.indeterminate {
+            self.time = (self.time + dt) % self.sweep_period.max(f32::EPSILON);
+        }
+        if self.smoothing > 0.0 {
+            let t = 1.0 - (-self.smoothing * dt).exp();
+            self.shown += (self.value - self.shown) * t;
+            if (self.value - self.shown).abs() < 1e-3 {
+                self.shown = self.value;
+            }
+        } else {
+            self.shown = self.value;
+        }
+    }
+
+    /// Span of the bar's length covered by the fill, as (start, end) fractions
+    fn fill_span(&self) -> (f32, f32) {
+        if !self.indeterminate {
+            return (0.0, self.shown);
+        }
+        // Ping-pong the segment start across the free length
+        let phase = self.time / self.sweep_period.max(f32::EPSILON);
+        let start = (1.0 - (phase * 2.0 - 1.0).abs()) * (1.0 - SWEEP_WIDTH);
+        (start, start + SWEEP_WIDTH)
+    }
+
+    /// Screen (min, size) of the fill inside a bar at `min` of `size`
+    #[must_use]
+    pub fn fill_bounds(&self, min: Vec2, size: Vec2) -> (Vec2, Vec2) {
+        let (start, end) = self.fill_span();
+        let length = end - start;
+        match self.direction {
+            FillDirection::LeftToRight => (
+                min + Vec2::new(size.x * start, 0.0),
+                Vec2::new(size.x * length, size.y),
+            ),
+            FillDirection::RightToLeft => (
+                min + Vec2::new(size.x * (1.0 - end), 0.0),
+                Vec2::new(size.x * length, size.y),
+            ),
+            FillDirection::TopToBottom => (
+                min + Vec2::new(0.0, size.y * start),
+                Vec2::new(size.x, size.y * length),
+            ),
+            FillDirection::BottomToTop => (
+                min + Vec2::new(0.0, size.y * (1.0 - end)),
+                Vec2::new(size.x, size.y * length),
+            ),
+        }
+    }
+}
+
+impl Widget for ProgressBar {
+    fn rect(&self) -> &Rect {
+        &self.rect
+    }
+
+    fn rect_mut(&mut self) -> &mut Rect {
+        &mut self.rect
+    }
+
+    fn state(&self) -> WidgetState {
+        WidgetState::Normal
+    }
+
+    fn on_mouse_move(&mut self, _position: Vec2, _parent_size: Vec2) {}
+    fn on_mouse_down(&mut self, _position: Vec2, _parent_size: Vec2) -> bool {
+        false
+    }
+    fn on_mouse_up(&mut self, _position: Vec2, _parent_size: Vec2) -> bool {
+        false
+    }
+
+    fn draw(&self, draw: &mut UiDrawList, fonts: RichTextFonts<'_>, parent_size: Vec2) {
+        let min = self.rect.absolute_position(parent_size);
+        let size = self.rect.size;
+        draw.push_styled_rect(min, size, &self.rect.style);
+
+        // Keep the fill inside the border
+        let border = Vec2::splat(self.rect.style.border_width.max(0.0));
+        let inner = (size - border * 2.0).max(Vec2::ZERO);
+        let (fill_min, fill_size) = self.fill_bounds(min + border, inner);
+        if fill_size.x > 0.0 && fill_size.y > 0.0 {
+            draw.push_rect(fill_min, fill_size, self.fill_color);
+        }
+
+        if let Some(label) = &self.label {
+            draw.push_text(
+                &plain_text(label),
+                fonts,
+                (min, size),
+                &TextStyle::default(),
+                Anchor::Center,
+                false,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_directions_and_smoothing() {
+        let min = Vec2::new(10.0, 20.0);
+        let size = Vec2::new(100.0, 10.0);
+        let mut bar = ProgressBar::new(Rect::default()).with_value(0.25);
+        assert_eq!(bar.fill_bounds(min, size), (min, Vec2::new(25.0, 10.0)));
+        bar.direction = FillDirection::RightToLeft;
+        assert_eq!(
+            bar.fill_bounds(min, size),
+            (Vec2::new(85.0, 20.0), Vec2::new(25.0, 10.0))
+        );
+        bar.direction = FillDirection::BottomToTop;
+        assert_eq!(
+            bar.fill_bounds(min, size),
+            (Vec2::new(10.0, 27.5), Vec2::new(100.0, 2.5))
+        );
+
+        let mut health = ProgressBar::new(Rect::default())
+            .with_value(1.0)
+            .with_smoothing(10.0);
+        health.set_value(0.5);
+        assert_eq!(health.shown(), 1.0);
+        health.update(0.05);
+        assert!(health.shown() < 1.0 && health.shown() > 0.5);
+        for _ in 0..100 {
+            health.update(0.05);
+        }
+        assert_eq!(health.shown(), 0.5);
+    }
+
+    #[test]
+    fn test_indeterminate_sweep_stays_inside() {
+        let mut bar = ProgressBar::new(Rect::default()).indeterminate();
+        let mut starts = Vec::new();
+        for _ in 0..30 {
+            bar.update(0.1);
+            let (min, size) = bar.fill_bounds(Vec2::ZERO, Vec2::new(100.0, 10.0));
+            assert!(min.x >= 0.0 && min.x + size.x <= 100.0 + 1e-3);
+            assert!((size.x - 30.0).abs() < 1e-3);
+            starts.push(min.x);
+        }
+        assert!(starts.iter().any(|&x| x < 10.0) && starts.iter().any(|&x| x > 60.0));
+    }
+}
@@ -111,6 +111,8 @@ pub struct Rect {
     pub anchor: Anchor,
     /// Style
     pub style: RectStyle,
+    /// Layer; higher layers draw over and take input before lower ones
+    pub z: i32,
 }
 
 impl Rect {
@@ -122,6 +124,7 @@ impl Rect {
             size: Vec2::new(width, height),
             anchor: Anchor::TopLeft,
             style: RectStyle::default(),
+            z: 0,
         }
     }
 
@@ -139,6 +142,13 @@ impl Rect {
         self
     }
 
+    /// Set the layer
+    #[must_use]
+    pub fn with_z(mut self, z: i32) -> Self {
+        self.z = z;
+        self
+    }
+
     /// Calculate absolute position in screen space
     #[must_use]
     pub fn absolute_position(&self, parent_size: Vec2) -> Vec2 {
@@ -0,0 +1,498 @@
+//! Single-line text input field
+//!
+//! Takes focus on click and then edits its text from the frame's
+//! [`TextEvent`]s: typed characters, IME composition, and key presses for
+//! deletion, caret movement and selection. Enable IME on the window with
+//! `EngineContext::set_ime_allowed` while a field is focused.
+
+use std::ops::Range;
+
+use glam::Vec2;
+use winit::event::Ime;
+use winit::keyboard::KeyCode;
+
+use super::draw::{FOCUS_COLOR, UiDrawList, plain_text, push_focus_ring, push_widget_rect};
+use super::rect::{Anchor, Rect};
+use super::widget::{Widget, WidgetState};
+use crate::input::{Input, TextEvent};
+use crate::renderer::{RichTextFonts, TextStyle, measure_rich_text};
+
+/// Space between the field edge and its text
+const TEXT_PADDING: f32 = 6.0;
+
+/// Width of the caret
+const CARET_WIDTH: f32 = 1.5;
+
+/// Color of the placeholder text
+const PLACEHOLDER_COLOR: [f32; 4] = [0.6, 0.6, 0.6, 1.0];
+
+/// Color behind selected text
+const SELECTION_COLOR: [f32; 4] = [0.2, 0.5, 0.9, 0.6];
+
+/// Characters a text input accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextFilter {
+    /// Any printable text
+    #[default]
+    Any,
+    /// An optionally negative whole number
+    Integer,
+    /// An optionally negative number with at most one decimal point
+    Decimal,
+}
+
+impl TextFilter {
+    /// Check if `text` is valid, or could become valid as it is typed
+    #[must_use]
+    pub fn accepts(&self, text: &str) -> bool {
+        let digits = text.strip_prefix('-').unwrap_or(text);
+        match self {
+            Self::Any => true,
+            Self::Integer => digits.chars().all(|c| c.is_ascii_digit()),
+            Self::Decimal => {
+                digits.chars().all(|c| c.is_ascii_digit() || c == '.')
+                    && digits.matches('.').count() <= 1
+            }
+        }
+    }
+}
+
+/// An editable single line of text
+#[derive(Debug, Clone)]
+pub struct TextInput {
+    /// Rectangle
+    pub rect: Rect,
+    /// Text shown while empty
+    pub placeholder: String,
+    /// Text size in pixels
+    pub text_size: f32,
+    /// Current text
+    text: String,
+    /// Caret position in characters
+    caret: usize,
+    /// Other end of the selection, in characters
+    selection_anchor: Option<usize>,
+    /// Longest allowed text in characters
+    max_length: Option<usize>,
+    /// Allowed characters
+    filter: TextFilter,
+    /// Uncommitted IME composition shown at the caret
+    preedit: String,
+    /// Current state
+    state: WidgetState,
+    /// Whether the field has keyboard focus
+    focused: bool,
+    /// Whether the text changed since last checked
+    changed: bool,
+    /// Whether Enter was pressed since last checked
+    submitted: bool,
+}
+
+impl TextInput {
+    /// Create an empty field
+    #[must_use]
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            rect,
+            placeholder: String::new(),
+            text_size: 16.0,
+            text: String::new(),
+            caret: 0,
+            selection_anchor: None,
+            max_length: None,
+            filter: TextFilter::Any,
+            preedit: String::new(),
+            state: WidgetState::Normal,
+            focused: false,
+            changed: false,
+            submitted: false,
+        }
+    }
+
+    /// Start with some text
+    #[must_use]
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Set the text shown while empty
+    #[must_use]
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Limit the text length in characters
+    #[must_use]
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Restrict the accepted characters
+    #[must_use]
+    pub fn with_filter(mut self, filter: TextFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Get the text
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replace the text and move the caret to the end
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        if let Some(max) = self.max_length {
+            self.text = self.text.chars().take(max).collect();
+        }
+        self.caret = self.char_count();
+        self.selection_anchor = None;
+    }
+
+    /// Get the caret position in characters
+    #[must_use]
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// Get the selected character range, if any
+    #[must_use]
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.selection_anchor.filter(|&a| a != self.caret)?;
+        Some(anchor.min(self.caret)..anchor.max(self.caret))
+    }
+
+    /// Get the selected text
+    #[must_use]
+    pub fn selected_text(&self) -> &str {
+        self.selection().map_or("", |range| {
+            &self.text[self.byte(range.start)..self.byte(range.end)]
+        })
+    }
+
+    /// Select all text
+    pub fn select_all(&mut self) {
+        self.selection_anchor = Some(0);
+        self.caret = self.char_count();
+    }
+
+    /// Get the uncommitted IME composition
+    #[must_use]
+    pub fn preedit(&self) -> &str {
+        &self.preedit
+    }
+
+    /// Check if the user edited the text (resets after check)
+    pub fn was_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
+    /// Check if the user pressed Enter (resets after check)
+    pub fn was_submitted(&mut self) -> bool {
+        std::mem::take(&mut self.submitted)
+    }
+
+    /// Type text at the caret, replacing the selection
+    ///
+    /// Characters the filter rejects or past the maximum length are dropped.
+    pub fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        for c in text.chars().filter(|c| !c.is_control()) {
+            if self.max_length.is_some_and(|max| self.char_count() >= max) {
+                break;
+            }
+            let at = self.byte(self.caret);
+            let mut candidate = self.text.clone();
+            candidate.insert(at, c);
+            if self.filter.accepts(&candidate) {
+                self.text = candidate;
+                self.caret += 1;
+                self.changed = true;
+            }
+        }
+    }
+
+    /// Apply this frame's typed text, IME and key presses while focused
+    ///
+    /// Returns whether the text changed.
+    pub fn update(&mut self, input: &Input) -> bool {
+        if !self.focused {
+            return false;
+        }
+        let before = self.text.clone();
+        let shift = input.is_shift_pressed();
+        let control = input.is_control_pressed();
+        for event in input.text_events() {
+            match event {
+                TextEvent::Key(key) => {
+                    self.edit_key(*key, shift, control);
+                }
+                TextEvent::Text(text) if !control => self.insert(text),
+                TextEvent::Text(_) => {}
+                TextEvent::Ime(Ime::Preedit(text, _)) => self.preedit.clone_from(text),
+                TextEvent::Ime(Ime::Commit(text)) => {
+                    self.preedit.clear();
+                    self.insert(text);
+                }
+                TextEvent::Ime(Ime::Enabled | Ime::Disabled) => self.preedit.clear(),
+            }
+        }
+        self.text != before
+    }
+
+    /// Apply an editing key, returning whether it was used
+    fn edit_key(&mut self, key: KeyCode, shift: bool, control: bool) -> bool {
+        if !self.preedit.is_empty() {
+            // The input method owns keys while composing
+            return false;
+        }
+        match key {
+            KeyCode::Backspace => {
+                if !self.delete_selection() && self.caret > 0 {
+                    self.caret -= 1;
+                    self.remove(self.caret..self.caret + 1);
+                }
+            }
+            KeyCode::Delete => {
+                if !self.delete_selection() && self.caret < self.char_count() {
+                    self.remove(self.caret..self.caret + 1);
+                }
+            }
+            KeyCode::ArrowLeft => match self.selection() {
+                Some(range) if !shift => self.move_caret(range.start, false),
+                _ => self.move_caret(self.caret.saturating_sub(1), shift),
+            },
+            KeyCode::ArrowRight => match self.selection() {
+                Some(range) if !shift => self.move_caret(range.end, false),
+                _ => self.move_caret((self.caret + 1).min(self.char_count()), shift),
+            },
+            KeyCode::Home | KeyCode::ArrowUp => self.move_caret(0, shift),
+            KeyCode::End | KeyCode::ArrowDown => self.move_caret(self.char_count(), shift),
+            KeyCode::KeyA if control => self.select_all(),
+            KeyCode::Enter | KeyCode::NumpadEnter => self.submitted = true,
+            KeyCode::Escape => self.set_focused(false),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Move the caret, extending the selection if `extend`
+    fn move_caret(&mut self, to: usize, extend: bool) {
+        if extend {
+            self.selection_anchor.get_or_insert(self.caret);
+        } else {
+            self.selection_anchor = None;
+        }
+        self.caret = to;
+    }
+
+    /// Delete the selection, returning whether there was one
+    fn delete_selection(&mut self) -> bool {
+        let Some(range) = self.selection() else {
+            self.selection_anchor = None;
+            return false;
+        };
+        self.caret = range.start;
+        self.remove(range);
+        true
+    }
+
+    /// Remove a character range
+    fn remove(&mut self, range: Range<usize>) {
+        let bytes = self.byte(range.start)..self.byte(range.end);
+        self.text.replace_range(bytes, "");
+        self.selection_anchor = None;
+        self.changed = true;
+    }
+
+    /// Number of characters in the text
+    fn char_count(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    /// Byte offset of a character index
+    fn byte(&self, index: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(index)
+            .map_or(self.text.len(), |(byte, _)| byte)
+    }
+}
+
+/// Width of a string laid out on one line
+fn text_width(fonts: RichTextFonts<'_>, style: &TextStyle, text: &str) -> f32 {
+    if text.is_empty() {
+        return 0.0;
+    }
+    measure_rich_text(&plain_text(text), fonts, None, style).x
+}
+
+impl Widget for TextInput {
+    fn rect(&self) -> &Rect {
+        &self.rect
+    }
+
+    fn rect_mut(&mut self) -> &mut Rect {
+        &mut self.rect
+    }
+
+    fn state(&self) -> WidgetState {
+        self.state
+    }
+
+    fn on_mouse_move(&mut self, position: Vec2, parent_size: Vec2) {
+        if self.state == WidgetState::Disabled {
+            return;
+        }
+        self.state = if self.rect.contains(position, parent_size) {
+            WidgetState::Hovered
+        } else {
+            WidgetState::Normal
+        };
+    }
+
+    fn on_mouse_down(&mut self, position: Vec2, parent_size: Vec2) -> bool {
+        if self.state == WidgetState::Disabled {
+            return false;
+        }
+        let inside = self.rect.contains(position, parent_size);
+        self.set_focused(inside);
+        inside
+    }
+
+    fn on_mouse_up(&mut self, _position: Vec2, _parent_size: Vec2) -> bool {
+        false
+    }
+
+    fn draw(&self, draw: &mut UiDrawList, fonts: RichTextFonts<'_>, parent_size: Vec2) {
+        let min = self.rect.absolute_position(parent_size);
+        let size = self.rect.size;
+        push_widget_rect(draw, min, size, &self.rect.style, self.state);
+        if self.focused {
+            push_focus_ring(draw, min, size);
+        }
+
+        let style = TextStyle::new(self.text_size);
+        let caret_byte = self.byte(self.caret);
+        let (before, after) = self.text.split_at(caret_byte);
+        let display = format!("{before}{}{after}", self.preedit);
+        let caret_x = text_width(fonts, &style, before);
+        let preedit_width = text_width(fonts, &style, &self.preedit);
+
+        // Scroll left so the caret stays inside the field
+        let inner = (size.x - TEXT_PADDING * 2.0).max(0.0);
+        let scroll = (caret_x + preedit_width - inner).max(0.0);
+        let origin = Vec2::new(min.x + TEXT_PADDING - scroll, min.y);
+        let line_top = min.y + (size.y - self.text_size) * 0.5;
+
+        if let Some(range) = self.selection() {
+            let start = text_width(fonts, &style, &self.text[..self.byte(range.start)]);
+            let end = text_width(fonts, &style, &self.text[..self.byte(range.end)]);
+            draw.push_rect(
+                Vec2::new(origin.x + start, line_top),
+                Vec2::new(end - start, self.text_size),
+                SELECTION_COLOR,
+            );
+        }
+
+        let bounds = (origin, Vec2::new(inner + scroll, size.y));
+        if display.is_empty() {
+            let style = style.with_color(PLACEHOLDER_COLOR);
+            let spans = plain_text(&self.placeholder);
+            draw.push_text(&spans, fonts, bounds, &style, Anchor::MiddleLeft, false);
+        } else {
+            let spans = plain_text(&display);
+            draw.push_text(&spans, fonts, bounds, &style, Anchor::MiddleLeft, false);
+        }
+
+        if !self.preedit.is_empty() {
+            draw.push_rect(
+                Vec2::new(origin.x + caret_x, line_top + self.text_size),
+                Vec2::new(preedit_width, 1.0),
+                FOCUS_COLOR,
+            );
+        }
+        if self.focused {
+            draw.push_rect(
+                Vec2::new(origin.x + caret_x + preedit_width, line_top),
+                Vec2::new(CARET_WIDTH, self.text_size),
+                [1.0; 4],
+            );
+        }
+    }
+
+    fn on_key_down(&mut self, key: KeyCode) -> bool {
+        self.focused && self.edit_key(key, false, false)
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.state != WidgetState::Disabled
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused && self.state != WidgetState::Disabled;
+        if !self.focused {
+            self.preedit.clear();
+            self.selection_anchor = None;
+        }
+    }
+
+    fn activate(&mut self) -> bool {
+        // Focus is all a field needs; the game opens an on-screen keyboard
+        self.state != WidgetState::Disabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editing_and_selection() {
+        let mut field = TextInput::new(Rect::default()).with_text("héllo");
+        field.set_focused(true);
+        field.edit_key(KeyCode::ArrowLeft, true, false);
+        field.edit_key(KeyCode::ArrowLeft, true, false);
+        assert_eq!(field.selected_text(), "lo");
+        field.insert("p!");
+        assert_eq!(field.text(), "hélp!");
+
+        field.edit_key(KeyCode::Home, false, false);
+        field.edit_key(KeyCode::Delete, false, false);
+        field.edit_key(KeyCode::End, false, false);
+        field.edit_key(KeyCode::Backspace, false, false);
+        assert_eq!(field.text(), "élp");
+        assert!(field.was_changed());
+    }
+
+    #[test]
+    fn test_filters_and_max_length() {
+        let mut field = TextInput::new(Rect::default())
+            .with_filter(TextFilter::Decimal)
+            .with_max_length(5);
+        field.insert("-1a.5.2x99");
+        assert_eq!(field.text(), "-1.52");
+
+        let mut input = Input::new();
+        input.process_ime(Ime::Preedit("に".into(), None));
+        let mut field = TextInput::new(Rect::default());
+        field.set_focused(true);
+        field.update(&input);
+        assert_eq!(field.preedit(), "に");
+
+        input.update();
+        input.process_ime(Ime::Commit("日本".into()));
+        input.process_text("\u{8}");
+        assert!(field.update(&input));
+        assert_eq!(field.text(), "日本");
+        assert!(field.preedit().is_empty());
+    }
+}
@@ -0,0 +1,545 @@
+//! Checkbox and radio button widgets
+//!
+//! Both follow the `Button` mouse pattern: a press inside arms the widget
+//! and the release inside commits. A click also focuses the widget, after
+//! which Space or Enter toggles a checkbox and the arrow keys move a radio
+//! group's selection.
+
+use glam::Vec2;
+use winit::keyboard::KeyCode;
+
+use super::draw::{
+    ACCENT_COLOR, UiDrawList, plain_text, push_focus_ring, push_widget_rect, state_color,
+};
+use super::rect::{Anchor, Rect, RectStyle};
+use super::widget::{Widget, WidgetState};
+use crate::renderer::{RichTextFonts, TextStyle};
+
+/// Gap between a box and its caption
+const CAPTION_GAP: f32 = 8.0;
+
+/// Fraction of a box covered by its check mark
+const MARK_SCALE: f32 = 0.5;
+
+/// Draw a toggle box with an optional mark and a caption to its right
+fn draw_toggle(
+    draw: &mut UiDrawList,
+    fonts: RichTextFonts<'_>,
+    (min, size): (Vec2, Vec2),
+    style: &RectStyle,
+    state: WidgetState,
+    marked: bool,
+    caption: &str,
+) {
+    let side = size.y.min(size.x);
+    let box_size = Vec2::splat(side);
+    push_widget_rect(draw, min, box_size, style, state);
+    if marked {
+        let mark = box_size * MARK_SCALE;
+        draw.push_rect(
+            min + (box_size - mark) * 0.5,
+            mark,
+            state_color(ACCENT_COLOR, state),
+        );
+    }
+
+    let mut color = [1.0; 4];
+    if state == WidgetState::Disabled {
+        color[3] = 0.5;
+    }
+    let offset = Vec2::new(side + CAPTION_GAP, 0.0);
+    draw.push_text(
+        &plain_text(caption),
+        fonts,
+        (min + offset, (size - offset).max(Vec2::ZERO)),
+        &TextStyle::default().with_color(color),
+        Anchor::MiddleLeft,
+        false,
+    );
+}
+
+/// Check if a key confirms a focused widget
+fn is_activate_key(key: KeyCode) -> bool {
+    matches!(key, KeyCode::Space | KeyCode::Enter | KeyCode::NumpadEnter)
+}
+
+/// A box that toggles on and off
+#[derive(Debug, Clone)]
+pub struct Checkbox {
+    /// Rectangle covering the box and caption
+    pub rect: Rect,
+    /// Caption drawn right of the box
+    pub text: String,
+    /// Tooltip markup shown while hovered
+    pub tooltip: Option<String>,
+    /// Whether the box is checked
+    checked: bool,
+    /// Current state
+    state: WidgetState,
+    /// Whether the checkbox has keyboard focus
+    focused: bool,
+    /// Whether the value changed since last checked
+    toggled: bool,
+}
+
+impl Checkbox {
+    /// Create an unchecked checkbox
+    #[must_use]
+    pub fn new(text: impl Into<String>, rect: Rect) -> Self {
+        Self {
+            rect,
+            text: text.into(),
+            tooltip: None,
+            checked: false,
+            state: WidgetState::Normal,
+            focused: false,
+            toggled: false,
+        }
+    }
+
+    /// Start checked or unchecked
+    #[must_use]
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    /// Show a tooltip (rich text markup) while hovered
+    #[must_use]
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Check if the box is checked
+    #[must_use]
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    /// Set the value without reporting a toggle
+    pub fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+    }
+
+    /// Flip the value as if clicked
+    pub fn toggle(&mut self) {
+        if self.is_disabled() {
+            return;
+        }
+        self.checked = !self.checked;
+        self.toggled = true;
+    }
+
+    /// Check if the user toggled the box (resets after check)
+    pub fn was_toggled(&mut self) -> bool {
+        std::mem::take(&mut self.toggled)
+    }
+
+    /// Disable or enable the checkbox
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.state = if disabled {
+            WidgetState::Disabled
+        } else {
+            WidgetState::Normal
+        };
+        if disabled {
+            self.focused = false;
+        }
+    }
+
+    /// Check if the checkbox is disabled
+    #[must_use]
+    pub fn is_disabled(&self) -> bool {
+        self.state == WidgetState::Disabled
+    }
+}
+
+impl Widget for Checkbox {
+    fn rect(&self) -> &Rect {
+        &self.rect
+    }
+
+    fn rect_mut(&mut self) -> &mut Rect {
+        &mut self.rect
+    }
+
+    fn state(&self) -> WidgetState {
+        self.state
+    }
+
+    fn on_mouse_move(&mut self, position: Vec2, parent_size: Vec2) {
+        if self.is_disabled() || self.state == WidgetState::Pressed {
+            return;
+        }
+        self.state = if self.rect.contains(position, parent_size) {
+            WidgetState::Hovered
+        } else {
+            WidgetState::Normal
+        };
+    }
+
+    fn on_mouse_down(&mut self, position: Vec2, parent_size: Vec2) -> bool {
+        if self.is_disabled() {
+            return false;
+        }
+        let inside = self.rect.contains(position, parent_size);
+        self.focused = inside;
+        if inside {
+            self.state = WidgetState::Pressed;
+        }
+        inside
+    }
+
+    fn on_mouse_up(&mut self, position: Vec2, parent_size: Vec2) -> bool {
+        if self.state != WidgetState::Pressed {
+            return false;
+        }
+        if self.rect.contains(position, parent_size) {
+            self.state = WidgetState::Hovered;
+            self.toggle();
+            return true;
+        }
+        self.state = WidgetState::Normal;
+        false
+    }
+
+    fn draw(&self, draw: &mut UiDrawList, fonts: RichTextFonts<'_>, parent_size: Vec2) {
+        let min = self.rect.absolute_position(parent_size);
+        draw_toggle(
+            draw,
+            fonts,
+            (min, self.rect.size),
+            &self.rect.style,
+            self.state,
+            self.checked,
+            &self.text,
+        );
+        if self.focused {
+            let side = self.rect.size.y.min(self.rect.size.x);
+            push_focus_ring(draw, min, Vec2::splat(side));
+        }
+    }
+
+    fn on_key_down(&mut self, key: KeyCode) -> bool {
+        if !self.focused || !is_activate_key(key) {
+            return false;
+        }
+        self.toggle();
+        true
+    }
+
+    fn is_focusable(&self) -> bool {
+        !self.is_disabled()
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused && !self.is_disabled();
+    }
+
+    fn activate(&mut self) -> bool {
+        if self.is_disabled() {
+            return false;
+        }
+        self.toggle();
+        true
+    }
+
+    fn tooltip(&self) -> Option<&str> {
+        self.tooltip.as_deref()
+    }
+}
+
+/// A list of options of which exactly one is selected
+#[derive(Debug, Clone)]
+pub struct RadioGroup {
+    /// Rectangle covering every option
+    pub rect: Rect,
+    /// Option captions, top to bottom
+    pub options: Vec<String>,
+    /// Height of each option row
+    pub row_height: f32,
+    /// Selected option
+    selected: Option<usize>,
+    /// Current state
+    state: WidgetState,
+    /// Option under the mouse
+    hovered: Option<usize>,
+    /// Option the mouse was pressed on
+    pressed: Option<usize>,
+    /// Whether the group has keyboard focus
+    focused: bool,
+    /// Whether the selection changed since last checked
+    changed: bool,
+}
+
+impl RadioGroup {
+    /// Create a group with nothing selected, rows filling the rect
+    #[must_use]
+    pub fn new(options: impl IntoIterator<Item = impl Into<String>>, rect: Rect) -> Self {
+        let options: Vec<String> = options.into_iter().map(Into::into).collect();
+        let row_height = rect.size.y / options.len().max(1) as f32;
+        Self {
+            rect,
+            options,
+            row_height,
+            selected: None,
+            state: WidgetState::Normal,
+            hovered: None,
+            pressed: None,
+            focused: false,
+            changed: false,
+        }
+    }
+
+    /// Start with an option selected
+    #[must_use]
+    pub fn with_selected(mut self, index: usize) -> Self {
+        self.selected = (index < self.options.len()).then_some(index);
+        self
+    }
+
+    /// Set the height of each row
+    #[must_use]
+    pub fn with_row_height(mut self, height: f32) -> Self {
+        self.row_height = height;
+        self
+    }
+
+    /// Get the selected option index
+    #[must_use]
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Get the selected option caption
+    #[must_use]
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selected
+            .and_then(|i| self.options.get(i))
+            .map(String::as_str)
+    }
+
+    /// Select an option as if clicked
+    pub fn select(&mut self, index: usize) {
+        if self.is_disabled() || index >= self.options.len() || self.selected == Some(index) {
+            return;
+        }
+        self.selected = Some(index);
+        self.changed = true;
+    }
+
+    /// Check if the user changed the selection (resets after check)
+    pub fn was_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
+    /// Disable or enable the group
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.state = if disabled {
+            WidgetState::Disabled
+        } else {
+            WidgetState::Normal
+        };
+        if disabled {
+            self.focused = false;
+        }
+    }
+
+    /// Check if the group is disabled
+    #[must_use]
+    pub fn is_disabled(&self) -> bool {
+        self.state == WidgetState::Disabled
+    }
+
+    /// Find the option row under a screen position
+    fn option_at(&self, position: Vec2, parent_size: Vec2) -> Option<usize> {
+        if !self.rect.contains(position, parent_size) || self.row_height <= 0.0 {
+            return None;
+        }
+        let min = self.rect.absolute_position(parent_size);
+        let row = ((position.y - min.y) / self.row_height) as usize;
+        (row < self.options.len()).then_some(row)
+    }
+
+    /// Move the selection by `step` rows, wrapping around
+    fn step(&mut self, step: isize) {
+        let count = self.options.len() as isize;
+        if count == 0 {
+            return;
+        }
+        let current = self
+            .selected
+            .map_or(if step > 0 { -1 } else { 0 }, |i| i as isize);
+        self.select((current + step).rem_euclid(count) as usize);
+    }
+}
+
+impl Widget for RadioGroup {
+    fn rect(&self) -> &Rect {
+        &self.rect
+    }
+
+    fn rect_mut(&mut self) -> &mut Rect {
+        &mut self.rect
+    }
+
+    fn state(&self) -> WidgetState {
+        self.state
+    }
+
+    fn on_mouse_move(&mut self, position: Vec2, parent_size: Vec2) {
+        if self.is_disabled() {
+            return;
+        }
+        self.hovered = self.option_at(position, parent_size);
+        if self.state != WidgetState::Pressed {
+            self.state = if self.hovered.is_some() {
+                WidgetState::Hovered
+            } else {
+                WidgetState::Normal
+            };
+        }
+    }
+
+    fn on_mouse_down(&mut self, position: Vec2, parent_size: Vec2) -> bool {
+        if self.is_disabled() {
+            return false;
+        }
+        let inside = self.rect.contains(position, parent_size);
+        self.focused = inside;
+        self.pressed = self.option_at(position, parent_size);
+        if self.pressed.is_some() {
+            self.state = WidgetState::Pressed;
+        }
+        inside
+    }
+
+    fn on_mouse_up(&mut self, position: Vec2, parent_size: Vec2) -> bool {
+        let Some(pressed) = self.pressed.take() else {
+            return false;
+        };
+        let released = self.option_at(position, parent_size);
+        self.state = if released.is_some() {
+            WidgetState::Hovered
+        } else {
+            WidgetState::Normal
+        };
+        if released == Some(pressed) {
+            self.select(pressed);
+            return true;
+        }
+        false
+    }
+
+    fn draw(&self, draw: &mut UiDrawList, fonts: RichTextFonts<'_>, parent_size: Vec2) {
+        let min = self.rect.absolute_position(parent_size);
+        let row_size = Vec2::new(self.rect.size.x, self.row_height);
+        for (index, option) in self.options.iter().enumerate() {
+            let row_min = min + Vec2::new(0.0, self.row_height * index as f32);
+            let state = match self.state {
+                WidgetState::Disabled => WidgetState::Disabled,
+                WidgetState::Pressed if self.pressed == Some(index) => WidgetState::Pressed,
+                _ if self.hovered == Some(index) => WidgetState::Hovered,
+                _ => WidgetState::Normal,
+            };
+            let selected = self.selected == Some(index);
+            draw_toggle(
+                draw,
+                fonts,
+                (row_min, row_size),
+                &self.rect.style,
+                state,
+                selected,
+                option,
+            );
+            if self.focused && (selected || (self.selected.is_none() && index == 0)) {
+                let side = row_size.y.min(row_size.x);
+                push_focus_ring(draw, row_min, Vec2::splat(side));
+            }
+        }
+    }
+
+    fn on_key_down(&mut self, key: KeyCode) -> bool {
+        if !self.focused {
+            return false;
+        }
+        match key {
+            KeyCode::ArrowUp | KeyCode::ArrowLeft => self.step(-1),
+            KeyCode::ArrowDown | KeyCode::ArrowRight => self.step(1),
+            key if is_activate_key(key) && self.selected.is_none() => self.select(0),
+            _ => return false,
+        }
+        true
+    }
+
+    fn is_focusable(&self) -> bool {
+        !self.is_disabled() && !self.options.is_empty()
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused && !self.is_disabled();
+    }
+
+    fn activate(&mut self) -> bool {
+        if self.is_disabled() {
+            return false;
+        }
+        self.step(1);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkbox_click_and_keyboard() {
+        let mut checkbox = Checkbox::new("Vsync", Rect::new(10.0, 10.0, 120.0, 20.0));
+        let parent = Vec2::new(800.0, 600.0);
+        let inside = Vec2::new(15.0, 15.0);
+
+        assert!(!checkbox.on_key_down(KeyCode::Space));
+        checkbox.on_mouse_down(inside, parent);
+        checkbox.on_mouse_up(inside, parent);
+        assert!(checkbox.is_checked());
+        assert!(checkbox.was_toggled());
+        assert!(!checkbox.was_toggled());
+
+        assert!(checkbox.is_focused());
+        assert!(checkbox.on_key_down(KeyCode::Space));
+        assert!(!checkbox.is_checked());
+
+        checkbox.on_mouse_down(Vec2::new(500.0, 500.0), parent);
+        assert!(!checkbox.is_focused());
+    }
+
+    #[test]
+    fn test_radio_group_selection() {
+        let mut radio =
+            RadioGroup::new(["Low", "Medium", "High"], Rect::new(0.0, 0.0, 100.0, 60.0));
+        let parent = Vec2::new(800.0, 600.0);
+        let medium = Vec2::new(10.0, 30.0);
+
+        radio.on_mouse_down(medium, parent);
+        radio.on_mouse_up(medium, parent);
+        assert_eq!(radio.selected_text(), Some("Medium"));
+        assert!(radio.was_changed());
+
+        assert!(radio.on_key_down(KeyCode::ArrowDown));
+        assert_eq!(radio.selected(), Some(2));
+        radio.on_key_down(KeyCode::ArrowDown);
+        assert_eq!(radio.selected(), Some(0));
+        radio.on_key_down(KeyCode::ArrowUp);
+        assert_eq!(radio.selected(), Some(2));
+    }
+}
@@ -0,0 +1,225 @@
+//! Hover tooltips
+//!
+//! Any widget can return tooltip markup from `Widget::tooltip`. `Tooltips`
+//! watches which widget the cursor rests on and, after a delay, draws the
+//! text in a styled popup beside the cursor, flipped and clamped so it stays
+//! on screen. Leaving the widget hides it again.
+
+use glam::Vec2;
+
+use super::draw::{OVERLAY_LAYER, UiDrawList};
+use super::rect::{Anchor, RectStyle};
+use super::widget::{Widget, input_order};
+use crate::renderer::{RichTextFonts, TextStyle, measure_rich_text, parse_rich_text};
+
+/// Offset from the cursor to the popup's top-left corner
+const CURSOR_OFFSET: Vec2 = Vec2::new(12.0, 18.0);
+
+/// Gap between the popup's text and its edge
+const TOOLTIP_PADDING: f32 = 6.0;
+
+/// Shows the tooltip of the widget under the cursor
+#[derive(Debug, Clone)]
+pub struct Tooltips {
+    /// Seconds of hovering before the popup appears
+    delay: f32,
+    /// Width at which the text wraps
+    max_width: f32,
+    /// Popup background and border
+    style: RectStyle,
+    /// Text size and color
+    text_style: TextStyle,
+    /// Widget under the cursor and its tooltip markup
+    hovered: Option<(usize, String)>,
+    /// Seconds the cursor has rested on the hovered widget
+    timer: f32,
+    /// Cursor position when the popup appeared
+    anchor: Vec2,
+}
+
+impl Tooltips {
+    /// Create a tooltip tracker with a half-second delay
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            delay: 0.5,
+            max_width: 280.0,
+            style: RectStyle::default()
+                .with_background([0.08, 0.08, 0.1, 0.95])
+                .with_border_color([0.5, 0.5, 0.55, 1.0]),
+            text_style: TextStyle::new(14.0),
+            hovered: None,
+            timer: 0.0,
+            anchor: Vec2::ZERO,
+        }
+    }
+
+    /// Set the hover delay in seconds
+    #[must_use]
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        self.delay = delay.max(0.0);
+        self
+    }
+
+    /// Set the width at which text wraps
+    #[must_use]
+    pub fn with_max_width(mut self, width: f32) -> Self {
+        self.max_width = width.max(1.0);
+        self
+    }
+
+    /// Set the popup background and border
+    #[must_use]
+    pub fn with_style(mut self, style: RectStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the text size and color
+    #[must_use]
+    pub fn with_text_style(mut self, style: TextStyle) -> Self {
+        self.text_style = style;
+        self
+    }
+
+    /// Get the markup of the tooltip being shown
+    #[must_use]
+    pub fn visible(&self) -> Option<&str> {
+        self.hovered
+            .as_ref()
+            .filter(|_| self.timer >= self.delay)
+            .map(|(_, text)| text.as_str())
+    }
+
+    /// Track the widget under the cursor
+    ///
+    /// The topmost widget by `input_order` counts. The popup stays where it
+    /// appeared until the cursor leaves the widget.
+    pub fn update(
+        &mut self,
+        widgets: &[&mut dyn Widget],
+        cursor: Vec2,
+        parent_size: Vec2,
+        dt: f32,
+    ) {
+        let under = input_order(widgets)
+            .into_iter()
+            .find(|&index| widgets[index].rect().contains(cursor, parent_size));
+        let text = under.and_then(|index| widgets[index].tooltip());
+        let (Some(index), Some(text)) = (under, text) else {
+            self.hide();
+            return;
+        };
+
+        match &mut self.hovered {
+            Some((hovered, current)) if *hovered == index => {
+                if current != text {
+                    *current = text.to_string();
+                }
+            }
+            _ => {
+                self.hovered = Some((index, text.to_string()));
+                self.timer = 0.0;
+            }
+        }
+        let was_visible = self.timer >= self.delay;
+        self.timer += dt;
+        if !was_visible {
+            self.anchor = cursor;
+        }
+    }
+
+    /// Hide the popup until the cursor rests on a widget again
+    pub fn hide(&mut self) {
+        self.hovered = None;
+        self.timer = 0.0;
+    }
+
+    /// Add the popup, if showing, inside a screen of `screen_size`, on the
+    /// overlay layer
+    pub fn draw(&self, draw: &mut UiDrawList, fonts: RichTextFonts<'_>, screen_size: Vec2) {
+        let Some(text) = self.visible() else {
+            return;
+        };
+        let spans = parse_rich_text(text);
+        let text_size = measure_rich_text(&spans, fonts, Some(self.max_width), &self.text_style);
+        let padding = Vec2::splat(TOOLTIP_PADDING);
+        let size = text_size + padding * 2.0;
+        let min = popup_position(self.anchor, size, screen_size);
+        let z = draw.z();
+        draw.set_z(OVERLAY_LAYER);
+        draw.push_styled_rect(min, size, &self.style);
+        draw.push_text(
+            &spans,
+            fonts,
+            (min + padding, text_size),
+            &self.text_style,
+            Anchor::TopLeft,
+            true,
+        );
+        draw.set_z(z);
+    }
+}
+
+impl Default for Tooltips {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Top-left corner of a popup of `size` shown at the cursor
+///
+/// The popup sits below-right of the cursor, moves left when it would
+/// cross the right edge and above the cursor when it would cross the
+/// bottom.
+#[must_use]
+pub fn popup_position(cursor: Vec2, size: Vec2, screen_size: Vec2) -> Vec2 {
+    let mut min = cursor + CURSOR_OFFSET;
+    if min.x + size.x > screen_size.x {
+        min.x = screen_size.x - size.x;
+    }
+    if min.y + size.y > screen_size.y {
+        min.y = cursor.y - size.y - TOOLTIP_PADDING;
+    }
+    min.max(Vec2::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::{Button, Label, Rect};
+
+    #[test]
+    fn test_tooltip_appears_after_delay_and_hides() {
+        let mut save = Button::new("Save", Rect::new(0.0, 0.0, 100.0, 30.0))
+            .with_tooltip("Write the game to disk");
+        let mut title = Label::new("Options", Rect::new(0.0, 40.0, 100.0, 30.0));
+        let widgets: [&mut dyn Widget; 2] = [&mut save, &mut title];
+        let parent = Vec2::new(800.0, 600.0);
+        let mut tooltips = Tooltips::new().with_delay(0.5);
+
+        tooltips.update(&widgets, Vec2::new(10.0, 10.0), parent, 0.3);
+        assert_eq!(tooltips.visible(), None);
+        tooltips.update(&widgets, Vec2::new(12.0, 10.0), parent, 0.3);
+        assert_eq!(tooltips.visible(), Some("Write the game to disk"));
+
+        tooltips.update(&widgets, Vec2::new(10.0, 50.0), parent, 0.3);
+        assert_eq!(tooltips.visible(), None);
+        tooltips.update(&widgets, Vec2::new(10.0, 10.0), parent, 0.3);
+        assert_eq!(tooltips.visible(), None);
+    }
+
+    #[test]
+    fn test_popup_stays_on_screen() {
+        let screen = Vec2::new(800.0, 600.0);
+        let size = Vec2::new(200.0, 50.0);
+        assert_eq!(
+            popup_position(Vec2::new(100.0, 100.0), size, screen),
+            Vec2::new(112.0, 118.0)
+        );
+        assert_eq!(
+            popup_position(Vec2::new(750.0, 580.0), size, screen),
+            Vec2::new(600.0, 524.0)
+        );
+    }
+}
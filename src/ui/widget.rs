@@ -3,8 +3,18 @@
 //! Provides interactive UI elements.
 
 use glam::Vec2;
+use winit::keyboard::KeyCode;
 
-use super::rect::Rect;
+use super::drag_drop::DragPayload;
+use super::draw::{UiDrawList, plain_text, push_focus_ring, push_widget_rect};
+use super::rect::{Anchor, Rect};
+use crate::renderer::{
+    RichTextFonts, RichTextLayout, TextSpan, TextStyle, layout_rich_text, measure_rich_text,
+    parse_rich_text,
+};
+
+/// Gap between a panel's edge and its title
+const PANEL_PADDING: f32 = 8.0;
 
 /// Widget state
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -39,6 +49,71 @@ pub trait Widget {
 
     /// Handle mouse button up
     fn on_mouse_up(&mut self, position: Vec2, parent_size: Vec2) -> bool;
+
+    /// Add the widget's quads and text to a draw list
+    fn draw(&self, _draw: &mut UiDrawList, _fonts: RichTextFonts<'_>, _parent_size: Vec2) {}
+
+    /// Handle a key press, returning whether it was used
+    fn on_key_down(&mut self, _key: KeyCode) -> bool {
+        false
+    }
+
+    /// Check if the widget can take keyboard focus
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    /// Check if the widget has keyboard focus
+    fn is_focused(&self) -> bool {
+        false
+    }
+
+    /// Give or take keyboard focus
+    fn set_focused(&mut self, _focused: bool) {}
+
+    /// Trigger the widget as a click would, e.g. from a gamepad's A button
+    ///
+    /// Returns whether the widget reacted.
+    fn activate(&mut self) -> bool {
+        false
+    }
+
+    /// Payload to carry when the widget is dragged; `None` if not draggable
+    fn drag_payload(&self) -> Option<DragPayload> {
+        None
+    }
+
+    /// Check if the widget would take a dropped payload
+    fn accepts_drop(&self, _payload: &DragPayload) -> bool {
+        false
+    }
+
+    /// Receive a dropped payload, returning whether it was taken
+    fn on_drop(&mut self, _payload: &DragPayload) -> bool {
+        false
+    }
+
+    /// Called on the source when its drag ends, with whether a target took it
+    fn on_drag_end(&mut self, _payload: &DragPayload, _dropped: bool) {}
+
+    /// Tooltip markup to show after hovering a while
+    fn tooltip(&self) -> Option<&str> {
+        None
+    }
+
+    /// Layer the widget draws on and takes input at
+    fn z_index(&self) -> i32 {
+        self.rect().z
+    }
+}
+
+/// Widget indices in the order they should see input: highest layer
+/// first, and later widgets first within a layer
+#[must_use]
+pub fn input_order(widgets: &[&mut dyn Widget]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..widgets.len()).rev().collect();
+    order.sort_by_key(|&index| std::cmp::Reverse(widgets[index].z_index()));
+    order
 }
 
 /// A clickable button
@@ -48,10 +123,18 @@ pub struct Button {
     pub rect: Rect,
     /// Label text
     pub text: String,
+    /// Caption color (RGBA)
+    pub text_color: [f32; 4],
+    /// Caption size in pixels
+    pub text_size: f32,
+    /// Tooltip markup shown while hovered
+    pub tooltip: Option<String>,
     /// Current state
     state: WidgetState,
     /// Whether button was clicked this frame
     clicked: bool,
+    /// Whether the button has keyboard or gamepad focus
+    focused: bool,
 }
 
 impl Button {
@@ -61,11 +144,36 @@ impl Button {
         Self {
             rect,
             text: text.into(),
+            text_color: [1.0, 1.0, 1.0, 1.0],
+            text_size: 16.0,
+            tooltip: None,
             state: WidgetState::Normal,
             clicked: false,
+            focused: false,
         }
     }
 
+    /// Set the caption color
+    #[must_use]
+    pub fn with_text_color(mut self, color: [f32; 4]) -> Self {
+        self.text_color = color;
+        self
+    }
+
+    /// Set the caption size in pixels
+    #[must_use]
+    pub fn with_text_size(mut self, size: f32) -> Self {
+        self.text_size = size;
+        self
+    }
+
+    /// Show a tooltip (rich text markup) while hovered
+    #[must_use]
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
     /// Check if button was clicked (resets after check)
     pub fn was_clicked(&mut self) -> bool {
         let result = self.clicked;
@@ -80,6 +188,9 @@ impl Button {
         } else {
             WidgetState::Normal
         };
+        if disabled {
+            self.focused = false;
+        }
     }
 
     /// Check if button is disabled
@@ -140,6 +251,55 @@ impl Widget for Button {
         }
         false
     }
+
+    fn draw(&self, draw: &mut UiDrawList, fonts: RichTextFonts<'_>, parent_size: Vec2) {
+        let min = self.rect.absolute_position(parent_size);
+        push_widget_rect(draw, min, self.rect.size, &self.rect.style, self.state);
+        if self.focused {
+            push_focus_ring(draw, min, self.rect.size);
+        }
+        let mut color = self.text_color;
+        if self.state == WidgetState::Disabled {
+            color[3] *= 0.5;
+        }
+        let style = TextStyle::new(self.text_size).with_color(color);
+        draw.push_text(
+            &plain_text(&self.text),
+            fonts,
+            (min, self.rect.size),
+            &style,
+            Anchor::Center,
+            false,
+        );
+    }
+
+    fn on_key_down(&mut self, key: KeyCode) -> bool {
+        self.focused && matches!(key, KeyCode::Enter | KeyCode::Space) && self.activate()
+    }
+
+    fn is_focusable(&self) -> bool {
+        !self.is_disabled()
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused && !self.is_disabled();
+    }
+
+    fn activate(&mut self) -> bool {
+        if self.is_disabled() {
+            return false;
+        }
+        self.clicked = true;
+        true
+    }
+
+    fn tooltip(&self) -> Option<&str> {
+        self.tooltip.as_deref()
+    }
 }
 
 /// A text label
@@ -151,6 +311,14 @@ pub struct Label {
     pub text: String,
     /// Text color (RGBA)
     pub color: [f32; 4],
+    /// Whether the text is rich text markup
+    pub markup: bool,
+    /// Whether to word-wrap at the rect width
+    pub wrap: bool,
+    /// Text size in pixels when drawn
+    pub size: f32,
+    /// Where the text sits inside the rect
+    pub align: Anchor,
 }
 
 impl Label {
@@ -161,6 +329,19 @@ impl Label {
             rect,
             text: text.into(),
             color: [1.0, 1.0, 1.0, 1.0],
+            markup: false,
+            wrap: true,
+            size: 16.0,
+            align: Anchor::TopLeft,
+        }
+    }
+
+    /// Create a label whose text is rich text markup
+    #[must_use]
+    pub fn rich(markup: impl Into<String>, rect: Rect) -> Self {
+        Self {
+            markup: true,
+            ..Self::new(markup, rect)
         }
     }
 
@@ -170,6 +351,83 @@ impl Label {
         self.color = color;
         self
     }
+
+    /// Enable or disable word wrapping
+    #[must_use]
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Set the text size in pixels
+    #[must_use]
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set where the text sits inside the rect
+    #[must_use]
+    pub fn with_align(mut self, align: Anchor) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Get the text as spans, parsing markup if enabled
+    #[must_use]
+    pub fn spans(&self) -> Vec<TextSpan> {
+        if self.markup {
+            parse_rich_text(&self.text)
+        } else {
+            self.text
+                .split('\n')
+                .enumerate()
+                .flat_map(|(i, line)| {
+                    let text = TextSpan::Text {
+                        text: line.to_string(),
+                        color: None,
+                        bold: false,
+                    };
+                    if i == 0 {
+                        vec![text]
+                    } else {
+                        vec![TextSpan::LineBreak, text]
+                    }
+                })
+                .collect()
+        }
+    }
+
+    /// Measure the text as it would be laid out in this label
+    #[must_use]
+    pub fn measure(&self, fonts: RichTextFonts<'_>, style: &TextStyle) -> Vec2 {
+        measure_rich_text(&self.spans(), fonts, self.wrap_width(), style)
+    }
+
+    /// Lay out the text inside the label's rect
+    ///
+    /// The label color overrides the style color for unmarked text.
+    #[must_use]
+    pub fn layout(
+        &self,
+        fonts: RichTextFonts<'_>,
+        style: &TextStyle,
+        parent_size: Vec2,
+    ) -> RichTextLayout {
+        let style = style.with_color(self.color);
+        layout_rich_text(
+            &self.spans(),
+            fonts,
+            self.rect.absolute_position(parent_size),
+            self.wrap_width(),
+            &style,
+        )
+    }
+
+    /// Get the wrap width, if wrapping
+    fn wrap_width(&self) -> Option<f32> {
+        self.wrap.then_some(self.rect.size.x)
+    }
 }
 
 impl Widget for Label {
@@ -192,6 +450,18 @@ impl Widget for Label {
     fn on_mouse_up(&mut self, _position: Vec2, _parent_size: Vec2) -> bool {
         false
     }
+    fn draw(&self, draw: &mut UiDrawList, fonts: RichTextFonts<'_>, parent_size: Vec2) {
+        let style = TextStyle::new(self.size).with_color(self.color);
+        let min = self.rect.absolute_position(parent_size);
+        draw.push_text(
+            &self.spans(),
+            fonts,
+            (min, self.rect.size),
+            &style,
+            self.align,
+            self.wrap,
+        );
+    }
 }
 
 /// A container panel
@@ -238,12 +508,42 @@ impl Widget for Panel {
     fn on_mouse_up(&mut self, _position: Vec2, _parent_size: Vec2) -> bool {
         false
     }
+    fn draw(&self, draw: &mut UiDrawList, fonts: RichTextFonts<'_>, parent_size: Vec2) {
+        let min = self.rect.absolute_position(parent_size);
+        draw.push_styled_rect(min, self.rect.size, &self.rect.style);
+        if let Some(title) = &self.title {
+            let padding = Vec2::splat(PANEL_PADDING);
+            let spans = [TextSpan::Text {
+                text: title.clone(),
+                color: None,
+                bold: true,
+            }];
+            draw.push_text(
+                &spans,
+                fonts,
+                (min + padding, self.rect.size - padding * 2.0),
+                &TextStyle::default(),
+                Anchor::TopLeft,
+                false,
+            );
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_input_order_by_layer() {
+        let mut back = Panel::new(Rect::default().with_z(-1));
+        let mut first = Button::new("A", Rect::default());
+        let mut second = Button::new("B", Rect::default());
+        let mut popup = Label::new("C", Rect::default().with_z(5));
+        let widgets: [&mut dyn Widget; 4] = [&mut popup, &mut back, &mut first, &mut second];
+        assert_eq!(input_order(&widgets), [0, 3, 2, 1]);
+    }
+
     #[test]
     fn test_button_click() {
         let rect = Rect::new(10.0, 10.0, 100.0, 30.0);
@@ -260,6 +560,27 @@ mod tests {
         assert_eq!(button.state(), WidgetState::Hovered);
     }
 
+    #[test]
+    fn test_plain_label_spans() {
+        let label = Label::new("a [b]\nc", Rect::default());
+        assert_eq!(
+            label.spans(),
+            vec![
+                TextSpan::Text {
+                    text: "a [b]".into(),
+                    color: None,
+                    bold: false
+                },
+                TextSpan::LineBreak,
+                TextSpan::Text {
+                    text: "c".into(),
+                    color: None,
+                    bold: false
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_button_click_outside() {
         let rect = Rect::new(10.0, 10.0, 100.0, 30.0);
@@ -0,0 +1,358 @@
+//! Window and modal dialog widgets
+//!
+//! A `Window` is a panel with a title bar that can be dragged around and an
+//! optional close button. In modal mode it dims everything behind it and
+//! swallows all mouse input while open, so widgets beneath stay inert; this
+//! suits pause menus and confirmation dialogs. Child widgets are laid out
+//! by the game inside `content_bounds`.
+
+use glam::Vec2;
+use winit::keyboard::KeyCode;
+
+use super::draw::{MODAL_LAYER, UiDrawList, plain_text, push_widget_rect};
+use super::rect::{Anchor, Rect, RectStyle};
+use super::widget::{Widget, WidgetState};
+use crate::renderer::{RichTextFonts, TextStyle};
+
+/// Gap between the title and the title bar's edges
+const TITLE_PADDING: f32 = 8.0;
+
+/// A draggable window with a title bar
+#[derive(Debug, Clone)]
+pub struct Window {
+    /// Rectangle including the title bar
+    pub rect: Rect,
+    /// Title shown in the bar
+    pub title: String,
+    /// Height of the title bar in pixels
+    pub title_height: f32,
+    /// Title bar color (RGBA)
+    pub title_color: [f32; 4],
+    /// Color laid over the screen behind a modal window (RGBA)
+    pub dim_color: [f32; 4],
+    /// Whether the window has a close button
+    closable: bool,
+    /// Whether the window blocks input to everything beneath it
+    modal: bool,
+    /// Whether the window is shown
+    open: bool,
+    /// Cursor minus the rect position while dragging by the title bar
+    grab: Option<Vec2>,
+    /// State of the close button
+    close_state: WidgetState,
+    /// Whether the window closed since last checked
+    closed: bool,
+}
+
+impl Window {
+    /// Create an open, closable, non-modal window
+    #[must_use]
+    pub fn new(title: impl Into<String>, rect: Rect) -> Self {
+        Self {
+            rect,
+            title: title.into(),
+            title_height: 28.0,
+            title_color: [0.12, 0.12, 0.16, 1.0],
+            dim_color: [0.0, 0.0, 0.0, 0.5],
+            closable: true,
+            modal: false,
+            open: true,
+            grab: None,
+            close_state: WidgetState::Normal,
+            closed: false,
+        }
+    }
+
+    /// Show or hide the close button
+    #[must_use]
+    pub fn with_closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+
+    /// Block input beneath the window and dim the background while open
+    #[must_use]
+    pub fn modal(mut self) -> Self {
+        self.modal = true;
+        self
+    }
+
+    /// Set the color laid over the screen behind a modal window
+    #[must_use]
+    pub fn with_dim_color(mut self, color: [f32; 4]) -> Self {
+        self.dim_color = color;
+        self
+    }
+
+    /// Set the title bar height
+    #[must_use]
+    pub fn with_title_height(mut self, height: f32) -> Self {
+        self.title_height = height.max(0.0);
+        self
+    }
+
+    /// Check if the window is shown
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Check if the window is modal
+    #[must_use]
+    pub fn is_modal(&self) -> bool {
+        self.modal
+    }
+
+    /// Check if the window is being dragged
+    #[must_use]
+    pub fn is_dragging(&self) -> bool {
+        self.grab.is_some()
+    }
+
+    /// Show the window
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    /// Hide the window
+    pub fn close(&mut self) {
+        if self.open {
+            self.closed = true;
+        }
+        self.open = false;
+        self.grab = None;
+        self.close_state = WidgetState::Normal;
+    }
+
+    /// Check if the window was closed (resets after check)
+    pub fn was_closed(&mut self) -> bool {
+        std::mem::take(&mut self.closed)
+    }
+
+    /// Check if widgets beneath must not receive input
+    #[must_use]
+    pub fn blocks_input(&self) -> bool {
+        self.open && self.modal
+    }
+
+    /// Check if the window takes input at a point instead of what is beneath
+    #[must_use]
+    pub fn captures(&self, position: Vec2, parent_size: Vec2) -> bool {
+        self.blocks_input() || (self.open && self.rect.contains(position, parent_size))
+    }
+
+    /// Screen (min, size) of the area below the title bar
+    #[must_use]
+    pub fn content_bounds(&self, parent_size: Vec2) -> (Vec2, Vec2) {
+        let min = self.rect.absolute_position(parent_size);
+        let bar = self.title_height.min(self.rect.size.y);
+        (
+            min + Vec2::new(0.0, bar),
+            Vec2::new(self.rect.size.x, self.rect.size.y - bar),
+        )
+    }
+
+    /// Screen (min, size) of the title bar
+    fn title_bounds(&self, parent_size: Vec2) -> (Vec2, Vec2) {
+        let min = self.rect.absolute_position(parent_size);
+        (
+            min,
+            Vec2::new(self.rect.size.x, self.title_height.min(self.rect.size.y)),
+        )
+    }
+
+    /// Screen (min, size) of the close button, a square at the bar's end
+    fn close_bounds(&self, parent_size: Vec2) -> Option<(Vec2, Vec2)> {
+        if !self.closable {
+            return None;
+        }
+        let (min, size) = self.title_bounds(parent_size);
+        let side = size.y - TITLE_PADDING;
+        let inset = TITLE_PADDING * 0.5;
+        Some((
+            Vec2::new(min.x + size.x - side - inset, min.y + inset),
+            Vec2::splat(side.max(0.0)),
+        ))
+    }
+}
+
+/// Check if a point lies in a (min, size) rect
+fn hit((min, size): (Vec2, Vec2), point: Vec2) -> bool {
+    point.cmpge(min).all() && point.cmple(min + size).all()
+}
+
+impl Widget for Window {
+    fn rect(&self) -> &Rect {
+        &self.rect
+    }
+
+    fn rect_mut(&mut self) -> &mut Rect {
+        &mut self.rect
+    }
+
+    fn state(&self) -> WidgetState {
+        WidgetState::Normal
+    }
+
+    fn on_mouse_move(&mut self, position: Vec2, parent_size: Vec2) {
+        if !self.open {
+            return;
+        }
+        if let Some(grab) = self.grab {
+            // Keep the whole window on screen
+            self.rect.position = position - grab;
+            let min = self.rect.absolute_position(parent_size);
+            let clamped = min.clamp(Vec2::ZERO, (parent_size - self.rect.size).max(Vec2::ZERO));
+            self.rect.position += clamped - min;
+        }
+        if self.close_state != WidgetState::Pressed {
+            let over = self
+                .close_bounds(parent_size)
+                .is_some_and(|bounds| hit(bounds, position));
+            self.close_state = if over {
+                WidgetState::Hovered
+            } else {
+                WidgetState::Normal
+            };
+        }
+    }
+
+    fn on_mouse_down(&mut self, position: Vec2, parent_size: Vec2) -> bool {
+        if !self.open {
+            return false;
+        }
+        if let Some(bounds) = self.close_bounds(parent_size)
+            && hit(bounds, position)
+        {
+            self.close_state = WidgetState::Pressed;
+            return true;
+        }
+        if hit(self.title_bounds(parent_size), position) {
+            self.grab = Some(position - self.rect.position);
+            return true;
+        }
+        self.captures(position, parent_size)
+    }
+
+    fn on_mouse_up(&mut self, position: Vec2, parent_size: Vec2) -> bool {
+        if !self.open {
+            return false;
+        }
+        let was_active = self.grab.take().is_some();
+        if self.close_state == WidgetState::Pressed {
+            self.close_state = WidgetState::Normal;
+            if self
+                .close_bounds(parent_size)
+                .is_some_and(|bounds| hit(bounds, position))
+            {
+                self.close();
+            }
+            return true;
+        }
+        was_active || self.captures(position, parent_size)
+    }
+
+    fn on_key_down(&mut self, key: KeyCode) -> bool {
+        if key == KeyCode::Escape && self.blocks_input() && self.closable {
+            self.close();
+            return true;
+        }
+        false
+    }
+
+    fn z_index(&self) -> i32 {
+        if self.blocks_input() {
+            self.rect.z.max(MODAL_LAYER)
+        } else {
+            self.rect.z
+        }
+    }
+
+    fn draw(&self, draw: &mut UiDrawList, fonts: RichTextFonts<'_>, parent_size: Vec2) {
+        if !self.open {
+            return;
+        }
+        if self.modal {
+            draw.push_rect(Vec2::ZERO, parent_size, self.dim_color);
+        }
+        let min = self.rect.absolute_position(parent_size);
+        draw.push_styled_rect(min, self.rect.size, &self.rect.style);
+
+        let (bar_min, bar_size) = self.title_bounds(parent_size);
+        let bar_style = self.rect.style.clone().with_background(self.title_color);
+        draw.push_styled_rect(bar_min, bar_size, &bar_style);
+        let close = self.close_bounds(parent_size);
+        let reserved = close.map_or(0.0, |(_, size)| size.x + TITLE_PADDING);
+        let title_min = bar_min + Vec2::new(TITLE_PADDING, 0.0);
+        let title_size = Vec2::new(bar_size.x - TITLE_PADDING - reserved, bar_size.y);
+        draw.push_text(
+            &plain_text(&self.title),
+            fonts,
+            (title_min, title_size.max(Vec2::ZERO)),
+            &TextStyle::default(),
+            Anchor::MiddleLeft,
+            false,
+        );
+
+        if let Some((close_min, close_size)) = close {
+            let style = RectStyle::default()
+                .with_background([0.6, 0.15, 0.15, 1.0])
+                .with_border_width(0.0);
+            push_widget_rect(draw, close_min, close_size, &style, self.close_state);
+            draw.push_text(
+                &plain_text("X"),
+                fonts,
+                (close_min, close_size),
+                &TextStyle::default(),
+                Anchor::Center,
+                false,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARENT: Vec2 = Vec2::new(800.0, 600.0);
+
+    #[test]
+    fn test_drag_by_title_bar_stays_on_screen() {
+        let mut window = Window::new("Inventory", Rect::new(100.0, 100.0, 300.0, 200.0));
+        assert!(window.on_mouse_down(Vec2::new(150.0, 110.0), PARENT));
+        window.on_mouse_move(Vec2::new(250.0, 160.0), PARENT);
+        assert_eq!(window.rect.position, Vec2::new(200.0, 150.0));
+        window.on_mouse_move(Vec2::new(2000.0, -50.0), PARENT);
+        assert_eq!(window.rect.position, Vec2::new(500.0, 0.0));
+        assert!(window.on_mouse_up(Vec2::new(2000.0, -50.0), PARENT));
+        assert!(!window.is_dragging());
+
+        // Body clicks are taken but do not drag
+        window.on_mouse_down(Vec2::new(600.0, 150.0), PARENT);
+        assert!(!window.is_dragging());
+        assert!(!window.captures(Vec2::new(10.0, 10.0), PARENT));
+    }
+
+    #[test]
+    fn test_modal_blocks_input_until_closed() {
+        let mut dialog = Window::new("Quit?", Rect::new(200.0, 200.0, 300.0, 150.0)).modal();
+        assert!(dialog.blocks_input());
+        assert!(dialog.on_mouse_down(Vec2::new(10.0, 10.0), PARENT));
+
+        // The close button sits at the right end of the title bar
+        let close = Vec2::new(490.0, 214.0);
+        dialog.on_mouse_down(close, PARENT);
+        assert!(dialog.is_open());
+        dialog.on_mouse_up(close, PARENT);
+        assert!(!dialog.is_open());
+        assert!(dialog.was_closed());
+        assert!(!dialog.was_closed());
+        assert!(!dialog.blocks_input());
+        assert!(!dialog.on_mouse_down(Vec2::new(10.0, 10.0), PARENT));
+
+        dialog.open();
+        assert!(dialog.on_key_down(KeyCode::Escape));
+        assert!(!dialog.is_open());
+    }
+}